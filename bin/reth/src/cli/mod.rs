@@ -6,8 +6,8 @@ use crate::{
         LogArgs,
     },
     commands::{
-        config_cmd, db, debug_cmd, dump_genesis, import, init_cmd, node, node::NoArgs, p2p,
-        recover, stage, test_vectors,
+        analyze, config_cmd, count_opcodes, db, debug_cmd, dump_genesis, export, import, init_cmd,
+        node, node::NoArgs, p2p, query, recover, stage, state, test_vectors, validate,
     },
     version::{LONG_VERSION, SHORT_VERSION},
 };
@@ -154,6 +154,14 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Debug(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Recover(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+            Commands::Export(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Query(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::State(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Validate(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::CountOpcodes(command) => {
+                runner.run_command_until_exit(|ctx| command.execute(ctx))
+            }
+            Commands::Analyze(command) => runner.run_blocking_until_ctrl_c(command.execute()),
         }
     }
 
@@ -202,6 +210,24 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Scripts for node recovery
     #[command(name = "recover")]
     Recover(recover::Command),
+    /// Export data from the database
+    #[command(name = "export")]
+    Export(export::Command),
+    /// Query the state of the database
+    #[command(name = "query")]
+    Query(query::Command),
+    /// Inspect the state of accounts
+    #[command(name = "state")]
+    State(state::Command),
+    /// Validate blocks in the database against the consensus rules without executing them
+    #[command(name = "validate")]
+    Validate(validate::Command),
+    /// Count the opcodes of the deployed contracts or of the executed transactions
+    #[command(name = "count-opcodes")]
+    CountOpcodes(count_opcodes::Command),
+    /// Analyze the deployed contracts with pluggable analyzers
+    #[command(name = "analyze")]
+    Analyze(analyze::Command),
 }
 
 #[cfg(test)]
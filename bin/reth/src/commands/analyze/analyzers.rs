@@ -0,0 +1,248 @@
+//! The built-in [BytecodeAnalyzer]s.
+
+use super::{BytecodeAnalyzer, Report};
+use crate::commands::count_opcodes::{
+    opcode_name, visit_opcodes, CodeKind, OpcodeCounts, StaticCallTargets, CALL_TARGETS,
+};
+use reth_primitives::{Bytecode, B256};
+use reth_revm::interpreter::opcode::JUMPDEST;
+use std::cmp::Reverse;
+
+/// Formats the share of `count` in `total` in percent.
+fn share(count: u64, total: u64) -> String {
+    if total == 0 {
+        return "-".to_string()
+    }
+    format!("{:.3}%", count as f64 * 100.0 / total as f64)
+}
+
+/// Counts the opcodes of the deployed contracts, see [visit_opcodes].
+#[derive(Debug)]
+pub struct OpcodeHistogram {
+    counts: OpcodeCounts,
+}
+
+impl Default for OpcodeHistogram {
+    fn default() -> Self {
+        Self { counts: [0; 256] }
+    }
+}
+
+impl BytecodeAnalyzer for OpcodeHistogram {
+    fn visit(&mut self, _code_hash: B256, bytecode: &Bytecode) {
+        visit_opcodes(&bytecode.original_bytes(), |opcode| self.counts[opcode as usize] += 1);
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (count, other) in self.counts.iter_mut().zip(other.counts) {
+            *count += other;
+        }
+    }
+
+    fn finish(&self) -> Report {
+        let total = self.counts.iter().sum::<u64>();
+        let mut opcodes =
+            (0..=u8::MAX).filter(|opcode| self.counts[*opcode as usize] > 0).collect::<Vec<_>>();
+        opcodes.sort_unstable_by_key(|opcode| (Reverse(self.counts[*opcode as usize]), *opcode));
+
+        let mut report = Report::new("Opcode histogram", ["OPCODE", "COUNT", "SHARE"]);
+        for opcode in opcodes {
+            let count = self.counts[opcode as usize];
+            report.push_row([opcode_name(opcode), count.to_string(), share(count, total)]);
+        }
+        report.push_row(["TOTAL".to_string(), total.to_string(), String::new()]);
+        report
+    }
+}
+
+/// Buckets the deployed contracts by their code size, in powers of two.
+#[derive(Debug, Default)]
+pub struct CodeSizeDistribution {
+    /// The number of contracts by the exponent of the smallest power of two not smaller than the
+    /// code size. Empty code is counted in the first bucket.
+    buckets: [u64; 32],
+    total_size: u64,
+    max_size: usize,
+}
+
+impl BytecodeAnalyzer for CodeSizeDistribution {
+    fn visit(&mut self, _code_hash: B256, bytecode: &Bytecode) {
+        let size = bytecode.original_bytes().len();
+        let bucket = (size.next_power_of_two().trailing_zeros() as usize).min(31);
+        self.buckets[bucket] += 1;
+        self.total_size += size as u64;
+        self.max_size = self.max_size.max(size);
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (count, other) in self.buckets.iter_mut().zip(other.buckets) {
+            *count += other;
+        }
+        self.total_size += other.total_size;
+        self.max_size = self.max_size.max(other.max_size);
+    }
+
+    fn finish(&self) -> Report {
+        let contracts = self.buckets.iter().sum::<u64>();
+        let mut report = Report::new("Code size distribution", ["SIZE", "CONTRACTS", "SHARE"]);
+        for (exponent, count) in self.buckets.iter().enumerate().filter(|(_, count)| **count > 0) {
+            let size = format!("<= {} B", 1u64 << exponent);
+            report.push_row([size, count.to_string(), share(*count, contracts)]);
+        }
+        let mean = if contracts == 0 { 0 } else { self.total_size / contracts };
+        report.push_row(["MEAN".to_string(), format!("{mean} B"), String::new()]);
+        report.push_row(["MAX".to_string(), format!("{} B", self.max_size), String::new()]);
+        report
+    }
+}
+
+/// Buckets the legacy contracts by the share of `JUMPDEST` instructions of all their
+/// instructions, in steps of 5%.
+///
+/// EOF contracts don't have `JUMPDEST`s and are skipped.
+#[derive(Debug, Default)]
+pub struct JumpdestDensity {
+    buckets: [u64; 21],
+    jumpdests: u64,
+    instructions: u64,
+}
+
+impl BytecodeAnalyzer for JumpdestDensity {
+    fn visit(&mut self, _code_hash: B256, bytecode: &Bytecode) {
+        let (mut jumpdests, mut instructions) = (0u64, 0u64);
+        let kind = visit_opcodes(&bytecode.original_bytes(), |opcode| {
+            jumpdests += (opcode == JUMPDEST) as u64;
+            instructions += 1;
+        });
+        if kind != CodeKind::Legacy || instructions == 0 {
+            return
+        }
+
+        self.buckets[(jumpdests * 20 / instructions) as usize] += 1;
+        self.jumpdests += jumpdests;
+        self.instructions += instructions;
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (count, other) in self.buckets.iter_mut().zip(other.buckets) {
+            *count += other;
+        }
+        self.jumpdests += other.jumpdests;
+        self.instructions += other.instructions;
+    }
+
+    fn finish(&self) -> Report {
+        let contracts = self.buckets.iter().sum::<u64>();
+        let mut report = Report::new("JUMPDEST density", ["JUMPDESTS", "CONTRACTS", "SHARE"]);
+        for (bucket, count) in self.buckets.iter().enumerate().filter(|(_, count)| **count > 0) {
+            let density = match bucket {
+                20 => "100%".to_string(),
+                _ => format!("{}-{}%", bucket * 5, bucket * 5 + 5),
+            };
+            report.push_row([density, count.to_string(), share(*count, contracts)]);
+        }
+        report.push_row([
+            "TOTAL".to_string(),
+            share(self.jumpdests, self.instructions),
+            String::new(),
+        ]);
+        report
+    }
+}
+
+/// Counts the call sites of precompiles and system contracts in legacy contracts.
+///
+/// A call site is recognized by the address pushed right before the call, optionally followed by
+/// `GAS`: `PUSHn <address> [GAS] CALL`, which is how the Solidity and Vyper compilers call
+/// precompiles with a constant address.
+#[derive(Debug, Default)]
+pub struct PrecompileCalls {
+    targets: StaticCallTargets,
+}
+
+impl BytecodeAnalyzer for PrecompileCalls {
+    fn visit(&mut self, _code_hash: B256, bytecode: &Bytecode) {
+        self.targets.visit(&bytecode.original_bytes());
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.targets = std::mem::take(&mut self.targets).merge(other.targets);
+    }
+
+    fn finish(&self) -> Report {
+        let mut report =
+            Report::new("Precompile calls", ["TARGET", "ADDRESS", "CALL SITES", "CONTRACTS"]);
+        for (index, (name, address)) in CALL_TARGETS.iter().enumerate() {
+            report.push_row([
+                name.to_string(),
+                address.to_string(),
+                self.targets.call_sites[index].to_string(),
+                self.targets.contracts[index].to_string(),
+            ]);
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Bytes;
+
+    fn bytecode(code: &[u8]) -> Bytecode {
+        Bytecode::new_raw(Bytes::copy_from_slice(code))
+    }
+
+    #[test]
+    fn opcode_histogram() {
+        let mut a = OpcodeHistogram::default();
+        a.visit(B256::ZERO, &bytecode(&[0x60, 0x01, 0x60, 0x02, 0x01]));
+        let mut b = OpcodeHistogram::default();
+        b.visit(B256::ZERO, &bytecode(&[0x00]));
+        a.merge(b);
+
+        let report = a.finish();
+        assert_eq!(report.rows[0], ["PUSH1", "2", "50.000%"]);
+        assert_eq!(report.rows.last().unwrap(), &["TOTAL", "4", ""]);
+    }
+
+    #[test]
+    fn code_size_distribution() {
+        let mut analyzer = CodeSizeDistribution::default();
+        analyzer.visit(B256::ZERO, &bytecode(&[0x00; 3]));
+        analyzer.visit(B256::ZERO, &bytecode(&[0x00; 4]));
+        analyzer.visit(B256::ZERO, &bytecode(&[0x00; 100]));
+
+        let report = analyzer.finish();
+        assert_eq!(report.rows[0], ["<= 4 B", "2", "66.667%"]);
+        assert_eq!(report.rows[1], ["<= 128 B", "1", "33.333%"]);
+        assert_eq!(report.rows[3], ["MAX", "100 B", ""]);
+    }
+
+    #[test]
+    fn jumpdest_density() {
+        let mut analyzer = JumpdestDensity::default();
+        // JUMPDEST PUSH1 0x5b STOP STOP
+        analyzer.visit(B256::ZERO, &bytecode(&[0x5b, 0x60, 0x5b, 0x00, 0x00]));
+
+        let report = analyzer.finish();
+        assert_eq!(report.rows[0], ["25-30%", "1", "100.000%"]);
+        assert_eq!(report.rows[1], ["TOTAL", "25.000%", ""]);
+    }
+
+    #[test]
+    fn precompile_calls() {
+        let mut analyzer = PrecompileCalls::default();
+        // PUSH1 0x01 GAS STATICCALL, PUSH2 0x0002 STATICCALL, PUSH1 0x01 GAS STATICCALL
+        let code = [0x60, 0x01, 0x5a, 0xfa, 0x61, 0x00, 0x02, 0xfa, 0x60, 0x01, 0x5a, 0xfa];
+        analyzer.visit(B256::ZERO, &bytecode(&code));
+        // PUSH1 0x01 ADD
+        analyzer.visit(B256::ZERO, &bytecode(&[0x60, 0x01, 0x01]));
+
+        let report = analyzer.finish();
+        assert_eq!(report.rows[0][2..], ["2", "1"]);
+        assert_eq!(report.rows[1][2..], ["1", "1"]);
+        assert_eq!(report.rows[2][2..], ["0", "0"]);
+        assert_eq!(report.rows.len(), CALL_TARGETS.len());
+    }
+}
@@ -0,0 +1,121 @@
+//! Command that runs analyzers over the deployed contracts.
+
+use super::{AnalyzerRegistry, DynAnalyzer};
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    commands::count_opcodes::scan_bytecodes,
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use reth_db::open_db_read_only;
+use reth_primitives::ChainSpec;
+use reth_provider::ProviderFactory;
+use std::{sync::Arc, time::Instant};
+use tracing::info;
+
+/// `reth analyze bytecodes` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// The analyzers to run, see `reth analyze list`. Defaults to all analyzers.
+    #[arg(long = "analyzer", value_name = "NAME", value_delimiter = ',')]
+    analyzers: Vec<String>,
+
+    /// The number of threads scanning the deployed contracts.
+    ///
+    /// Defaults to the number of CPUs.
+    #[arg(long, short, value_name = "JOBS")]
+    jobs: Option<usize>,
+}
+
+impl Command {
+    /// Execute `analyze bytecodes` command
+    pub async fn execute(self, registry: &AnalyzerRegistry) -> eyre::Result<()> {
+        let analyzers = registry.select(&self.analyzers)?;
+
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = Arc::new(open_db_read_only(&data_dir.db_path(), self.db.database_args())?);
+        let factory = ProviderFactory::new(db, self.chain.clone(), data_dir.static_files_path())?;
+
+        let mut pool = rayon::ThreadPoolBuilder::new();
+        if let Some(jobs) = self.jobs {
+            pool = pool.num_threads(jobs);
+        }
+
+        let start = Instant::now();
+        info!(
+            target: "reth::cli",
+            analyzers = ?analyzers.iter().map(|analyzer| analyzer.name).collect::<Vec<_>>(),
+            "Analyzing deployed contracts"
+        );
+        let partitions = pool.build()?.install(|| {
+            scan_bytecodes(
+                &factory,
+                || analyzers.iter().map(|analyzer| (analyzer.new)()).collect::<Vec<_>>(),
+                |partition, code_hash, bytecode| {
+                    for analyzer in partition.iter_mut() {
+                        analyzer.visit(code_hash, &bytecode);
+                    }
+                },
+            )
+        })?;
+        info!(target: "reth::cli", elapsed = ?start.elapsed(), "Analyzed deployed contracts");
+
+        let mut partitions = partitions.into_iter();
+        let mut merged: Vec<Box<dyn DynAnalyzer>> = partitions.next().unwrap_or_default();
+        for partition in partitions {
+            for (analyzer, other) in merged.iter_mut().zip(partition) {
+                analyzer.merge(other);
+            }
+        }
+
+        for (i, analyzer) in merged.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            print!("{}", analyzer.finish());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_analyzers() {
+        let command =
+            Command::try_parse_from(["reth", "--analyzer", "opcodes,code-size", "--analyzer", "x"])
+                .unwrap();
+        assert_eq!(command.analyzers, ["opcodes", "code-size", "x"]);
+    }
+}
@@ -0,0 +1,287 @@
+//! `reth analyze` command.
+//!
+//! Analyzes the deployed contracts with a set of [BytecodeAnalyzer]s. The built-in analyzers are
+//! registered in the default [AnalyzerRegistry], binaries built on top of reth can register their
+//! own analyzers and run the command with [Command::execute_with_registry].
+
+use clap::{Parser, Subcommand};
+use reth_primitives::{Bytecode, B256};
+use std::{any::Any, fmt};
+
+pub mod analyzers;
+mod bytecodes;
+
+/// `reth analyze` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth analyze` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Run analyzers over all contracts of the `Bytecodes` table
+    Bytecodes(bytecodes::Command),
+    /// List the available analyzers
+    List,
+}
+
+impl Command {
+    /// Execute `analyze` command with the built-in analyzers
+    pub async fn execute(self) -> eyre::Result<()> {
+        self.execute_with_registry(AnalyzerRegistry::default()).await
+    }
+
+    /// Execute `analyze` command with the analyzers of the registry
+    pub async fn execute_with_registry(self, registry: AnalyzerRegistry) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Bytecodes(command) => command.execute(&registry).await,
+            Subcommands::List => {
+                let width = registry.analyzers.iter().map(|a| a.name.len()).max().unwrap_or(0);
+                for analyzer in &registry.analyzers {
+                    println!("{:<width$}  {}", analyzer.name, analyzer.description);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// An analysis of the deployed contracts.
+///
+/// The contracts are visited in parallel: every thread visits a part of the contracts with its
+/// own analyzer, created with [Default], and the analyzers are merged before the report is
+/// created.
+pub trait BytecodeAnalyzer: Default + Send + 'static {
+    /// Visits a deployed contract.
+    fn visit(&mut self, code_hash: B256, bytecode: &Bytecode);
+
+    /// Merges an analyzer that visited other contracts into this one.
+    fn merge(&mut self, other: Self);
+
+    /// Returns the report of the visited contracts.
+    fn finish(&self) -> Report;
+}
+
+/// The result of a [BytecodeAnalyzer], printed as a table.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    /// The title of the report.
+    pub title: String,
+    /// The headers of the columns.
+    pub columns: Vec<String>,
+    /// The rows, with one value per column.
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Report {
+    /// Creates an empty report with the given title and columns.
+    pub fn new<C: Into<String>>(
+        title: impl Into<String>,
+        columns: impl IntoIterator<Item = C>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            columns: columns.into_iter().map(Into::into).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Appends a row.
+    pub fn push_row<V: ToString>(&mut self, values: impl IntoIterator<Item = V>) {
+        self.rows.push(values.into_iter().map(|value| value.to_string()).collect());
+    }
+}
+
+impl fmt::Display for Report {
+    /// Formats the report as a table, with the first column aligned to the left and the others to
+    /// the right.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut widths = self.columns.iter().map(String::len).collect::<Vec<_>>();
+        for row in &self.rows {
+            for (i, value) in row.iter().enumerate() {
+                match widths.get_mut(i) {
+                    Some(width) => *width = (*width).max(value.len()),
+                    None => widths.push(value.len()),
+                }
+            }
+        }
+
+        writeln!(f, "{}", self.title)?;
+        for row in std::iter::once(&self.columns).chain(&self.rows) {
+            let mut line = String::new();
+            for (i, (value, width)) in row.iter().zip(&widths).enumerate() {
+                if i == 0 {
+                    line.push_str(&format!("{value:<width$}"));
+                } else {
+                    line.push_str(&format!("  {value:>width$}"));
+                }
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+/// The analyzers available to `reth analyze`, by name.
+pub struct AnalyzerRegistry {
+    analyzers: Vec<RegisteredAnalyzer>,
+}
+
+impl AnalyzerRegistry {
+    /// Creates a registry without analyzers.
+    pub fn empty() -> Self {
+        Self { analyzers: Vec::new() }
+    }
+
+    /// Registers an analyzer under the given name, replacing an analyzer registered under the same
+    /// name.
+    pub fn with_analyzer<A: BytecodeAnalyzer>(
+        mut self,
+        name: &'static str,
+        description: &'static str,
+    ) -> Self {
+        self.analyzers.retain(|analyzer| analyzer.name != name);
+        self.analyzers.push(RegisteredAnalyzer { name, description, new: new_analyzer::<A> });
+        self
+    }
+
+    /// Returns the names of the registered analyzers.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.analyzers.iter().map(|analyzer| analyzer.name)
+    }
+
+    /// Returns the analyzers with the given names, or all analyzers if no names are given.
+    fn select(&self, names: &[String]) -> eyre::Result<Vec<&RegisteredAnalyzer>> {
+        if names.is_empty() {
+            return Ok(self.analyzers.iter().collect())
+        }
+        names
+            .iter()
+            .map(|name| {
+                self.analyzers.iter().find(|analyzer| analyzer.name == name.as_str()).ok_or_else(
+                    || {
+                        eyre::eyre!(
+                            "Unknown analyzer {name}, available analyzers: {}",
+                            self.names().collect::<Vec<_>>().join(", ")
+                        )
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+impl Default for AnalyzerRegistry {
+    /// Creates a registry with the built-in analyzers.
+    fn default() -> Self {
+        Self::empty()
+            .with_analyzer::<analyzers::OpcodeHistogram>(
+                "opcodes",
+                "Histogram of the opcodes of the deployed contracts",
+            )
+            .with_analyzer::<analyzers::CodeSizeDistribution>(
+                "code-size",
+                "Distribution of the code sizes of the deployed contracts",
+            )
+            .with_analyzer::<analyzers::JumpdestDensity>(
+                "jumpdest-density",
+                "Distribution of the share of JUMPDEST instructions in legacy contracts",
+            )
+            .with_analyzer::<analyzers::PrecompileCalls>(
+                "precompile-calls",
+                "Precompiles and system contracts called with a constant address by legacy contracts",
+            )
+    }
+}
+
+impl fmt::Debug for AnalyzerRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+/// An analyzer registered in the [AnalyzerRegistry].
+struct RegisteredAnalyzer {
+    name: &'static str,
+    description: &'static str,
+    new: fn() -> Box<dyn DynAnalyzer>,
+}
+
+fn new_analyzer<A: BytecodeAnalyzer>() -> Box<dyn DynAnalyzer> {
+    Box::<A>::default()
+}
+
+/// Object safe version of [BytecodeAnalyzer], so analyzers of different types can run together.
+trait DynAnalyzer: Send {
+    fn visit(&mut self, code_hash: B256, bytecode: &Bytecode);
+
+    /// Merges an analyzer of the same type.
+    fn merge(&mut self, other: Box<dyn DynAnalyzer>);
+
+    fn finish(&self) -> Report;
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<A: BytecodeAnalyzer> DynAnalyzer for A {
+    fn visit(&mut self, code_hash: B256, bytecode: &Bytecode) {
+        BytecodeAnalyzer::visit(self, code_hash, bytecode)
+    }
+
+    fn merge(&mut self, other: Box<dyn DynAnalyzer>) {
+        let other = other
+            .into_any()
+            .downcast::<A>()
+            .expect("analyzers are only merged with analyzers of the same registration");
+        BytecodeAnalyzer::merge(self, *other)
+    }
+
+    fn finish(&self) -> Report {
+        BytecodeAnalyzer::finish(self)
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_report() {
+        let mut report = Report::new("Sizes", ["SIZE", "CONTRACTS"]);
+        report.push_row(["<= 32 B", "1"]);
+        report.push_row(["<= 1 KiB", "1000"]);
+
+        assert_eq!(
+            report.to_string(),
+            "Sizes\nSIZE      CONTRACTS\n<= 32 B           1\n<= 1 KiB       1000\n"
+        );
+    }
+
+    #[test]
+    fn select_analyzers() {
+        let registry = AnalyzerRegistry::default();
+        assert_eq!(registry.select(&[]).unwrap().len(), registry.analyzers.len());
+
+        let selected = registry.select(&["code-size".to_string()]).unwrap();
+        assert_eq!(
+            selected.iter().map(|analyzer| analyzer.name).collect::<Vec<_>>(),
+            ["code-size"]
+        );
+        assert!(registry.select(&["unknown".to_string()]).is_err());
+    }
+
+    #[test]
+    fn replace_analyzer() {
+        let registry = AnalyzerRegistry::default()
+            .with_analyzer::<analyzers::OpcodeHistogram>("code-size", "Opcodes instead");
+        let names = registry.names().collect::<Vec<_>>();
+        assert_eq!(names.iter().filter(|name| **name == "code-size").count(), 1);
+        assert_eq!(registry.analyzers.last().unwrap().description, "Opcodes instead");
+    }
+}
@@ -0,0 +1,104 @@
+//! Iteration over the instructions of legacy code.
+
+use reth_revm::interpreter::opcode::{PUSH1, PUSH32};
+
+/// An instruction of legacy code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Instruction<'a> {
+    /// The opcode.
+    pub(crate) opcode: u8,
+    /// The data of a `PUSH` instruction, empty for other instructions.
+    ///
+    /// The data of a `PUSH` instruction at the end of the code may be truncated.
+    pub(crate) data: &'a [u8],
+}
+
+impl Instruction<'_> {
+    /// Returns the number of data bytes of the opcode.
+    pub(crate) const fn data_size(opcode: u8) -> usize {
+        match opcode {
+            PUSH1..=PUSH32 => (opcode - PUSH1 + 1) as usize,
+            _ => 0,
+        }
+    }
+}
+
+/// A bounds-checked iterator over the instructions of legacy code, see [instructions].
+#[derive(Debug, Clone)]
+pub(crate) struct Instructions<'a> {
+    code: &'a [u8],
+    pc: usize,
+}
+
+impl<'a> Iterator for Instructions<'a> {
+    type Item = Instruction<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let opcode = *self.code.get(self.pc)?;
+        let data = self.code.get(self.pc + 1..).unwrap_or_default();
+        let data = &data[..Instruction::data_size(opcode).min(data.len())];
+        self.pc += 1 + data.len();
+        Some(Instruction { opcode, data })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.code.len().saturating_sub(self.pc);
+        // every instruction takes between 1 and 33 bytes
+        (remaining.div_ceil(1 + Instruction::data_size(PUSH32)), Some(remaining))
+    }
+}
+
+/// Returns the instructions of legacy code, skipping the data of `PUSH` instructions.
+pub(crate) fn instructions(code: &[u8]) -> Instructions<'_> {
+    Instructions { code, pc: 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn truncated_push() {
+        // PUSH1 0x01 PUSH4 0xaabb
+        let code = [0x60, 0x01, 0x63, 0xAA, 0xBB];
+        let instructions = instructions(&code).collect::<Vec<_>>();
+        assert_eq!(
+            instructions,
+            [
+                Instruction { opcode: 0x60, data: &[0x01] },
+                Instruction { opcode: 0x63, data: &[0xAA, 0xBB] },
+            ]
+        );
+
+        // PUSH32 without data
+        let instructions = super::instructions(&[0x7F]).collect::<Vec<_>>();
+        assert_eq!(instructions, [Instruction { opcode: 0x7F, data: &[] }]);
+    }
+
+    proptest! {
+        #[test]
+        fn instructions_cover_code(code in proptest::collection::vec(any::<u8>(), 0..512)) {
+            let instructions = instructions(&code).collect::<Vec<_>>();
+
+            let mut pc = 0;
+            for (i, instruction) in instructions.iter().enumerate() {
+                prop_assert_eq!(instruction.opcode, code[pc]);
+                prop_assert_eq!(instruction.data, &code[pc + 1..pc + 1 + instruction.data.len()]);
+                // only the last instruction can be truncated
+                let truncated = instruction.data.len() < Instruction::data_size(instruction.opcode);
+                prop_assert!(!truncated || i == instructions.len() - 1);
+                pc += 1 + instruction.data.len();
+            }
+            prop_assert_eq!(pc, code.len());
+        }
+
+        #[test]
+        fn size_hint_bounds(code in proptest::collection::vec(any::<u8>(), 0..512)) {
+            let (lower, upper) = instructions(&code).size_hint();
+            let count = instructions(&code).count();
+            prop_assert!(lower <= count);
+            prop_assert!(upper.map_or(true, |upper| count <= upper));
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Basic block statistics of the deployed contracts.
+
+use super::{eof, instructions, scan_bytecodes};
+use reth_db::DatabaseEnv;
+use reth_provider::ProviderFactory;
+use reth_revm::interpreter::opcode::{
+    OpCode, INVALID, JUMP, JUMPDEST, JUMPI, RETURN, REVERT, SELFDESTRUCT, STOP,
+};
+use std::sync::Arc;
+use tracing::info;
+
+/// The basic blocks of a legacy contract.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct CodeBlocks {
+    blocks: u64,
+    /// The number of instructions in the basic blocks.
+    instructions: u64,
+    /// The number of bytes after a terminating instruction that are not reachable without a
+    /// `JUMPDEST`, e.g. the metadata appended by the compiler or data read with `CODECOPY`.
+    unreachable_bytes: u64,
+}
+
+/// Returns whether execution can't continue with the next instruction after the opcode.
+fn is_terminator(opcode: u8) -> bool {
+    matches!(opcode, STOP | RETURN | REVERT | INVALID | SELFDESTRUCT | JUMP) ||
+        OpCode::new(opcode).is_none()
+}
+
+/// Splits legacy code into basic blocks.
+///
+/// A basic block starts at a `JUMPDEST` or after a `JUMPI`, and ends with a terminating
+/// instruction, a `JUMPI` or right before a `JUMPDEST`. The instructions after a terminating
+/// instruction are unreachable until the next `JUMPDEST`.
+fn code_blocks(code: &[u8]) -> CodeBlocks {
+    let mut blocks = CodeBlocks::default();
+    let (mut in_block, mut reachable) = (false, true);
+    for instruction in instructions(code) {
+        if instruction.opcode == JUMPDEST {
+            (in_block, reachable) = (false, true);
+        }
+        if !reachable {
+            blocks.unreachable_bytes += 1 + instruction.data.len() as u64;
+            continue
+        }
+
+        if !in_block {
+            blocks.blocks += 1;
+            in_block = true;
+        }
+        blocks.instructions += 1;
+        if is_terminator(instruction.opcode) {
+            (in_block, reachable) = (false, false);
+        } else if instruction.opcode == JUMPI {
+            in_block = false;
+        }
+    }
+    blocks
+}
+
+/// The basic block statistics of the deployed contracts.
+#[derive(Debug, Default)]
+pub(super) struct CfgStats {
+    /// The number of legacy contracts.
+    contracts: u64,
+    /// The number of EOF contracts, which are not analyzed.
+    eof_contracts: u64,
+    code_bytes: u64,
+    blocks: u64,
+    max_blocks: u64,
+    instructions: u64,
+    unreachable_bytes: u64,
+    /// The number of contracts with unreachable code.
+    unreachable_contracts: u64,
+}
+
+impl CfgStats {
+    fn visit(&mut self, code: &[u8]) {
+        if eof::is_eof(code) {
+            self.eof_contracts += 1;
+            return
+        }
+        let blocks = code_blocks(code);
+        self.contracts += 1;
+        self.code_bytes += code.len() as u64;
+        self.blocks += blocks.blocks;
+        self.max_blocks = self.max_blocks.max(blocks.blocks);
+        self.instructions += blocks.instructions;
+        self.unreachable_bytes += blocks.unreachable_bytes;
+        self.unreachable_contracts += (blocks.unreachable_bytes > 0) as u64;
+    }
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            contracts: self.contracts + other.contracts,
+            eof_contracts: self.eof_contracts + other.eof_contracts,
+            code_bytes: self.code_bytes + other.code_bytes,
+            blocks: self.blocks + other.blocks,
+            max_blocks: self.max_blocks.max(other.max_blocks),
+            instructions: self.instructions + other.instructions,
+            unreachable_bytes: self.unreachable_bytes + other.unreachable_bytes,
+            unreachable_contracts: self.unreachable_contracts + other.unreachable_contracts,
+        }
+    }
+}
+
+/// Computes the basic block statistics of all contracts in the `Bytecodes` table.
+pub(super) fn count_cfg_stats(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+) -> eyre::Result<CfgStats> {
+    info!(target: "reth::cli", "Splitting deployed contracts into basic blocks");
+    let partitions = scan_bytecodes(factory, CfgStats::default, |stats, _, bytecode| {
+        stats.visit(&bytecode.original_bytes())
+    })?;
+    Ok(partitions.into_iter().fold(CfgStats::default(), CfgStats::merge))
+}
+
+/// Prints the basic block statistics.
+pub(super) fn print_cfg_stats(stats: &CfgStats) {
+    let ratio = |a: u64, b: u64| if b == 0 { 0.0 } else { a as f64 / b as f64 };
+    println!(
+        "{:<32} {} ({} EOF contracts skipped)",
+        "Legacy contracts", stats.contracts, stats.eof_contracts
+    );
+    println!("{:<32} {}", "Basic blocks", stats.blocks);
+    println!("{:<32} {:.1}", "Basic blocks per contract", ratio(stats.blocks, stats.contracts));
+    println!("{:<32} {}", "Most basic blocks of a contract", stats.max_blocks);
+    println!(
+        "{:<32} {:.2} instructions",
+        "Average basic block length",
+        ratio(stats.instructions, stats.blocks)
+    );
+    println!(
+        "{:<32} {} B ({:.3}% of {} B of code)",
+        "Unreachable code",
+        stats.unreachable_bytes,
+        ratio(stats.unreachable_bytes, stats.code_bytes) * 100.0,
+        stats.code_bytes
+    );
+    println!("{:<32} {}", "Contracts with unreachable code", stats.unreachable_contracts);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_basic_blocks() {
+        // PUSH1 0x06 JUMPI | PUSH1 0x00 STOP 0xaa 0xbb | JUMPDEST PUSH1 0x0a JUMP | JUMPDEST
+        // INVALID 0xa2 0x64
+        let code = [
+            0x60, 0x06, 0x57, 0x60, 0x00, 0x00, 0xaa, 0xbb, 0x5b, 0x60, 0x0a, 0x56, 0x5b, 0xfe,
+            0xa2, 0x64,
+        ];
+        assert_eq!(
+            code_blocks(&code),
+            CodeBlocks { blocks: 4, instructions: 9, unreachable_bytes: 4 }
+        );
+
+        assert_eq!(code_blocks(&[]), CodeBlocks::default());
+        // undefined opcodes terminate execution
+        assert_eq!(
+            code_blocks(&[0x0c, 0x00]),
+            CodeBlocks { blocks: 1, instructions: 1, unreachable_bytes: 1 }
+        );
+    }
+
+    #[test]
+    fn merge_stats() {
+        let mut a = CfgStats::default();
+        a.visit(&[0x60, 0x00, 0x00, 0xaa]);
+        let mut b = CfgStats::default();
+        b.visit(&[0x5b, 0x5b, 0x00]);
+        b.visit(&[0xEF, 0x00]);
+
+        let stats = a.merge(b);
+        assert_eq!((stats.contracts, stats.eof_contracts), (2, 1));
+        assert_eq!((stats.blocks, stats.max_blocks, stats.instructions), (3, 2, 5));
+        assert_eq!((stats.unreachable_bytes, stats.unreachable_contracts), (1, 1));
+        assert_eq!(stats.code_bytes, 7);
+    }
+}
@@ -0,0 +1,192 @@
+//! Command that compares the opcode counts of the deployed contracts of two databases.
+
+use super::{count_deployed_opcodes, opcode_name, OpcodeCounts};
+use crate::{
+    args::{
+        utils::{genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, PlatformPath},
+};
+use clap::Parser;
+use reth_db::open_db_read_only;
+use reth_primitives::ChainSpec;
+use reth_provider::ProviderFactory;
+use std::{path::PathBuf, sync::Arc, time::Instant};
+use tracing::info;
+
+/// `reth count-opcodes diff` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The chain of the first database.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// The chain of the second database, if it differs from `--chain`.
+    #[arg(long, value_name = "CHAIN_OR_PATH", value_parser = genesis_value_parser)]
+    chain_b: Option<Arc<ChainSpec>>,
+
+    /// The data dir of the first database, containing the `db` and `static_files` directories.
+    #[arg(long, value_name = "DATA_DIR")]
+    db_a: PlatformPath<DataDirPath>,
+
+    /// The data dir of the second database, containing the `db` and `static_files` directories.
+    #[arg(long, value_name = "DATA_DIR")]
+    db_b: PlatformPath<DataDirPath>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// The number of threads scanning the deployed contracts.
+    ///
+    /// Defaults to the number of CPUs.
+    #[arg(long, short, value_name = "JOBS")]
+    jobs: Option<usize>,
+}
+
+impl Command {
+    /// Execute `count-opcodes diff` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let mut pool = rayon::ThreadPoolBuilder::new();
+        if let Some(jobs) = self.jobs {
+            pool = pool.num_threads(jobs);
+        }
+        let pool = pool.build()?;
+
+        let chain_b = self.chain_b.clone().unwrap_or_else(|| self.chain.clone());
+        let start = Instant::now();
+        let a = pool.install(|| self.count(&self.db_a, self.chain.clone()))?;
+        let b = pool.install(|| self.count(&self.db_b, chain_b))?;
+        info!(target: "reth::cli", elapsed = ?start.elapsed(), "Counted opcodes");
+
+        print_diff(&a, &b);
+        Ok(())
+    }
+
+    /// Counts the opcodes of the deployed contracts of the database in the data dir.
+    fn count(
+        &self,
+        data_dir: &PlatformPath<DataDirPath>,
+        chain: Arc<ChainSpec>,
+    ) -> eyre::Result<OpcodeCounts> {
+        info!(target: "reth::cli", %data_dir, "Opening database");
+        let db_path: PathBuf = data_dir.join("db").into();
+        let db = Arc::new(open_db_read_only(&db_path, self.db.database_args())?);
+        let factory = ProviderFactory::new(db, chain, data_dir.join("static_files").into())?;
+        Ok(count_deployed_opcodes(&factory, None)?.counts)
+    }
+}
+
+/// The change of the count and share of an opcode between two databases.
+#[derive(Debug, PartialEq)]
+struct OpcodeDelta {
+    opcode: u8,
+    a: u64,
+    b: u64,
+    /// The share of the opcode of all opcodes of the first database, in percent.
+    share_a: f64,
+    /// The share of the opcode of all opcodes of the second database, in percent.
+    share_b: f64,
+}
+
+impl OpcodeDelta {
+    fn delta(&self) -> i128 {
+        self.b as i128 - self.a as i128
+    }
+
+    /// The change of the share, in percentage points.
+    fn share_delta(&self) -> f64 {
+        self.share_b - self.share_a
+    }
+}
+
+/// Returns the deltas of the opcodes found in either database, sorted by the largest change of
+/// their share.
+fn opcode_deltas(a: &OpcodeCounts, b: &OpcodeCounts) -> Vec<OpcodeDelta> {
+    let share = |count: u64, total: u64| {
+        if total == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / total as f64
+        }
+    };
+    let (total_a, total_b) = (a.iter().sum::<u64>(), b.iter().sum::<u64>());
+
+    let mut deltas = (0..=u8::MAX)
+        .map(|opcode| {
+            let (a, b) = (a[opcode as usize], b[opcode as usize]);
+            OpcodeDelta { opcode, a, b, share_a: share(a, total_a), share_b: share(b, total_b) }
+        })
+        .filter(|delta| delta.a > 0 || delta.b > 0)
+        .collect::<Vec<_>>();
+    deltas.sort_by(|x, y| {
+        y.share_delta().abs().total_cmp(&x.share_delta().abs()).then(x.opcode.cmp(&y.opcode))
+    });
+    deltas
+}
+
+/// Prints the per-opcode delta report.
+fn print_diff(a: &OpcodeCounts, b: &OpcodeCounts) {
+    println!(
+        "{:<16} {:>16} {:>16} {:>16} {:>8} {:>8} {:>9}",
+        "OPCODE", "A", "B", "DELTA", "SHARE A", "SHARE B", "DELTA PP"
+    );
+    for delta in opcode_deltas(a, b) {
+        println!(
+            "{:<16} {:>16} {:>16} {:>+16} {:>7.3}% {:>7.3}% {:>+9.3}",
+            opcode_name(delta.opcode),
+            delta.a,
+            delta.b,
+            delta.delta(),
+            delta.share_a,
+            delta.share_b,
+            delta.share_delta()
+        );
+    }
+    println!(
+        "{:<16} {:>16} {:>16} {:>+16}",
+        "TOTAL",
+        a.iter().sum::<u64>(),
+        b.iter().sum::<u64>(),
+        b.iter().sum::<u64>() as i128 - a.iter().sum::<u64>() as i128
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_diff_args() {
+        let command =
+            Command::try_parse_from(["reth", "--db-a", "/tmp/a", "--db-b", "/tmp/b"]).unwrap();
+        assert_eq!(command.db_a.as_ref(), std::path::Path::new("/tmp/a"));
+        assert_eq!(command.db_b.as_ref(), std::path::Path::new("/tmp/b"));
+        assert!(command.chain_b.is_none());
+
+        assert!(Command::try_parse_from(["reth", "--db-a", "/tmp/a"]).is_err());
+    }
+
+    #[test]
+    fn deltas_sorted_by_share_change() {
+        let mut a = [0; 256];
+        let mut b = [0; 256];
+        // PUSH1: 50% -> 25%, SSTORE: 50% -> 50%, CALL: 0% -> 25%
+        (a[0x60], a[0x55]) = (2, 2);
+        (b[0x60], b[0x55], b[0xF1]) = (1, 2, 1);
+
+        let deltas = opcode_deltas(&a, &b);
+        assert_eq!(deltas.iter().map(|delta| delta.opcode).collect::<Vec<_>>(), [0x60, 0xF1, 0x55]);
+        assert_eq!(deltas[0].delta(), -1);
+        assert_eq!(deltas[0].share_delta(), -25.0);
+        assert_eq!(deltas[1].share_delta(), 25.0);
+    }
+}
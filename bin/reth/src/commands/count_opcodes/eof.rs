@@ -0,0 +1,212 @@
+//! Decoding of EOF ([EIP-3540](https://eips.ethereum.org/EIPS/eip-3540)) containers.
+
+use reth_revm::interpreter::opcode::{PUSH1, PUSH32};
+
+/// The magic every EOF container starts with.
+const EOF_MAGIC: [u8; 2] = [0xEF, 0x00];
+
+/// The only supported EOF version.
+const EOF_VERSION: u8 = 0x01;
+
+const KIND_TERMINATOR: u8 = 0x00;
+const KIND_TYPES: u8 = 0x01;
+const KIND_CODE: u8 = 0x02;
+const KIND_CONTAINER: u8 = 0x03;
+const KIND_DATA: u8 = 0x04;
+
+/// The size of the type of a code section in the types section.
+const TYPE_SIZE: usize = 4;
+
+const DATALOADN: u8 = 0xD1;
+const RJUMP: u8 = 0xE0;
+const RJUMPI: u8 = 0xE1;
+const RJUMPV: u8 = 0xE2;
+const CALLF: u8 = 0xE3;
+const JUMPF: u8 = 0xE5;
+const DUPN: u8 = 0xE6;
+const SWAPN: u8 = 0xE7;
+const EXCHANGE: u8 = 0xE8;
+const EOFCREATE: u8 = 0xEC;
+const RETURNCONTRACT: u8 = 0xEE;
+
+/// Returns `true` if the code starts with the EOF magic.
+///
+/// Since [EIP-3541](https://eips.ethereum.org/EIPS/eip-3541) no legacy code starting with `0xEF`
+/// can be deployed, so such code is either an EOF container or malformed.
+pub(super) fn is_eof(code: &[u8]) -> bool {
+    code.starts_with(&EOF_MAGIC)
+}
+
+/// A decoded EOF container, borrowing its sections from the code.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct EofContainer<'a> {
+    /// The code sections.
+    pub(super) code_sections: Vec<&'a [u8]>,
+    /// The nested containers.
+    pub(super) containers: Vec<EofContainer<'a>>,
+}
+
+impl<'a> EofContainer<'a> {
+    /// Decodes the container, returning `None` if it is malformed.
+    ///
+    /// Only the layout of the container is validated, the code sections are not.
+    pub(super) fn decode(code: &'a [u8]) -> Option<Self> {
+        let mut reader = Reader(code);
+        if reader.bytes(EOF_MAGIC.len())? != EOF_MAGIC || reader.u8()? != EOF_VERSION {
+            return None
+        }
+
+        reader.kind(KIND_TYPES)?;
+        let types_size = reader.u16()? as usize;
+
+        reader.kind(KIND_CODE)?;
+        let code_sizes = reader.sizes()?;
+        if code_sizes.is_empty() || types_size != code_sizes.len() * TYPE_SIZE {
+            return None
+        }
+
+        let container_sizes = match reader.peek()? {
+            KIND_CONTAINER => {
+                reader.u8()?;
+                let sizes = reader.sizes()?;
+                if sizes.is_empty() {
+                    return None
+                }
+                sizes
+            }
+            _ => Vec::new(),
+        };
+
+        reader.kind(KIND_DATA)?;
+        let data_size = reader.u16()? as usize;
+        reader.kind(KIND_TERMINATOR)?;
+
+        reader.bytes(types_size)?;
+        let code_sections =
+            code_sizes.into_iter().map(|size| reader.bytes(size)).collect::<Option<Vec<_>>>()?;
+        let containers = container_sizes
+            .into_iter()
+            .map(|size| Self::decode(reader.bytes(size)?))
+            .collect::<Option<Vec<_>>>()?;
+        // the data section of an initcode container may be shorter than declared
+        if reader.0.len() > data_size {
+            return None
+        }
+
+        Some(Self { code_sections, containers })
+    }
+
+    /// Calls `f` with every opcode of the code sections of this container and of the nested
+    /// containers, skipping the immediates.
+    pub(super) fn for_each_opcode(&self, f: &mut impl FnMut(u8)) {
+        for section in &self.code_sections {
+            section_opcodes(section).for_each(&mut *f);
+        }
+        for container in &self.containers {
+            container.for_each_opcode(f);
+        }
+    }
+}
+
+/// Returns the opcodes of an EOF code section, skipping the immediates.
+fn section_opcodes(code: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    let mut pc = 0;
+    std::iter::from_fn(move || {
+        let opcode = *code.get(pc)?;
+        pc += 1 + match opcode {
+            PUSH1..=PUSH32 => (opcode - PUSH1 + 1) as usize,
+            RJUMP | RJUMPI | CALLF | JUMPF | DATALOADN => 2,
+            DUPN | SWAPN | EXCHANGE | EOFCREATE | RETURNCONTRACT => 1,
+            // the jump table size is encoded as the maximum index
+            RJUMPV => 1 + code.get(pc + 1).map_or(0, |max_index| (*max_index as usize + 1) * 2),
+            _ => 0,
+        };
+        Some(opcode)
+    })
+}
+
+/// Reads the header and body of a container.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.0.first().copied()
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2).map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Reads the section kind, returning `None` if it's not the expected one.
+    fn kind(&mut self, kind: u8) -> Option<()> {
+        (self.u8()? == kind).then_some(())
+    }
+
+    /// Reads the number of sections followed by their sizes.
+    fn sizes(&mut self) -> Option<Vec<usize>> {
+        let count = self.u16()?;
+        (0..count).map(|_| self.u16().map(usize::from)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A container with a single code section `PUSH1 0x01 RJUMPV [0, 1] STOP` and a 2 byte
+    /// data section.
+    const CONTAINER: &[u8] = &[
+        0xEF, 0x00, 0x01, // magic and version
+        0x01, 0x00, 0x04, // types section
+        0x02, 0x00, 0x01, 0x00, 0x09, // code sections
+        0x04, 0x00, 0x02, // data section
+        0x00, // terminator
+        0x00, 0x80, 0x00, 0x01, // types
+        0x60, 0x01, 0xE2, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, // code
+        0xAA, 0xBB, // data
+    ];
+
+    #[test]
+    fn decode_container() {
+        assert!(is_eof(CONTAINER));
+        let container = EofContainer::decode(CONTAINER).unwrap();
+        assert_eq!(container.code_sections, vec![&CONTAINER[19..28]]);
+        assert!(container.containers.is_empty());
+
+        let mut opcodes = Vec::new();
+        container.for_each_opcode(&mut |opcode| opcodes.push(opcode));
+        assert_eq!(opcodes, vec![0x60, RJUMPV, 0x00]);
+    }
+
+    #[test]
+    fn decode_truncated_data() {
+        assert!(EofContainer::decode(&CONTAINER[..CONTAINER.len() - 1]).is_some());
+    }
+
+    #[test]
+    fn decode_malformed() {
+        // trailing bytes after the data section
+        assert_eq!(EofContainer::decode(&[CONTAINER, &[0x00]].concat()), None);
+        // truncated code section
+        assert_eq!(EofContainer::decode(&CONTAINER[..25]), None);
+        // unsupported version
+        let mut code = CONTAINER.to_vec();
+        code[2] = 0x02;
+        assert_eq!(EofContainer::decode(&code), None);
+        // legacy code
+        assert!(!is_eof(&[0x60, 0x80, 0x60, 0x40]));
+    }
+}
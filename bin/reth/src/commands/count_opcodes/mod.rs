@@ -0,0 +1,1104 @@
+//! Command that counts the opcodes of the deployed contracts, or the opcodes executed by the
+//! transactions of a range of blocks.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+    prometheus_exporter,
+};
+use clap::{Parser, Subcommand};
+use eyre::{eyre, WrapErr};
+use metrics::{describe_gauge, gauge};
+use rayon::prelude::*;
+use reth_cli_runner::CliContext;
+use reth_db::{cursor::DbCursorRO, open_db_read_only, tables, transaction::DbTx, DatabaseEnv};
+use reth_interfaces::provider::ProviderError;
+use reth_node_api::ConfigureEvm;
+use reth_node_ethereum::EthEvmConfig;
+use reth_primitives::{
+    hex, keccak256, revm::env::tx_env_with_recovered, Address, BlockNumber, Bytecode, ChainSpec,
+    B256,
+};
+use reth_provider::{
+    BlockNumReader, BlockReader, ChainSpecProvider, EvmEnvProvider, ProviderFactory,
+    StateProviderBox, TransactionVariant,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    interpreter::{
+        opcode::{OpCode, DUP1, DUP16, EQ, JUMPI, PUSH1, PUSH32, PUSH4},
+        Interpreter,
+    },
+    primitives::{EnvWithHandlerCfg, ResultAndState},
+    state_change::apply_beacon_root_contract_call,
+    Database, DatabaseCommit, EvmContext, Inspector,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    fs,
+    net::SocketAddr,
+    ops::Bound,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+use tracing::info;
+
+mod bytecode;
+mod cfg;
+mod diff;
+mod eof;
+mod slots;
+mod targets;
+mod tx;
+
+pub(crate) use bytecode::instructions;
+pub(crate) use targets::{StaticCallTargets, CALL_TARGETS};
+
+/// The number of blocks that are re-executed in parallel before the progress is logged.
+const BATCH_SIZE: u64 = 1_000;
+
+/// `reth count-opcodes` command
+#[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Option<Subcommands>,
+
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// Re-execute the transactions of the blocks starting at this block and count the executed
+    /// opcodes, instead of the opcodes of the deployed contracts.
+    #[arg(long, value_name = "BLOCK")]
+    from_block: Option<BlockNumber>,
+
+    /// The last block to re-execute. Defaults to the latest block.
+    #[arg(long, value_name = "BLOCK", requires = "from_block")]
+    to_block: Option<BlockNumber>,
+
+    /// Report the storage slots and contracts with the most `SLOAD`s and `SSTORE`s of the
+    /// re-executed transactions, instead of the opcode counts.
+    #[arg(long, requires = "from_block")]
+    storage_slots: bool,
+
+    /// Report the deployed contracts with the most occurrences of the opcode given with
+    /// `--opcode`, instead of the opcode counts.
+    #[arg(long, requires = "opcode", conflicts_with = "from_block")]
+    per_contract: bool,
+
+    /// The opcode to report the contracts of, by name (e.g. `SSTORE`) or value (e.g. `0x55`).
+    #[arg(long, value_name = "OPCODE", value_parser = parse_opcode, requires = "per_contract")]
+    opcode: Option<u8>,
+
+    /// The number of contracts, selectors or storage slots to report.
+    #[arg(long, value_name = "N", default_value_t = 20)]
+    top: usize,
+
+    /// Resolve the addresses the reported contracts are deployed at from the plain account
+    /// state. This walks all accounts.
+    #[arg(long, requires = "per_contract")]
+    resolve_addresses: bool,
+
+    /// Also report the opcode counts of the deployed contracts weighted by the number of accounts
+    /// the code is deployed at, next to the counts per unique code. This walks all accounts.
+    #[arg(long, conflicts_with_all = ["from_block", "per_contract"])]
+    weight_by_accounts: bool,
+
+    /// Report the function selectors dispatched by the most deployed contracts, instead of the
+    /// opcode counts.
+    ///
+    /// Selectors are recognized by the dispatch pattern of the Solidity and Vyper compilers,
+    /// `PUSH4 <selector> [DUPn] EQ PUSHn <destination> JUMPI`, in legacy code.
+    #[arg(long, conflicts_with_all = ["from_block", "per_contract", "weight_by_accounts"])]
+    selectors: bool,
+
+    /// A file with the text signatures to resolve the names of the selectors with, e.g. an export
+    /// of the 4byte directory.
+    ///
+    /// Every line is either a signature, e.g. `transfer(address,uint256)`, or a selector followed
+    /// by its signature, e.g. `0xa9059cbb transfer(address,uint256)`.
+    #[arg(long, value_name = "FILE", verbatim_doc_comment, requires = "selectors")]
+    signatures: Option<PathBuf>,
+
+    /// Report the calls to precompiles and system contracts, instead of the opcode counts.
+    ///
+    /// Counts the call sites of the deployed contracts, recognized by the pattern `PUSHn <address>
+    /// [GAS] CALL` in legacy code, or the executed calls of the re-executed transactions with
+    /// `--from-block`.
+    #[arg(
+        long,
+        conflicts_with_all = ["per_contract", "weight_by_accounts", "selectors", "storage_slots"]
+    )]
+    call_targets: bool,
+
+    /// Report basic block statistics of the deployed legacy contracts, instead of the opcode
+    /// counts: the number of basic blocks, their average length and the unreachable code after
+    /// terminating instructions.
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "from_block",
+            "per_contract",
+            "weight_by_accounts",
+            "selectors",
+            "call_targets",
+        ]
+    )]
+    cfg_stats: bool,
+
+    /// The number of threads scanning the deployed contracts or re-executing blocks.
+    ///
+    /// Defaults to the number of CPUs.
+    #[arg(long, short, value_name = "JOBS")]
+    jobs: Option<usize>,
+
+    /// Enable Prometheus metrics.
+    ///
+    /// The opcode counts are exported as gauges next to the database and process metrics, served
+    /// at the given interface and port. The counts of re-executed blocks are updated after every
+    /// batch of blocks, and the endpoint is served until the command is interrupted.
+    #[arg(
+        long,
+        value_name = "SOCKET",
+        conflicts_with_all = [
+            "per_contract",
+            "selectors",
+            "storage_slots",
+            "call_targets",
+            "cfg_stats",
+        ]
+    )]
+    metrics: Option<SocketAddr>,
+}
+
+/// `reth count-opcodes` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Compare the opcode counts of the deployed contracts of two databases
+    Diff(diff::Command),
+    /// Replay a transaction and profile the opcodes executed by each of its call frames
+    Tx(tx::Command),
+}
+
+impl Command {
+    /// Execute `count-opcodes` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        match self.command {
+            Some(Subcommands::Diff(command)) => return command.execute().await,
+            Some(Subcommands::Tx(command)) => return command.execute().await,
+            None => {}
+        }
+
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = Arc::new(open_db_read_only(&data_dir.db_path(), self.db.database_args())?);
+        let factory = ProviderFactory::new(
+            Arc::clone(&db),
+            self.chain.clone(),
+            data_dir.static_files_path(),
+        )?;
+
+        if let Some(listen_addr) = self.metrics {
+            info!(target: "reth::cli", "Starting metrics endpoint at {}", listen_addr);
+            prometheus_exporter::serve(
+                listen_addr,
+                prometheus_exporter::install_recorder()?,
+                db,
+                factory.static_file_provider(),
+                metrics_process::Collector::default(),
+                ctx.task_executor,
+            )
+            .await?;
+            describe_metrics();
+        }
+
+        let mut pool = rayon::ThreadPoolBuilder::new();
+        if let Some(jobs) = self.jobs {
+            pool = pool.num_threads(jobs);
+        }
+        pool.build()?.install(|| self.count(&factory))?;
+
+        if self.metrics.is_some() {
+            info!(target: "reth::cli", "Serving metrics until interrupted");
+            std::future::pending::<()>().await;
+        }
+        Ok(())
+    }
+
+    /// Counts the opcodes and prints the report, on the current thread pool.
+    fn count(&self, factory: &ProviderFactory<Arc<DatabaseEnv>>) -> eyre::Result<()> {
+        let start = Instant::now();
+        // `--opcode` is only accepted with `--per-contract`
+        if let Some(opcode) = self.opcode {
+            let contracts = top_contracts(factory, opcode, self.top, self.resolve_addresses)?;
+            info!(target: "reth::cli", elapsed = ?start.elapsed(), "Counted opcodes");
+
+            print_contracts(opcode, &contracts);
+            return Ok(())
+        }
+
+        if self.selectors {
+            let signatures = self.signatures.as_deref().map(read_signatures).transpose()?;
+            let selectors = count_selectors(factory)?;
+            info!(target: "reth::cli", elapsed = ?start.elapsed(), "Counted selectors");
+
+            print_selectors(&selectors, signatures.as_ref(), self.top);
+            return Ok(())
+        }
+
+        if let Some(from) = self.from_block {
+            let best_block = factory.provider()?.best_block_number()?;
+            let to = self.to_block.unwrap_or(best_block);
+            if from == 0 || from > to || to > best_block {
+                eyre::bail!(
+                    "Invalid block range: {from}..={to}, blocks 1..={best_block} can be executed"
+                )
+            }
+            if self.call_targets {
+                let calls = targets::count_executed_call_targets(factory, from, to)?;
+                info!(target: "reth::cli", elapsed = ?start.elapsed(), "Counted call targets");
+
+                targets::print_executed_call_targets(&calls);
+                return Ok(())
+            }
+            if self.storage_slots {
+                let slots = slots::count_storage_slots(factory, from, to)?;
+                info!(target: "reth::cli", elapsed = ?start.elapsed(), "Counted storage slots");
+
+                slots::print_storage_slots(&slots, self.top);
+                return Ok(())
+            }
+            let counts = count_executed_opcodes(factory, from, to, |block, counts| {
+                if self.metrics.is_some() {
+                    record_executed_metrics(block, counts);
+                }
+            })?;
+            info!(target: "reth::cli", elapsed = ?start.elapsed(), "Counted opcodes");
+
+            print_counts(&counts, None);
+            return Ok(())
+        }
+
+        if self.cfg_stats {
+            let stats = cfg::count_cfg_stats(factory)?;
+            info!(target: "reth::cli", elapsed = ?start.elapsed(), "Counted basic blocks");
+
+            cfg::print_cfg_stats(&stats);
+            return Ok(())
+        }
+
+        if self.call_targets {
+            let call_sites = targets::count_static_call_targets(factory)?;
+            info!(target: "reth::cli", elapsed = ?start.elapsed(), "Counted call targets");
+
+            targets::print_static_call_targets(&call_sites);
+            return Ok(())
+        }
+
+        let accounts =
+            self.weight_by_accounts.then(|| count_accounts_by_code(factory)).transpose()?;
+        let deployed = count_deployed_opcodes(factory, accounts.as_ref())?;
+        info!(target: "reth::cli", elapsed = ?start.elapsed(), "Counted opcodes");
+        if self.metrics.is_some() {
+            record_deployed_metrics(&deployed, accounts.is_some());
+        }
+
+        print_counts(&deployed.counts, accounts.is_some().then_some(&deployed.weighted));
+        println!();
+        println!(
+            "{} legacy contracts, {} EOF contracts, {} malformed EOF contracts",
+            deployed.legacy, deployed.eof, deployed.malformed_eof
+        );
+        Ok(())
+    }
+}
+
+/// The number of times each opcode was found.
+pub(crate) type OpcodeCounts = [u64; 256];
+
+/// The opcode counts of the deployed contracts.
+#[derive(Debug)]
+struct DeployedCounts {
+    /// The counts per unique code.
+    counts: OpcodeCounts,
+    /// The counts weighted by the number of accounts the code is deployed at.
+    weighted: OpcodeCounts,
+    /// The number of legacy contracts.
+    legacy: u64,
+    /// The number of EOF contracts.
+    eof: u64,
+    /// The number of contracts starting with the EOF magic that are not valid EOF containers.
+    /// Their opcodes are not counted.
+    malformed_eof: u64,
+}
+
+impl Default for DeployedCounts {
+    fn default() -> Self {
+        Self { counts: [0; 256], weighted: [0; 256], legacy: 0, eof: 0, malformed_eof: 0 }
+    }
+}
+
+impl DeployedCounts {
+    fn merge(self, other: Self) -> Self {
+        Self {
+            counts: add_counts(self.counts, &other.counts),
+            weighted: add_counts(self.weighted, &other.weighted),
+            legacy: self.legacy + other.legacy,
+            eof: self.eof + other.eof,
+            malformed_eof: self.malformed_eof + other.malformed_eof,
+        }
+    }
+}
+
+/// Counts the opcodes of all contracts in the `Bytecodes` table, see [visit_opcodes].
+///
+/// If the number of accounts per code hash is given, the counts are weighted by the number of
+/// accounts as well.
+fn count_deployed_opcodes(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    accounts: Option<&HashMap<B256, u64>>,
+) -> eyre::Result<DeployedCounts> {
+    info!(target: "reth::cli", "Counting opcodes of deployed contracts");
+    let partitions =
+        scan_bytecodes(factory, DeployedCounts::default, |deployed, code_hash, bytecode| {
+            let weight =
+                accounts.and_then(|accounts| accounts.get(&code_hash)).copied().unwrap_or_default();
+            let kind = visit_opcodes(&bytecode.original_bytes(), |opcode| {
+                deployed.counts[opcode as usize] += 1;
+                deployed.weighted[opcode as usize] += weight;
+            });
+            match kind {
+                CodeKind::Legacy => deployed.legacy += 1,
+                CodeKind::Eof => deployed.eof += 1,
+                CodeKind::MalformedEof => deployed.malformed_eof += 1,
+            }
+        })?;
+    Ok(partitions.into_iter().fold(DeployedCounts::default(), DeployedCounts::merge))
+}
+
+/// Returns the number of accounts in the plain account state per code hash.
+fn count_accounts_by_code(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+) -> eyre::Result<HashMap<B256, u64>> {
+    info!(target: "reth::cli", "Counting accounts per deployed code");
+    let provider = factory.provider()?;
+    let mut cursor = provider.tx_ref().cursor_read::<tables::PlainAccountState>()?;
+
+    let mut accounts = HashMap::new();
+    for entry in cursor.walk(None)? {
+        let (_, account) = entry?;
+        if let Some(code_hash) = account.bytecode_hash {
+            *accounts.entry(code_hash).or_default() += 1;
+        }
+    }
+    Ok(accounts)
+}
+
+/// Walks the `Bytecodes` table in parallel, with one partition of the code hashes per thread of
+/// the current thread pool.
+///
+/// Every partition is folded into its own accumulator, created with `init`. The accumulators of
+/// all partitions are returned.
+pub(crate) fn scan_bytecodes<T, I, F>(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    init: I,
+    f: F,
+) -> eyre::Result<Vec<T>>
+where
+    T: Send,
+    I: Fn() -> T + Sync,
+    F: Fn(&mut T, B256, Bytecode) + Sync,
+{
+    code_hash_partitions(rayon::current_num_threads())
+        .into_par_iter()
+        .map(|range| -> eyre::Result<T> {
+            let provider = factory.provider()?;
+            let mut cursor = provider.tx_ref().cursor_read::<tables::Bytecodes>()?;
+            let mut acc = init();
+            for entry in cursor.walk_range(range)? {
+                let (code_hash, bytecode) = entry?;
+                f(&mut acc, code_hash, bytecode);
+            }
+            Ok(acc)
+        })
+        .collect()
+}
+
+/// Splits the code hashes into `count` ranges of equal size, by the first 8 bytes of the hash.
+fn code_hash_partitions(count: usize) -> Vec<(Bound<B256>, Bound<B256>)> {
+    let count = count.max(1) as u128;
+    let boundary = |i: u128| {
+        let mut hash = B256::ZERO;
+        hash[..8].copy_from_slice(&(((i << 64) / count) as u64).to_be_bytes());
+        hash
+    };
+    (0..count)
+        .map(|i| {
+            let start = if i == 0 { Bound::Unbounded } else { Bound::Included(boundary(i)) };
+            let end =
+                if i == count - 1 { Bound::Unbounded } else { Bound::Excluded(boundary(i + 1)) };
+            (start, end)
+        })
+        .collect()
+}
+
+/// A deployed contract and the number of occurrences of an opcode in its code.
+#[derive(Debug)]
+struct ContractCount {
+    code_hash: B256,
+    code_size: usize,
+    count: u64,
+    /// The addresses the code is deployed at, if resolved.
+    addresses: Vec<Address>,
+}
+
+/// Returns the `top` contracts of the `Bytecodes` table with the most occurrences of the opcode,
+/// sorted by the number of occurrences.
+fn top_contracts(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    opcode: u8,
+    top: usize,
+    resolve_addresses: bool,
+) -> eyre::Result<Vec<ContractCount>> {
+    info!(target: "reth::cli", opcode = %opcode_name(opcode), top, "Counting opcode per contract");
+
+    // min-heaps of the `top` contracts with the most occurrences found so far
+    let push = |heap: &mut BinaryHeap<_>, contract| {
+        heap.push(contract);
+        if heap.len() > top {
+            heap.pop();
+        }
+    };
+    let heaps = scan_bytecodes(
+        factory,
+        || BinaryHeap::with_capacity(top + 1),
+        |heap, code_hash, bytecode| {
+            let code = bytecode.original_bytes();
+            let mut count = 0;
+            visit_opcodes(&code, |op| count += (op == opcode) as u64);
+            if count > 0 {
+                push(heap, Reverse((count, code_hash, code.len())));
+            }
+        },
+    )?;
+    let mut heap = BinaryHeap::with_capacity(top + 1);
+    for contract in heaps.into_iter().flatten() {
+        push(&mut heap, contract);
+    }
+
+    let mut contracts = heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse((count, code_hash, code_size))| ContractCount {
+            code_hash,
+            code_size,
+            count,
+            addresses: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+
+    if resolve_addresses && !contracts.is_empty() {
+        info!(target: "reth::cli", "Resolving contract addresses");
+        let provider = factory.provider()?;
+        let mut by_hash = contracts
+            .iter_mut()
+            .map(|contract| (contract.code_hash, &mut contract.addresses))
+            .collect::<HashMap<_, _>>();
+        let mut cursor = provider.tx_ref().cursor_read::<tables::PlainAccountState>()?;
+        for entry in cursor.walk(None)? {
+            let (address, account) = entry?;
+            if let Some(addresses) = account.bytecode_hash.and_then(|hash| by_hash.get_mut(&hash)) {
+                addresses.push(address);
+            }
+        }
+    }
+
+    Ok(contracts)
+}
+
+/// The format of a deployed code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CodeKind {
+    Legacy,
+    Eof,
+    MalformedEof,
+}
+
+/// Calls `f` with every opcode of the code and returns the format of the code.
+///
+/// The opcodes of legacy code are walked skipping the data of `PUSH` instructions. For EOF
+/// containers only the opcodes of the code sections are visited, skipping the immediates.
+pub(crate) fn visit_opcodes(code: &[u8], mut f: impl FnMut(u8)) -> CodeKind {
+    if !eof::is_eof(code) {
+        opcodes(code).for_each(f);
+        return CodeKind::Legacy
+    }
+    match eof::EofContainer::decode(code) {
+        Some(container) => {
+            container.for_each_opcode(&mut f);
+            CodeKind::Eof
+        }
+        None => CodeKind::MalformedEof,
+    }
+}
+
+/// Returns the opcodes of legacy code, skipping the data of `PUSH` instructions.
+fn opcodes(code: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    instructions(code).map(|instruction| instruction.opcode)
+}
+
+/// A 4-byte function selector.
+type Selector = [u8; 4];
+
+/// Counts the number of contracts in the `Bytecodes` table dispatching each selector, see
+/// [dispatched_selectors].
+fn count_selectors(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+) -> eyre::Result<HashMap<Selector, u64>> {
+    info!(target: "reth::cli", "Counting selectors of deployed contracts");
+    let partitions = scan_bytecodes(factory, HashMap::new, |counts, _, bytecode| {
+        for selector in dispatched_selectors(&bytecode.original_bytes()) {
+            *counts.entry(selector).or_default() += 1;
+        }
+    })?;
+
+    let mut counts = HashMap::<Selector, u64>::new();
+    for (selector, count) in partitions.into_iter().flatten() {
+        *counts.entry(selector).or_default() += count;
+    }
+    Ok(counts)
+}
+
+/// Returns the selectors of the function dispatcher of legacy code, recognized by the pattern
+/// `PUSH4 <selector> [DUPn] EQ PUSHn <destination> JUMPI`.
+fn dispatched_selectors(code: &[u8]) -> HashSet<Selector> {
+    let instructions = instructions(code).collect::<Vec<_>>();
+    let mut selectors = HashSet::new();
+    for (i, instruction) in instructions.iter().enumerate() {
+        if instruction.opcode != PUSH4 {
+            continue
+        }
+
+        let mut next =
+            instructions[i + 1..].iter().map(|instruction| instruction.opcode).peekable();
+        next.next_if(|opcode| (DUP1..=DUP16).contains(opcode));
+        if next.next() == Some(EQ) &&
+            next.next().map_or(false, |opcode| (PUSH1..=PUSH32).contains(&opcode)) &&
+            next.next() == Some(JUMPI)
+        {
+            if let Ok(selector) = Selector::try_from(instruction.data) {
+                selectors.insert(selector);
+            }
+        }
+    }
+    selectors
+}
+
+/// Reads a file of text signatures, returning the signatures by their selector.
+fn read_signatures(path: &Path) -> eyre::Result<HashMap<Selector, Vec<String>>> {
+    let file = fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read signatures from {}", path.display()))?;
+
+    let mut signatures = HashMap::<Selector, Vec<String>>::new();
+    for line in file.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let (selector, signature) = match line.split_once(char::is_whitespace) {
+            Some((selector, signature)) => {
+                let selector = hex::decode(selector)
+                    .ok()
+                    .and_then(|selector| Selector::try_from(selector).ok())
+                    .ok_or_else(|| eyre!("Invalid selector in {}: {line}", path.display()))?;
+                (selector, signature.trim())
+            }
+            None => {
+                let hash = keccak256(line);
+                ([hash[0], hash[1], hash[2], hash[3]], line)
+            }
+        };
+        signatures.entry(selector).or_default().push(signature.to_string());
+    }
+    Ok(signatures)
+}
+
+/// Re-executes the transactions of the blocks in the range and counts the executed opcodes.
+///
+/// `on_batch` is called with the last executed block and the counts so far after every batch of
+/// blocks.
+fn count_executed_opcodes(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    from: BlockNumber,
+    to: BlockNumber,
+    mut on_batch: impl FnMut(BlockNumber, &OpcodeCounts),
+) -> eyre::Result<OpcodeCounts> {
+    info!(target: "reth::cli", from, to, "Counting opcodes of executed transactions");
+    let counter = replay_blocks(
+        factory,
+        from,
+        to,
+        OpcodeCounter::default,
+        |a, b| OpcodeCounter { counts: add_counts(a.counts, &b.counts) },
+        |block, counter| on_batch(block, &counter.counts),
+    )?;
+    Ok(counter.counts)
+}
+
+/// The database the blocks are re-executed on, on top of the state of their parent.
+type ReplayDb = CacheDB<StateProviderDatabase<StateProviderBox>>;
+
+/// Re-executes the transactions of the blocks in the range in parallel, in batches of
+/// [BATCH_SIZE] blocks.
+///
+/// The transactions of every block are inspected by their own inspector, created with `init`, and
+/// the inspectors of all blocks are merged with `merge`. `on_batch` is called with the last
+/// executed block and the merged inspector after every batch.
+fn replay_blocks<I>(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    from: BlockNumber,
+    to: BlockNumber,
+    init: impl Fn() -> I + Sync + Send,
+    merge: impl Fn(I, I) -> I + Sync + Send,
+    mut on_batch: impl FnMut(BlockNumber, &I),
+) -> eyre::Result<I>
+where
+    I: for<'a> Inspector<&'a mut ReplayDb> + Send,
+{
+    let evm_config = EthEvmConfig::new(&factory.chain_spec())?;
+
+    let mut acc = init();
+    let mut batch_start = from;
+    while batch_start <= to {
+        let batch_end = (batch_start + BATCH_SIZE - 1).min(to);
+        let batch = (batch_start..=batch_end)
+            .into_par_iter()
+            .map(|number| -> eyre::Result<I> {
+                let mut inspector = init();
+                replay_block(factory, &evm_config, number, &mut inspector)?;
+                Ok(inspector)
+            })
+            .try_reduce(&init, |a, b| Ok(merge(a, b)))?;
+        acc = merge(acc, batch);
+        on_batch(batch_end, &acc);
+
+        info!(target: "reth::cli", block = batch_end, "Executed blocks");
+        batch_start = batch_end + 1;
+    }
+    Ok(acc)
+}
+
+/// Re-executes the block on top of the state of its parent, inspecting its transactions.
+fn replay_block<I>(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    evm_config: &EthEvmConfig,
+    number: BlockNumber,
+    inspector: &mut I,
+) -> eyre::Result<()>
+where
+    I: for<'a> Inspector<&'a mut ReplayDb>,
+{
+    let provider = factory.provider()?;
+    let block = provider
+        .block_with_senders(number.into(), TransactionVariant::WithHash)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+    if block.body.is_empty() {
+        return Ok(())
+    }
+
+    let (cfg, block_env) = provider.env_with_header(&block.header, evm_config.clone())?;
+    let state = factory.history_by_block_number(number - 1)?;
+    let mut db: ReplayDb = CacheDB::new(StateProviderDatabase::new(state));
+
+    // apply the EIP-4788 pre block contract call, it is not inspected
+    let env =
+        EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), Default::default());
+    let mut evm = evm_config.evm_with_env(&mut db, env);
+    apply_beacon_root_contract_call(
+        &factory.chain_spec(),
+        block.timestamp,
+        number,
+        block.parent_beacon_block_root,
+        &mut evm,
+    )?;
+    drop(evm);
+
+    for tx in block.into_transactions_ecrecovered() {
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            cfg.clone(),
+            block_env.clone(),
+            tx_env_with_recovered(&tx),
+        );
+        let mut evm = evm_config.evm_with_env_and_inspector(&mut db, env, &mut *inspector);
+        let ResultAndState { state, .. } =
+            evm.transact().map_err(|err| eyre!("failed to execute transaction: {err}"))?;
+        drop(evm);
+        db.commit(state);
+    }
+
+    Ok(())
+}
+
+fn add_counts(mut a: OpcodeCounts, b: &OpcodeCounts) -> OpcodeCounts {
+    for (a, b) in a.iter_mut().zip(b) {
+        *a += b;
+    }
+    a
+}
+
+/// An inspector counting the executed opcodes.
+#[derive(Debug)]
+struct OpcodeCounter {
+    counts: OpcodeCounts,
+}
+
+impl Default for OpcodeCounter {
+    fn default() -> Self {
+        Self { counts: [0; 256] }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for OpcodeCounter {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        self.counts[interp.current_opcode() as usize] += 1;
+    }
+}
+
+/// Describes the opcode count metrics, after the recorder is installed.
+fn describe_metrics() {
+    describe_gauge!("count_opcodes.deployed", "The opcode counts of the deployed contracts");
+    describe_gauge!(
+        "count_opcodes.deployed_weighted",
+        "The opcode counts of the deployed contracts weighted by the accounts they are deployed at"
+    );
+    describe_gauge!("count_opcodes.contracts", "The number of deployed contracts by code format");
+    describe_gauge!("count_opcodes.executed", "The opcode counts of the re-executed transactions");
+    describe_gauge!("count_opcodes.executed_block", "The last re-executed block");
+}
+
+/// Records the opcode counts of the deployed contracts as gauges, labeled by opcode.
+fn record_deployed_metrics(deployed: &DeployedCounts, weighted: bool) {
+    for opcode in (0..=u8::MAX).filter(|opcode| deployed.counts[*opcode as usize] > 0) {
+        let name = opcode_name(opcode);
+        let (count, weighted_count) =
+            (deployed.counts[opcode as usize], deployed.weighted[opcode as usize]);
+        gauge!("count_opcodes.deployed", count as f64, "opcode" => name.clone());
+        if weighted {
+            gauge!("count_opcodes.deployed_weighted", weighted_count as f64, "opcode" => name);
+        }
+    }
+    for (kind, contracts) in [
+        ("legacy", deployed.legacy),
+        ("eof", deployed.eof),
+        ("malformed_eof", deployed.malformed_eof),
+    ] {
+        gauge!("count_opcodes.contracts", contracts as f64, "kind" => kind);
+    }
+}
+
+/// Records the opcode counts of the re-executed transactions up to the block as gauges, labeled
+/// by opcode.
+fn record_executed_metrics(block: BlockNumber, counts: &OpcodeCounts) {
+    for opcode in (0..=u8::MAX).filter(|opcode| counts[*opcode as usize] > 0) {
+        let count = counts[opcode as usize] as f64;
+        gauge!("count_opcodes.executed", count, "opcode" => opcode_name(opcode));
+    }
+    gauge!("count_opcodes.executed_block", block as f64);
+}
+
+/// Prints the found opcodes, sorted by their count.
+///
+/// If given, the counts weighted by accounts are printed as well and the opcodes are sorted by
+/// them instead.
+fn print_counts(counts: &OpcodeCounts, weighted: Option<&OpcodeCounts>) {
+    let share = |count: u64, total: u64| count as f64 * 100.0 / total as f64;
+    let total = counts.iter().sum::<u64>();
+    let mut opcodes =
+        (0..=u8::MAX).filter(|opcode| counts[*opcode as usize] > 0).collect::<Vec<_>>();
+    let sort_by = weighted.unwrap_or(counts);
+    opcodes.sort_unstable_by_key(|opcode| (Reverse(sort_by[*opcode as usize]), *opcode));
+
+    match weighted {
+        None => {
+            println!("{:<16} {:>16} {:>8}", "OPCODE", "COUNT", "SHARE");
+            for opcode in opcodes {
+                let count = counts[opcode as usize];
+                println!("{:<16} {count:>16} {:>7.3}%", opcode_name(opcode), share(count, total));
+            }
+            println!("{:<16} {total:>16}", "TOTAL");
+        }
+        Some(weighted) => {
+            let weighted_total = weighted.iter().sum::<u64>();
+            println!(
+                "{:<16} {:>16} {:>8} {:>16} {:>8}",
+                "OPCODE", "UNIQUE", "SHARE", "WEIGHTED", "SHARE"
+            );
+            for opcode in opcodes {
+                let (count, weighted) = (counts[opcode as usize], weighted[opcode as usize]);
+                println!(
+                    "{:<16} {count:>16} {:>7.3}% {weighted:>16} {:>7.3}%",
+                    opcode_name(opcode),
+                    share(count, total),
+                    share(weighted, weighted_total)
+                );
+            }
+            println!("{:<16} {total:>16} {:>8} {weighted_total:>16}", "TOTAL", "");
+        }
+    }
+}
+
+/// Prints the contracts with the most occurrences of the opcode.
+fn print_contracts(opcode: u8, contracts: &[ContractCount]) {
+    println!(
+        "{:<4} {:<66} {:>10} {:>8} ADDRESSES",
+        "RANK",
+        "CODE HASH",
+        opcode_name(opcode),
+        "SIZE"
+    );
+    for (rank, contract) in contracts.iter().enumerate() {
+        let addresses =
+            contract.addresses.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+        println!(
+            "{:<4} {:<66} {:>10} {:>8} {addresses}",
+            rank + 1,
+            contract.code_hash.to_string(),
+            contract.count,
+            contract.code_size
+        );
+    }
+}
+
+/// Prints the `top` selectors dispatched by the most contracts.
+fn print_selectors(
+    counts: &HashMap<Selector, u64>,
+    signatures: Option<&HashMap<Selector, Vec<String>>>,
+    top: usize,
+) {
+    let mut selectors = counts.iter().collect::<Vec<_>>();
+    selectors.sort_unstable_by_key(|(selector, count)| (Reverse(**count), **selector));
+
+    println!("{:<4} {:<10} {:>10} SIGNATURES", "RANK", "SELECTOR", "CONTRACTS");
+    for (rank, (selector, count)) in selectors.into_iter().take(top).enumerate() {
+        let signatures = signatures
+            .and_then(|signatures| signatures.get(selector))
+            .map(|signatures| signatures.join(", "))
+            .unwrap_or_default();
+        println!("{:<4} {:<10} {count:>10} {signatures}", rank + 1, hex::encode_prefixed(selector));
+    }
+}
+
+/// Returns the name of the opcode, or its value if it is not defined.
+pub(crate) fn opcode_name(opcode: u8) -> String {
+    match OpCode::new(opcode) {
+        Some(opcode) => opcode.as_str().to_string(),
+        None => format!("0x{opcode:02x}"),
+    }
+}
+
+/// Parses an opcode from its name, case-insensitive, or from its hex value.
+fn parse_opcode(value: &str) -> Result<u8, String> {
+    if let Some(hex) = value.strip_prefix("0x") {
+        return u8::from_str_radix(hex, 16).map_err(|err| format!("invalid opcode {value}: {err}"))
+    }
+    (0..=u8::MAX)
+        .find(|opcode| {
+            OpCode::new(*opcode).map_or(false, |op| op.as_str().eq_ignore_ascii_case(value))
+        })
+        .ok_or_else(|| format!("unknown opcode {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_count_opcodes_command_chain_args() {
+        for chain in SUPPORTED_CHAINS {
+            let command = Command::try_parse_from(["reth", "--chain", chain]).unwrap();
+            assert_eq!(command.chain.chain, chain.parse::<reth_primitives::Chain>().unwrap());
+        }
+    }
+
+    #[test]
+    fn parse_block_range() {
+        let command =
+            Command::try_parse_from(["reth", "--from-block", "100", "--to-block", "200"]).unwrap();
+        assert_eq!(command.from_block, Some(100));
+        assert_eq!(command.to_block, Some(200));
+
+        assert!(Command::try_parse_from(["reth", "--to-block", "200"]).is_err());
+    }
+
+    #[test]
+    fn parse_per_contract() {
+        let command = Command::try_parse_from([
+            "reth",
+            "--per-contract",
+            "--opcode",
+            "sstore",
+            "--top",
+            "50",
+        ])
+        .unwrap();
+        assert!(command.per_contract);
+        assert_eq!(command.opcode, Some(0x55));
+        assert_eq!(command.top, 50);
+
+        assert_eq!(parse_opcode("0x55"), Ok(0x55));
+        assert!(parse_opcode("NOTANOPCODE").is_err());
+        assert!(Command::try_parse_from(["reth", "--per-contract"]).is_err());
+    }
+
+    #[test]
+    fn parse_weight_by_accounts() {
+        let command = Command::try_parse_from(["reth", "--weight-by-accounts"]).unwrap();
+        assert!(command.weight_by_accounts);
+
+        assert!(
+            Command::try_parse_from(["reth", "--weight-by-accounts", "--from-block", "1"]).is_err()
+        );
+    }
+
+    #[test]
+    fn parse_storage_slots() {
+        let command =
+            Command::try_parse_from(["reth", "--from-block", "1", "--storage-slots"]).unwrap();
+        assert!(command.storage_slots);
+
+        assert!(Command::try_parse_from(["reth", "--storage-slots"]).is_err());
+    }
+
+    #[test]
+    fn parse_call_targets() {
+        let command = Command::try_parse_from(["reth", "--call-targets"]).unwrap();
+        assert!(command.call_targets);
+        let command =
+            Command::try_parse_from(["reth", "--call-targets", "--from-block", "1"]).unwrap();
+        assert!(command.call_targets);
+
+        assert!(Command::try_parse_from(["reth", "--call-targets", "--selectors"]).is_err());
+    }
+
+    #[test]
+    fn parse_cfg_stats() {
+        let command = Command::try_parse_from(["reth", "--cfg-stats"]).unwrap();
+        assert!(command.cfg_stats);
+
+        assert!(Command::try_parse_from(["reth", "--cfg-stats", "--from-block", "1"]).is_err());
+    }
+
+    #[test]
+    fn parse_metrics() {
+        let command = Command::try_parse_from(["reth", "--metrics", "127.0.0.1:9001"]).unwrap();
+        assert_eq!(command.metrics, Some("127.0.0.1:9001".parse().unwrap()));
+
+        assert!(Command::try_parse_from(["reth", "--metrics", "127.0.0.1:9001", "--selectors"])
+            .is_err());
+    }
+
+    #[test]
+    fn code_hash_partitions_cover_all_hashes() {
+        assert_eq!(code_hash_partitions(1), vec![(Bound::Unbounded, Bound::Unbounded)]);
+
+        let partitions = code_hash_partitions(3);
+        assert_eq!(partitions.len(), 3);
+        assert_eq!(partitions[0].0, Bound::Unbounded);
+        assert_eq!(partitions[2].1, Bound::Unbounded);
+        for window in partitions.windows(2) {
+            let (Bound::Excluded(end), Bound::Included(start)) = (window[0].1, window[1].0) else {
+                panic!("partitions are not adjacent: {window:?}")
+            };
+            assert_eq!(end, start);
+        }
+    }
+
+    #[test]
+    fn visit_legacy_and_eof() {
+        let mut opcodes = Vec::new();
+        assert_eq!(visit_opcodes(&[0x60, 0xEF, 0x00], |op| opcodes.push(op)), CodeKind::Legacy);
+        assert_eq!(opcodes, vec![0x60, 0x00]);
+
+        // header bytes of malformed containers are not visited
+        assert_eq!(visit_opcodes(&[0xEF, 0x00, 0x01], |_| unreachable!()), CodeKind::MalformedEof);
+    }
+
+    #[test]
+    fn dispatched_selectors_pattern() {
+        // DUP1 PUSH4 0xa9059cbb EQ PUSH2 0x0042 JUMPI
+        let dispatch = [0x80, 0x63, 0xA9, 0x05, 0x9C, 0xBB, 0x14, 0x61, 0x00, 0x42, 0x57];
+        // PUSH4 0x70a08231 DUP2 EQ PUSH1 0x10 JUMPI
+        let dispatch_dup = [0x63, 0x70, 0xA0, 0x82, 0x31, 0x81, 0x14, 0x60, 0x10, 0x57];
+        // PUSH4 0x18160ddd GT PUSH1 0x10 JUMPI
+        let binary_search = [0x63, 0x18, 0x16, 0x0D, 0xDD, 0x11, 0x60, 0x10, 0x57];
+
+        let code = [&dispatch[..], &dispatch_dup, &binary_search].concat();
+        assert_eq!(
+            dispatched_selectors(&code),
+            HashSet::from([[0xA9, 0x05, 0x9C, 0xBB], [0x70, 0xA0, 0x82, 0x31]])
+        );
+    }
+
+    #[test]
+    fn read_signatures_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("signatures.txt");
+        fs::write(&path, "transfer(address,uint256)\n\n0x70a08231 balanceOf(address)\n").unwrap();
+
+        let signatures = read_signatures(&path).unwrap();
+        assert_eq!(signatures[&[0xA9, 0x05, 0x9C, 0xBB]], vec!["transfer(address,uint256)"]);
+        assert_eq!(signatures[&[0x70, 0xA0, 0x82, 0x31]], vec!["balanceOf(address)"]);
+    }
+
+    proptest! {
+        #[test]
+        fn visit_malformed_code(code in proptest::collection::vec(any::<u8>(), 0..512)) {
+            let mut count = 0;
+            let kind = visit_opcodes(&code, |_| count += 1);
+            prop_assert!(count <= code.len());
+            prop_assert_eq!(kind == CodeKind::Legacy, !code.starts_with(&[0xEF, 0x00]));
+            dispatched_selectors(&code);
+        }
+
+        #[test]
+        fn visit_malformed_eof(body in proptest::collection::vec(any::<u8>(), 0..512)) {
+            let code = [&[0xEF, 0x00, 0x01][..], &body].concat();
+            let mut count = 0;
+            let kind = visit_opcodes(&code, |_| count += 1);
+            prop_assert_ne!(kind, CodeKind::Legacy);
+            prop_assert!(count <= code.len());
+        }
+    }
+
+    #[test]
+    fn skip_push_data() {
+        // PUSH2 0x5555, SSTORE, PUSH1 0x60, STOP
+        let code = [0x61, 0x55, 0x55, 0x55, 0x60, 0x60, 0x00];
+        assert_eq!(opcodes(&code).collect::<Vec<_>>(), vec![0x61, 0x55, 0x60, 0x00]);
+    }
+}
@@ -0,0 +1,241 @@
+//! Storage slots accessed by the re-executed transactions.
+
+use super::replay_blocks;
+use reth_db::DatabaseEnv;
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_provider::ProviderFactory;
+use reth_revm::{
+    interpreter::{
+        opcode::{SLOAD, SSTORE},
+        Interpreter,
+    },
+    Database, EvmContext, Inspector,
+};
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tracing::info;
+
+/// The number of times a storage slot was loaded and stored.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SlotAccesses {
+    loads: u64,
+    stores: u64,
+}
+
+impl SlotAccesses {
+    fn total(&self) -> u64 {
+        self.loads + self.stores
+    }
+
+    fn add(&mut self, other: Self) {
+        self.loads += other.loads;
+        self.stores += other.stores;
+    }
+}
+
+/// An inspector counting the `SLOAD`s and `SSTORE`s per storage slot, keyed by the address of the
+/// account owning the storage and the slot.
+#[derive(Debug, Default)]
+pub(super) struct SlotCounter {
+    slots: HashMap<(Address, B256), SlotAccesses>,
+}
+
+impl SlotCounter {
+    fn merge(mut self, mut other: Self) -> Self {
+        if self.slots.len() < other.slots.len() {
+            std::mem::swap(&mut self, &mut other);
+        }
+        for (key, accesses) in other.slots {
+            self.slots.entry(key).or_default().add(accesses);
+        }
+        self
+    }
+}
+
+impl<DB: Database> Inspector<DB> for SlotCounter {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let opcode = interp.current_opcode();
+        if opcode != SLOAD && opcode != SSTORE {
+            return
+        }
+        // the slot is on top of the stack, a stack underflow is reported by the instruction
+        let Ok(slot) = interp.stack().peek(0) else { return };
+
+        // the storage of delegate calls is the storage of the calling contract
+        let accesses = self.slots.entry((interp.contract.address, B256::from(slot))).or_default();
+        if opcode == SLOAD {
+            accesses.loads += 1;
+        } else {
+            accesses.stores += 1;
+        }
+    }
+}
+
+/// Re-executes the transactions of the blocks in the range and counts the accesses per storage
+/// slot.
+pub(super) fn count_storage_slots(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    from: BlockNumber,
+    to: BlockNumber,
+) -> eyre::Result<SlotCounter> {
+    info!(target: "reth::cli", from, to, "Counting storage slots of executed transactions");
+    replay_blocks(factory, from, to, SlotCounter::default, SlotCounter::merge, |_, _| {})
+}
+
+/// The storage accesses of a contract.
+#[derive(Debug, PartialEq, Eq)]
+struct ContractAccesses {
+    address: Address,
+    /// The number of distinct slots accessed.
+    slots: u64,
+    accesses: SlotAccesses,
+}
+
+/// Returns the `top` slots with the most accesses.
+fn hot_slots(counter: &SlotCounter, top: usize) -> Vec<(&(Address, B256), &SlotAccesses)> {
+    let mut slots = counter.slots.iter().collect::<Vec<_>>();
+    slots.sort_unstable_by_key(|(key, accesses)| (Reverse(accesses.total()), **key));
+    slots.truncate(top);
+    slots
+}
+
+/// Returns the `top` contracts with the most storage accesses.
+fn hot_contracts(counter: &SlotCounter, top: usize) -> Vec<ContractAccesses> {
+    let mut by_address = HashMap::<Address, (u64, SlotAccesses)>::new();
+    for ((address, _), accesses) in &counter.slots {
+        let (slots, total) = by_address.entry(*address).or_default();
+        *slots += 1;
+        total.add(*accesses);
+    }
+
+    let mut contracts = by_address
+        .into_iter()
+        .map(|(address, (slots, accesses))| ContractAccesses { address, slots, accesses })
+        .collect::<Vec<_>>();
+    contracts
+        .sort_unstable_by_key(|contract| (Reverse(contract.accesses.total()), contract.address));
+    contracts.truncate(top);
+    contracts
+}
+
+/// Prints the `top` slots and contracts with the most storage accesses.
+pub(super) fn print_storage_slots(counter: &SlotCounter, top: usize) {
+    println!("{:<4} {:<42} {:<66} {:>12} {:>12}", "RANK", "ADDRESS", "SLOT", "SLOADS", "SSTORES");
+    for (rank, ((address, slot), accesses)) in hot_slots(counter, top).into_iter().enumerate() {
+        println!(
+            "{:<4} {:<42} {:<66} {:>12} {:>12}",
+            rank + 1,
+            address.to_string(),
+            slot.to_string(),
+            accesses.loads,
+            accesses.stores
+        );
+    }
+
+    println!();
+    println!("{:<4} {:<42} {:>12} {:>12} {:>12}", "RANK", "ADDRESS", "SLOTS", "SLOADS", "SSTORES");
+    for (rank, contract) in hot_contracts(counter, top).into_iter().enumerate() {
+        println!(
+            "{:<4} {:<42} {:>12} {:>12} {:>12}",
+            rank + 1,
+            contract.address.to_string(),
+            contract.slots,
+            contract.accesses.loads,
+            contract.accesses.stores
+        );
+    }
+
+    let mut total = SlotAccesses::default();
+    counter.slots.values().for_each(|accesses| total.add(*accesses));
+    let contracts = counter.slots.keys().map(|(address, _)| address).collect::<HashSet<_>>();
+    println!();
+    println!(
+        "{} slots of {} contracts, {} SLOADs, {} SSTOREs",
+        counter.slots.len(),
+        contracts.len(),
+        total.loads,
+        total.stores
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_api::ConfigureEvm;
+    use reth_node_ethereum::EthEvmConfig;
+    use reth_primitives::{Bytes, U256};
+    use reth_revm::{
+        db::{CacheDB, EmptyDB},
+        primitives::{AccountInfo, Bytecode, Env, EnvWithHandlerCfg, SpecId, TransactTo},
+    };
+
+    fn slot(n: u64) -> B256 {
+        B256::from(U256::from(n))
+    }
+
+    #[test]
+    fn count_slot_accesses() {
+        let contract = Address::with_last_byte(0xAA);
+        let mut db = CacheDB::new(EmptyDB::default());
+        // PUSH1 1 PUSH1 0 SSTORE PUSH1 0 SLOAD PUSH1 2 SLOAD STOP
+        let code = Bytes::from_static(&[
+            0x60, 0x01, 0x60, 0x00, 0x55, 0x60, 0x00, 0x54, 0x60, 0x02, 0x54, 0x00,
+        ]);
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+
+        let mut env = Env::default();
+        env.tx.transact_to = TransactTo::Call(contract);
+        env.tx.gas_limit = 1_000_000;
+        let env = EnvWithHandlerCfg::new_with_spec_id(Box::new(env), SpecId::CANCUN);
+
+        let mut counter = SlotCounter::default();
+        let mut evm = EthEvmConfig::default().evm_with_env_and_inspector(db, env, &mut counter);
+        assert!(evm.transact().unwrap().result.is_success());
+        drop(evm);
+
+        assert_eq!(counter.slots.len(), 2);
+        assert_eq!(counter.slots[&(contract, slot(0))], SlotAccesses { loads: 1, stores: 1 });
+        assert_eq!(counter.slots[&(contract, slot(2))], SlotAccesses { loads: 1, stores: 0 });
+    }
+
+    #[test]
+    fn merge_and_rank() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let counter = |slots: &[((Address, B256), SlotAccesses)]| SlotCounter {
+            slots: slots.iter().copied().collect(),
+        };
+        let merged =
+            counter(&[((a, slot(0)), SlotAccesses { loads: 2, stores: 0 })]).merge(counter(&[
+                ((a, slot(0)), SlotAccesses { loads: 1, stores: 1 }),
+                ((a, slot(1)), SlotAccesses { loads: 1, stores: 0 }),
+                ((b, slot(0)), SlotAccesses { loads: 3, stores: 0 }),
+            ]));
+
+        let slots = hot_slots(&merged, 2);
+        assert_eq!(slots[0], (&(a, slot(0)), &SlotAccesses { loads: 3, stores: 1 }));
+        assert_eq!(slots[1], (&(b, slot(0)), &SlotAccesses { loads: 3, stores: 0 }));
+
+        let contracts = hot_contracts(&merged, 10);
+        assert_eq!(
+            contracts,
+            [
+                ContractAccesses {
+                    address: a,
+                    slots: 2,
+                    accesses: SlotAccesses { loads: 4, stores: 1 }
+                },
+                ContractAccesses {
+                    address: b,
+                    slots: 1,
+                    accesses: SlotAccesses { loads: 3, stores: 0 }
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,253 @@
+//! Calls to precompiles and system contracts.
+
+use super::{instructions, replay_blocks, scan_bytecodes};
+use reth_db::DatabaseEnv;
+use reth_primitives::{address, constants::BEACON_ROOTS_ADDRESS, Address, BlockNumber};
+use reth_provider::ProviderFactory;
+use reth_revm::{
+    interpreter::{
+        opcode::{CALL, CALLCODE, DELEGATECALL, GAS, PUSH1, PUSH32, STATICCALL},
+        CallInputs, CallOutcome, CallScheme,
+    },
+    Database, EvmContext, Inspector,
+};
+use std::{collections::BTreeSet, sync::Arc};
+use tracing::info;
+
+/// The address of the history storage contract defined in EIP-2935.
+const HISTORY_STORAGE_ADDRESS: Address = address!("25a219378dad9b3503c8268c9ca836a52427a4fb");
+
+/// The precompiles of the Ethereum mainnet and the system contracts, by name.
+pub(crate) const CALL_TARGETS: [(&str, Address); 12] = [
+    ("ecrecover", Address::with_last_byte(0x01)),
+    ("sha256", Address::with_last_byte(0x02)),
+    ("ripemd160", Address::with_last_byte(0x03)),
+    ("identity", Address::with_last_byte(0x04)),
+    ("modexp", Address::with_last_byte(0x05)),
+    ("ecadd", Address::with_last_byte(0x06)),
+    ("ecmul", Address::with_last_byte(0x07)),
+    ("ecpairing", Address::with_last_byte(0x08)),
+    ("blake2f", Address::with_last_byte(0x09)),
+    ("point evaluation", Address::with_last_byte(0x0a)),
+    ("beacon roots", BEACON_ROOTS_ADDRESS),
+    ("history storage", HISTORY_STORAGE_ADDRESS),
+];
+
+/// Returns the index of the address in [CALL_TARGETS].
+fn call_target_index(address: &Address) -> Option<usize> {
+    CALL_TARGETS.iter().position(|(_, target)| target == address)
+}
+
+/// Calls `f` with the index in [CALL_TARGETS] of every call site of legacy code calling a
+/// precompile or system contract.
+///
+/// EOF code can only call with `EXTCALL` and is skipped. A call site is recognized by the address
+/// of the target pushed right before the call, optionally followed by `GAS`: `PUSHn <address>
+/// [GAS] CALL`, which is how the Solidity and Vyper compilers call precompiles with a constant
+/// address.
+fn visit_call_targets(code: &[u8], mut f: impl FnMut(usize)) {
+    // no legacy code starting with 0xEF can be deployed since EIP-3541
+    if code.first() == Some(&0xEF) {
+        return
+    }
+    let instructions = instructions(code).collect::<Vec<_>>();
+
+    for (i, instruction) in instructions.iter().enumerate() {
+        if !(PUSH1..=PUSH32).contains(&instruction.opcode) {
+            continue
+        }
+        let Some(index) = pushed_address(instruction.data).as_ref().and_then(call_target_index)
+        else {
+            continue
+        };
+
+        let mut next =
+            instructions[i + 1..].iter().map(|instruction| instruction.opcode).peekable();
+        next.next_if_eq(&GAS);
+        if next
+            .next()
+            .map_or(false, |opcode| matches!(opcode, CALL | CALLCODE | DELEGATECALL | STATICCALL))
+        {
+            f(index);
+        }
+    }
+}
+
+/// Returns the address of the pushed value, if it fits into an address.
+fn pushed_address(data: &[u8]) -> Option<Address> {
+    let (zeros, address) = data.split_at(data.len().saturating_sub(20));
+    if !zeros.iter().all(|byte| *byte == 0) {
+        return None
+    }
+    let mut padded = [0; 20];
+    padded[20 - address.len()..].copy_from_slice(address);
+    Some(Address::from(padded))
+}
+
+/// The call sites of the deployed contracts per call target, by index in [CALL_TARGETS].
+#[derive(Debug, Default)]
+pub(crate) struct StaticCallTargets {
+    /// The number of call sites.
+    pub(crate) call_sites: [u64; CALL_TARGETS.len()],
+    /// The number of contracts with a call site.
+    pub(crate) contracts: [u64; CALL_TARGETS.len()],
+}
+
+impl StaticCallTargets {
+    /// Counts the call sites of the code, see [visit_call_targets].
+    pub(crate) fn visit(&mut self, code: &[u8]) {
+        let mut called = BTreeSet::new();
+        visit_call_targets(code, |index| {
+            self.call_sites[index] += 1;
+            called.insert(index);
+        });
+        for index in called {
+            self.contracts[index] += 1;
+        }
+    }
+
+    pub(crate) fn merge(mut self, other: Self) -> Self {
+        for (count, other) in self.call_sites.iter_mut().zip(other.call_sites) {
+            *count += other;
+        }
+        for (count, other) in self.contracts.iter_mut().zip(other.contracts) {
+            *count += other;
+        }
+        self
+    }
+}
+
+/// Counts the call sites of precompiles and system contracts of all contracts in the `Bytecodes`
+/// table, see [visit_call_targets].
+pub(super) fn count_static_call_targets(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+) -> eyre::Result<StaticCallTargets> {
+    info!(target: "reth::cli", "Counting call targets of deployed contracts");
+    let partitions =
+        scan_bytecodes(factory, StaticCallTargets::default, |targets, _, bytecode| {
+            targets.visit(&bytecode.original_bytes())
+        })?;
+    Ok(partitions.into_iter().fold(StaticCallTargets::default(), StaticCallTargets::merge))
+}
+
+/// An inspector counting the calls to precompiles and system contracts by call target and call
+/// scheme: `CALL`, `CALLCODE`, `DELEGATECALL` and `STATICCALL`.
+#[derive(Debug, Default)]
+pub(super) struct CallTargetCounter {
+    calls: [[u64; 4]; CALL_TARGETS.len()],
+}
+
+impl CallTargetCounter {
+    fn merge(mut self, other: Self) -> Self {
+        for (calls, other) in self.calls.iter_mut().zip(other.calls) {
+            for (count, other) in calls.iter_mut().zip(other) {
+                *count += other;
+            }
+        }
+        self
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CallTargetCounter {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if let Some(index) = call_target_index(&inputs.contract) {
+            let scheme = match inputs.context.scheme {
+                CallScheme::Call => 0,
+                CallScheme::CallCode => 1,
+                CallScheme::DelegateCall => 2,
+                CallScheme::StaticCall => 3,
+            };
+            self.calls[index][scheme] += 1;
+        }
+        None
+    }
+}
+
+/// Re-executes the transactions of the blocks in the range and counts the calls to precompiles
+/// and system contracts.
+pub(super) fn count_executed_call_targets(
+    factory: &ProviderFactory<Arc<DatabaseEnv>>,
+    from: BlockNumber,
+    to: BlockNumber,
+) -> eyre::Result<CallTargetCounter> {
+    info!(target: "reth::cli", from, to, "Counting call targets of executed transactions");
+    replay_blocks(
+        factory,
+        from,
+        to,
+        CallTargetCounter::default,
+        CallTargetCounter::merge,
+        |_, _| {},
+    )
+}
+
+/// Prints the call sites of the precompiles and system contracts.
+pub(super) fn print_static_call_targets(targets: &StaticCallTargets) {
+    println!("{:<16} {:<42} {:>12} {:>12}", "TARGET", "ADDRESS", "CALL SITES", "CONTRACTS");
+    for (index, (name, address)) in CALL_TARGETS.iter().enumerate() {
+        println!(
+            "{name:<16} {:<42} {:>12} {:>12}",
+            address.to_string(),
+            targets.call_sites[index],
+            targets.contracts[index]
+        );
+    }
+}
+
+/// Prints the calls to the precompiles and system contracts by call scheme.
+pub(super) fn print_executed_call_targets(counter: &CallTargetCounter) {
+    println!(
+        "{:<16} {:<42} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "TARGET", "ADDRESS", "CALL", "CALLCODE", "DELEGATECALL", "STATICCALL", "TOTAL"
+    );
+    for (index, (name, address)) in CALL_TARGETS.iter().enumerate() {
+        let [call, callcode, delegatecall, staticcall] = counter.calls[index];
+        println!(
+            "{name:<16} {:<42} {call:>12} {callcode:>12} {delegatecall:>12} {staticcall:>12} \
+             {:>12}",
+            address.to_string(),
+            counter.calls[index].iter().sum::<u64>()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pushed_addresses() {
+        assert_eq!(pushed_address(&[0x01]), Some(Address::with_last_byte(0x01)));
+        assert_eq!(pushed_address(&[0x00, 0x00, 0x02]), Some(Address::with_last_byte(0x02)));
+        assert_eq!(pushed_address(BEACON_ROOTS_ADDRESS.as_slice()), Some(BEACON_ROOTS_ADDRESS));
+
+        let mut word = [0; 32];
+        word[12..].copy_from_slice(BEACON_ROOTS_ADDRESS.as_slice());
+        assert_eq!(pushed_address(&word), Some(BEACON_ROOTS_ADDRESS));
+        word[0] = 1;
+        assert_eq!(pushed_address(&word), None);
+    }
+
+    #[test]
+    fn static_call_targets() {
+        let mut targets = StaticCallTargets::default();
+        // PUSH1 0x01 GAS STATICCALL, PUSH20 <beacon roots> CALL, PUSH1 0x01 ADD
+        let code = [
+            &[0x60, 0x01, 0x5a, 0xfa, 0x73][..],
+            BEACON_ROOTS_ADDRESS.as_slice(),
+            &[0xf1, 0x60, 0x01, 0x01],
+        ]
+        .concat();
+        targets.visit(&code);
+        targets.visit(&code);
+
+        assert_eq!((targets.call_sites[0], targets.contracts[0]), (2, 2));
+        let beacon_roots = call_target_index(&BEACON_ROOTS_ADDRESS).unwrap();
+        assert_eq!((targets.call_sites[beacon_roots], targets.contracts[beacon_roots]), (2, 2));
+        assert_eq!(targets.call_sites.iter().sum::<u64>(), 4);
+    }
+}
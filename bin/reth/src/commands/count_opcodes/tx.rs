@@ -0,0 +1,469 @@
+//! Command that replays a transaction and profiles the opcodes executed by each of its call
+//! frames.
+
+use super::{opcode_name, OpcodeCounts};
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use eyre::eyre;
+use reth_db::{open_db_read_only, DatabaseEnv};
+use reth_interfaces::provider::ProviderError;
+use reth_node_api::ConfigureEvm;
+use reth_node_ethereum::EthEvmConfig;
+use reth_primitives::{revm::env::tx_env_with_recovered, Address, BlockNumber, ChainSpec, TxHash};
+use reth_provider::{
+    BlockReader, ChainSpecProvider, EvmEnvProvider, ProviderFactory, TransactionVariant,
+    TransactionsProvider,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    interpreter::{
+        gas::CALL_STIPEND,
+        opcode::{CALL, CALLCODE, CREATE, CREATE2, DELEGATECALL, STATICCALL},
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, CreateScheme,
+        Interpreter,
+    },
+    primitives::{EnvWithHandlerCfg, ResultAndState},
+    state_change::apply_beacon_root_contract_call,
+    Database, DatabaseCommit, EvmContext, Inspector,
+};
+use std::{cmp::Reverse, collections::BTreeMap, sync::Arc, time::Instant};
+use tracing::info;
+
+/// `reth count-opcodes tx` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// The number of opcodes to report per call frame, by gas spent.
+    #[arg(long, value_name = "N", default_value_t = 10)]
+    top: usize,
+
+    /// The hash of the transaction to replay.
+    #[arg(value_name = "TX_HASH")]
+    tx_hash: TxHash,
+}
+
+impl Command {
+    /// Execute `count-opcodes tx` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = open_db_read_only(&data_dir.db_path(), self.db.database_args())?;
+        let factory = ProviderFactory::new(db, self.chain.clone(), data_dir.static_files_path())?;
+
+        let start = Instant::now();
+        let profile = profile_transaction(&factory, self.tx_hash)?;
+        info!(target: "reth::cli", elapsed = ?start.elapsed(), "Replayed transaction");
+
+        print_profile(self.tx_hash, &profile, self.top);
+        Ok(())
+    }
+}
+
+/// The execution profile of a replayed transaction.
+#[derive(Debug)]
+struct TransactionProfile {
+    block_number: BlockNumber,
+    index: u64,
+    gas_used: u64,
+    success: bool,
+    /// The call frames, in the order they were entered.
+    frames: Vec<Frame>,
+}
+
+/// Re-executes the block of the transaction on top of the state of its parent, up to and
+/// including the transaction, and profiles the call frames of the transaction.
+fn profile_transaction(
+    factory: &ProviderFactory<DatabaseEnv>,
+    tx_hash: TxHash,
+) -> eyre::Result<TransactionProfile> {
+    let provider = factory.provider()?;
+    let (_, meta) = provider
+        .transaction_by_hash_with_meta(tx_hash)?
+        .ok_or_else(|| eyre!("Transaction {tx_hash} not found"))?;
+    let number = meta.block_number;
+    info!(
+        target: "reth::cli",
+        %tx_hash,
+        block = number,
+        index = meta.index,
+        "Replaying transaction"
+    );
+
+    let block = provider
+        .block_with_senders(number.into(), TransactionVariant::WithHash)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+    let evm_config = EthEvmConfig::new(&factory.chain_spec())?;
+    let (cfg, block_env) = provider.env_with_header(&block.header, evm_config.clone())?;
+    let state = factory.history_by_block_number(number - 1)?;
+    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+    // apply the EIP-4788 pre block contract call
+    let env =
+        EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), Default::default());
+    let mut evm = evm_config.evm_with_env(&mut db, env);
+    apply_beacon_root_contract_call(
+        &factory.chain_spec(),
+        block.timestamp,
+        number,
+        block.parent_beacon_block_root,
+        &mut evm,
+    )?;
+    drop(evm);
+
+    // execute the transactions preceding the transaction without profiling them
+    let mut transactions = block.into_transactions_ecrecovered();
+    for tx in transactions.by_ref().take(meta.index as usize) {
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            cfg.clone(),
+            block_env.clone(),
+            tx_env_with_recovered(&tx),
+        );
+        let mut evm = evm_config.evm_with_env(&mut db, env);
+        let ResultAndState { state, .. } =
+            evm.transact().map_err(|err| eyre!("failed to execute transaction: {err}"))?;
+        drop(evm);
+        db.commit(state);
+    }
+
+    let tx = transactions
+        .next()
+        .ok_or_else(|| eyre!("Transaction {tx_hash} not found in block {number}"))?;
+    let env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, tx_env_with_recovered(&tx));
+    let mut profiler = FrameProfiler::default();
+    let mut evm = evm_config.evm_with_env_and_inspector(&mut db, env, &mut profiler);
+    let ResultAndState { result, .. } =
+        evm.transact().map_err(|err| eyre!("failed to execute transaction: {err}"))?;
+    drop(evm);
+
+    Ok(TransactionProfile {
+        block_number: number,
+        index: meta.index,
+        gas_used: result.gas_used(),
+        success: result.is_success(),
+        frames: profiler.frames,
+    })
+}
+
+/// The opcodes executed by a call frame.
+#[derive(Debug)]
+struct Frame {
+    /// The call depth, zero for the frame of the transaction itself.
+    depth: usize,
+    /// The opcode that entered the frame: `CALL`, `CALLCODE`, `DELEGATECALL`, `STATICCALL`,
+    /// `CREATE` or `CREATE2`.
+    kind: u8,
+    /// The address whose code is executed, unknown for failed creates.
+    address: Option<Address>,
+    counts: OpcodeCounts,
+    /// The gas spent per opcode, excluding the gas forwarded to the frames the opcode enters.
+    gas: OpcodeCounts,
+    /// The gas spent by the frame, including the gas spent by the frames it entered.
+    gas_used: u64,
+    success: bool,
+    /// The last executed opcode and the gas remaining before it.
+    current: Option<(u8, u64)>,
+}
+
+impl Frame {
+    fn new(depth: usize, kind: u8, address: Option<Address>) -> Self {
+        Self {
+            depth,
+            kind,
+            address,
+            counts: [0; 256],
+            gas: [0; 256],
+            gas_used: 0,
+            success: false,
+            current: None,
+        }
+    }
+}
+
+/// An inspector recording the executed opcodes and the gas spent by them per call frame.
+#[derive(Debug, Default)]
+struct FrameProfiler {
+    /// All frames, in the order they were entered.
+    frames: Vec<Frame>,
+    /// The indices of the frames that are executing, the innermost last.
+    open: Vec<usize>,
+}
+
+impl FrameProfiler {
+    /// Enters a frame, deducting the gas forwarded to it from the opcode of the calling frame.
+    fn enter(&mut self, kind: u8, address: Option<Address>, forwarded_gas: u64) {
+        if let Some(caller) = self.open.last().map(|index| &mut self.frames[*index]) {
+            if let Some((opcode, _)) = caller.current {
+                let gas = &mut caller.gas[opcode as usize];
+                *gas = gas.saturating_sub(forwarded_gas);
+            }
+        }
+        self.open.push(self.frames.len());
+        self.frames.push(Frame::new(self.open.len() - 1, kind, address));
+    }
+
+    /// Exits the innermost frame.
+    fn exit(&mut self, address: Option<Address>, gas_used: u64, success: bool) {
+        if let Some(frame) = self.open.pop().map(|index| &mut self.frames[index]) {
+            frame.address = frame.address.or(address);
+            frame.gas_used = gas_used;
+            frame.success = success;
+        }
+    }
+
+    fn current_frame(&mut self) -> Option<&mut Frame> {
+        self.open.last().map(|index| &mut self.frames[*index])
+    }
+}
+
+impl<DB: Database> Inspector<DB> for FrameProfiler {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if let Some(frame) = self.current_frame() {
+            let opcode = interp.current_opcode();
+            frame.counts[opcode as usize] += 1;
+            frame.current = Some((opcode, interp.gas.remaining()));
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if let Some(frame) = self.current_frame() {
+            if let Some((opcode, remaining)) = frame.current {
+                frame.gas[opcode as usize] += remaining.saturating_sub(interp.gas.remaining());
+            }
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let kind = match inputs.context.scheme {
+            CallScheme::Call => CALL,
+            CallScheme::CallCode => CALLCODE,
+            CallScheme::DelegateCall => DELEGATECALL,
+            CallScheme::StaticCall => STATICCALL,
+        };
+        // the stipend of value transfers is not paid by the caller
+        let stipend = if inputs.transfer.value.is_zero() { 0 } else { CALL_STIPEND };
+        self.enter(kind, Some(inputs.contract), inputs.gas_limit.saturating_sub(stipend));
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit(None, outcome.result.gas.spent(), outcome.result.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let kind = match inputs.scheme {
+            CreateScheme::Create => CREATE,
+            CreateScheme::Create2 { .. } => CREATE2,
+        };
+        self.enter(kind, None, inputs.gas_limit);
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.exit(outcome.address, outcome.result.gas.spent(), outcome.result.result.is_ok());
+        outcome
+    }
+}
+
+/// The frames, opcodes and gas spent at a call depth.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DepthStats {
+    frames: u64,
+    opcodes: u64,
+    gas: u64,
+}
+
+/// Returns the number of frames, executed opcodes and gas spent by the opcodes per call depth.
+fn depth_distribution(frames: &[Frame]) -> BTreeMap<usize, DepthStats> {
+    let mut depths = BTreeMap::<usize, DepthStats>::new();
+    for frame in frames {
+        let stats = depths.entry(frame.depth).or_default();
+        stats.frames += 1;
+        stats.opcodes += frame.counts.iter().sum::<u64>();
+        stats.gas += frame.gas.iter().sum::<u64>();
+    }
+    depths
+}
+
+/// Prints the opcodes with their count and gas spent, sorted by the gas spent.
+fn print_opcodes(counts: &OpcodeCounts, gas: &OpcodeCounts, top: usize, indent: &str) {
+    let mut opcodes =
+        (0..=u8::MAX).filter(|opcode| counts[*opcode as usize] > 0).collect::<Vec<_>>();
+    opcodes.sort_unstable_by_key(|opcode| {
+        (Reverse(gas[*opcode as usize]), Reverse(counts[*opcode as usize]), *opcode)
+    });
+
+    println!("{indent}{:<16} {:>12} {:>14}", "OPCODE", "COUNT", "GAS");
+    for opcode in opcodes.into_iter().take(top) {
+        println!(
+            "{indent}{:<16} {:>12} {:>14}",
+            opcode_name(opcode),
+            counts[opcode as usize],
+            gas[opcode as usize]
+        );
+    }
+}
+
+/// Prints the per-frame breakdown, the call depth distribution and the opcode totals of the
+/// transaction.
+fn print_profile(tx_hash: TxHash, profile: &TransactionProfile, top: usize) {
+    println!(
+        "Transaction {tx_hash} at index {} of block {}: {}, {} gas used",
+        profile.index,
+        profile.block_number,
+        if profile.success { "success" } else { "failed" },
+        profile.gas_used
+    );
+
+    for (index, frame) in profile.frames.iter().enumerate() {
+        let indent = "  ".repeat(frame.depth);
+        let address = frame.address.map(|address| address.to_string()).unwrap_or_default();
+        println!();
+        println!(
+            "{indent}#{index} {} {address} depth {}: {} opcodes, {} gas used, {}",
+            opcode_name(frame.kind),
+            frame.depth,
+            frame.counts.iter().sum::<u64>(),
+            frame.gas_used,
+            if frame.success { "success" } else { "failed" }
+        );
+        print_opcodes(&frame.counts, &frame.gas, top, &indent);
+    }
+
+    println!();
+    println!("{:<8} {:>8} {:>12} {:>14}", "DEPTH", "FRAMES", "OPCODES", "GAS");
+    for (depth, stats) in depth_distribution(&profile.frames) {
+        println!("{depth:<8} {:>8} {:>12} {:>14}", stats.frames, stats.opcodes, stats.gas);
+    }
+
+    let (mut counts, mut gas) = ([0; 256], [0; 256]);
+    for frame in &profile.frames {
+        for opcode in 0..256 {
+            counts[opcode] += frame.counts[opcode];
+            gas[opcode] += frame.gas[opcode];
+        }
+    }
+    println!();
+    print_opcodes(&counts, &gas, usize::MAX, "");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Bytes;
+    use reth_revm::{
+        db::EmptyDB,
+        primitives::{AccountInfo, Bytecode, Env, SpecId, TransactTo},
+    };
+
+    #[test]
+    fn parse_tx_args() {
+        let hash = "0x5c504ed432cb51138bcf09aa5e8a410dd4a1e204ef84bfed1be16dfba1b22060";
+        let command = Command::try_parse_from(["reth", hash]).unwrap();
+        assert_eq!(command.tx_hash, hash.parse::<TxHash>().unwrap());
+        assert_eq!(command.top, 10);
+
+        assert!(Command::try_parse_from(["reth"]).is_err());
+    }
+
+    #[test]
+    fn profile_nested_call() {
+        let (contract, callee) = (Address::with_last_byte(0xAA), Address::with_last_byte(0xBB));
+        let mut db = CacheDB::new(EmptyDB::default());
+        // PUSH1 0 PUSH1 0 PUSH1 0 PUSH1 0 PUSH1 0 PUSH20 <callee> GAS CALL STOP
+        let code = [
+            &[0x60, 0, 0x60, 0, 0x60, 0, 0x60, 0, 0x60, 0, 0x73][..],
+            callee.as_slice(),
+            &[0x5A, 0xF1, 0x00],
+        ]
+        .concat();
+        db.insert_account_info(
+            contract,
+            AccountInfo { code: Some(Bytecode::new_raw(code.into())), ..Default::default() },
+        );
+        // PUSH1 1 PUSH1 2 ADD STOP
+        let code = Bytes::from_static(&[0x60, 0x01, 0x60, 0x02, 0x01, 0x00]);
+        db.insert_account_info(
+            callee,
+            AccountInfo { code: Some(Bytecode::new_raw(code)), ..Default::default() },
+        );
+
+        let mut env = Env::default();
+        env.tx.transact_to = TransactTo::Call(contract);
+        env.tx.gas_limit = 1_000_000;
+        let env = EnvWithHandlerCfg::new_with_spec_id(Box::new(env), SpecId::CANCUN);
+
+        let mut profiler = FrameProfiler::default();
+        let mut evm = EthEvmConfig::default().evm_with_env_and_inspector(db, env, &mut profiler);
+        assert!(evm.transact().unwrap().result.is_success());
+        drop(evm);
+
+        let [outer, inner] = profiler.frames.as_slice() else {
+            panic!("unexpected frames: {:?}", profiler.frames)
+        };
+        assert_eq!((outer.depth, outer.kind, outer.address), (0, CALL, Some(contract)));
+        assert_eq!((inner.depth, inner.kind, inner.address), (1, CALL, Some(callee)));
+        assert!(outer.success && inner.success);
+
+        assert_eq!(
+            (outer.counts[0x60], outer.counts[0x73], outer.counts[CALL as usize]),
+            (5, 1, 1)
+        );
+        // only the cold account access of the callee is spent by the `CALL` itself
+        assert_eq!(outer.gas[CALL as usize], 2600);
+        assert_eq!((inner.counts[0x60], inner.gas[0x60], inner.gas[0x01]), (2, 6, 3));
+        assert_eq!(inner.gas_used, 9);
+
+        let depths = depth_distribution(&profiler.frames);
+        assert_eq!(depths[&1], DepthStats { frames: 1, opcodes: 4, gas: 9 });
+    }
+}
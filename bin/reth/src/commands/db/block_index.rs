@@ -0,0 +1,85 @@
+use crate::utils::DbTool;
+use clap::Parser;
+use reth_db::{static_file::iter_static_files, DatabaseEnv};
+use reth_nippy_jar::NippyJar;
+use reth_primitives::{
+    static_file::{find_fixed_range, SegmentHeader},
+    StaticFileSegment,
+};
+use reth_provider::{providers::StaticFileBlockIndex, BlockReader};
+use tracing::{info, warn};
+
+#[derive(Parser, Debug)]
+/// The arguments for the `reth db static-file-block-index` command
+pub struct Command {
+    /// Static File segments to index. Defaults to all transaction based segments
+    segments: Vec<StaticFileSegment>,
+
+    /// Rebuilds the block index even if a valid one already exists
+    #[arg(long, default_value_t = false)]
+    force: bool,
+}
+
+impl Command {
+    /// Execute `db static-file-block-index` command
+    pub fn execute(self, tool: &DbTool<DatabaseEnv>) -> eyre::Result<()> {
+        warn!("This command should be run without the node running!");
+
+        let segments = if self.segments.is_empty() {
+            vec![StaticFileSegment::Transactions, StaticFileSegment::Receipts]
+        } else {
+            self.segments
+        };
+
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let static_files = iter_static_files(static_file_provider.directory())?;
+        let provider = tool.provider_factory.provider()?;
+
+        for segment in segments {
+            if segment.is_headers() {
+                warn!(?segment, "Block index is only available for transaction based segments");
+                continue
+            }
+
+            for (block_range, _) in static_files.get(&segment).into_iter().flatten() {
+                let path = static_file_provider
+                    .directory()
+                    .join(segment.filename(&find_fixed_range(block_range.start())));
+                let jar = NippyJar::<SegmentHeader>::load(&path)?;
+                let header = jar.user_header();
+
+                if !self.force &&
+                    StaticFileBlockIndex::load(&path)?
+                        .is_some_and(|index| index.is_consistent(header))
+                {
+                    info!(?segment, ?block_range, "Block index already exists, skipping");
+                    continue
+                }
+
+                let mut tx_counts =
+                    Vec::with_capacity(header.block_len().unwrap_or_default() as usize);
+                for block in block_range.start()..=block_range.end() {
+                    let indices = provider
+                        .block_body_indices(block)?
+                        .ok_or_else(|| eyre::eyre!("missing body indices for block {block}"))?;
+                    tx_counts.push(indices.tx_count);
+                }
+
+                let index = StaticFileBlockIndex::from_tx_counts(tx_counts);
+                if !index.is_consistent(header) {
+                    warn!(
+                        ?segment,
+                        ?block_range,
+                        "Block body indices don't match the static file, skipping"
+                    );
+                    continue
+                }
+
+                index.save(&path)?;
+                info!(?segment, ?block_range, "Built block index");
+            }
+        }
+
+        Ok(())
+    }
+}
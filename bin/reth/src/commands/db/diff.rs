@@ -6,12 +6,13 @@ use crate::{
 use clap::Parser;
 use reth_db::{
     cursor::DbCursorRO, database::Database, open_db_read_only, table::Table, transaction::DbTx,
-    AccountChangeSets, AccountsHistory, AccountsTrie, BlockBodyIndices, BlockOmmers,
-    BlockWithdrawals, Bytecodes, CanonicalHeaders, DatabaseEnv, HashedAccounts, HashedStorages,
-    HeaderNumbers, HeaderTerminalDifficulties, Headers, PlainAccountState, PlainStorageState,
-    PruneCheckpoints, Receipts, StageCheckpointProgresses, StageCheckpoints, StorageChangeSets,
-    StoragesHistory, StoragesTrie, Tables, TransactionBlocks, TransactionHashNumbers,
-    TransactionSenders, Transactions, VersionHistory,
+    AccountChangeSets, AccountsHistory, AccountsTrie, AddressTokenTransfers, AddressTransactions,
+    BlockBodyIndices, BlockOmmers, BlockWithdrawals, Bytecodes, CanonicalHeaders, DatabaseEnv,
+    ExExCheckpoints, HashedAccounts, HashedStorages, HeaderNumbers, HeaderTerminalDifficulties,
+    Headers, LogAddressBlocks, LogTopicBlocks, PlainAccountState, PlainStorageState,
+    PruneCheckpoints, Receipts, ReorgHistory, SenderNonceTransactions, StageCheckpointProgresses,
+    StageCheckpoints, StorageChangeSets, StoragesHistory, StoragesTrie, Tables, TokenTransfers,
+    TransactionBlocks, TransactionHashNumbers, TransactionSenders, Transactions, VersionHistory,
 };
 use std::{
     collections::HashMap,
@@ -121,6 +122,9 @@ impl Command {
                 Tables::StoragesHistory => {
                     find_diffs::<StoragesHistory>(primary_tx, secondary_tx, output_dir)?
                 }
+                Tables::AddressTransactions => {
+                    find_diffs::<AddressTransactions>(primary_tx, secondary_tx, output_dir)?
+                }
                 Tables::AccountChangeSets => {
                     find_diffs::<AccountChangeSets>(primary_tx, secondary_tx, output_dir)?
                 }
@@ -154,6 +158,27 @@ impl Command {
                 Tables::VersionHistory => {
                     find_diffs::<VersionHistory>(primary_tx, secondary_tx, output_dir)?
                 }
+                Tables::ReorgHistory => {
+                    find_diffs::<ReorgHistory>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::TokenTransfers => {
+                    find_diffs::<TokenTransfers>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::AddressTokenTransfers => {
+                    find_diffs::<AddressTokenTransfers>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::SenderNonceTransactions => {
+                    find_diffs::<SenderNonceTransactions>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::LogAddressBlocks => {
+                    find_diffs::<LogAddressBlocks>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::LogTopicBlocks => {
+                    find_diffs::<LogTopicBlocks>(primary_tx, secondary_tx, output_dir)?
+                }
+                Tables::ExExCheckpoints => {
+                    find_diffs::<ExExCheckpoints>(primary_tx, secondary_tx, output_dir)?
+                }
             };
         }
 
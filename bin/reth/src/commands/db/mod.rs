@@ -20,6 +20,7 @@ use std::{
     sync::Arc,
 };
 
+mod block_index;
 mod checksum;
 mod clear;
 mod diff;
@@ -86,6 +87,8 @@ pub enum Subcommands {
     Clear(clear::Command),
     /// Creates static files from database tables
     CreateStaticFiles(static_files::Command),
+    /// Builds the block index of transaction based static files for existing datadirs
+    StaticFileBlockIndex(block_index::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -172,6 +175,11 @@ impl Command {
             Subcommands::CreateStaticFiles(command) => {
                 command.execute(data_dir, self.db.database_args(), self.chain.clone())?;
             }
+            Subcommands::StaticFileBlockIndex(command) => {
+                db_ro_exec!(self.chain, &db_path, db_args, static_files_path, tool, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
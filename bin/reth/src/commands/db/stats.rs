@@ -8,12 +8,13 @@ use human_bytes::human_bytes;
 use itertools::Itertools;
 use reth_db::{
     database::Database, mdbx, static_file::iter_static_files, AccountChangeSets, AccountsHistory,
-    AccountsTrie, BlockBodyIndices, BlockOmmers, BlockWithdrawals, Bytecodes, CanonicalHeaders,
-    DatabaseEnv, HashedAccounts, HashedStorages, HeaderNumbers, HeaderTerminalDifficulties,
-    Headers, PlainAccountState, PlainStorageState, PruneCheckpoints, Receipts,
-    StageCheckpointProgresses, StageCheckpoints, StorageChangeSets, StoragesHistory, StoragesTrie,
-    Tables, TransactionBlocks, TransactionHashNumbers, TransactionSenders, Transactions,
-    VersionHistory,
+    AccountsTrie, AddressTokenTransfers, AddressTransactions, BlockBodyIndices, BlockOmmers,
+    BlockWithdrawals, Bytecodes, CanonicalHeaders, DatabaseEnv, ExExCheckpoints, HashedAccounts,
+    HashedStorages, HeaderNumbers, HeaderTerminalDifficulties, Headers, LogAddressBlocks,
+    LogTopicBlocks, PlainAccountState, PlainStorageState, PruneCheckpoints, Receipts, ReorgHistory,
+    SenderNonceTransactions, StageCheckpointProgresses, StageCheckpoints, StorageChangeSets,
+    StoragesHistory, StoragesTrie, Tables, TokenTransfers, TransactionBlocks,
+    TransactionHashNumbers, TransactionSenders, Transactions, VersionHistory,
 };
 use reth_node_core::dirs::{ChainPath, DataDirPath};
 use reth_primitives::static_file::{find_fixed_range, SegmentRangeInclusive};
@@ -329,6 +330,9 @@ impl Command {
                 Tables::AccountsHistory => viewer.get_checksum::<AccountsHistory>().unwrap(),
                 Tables::AccountChangeSets => viewer.get_checksum::<AccountChangeSets>().unwrap(),
                 Tables::AccountsTrie => viewer.get_checksum::<AccountsTrie>().unwrap(),
+                Tables::AddressTransactions => {
+                    viewer.get_checksum::<AddressTransactions>().unwrap()
+                }
                 Tables::BlockBodyIndices => viewer.get_checksum::<BlockBodyIndices>().unwrap(),
                 Tables::BlockOmmers => viewer.get_checksum::<BlockOmmers>().unwrap(),
                 Tables::BlockWithdrawals => viewer.get_checksum::<BlockWithdrawals>().unwrap(),
@@ -359,6 +363,17 @@ impl Command {
                 Tables::TransactionSenders => viewer.get_checksum::<TransactionSenders>().unwrap(),
                 Tables::Transactions => viewer.get_checksum::<Transactions>().unwrap(),
                 Tables::VersionHistory => viewer.get_checksum::<VersionHistory>().unwrap(),
+                Tables::ReorgHistory => viewer.get_checksum::<ReorgHistory>().unwrap(),
+                Tables::TokenTransfers => viewer.get_checksum::<TokenTransfers>().unwrap(),
+                Tables::AddressTokenTransfers => {
+                    viewer.get_checksum::<AddressTokenTransfers>().unwrap()
+                }
+                Tables::SenderNonceTransactions => {
+                    viewer.get_checksum::<SenderNonceTransactions>().unwrap()
+                }
+                Tables::LogAddressBlocks => viewer.get_checksum::<LogAddressBlocks>().unwrap(),
+                Tables::LogTopicBlocks => viewer.get_checksum::<LogTopicBlocks>().unwrap(),
+                Tables::ExExCheckpoints => viewer.get_checksum::<ExExCheckpoints>().unwrap(),
             };
 
             // increment duration for final report
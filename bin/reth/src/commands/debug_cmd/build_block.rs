@@ -165,13 +165,13 @@ impl Command {
         let evm_config = reth_node_optimism::OptimismEvmConfig::default();
 
         #[cfg(not(feature = "optimism"))]
-        let evm_config = EthEvmConfig::default();
+        let evm_config = EthEvmConfig::new(&self.chain)?;
 
         // configure blockchain tree
         let tree_externals = TreeExternals::new(
             provider_factory.clone(),
             Arc::clone(&consensus),
-            EvmProcessorFactory::new(self.chain.clone(), evm_config),
+            EvmProcessorFactory::new(self.chain.clone(), evm_config.clone()),
         );
         let tree = BlockchainTree::new(tree_externals, BlockchainTreeConfig::default(), None)?;
         let blockchain_tree = Arc::new(ShareableBlockchainTree::new(tree));
@@ -287,14 +287,13 @@ impl Command {
         );
 
         #[cfg(feature = "optimism")]
-        let payload_builder = reth_node_optimism::OptimismPayloadBuilder::new(
-            self.chain.clone(),
-            reth_node_optimism::OptimismEvmConfig::default(),
-        )
-        .compute_pending_block();
+        let payload_builder =
+            reth_node_optimism::OptimismPayloadBuilder::new(self.chain.clone(), evm_config.clone())
+                .compute_pending_block();
 
         #[cfg(not(feature = "optimism"))]
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
+        let payload_builder =
+            reth_ethereum_payload_builder::EthereumPayloadBuilder::new(evm_config.clone());
 
         match payload_builder.try_build(args)? {
             BuildOutcome::Better { payload, .. } => {
@@ -0,0 +1,122 @@
+use super::replay_engine::{ReplayEngineTypes, ReplayNodeArgs};
+use clap::Parser;
+use reth_cli_runner::CliContext;
+use reth_node_api::EngineTypes;
+use reth_node_core::{
+    engine_api_store::StoredEngineApiMessage,
+    engine_journal::{EngineJournal, EngineJournalResponse},
+};
+use std::{path::PathBuf, time::Duration};
+use tracing::*;
+
+/// `reth debug engine-replay` command
+/// Replays the requests of an engine journal, recorded with `--debug.engine-journal`, against the
+/// node and compares the responses with the journaled ones.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    node: ReplayNodeArgs,
+
+    /// The directory to read the engine journal from.
+    #[arg(long = "engine-journal", value_name = "DIR")]
+    engine_journal: PathBuf,
+
+    /// Replay the requests with the same delays between them as they were received with.
+    #[arg(long)]
+    realtime: bool,
+
+    /// Stop at the first response that does not match the journal.
+    #[arg(long = "fail-fast")]
+    fail_fast: bool,
+}
+
+impl Command {
+    /// Execute `debug engine-replay` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let entries = EngineJournal::read_entries::<
+            <ReplayEngineTypes as EngineTypes>::PayloadAttributes,
+        >(&self.engine_journal)?;
+        info!(target: "reth::cli", entries = entries.len(), "Read engine journal");
+
+        let (beacon_engine_handle, _rx) = self.node.launch_engine(&ctx).await?;
+
+        let mut mismatches = 0;
+        let mut last_received_at = None;
+        for entry in entries {
+            if self.realtime {
+                if let Some(last_received_at) = last_received_at {
+                    let delay = entry.received_at.saturating_sub(last_received_at);
+                    tokio::time::sleep(Duration::from_millis(delay)).await;
+                }
+                last_received_at = Some(entry.received_at);
+            }
+
+            debug!(
+                target: "reth::cli",
+                seq = entry.seq,
+                request = ?entry.request,
+                "Replaying engine API request"
+            );
+            let response = match entry.request {
+                StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
+                    EngineJournalResponse::ForkchoiceUpdated(
+                        beacon_engine_handle
+                            .fork_choice_updated(state, payload_attrs)
+                            .await
+                            .map_err(|err| err.to_string()),
+                    )
+                }
+                StoredEngineApiMessage::NewPayload { payload, cancun_fields } => {
+                    EngineJournalResponse::NewPayload(
+                        beacon_engine_handle
+                            .new_payload(payload, cancun_fields)
+                            .await
+                            .map_err(|err| err.to_string()),
+                    )
+                }
+            };
+
+            if responses_match(&entry.response, &response) {
+                debug!(
+                    target: "reth::cli",
+                    seq = entry.seq,
+                    ?response,
+                    "Engine API response matches"
+                );
+                continue
+            }
+
+            mismatches += 1;
+            warn!(
+                target: "reth::cli",
+                seq = entry.seq,
+                journaled = ?entry.response,
+                replayed = ?response,
+                "Mismatching engine API response"
+            );
+            if self.fail_fast {
+                eyre::bail!("mismatching response to engine API request {}", entry.seq)
+            }
+        }
+
+        info!(target: "reth::cli", mismatches, "Finished replaying engine journal");
+        Ok(())
+    }
+}
+
+/// Returns `true` if the replayed response matches the journaled one.
+///
+/// Errors only need to be present in both, their messages differ because the engine handle wraps
+/// the errors the journal records.
+fn responses_match(journaled: &EngineJournalResponse, replayed: &EngineJournalResponse) -> bool {
+    match (journaled, replayed) {
+        (
+            EngineJournalResponse::ForkchoiceUpdated(Err(_)),
+            EngineJournalResponse::ForkchoiceUpdated(Err(_)),
+        ) |
+        (EngineJournalResponse::NewPayload(Err(_)), EngineJournalResponse::NewPayload(Err(_))) => {
+            true
+        }
+        _ => journaled == replayed,
+    }
+}
@@ -108,8 +108,10 @@ impl Command {
         let stage_conf = &config.stages;
 
         let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-        let factory =
-            reth_revm::EvmProcessorFactory::new(self.chain.clone(), EthEvmConfig::default());
+        let factory = reth_revm::EvmProcessorFactory::new(
+            self.chain.clone(),
+            EthEvmConfig::new(&self.chain)?,
+        );
 
         let header_mode = HeaderSyncMode::Tip(tip_rx);
         let pipeline = Pipeline::builder()
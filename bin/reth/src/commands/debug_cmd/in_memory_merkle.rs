@@ -161,8 +161,10 @@ impl Command {
             )
             .await?;
 
-        let executor_factory =
-            reth_revm::EvmProcessorFactory::new(self.chain.clone(), EthEvmConfig::default());
+        let executor_factory = reth_revm::EvmProcessorFactory::new(
+            self.chain.clone(),
+            EthEvmConfig::new(&self.chain)?,
+        );
         let mut executor = executor_factory.with_state(LatestStateProviderRef::new(
             provider.tx_ref(),
             factory.static_file_provider(),
@@ -201,8 +201,10 @@ impl Command {
                         checkpoint.stage_checkpoint.is_some()
                 });
 
-        let factory =
-            reth_revm::EvmProcessorFactory::new(self.chain.clone(), EthEvmConfig::default());
+        let factory = reth_revm::EvmProcessorFactory::new(
+            self.chain.clone(),
+            EthEvmConfig::new(&self.chain)?,
+        );
         let mut execution_stage = ExecutionStage::new(
             factory,
             ExecutionStageThresholds {
@@ -4,10 +4,12 @@ use clap::{Parser, Subcommand};
 use reth_cli_runner::CliContext;
 
 mod build_block;
+mod engine_replay;
 mod execution;
 mod in_memory_merkle;
 mod merkle;
 mod replay_engine;
+mod replay_state_root;
 
 /// `reth debug` command
 #[derive(Debug, Parser)]
@@ -29,6 +31,10 @@ pub enum Subcommands {
     BuildBlock(build_block::Command),
     /// Debug engine API by replaying stored messages.
     ReplayEngine(replay_engine::Command),
+    /// Debug state root calculation by replaying captured inputs.
+    ReplayStateRoot(replay_state_root::Command),
+    /// Debug engine API by replaying a journal of requests and comparing the responses.
+    EngineReplay(engine_replay::Command),
 }
 
 impl Command {
@@ -40,6 +46,8 @@ impl Command {
             Subcommands::InMemoryMerkle(command) => command.execute(ctx).await,
             Subcommands::BuildBlock(command) => command.execute(ctx).await,
             Subcommands::ReplayEngine(command) => command.execute(ctx).await,
+            Subcommands::ReplayStateRoot(command) => command.execute().await,
+            Subcommands::EngineReplay(command) => command.execute(ctx).await,
         }
     }
 }
@@ -6,10 +6,13 @@ use crate::{
     },
     dirs::{DataDirPath, MaybePlatformPath},
 };
-use clap::Parser;
+use clap::{Args, Parser};
 use eyre::Context;
 use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
-use reth_beacon_consensus::{hooks::EngineHooks, BeaconConsensus, BeaconConsensusEngine};
+use reth_beacon_consensus::{
+    hooks::EngineHooks, BeaconConsensus, BeaconConsensusEngine, BeaconConsensusEngineError,
+    BeaconConsensusEngineHandle,
+};
 use reth_blockchain_tree::{
     BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
 };
@@ -34,11 +37,34 @@ use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 use tokio::sync::{mpsc, oneshot};
 use tracing::*;
 
+/// The engine types of the node that engine API messages are replayed against.
+#[cfg(not(feature = "optimism"))]
+pub(crate) type ReplayEngineTypes = EthEngineTypes;
+
+/// The engine types of the node that engine API messages are replayed against.
+#[cfg(feature = "optimism")]
+pub(crate) type ReplayEngineTypes = reth_node_optimism::OptimismEngineTypes;
+
 /// `reth debug replay-engine` command
 /// This script will read stored engine API messages and replay them by the timestamp.
 /// It does not require
 #[derive(Debug, Parser)]
 pub struct Command {
+    #[command(flatten)]
+    node: ReplayNodeArgs,
+
+    /// The path to read engine API messages from.
+    #[arg(long = "engine-api-store", value_name = "PATH")]
+    engine_api_store: PathBuf,
+
+    /// The number of milliseconds between Engine API messages.
+    #[arg(long = "interval", default_value_t = 1_000)]
+    interval: u64,
+}
+
+/// Arguments of the node that engine API messages are replayed against.
+#[derive(Debug, Args)]
+pub(crate) struct ReplayNodeArgs {
     /// The path to the data dir for all reth files and subdirectories.
     ///
     /// Defaults to the OS-specific data directory:
@@ -66,17 +92,9 @@ pub struct Command {
 
     #[command(flatten)]
     network: NetworkArgs,
-
-    /// The path to read engine API messages from.
-    #[arg(long = "engine-api-store", value_name = "PATH")]
-    engine_api_store: PathBuf,
-
-    /// The number of milliseconds between Engine API messages.
-    #[arg(long = "interval", default_value_t = 1_000)]
-    interval: u64,
 }
 
-impl Command {
+impl ReplayNodeArgs {
     async fn build_network(
         &self,
         config: &Config,
@@ -107,8 +125,16 @@ impl Command {
         Ok(network)
     }
 
-    /// Execute `debug replay-engine` command
-    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+    /// Launches the consensus engine of the node.
+    ///
+    /// Returns the handle to the engine and a receiver for the result of the engine once it exits.
+    pub(crate) async fn launch_engine(
+        &self,
+        ctx: &CliContext,
+    ) -> eyre::Result<(
+        BeaconConsensusEngineHandle<ReplayEngineTypes>,
+        oneshot::Receiver<Result<(), BeaconConsensusEngineError>>,
+    )> {
         let config = Config::default();
 
         // Add network name to data dir
@@ -124,7 +150,7 @@ impl Command {
         let consensus: Arc<dyn Consensus> = Arc::new(BeaconConsensus::new(Arc::clone(&self.chain)));
 
         #[cfg(not(feature = "optimism"))]
-        let evm_config = EthEvmConfig::default();
+        let evm_config = EthEvmConfig::new(&self.chain)?;
 
         #[cfg(feature = "optimism")]
         let evm_config = reth_node_optimism::OptimismEvmConfig::default();
@@ -133,7 +159,7 @@ impl Command {
         let tree_externals = TreeExternals::new(
             provider_factory.clone(),
             Arc::clone(&consensus),
-            EvmProcessorFactory::new(self.chain.clone(), evm_config),
+            EvmProcessorFactory::new(self.chain.clone(), evm_config.clone()),
         );
         let tree = BlockchainTree::new(tree_externals, BlockchainTreeConfig::default(), None)?;
         let blockchain_tree = Arc::new(ShareableBlockchainTree::new(tree));
@@ -156,14 +182,13 @@ impl Command {
 
         // Set up payload builder
         #[cfg(not(feature = "optimism"))]
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
+        let payload_builder =
+            reth_ethereum_payload_builder::EthereumPayloadBuilder::new(evm_config);
 
         // Optimism's payload builder is implemented on the OptimismPayloadBuilder type.
         #[cfg(feature = "optimism")]
-        let payload_builder = reth_node_optimism::OptimismPayloadBuilder::new(
-            self.chain.clone(),
-            reth_node_optimism::OptimismEvmConfig::default(),
-        );
+        let payload_builder =
+            reth_node_optimism::OptimismPayloadBuilder::new(self.chain.clone(), evm_config);
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
             blockchain_db.clone(),
@@ -174,14 +199,7 @@ impl Command {
             payload_builder,
         );
 
-        #[cfg(feature = "optimism")]
-        let (payload_service, payload_builder): (
-            _,
-            PayloadBuilderHandle<reth_node_optimism::OptimismEngineTypes>,
-        ) = PayloadBuilderService::new(payload_generator, blockchain_db.canonical_state_stream());
-
-        #[cfg(not(feature = "optimism"))]
-        let (payload_service, payload_builder): (_, PayloadBuilderHandle<EthEngineTypes>) =
+        let (payload_service, payload_builder): (_, PayloadBuilderHandle<ReplayEngineTypes>) =
             PayloadBuilderService::new(payload_generator, blockchain_db.canonical_state_stream());
 
         ctx.task_executor.spawn_critical("payload builder service", payload_service);
@@ -221,6 +239,15 @@ impl Command {
             let _ = tx.send(res);
         });
 
+        Ok((beacon_engine_handle, rx))
+    }
+}
+
+impl Command {
+    /// Execute `debug replay-engine` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let (beacon_engine_handle, rx) = self.node.launch_engine(&ctx).await?;
+
         let engine_api_store = EngineApiStore::new(self.engine_api_store.clone());
         for filepath in engine_api_store.engine_messages_iter()? {
             let contents =
@@ -0,0 +1,147 @@
+//! Command for replaying captured state root computations.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use eyre::WrapErr;
+use reth_blockchain_tree::StateRootInput;
+use reth_db::open_db_read_only;
+use reth_primitives::{ChainSpec, B256};
+use reth_provider::{
+    providers::ConsistentDbView, BlockHashReader, BlockNumReader, ProviderFactory,
+};
+use reth_trie_parallel::parallel_root::ParallelStateRoot;
+use std::{path::PathBuf, sync::Arc, time::Instant};
+use tracing::{info, warn};
+
+/// `reth debug replay-state-root` command
+/// Replays a state root computation that was captured with `--debug.state-root-capture`.
+///
+/// The state root is computed on top of the database, which must be at the same state as the
+/// database of the node when the state root was captured, e.g. a copy of it.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// The captured state root input to replay.
+    #[arg(value_name = "FILE")]
+    file: PathBuf,
+}
+
+impl Command {
+    /// Execute `debug replay-state-root` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let input = StateRootInput::read(&self.file)
+            .wrap_err_with(|| format!("Failed to read {}", self.file.display()))?;
+
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = Arc::new(open_db_read_only(&data_dir.db_path(), self.db.database_args())?);
+        let factory = ProviderFactory::new(db, self.chain.clone(), data_dir.static_files_path())?;
+        let provider = factory.provider()?;
+
+        // the captured state is relative to the database tip at the time of the capture
+        let tip_number = provider.last_block_number()?;
+        let tip_hash = provider.block_hash(tip_number)?;
+        if tip_number != input.tip_number || tip_hash != Some(input.tip_hash) {
+            eyre::bail!(
+                "Database tip {tip_number} ({tip_hash:?}) does not match the captured tip {} ({})",
+                input.tip_number,
+                input.tip_hash
+            )
+        }
+
+        let hashed_state = input.state.to_hashed_state();
+        info!(
+            target: "reth::cli",
+            block_number = input.block_number,
+            block_hash = %input.block_hash,
+            accounts = hashed_state.accounts.len(),
+            storages = hashed_state.storages.len(),
+            "Replaying state root"
+        );
+
+        let start = Instant::now();
+        let consistent_view = ConsistentDbView::new(factory.clone(), Some(input.tip_hash));
+        let parallel_root = ParallelStateRoot::new(consistent_view, hashed_state.clone())
+            .incremental_root_with_updates()
+            .map(|(root, _)| root);
+        info!(
+            target: "reth::cli",
+            elapsed = ?start.elapsed(),
+            result = ?parallel_root,
+            "Computed parallel state root"
+        );
+
+        let start = Instant::now();
+        let (synchronous_root, _) = hashed_state.state_root_with_updates(provider.tx_ref())?;
+        info!(
+            target: "reth::cli",
+            elapsed = ?start.elapsed(),
+            root = %synchronous_root,
+            "Computed synchronous state root"
+        );
+
+        let parallel_root = parallel_root.ok();
+        report("parallel", parallel_root, input.parallel_state_root, input.expected_state_root);
+        report(
+            "synchronous",
+            Some(synchronous_root),
+            Some(input.synchronous_state_root),
+            input.expected_state_root,
+        );
+
+        Ok(())
+    }
+}
+
+/// Compares a replayed state root with the captured one and the state root of the block.
+fn report(kind: &str, replayed: Option<B256>, captured: Option<B256>, expected: B256) {
+    if replayed != captured {
+        warn!(
+            target: "reth::cli",
+            kind,
+            ?replayed,
+            ?captured,
+            "Replayed state root differs from the captured state root"
+        );
+    }
+    if replayed == Some(expected) {
+        info!(target: "reth::cli", kind, root = %expected, "Replayed state root matches the block");
+    } else {
+        warn!(
+            target: "reth::cli",
+            kind,
+            ?replayed,
+            %expected,
+            "Replayed state root does not match the block"
+        );
+    }
+}
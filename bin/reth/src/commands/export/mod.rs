@@ -0,0 +1,32 @@
+//! `reth export` command.
+
+use clap::{Parser, Subcommand};
+
+mod receipts;
+mod traces;
+
+/// `reth export` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth export` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Re-executes a range of blocks and writes their traces into a SQLite database.
+    Traces(traces::Command),
+    /// Exports the logs matching a filter, together with their receipts, as JSONL or Parquet.
+    Receipts(receipts::Command),
+}
+
+impl Command {
+    /// Execute `export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Traces(command) => command.execute().await,
+            Subcommands::Receipts(command) => command.execute().await,
+        }
+    }
+}
@@ -0,0 +1,391 @@
+//! Command that exports the logs matching a filter, together with their receipts.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use arrow_array::{ArrayRef, BinaryArray, BooleanArray, RecordBatch, UInt64Array, UInt8Array};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use clap::{Parser, ValueEnum};
+use eyre::WrapErr;
+use parquet::arrow::ArrowWriter;
+use reth_db::open_db_read_only;
+use reth_interfaces::provider::ProviderError;
+use reth_primitives::{
+    Address, BlockNumber, Bloom, BloomInput, Bytes, ChainSpec, Log, Receipt, B256,
+};
+use reth_provider::{
+    BlockReader, HeaderProvider, ProviderFactory, ReceiptProvider, TransactionsProvider,
+};
+use serde::Serialize;
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::Arc,
+    time::Instant,
+};
+use tracing::info;
+
+/// The output format of the export.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// One JSON object per line.
+    #[default]
+    Jsonl,
+    /// Apache Parquet file.
+    Parquet,
+}
+
+/// `reth export receipts` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// Only export logs emitted by these contracts. Can be specified multiple times.
+    #[arg(long, value_name = "ADDRESS")]
+    address: Vec<Address>,
+
+    /// Only export logs with one of these event signatures as first topic. Can be specified
+    /// multiple times.
+    #[arg(long, value_name = "SIGNATURE")]
+    topic0: Vec<B256>,
+
+    /// The first block of the range to export.
+    #[arg(long, value_name = "BLOCK")]
+    from: BlockNumber,
+
+    /// The last block of the range to export, inclusive.
+    #[arg(long, value_name = "BLOCK")]
+    to: BlockNumber,
+
+    /// The path of the file to write the logs to.
+    #[arg(long, value_name = "PATH")]
+    out: PathBuf,
+
+    /// The format of the output file.
+    #[arg(long, value_enum, default_value_t)]
+    format: ExportFormat,
+
+    /// Number of blocks that are read at once.
+    #[arg(long, value_name = "BLOCKS", default_value_t = 10_000)]
+    batch_size: u64,
+}
+
+impl Command {
+    /// Execute `export receipts` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        if self.from > self.to {
+            eyre::bail!("Invalid block range: {}..={}", self.from, self.to)
+        }
+
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = open_db_read_only(&data_dir.db_path(), self.db.database_args())?;
+        let factory = ProviderFactory::new(db, self.chain.clone(), data_dir.static_files_path())?;
+        let provider = factory.provider()?;
+
+        let file = File::create(&self.out)
+            .wrap_err_with(|| format!("Failed to create {}", self.out.display()))?;
+        let mut writer = LogWriter::new(self.format, file)?;
+
+        let started_at = Instant::now();
+        let mut exported = 0;
+        let mut batch_start = self.from;
+        while batch_start <= self.to {
+            let batch_end = self.to.min(batch_start.saturating_add(self.batch_size.max(1) - 1));
+
+            let mut logs = Vec::new();
+            let headers = provider.headers_range(batch_start..=batch_end)?;
+            if headers.len() as u64 != batch_end - batch_start + 1 {
+                let missing = batch_start + headers.len() as u64;
+                return Err(ProviderError::HeaderNotFound(missing.into()).into())
+            }
+            for (number, header) in (batch_start..=batch_end).zip(headers) {
+                // most blocks can be skipped without reading their receipts
+                if !self.bloom_matches(&header.logs_bloom) {
+                    continue
+                }
+
+                let indices = provider
+                    .block_body_indices(number)?
+                    .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+                let receipts = provider.receipts_by_tx_range(indices.tx_num_range())?;
+                if receipts.len() as u64 != indices.tx_count {
+                    let missing = indices.first_tx_num() + receipts.len() as u64;
+                    return Err(ProviderError::ReceiptNotFound(missing.into()).into())
+                }
+
+                let mut block_hash = None;
+                let mut log_index = 0;
+                for (tx_num, receipt) in indices.tx_num_range().zip(receipts) {
+                    for log in &receipt.logs {
+                        if self.log_matches(log) {
+                            let block_hash = *block_hash.get_or_insert_with(|| header.hash_slow());
+                            let transaction_hash = provider
+                                .transaction_by_id_no_hash(tx_num)?
+                                .ok_or(ProviderError::TransactionNotFound(tx_num.into()))?
+                                .hash();
+                            logs.push(ExportedLog::new(
+                                number,
+                                block_hash,
+                                transaction_hash,
+                                tx_num - indices.first_tx_num(),
+                                log_index,
+                                log,
+                                &receipt,
+                            ));
+                        }
+                        log_index += 1;
+                    }
+                }
+            }
+
+            exported += logs.len();
+            writer.write(logs)?;
+
+            info!(
+                target: "reth::cli",
+                from = batch_start,
+                to = batch_end,
+                exported,
+                elapsed = ?started_at.elapsed(),
+                "Exported logs"
+            );
+            batch_start = batch_end + 1;
+        }
+
+        writer.finish()?;
+        info!(target: "reth::cli", exported, out = %self.out.display(), "Finished export");
+        Ok(())
+    }
+
+    /// Returns `true` if the block's bloom filter may contain matching logs.
+    fn bloom_matches(&self, bloom: &Bloom) -> bool {
+        let contains = |input: &[u8]| bloom.contains_input(BloomInput::Raw(input));
+        let any_address = self.address.iter().any(|address| contains(address.as_slice()));
+        let any_topic = self.topic0.iter().any(|topic| contains(topic.as_slice()));
+        (self.address.is_empty() || any_address) && (self.topic0.is_empty() || any_topic)
+    }
+
+    /// Returns `true` if the log matches the address and topic filters.
+    fn log_matches(&self, log: &Log) -> bool {
+        (self.address.is_empty() || self.address.contains(&log.address)) &&
+            (self.topic0.is_empty() ||
+                log.topics().first().is_some_and(|topic| self.topic0.contains(topic)))
+    }
+}
+
+/// An exported log with the fields of its receipt.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportedLog {
+    block_number: BlockNumber,
+    block_hash: B256,
+    transaction_hash: B256,
+    transaction_index: u64,
+    log_index: u64,
+    address: Address,
+    topics: Vec<B256>,
+    data: Bytes,
+    transaction_type: u8,
+    status: bool,
+    cumulative_gas_used: u64,
+}
+
+impl ExportedLog {
+    fn new(
+        block_number: BlockNumber,
+        block_hash: B256,
+        transaction_hash: B256,
+        transaction_index: u64,
+        log_index: u64,
+        log: &Log,
+        receipt: &Receipt,
+    ) -> Self {
+        Self {
+            block_number,
+            block_hash,
+            transaction_hash,
+            transaction_index,
+            log_index,
+            address: log.address,
+            topics: log.topics().to_vec(),
+            data: log.data.data.clone(),
+            transaction_type: receipt.tx_type.into(),
+            status: receipt.success,
+            cumulative_gas_used: receipt.cumulative_gas_used,
+        }
+    }
+}
+
+/// Writes exported logs in the configured [ExportFormat].
+enum LogWriter {
+    Jsonl(BufWriter<File>),
+    Parquet { writer: ArrowWriter<File>, schema: SchemaRef },
+}
+
+impl LogWriter {
+    fn new(format: ExportFormat, file: File) -> eyre::Result<Self> {
+        Ok(match format {
+            ExportFormat::Jsonl => Self::Jsonl(BufWriter::new(file)),
+            ExportFormat::Parquet => {
+                let schema = Arc::new(parquet_schema());
+                Self::Parquet { writer: ArrowWriter::try_new(file, schema.clone(), None)?, schema }
+            }
+        })
+    }
+
+    /// Writes the logs, in order.
+    fn write(&mut self, logs: Vec<ExportedLog>) -> eyre::Result<()> {
+        if logs.is_empty() {
+            return Ok(())
+        }
+
+        match self {
+            Self::Jsonl(writer) => {
+                for log in logs {
+                    serde_json::to_writer(&mut *writer, &log)?;
+                    writer.write_all(b"\n")?;
+                }
+            }
+            Self::Parquet { writer, schema } => {
+                let topic = |index: usize| -> ArrayRef {
+                    Arc::new(BinaryArray::from_iter(
+                        logs.iter().map(|log| log.topics.get(index).map(|topic| topic.as_slice())),
+                    ))
+                };
+                let columns: Vec<ArrayRef> = vec![
+                    Arc::new(UInt64Array::from_iter_values(
+                        logs.iter().map(|log| log.block_number),
+                    )),
+                    Arc::new(BinaryArray::from_iter_values(
+                        logs.iter().map(|log| log.block_hash.as_slice()),
+                    )),
+                    Arc::new(BinaryArray::from_iter_values(
+                        logs.iter().map(|log| log.transaction_hash.as_slice()),
+                    )),
+                    Arc::new(UInt64Array::from_iter_values(
+                        logs.iter().map(|log| log.transaction_index),
+                    )),
+                    Arc::new(UInt64Array::from_iter_values(logs.iter().map(|log| log.log_index))),
+                    Arc::new(BinaryArray::from_iter_values(
+                        logs.iter().map(|log| log.address.as_slice()),
+                    )),
+                    topic(0),
+                    topic(1),
+                    topic(2),
+                    topic(3),
+                    Arc::new(BinaryArray::from_iter_values(
+                        logs.iter().map(|log| log.data.as_ref()),
+                    )),
+                    Arc::new(UInt8Array::from_iter_values(
+                        logs.iter().map(|log| log.transaction_type),
+                    )),
+                    Arc::new(BooleanArray::from(
+                        logs.iter().map(|log| log.status).collect::<Vec<_>>(),
+                    )),
+                    Arc::new(UInt64Array::from_iter_values(
+                        logs.iter().map(|log| log.cumulative_gas_used),
+                    )),
+                ];
+                writer.write(&RecordBatch::try_new(schema.clone(), columns)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Flushes all buffered logs and finalizes the file.
+    fn finish(self) -> eyre::Result<()> {
+        match self {
+            Self::Jsonl(mut writer) => writer.flush()?,
+            Self::Parquet { writer, .. } => {
+                writer.close()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The columns of the Parquet export. Hashes, addresses and data are stored as raw bytes.
+fn parquet_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("block_number", DataType::UInt64, false),
+        Field::new("block_hash", DataType::Binary, false),
+        Field::new("transaction_hash", DataType::Binary, false),
+        Field::new("transaction_index", DataType::UInt64, false),
+        Field::new("log_index", DataType::UInt64, false),
+        Field::new("address", DataType::Binary, false),
+        Field::new("topic0", DataType::Binary, true),
+        Field::new("topic1", DataType::Binary, true),
+        Field::new("topic2", DataType::Binary, true),
+        Field::new("topic3", DataType::Binary, true),
+        Field::new("data", DataType::Binary, false),
+        Field::new("transaction_type", DataType::UInt8, false),
+        Field::new("status", DataType::Boolean, false),
+        Field::new("cumulative_gas_used", DataType::UInt64, false),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_receipts_filter() {
+        let address = Address::with_last_byte(1);
+        let topic = B256::with_last_byte(2);
+        let command = Command::try_parse_from([
+            "reth",
+            "--address",
+            &address.to_string(),
+            "--topic0",
+            &topic.to_string(),
+            "--from",
+            "1",
+            "--to",
+            "10",
+            "--out",
+            "logs.parquet",
+            "--format",
+            "parquet",
+        ])
+        .unwrap();
+        assert_eq!(command.format, ExportFormat::Parquet);
+
+        let log = Log::new_unchecked(address, vec![topic], Bytes::new());
+        assert!(command.log_matches(&log));
+        assert!(!command.log_matches(&Log::new_unchecked(address, vec![], Bytes::new())));
+
+        let mut bloom = Bloom::default();
+        bloom.accrue_log(&log);
+        assert!(command.bloom_matches(&bloom));
+        assert!(!command.bloom_matches(&Bloom::default()));
+    }
+}
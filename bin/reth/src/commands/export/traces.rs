@@ -0,0 +1,567 @@
+//! Command that re-executes a range of blocks and exports their traces into a SQLite database.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use eyre::{eyre, WrapErr};
+use rayon::prelude::*;
+use reth_db::{open_db_read_only, DatabaseEnv};
+use reth_interfaces::provider::ProviderError;
+use reth_node_api::ConfigureEvm;
+use reth_node_ethereum::EthEvmConfig;
+use reth_primitives::{
+    revm::env::tx_env_with_recovered, Address, BlockNumber, Bytes, ChainSpec, Log, B256, U256,
+};
+use reth_provider::{
+    BlockReader, ChainSpecProvider, EvmEnvProvider, ProviderFactory, TransactionVariant,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    primitives::{EnvWithHandlerCfg, ResultAndState, State},
+    state_change::apply_beacon_root_contract_call,
+    tracing::{TracingInspector, TracingInspectorConfig},
+    DatabaseCommit, DatabaseRef,
+};
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use std::{path::PathBuf, sync::Arc, time::Instant};
+use tracing::info;
+
+/// The schema of the exported traces.
+///
+/// Addresses, hashes and 256-bit values are stored as big-endian blobs. A block is only written
+/// together with all of its traces, so the `blocks` table doubles as the export progress.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS blocks (
+    number INTEGER PRIMARY KEY,
+    hash BLOB NOT NULL,
+    timestamp INTEGER NOT NULL,
+    tx_count INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS transactions (
+    block_number INTEGER NOT NULL,
+    tx_index INTEGER NOT NULL,
+    hash BLOB NOT NULL,
+    sender BLOB NOT NULL,
+    recipient BLOB,
+    success INTEGER NOT NULL,
+    gas_used INTEGER NOT NULL,
+    PRIMARY KEY (block_number, tx_index)
+);
+CREATE TABLE IF NOT EXISTS calls (
+    block_number INTEGER NOT NULL,
+    tx_index INTEGER NOT NULL,
+    call_index INTEGER NOT NULL,
+    parent_index INTEGER,
+    depth INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    caller BLOB NOT NULL,
+    address BLOB NOT NULL,
+    value BLOB NOT NULL,
+    gas_limit INTEGER NOT NULL,
+    gas_used INTEGER NOT NULL,
+    input BLOB NOT NULL,
+    output BLOB NOT NULL,
+    success INTEGER NOT NULL,
+    PRIMARY KEY (block_number, tx_index, call_index)
+);
+CREATE TABLE IF NOT EXISTS logs (
+    block_number INTEGER NOT NULL,
+    tx_index INTEGER NOT NULL,
+    log_index INTEGER NOT NULL,
+    address BLOB NOT NULL,
+    topic0 BLOB,
+    topic1 BLOB,
+    topic2 BLOB,
+    topic3 BLOB,
+    data BLOB NOT NULL,
+    PRIMARY KEY (block_number, tx_index, log_index)
+);
+CREATE TABLE IF NOT EXISTS state_diffs (
+    block_number INTEGER NOT NULL,
+    tx_index INTEGER NOT NULL,
+    address BLOB NOT NULL,
+    kind TEXT NOT NULL,
+    slot BLOB,
+    old_value BLOB,
+    new_value BLOB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS calls_address ON calls (address);
+CREATE INDEX IF NOT EXISTS logs_address_topic0 ON logs (address, topic0);
+CREATE INDEX IF NOT EXISTS state_diffs_address ON state_diffs (address);
+CREATE INDEX IF NOT EXISTS state_diffs_block ON state_diffs (block_number, tx_index);
+";
+
+/// `reth export traces` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// The first block of the range to export.
+    #[arg(long, value_name = "BLOCK")]
+    from: BlockNumber,
+
+    /// The last block of the range to export, inclusive.
+    #[arg(long, value_name = "BLOCK")]
+    to: BlockNumber,
+
+    /// The path of the SQLite database to write the traces to.
+    ///
+    /// If the database already contains blocks of the range, the export resumes after the
+    /// highest exported block.
+    #[arg(long, value_name = "PATH")]
+    out: PathBuf,
+
+    /// Number of blocks that are executed in parallel and written in a single database
+    /// transaction.
+    #[arg(long, value_name = "BLOCKS", default_value_t = 100)]
+    batch_size: u64,
+
+    /// Number of threads used to execute blocks. Defaults to the number of CPUs.
+    #[arg(long, value_name = "COUNT")]
+    jobs: Option<usize>,
+}
+
+impl Command {
+    /// Execute `export traces` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        if self.from > self.to {
+            eyre::bail!("Invalid block range: {}..={}", self.from, self.to)
+        }
+
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = open_db_read_only(&data_dir.db_path(), self.db.database_args())?;
+        let factory = ProviderFactory::new(db, self.chain.clone(), data_dir.static_files_path())?;
+        let evm_config = EthEvmConfig::new(&self.chain)?;
+
+        let mut connection = Connection::open(&self.out)
+            .wrap_err_with(|| format!("Failed to open {}", self.out.display()))?;
+        connection.execute_batch("PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")?;
+        connection.execute_batch(SCHEMA)?;
+
+        let start = match highest_exported_block(&connection, self.from, self.to)? {
+            Some(highest) => {
+                info!(target: "reth::cli", highest, "Resuming export");
+                highest + 1
+            }
+            None => self.from,
+        };
+
+        let mut pool = rayon::ThreadPoolBuilder::new();
+        if let Some(jobs) = self.jobs {
+            pool = pool.num_threads(jobs);
+        }
+        let pool = pool.build()?;
+
+        let started_at = Instant::now();
+        let mut batch_start = start;
+        while batch_start <= self.to {
+            let batch_end = self.to.min(batch_start.saturating_add(self.batch_size.max(1) - 1));
+
+            let blocks = pool.install(|| {
+                (batch_start..=batch_end)
+                    .into_par_iter()
+                    .map(|number| trace_block(&factory, &evm_config, number))
+                    .collect::<eyre::Result<Vec<_>>>()
+            })?;
+
+            let tx = connection.transaction()?;
+            for block in &blocks {
+                write_block(&tx, block)?;
+            }
+            tx.commit()?;
+
+            info!(
+                target: "reth::cli",
+                from = batch_start,
+                to = batch_end,
+                remaining = self.to - batch_end,
+                elapsed = ?started_at.elapsed(),
+                "Exported traces"
+            );
+            batch_start = batch_end + 1;
+        }
+
+        info!(target: "reth::cli", out = %self.out.display(), "Finished export");
+        Ok(())
+    }
+}
+
+/// The traces of a block.
+struct BlockTraces {
+    number: BlockNumber,
+    hash: B256,
+    timestamp: u64,
+    transactions: Vec<TransactionTraces>,
+}
+
+/// The traces of a transaction.
+struct TransactionTraces {
+    hash: B256,
+    sender: Address,
+    recipient: Option<Address>,
+    success: bool,
+    gas_used: u64,
+    calls: Vec<CallFrame>,
+    logs: Vec<Log>,
+    state_diffs: Vec<StateDiff>,
+}
+
+/// A call frame of a transaction, in the order of execution.
+struct CallFrame {
+    parent: Option<usize>,
+    depth: usize,
+    kind: String,
+    caller: Address,
+    address: Address,
+    value: U256,
+    gas_limit: u64,
+    gas_used: u64,
+    input: Bytes,
+    output: Bytes,
+    success: bool,
+}
+
+/// A changed value of an account.
+struct StateDiff {
+    address: Address,
+    kind: &'static str,
+    slot: Option<U256>,
+    /// `None` if the account didn't exist before.
+    old_value: Option<B256>,
+    new_value: B256,
+}
+
+/// Re-executes the block on top of the state of its parent and collects the traces of all
+/// transactions.
+fn trace_block(
+    factory: &ProviderFactory<DatabaseEnv>,
+    evm_config: &EthEvmConfig,
+    number: BlockNumber,
+) -> eyre::Result<BlockTraces> {
+    let provider = factory.provider()?;
+    let block = provider
+        .block_with_senders(number.into(), TransactionVariant::WithHash)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+
+    let mut traces = BlockTraces {
+        number,
+        hash: block.header.hash_slow(),
+        timestamp: block.timestamp,
+        transactions: Vec::with_capacity(block.body.len()),
+    };
+    if block.body.is_empty() {
+        return Ok(traces)
+    }
+
+    let (cfg, block_env) = provider.env_with_header(&block.header, evm_config.clone())?;
+    let state = factory.history_by_block_number(number - 1)?;
+    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+    // apply the EIP-4788 pre block contract call, like the block executor does
+    let env =
+        EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), Default::default());
+    let mut evm = evm_config.evm_with_env(&mut db, env);
+    apply_beacon_root_contract_call(
+        &factory.chain_spec(),
+        block.timestamp,
+        number,
+        block.parent_beacon_block_root,
+        &mut evm,
+    )?;
+    drop(evm);
+
+    for tx in block.into_transactions_ecrecovered() {
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            cfg.clone(),
+            block_env.clone(),
+            tx_env_with_recovered(&tx),
+        );
+        let mut inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+        let mut evm = evm_config.evm_with_env_and_inspector(&mut db, env, &mut inspector);
+        let ResultAndState { result, state } =
+            evm.transact().map_err(|err| eyre!("failed to execute transaction: {err}"))?;
+        drop(evm);
+
+        let calls = inspector
+            .into_traces()
+            .into_nodes()
+            .into_iter()
+            .map(|node| CallFrame {
+                parent: node.parent,
+                depth: node.trace.depth,
+                kind: node.trace.kind.to_string(),
+                caller: node.trace.caller,
+                address: node.trace.address,
+                value: node.trace.value,
+                gas_limit: node.trace.gas_limit,
+                gas_used: node.trace.gas_used,
+                input: node.trace.data,
+                output: node.trace.output,
+                success: node.trace.success,
+            })
+            .collect();
+
+        traces.transactions.push(TransactionTraces {
+            hash: tx.hash(),
+            sender: tx.signer(),
+            recipient: tx.to(),
+            success: result.is_success(),
+            gas_used: result.gas_used(),
+            calls,
+            logs: result.into_logs(),
+            state_diffs: state_diffs(&db, &state)?,
+        });
+
+        db.commit(state);
+    }
+
+    Ok(traces)
+}
+
+/// Returns the changed balances, nonces, code and storage of the transaction's state changes,
+/// compared to the state before the transaction.
+fn state_diffs<DB: DatabaseRef>(db: &CacheDB<DB>, state: &State) -> eyre::Result<Vec<StateDiff>>
+where
+    DB::Error: std::fmt::Display,
+{
+    let mut diffs = Vec::new();
+    for (address, account) in state {
+        if !account.is_touched() {
+            continue
+        }
+
+        let old = db.basic_ref(*address).map_err(|err| eyre!("failed to load account: {err}"))?;
+        let new = &account.info;
+        let mut diff = |kind, old_value: Option<B256>, new_value: B256| {
+            if old_value != Some(new_value) {
+                diffs.push(StateDiff { address: *address, kind, slot: None, old_value, new_value });
+            }
+        };
+        diff("balance", old.as_ref().map(|old| old.balance.into()), new.balance.into());
+        diff(
+            "nonce",
+            old.as_ref().map(|old| U256::from(old.nonce).into()),
+            U256::from(new.nonce).into(),
+        );
+        diff("code", old.as_ref().map(|old| old.code_hash), new.code_hash);
+
+        for (slot, value) in account.storage.iter().filter(|(_, value)| value.is_changed()) {
+            diffs.push(StateDiff {
+                address: *address,
+                kind: "storage",
+                slot: Some(*slot),
+                old_value: Some(value.previous_or_original_value.into()),
+                new_value: value.present_value.into(),
+            });
+        }
+    }
+    Ok(diffs)
+}
+
+/// Returns the highest block of the range that was already exported.
+fn highest_exported_block(
+    connection: &Connection,
+    from: BlockNumber,
+    to: BlockNumber,
+) -> rusqlite::Result<Option<BlockNumber>> {
+    Ok(connection
+        .query_row(
+            "SELECT MAX(number) FROM blocks WHERE number BETWEEN ?1 AND ?2",
+            params![from, to],
+            |row| row.get::<_, Option<u64>>(0),
+        )
+        .optional()?
+        .flatten())
+}
+
+/// Writes the traces of the block.
+fn write_block(tx: &Transaction<'_>, block: &BlockTraces) -> rusqlite::Result<()> {
+    tx.prepare_cached(
+        "INSERT OR REPLACE INTO blocks (number, hash, timestamp, tx_count) VALUES (?1, ?2, ?3, ?4)",
+    )?
+    .execute(params![
+        block.number,
+        block.hash.as_slice(),
+        block.timestamp,
+        block.transactions.len()
+    ])?;
+
+    for (tx_index, transaction) in block.transactions.iter().enumerate() {
+        tx.prepare_cached(
+            "INSERT OR REPLACE INTO transactions \
+             (block_number, tx_index, hash, sender, recipient, success, gas_used) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?
+        .execute(params![
+            block.number,
+            tx_index,
+            transaction.hash.as_slice(),
+            transaction.sender.as_slice(),
+            transaction.recipient.as_ref().map(|recipient| recipient.as_slice()),
+            transaction.success,
+            transaction.gas_used,
+        ])?;
+
+        let mut insert_call = tx.prepare_cached(
+            "INSERT OR REPLACE INTO calls \
+             (block_number, tx_index, call_index, parent_index, depth, kind, caller, address, \
+             value, gas_limit, gas_used, input, output, success) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+        )?;
+        for (call_index, call) in transaction.calls.iter().enumerate() {
+            insert_call.execute(params![
+                block.number,
+                tx_index,
+                call_index,
+                call.parent,
+                call.depth,
+                call.kind,
+                call.caller.as_slice(),
+                call.address.as_slice(),
+                B256::from(call.value).as_slice(),
+                call.gas_limit,
+                call.gas_used,
+                call.input.as_ref(),
+                call.output.as_ref(),
+                call.success,
+            ])?;
+        }
+
+        let mut insert_log = tx.prepare_cached(
+            "INSERT OR REPLACE INTO logs \
+             (block_number, tx_index, log_index, address, topic0, topic1, topic2, topic3, data) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        )?;
+        for (log_index, log) in transaction.logs.iter().enumerate() {
+            let topic = |index: usize| log.topics().get(index).map(|topic| topic.as_slice());
+            insert_log.execute(params![
+                block.number,
+                tx_index,
+                log_index,
+                log.address.as_slice(),
+                topic(0),
+                topic(1),
+                topic(2),
+                topic(3),
+                log.data.data.as_ref(),
+            ])?;
+        }
+
+        let mut insert_state_diff = tx.prepare_cached(
+            "INSERT INTO state_diffs \
+             (block_number, tx_index, address, kind, slot, old_value, new_value) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        )?;
+        for diff in &transaction.state_diffs {
+            insert_state_diff.execute(params![
+                block.number,
+                tx_index,
+                diff.address.as_slice(),
+                diff.kind,
+                diff.slot.map(B256::from).as_ref().map(|slot| slot.as_slice()),
+                diff.old_value.as_ref().map(|value| value.as_slice()),
+                diff.new_value.as_slice(),
+            ])?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_revm::{
+        db::EmptyDB,
+        primitives::{Account, AccountInfo, StorageSlot},
+    };
+
+    #[test]
+    fn parse_traces_args() {
+        let command = Command::try_parse_from([
+            "reth",
+            "--from",
+            "1",
+            "--to",
+            "10",
+            "--out",
+            "traces.db",
+            "--batch-size",
+            "5",
+            "--jobs",
+            "2",
+        ])
+        .unwrap();
+        assert_eq!((command.from, command.to), (1, 10));
+        assert_eq!(command.batch_size, 5);
+        assert_eq!(command.jobs, Some(2));
+    }
+
+    #[test]
+    fn only_changed_values_are_diffed() {
+        let changed = Address::with_last_byte(1);
+        let untouched = Address::with_last_byte(2);
+        let mut db = CacheDB::new(EmptyDB::default());
+        let info = AccountInfo { balance: U256::from(1), nonce: 1, ..Default::default() };
+        db.insert_account_info(changed, info.clone());
+
+        let mut account = Account::from(AccountInfo { balance: U256::from(2), ..info.clone() });
+        account.storage.insert(U256::from(1), StorageSlot::new_changed(U256::ZERO, U256::from(3)));
+        account.storage.insert(U256::from(2), StorageSlot::new(U256::from(4)));
+        account.mark_touch();
+        let state = State::from_iter([(changed, account), (untouched, Account::from(info))]);
+
+        let diffs = state_diffs(&db, &state).unwrap();
+        let kinds = diffs.iter().map(|diff| (diff.address, diff.kind)).collect::<Vec<_>>();
+        assert_eq!(kinds, vec![(changed, "balance"), (changed, "storage")]);
+        assert_eq!(diffs[1].slot, Some(U256::from(1)));
+    }
+
+    #[test]
+    fn resume_after_highest_exported_block() {
+        let mut connection = Connection::open_in_memory().unwrap();
+        connection.execute_batch(SCHEMA).unwrap();
+        assert_eq!(highest_exported_block(&connection, 1, 10).unwrap(), None);
+
+        let tx = connection.transaction().unwrap();
+        for number in [5, 20] {
+            let block =
+                BlockTraces { number, hash: B256::ZERO, timestamp: 0, transactions: Vec::new() };
+            write_block(&tx, &block).unwrap();
+        }
+        tx.commit().unwrap();
+
+        // blocks outside of the range are ignored
+        assert_eq!(highest_exported_block(&connection, 1, 10).unwrap(), Some(5));
+    }
+}
@@ -235,8 +235,10 @@ impl ImportCommand {
             .expect("failed to set download range");
 
         let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
-        let factory =
-            reth_revm::EvmProcessorFactory::new(self.chain.clone(), EthEvmConfig::default());
+        let factory = reth_revm::EvmProcessorFactory::new(
+            self.chain.clone(),
+            EthEvmConfig::new(&self.chain)?,
+        );
 
         let max_block = file_client.max_block().unwrap_or(0);
 
@@ -1,15 +1,21 @@
 //! This contains all of the `reth` commands
 
+pub mod analyze;
 pub mod config_cmd;
+pub mod count_opcodes;
 pub mod db;
 pub mod debug_cmd;
 pub mod dump_genesis;
+pub mod export;
 pub mod import;
 
 pub mod init_cmd;
 
 pub mod node;
 pub mod p2p;
+pub mod query;
 pub mod recover;
 pub mod stage;
+pub mod state;
 pub mod test_vectors;
+pub mod validate;
@@ -3,8 +3,8 @@
 use crate::{
     args::{
         utils::{chain_help, genesis_value_parser, parse_socket_address, SUPPORTED_CHAINS},
-        DatabaseArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs,
-        RpcServerArgs, TxPoolArgs,
+        DatabaseArgs, DebugArgs, DevArgs, EngineArgs, ExExArgs, NetworkArgs, PayloadBuilderArgs,
+        PruningArgs, RpcServerArgs, StaticFilesArgs, TxPoolArgs,
     },
     dirs::{DataDirPath, MaybePlatformPath},
 };
@@ -100,6 +100,10 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[command(flatten)]
     pub db: DatabaseArgs,
 
+    /// All static files related arguments
+    #[command(flatten)]
+    pub static_files: StaticFilesArgs,
+
     /// All dev related arguments with --dev prefix
     #[command(flatten)]
     pub dev: DevArgs,
@@ -108,6 +112,14 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[command(flatten)]
     pub pruning: PruningArgs,
 
+    /// All engine related arguments
+    #[command(flatten)]
+    pub engine: EngineArgs,
+
+    /// All built-in execution extensions related arguments
+    #[command(flatten)]
+    pub exex: ExExArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -154,8 +166,11 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             builder,
             debug,
             db,
+            static_files,
             dev,
             pruning,
+            engine,
+            exex,
             ext,
         } = self;
 
@@ -171,8 +186,11 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             builder,
             debug,
             db,
+            static_files,
             dev,
             pruning,
+            engine,
+            exex,
         };
 
         // Register the prometheus recorder before creating the database,
@@ -0,0 +1,196 @@
+//! Command that executes a call against the state of a block in the database.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use eyre::eyre;
+use reth_db::open_db_read_only;
+use reth_interfaces::provider::ProviderError;
+use reth_node_api::ConfigureEvm;
+use reth_node_ethereum::EthEvmConfig;
+use reth_primitives::{Address, BlockNumber, Bytes, ChainSpec, Log, U256};
+use reth_provider::{BlockNumReader, EvmEnvProvider, HeaderProvider, ProviderFactory};
+use reth_revm::{
+    database::StateProviderDatabase,
+    primitives::{EnvWithHandlerCfg, ExecutionResult, TransactTo, TxEnv},
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// `reth query call` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// The block whose state the call is executed on. Defaults to the latest block.
+    #[arg(long, value_name = "BLOCK")]
+    at: Option<BlockNumber>,
+
+    /// The address of the caller.
+    #[arg(long, value_name = "ADDRESS", default_value_t = Address::ZERO)]
+    from: Address,
+
+    /// The address of the called contract.
+    #[arg(long, value_name = "ADDRESS")]
+    to: Address,
+
+    /// The hex encoded calldata.
+    #[arg(long, value_name = "HEX", default_value_t)]
+    data: Bytes,
+
+    /// The value sent with the call, in wei.
+    #[arg(long, value_name = "WEI", default_value_t)]
+    value: U256,
+
+    /// The gas limit of the call. Defaults to the gas limit of the block.
+    #[arg(long, value_name = "GAS")]
+    gas: Option<u64>,
+}
+
+impl Command {
+    /// Execute `query call` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = open_db_read_only(&data_dir.db_path(), self.db.database_args())?;
+        let factory = ProviderFactory::new(db, self.chain.clone(), data_dir.static_files_path())?;
+        let provider = factory.provider()?;
+
+        let block = match self.at {
+            Some(block) => block,
+            None => provider.best_block_number()?,
+        };
+        let header =
+            provider.header_by_number(block)?.ok_or(ProviderError::HeaderNotFound(block.into()))?;
+
+        let evm_config = EthEvmConfig::new(&self.chain)?;
+        let (mut cfg, block_env) = provider.env_with_header(&header, evm_config.clone())?;
+        // same relaxations as `eth_call`
+        cfg.disable_block_gas_limit = true;
+        cfg.disable_eip3607 = true;
+        cfg.disable_base_fee = true;
+
+        let tx = TxEnv {
+            caller: self.from,
+            gas_limit: self.gas.unwrap_or(header.gas_limit),
+            gas_price: U256::ZERO,
+            transact_to: TransactTo::Call(self.to),
+            value: self.value,
+            data: self.data,
+            chain_id: Some(self.chain.chain.id()),
+            nonce: None,
+            ..Default::default()
+        };
+
+        let state = factory.history_by_block_number(block)?;
+        let mut evm = evm_config.evm_with_env(
+            StateProviderDatabase::new(state),
+            EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, tx),
+        );
+        let result = evm.transact().map_err(|err| eyre!("failed to execute call: {err}"))?.result;
+
+        println!("{}", serde_json::to_string_pretty(&CallOutput::new(block, result))?);
+        Ok(())
+    }
+}
+
+/// The printed result of the call.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CallOutput {
+    /// The block whose state the call was executed on.
+    block: BlockNumber,
+    /// Either `success`, `revert` or `halt`.
+    status: &'static str,
+    gas_used: u64,
+    /// The return data, or the revert data if the call reverted.
+    output: Bytes,
+    logs: Vec<Log>,
+    /// The reason the call halted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    halt_reason: Option<String>,
+}
+
+impl CallOutput {
+    fn new(block: BlockNumber, result: ExecutionResult) -> Self {
+        match result {
+            ExecutionResult::Success { gas_used, output, logs, .. } => Self {
+                block,
+                status: "success",
+                gas_used,
+                output: output.into_data(),
+                logs,
+                halt_reason: None,
+            },
+            ExecutionResult::Revert { gas_used, output } => Self {
+                block,
+                status: "revert",
+                gas_used,
+                output,
+                logs: Vec::new(),
+                halt_reason: None,
+            },
+            ExecutionResult::Halt { reason, gas_used } => Self {
+                block,
+                status: "halt",
+                gas_used,
+                output: Bytes::new(),
+                logs: Vec::new(),
+                halt_reason: Some(format!("{reason:?}")),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_call_args() {
+        let to = Address::with_last_byte(1);
+        let command = Command::try_parse_from([
+            "reth",
+            "--at",
+            "100",
+            "--to",
+            &to.to_string(),
+            "--data",
+            "0x70a08231",
+        ])
+        .unwrap();
+        assert_eq!(command.at, Some(100));
+        assert_eq!(command.to, to);
+        assert_eq!(command.from, Address::ZERO);
+        assert_eq!(command.data, Bytes::from_static(&[0x70, 0xa0, 0x82, 0x31]));
+        assert_eq!(command.gas, None);
+    }
+}
@@ -0,0 +1,28 @@
+//! `reth query` command.
+
+use clap::{Parser, Subcommand};
+
+mod call;
+
+/// `reth query` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth query` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Executes a call against the state of a block, like `eth_call`, without a running node.
+    Call(call::Command),
+}
+
+impl Command {
+    /// Execute `query` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Call(command) => command.execute().await,
+        }
+    }
+}
@@ -133,7 +133,7 @@ async fn unwind_and_copy<DB: Database>(
 
     let mut exec_stage = ExecutionStage::new_with_factory(EvmProcessorFactory::new(
         db_tool.chain.clone(),
-        EthEvmConfig::default(),
+        EthEvmConfig::new(&db_tool.chain)?,
     ));
 
     exec_stage.unwind(
@@ -165,7 +165,7 @@ async fn dry_run<DB: Database>(
 
     let mut exec_stage = ExecutionStage::new_with_factory(EvmProcessorFactory::new(
         output_provider_factory.chain_spec(),
-        EthEvmConfig::default(),
+        EthEvmConfig::new(&output_provider_factory.chain_spec())?,
     ));
 
     let input =
@@ -87,7 +87,10 @@ async fn unwind_and_copy<DB: Database>(
 
     // Bring Plainstate to TO (hashing stage execution requires it)
     let mut exec_stage = ExecutionStage::new(
-        reth_revm::EvmProcessorFactory::new(db_tool.chain.clone(), EthEvmConfig::default()),
+        reth_revm::EvmProcessorFactory::new(
+            db_tool.chain.clone(),
+            EthEvmConfig::new(&db_tool.chain)?,
+        ),
         ExecutionStageThresholds {
             max_blocks: Some(u64::MAX),
             max_changes: None,
@@ -229,7 +229,7 @@ impl Command {
                 StageEnum::Execution => {
                     let factory = reth_revm::EvmProcessorFactory::new(
                         self.chain.clone(),
-                        EthEvmConfig::default(),
+                        EthEvmConfig::new(&self.chain)?,
                     );
                     (
                         Box::new(ExecutionStage::new(
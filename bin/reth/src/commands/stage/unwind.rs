@@ -179,7 +179,7 @@ impl Command {
         let (tip_tx, tip_rx) = watch::channel(B256::ZERO);
         let factory = reth_revm::EvmProcessorFactory::new(
             provider_factory.chain_spec(),
-            EthEvmConfig::default(),
+            EthEvmConfig::new(&provider_factory.chain_spec())?,
         );
 
         let header_mode = HeaderSyncMode::Tip(tip_rx);
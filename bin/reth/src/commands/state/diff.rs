@@ -0,0 +1,297 @@
+//! Command that lists the storage slots of an account that changed between two blocks.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use eyre::eyre;
+use reth_db::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress},
+    open_db_read_only, tables,
+    transaction::DbTx,
+    DatabaseEnv,
+};
+use reth_interfaces::provider::ProviderError;
+use reth_node_api::ConfigureEvm;
+use reth_node_ethereum::EthEvmConfig;
+use reth_primitives::{
+    revm::env::tx_env_with_recovered, Address, BlockNumber, ChainSpec, B256, U256,
+};
+use reth_provider::{
+    BlockReader, ChainSpecProvider, EvmEnvProvider, ProviderFactory, StateProvider,
+    TransactionVariant,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    primitives::{EnvWithHandlerCfg, ResultAndState},
+    state_change::apply_beacon_root_contract_call,
+    DatabaseCommit,
+};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
+use tracing::info;
+
+/// `reth state diff` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// The account whose storage is compared.
+    #[arg(long, value_name = "ADDRESS")]
+    address: Address,
+
+    /// The block whose state is compared against.
+    #[arg(long, value_name = "BLOCK")]
+    from: BlockNumber,
+
+    /// The block whose state is compared, changes of all blocks after `--from` up to and
+    /// including this block are listed.
+    #[arg(long, value_name = "BLOCK")]
+    to: BlockNumber,
+}
+
+impl Command {
+    /// Execute `state diff` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        if self.from >= self.to {
+            eyre::bail!("Invalid block range: {}..={}", self.from, self.to)
+        }
+
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = open_db_read_only(&data_dir.db_path(), self.db.database_args())?;
+        let factory = ProviderFactory::new(db, self.chain.clone(), data_dir.static_files_path())?;
+        let provider = factory.provider()?;
+
+        let changed_blocks = changed_blocks(provider.tx_ref(), self.address, self.from, self.to)?;
+        let blocks = changed_blocks.values().flatten().copied().collect::<BTreeSet<_>>();
+        info!(
+            target: "reth::cli",
+            slots = changed_blocks.len(),
+            blocks = blocks.len(),
+            "Resolving storage changes"
+        );
+
+        // attribute the changes of each block to its transactions
+        let mut changes = BTreeMap::<B256, Vec<SlotChange>>::new();
+        for block in blocks {
+            let slots = changed_blocks
+                .iter()
+                .filter(|(_, blocks)| blocks.contains(&block))
+                .map(|(slot, _)| *slot)
+                .collect::<BTreeSet<_>>();
+
+            let mut unattributed = slots.clone();
+            for (slot, change) in block_changes(&factory, block, self.address, &slots)? {
+                unattributed.remove(&slot);
+                changes.entry(slot).or_default().push(change);
+            }
+
+            // changes that were not made by a transaction, e.g. by a system call
+            if !unattributed.is_empty() {
+                let mut changesets =
+                    provider.tx_ref().cursor_dup_read::<tables::StorageChangeSets>()?;
+                let state = factory.history_by_block_number(block)?;
+                for slot in unattributed {
+                    let old_value = changesets
+                        .seek_by_key_subkey(BlockNumberAddress((block, self.address)), slot)?
+                        .filter(|entry| entry.key == slot)
+                        .map(|entry| entry.value)
+                        .unwrap_or_default();
+                    let new_value = state.storage(self.address, slot)?.unwrap_or_default();
+                    changes.entry(slot).or_default().push(SlotChange {
+                        block,
+                        transaction_index: None,
+                        transaction_hash: None,
+                        old_value,
+                        new_value,
+                    });
+                }
+            }
+        }
+
+        let from_state = factory.history_by_block_number(self.from)?;
+        let to_state = factory.history_by_block_number(self.to)?;
+        let mut slots = Vec::with_capacity(changes.len());
+        for (slot, changes) in changes {
+            slots.push(SlotDiff {
+                slot,
+                old_value: from_state.storage(self.address, slot)?.unwrap_or_default(),
+                new_value: to_state.storage(self.address, slot)?.unwrap_or_default(),
+                changes,
+            });
+        }
+
+        let diff = StorageDiff { address: self.address, from: self.from, to: self.to, slots };
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+        Ok(())
+    }
+}
+
+/// The printed storage diff of the account.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StorageDiff {
+    address: Address,
+    from: BlockNumber,
+    to: BlockNumber,
+    slots: Vec<SlotDiff>,
+}
+
+/// A storage slot that was changed in the block range.
+///
+/// The values of the slot can be equal if the slot was changed back within the range.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SlotDiff {
+    slot: B256,
+    /// The value at the `--from` block.
+    old_value: U256,
+    /// The value at the `--to` block.
+    new_value: U256,
+    /// All changes of the slot in the block range, in order.
+    changes: Vec<SlotChange>,
+}
+
+/// A change of a storage slot.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SlotChange {
+    block: BlockNumber,
+    /// The index of the transaction that changed the slot, `None` if it was changed outside of
+    /// a transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_index: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transaction_hash: Option<B256>,
+    old_value: U256,
+    new_value: U256,
+}
+
+/// Returns the blocks in the range `(from, to]` in which each storage slot of the account changed,
+/// according to the storage history index.
+fn changed_blocks<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    from: BlockNumber,
+    to: BlockNumber,
+) -> eyre::Result<BTreeMap<B256, BTreeSet<BlockNumber>>> {
+    let mut changed = BTreeMap::<B256, BTreeSet<BlockNumber>>::new();
+
+    // the shards of a slot are keyed by the highest block number they contain
+    let mut history = tx.cursor_read::<tables::StoragesHistory>()?;
+    for entry in history.walk(Some(StorageShardedKey::new(address, B256::ZERO, 0)))? {
+        let (key, blocks) = entry?;
+        if key.address != address {
+            break
+        }
+        if key.sharded_key.highest_block_number <= from {
+            continue
+        }
+
+        let blocks = blocks.iter().filter(|block| *block > from && *block <= to);
+        changed.entry(key.sharded_key.key).or_default().extend(blocks);
+    }
+
+    changed.retain(|_, blocks| !blocks.is_empty());
+    Ok(changed)
+}
+
+/// Re-executes the block and returns the changes of the given storage slots of the account, with
+/// the transactions that made them.
+fn block_changes(
+    factory: &ProviderFactory<DatabaseEnv>,
+    number: BlockNumber,
+    address: Address,
+    slots: &BTreeSet<B256>,
+) -> eyre::Result<Vec<(B256, SlotChange)>> {
+    let provider = factory.provider()?;
+    let block = provider
+        .block_with_senders(number.into(), TransactionVariant::WithHash)?
+        .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+
+    let evm_config = EthEvmConfig::new(&factory.chain_spec())?;
+    let (cfg, block_env) = provider.env_with_header(&block.header, evm_config.clone())?;
+    let state = factory.history_by_block_number(number - 1)?;
+    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+    // apply the EIP-4788 pre block contract call, like the block executor does
+    let env =
+        EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), Default::default());
+    let mut evm = evm_config.evm_with_env(&mut db, env);
+    apply_beacon_root_contract_call(
+        &factory.chain_spec(),
+        block.timestamp,
+        number,
+        block.parent_beacon_block_root,
+        &mut evm,
+    )?;
+    drop(evm);
+
+    let mut changes = Vec::new();
+    for (index, tx) in block.into_transactions_ecrecovered().enumerate() {
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            cfg.clone(),
+            block_env.clone(),
+            tx_env_with_recovered(&tx),
+        );
+        let mut evm = evm_config.evm_with_env(&mut db, env);
+        let ResultAndState { state, .. } =
+            evm.transact().map_err(|err| eyre!("failed to execute transaction: {err}"))?;
+        drop(evm);
+
+        if let Some(account) = state.get(&address) {
+            for (slot, value) in &account.storage {
+                let slot = B256::from(*slot);
+                if value.is_changed() && slots.contains(&slot) {
+                    changes.push((
+                        slot,
+                        SlotChange {
+                            block: number,
+                            transaction_index: Some(index as u64),
+                            transaction_hash: Some(tx.hash()),
+                            old_value: value.previous_or_original_value,
+                            new_value: value.present_value,
+                        },
+                    ));
+                }
+            }
+        }
+
+        db.commit(state);
+    }
+
+    Ok(changes)
+}
@@ -0,0 +1,189 @@
+//! Command that prints the state of an account at a block.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use reth_db::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::storage_sharded_key::StorageShardedKey,
+    open_db_read_only, tables,
+    transaction::DbTx,
+};
+use reth_primitives::{Address, BlockNumber, ChainSpec, StorageValue, B256, KECCAK_EMPTY, U256};
+use reth_provider::{AccountReader, BlockNumReader, ProviderFactory, StateProvider};
+use reth_rpc_types::EIP1186AccountProofResponse;
+use reth_rpc_types_compat::proof::from_primitive_account_proof;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Bound,
+    sync::Arc,
+};
+
+/// `reth state dump` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// The account to print.
+    #[arg(long, value_name = "ADDRESS")]
+    address: Address,
+
+    /// The block whose state is printed. Defaults to the latest block.
+    #[arg(long, value_name = "BLOCK")]
+    at: Option<BlockNumber>,
+
+    /// Also print the non-zero storage slots of the account, in ascending order.
+    #[arg(long)]
+    storage: bool,
+
+    /// Only print storage slots after this slot. Use the `nextSlot` of the previous output to
+    /// print the next page.
+    #[arg(long, value_name = "SLOT", requires = "storage")]
+    after: Option<B256>,
+
+    /// Maximum number of storage slots to print.
+    #[arg(long, value_name = "COUNT", default_value_t = 1000, requires = "storage")]
+    limit: usize,
+
+    /// Also print the Merkle proof of the account and the printed storage slots.
+    ///
+    /// Proofs are only available for the latest block.
+    #[arg(long)]
+    proof: bool,
+}
+
+impl Command {
+    /// Execute `state dump` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = open_db_read_only(&data_dir.db_path(), self.db.database_args())?;
+        let factory = ProviderFactory::new(db, self.chain.clone(), data_dir.static_files_path())?;
+        let provider = factory.provider()?;
+
+        let block = match self.at {
+            Some(block) => block,
+            None => provider.best_block_number()?,
+        };
+        let state = factory.history_by_block_number(block)?;
+
+        let account = state.basic_account(self.address)?.unwrap_or_default();
+        let mut dump = AccountDump {
+            address: self.address,
+            block,
+            balance: account.balance,
+            nonce: account.nonce,
+            code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+            storage: None,
+            next_slot: None,
+            proof: None,
+        };
+
+        if self.storage {
+            let slots = storage_slots(provider.tx_ref(), self.address, block)?;
+            let lower = self.after.map_or(Bound::Unbounded, Bound::Excluded);
+
+            let mut storage = BTreeMap::new();
+            for slot in slots.range((lower, Bound::Unbounded)) {
+                let value = state.storage(self.address, *slot)?.unwrap_or_default();
+                if value.is_zero() {
+                    continue
+                }
+                if storage.len() == self.limit {
+                    dump.next_slot = storage.keys().next_back().copied();
+                    break
+                }
+                storage.insert(*slot, value);
+            }
+            dump.storage = Some(storage);
+        }
+
+        if self.proof {
+            let slots = dump.storage.iter().flat_map(|storage| storage.keys()).copied();
+            let proof = state.proof(self.address, &slots.collect::<Vec<_>>())?;
+            dump.proof = Some(from_primitive_account_proof(proof));
+        }
+
+        println!("{}", serde_json::to_string_pretty(&dump)?);
+        Ok(())
+    }
+}
+
+/// The printed state of the account.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountDump {
+    address: Address,
+    block: BlockNumber,
+    balance: U256,
+    nonce: u64,
+    code_hash: B256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage: Option<BTreeMap<B256, StorageValue>>,
+    /// The slot to continue with if there are more storage slots than printed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_slot: Option<B256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof: Option<EIP1186AccountProofResponse>,
+}
+
+/// Returns all storage slots of the account that may be non-zero at the given block.
+///
+/// These are the slots of the latest state and all slots that were changed after the block,
+/// because those may have been cleared since.
+fn storage_slots<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    block: BlockNumber,
+) -> eyre::Result<BTreeSet<B256>> {
+    let mut slots = BTreeSet::new();
+
+    let mut plain_storage = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    for entry in plain_storage.walk_dup(Some(address), None)? {
+        let (_, entry) = entry?;
+        slots.insert(entry.key);
+    }
+
+    // the shards of a slot are keyed by the highest block number they contain
+    let mut history = tx.cursor_read::<tables::StoragesHistory>()?;
+    for entry in history.walk(Some(StorageShardedKey::new(address, B256::ZERO, 0)))? {
+        let (key, _) = entry?;
+        if key.address != address {
+            break
+        }
+        if key.sharded_key.highest_block_number > block {
+            slots.insert(key.sharded_key.key);
+        }
+    }
+
+    Ok(slots)
+}
@@ -0,0 +1,33 @@
+//! `reth state` command.
+
+use clap::{Parser, Subcommand};
+
+mod diff;
+mod dump;
+
+/// `reth state` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth state` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Prints the state of an account at a block.
+    Dump(dump::Command),
+    /// Lists the storage slots of an account that changed between two blocks, with the
+    /// transactions that changed them.
+    Diff(diff::Command),
+}
+
+impl Command {
+    /// Execute `state` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Dump(command) => command.execute().await,
+            Subcommands::Diff(command) => command.execute().await,
+        }
+    }
+}
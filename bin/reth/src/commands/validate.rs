@@ -0,0 +1,247 @@
+//! Command that validates a range of blocks in the database against the consensus rules, without
+//! executing them.
+
+use crate::{
+    args::{
+        utils::{chain_help, genesis_value_parser, SUPPORTED_CHAINS},
+        DatabaseArgs,
+    },
+    dirs::{DataDirPath, MaybePlatformPath},
+};
+use clap::Parser;
+use reth_beacon_consensus::BeaconConsensus;
+use reth_consensus::Consensus;
+use reth_db::open_db_read_only;
+use reth_interfaces::provider::ProviderError;
+use reth_primitives::{BlockNumber, ChainSpec, SealedBlock, SealedHeader, U256};
+use reth_provider::{
+    BlockHashReader, BlockNumReader, BlockReader, HeaderProvider, ProviderFactory, ReceiptProvider,
+};
+use std::{sync::Arc, time::Instant};
+use tracing::{error, info};
+
+/// The number of blocks between two progress logs.
+const LOG_INTERVAL: u64 = 10_000;
+
+/// `reth validate` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the data dir for all reth files and subdirectories.
+    ///
+    /// Defaults to the OS-specific data directory:
+    ///
+    /// - Linux: `$XDG_DATA_HOME/reth/` or `$HOME/.local/share/reth/`
+    /// - Windows: `{FOLDERID_RoamingAppData}/reth/`
+    /// - macOS: `$HOME/Library/Application Support/reth/`
+    #[arg(long, value_name = "DATA_DIR", verbatim_doc_comment, default_value_t)]
+    datadir: MaybePlatformPath<DataDirPath>,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = genesis_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    /// All database related arguments
+    #[command(flatten)]
+    db: DatabaseArgs,
+
+    /// The first block to validate.
+    #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to validate. Defaults to the latest block.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<BlockNumber>,
+
+    /// Stop at the first block that fails validation.
+    #[arg(long)]
+    fail_fast: bool,
+}
+
+impl Command {
+    /// Execute `validate` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.unwrap_or_chain_default(self.chain.chain);
+        let db = open_db_read_only(&data_dir.db_path(), self.db.database_args())?;
+        let factory = ProviderFactory::new(db, self.chain.clone(), data_dir.static_files_path())?;
+        let provider = factory.provider()?;
+        let consensus = BeaconConsensus::new(self.chain.clone());
+
+        let best_block = provider.best_block_number()?;
+        let to = self.to.unwrap_or(best_block);
+        if self.from > to || to > best_block {
+            eyre::bail!("Invalid block range: {}..={to}, latest block is {best_block}", self.from)
+        }
+
+        info!(target: "reth::cli", from = self.from, to, "Validating blocks");
+        let start = Instant::now();
+
+        let mut parent = match self.from.checked_sub(1) {
+            Some(number) => Some(
+                provider
+                    .sealed_header(number)?
+                    .ok_or(ProviderError::HeaderNotFound(number.into()))?,
+            ),
+            None => None,
+        };
+        let mut total_difficulty = match &parent {
+            Some(parent) => provider
+                .header_td_by_number(parent.number)?
+                .ok_or(ProviderError::TotalDifficultyNotFound(parent.number))?,
+            None => U256::ZERO,
+        };
+
+        let mut invalid_blocks = 0;
+        let mut skipped_receipts = 0;
+        for number in self.from..=to {
+            let block = provider
+                .block(number.into())?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?
+                .seal_slow();
+            total_difficulty += block.difficulty;
+
+            let validation = validate_block(
+                &provider,
+                &consensus,
+                &self.chain,
+                &block,
+                parent.as_ref(),
+                total_difficulty,
+            )?;
+            if validation.receipts_skipped {
+                skipped_receipts += 1;
+            }
+            if !validation.errors.is_empty() {
+                invalid_blocks += 1;
+                for error in &validation.errors {
+                    error!(target: "reth::cli", block = number, hash = ?block.hash(), %error, "Block failed validation");
+                }
+                if self.fail_fast {
+                    break
+                }
+            }
+
+            if number % LOG_INTERVAL == 0 {
+                info!(target: "reth::cli", block = number, invalid_blocks, "Validated blocks");
+            }
+            parent = Some(block.header);
+        }
+
+        info!(
+            target: "reth::cli",
+            invalid_blocks,
+            skipped_receipts,
+            elapsed = ?start.elapsed(),
+            "Validation finished"
+        );
+        if invalid_blocks > 0 {
+            eyre::bail!("{invalid_blocks} blocks failed validation")
+        }
+        Ok(())
+    }
+}
+
+/// The result of validating a block.
+#[derive(Debug, Default)]
+struct BlockValidation {
+    /// The validation errors of the block.
+    errors: Vec<String>,
+    /// Whether the receipts were not validated because they are not available, e.g. pruned.
+    receipts_skipped: bool,
+}
+
+/// Validates the header, the body and the receipts of the block, without executing it.
+fn validate_block<Provider>(
+    provider: &Provider,
+    consensus: &BeaconConsensus,
+    chain_spec: &ChainSpec,
+    block: &SealedBlock,
+    parent: Option<&SealedHeader>,
+    total_difficulty: U256,
+) -> eyre::Result<BlockValidation>
+where
+    Provider: BlockHashReader + ReceiptProvider,
+{
+    let mut validation = BlockValidation::default();
+
+    let canonical_hash = provider.block_hash(block.number)?;
+    if canonical_hash != Some(block.hash()) {
+        validation.errors.push(format!(
+            "header hash {} does not match canonical hash {canonical_hash:?}",
+            block.hash()
+        ));
+    }
+
+    // the genesis block is only checked against the chain specification
+    let Some(parent) = parent else {
+        let genesis_hash = chain_spec.genesis_hash();
+        if block.hash() != genesis_hash {
+            validation
+                .errors
+                .push(format!("genesis hash does not match chain spec {genesis_hash}"));
+        }
+        return Ok(validation)
+    };
+
+    if let Err(error) = consensus.validate_header(&block.header) {
+        validation.errors.push(error.to_string());
+    }
+    if let Err(error) = consensus.validate_header_against_parent(&block.header, parent) {
+        validation.errors.push(error.to_string());
+    }
+    if let Err(error) =
+        consensus.validate_header_with_total_difficulty(&block.header, total_difficulty)
+    {
+        validation.errors.push(error.to_string());
+    }
+    // transactions root, ommers hash, withdrawals root and blob gas used
+    if let Err(error) = consensus.validate_block(block) {
+        validation.errors.push(error.to_string());
+    }
+
+    // receipts root and logs bloom
+    match provider.receipts_by_block(block.number.into())? {
+        Some(receipts) if receipts.len() == block.body.len() => {
+            #[cfg(not(feature = "optimism"))]
+            let res = reth_revm::processor::verify_receipt(
+                block.receipts_root,
+                block.logs_bloom,
+                receipts.iter(),
+            );
+            #[cfg(feature = "optimism")]
+            let res = reth_revm::optimism::processor::verify_receipt_optimism(
+                block.receipts_root,
+                block.logs_bloom,
+                receipts.iter(),
+                chain_spec,
+                block.timestamp,
+            );
+            if let Err(error) = res {
+                validation.errors.push(error.to_string());
+            }
+        }
+        _ => validation.receipts_skipped = true,
+    }
+
+    Ok(validation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_validate_args() {
+        let command = Command::try_parse_from(["reth", "--from", "10", "--to", "20"]).unwrap();
+        assert_eq!(command.from, 10);
+        assert_eq!(command.to, Some(20));
+        assert!(!command.fail_fast);
+    }
+}
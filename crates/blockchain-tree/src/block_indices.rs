@@ -229,6 +229,22 @@ impl BlockIndices {
             .collect()
     }
 
+    /// Remove a chain that no other chain forks from, including the link from its fork block to
+    /// its first block.
+    pub(crate) fn remove_leaf_chain(&mut self, chain: &Chain) {
+        let first = chain.first();
+        if let hash_map::Entry::Occupied(mut entry) = self.fork_to_child.entry(first.parent_hash) {
+            let set = entry.get_mut();
+            set.remove(&first.hash());
+            // remove set if empty
+            if set.is_empty() {
+                entry.remove();
+            }
+        }
+        let dependent_chains = self.remove_chain(chain);
+        debug_assert!(dependent_chains.is_empty(), "Leaf chain should have no dependent chains");
+    }
+
     /// Remove Blocks from indices.
     fn remove_block(
         &mut self,
@@ -3,7 +3,8 @@
 use crate::{
     metrics::{MakeCanonicalAction, MakeCanonicalDurationsRecorder, TreeMetrics},
     state::{BlockChainId, TreeState},
-    AppendableChain, BlockIndices, BlockchainTreeConfig, BundleStateData, TreeExternals,
+    AppendableChain, BlockIndices, BlockchainTreeConfig, BundleStateData, ChainSpill,
+    TreeExternals,
 };
 use reth_consensus::{Consensus, ConsensusError};
 use reth_db::database::Database;
@@ -18,18 +19,21 @@ use reth_interfaces::{
 };
 use reth_primitives::{
     BlockHash, BlockNumHash, BlockNumber, ForkBlock, GotExpected, Hardfork, PruneModes, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, U256,
+    ReorgRecord, SealedBlock, SealedBlockWithSenders, SealedHeader, U256,
 };
 use reth_provider::{
     chain::{ChainSplit, ChainSplitTarget},
     BlockExecutionWriter, BlockNumReader, BlockWriter, BundleStateWithReceipts,
     CanonStateNotification, CanonStateNotificationSender, CanonStateNotifications, Chain,
     ChainSpecProvider, DisplayBlocksChain, ExecutorFactory, HeaderProvider, ProviderError,
+    ReorgHistoryWriter,
 };
 use reth_stages_api::{MetricEvent, MetricEventsSender};
+use reth_trie::HashedPostState;
 use std::{
-    collections::{btree_map::Entry, BTreeMap, HashSet},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet, HashSet},
     sync::Arc,
+    time::{Instant, SystemTime, UNIX_EPOCH},
 };
 use tracing::{debug, error, info, instrument, trace, warn};
 
@@ -73,6 +77,8 @@ pub struct BlockchainTree<DB, EVM> {
     /// Metrics for sync stages.
     sync_metrics_tx: Option<MetricEventsSender>,
     prune_modes: Option<PruneModes>,
+    /// Sidechains that fell behind the canonical tip and were spilled to disk, if enabled.
+    spill: Option<ChainSpill>,
 }
 
 impl<DB, EVM> BlockchainTree<DB, EVM> {
@@ -115,7 +121,7 @@ where
     ///   storage space efficiently. It's important to validate this configuration to ensure it does
     ///   not lead to unintended data loss.
     pub fn new(
-        externals: TreeExternals<DB, EVM>,
+        mut externals: TreeExternals<DB, EVM>,
         config: BlockchainTreeConfig,
         prune_modes: Option<PruneModes>,
     ) -> RethResult<Self> {
@@ -141,6 +147,16 @@ where
         .copied()
         .unwrap_or_default();
 
+        externals.trie_checkpoints = config
+            .trie_checkpoints()
+            .map(|config| externals.fetch_trie_checkpoints(config))
+            .transpose()?;
+
+        let metrics = TreeMetrics::default();
+        if let Some(checkpoints) = &externals.trie_checkpoints {
+            metrics.trie_checkpoints.set(checkpoints.len() as f64);
+        }
+
         Ok(Self {
             externals,
             state: TreeState::new(
@@ -150,9 +166,10 @@ where
             ),
             config,
             canon_state_notification_sender,
-            metrics: Default::default(),
+            metrics,
             sync_metrics_tx: None,
             prune_modes,
+            spill: None,
         })
     }
 
@@ -165,6 +182,17 @@ where
         self
     }
 
+    /// Set the spill for sidechains.
+    ///
+    /// Sidechains whose tip falls more than [ChainSpill::depth] blocks behind the canonical tip are
+    /// written to disk, along with their executed state, and dropped from memory. They are
+    /// reloaded when one of their blocks is inserted, extended or made canonical, and their blocks
+    /// can still be looked up while they are spilled.
+    pub fn with_chain_spill(mut self, spill: ChainSpill) -> Self {
+        self.spill = Some(spill);
+        self
+    }
+
     /// Check if the block is known to blockchain tree or database and return its status.
     ///
     /// Function will check:
@@ -241,6 +269,22 @@ where
         self.state.block_with_senders_by_hash(block_hash)
     }
 
+    /// Returns the header of a block of a spilled side-chain, see [ChainSpill].
+    pub fn spilled_header_by_hash(&self, block_hash: BlockHash) -> Option<&SealedHeader> {
+        self.spill.as_ref()?.header(&block_hash)
+    }
+
+    /// Reads a block of a spilled side-chain from disk, see [ChainSpill].
+    pub fn spilled_block_with_senders_by_hash(
+        &self,
+        block_hash: BlockHash,
+    ) -> Option<SealedBlockWithSenders> {
+        self.spill.as_ref()?.block(&block_hash).unwrap_or_else(|err| {
+            warn!(target: "blockchain_tree", %err, ?block_hash, "Failed to read spilled block");
+            None
+        })
+    }
+
     /// Returns the block's receipts with matching hash from any side-chain.
     ///
     /// Caution: This will not return blocks from the canonical chain.
@@ -330,6 +374,21 @@ where
 
         // if not found, check if the parent can be found inside canonical chain.
         if self.is_block_hash_canonical(&parent.hash)? {
+            // if the parent is canonical but below the in-memory window, the state of the parent
+            // is reconstructed from the trie checkpoints. The database is only reverted once the
+            // fork becomes canonical.
+            if self.block_indices().canonical_hash(&parent.number) != Some(parent.hash) &&
+                !self
+                    .externals
+                    .trie_checkpoints
+                    .as_ref()
+                    .is_some_and(|checkpoints| checkpoints.nearest(parent.number).is_some())
+            {
+                return Err(BlockchainTreeError::TrieCheckpointNotFound {
+                    block_number: parent.number,
+                }
+                .into())
+            }
             return self.try_append_canonical_chain(block.clone(), block_validation_kind)
         }
 
@@ -384,6 +443,22 @@ where
         let block_num_hash = block.num_hash();
         debug!(target: "blockchain_tree", head = ?block_num_hash.hash, ?parent, "Appending block to canonical chain");
 
+        let (chain, block_attachment) = self.new_canonical_fork(block, block_validation_kind)?;
+
+        self.insert_chain(chain);
+        self.try_connect_buffered_blocks(block_num_hash);
+
+        Ok(BlockStatus::Valid(block_attachment))
+    }
+
+    /// Validates and executes the given block on top of its canonical parent and returns the
+    /// resulting chain, without inserting it.
+    fn new_canonical_fork(
+        &self,
+        block: SealedBlockWithSenders,
+        block_validation_kind: BlockValidationKind,
+    ) -> Result<(AppendableChain, BlockAttachment), InsertBlockErrorKind> {
+        let parent = block.parent_num_hash();
         let provider = self.externals.provider_factory.provider()?;
 
         // Validate that the block is post merge
@@ -426,12 +501,84 @@ where
             &self.externals,
             block_attachment,
             block_validation_kind,
+            self.config.state_root_strategy(),
+            self.config.detailed_state_root_metrics(),
         )?;
 
-        self.insert_chain(chain);
-        self.try_connect_buffered_blocks(block_num_hash);
+        Ok((chain, block_attachment))
+    }
 
-        Ok(BlockStatus::Valid(block_attachment))
+    /// Validates and executes the given block like [Self::insert_block], including its state root,
+    /// but without inserting it into the tree or buffering it.
+    ///
+    /// Returns [BlockStatus::Disconnected] if the parent of the block is unknown.
+    pub fn validate_block_without_insert(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BlockStatus, InsertBlockError> {
+        match self.is_block_known(block.num_hash()) {
+            Ok(Some(status)) => return Ok(status),
+            Err(err) => return Err(InsertBlockError::new(block.block, err)),
+            _ => {}
+        }
+
+        if let Err(err) = self.validate_block(&block) {
+            return Err(InsertBlockError::consensus_error(err, block.block))
+        }
+
+        self.try_validate_block(block.clone())
+            .map_err(|kind| InsertBlockError::new(block.block, kind))
+    }
+
+    /// Validates and executes a validated [Self::validate_block] block on top of its parent in the
+    /// tree or in the canonical chain, without inserting it.
+    fn try_validate_block(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BlockStatus, InsertBlockErrorKind> {
+        let parent = block.parent_num_hash();
+
+        if let Some(chain_id) = self.block_indices().get_blocks_chain_id(&parent.hash) {
+            let canonical_fork = self.canonical_fork(chain_id).ok_or_else(|| {
+                BlockchainTreeError::BlockSideChainIdConsistency { chain_id: chain_id.into() }
+            })?;
+            let parent_chain = self.state.chains.get(&chain_id).ok_or_else(|| {
+                BlockchainTreeError::BlockSideChainIdConsistency { chain_id: chain_id.into() }
+            })?;
+            let canonical_chain = self.state.block_indices.canonical_chain();
+            let block_attachment = if parent_chain.tip().hash() == parent.hash &&
+                canonical_fork.hash == canonical_chain.tip().hash
+            {
+                BlockAttachment::Canonical
+            } else {
+                BlockAttachment::HistoricalFork
+            };
+
+            // forking the parent chain at the parent executes the block on top of its state
+            // without modifying the parent chain
+            parent_chain.new_chain_fork(
+                block,
+                self.all_chain_hashes(chain_id),
+                canonical_chain.inner(),
+                canonical_fork,
+                &self.externals,
+                BlockValidationKind::Exhaustive,
+            )?;
+            return Ok(BlockStatus::Valid(block_attachment))
+        }
+
+        if self.is_block_hash_canonical(&parent.hash)? {
+            let (_, block_attachment) =
+                self.new_canonical_fork(block, BlockValidationKind::Exhaustive)?;
+            return Ok(BlockStatus::Valid(block_attachment))
+        }
+
+        let missing_ancestor = self
+            .state
+            .buffered_blocks
+            .lowest_ancestor(&parent.hash)
+            .map_or(parent, |ancestor| ancestor.parent_num_hash());
+        Ok(BlockStatus::Disconnected { missing_ancestor })
     }
 
     /// Try inserting a block into the given side chain.
@@ -483,6 +630,8 @@ where
                 canonical_fork,
                 block_attachment,
                 block_validation_kind,
+                self.config.state_root_strategy(),
+                self.config.detailed_state_root_metrics(),
             )?;
 
             self.state.block_indices.insert_non_fork_block(block_number, block_hash, chain_id);
@@ -752,6 +901,10 @@ where
         block: SealedBlockWithSenders,
         block_validation_kind: BlockValidationKind,
     ) -> Result<InsertPayloadOk, InsertBlockError> {
+        // reload the chain of the block or of its parent if it was spilled to disk
+        self.reload_spilled_chain(&block.hash());
+        self.reload_spilled_chain(&block.parent_hash);
+
         // check if we already have this block
         match self.is_block_known(block.num_hash()) {
             Ok(Some(status)) => return Ok(InsertPayloadOk::AlreadySeen(status)),
@@ -783,8 +936,133 @@ where
                 remove_chains.extend(self.state.block_indices.remove_chain(&chain));
             }
         }
+        // remove chains that fork off below the in-memory window of canonical hashes, those are
+        // not found by their fork block above.
+        let mut remove_chains = self
+            .state
+            .chains
+            .iter()
+            .filter(|(_, chain)| chain.fork_block().number < finalized_block)
+            .map(|(chain_id, _)| *chain_id)
+            .collect::<BTreeSet<_>>();
+        while let Some(chain_id) = remove_chains.pop_first() {
+            if let Some(chain) = self.state.chains.remove(&chain_id) {
+                remove_chains.extend(self.state.block_indices.remove_chain(&chain));
+            }
+        }
         // clean block buffer.
         self.state.buffered_blocks.remove_old_blocks(finalized_block);
+        // the chain can not be reorged below the finalized block.
+        if let Some(checkpoints) = &mut self.externals.trie_checkpoints {
+            checkpoints.finalize(finalized_block);
+            self.metrics.trie_checkpoints.set(checkpoints.len() as f64);
+        }
+        // spilled chains below the finalized block can not become canonical.
+        if let Some(spill) = &mut self.spill {
+            spill.finalize(finalized_block);
+        }
+    }
+
+    /// Writes sidechains whose tip is more than [ChainSpill::depth] blocks behind the canonical
+    /// tip to disk and removes them from the tree.
+    ///
+    /// Only chains that no other chain forks from are spilled, so that every chain in the tree
+    /// still connects to the canonical chain.
+    fn spill_stale_chains(&mut self) {
+        let Some(depth) = self.spill.as_ref().map(ChainSpill::depth) else { return };
+        let canonical_tip = self.block_indices().canonical_tip().number;
+
+        loop {
+            let fork_to_child = self.block_indices().fork_to_child();
+            let stale_chains = self
+                .state
+                .chains
+                .iter()
+                .filter(|(_, chain)| {
+                    chain.tip().number + depth < canonical_tip &&
+                        chain
+                            .blocks()
+                            .values()
+                            .all(|block| !fork_to_child.contains_key(&block.hash()))
+                })
+                .map(|(chain_id, _)| *chain_id)
+                .collect::<Vec<_>>();
+            if stale_chains.is_empty() {
+                break
+            }
+
+            for chain_id in stale_chains {
+                let chain = self.state.chains.remove(&chain_id).expect("Chain should be in tree");
+                self.state.block_indices.remove_leaf_chain(&chain);
+
+                let (first, tip) = (chain.first().num_hash(), chain.tip().num_hash());
+                let spill = self.spill.as_mut().expect("Spill is enabled");
+                match spill.spill(chain.into_inner()) {
+                    Ok(()) => {
+                        debug!(target: "blockchain_tree", ?first, ?tip, "Spilled sidechain to disk")
+                    }
+                    Err(err) => {
+                        warn!(
+                            target: "blockchain_tree",
+                            %err,
+                            ?first,
+                            ?tip,
+                            "Failed to spill sidechain, dropping it"
+                        )
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reloads the spilled chain that contains the given block, along with the spilled chains it
+    /// forks from, and inserts it back into the tree with its executed state.
+    fn reload_spilled_chain(&mut self, block_hash: &BlockHash) {
+        let Some(spill) = &mut self.spill else { return };
+        let start = Instant::now();
+        let chain = match spill.take(block_hash) {
+            Ok(Some(chain)) => chain,
+            Ok(None) => return,
+            Err(err) => {
+                warn!(
+                    target: "blockchain_tree",
+                    %err,
+                    ?block_hash,
+                    "Failed to reload spilled sidechain"
+                );
+                return
+            }
+        };
+
+        // the chain may fork from a chain that was spilled as well.
+        let fork_block = chain.fork_block();
+        self.reload_spilled_chain(&fork_block.hash);
+
+        // the blocks were validated before they were spilled, the chain only needs to connect to
+        // the tree or the canonical chain.
+        let connected = self.is_block_hash_inside_chain(fork_block.hash) ||
+            self.is_block_hash_canonical(&fork_block.hash).unwrap_or_default();
+        if !connected {
+            warn!(
+                target: "blockchain_tree",
+                ?block_hash,
+                fork = ?fork_block,
+                "Dropping spilled sidechain that no longer connects to the tree"
+            );
+            return
+        }
+
+        debug!(
+            target: "blockchain_tree",
+            ?block_hash,
+            blocks = chain.len(),
+            "Reloading spilled sidechain"
+        );
+        self.state.insert_chain(AppendableChain::new(chain));
+
+        if let Some(spill) = &self.spill {
+            spill.record_reload(start.elapsed());
+        }
     }
 
     /// Reads the last `N` canonical hashes from the database and updates the block indices of the
@@ -804,6 +1082,10 @@ where
     ) -> RethResult<()> {
         self.finalize_block(last_finalized_block);
 
+        // the database may have been changed outside of the tree, e.g. by the pipeline.
+        self.externals.trie_node_cache.clear();
+        self.clear_execution_cache();
+
         let last_canonical_hashes = self
             .externals
             .fetch_latest_canonical_hashes(self.config.num_of_canonical_hashes() as usize)?;
@@ -829,6 +1111,10 @@ where
     /// `N` is the maximum of `max_reorg_depth` and the number of block hashes needed to satisfy the
     /// `BLOCKHASH` opcode in the EVM.
     pub fn connect_buffered_blocks_to_canonical_hashes(&mut self) -> RethResult<()> {
+        // the database may have been changed outside of the tree, e.g. by the pipeline.
+        self.externals.trie_node_cache.clear();
+        self.clear_execution_cache();
+
         let last_canonical_hashes = self
             .externals
             .fetch_latest_canonical_hashes(self.config.num_of_canonical_hashes() as usize)?;
@@ -966,6 +1252,9 @@ where
         &mut self,
         block_hash: BlockHash,
     ) -> Result<CanonicalOutcome, CanonicalError> {
+        // reload the chain of the block if it was spilled to disk
+        self.reload_spilled_chain(&block_hash);
+
         let mut durations_recorder = MakeCanonicalDurationsRecorder::default();
 
         let old_block_indices = self.block_indices().clone();
@@ -1055,13 +1344,23 @@ where
         // If chain extends the tip
         let chain_notification = if new_canon_chain.fork_block().hash == old_tip.hash {
             // Commit new canonical chain to database.
-            self.commit_canonical_to_database(new_canon_chain.clone(), &mut durations_recorder)?;
+            self.commit_canonical_to_database(
+                new_canon_chain.clone(),
+                None,
+                &mut durations_recorder,
+            )?;
+            self.record_trie_checkpoints(&new_canon_chain)?;
             CanonStateNotification::Commit { new: Arc::new(new_canon_chain) }
         } else {
             // It forks to canonical block that is not the tip.
             let canon_fork: BlockNumHash = new_canon_chain.fork_block();
+            // the fork block is either in the in-memory window of canonical hashes, or below it if
+            // the chain was validated on top of the trie checkpoints.
+            let fork_in_window = self.block_indices().canonical_hash(&canon_fork.number).is_some();
             // sanity check
-            if self.block_indices().canonical_hash(&canon_fork.number) != Some(canon_fork.hash) {
+            if self.block_indices().canonical_hash(&canon_fork.number) != Some(canon_fork.hash) &&
+                (fork_in_window || !self.is_block_hash_canonical(&canon_fork.hash)?)
+            {
                 error!(
                     target: "blockchain_tree",
                     ?canon_fork,
@@ -1085,8 +1384,23 @@ where
             durations_recorder
                 .record_relative(MakeCanonicalAction::RevertCanonicalChainFromDatabase);
 
-            // Commit new canonical chain.
-            self.commit_canonical_to_database(new_canon_chain.clone(), &mut durations_recorder)?;
+            // Commit new canonical chain together with the record of the reorg.
+            let reorg = old_canon_chain.as_ref().and_then(|old_canon_chain| {
+                self.reorg_record(canon_fork, old_canon_chain, &new_canon_chain)
+            });
+            self.commit_canonical_to_database(
+                new_canon_chain.clone(),
+                reorg,
+                &mut durations_recorder,
+            )?;
+            self.record_trie_checkpoints(&new_canon_chain)?;
+
+            if !fork_in_window {
+                // the in-memory window only contains the new chain, reload it from the database.
+                self.reload_canonical_hashes()
+                    .map_err(|e| CanonicalError::CanonicalCommit(e.to_string()))?;
+                self.metrics.deep_reorgs.increment(1);
+            }
 
             if let Some(old_canon_chain) = old_canon_chain {
                 self.update_reorg_metrics(old_canon_chain.len() as f64);
@@ -1115,13 +1429,26 @@ where
         // Send notification about new canonical chain and return outcome of canonicalization.
         let outcome = CanonicalOutcome::Committed { head: chain_notification.tip().header.clone() };
         let _ = self.canon_state_notification_sender.send(chain_notification);
+        self.spill_stale_chains();
         Ok(outcome)
     }
 
+    /// Clears the execution cache, if any, after the database state was changed in a way that
+    /// can't be tracked.
+    fn clear_execution_cache(&self) {
+        if let Some(cache) = &self.externals.execution_cache {
+            cache.clear();
+        }
+    }
+
     /// Write the given chain to the database as canonical.
+    ///
+    /// The record of the reorg that the chain is committed by, if any, is written in the same
+    /// transaction.
     fn commit_canonical_to_database(
         &self,
         chain: Chain,
+        reorg: Option<ReorgRecord>,
         recorder: &mut MakeCanonicalDurationsRecorder,
     ) -> Result<(), CanonicalError> {
         let (blocks, state, chain_trie_updates) = chain.into_inner();
@@ -1163,6 +1490,16 @@ where
         };
         recorder.record_relative(MakeCanonicalAction::RetrieveStateTrieUpdates);
 
+        // the cached trie nodes that are overwritten by the commit are no longer valid.
+        self.externals.trie_node_cache.invalidate(&trie_updates);
+        self.metrics.trie_node_cache_entries.set(self.externals.trie_node_cache.len() as f64);
+
+        let changed_accounts = self
+            .externals
+            .execution_cache
+            .as_ref()
+            .map(|_| state.state().state.keys().copied().collect::<Vec<_>>());
+
         let provider_rw = self.externals.provider_factory.provider_rw()?;
         provider_rw
             .append_blocks_with_state(
@@ -1173,10 +1510,22 @@ where
                 self.prune_modes.as_ref(),
             )
             .map_err(|e| CanonicalError::CanonicalCommit(e.to_string()))?;
+        if let Some(reorg) = reorg {
+            provider_rw
+                .insert_reorg(reorg, self.config.reorg_history_retention())
+                .map_err(|e| CanonicalError::CanonicalCommit(e.to_string()))?;
+        }
 
         provider_rw.commit()?;
         recorder.record_relative(MakeCanonicalAction::CommitCanonicalChainToDatabase);
 
+        // the cached state of the committed accounts is no longer valid.
+        if let Some((cache, changed_accounts)) =
+            self.externals.execution_cache.as_ref().zip(changed_accounts)
+        {
+            cache.invalidate(&changed_accounts);
+        }
+
         Ok(())
     }
 
@@ -1220,6 +1569,16 @@ where
 
         provider_rw.commit()?;
 
+        // the trie nodes and the state of the reverted blocks are no longer valid.
+        self.externals.trie_node_cache.clear();
+        self.clear_execution_cache();
+
+        // checkpoints of the reverted blocks are no longer valid.
+        if let Some(checkpoints) = &mut self.externals.trie_checkpoints {
+            checkpoints.unwind(revert_until);
+            self.metrics.trie_checkpoints.set(checkpoints.len() as f64);
+        }
+
         if blocks_and_execution.is_empty() {
             Ok(None)
         } else {
@@ -1227,11 +1586,89 @@ where
         }
     }
 
+    /// Records the trie checkpoints of the newly committed canonical chain and prunes the ones that
+    /// are too deep to be reorged to.
+    fn record_trie_checkpoints(&mut self, chain: &Chain) -> Result<(), CanonicalError> {
+        let Some(checkpoints) = &mut self.externals.trie_checkpoints else { return Ok(()) };
+
+        let provider = self.externals.provider_factory.provider()?;
+        for block in chain.blocks().values() {
+            if !checkpoints.is_checkpoint(block.number) {
+                continue
+            }
+            // the reverts of the interval are read back from the changesets that were just
+            // committed, the interval may span multiple commits.
+            let reverts = HashedPostState::from_revert_range(
+                provider.tx_ref(),
+                checkpoints.interval_range(block.number),
+            )
+            .map_err(ProviderError::from)?;
+            checkpoints.record(&block.header, reverts);
+            trace!(
+                target: "blockchain_tree",
+                number = block.number,
+                hash = ?block.hash(),
+                "Recorded trie checkpoint"
+            );
+        }
+        checkpoints.prune(chain.tip().number);
+        self.metrics.trie_checkpoints.set(checkpoints.len() as f64);
+        Ok(())
+    }
+
+    /// Reloads the in-memory window of canonical hashes from the database and removes the chains
+    /// that no longer connect to it.
+    fn reload_canonical_hashes(&mut self) -> RethResult<()> {
+        let last_canonical_hashes = self
+            .externals
+            .fetch_latest_canonical_hashes(self.config.num_of_canonical_hashes() as usize)?;
+        let (mut remove_chains, _) =
+            self.state.block_indices.update_block_hashes(last_canonical_hashes);
+
+        // remove all chains that got discarded
+        while let Some(chain_id) = remove_chains.pop_first() {
+            if let Some(chain) = self.state.chains.remove(&chain_id) {
+                remove_chains.extend(self.state.block_indices.remove_chain(&chain));
+            }
+        }
+
+        Ok(())
+    }
+
     fn update_reorg_metrics(&mut self, reorg_depth: f64) {
         self.metrics.reorgs.increment(1);
         self.metrics.latest_reorg_depth.set(reorg_depth);
     }
 
+    /// Returns the record of the reorg from the old to the new canonical chain for the reorg
+    /// history table, or `None` if reorgs are not recorded.
+    fn reorg_record(&self, fork: BlockNumHash, old: &Chain, new: &Chain) -> Option<ReorgRecord> {
+        if self.config.reorg_history_retention() == 0 {
+            return None
+        }
+
+        let new_transactions = new
+            .blocks_iter()
+            .flat_map(|block| block.body.iter().map(|tx| tx.hash()))
+            .collect::<HashSet<_>>();
+        let reorged_transactions = old
+            .blocks_iter()
+            .flat_map(|block| block.body.iter().map(|tx| tx.hash()))
+            .filter(|hash| !new_transactions.contains(hash))
+            .collect();
+
+        Some(ReorgRecord {
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs(),
+            fork_block: fork.number,
+            old_tip_number: old.tip().number,
+            old_tip_hash: old.tip().hash(),
+            new_tip_number: new.tip().number,
+            new_tip_hash: new.tip().hash(),
+            depth: old.len() as u64,
+            reorged_transactions,
+        })
+    }
+
     /// Update blockchain tree chains (canonical and sidechains) and sync metrics.
     ///
     /// NOTE: this method should not be called during the pipeline sync, because otherwise the sync
@@ -1257,6 +1694,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::StateRootStrategy;
     use assert_matches::assert_matches;
     use linked_hash_set::LinkedHashSet;
     use reth_consensus::test_utils::TestConsensus;
@@ -1272,7 +1710,7 @@ mod tests {
         proofs::{calculate_transaction_root, state_root_unhashed},
         revm_primitives::AccountInfo,
         stage::StageCheckpoint,
-        Account, Address, ChainSpecBuilder, Genesis, GenesisAccount, Header, Signature,
+        Account, Address, ChainSpec, ChainSpecBuilder, Genesis, GenesisAccount, Header, Signature,
         Transaction, TransactionKind, TransactionSigned, TransactionSignedEcRecovered, TxEip1559,
         Withdrawals, B256, MAINNET,
     };
@@ -1281,11 +1719,11 @@ mod tests {
             blocks::BlockChainTestData, create_test_provider_factory_with_chain_spec,
             TestExecutorFactory,
         },
-        ProviderFactory,
+        BlockHashReader, ProviderFactory, ReorgHistoryReader,
     };
     use reth_revm::EvmProcessorFactory;
     use reth_trie::StateRoot;
-    use std::collections::HashMap;
+    use std::{collections::HashMap, fs};
 
     fn setup_externals(
         exec_res: Vec<BundleStateWithReceipts>,
@@ -1408,49 +1846,72 @@ mod tests {
         }
     }
 
-    #[test]
-    fn consecutive_reorgs() {
-        let signer = Address::random();
-        let initial_signer_balance = U256::from(10).pow(U256::from(18));
-        let chain_spec = Arc::new(
-            ChainSpecBuilder::default()
-                .chain(MAINNET.chain)
-                .genesis(Genesis {
-                    alloc: BTreeMap::from([(
-                        signer,
-                        GenesisAccount { balance: initial_signer_balance, ..Default::default() },
-                    )]),
-                    ..MAINNET.genesis.clone()
-                })
-                .shanghai_activated()
-                .build(),
-        );
-        let provider_factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
-        let consensus = Arc::new(TestConsensus::default());
-        let executor_factory =
-            EvmProcessorFactory::new(chain_spec.clone(), EthEvmConfig::default());
+    /// A chain whose blocks only contain transfers of a single funded signer, executed with the
+    /// real EVM.
+    struct SignerChain {
+        chain_spec: Arc<ChainSpec>,
+        signer: Address,
+        initial_signer_balance: U256,
+    }
+
+    impl SignerChain {
+        fn new() -> Self {
+            let signer = Address::random();
+            let initial_signer_balance = U256::from(10).pow(U256::from(18));
+            let chain_spec = Arc::new(
+                ChainSpecBuilder::default()
+                    .chain(MAINNET.chain)
+                    .genesis(Genesis {
+                        alloc: BTreeMap::from([(
+                            signer,
+                            GenesisAccount {
+                                balance: initial_signer_balance,
+                                ..Default::default()
+                            },
+                        )]),
+                        ..MAINNET.genesis.clone()
+                    })
+                    .shanghai_activated()
+                    .build(),
+            );
+            Self { chain_spec, signer, initial_signer_balance }
+        }
 
+        /// Creates the tree externals with the genesis block and the signer in the database.
+        fn externals(
+            &self,
+        ) -> TreeExternals<Arc<TempDatabase<DatabaseEnv>>, EvmProcessorFactory<EthEvmConfig>>
         {
+            let provider_factory =
+                create_test_provider_factory_with_chain_spec(self.chain_spec.clone());
+            let consensus = Arc::new(TestConsensus::default());
+            let executor_factory =
+                EvmProcessorFactory::new(self.chain_spec.clone(), EthEvmConfig::default());
+
             let provider_rw = provider_factory.provider_rw().unwrap();
             provider_rw
                 .insert_block(
-                    SealedBlock::new(chain_spec.sealed_genesis_header(), Default::default())
+                    SealedBlock::new(self.chain_spec.sealed_genesis_header(), Default::default())
                         .try_seal_with_senders()
                         .unwrap(),
                     None,
                 )
                 .unwrap();
-            let account = Account { balance: initial_signer_balance, ..Default::default() };
-            provider_rw.tx_ref().put::<tables::PlainAccountState>(signer, account).unwrap();
-            provider_rw.tx_ref().put::<tables::HashedAccounts>(keccak256(signer), account).unwrap();
+            let account = Account { balance: self.initial_signer_balance, ..Default::default() };
+            provider_rw.tx_ref().put::<tables::PlainAccountState>(self.signer, account).unwrap();
+            provider_rw
+                .tx_ref()
+                .put::<tables::HashedAccounts>(keccak256(self.signer), account)
+                .unwrap();
             provider_rw.commit().unwrap();
+
+            TreeExternals::new(provider_factory, consensus, executor_factory)
         }
 
-        let single_tx_cost = U256::from(EIP1559_INITIAL_BASE_FEE * 21_000);
-        let mock_tx = |nonce: u64| -> TransactionSignedEcRecovered {
+        fn tx(&self, nonce: u64) -> TransactionSignedEcRecovered {
             TransactionSigned::from_transaction_and_signature(
                 Transaction::Eip1559(TxEip1559 {
-                    chain_id: chain_spec.chain.id(),
+                    chain_id: self.chain_spec.chain.id(),
                     nonce,
                     gas_limit: 21_000,
                     to: TransactionKind::Call(Address::ZERO),
@@ -1459,14 +1920,19 @@ mod tests {
                 }),
                 Signature::default(),
             )
-            .with_signer(signer)
-        };
+            .with_signer(self.signer)
+        }
 
-        let mock_block = |number: u64,
-                          parent: Option<B256>,
-                          body: Vec<TransactionSignedEcRecovered>,
-                          num_of_signer_txs: u64|
-         -> SealedBlockWithSenders {
+        /// Creates a block with the given transactions of the signer, whose state root assumes
+        /// that the signer sent `num_of_signer_txs` transactions up to and including this block.
+        fn block(
+            &self,
+            number: u64,
+            parent: B256,
+            body: Vec<TransactionSignedEcRecovered>,
+            num_of_signer_txs: u64,
+        ) -> SealedBlockWithSenders {
+            let single_tx_cost = U256::from(EIP1559_INITIAL_BASE_FEE * 21_000);
             let transactions_root = calculate_transaction_root(&body);
             let receipts = body
                 .iter()
@@ -1486,13 +1952,13 @@ mod tests {
             let receipts_root = calculate_receipt_root(&receipts);
 
             #[cfg(feature = "optimism")]
-            let receipts_root = calculate_receipt_root_optimism(&receipts, &chain_spec, 0);
+            let receipts_root = calculate_receipt_root_optimism(&receipts, &self.chain_spec, 0);
 
             SealedBlockWithSenders::new(
                 SealedBlock {
                     header: Header {
                         number,
-                        parent_hash: parent.unwrap_or_default(),
+                        parent_hash: parent,
                         gas_used: body.len() as u64 * 21_000,
                         gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
                         mix_hash: B256::random(),
@@ -1500,10 +1966,10 @@ mod tests {
                         transactions_root,
                         receipts_root,
                         state_root: state_root_unhashed(HashMap::from([(
-                            signer,
+                            self.signer,
                             (
                                 AccountInfo {
-                                    balance: initial_signer_balance -
+                                    balance: self.initial_signer_balance -
                                         (single_tx_cost * U256::from(num_of_signer_txs)),
                                     nonce: num_of_signer_txs,
                                     ..Default::default()
@@ -1521,26 +1987,30 @@ mod tests {
                 body.iter().map(|tx| tx.signer()).collect(),
             )
             .unwrap()
-        };
+        }
+    }
 
-        let fork_block = mock_block(1, Some(chain_spec.genesis_hash()), Vec::from([mock_tx(0)]), 1);
+    #[test]
+    fn consecutive_reorgs() {
+        let chain = SignerChain::new();
+
+        let fork_block =
+            chain.block(1, chain.chain_spec.genesis_hash(), Vec::from([chain.tx(0)]), 1);
 
         let canonical_block_1 =
-            mock_block(2, Some(fork_block.hash()), Vec::from([mock_tx(1), mock_tx(2)]), 3);
-        let canonical_block_2 = mock_block(3, Some(canonical_block_1.hash()), Vec::new(), 3);
+            chain.block(2, fork_block.hash(), Vec::from([chain.tx(1), chain.tx(2)]), 3);
+        let canonical_block_2 = chain.block(3, canonical_block_1.hash(), Vec::new(), 3);
         let canonical_block_3 =
-            mock_block(4, Some(canonical_block_2.hash()), Vec::from([mock_tx(3)]), 4);
+            chain.block(4, canonical_block_2.hash(), Vec::from([chain.tx(3)]), 4);
 
-        let sidechain_block_1 = mock_block(2, Some(fork_block.hash()), Vec::from([mock_tx(1)]), 2);
+        let sidechain_block_1 = chain.block(2, fork_block.hash(), Vec::from([chain.tx(1)]), 2);
         let sidechain_block_2 =
-            mock_block(3, Some(sidechain_block_1.hash()), Vec::from([mock_tx(2)]), 3);
+            chain.block(3, sidechain_block_1.hash(), Vec::from([chain.tx(2)]), 3);
 
-        let mut tree = BlockchainTree::new(
-            TreeExternals::new(provider_factory, consensus, executor_factory),
-            BlockchainTreeConfig::default(),
-            None,
-        )
-        .expect("failed to create tree");
+        // only the last three reorgs are kept in the reorg history
+        let config = BlockchainTreeConfig::default().with_reorg_history_retention(3);
+        let mut tree =
+            BlockchainTree::new(chain.externals(), config, None).expect("failed to create tree");
 
         tree.insert_block(fork_block.clone(), BlockValidationKind::Exhaustive).unwrap();
 
@@ -1598,6 +2068,120 @@ mod tests {
             tree.make_canonical(canonical_block_3.hash()).unwrap(),
             CanonicalOutcome::Committed { head: canonical_block_3.header.clone() }
         );
+
+        // the reorgs are recorded with the canonical commits, newest first
+        let reorgs = tree.externals.provider_factory.reorg_history(10).unwrap();
+        assert_eq!(
+            reorgs
+                .iter()
+                .map(|reorg| (
+                    reorg.fork_block,
+                    reorg.old_tip_hash,
+                    reorg.new_tip_hash,
+                    reorg.depth
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, sidechain_block_2.hash(), canonical_block_3.hash(), 2),
+                (1, canonical_block_1.hash(), sidechain_block_2.hash(), 1),
+                (1, sidechain_block_1.hash(), canonical_block_1.hash(), 1),
+            ]
+        );
+        // all transactions of the reorged blocks are included in the new canonical chains
+        assert!(reorgs.iter().all(|reorg| reorg.reorged_transactions.is_empty()));
+    }
+
+    #[test]
+    fn deep_side_chain_is_committed_on_make_canonical() {
+        let chain = SignerChain::new();
+        let externals = chain.externals();
+        let provider_factory = externals.provider_factory.clone();
+
+        // two canonical hashes are kept in memory and a trie checkpoint is recorded every two
+        // blocks.
+        let config = BlockchainTreeConfig::new(2, 3, 2, 10).with_trie_checkpoints(2, 16);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+
+        // the signer sends a transaction in block 1, blocks 2 to 8 are empty.
+        let mut canonical =
+            vec![chain.block(1, chain.chain_spec.genesis_hash(), Vec::from([chain.tx(0)]), 1)];
+        for number in 2..=8 {
+            canonical.push(chain.block(number, canonical[canonical.len() - 1].hash(), vec![], 1));
+        }
+        for block in &canonical {
+            tree.insert_block(block.clone(), BlockValidationKind::Exhaustive).unwrap();
+            tree.make_canonical(block.hash()).unwrap();
+        }
+
+        // block 1 is finalized and the in-memory window only contains blocks 7 and 8.
+        tree.connect_buffered_blocks_to_canonical_hashes_and_finalize(1).unwrap();
+        assert_eq!(tree.block_indices().canonical_hash(&3), None);
+
+        // the side chain forks off block 3, below the in-memory window.
+        let mut side_chain = vec![chain.block(4, canonical[2].hash(), Vec::from([chain.tx(1)]), 2)];
+        for number in 5..=9 {
+            side_chain.push(chain.block(
+                number,
+                side_chain[side_chain.len() - 1].hash(),
+                vec![],
+                2,
+            ));
+        }
+        for block in &side_chain {
+            assert_eq!(
+                tree.insert_block(block.clone(), BlockValidationKind::Exhaustive).unwrap(),
+                InsertPayloadOk::Inserted(BlockStatus::Valid(BlockAttachment::HistoricalFork))
+            );
+        }
+
+        // the canonical chain in the database is untouched until the side chain becomes canonical.
+        let provider = provider_factory.provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 8);
+        assert_eq!(provider.block_hash(4).unwrap(), Some(canonical[3].hash()));
+        drop(provider);
+        assert_eq!(tree.block_indices().canonical_tip(), canonical[7].num_hash());
+
+        let side_chain_tip = &side_chain[side_chain.len() - 1];
+        assert_eq!(
+            tree.make_canonical(side_chain_tip.hash()).unwrap(),
+            CanonicalOutcome::Committed { head: side_chain_tip.header.clone() }
+        );
+
+        let provider = provider_factory.provider().unwrap();
+        assert_eq!(provider.last_block_number().unwrap(), 9);
+        assert_eq!(provider.block_hash(4).unwrap(), Some(side_chain[0].hash()));
+        assert_eq!(tree.block_indices().canonical_hash(&9), Some(side_chain_tip.hash()));
+        // the reverted canonical blocks are kept in the tree as a side chain.
+        assert!(tree.block_indices().get_blocks_chain_id(&canonical[7].hash()).is_some());
+    }
+
+    #[test]
+    fn side_chain_below_checkpoints_is_rejected() {
+        let chain = SignerChain::new();
+        let config = BlockchainTreeConfig::new(2, 3, 2, 10);
+        let mut tree =
+            BlockchainTree::new(chain.externals(), config, None).expect("failed to create tree");
+
+        let mut canonical =
+            vec![chain.block(1, chain.chain_spec.genesis_hash(), Vec::from([chain.tx(0)]), 1)];
+        for number in 2..=5 {
+            canonical.push(chain.block(number, canonical[canonical.len() - 1].hash(), vec![], 1));
+        }
+        for block in &canonical {
+            tree.insert_block(block.clone(), BlockValidationKind::Exhaustive).unwrap();
+            tree.make_canonical(block.hash()).unwrap();
+        }
+        tree.connect_buffered_blocks_to_canonical_hashes_and_finalize(1).unwrap();
+
+        // without trie checkpoints, the state of a fork block below the window is not known.
+        let block = chain.block(3, canonical[1].hash(), Vec::from([chain.tx(1)]), 2);
+        let err = tree.insert_block(block, BlockValidationKind::Exhaustive).unwrap_err();
+        assert_matches!(
+            err.kind(),
+            InsertBlockErrorKind::Tree(BlockchainTreeError::TrieCheckpointNotFound {
+                block_number: 2
+            })
+        );
     }
 
     #[test]
@@ -1764,6 +2348,104 @@ mod tests {
         assert_eq!(state_root, block5.state_root);
     }
 
+    #[test]
+    fn synchronous_state_root() {
+        let data = BlockChainTestData::default_from_number(11);
+        let (block1, exec1) = data.blocks[0].clone();
+        let genesis = data.genesis;
+
+        let externals = setup_externals(vec![exec1]);
+        setup_genesis(&externals.provider_factory, genesis);
+
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2)
+            .with_state_root_strategy(StateRootStrategy::Synchronous);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+        tree.make_canonical(B256::ZERO).unwrap();
+        tree.finalize_block(10);
+
+        assert_eq!(
+            tree.insert_block(block1.clone(), BlockValidationKind::Exhaustive).unwrap(),
+            InsertPayloadOk::Inserted(BlockStatus::Valid(BlockAttachment::Canonical))
+        );
+        let block1_chain_id = tree.state.block_indices.get_blocks_chain_id(&block1.hash()).unwrap();
+        let block1_chain = tree.state.chains.get(&block1_chain_id).unwrap();
+        assert!(block1_chain.trie_updates().is_some());
+
+        assert_eq!(
+            tree.make_canonical(block1.hash()).unwrap(),
+            CanonicalOutcome::Committed { head: block1.header.clone() }
+        );
+    }
+
+    #[test]
+    fn spill_and_reload_side_chain() {
+        let data = BlockChainTestData::default_from_number(11);
+        let (block1, exec1) = data.blocks[0].clone();
+        let (block2, exec2) = data.blocks[1].clone();
+        let genesis = data.genesis;
+
+        // test pops execution results from vector, so order is from last to first.
+        let externals = setup_externals(vec![exec2.clone(), exec2, exec1.clone(), exec1]);
+        setup_genesis(&externals.provider_factory, genesis);
+
+        let dir = tempfile::tempdir().unwrap();
+        let spill = ChainSpill::new(dir.path().join("spill"), 0).unwrap();
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2);
+        let mut tree = BlockchainTree::new(externals, config, None)
+            .expect("failed to create tree")
+            .with_chain_spill(spill);
+        tree.make_canonical(B256::ZERO).unwrap();
+        tree.finalize_block(10);
+
+        // sidechain that forks off at the genesis block
+        let mut block1a = block1.clone();
+        block1a.set_hash(B256::new([0x35; 32]));
+
+        tree.insert_block(block1.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.insert_block(block1a.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.insert_block(block2.clone(), BlockValidationKind::Exhaustive).unwrap();
+        let sidechain_state = tree
+            .state
+            .chains
+            .values()
+            .find(|chain| chain.blocks().values().any(|block| block.hash() == block1a.hash()))
+            .unwrap()
+            .state()
+            .clone();
+
+        // the sidechain falls behind the canonical tip and is spilled
+        tree.make_canonical(block2.hash()).unwrap();
+        assert!(tree.state.chains.is_empty());
+        assert!(tree.block_by_hash(block1a.hash()).is_none());
+        let spill = tree.spill.as_ref().unwrap();
+        assert!(spill.contains(&block1a.hash()));
+        assert_eq!(fs::read_dir(spill.dir()).unwrap().count(), 2);
+
+        // spilled blocks can still be looked up
+        assert_eq!(tree.spilled_header_by_hash(block1a.hash()), Some(&block1a.header));
+        assert_eq!(tree.spilled_block_with_senders_by_hash(block1a.hash()), Some(block1a.clone()));
+
+        // extending the sidechain reloads it with its executed state
+        let mut block2a = block2.clone();
+        block2a.set_parent_hash(block1a.hash());
+        block2a.set_hash(B256::new([0x36; 32]));
+        assert_matches!(
+            tree.insert_block(block2a.clone(), BlockValidationKind::Exhaustive),
+            Ok(InsertPayloadOk::Inserted(BlockStatus::Valid(_)))
+        );
+        assert!(tree.spill.as_ref().unwrap().is_empty());
+        assert_eq!(tree.block_by_hash(block1a.hash()), Some(&block1a.block));
+
+        let chain_id = tree.block_indices().get_blocks_chain_id(&block1a.hash()).unwrap();
+        let chain = tree.state.chains.get(&chain_id).unwrap();
+        assert_eq!(chain.blocks().len(), 2);
+        assert_eq!(chain.state().receipts().len(), 2);
+        assert_eq!(
+            chain.state().receipts_by_block(block1a.number),
+            sidechain_state.receipts_by_block(block1a.number)
+        );
+    }
+
     #[test]
     fn test_side_chain_fork() {
         let data = BlockChainTestData::default_from_number(11);
@@ -1862,6 +2544,33 @@ mod tests {
         assert_eq!(chain1.first_block(), block2.number);
     }
 
+    #[test]
+    fn validate_block_without_insert() {
+        let data = BlockChainTestData::default_from_number(11);
+        let (block1, exec1) = data.blocks[0].clone();
+        let (block2, _) = data.blocks[1].clone();
+
+        let externals = setup_externals(vec![exec1]);
+        setup_genesis(&externals.provider_factory, data.genesis);
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+        tree.make_canonical(B256::ZERO).unwrap();
+
+        // the block is executed on top of the canonical chain
+        assert_eq!(
+            tree.validate_block_without_insert(block1).unwrap(),
+            BlockStatus::Valid(BlockAttachment::Canonical)
+        );
+        // the parent of block 2 is unknown
+        assert_eq!(
+            tree.validate_block_without_insert(block2.clone()).unwrap(),
+            BlockStatus::Disconnected { missing_ancestor: block2.parent_num_hash() }
+        );
+
+        // neither block was inserted nor buffered
+        TreeTester::default().with_chain_num(0).with_buffered_blocks(HashMap::new()).assert(&tree);
+    }
+
     #[test]
     fn sanity_path() {
         let data = BlockChainTestData::default_from_number(11);
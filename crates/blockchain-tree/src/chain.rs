@@ -4,7 +4,13 @@
 //! blocks, as well as a list of the blocks the chain is composed of.
 
 use super::externals::TreeExternals;
-use crate::BundleStateDataRef;
+use crate::{
+    execution_cache::CachedStateProvider,
+    invalid_block_hook::{RootDiff, WitnessRecorder},
+    metrics::StateRootPath,
+    state_root_capture::HashedStateInput,
+    BundleStateDataRef, InvalidBlock, StateRootInput, StateRootStrategy, TrieCheckpoints,
+};
 use reth_consensus::{Consensus, ConsensusError};
 use reth_db::database::Database;
 use reth_interfaces::{
@@ -12,20 +18,24 @@ use reth_interfaces::{
         error::{BlockchainTreeError, InsertBlockErrorKind},
         BlockAttachment, BlockValidationKind,
     },
+    executor::{BlockExecutionError, BlockValidationError},
     RethResult,
 };
 use reth_primitives::{
-    BlockHash, BlockNumber, ForkBlock, GotExpected, SealedBlockWithSenders, SealedHeader, U256,
+    BlockHash, BlockNumHash, BlockNumber, BlockWithSenders, ForkBlock, GotExpected,
+    SealedBlockWithSenders, SealedHeader, B256, U256,
 };
 use reth_provider::{
     providers::{BundleStateProvider, ConsistentDbView},
-    BundleStateDataProvider, BundleStateWithReceipts, Chain, ExecutorFactory, ProviderError,
-    StateRootProvider,
+    BlockNumReader, BundleStateDataProvider, BundleStateWithReceipts, Chain, ExecutorFactory,
+    ProviderError, ProviderFactory, StateProvider, StateRootProvider,
 };
-use reth_trie::updates::TrieUpdates;
+use reth_trie::{updates::TrieUpdates, HashedPostState};
 use reth_trie_parallel::parallel_root::ParallelStateRoot;
+use revm::db::BundleState;
 use std::{
     collections::BTreeMap,
+    fmt,
     ops::{Deref, DerefMut},
     time::Instant,
 };
@@ -66,6 +76,7 @@ impl AppendableChain {
     ///
     /// if [BlockValidationKind::Exhaustive] is specified, the method will verify the state root of
     /// the block.
+    #[allow(clippy::too_many_arguments)]
     pub fn new_canonical_fork<DB, EF>(
         block: SealedBlockWithSenders,
         parent_header: &SealedHeader,
@@ -74,6 +85,8 @@ impl AppendableChain {
         externals: &TreeExternals<DB, EF>,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
+        state_root_strategy: StateRootStrategy,
+        detailed_state_root_metrics: bool,
     ) -> Result<Self, InsertBlockErrorKind>
     where
         DB: Database + Clone,
@@ -96,6 +109,8 @@ impl AppendableChain {
             externals,
             block_attachment,
             block_validation_kind,
+            state_root_strategy,
+            detailed_state_root_metrics,
         )?;
 
         Ok(Self { chain: Chain::new(vec![block], bundle_state, trie_updates) })
@@ -141,6 +156,10 @@ impl AppendableChain {
             externals,
             BlockAttachment::HistoricalFork,
             block_validation_kind,
+            // the strategy and the detailed metrics only apply to blocks that extend the canonical
+            // chain
+            StateRootStrategy::default(),
+            false,
         )?;
         // extending will also optimize few things, mostly related to selfdestruct and wiping of
         // storage.
@@ -166,6 +185,10 @@ impl AppendableChain {
     ///   - [BlockAttachment] represents if the block extends the canonical chain, and thus we can
     ///     cache the trie state updates.
     ///   - [BlockValidationKind] determines if the state root __should__ be validated.
+    ///
+    /// The state root of a block that extends the canonical chain is computed with the given
+    /// [StateRootStrategy].
+    #[allow(clippy::too_many_arguments)]
     fn validate_and_execute<BSDP, DB, EVM>(
         block: SealedBlockWithSenders,
         parent_block: &SealedHeader,
@@ -173,6 +196,8 @@ impl AppendableChain {
         externals: &TreeExternals<DB, EVM>,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
+        state_root_strategy: StateRootStrategy,
+        detailed_state_root_metrics: bool,
     ) -> RethResult<(BundleStateWithReceipts, Option<TrieUpdates>)>
     where
         BSDP: BundleStateDataProvider,
@@ -194,19 +219,47 @@ impl AppendableChain {
         // The usage has to be re-evaluated if that was ever to change.
         let consistent_view =
             ConsistentDbView::new_with_latest_tip(externals.provider_factory.clone())?;
-        let state_provider = consistent_view
+        // the epoch of the execution cache must be read before the state is opened, so that
+        // reads of a state that is committed over in the meantime are not cached.
+        let execution_cache = externals
+            .execution_cache
+            .as_ref()
+            .filter(|_| block_attachment.is_canonical())
+            .map(|cache| (cache.clone(), cache.epoch()));
+        let mut state_provider = consistent_view
             .provider_ro()?
             // State root calculation can take a while, and we're sure no write transaction
             // will be open in parallel. See https://github.com/paradigmxyz/reth/issues/7509.
             .disable_long_read_transaction_safety()
             .state_provider_by_block_number(canonical_fork.number)?;
+        if let Some((cache, epoch)) = execution_cache {
+            // the block extends the canonical chain, so the state is the latest database state
+            state_provider = Box::new(CachedStateProvider::new(state_provider, cache, epoch));
+        }
 
         let provider = BundleStateProvider::new(state_provider, bundle_state_data_provider);
 
         let mut executor = externals.executor_factory.with_state(&provider);
         let block_hash = block.hash();
         let block = block.unseal();
-        executor.execute_and_verify_receipt(&block, U256::MAX)?;
+        if let Err(err) = executor.execute_and_verify_receipt(&block, U256::MAX) {
+            let receipts_root = match &err {
+                BlockExecutionError::Validation(BlockValidationError::ReceiptRootDiff(diff)) => {
+                    Some(diff.got)
+                }
+                _ => None,
+            };
+            Self::on_invalid_block(
+                externals,
+                &provider,
+                &block,
+                block_hash,
+                &err,
+                None,
+                receipts_root,
+            );
+            return Err(err.into())
+        }
         let bundle_state = executor.take_output_state();
 
         // check state root if the block extends the canonical chain __and__ if state root
@@ -217,19 +270,49 @@ impl AppendableChain {
             let (state_root, trie_updates) = if block_attachment.is_canonical() {
                 let mut state = provider.bundle_state_data_provider.state().clone();
                 state.extend(bundle_state.clone());
-                let hashed_state = state.hash_state_slow();
-                ParallelStateRoot::new(consistent_view, hashed_state)
-                    .incremental_root_with_updates()
-                    .map(|(root, updates)| (root, Some(updates)))
-                    .map_err(ProviderError::from)?
+                Self::canonical_state_root(
+                    consistent_view,
+                    externals,
+                    &state,
+                    canonical_fork,
+                    BlockNumHash::new(block.number, block_hash),
+                    block.state_root,
+                    state_root_strategy,
+                    detailed_state_root_metrics,
+                )
+                .map(|(root, updates)| (root, Some(updates)))?
+            } else if let Some(checkpoints) = &externals.trie_checkpoints {
+                // the state of the fork block is reconstructed from the trie checkpoints instead
+                // of reverting every changeset since the fork block.
+                let mut state = provider.bundle_state_data_provider.state().state().clone();
+                state.extend(bundle_state.state().clone());
+                (
+                    Self::checkpoint_state_root(
+                        consistent_view,
+                        checkpoints,
+                        &state,
+                        canonical_fork,
+                    )?,
+                    None,
+                )
             } else {
                 (provider.state_root(bundle_state.state())?, None)
             };
             if block.state_root != state_root {
-                return Err(ConsensusError::BodyStateRootDiff(
+                let err = ConsensusError::BodyStateRootDiff(
                     GotExpected { got: state_root, expected: block.state_root }.into(),
-                )
-                .into())
+                );
+                Self::on_invalid_block(
+                    externals,
+                    &provider,
+                    &block,
+                    block_hash,
+                    &err,
+                    Some(state_root),
+                    // the receipts root was verified by the execution
+                    Some(block.receipts_root),
+                );
+                return Err(err.into())
             }
 
             tracing::debug!(
@@ -246,6 +329,185 @@ impl AppendableChain {
         }
     }
 
+    /// Computes the state root of the given state on top of the canonical fork block, with the
+    /// state of the fork block reconstructed from the trie checkpoints.
+    fn checkpoint_state_root<DB>(
+        consistent_view: ConsistentDbView<DB, ProviderFactory<DB>>,
+        checkpoints: &TrieCheckpoints,
+        state: &BundleState,
+        canonical_fork: ForkBlock,
+    ) -> RethResult<B256>
+    where
+        DB: Database + Clone,
+    {
+        let provider = consistent_view
+            .provider_ro()?
+            // State root calculation can take a while, and we're sure no write transaction
+            // will be open in parallel. See https://github.com/paradigmxyz/reth/issues/7509.
+            .disable_long_read_transaction_safety();
+        let tip = provider.last_block_number()?;
+        let mut hashed_state =
+            checkpoints.revert_state(provider.tx_ref(), canonical_fork.number, tip)?;
+        hashed_state.extend(HashedPostState::from_bundle_state(&state.state));
+        Ok(hashed_state
+            .state_root(provider.tx_ref())
+            .map_err(|err| ProviderError::Database(err.into()))?)
+    }
+
+    /// Invokes the invalid block hooks of the externals, if any, with the block that failed
+    /// validation and the computed roots.
+    ///
+    /// The block is executed again on top of the given state of its parent to record the state it
+    /// reads.
+    fn on_invalid_block<SP, DB, EVM>(
+        externals: &TreeExternals<DB, EVM>,
+        state: SP,
+        block: &BlockWithSenders,
+        block_hash: B256,
+        error: &dyn fmt::Display,
+        computed_state_root: Option<B256>,
+        computed_receipts_root: Option<B256>,
+    ) where
+        SP: StateProvider,
+        EVM: ExecutorFactory,
+    {
+        if externals.invalid_block_hooks.is_empty() {
+            return
+        }
+
+        let recorder = WitnessRecorder::new(state);
+        // the execution is expected to fail again, only the state it reads is of interest
+        let _ = externals
+            .executor_factory
+            .with_state(&recorder)
+            .execute_and_verify_receipt(block, U256::MAX);
+
+        let invalid_block = InvalidBlock {
+            block: block.block.clone().seal(block_hash),
+            senders: block.senders.clone(),
+            error: error.to_string(),
+            state_root: RootDiff { expected: block.state_root, computed: computed_state_root },
+            receipts_root: RootDiff {
+                expected: block.receipts_root,
+                computed: computed_receipts_root,
+            },
+            witness: recorder.into_witness(),
+        };
+        for hook in &externals.invalid_block_hooks {
+            hook.on_invalid_block(&invalid_block);
+        }
+    }
+
+    /// Computes the state root and the trie updates of the given state on top of the database.
+    ///
+    /// The parallel computation reads the trie nodes through the cache of the externals, the
+    /// synchronous computation reads them from the database.
+    ///
+    /// With [StateRootStrategy::Parallel], the state root is recomputed synchronously if the
+    /// parallel computation fails or does not match the expected state root, so that the block is
+    /// only rejected if the synchronous state root does not match either.
+    ///
+    /// If any of the computed state roots does not match, the inputs are written to the state root
+    /// capture of the externals, if any.
+    #[allow(clippy::too_many_arguments)]
+    fn canonical_state_root<DB, EVM>(
+        consistent_view: ConsistentDbView<DB, ProviderFactory<DB>>,
+        externals: &TreeExternals<DB, EVM>,
+        state: &BundleStateWithReceipts,
+        canonical_fork: ForkBlock,
+        block: BlockNumHash,
+        expected: B256,
+        strategy: StateRootStrategy,
+        detailed_metrics: bool,
+    ) -> RethResult<(B256, TrieUpdates)>
+    where
+        DB: Database + Clone,
+    {
+        let mut path = StateRootPath::Synchronous;
+        let mut parallel_root = None;
+        if strategy == StateRootStrategy::Parallel {
+            let start = Instant::now();
+            match ParallelStateRoot::new(consistent_view.clone(), state.hash_state_slow())
+                .with_trie_node_cache(externals.trie_node_cache.clone())
+                .with_detailed_metrics(detailed_metrics)
+                .incremental_root_with_updates()
+            {
+                Ok((root, updates)) if root == expected => {
+                    StateRootPath::Parallel.record(start.elapsed());
+                    return Ok((root, updates))
+                }
+                Ok((root, _)) => {
+                    tracing::debug!(
+                        target: "blockchain_tree::chain",
+                        got = %root,
+                        %expected,
+                        "Parallel state root mismatch, recomputing synchronously"
+                    );
+                    parallel_root = Some(root);
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        target: "blockchain_tree::chain",
+                        %err,
+                        "Parallel state root computation failed, recomputing synchronously"
+                    );
+                }
+            }
+            path = StateRootPath::Fallback;
+        }
+
+        let start = Instant::now();
+        let provider = consistent_view
+            .provider_ro()?
+            // State root calculation can take a while, and we're sure no write transaction
+            // will be open in parallel. See https://github.com/paradigmxyz/reth/issues/7509.
+            .disable_long_read_transaction_safety();
+        let hashed_state = state.hash_state_slow();
+        let (root, updates) = hashed_state
+            .state_root_with_updates(provider.tx_ref())
+            .map_err(|err| ProviderError::Database(err.into()))?;
+        path.record(start.elapsed());
+
+        if matches!(path, StateRootPath::Fallback) && root == expected {
+            tracing::warn!(
+                target: "blockchain_tree::chain",
+                %root,
+                "Parallel state root was incorrect, using synchronous state root"
+            );
+        }
+
+        if let Some(capture) = externals
+            .state_root_capture
+            .as_ref()
+            .filter(|_| matches!(path, StateRootPath::Fallback) || root != expected)
+        {
+            let input = StateRootInput {
+                block_number: block.number,
+                block_hash: block.hash,
+                tip_number: canonical_fork.number,
+                tip_hash: canonical_fork.hash,
+                expected_state_root: expected,
+                parallel_state_root: parallel_root,
+                synchronous_state_root: root,
+                state: HashedStateInput::from(&hashed_state),
+            };
+            match capture.write(&input) {
+                Ok(file) => tracing::info!(
+                    target: "blockchain_tree::chain",
+                    file = %file.display(),
+                    "Captured state root input"
+                ),
+                Err(err) => tracing::warn!(
+                    target: "blockchain_tree::chain",
+                    %err,
+                    "Failed to capture state root input"
+                ),
+            }
+        }
+
+        Ok((root, updates))
+    }
+
     /// Validate and execute the given block, and append it to this chain.
     ///
     /// This expects that the block's ancestors can be traced back to the `canonical_fork` (the
@@ -268,6 +530,8 @@ impl AppendableChain {
         canonical_fork: ForkBlock,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
+        state_root_strategy: StateRootStrategy,
+        detailed_state_root_metrics: bool,
     ) -> Result<(), InsertBlockErrorKind>
     where
         DB: Database + Clone,
@@ -289,6 +553,8 @@ impl AppendableChain {
             externals,
             block_attachment,
             block_validation_kind,
+            state_root_strategy,
+            detailed_state_root_metrics,
         )?;
         // extend the state.
         self.chain.append_block(block, block_state);
@@ -0,0 +1,261 @@
+//! Periodic trie checkpoints used to handle reorgs that fork off below the in-memory window of the
+//! [`BlockchainTree`](crate::BlockchainTree).
+
+use crate::TrieCheckpointsConfig;
+use reth_db::{transaction::DbTx, DatabaseError};
+use reth_primitives::{BlockHash, BlockNumber, SealedHeader, B256};
+use reth_trie::HashedPostState;
+use std::collections::BTreeMap;
+
+/// A canonical block at a checkpoint interval and the hashed state reverts of the interval that
+/// ends at it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrieCheckpoint {
+    /// Number of the checkpoint block.
+    pub number: BlockNumber,
+    /// Hash of the checkpoint block.
+    pub hash: BlockHash,
+    /// State root of the checkpoint block.
+    pub state_root: B256,
+    /// Hashed state reverts of the blocks in `(number - interval, number]`.
+    ///
+    /// Applied on top of the state of this checkpoint, they yield the state of the previous
+    /// checkpoint.
+    pub reverts: HashedPostState,
+}
+
+/// Trie checkpoints of the canonical chain, recorded every `interval` blocks.
+///
+/// The state of a canonical block is reconstructed on top of the database state by applying the
+/// reverts of the checkpoints above it, so that the state root of a block that forks off deep
+/// below the tip only reads the changesets of at most two intervals from the database.
+///
+/// Only checkpoints within `max_depth` blocks of the canonical tip are retained, which bounds the
+/// depth of the forks that can be validated.
+#[derive(Debug, Clone)]
+pub struct TrieCheckpoints {
+    /// Checkpoints configuration.
+    config: TrieCheckpointsConfig,
+    /// Recorded checkpoints by block number.
+    checkpoints: BTreeMap<BlockNumber, TrieCheckpoint>,
+}
+
+impl TrieCheckpoints {
+    /// Create an empty set of checkpoints.
+    pub fn new(config: TrieCheckpointsConfig) -> Self {
+        Self { config, checkpoints: BTreeMap::new() }
+    }
+
+    /// Returns the checkpoints configuration.
+    pub fn config(&self) -> TrieCheckpointsConfig {
+        self.config
+    }
+
+    /// Returns `true` if a checkpoint should be recorded for the given block number.
+    pub fn is_checkpoint(&self, number: BlockNumber) -> bool {
+        number % self.config.interval == 0
+    }
+
+    /// Returns the block numbers of the checkpoints that should be retained for the given
+    /// canonical tip, in ascending order.
+    pub fn checkpoint_numbers(&self, tip: BlockNumber) -> impl Iterator<Item = BlockNumber> {
+        let interval = self.config.interval;
+        let lowest = tip.saturating_sub(self.config.max_depth).div_ceil(interval) * interval;
+        (lowest..=tip).step_by(interval as usize)
+    }
+
+    /// Returns the range of blocks whose reverts are recorded by the checkpoint at the given block
+    /// number.
+    pub fn interval_range(&self, number: BlockNumber) -> std::ops::RangeInclusive<BlockNumber> {
+        (number.saturating_sub(self.config.interval) + 1)..=number
+    }
+
+    /// Record the header as a checkpoint with the reverts of its interval, see
+    /// [Self::interval_range], if it is at a checkpoint interval.
+    ///
+    /// Returns `true` if the checkpoint was recorded.
+    pub fn record(&mut self, header: &SealedHeader, reverts: HashedPostState) -> bool {
+        if !self.is_checkpoint(header.number) {
+            return false
+        }
+        self.checkpoints.insert(
+            header.number,
+            TrieCheckpoint {
+                number: header.number,
+                hash: header.hash(),
+                state_root: header.state_root,
+                reverts,
+            },
+        );
+        true
+    }
+
+    /// Remove all checkpoints that are deeper than `max_depth` below the given tip.
+    pub fn prune(&mut self, tip: BlockNumber) {
+        let lowest = tip.saturating_sub(self.config.max_depth);
+        self.checkpoints = self.checkpoints.split_off(&lowest);
+    }
+
+    /// Remove all checkpoints that are lower than the given finalized block, as the chain can not
+    /// be reorged below it.
+    pub fn finalize(&mut self, finalized: BlockNumber) {
+        self.checkpoints = self.checkpoints.split_off(&finalized);
+    }
+
+    /// Remove all checkpoints above the given block number, i.e. the block stays.
+    pub fn unwind(&mut self, unwind_to: BlockNumber) {
+        self.checkpoints.retain(|number, _| *number <= unwind_to);
+    }
+
+    /// Returns the highest checkpoint at or below the given block number.
+    ///
+    /// If there is one, the state of the block can be reconstructed from the checkpoints, see
+    /// [Self::revert_state].
+    pub fn nearest(&self, number: BlockNumber) -> Option<&TrieCheckpoint> {
+        self.checkpoints.range(..=number).next_back().map(|(_, checkpoint)| checkpoint)
+    }
+
+    /// Returns the hashed state reverts that turn the state of the canonical `tip` in the database
+    /// into the state of the canonical `fork` block.
+    ///
+    /// The reverts of the checkpoint intervals between the fork block and the tip are taken from
+    /// the checkpoints, only the blocks that are not covered by a checkpoint are read from the
+    /// changesets in the database.
+    pub fn revert_state<TX: DbTx>(
+        &self,
+        tx: &TX,
+        fork: BlockNumber,
+        tip: BlockNumber,
+    ) -> Result<HashedPostState, DatabaseError> {
+        // the reverts are collected from the tip down to the fork block, so that the reverts of
+        // older blocks take precedence.
+        let mut reverts = HashedPostState::default();
+        // the blocks in `(fork, upper]` are not reverted yet.
+        let mut upper = tip;
+        let lowest = fork.saturating_add(self.config.interval);
+        for checkpoint in self.checkpoints.range(lowest..=tip).rev().map(|(_, c)| c) {
+            if checkpoint.number < upper {
+                reverts.extend(HashedPostState::from_revert_range(
+                    tx,
+                    (checkpoint.number + 1)..=upper,
+                )?);
+            }
+            reverts.extend(checkpoint.reverts.clone());
+            upper = checkpoint.number - self.config.interval;
+        }
+        if upper > fork {
+            reverts.extend(HashedPostState::from_revert_range(tx, (fork + 1)..=upper)?);
+        }
+
+        Ok(reverts)
+    }
+
+    /// Returns the number of retained checkpoints.
+    pub fn len(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// Returns `true` if there are no retained checkpoints.
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{
+        models::AccountBeforeTx, tables, test_utils::create_test_rw_db, transaction::DbTxMut,
+        Database,
+    };
+    use reth_primitives::{keccak256, Account, Address, Header};
+
+    fn header(number: BlockNumber) -> SealedHeader {
+        Header { number, ..Default::default() }.seal_slow()
+    }
+
+    fn checkpoints() -> TrieCheckpoints {
+        TrieCheckpoints::new(TrieCheckpointsConfig { interval: 10, max_depth: 50 })
+    }
+
+    fn numbers(checkpoints: &TrieCheckpoints) -> Vec<BlockNumber> {
+        checkpoints.checkpoints.keys().copied().collect()
+    }
+
+    #[test]
+    fn record_and_prune() {
+        let mut checkpoints = checkpoints();
+        for number in 0..=100 {
+            checkpoints.record(&header(number), HashedPostState::default());
+        }
+        assert_eq!(checkpoints.len(), 11);
+
+        checkpoints.prune(100);
+        assert_eq!(numbers(&checkpoints), vec![50, 60, 70, 80, 90, 100]);
+
+        checkpoints.finalize(65);
+        assert_eq!(numbers(&checkpoints), vec![70, 80, 90, 100]);
+    }
+
+    #[test]
+    fn nearest_and_unwind() {
+        let mut checkpoints = checkpoints();
+        for number in 0..=100 {
+            checkpoints.record(&header(number), HashedPostState::default());
+        }
+
+        assert_eq!(checkpoints.nearest(79).map(|c| c.number), Some(70));
+        assert_eq!(checkpoints.nearest(80).map(|c| c.number), Some(80));
+
+        checkpoints.unwind(75);
+        assert_eq!(checkpoints.nearest(100).map(|c| c.number), Some(70));
+    }
+
+    #[test]
+    fn checkpoint_numbers() {
+        let checkpoints = checkpoints();
+        assert_eq!(
+            checkpoints.checkpoint_numbers(105).collect::<Vec<_>>(),
+            vec![60, 70, 80, 90, 100]
+        );
+        assert_eq!(checkpoints.checkpoint_numbers(20).collect::<Vec<_>>(), vec![0, 10, 20]);
+        assert_eq!(checkpoints.interval_range(20), 11..=20);
+        assert_eq!(checkpoints.interval_range(0), 1..=0);
+    }
+
+    #[test]
+    fn revert_state_matches_changesets() {
+        let db = create_test_rw_db();
+        let address = Address::random();
+        let account = |nonce| Account { nonce, ..Default::default() };
+
+        // the account is changed in every block, its changeset holds the nonce before the block.
+        let tx = db.tx_mut().unwrap();
+        for number in 1..=35 {
+            tx.put::<tables::AccountChangeSets>(
+                number,
+                AccountBeforeTx { address, info: Some(account(number - 1)) },
+            )
+            .unwrap();
+        }
+        tx.commit().unwrap();
+
+        let mut checkpoints = checkpoints();
+        let tx = db.tx().unwrap();
+        for number in [10, 20, 30] {
+            let reverts =
+                HashedPostState::from_revert_range(&tx, checkpoints.interval_range(number))
+                    .unwrap();
+            checkpoints.record(&header(number), reverts);
+        }
+
+        for fork in [0, 5, 10, 15, 29, 34, 35] {
+            let expected = HashedPostState::from_revert_range(&tx, (fork + 1)..=35).unwrap();
+            let reverts = checkpoints.revert_state(&tx, fork, 35).unwrap();
+            assert_eq!(reverts, expected, "fork {fork}");
+            if fork < 35 {
+                assert_eq!(reverts.accounts.get(&keccak256(address)), Some(&Some(account(fork))));
+            }
+        }
+    }
+}
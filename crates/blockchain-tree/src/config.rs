@@ -1,5 +1,7 @@
 //! Blockchain tree configuration
 
+use std::{fmt, str::FromStr};
+
 /// The configuration for the blockchain tree.
 #[derive(Clone, Copy, Debug)]
 pub struct BlockchainTreeConfig {
@@ -19,6 +21,78 @@ pub struct BlockchainTreeConfig {
     /// be 256. It covers both number of blocks required for reorg, and number of blocks
     /// required for `BLOCKHASH` EVM opcode.
     num_of_additional_canonical_block_hashes: u64,
+    /// Periodic trie checkpoints used to handle reorgs deeper than `max_reorg_depth`.
+    ///
+    /// If `None`, reorgs below the in-memory window are not supported.
+    trie_checkpoints: Option<TrieCheckpointsConfig>,
+    /// Number of processed reorgs that are kept in the reorg history table.
+    ///
+    /// If zero, reorgs are not recorded.
+    reorg_history_retention: u64,
+    /// How the state root of blocks that extend the canonical chain is computed.
+    state_root_strategy: StateRootStrategy,
+    /// Whether to record the detailed metrics of parallel state root computations.
+    detailed_state_root_metrics: bool,
+}
+
+/// The configuration for periodic trie checkpoints.
+///
+/// Every `interval` blocks, the hashed state reverts of the interval are recorded with the
+/// canonical block as a checkpoint. A block that forks off below the in-memory window is validated
+/// on top of the state reconstructed from the checkpoints, instead of being rejected. The database
+/// is only reverted to the fork block once the fork becomes canonical.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrieCheckpointsConfig {
+    /// Number of blocks between two consecutive checkpoints.
+    pub interval: u64,
+    /// Maximum depth below the canonical tip for which checkpoints are retained.
+    ///
+    /// This bounds the deepest fork that can be validated with checkpoints.
+    pub max_depth: u64,
+}
+
+/// How the state root of a block that extends the canonical chain is computed.
+///
+/// The state root of a block that is attached to a sidechain is always computed on top of the
+/// in-memory state of the sidechain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StateRootStrategy {
+    /// Compute the storage roots of the changed accounts in parallel.
+    ///
+    /// If the computation fails or the root does not match the state root of the block, the state
+    /// root is recomputed synchronously before the block is rejected.
+    #[default]
+    Parallel,
+    /// Compute the state root on a single database transaction.
+    Synchronous,
+}
+
+impl StateRootStrategy {
+    /// Returns the name of the strategy.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Parallel => "parallel",
+            Self::Synchronous => "synchronous",
+        }
+    }
+}
+
+impl FromStr for StateRootStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parallel" => Ok(Self::Parallel),
+            "synchronous" => Ok(Self::Synchronous),
+            _ => Err(format!("invalid state root strategy: {s}, expected parallel or synchronous")),
+        }
+    }
+}
+
+impl fmt::Display for StateRootStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 impl Default for BlockchainTreeConfig {
@@ -33,6 +107,14 @@ impl Default for BlockchainTreeConfig {
             num_of_additional_canonical_block_hashes: 256,
             // max unconnected blocks.
             max_unconnected_blocks: 200,
+            // deep reorgs are disabled by default.
+            trie_checkpoints: None,
+            // keep the last 1000 reorgs.
+            reorg_history_retention: 1000,
+            // parallel state root with synchronous fallback.
+            state_root_strategy: StateRootStrategy::Parallel,
+            // only the aggregated state root metrics.
+            detailed_state_root_metrics: false,
         }
     }
 }
@@ -53,9 +135,50 @@ impl BlockchainTreeConfig {
             max_reorg_depth,
             num_of_additional_canonical_block_hashes,
             max_unconnected_blocks,
+            trie_checkpoints: None,
+            reorg_history_retention: Self::default().reorg_history_retention,
+            state_root_strategy: StateRootStrategy::default(),
+            detailed_state_root_metrics: false,
         }
     }
 
+    /// Enable periodic trie checkpoints, allowing reorgs deeper than `max_reorg_depth`.
+    ///
+    /// # Panics
+    ///
+    /// If `interval` is zero or `max_depth` is lower than `max_reorg_depth`.
+    pub fn with_trie_checkpoints(mut self, interval: u64, max_depth: u64) -> Self {
+        if interval == 0 {
+            panic!("Trie checkpoint interval should be greater than zero");
+        }
+        if max_depth < self.max_reorg_depth {
+            panic!("Maximum checkpoint depth should be at least the finalization window");
+        }
+        self.trie_checkpoints = Some(TrieCheckpointsConfig { interval, max_depth });
+        self
+    }
+
+    /// Set the number of processed reorgs that are kept in the reorg history table.
+    ///
+    /// Zero disables recording reorgs.
+    pub fn with_reorg_history_retention(mut self, retention: u64) -> Self {
+        self.reorg_history_retention = retention;
+        self
+    }
+
+    /// Set how the state root of blocks that extend the canonical chain is computed.
+    pub fn with_state_root_strategy(mut self, strategy: StateRootStrategy) -> Self {
+        self.state_root_strategy = strategy;
+        self
+    }
+
+    /// Set whether to record the detailed metrics of parallel state root computations, e.g. the
+    /// number of storage roots in flight and the time spent in each phase.
+    pub fn with_detailed_state_root_metrics(mut self, enabled: bool) -> Self {
+        self.detailed_state_root_metrics = enabled;
+        self
+    }
+
     /// Return the maximum reorg depth.
     pub fn max_reorg_depth(&self) -> u64 {
         self.max_reorg_depth
@@ -87,4 +210,24 @@ impl BlockchainTreeConfig {
     pub fn max_unconnected_blocks(&self) -> usize {
         self.max_unconnected_blocks
     }
+
+    /// Return the trie checkpoints configuration, if deep reorgs are enabled.
+    pub fn trie_checkpoints(&self) -> Option<TrieCheckpointsConfig> {
+        self.trie_checkpoints
+    }
+
+    /// Return the number of processed reorgs that are kept in the reorg history table.
+    pub fn reorg_history_retention(&self) -> u64 {
+        self.reorg_history_retention
+    }
+
+    /// Return how the state root of blocks that extend the canonical chain is computed.
+    pub fn state_root_strategy(&self) -> StateRootStrategy {
+        self.state_root_strategy
+    }
+
+    /// Return whether the detailed metrics of parallel state root computations are recorded.
+    pub fn detailed_state_root_metrics(&self) -> bool {
+        self.detailed_state_root_metrics
+    }
 }
@@ -0,0 +1,316 @@
+//! Cache of the state that is read while executing blocks on top of the canonical head.
+
+use parking_lot::RwLock;
+use reth_interfaces::provider::ProviderResult;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{
+    trie::AccountProof, Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256,
+};
+use reth_provider::{AccountReader, BlockHashReader, StateProvider, StateRootProvider};
+use reth_trie::updates::TrieUpdates;
+use revm::db::BundleState;
+use std::{collections::HashMap, sync::Arc};
+
+/// Default maximum number of accounts, storage slots and bytecodes kept in an [ExecutionCache].
+pub const DEFAULT_EXECUTION_CACHE_ENTRIES: usize = 1_000_000;
+
+/// A cache of the accounts, storage slots and bytecodes of the state at the canonical head.
+///
+/// The cache is shared between the execution of the blocks that extend the canonical chain and
+/// tasks that warm it up ahead of time, e.g. by speculatively executing pending transactions.
+///
+/// The cache is bound to the state in the database. Every change of the database state starts a
+/// new epoch and drops the affected entries, and entries that were read in an earlier epoch are
+/// not inserted anymore.
+#[derive(Clone, Debug)]
+pub struct ExecutionCache {
+    inner: Arc<RwLock<ExecutionCacheInner>>,
+    metrics: ExecutionCacheMetrics,
+}
+
+#[derive(Debug, Default)]
+struct ExecutionCacheInner {
+    /// The current epoch, incremented on every change of the database state.
+    epoch: u64,
+    /// Cached accounts.
+    accounts: HashMap<Address, Option<Account>>,
+    /// Cached storage slots, keyed by account.
+    storage: HashMap<Address, HashMap<StorageKey, Option<StorageValue>>>,
+    /// Cached bytecodes, keyed by code hash.
+    bytecodes: HashMap<B256, Bytecode>,
+    /// Total number of cached entries.
+    entries: usize,
+    /// Maximum number of cached entries, the cache is cleared once it is exceeded.
+    max_entries: usize,
+}
+
+impl ExecutionCacheInner {
+    fn clear(&mut self) {
+        self.epoch += 1;
+        self.accounts.clear();
+        self.storage.clear();
+        self.bytecodes.clear();
+        self.entries = 0;
+    }
+
+    /// Returns `false` if the entry must not be inserted because it was read in an earlier epoch.
+    fn reserve(&mut self, epoch: u64) -> bool {
+        if epoch != self.epoch {
+            return false
+        }
+        if self.entries >= self.max_entries {
+            // keep the epoch, the entries are still consistent with the database
+            let epoch = self.epoch;
+            self.clear();
+            self.epoch = epoch;
+        }
+        true
+    }
+}
+
+impl Default for ExecutionCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_EXECUTION_CACHE_ENTRIES)
+    }
+}
+
+impl ExecutionCache {
+    /// Creates a new cache that holds up to `max_entries` accounts, storage slots and bytecodes.
+    pub fn new(max_entries: usize) -> Self {
+        let inner = ExecutionCacheInner { max_entries, ..Default::default() };
+        Self { inner: Arc::new(RwLock::new(inner)), metrics: ExecutionCacheMetrics::default() }
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.inner.read().entries
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the current epoch of the cache.
+    ///
+    /// The epoch must be read __before__ the state provider that is wrapped in a
+    /// [CachedStateProvider] is opened.
+    pub fn epoch(&self) -> u64 {
+        self.inner.read().epoch
+    }
+
+    /// Removes all cached entries and starts a new epoch.
+    pub fn clear(&self) {
+        self.inner.write().clear();
+    }
+
+    /// Removes the accounts and storage slots that were changed by the given state, which was
+    /// written to the database, and starts a new epoch.
+    pub fn invalidate<'a>(&self, changed_accounts: impl IntoIterator<Item = &'a Address>) {
+        let mut inner = self.inner.write();
+        inner.epoch += 1;
+        let mut removed = 0;
+        for address in changed_accounts {
+            removed += inner.accounts.remove(address).is_some() as usize;
+            removed += inner.storage.remove(address).map_or(0, |slots| slots.len());
+        }
+        inner.entries -= removed;
+    }
+
+    fn account(&self, address: &Address) -> Option<Option<Account>> {
+        self.inner.read().accounts.get(address).copied()
+    }
+
+    fn insert_account(&self, epoch: u64, address: Address, account: Option<Account>) {
+        let mut inner = self.inner.write();
+        if inner.reserve(epoch) && inner.accounts.insert(address, account).is_none() {
+            inner.entries += 1;
+        }
+    }
+
+    fn storage(&self, address: &Address, key: &StorageKey) -> Option<Option<StorageValue>> {
+        self.inner.read().storage.get(address)?.get(key).copied()
+    }
+
+    fn insert_storage(
+        &self,
+        epoch: u64,
+        address: Address,
+        key: StorageKey,
+        value: Option<StorageValue>,
+    ) {
+        let mut inner = self.inner.write();
+        if inner.reserve(epoch) &&
+            inner.storage.entry(address).or_default().insert(key, value).is_none()
+        {
+            inner.entries += 1;
+        }
+    }
+
+    fn bytecode(&self, code_hash: &B256) -> Option<Bytecode> {
+        self.inner.read().bytecodes.get(code_hash).cloned()
+    }
+
+    fn insert_bytecode(&self, epoch: u64, code_hash: B256, bytecode: Bytecode) {
+        let mut inner = self.inner.write();
+        if inner.reserve(epoch) && inner.bytecodes.insert(code_hash, bytecode).is_none() {
+            inner.entries += 1;
+        }
+    }
+}
+
+/// A [StateProvider] that serves accounts, storage slots and bytecodes from an [ExecutionCache]
+/// and caches the ones it reads from the wrapped state provider.
+///
+/// The wrapped state provider must be the latest state of the database.
+#[derive(Debug)]
+pub struct CachedStateProvider<SP> {
+    /// The latest state.
+    state: SP,
+    /// The shared cache.
+    cache: ExecutionCache,
+    /// The epoch of the cache when the state was opened.
+    epoch: u64,
+}
+
+impl<SP: StateProvider> CachedStateProvider<SP> {
+    /// Creates a new cached state provider on top of the latest state.
+    ///
+    /// The `epoch` must be read from the cache before the latest state was opened, see
+    /// [ExecutionCache::epoch].
+    pub fn new(state: SP, cache: ExecutionCache, epoch: u64) -> Self {
+        Self { state, cache, epoch }
+    }
+}
+
+impl<SP: StateProvider> BlockHashReader for CachedStateProvider<SP> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.state.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.state.canonical_hashes_range(start, end)
+    }
+}
+
+impl<SP: StateProvider> AccountReader for CachedStateProvider<SP> {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.cache.account(&address) {
+            self.cache.metrics.hits.increment(1);
+            return Ok(account)
+        }
+        self.cache.metrics.misses.increment(1);
+        let account = self.state.basic_account(address)?;
+        self.cache.insert_account(self.epoch, address, account);
+        Ok(account)
+    }
+}
+
+impl<SP: StateProvider> StateRootProvider for CachedStateProvider<SP> {
+    fn state_root(&self, bundle_state: &BundleState) -> ProviderResult<B256> {
+        self.state.state_root(bundle_state)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        bundle_state: &BundleState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state.state_root_with_updates(bundle_state)
+    }
+}
+
+impl<SP: StateProvider> StateProvider for CachedStateProvider<SP> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) = self.cache.storage(&account, &storage_key) {
+            self.cache.metrics.hits.increment(1);
+            return Ok(value)
+        }
+        self.cache.metrics.misses.increment(1);
+        let value = self.state.storage(account, storage_key)?;
+        self.cache.insert_storage(self.epoch, account, storage_key, value);
+        Ok(value)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        if let Some(bytecode) = self.cache.bytecode(&code_hash) {
+            self.cache.metrics.hits.increment(1);
+            return Ok(Some(bytecode))
+        }
+        self.cache.metrics.misses.increment(1);
+        let bytecode = self.state.bytecode_by_hash(code_hash)?;
+        if let Some(bytecode) = &bytecode {
+            self.cache.insert_bytecode(self.epoch, code_hash, bytecode.clone());
+        }
+        Ok(bytecode)
+    }
+
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.state.proof(address, keys)
+    }
+}
+
+/// Metrics for the execution cache.
+#[derive(Clone, Metrics)]
+#[metrics(scope = "blockchain_tree.execution_cache")]
+struct ExecutionCacheMetrics {
+    /// The number of reads that were served from the cache
+    hits: Counter,
+    /// The number of reads that were served from the database
+    misses: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn serves_reads_from_cache() {
+        let address = Address::random();
+        let slot = B256::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::from(1)).extend_storage([(slot, U256::from(2))]),
+        );
+
+        let cache = ExecutionCache::default();
+        let state = CachedStateProvider::new(provider.clone(), cache.clone(), cache.epoch());
+        assert_eq!(state.basic_account(address).unwrap().unwrap().balance, U256::from(1));
+        assert_eq!(state.storage(address, slot).unwrap(), Some(U256::from(2)));
+        assert_eq!(cache.len(), 2);
+
+        // the cached values are served even if the state changed
+        provider.add_account(address, ExtendedAccount::new(0, U256::from(3)));
+        assert_eq!(state.basic_account(address).unwrap().unwrap().balance, U256::from(1));
+
+        // once the change is committed, the account is read again
+        cache.invalidate([&address]);
+        assert!(cache.is_empty());
+        let state = CachedStateProvider::new(provider.clone(), cache.clone(), cache.epoch());
+        assert_eq!(state.basic_account(address).unwrap().unwrap().balance, U256::from(3));
+    }
+
+    #[test]
+    fn ignores_reads_of_previous_epoch() {
+        let address = Address::random();
+        let provider = MockEthProvider::default();
+        provider.add_account(address, ExtendedAccount::new(0, U256::from(1)));
+
+        let cache = ExecutionCache::default();
+        let state = CachedStateProvider::new(provider, cache.clone(), cache.epoch());
+        cache.invalidate([&address]);
+
+        assert!(state.basic_account(address).unwrap().is_some());
+        assert!(cache.is_empty());
+    }
+}
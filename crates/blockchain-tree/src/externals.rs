@@ -1,12 +1,16 @@
 //! Blockchain tree externals.
 
+use crate::{
+    ExecutionCache, InvalidBlockHook, StateRootCapture, TrieCheckpoints, TrieCheckpointsConfig,
+};
 use reth_consensus::Consensus;
 use reth_db::{
     cursor::DbCursorRO, database::Database, static_file::HeaderMask, tables, transaction::DbTx,
 };
 use reth_interfaces::RethResult;
 use reth_primitives::{BlockHash, BlockNumber, StaticFileSegment};
-use reth_provider::{ProviderFactory, StatsReader};
+use reth_provider::{BlockNumReader, HeaderProvider, ProviderFactory, StatsReader};
+use reth_trie::{trie_cursor::TrieNodeCache, HashedPostState};
 use std::{collections::BTreeMap, sync::Arc};
 
 /// A container for external components.
@@ -18,6 +22,11 @@ use std::{collections::BTreeMap, sync::Arc};
 /// - A handle to the consensus engine
 /// - The executor factory to execute blocks with
 /// - The chain spec
+/// - A cache of the trie nodes in the database
+/// - An optional cache of the state read while executing blocks
+/// - An optional capture of the inputs of mismatching state roots
+/// - Optional trie checkpoints of the canonical chain
+/// - The hooks that are invoked with the blocks that fail validation
 #[derive(Debug)]
 pub struct TreeExternals<DB, EVM> {
     /// The provider factory, used to commit the canonical chain, or unwind it.
@@ -26,6 +35,18 @@ pub struct TreeExternals<DB, EVM> {
     pub(crate) consensus: Arc<dyn Consensus>,
     /// The executor factory to execute blocks with.
     pub(crate) executor_factory: EVM,
+    /// Cache of the trie nodes in the database, shared between the state root computations of
+    /// consecutive blocks.
+    pub(crate) trie_node_cache: TrieNodeCache,
+    /// Cache of the state in the database, read by the blocks that extend the canonical chain.
+    pub(crate) execution_cache: Option<ExecutionCache>,
+    /// Capture of the inputs of state root computations that did not match the block.
+    pub(crate) state_root_capture: Option<StateRootCapture>,
+    /// Periodic trie checkpoints of the canonical chain, used to compute the state root of blocks
+    /// that fork off below the in-memory window. Loaded by the tree if deep reorgs are enabled.
+    pub(crate) trie_checkpoints: Option<TrieCheckpoints>,
+    /// Hooks that are invoked with the blocks that fail execution or root validation.
+    pub(crate) invalid_block_hooks: Vec<Box<dyn InvalidBlockHook>>,
 }
 
 impl<DB, EVM> TreeExternals<DB, EVM> {
@@ -35,7 +56,39 @@ impl<DB, EVM> TreeExternals<DB, EVM> {
         consensus: Arc<dyn Consensus>,
         executor_factory: EVM,
     ) -> Self {
-        Self { provider_factory, consensus, executor_factory }
+        Self {
+            provider_factory,
+            consensus,
+            executor_factory,
+            trie_node_cache: Default::default(),
+            execution_cache: None,
+            state_root_capture: None,
+            trie_checkpoints: None,
+            invalid_block_hooks: Vec::new(),
+        }
+    }
+
+    /// Sets the cache of the state that is read while executing blocks that extend the canonical
+    /// chain.
+    ///
+    /// The cache can be warmed up by other tasks, e.g. by executing pending transactions.
+    pub fn with_execution_cache(mut self, execution_cache: ExecutionCache) -> Self {
+        self.execution_cache = Some(execution_cache);
+        self
+    }
+
+    /// Sets the capture that the inputs of state root computations of blocks that extend the
+    /// canonical chain are written to if the computed state root does not match the block.
+    pub fn with_state_root_capture(mut self, capture: StateRootCapture) -> Self {
+        self.state_root_capture = Some(capture);
+        self
+    }
+
+    /// Adds a hook that is invoked with every block that fails execution or whose state root or
+    /// receipts root does not match the computed one.
+    pub fn with_invalid_block_hook(mut self, hook: Box<dyn InvalidBlockHook>) -> Self {
+        self.invalid_block_hooks.push(hook);
+        self
     }
 }
 
@@ -82,4 +135,29 @@ impl<DB: Database, EVM> TreeExternals<DB, EVM> {
         let hashes = hashes.into_iter().rev().take(num_hashes).collect();
         Ok(hashes)
     }
+
+    /// Loads the trie checkpoints of the canonical chain that are retained for the current tip.
+    ///
+    /// Every canonical block in the database had its state root verified when it was committed,
+    /// so checkpoints are rebuilt from the canonical headers and the changesets of their intervals.
+    pub(crate) fn fetch_trie_checkpoints(
+        &self,
+        config: TrieCheckpointsConfig,
+    ) -> RethResult<TrieCheckpoints> {
+        let provider = self.provider_factory.provider()?;
+        let tip = provider.last_block_number()?;
+
+        let mut checkpoints = TrieCheckpoints::new(config);
+        for number in checkpoints.checkpoint_numbers(tip).collect::<Vec<_>>() {
+            if let Some(header) = provider.sealed_header(number)? {
+                let reverts = HashedPostState::from_revert_range(
+                    provider.tx_ref(),
+                    checkpoints.interval_range(number),
+                )?;
+                checkpoints.record(&header, reverts);
+            }
+        }
+
+        Ok(checkpoints)
+    }
 }
@@ -0,0 +1,385 @@
+//! Hooks that are invoked with the blocks that fail validation.
+
+use parking_lot::Mutex;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{
+    trie::AccountProof, Account, Address, BlockNumber, Bytecode, SealedBlock, StorageKey,
+    StorageValue, B256,
+};
+use reth_provider::{AccountReader, BlockHashReader, StateProvider, StateRootProvider};
+use reth_trie::updates::TrieUpdates;
+use revm::db::BundleState;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// A hook that is invoked with every block that fails execution or whose roots do not match the
+/// computed ones.
+///
+/// Hooks are invoked synchronously by the blockchain tree, so they should not block for long.
+pub trait InvalidBlockHook: fmt::Debug + Send + Sync {
+    /// Invoked with the block that failed validation.
+    fn on_invalid_block(&self, invalid_block: &InvalidBlock);
+}
+
+/// A block that failed validation, along with the state it read during execution.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidBlock {
+    /// The invalid block.
+    pub block: SealedBlock,
+    /// The recovered senders of the transactions of the block.
+    pub senders: Vec<Address>,
+    /// The validation error.
+    pub error: String,
+    /// The state root of the block and the computed one, if the block was executed and its state
+    /// root computed.
+    pub state_root: RootDiff,
+    /// The receipts root of the block and the computed one, if the block was executed.
+    pub receipts_root: RootDiff,
+    /// The state that was read while executing the block.
+    pub witness: ExecutionWitness,
+}
+
+/// A root of a block and the root computed from its execution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RootDiff {
+    /// The root of the block.
+    pub expected: B256,
+    /// The computed root, if it was computed.
+    pub computed: Option<B256>,
+}
+
+/// The state that was read while executing a block, on top of the state of its parent.
+///
+/// The block can be executed again on top of the witness alone.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionWitness {
+    /// The accounts that were read. `None` if the account does not exist.
+    pub accounts: BTreeMap<Address, Option<Account>>,
+    /// The storage slots that were read, keyed by account.
+    pub storage: BTreeMap<Address, BTreeMap<StorageKey, StorageValue>>,
+    /// The bytecodes that were read, keyed by code hash.
+    pub bytecodes: BTreeMap<B256, Bytecode>,
+    /// The block hashes that were read.
+    pub block_hashes: BTreeMap<BlockNumber, B256>,
+}
+
+/// A [StateProvider] that records the state that is read from the wrapped state provider into an
+/// [ExecutionWitness].
+#[derive(Debug)]
+pub(crate) struct WitnessRecorder<SP> {
+    /// The wrapped state.
+    state: SP,
+    /// The recorded state.
+    witness: Mutex<ExecutionWitness>,
+}
+
+impl<SP: StateProvider> WitnessRecorder<SP> {
+    /// Creates a new recorder on top of the given state.
+    pub(crate) fn new(state: SP) -> Self {
+        Self { state, witness: Mutex::default() }
+    }
+
+    /// Returns the recorded state.
+    pub(crate) fn into_witness(self) -> ExecutionWitness {
+        self.witness.into_inner()
+    }
+}
+
+impl<SP: StateProvider> BlockHashReader for WitnessRecorder<SP> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        let hash = self.state.block_hash(number)?;
+        if let Some(hash) = hash {
+            self.witness.lock().block_hashes.insert(number, hash);
+        }
+        Ok(hash)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        let hashes = self.state.canonical_hashes_range(start, end)?;
+        self.witness.lock().block_hashes.extend((start..end).zip(hashes.iter().copied()));
+        Ok(hashes)
+    }
+}
+
+impl<SP: StateProvider> AccountReader for WitnessRecorder<SP> {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        let account = self.state.basic_account(address)?;
+        self.witness.lock().accounts.insert(address, account);
+        Ok(account)
+    }
+}
+
+impl<SP: StateProvider> StateRootProvider for WitnessRecorder<SP> {
+    fn state_root(&self, bundle_state: &BundleState) -> ProviderResult<B256> {
+        self.state.state_root(bundle_state)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        bundle_state: &BundleState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state.state_root_with_updates(bundle_state)
+    }
+}
+
+impl<SP: StateProvider> StateProvider for WitnessRecorder<SP> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        let value = self.state.storage(account, storage_key)?;
+        self.witness
+            .lock()
+            .storage
+            .entry(account)
+            .or_default()
+            .insert(storage_key, value.unwrap_or_default());
+        Ok(value)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        let bytecode = self.state.bytecode_by_hash(code_hash)?;
+        if let Some(bytecode) = &bytecode {
+            self.witness.lock().bytecodes.insert(code_hash, bytecode.clone());
+        }
+        Ok(bytecode)
+    }
+
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.state.proof(address, keys)
+    }
+}
+
+/// An [InvalidBlockHook] that writes the invalid blocks to a directory, as debug bundles that can
+/// be inspected and executed offline.
+#[derive(Clone, Debug)]
+pub struct DiskInvalidBlockHook {
+    /// The directory the invalid blocks are written to.
+    dir: PathBuf,
+}
+
+impl DiskInvalidBlockHook {
+    /// Creates a new hook that writes to the given directory.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Writes the invalid block to `<block_number>-<block_hash>.json` in the directory and returns
+    /// the path of the file.
+    pub fn write(&self, invalid_block: &InvalidBlock) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!(
+            "{}-{}.json",
+            invalid_block.block.number,
+            invalid_block.block.hash()
+        ));
+        fs::write(&path, serde_json::to_vec(invalid_block)?)?;
+        Ok(path)
+    }
+
+    /// Reads an invalid block from the given JSON file.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<InvalidBlock> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+}
+
+impl InvalidBlockHook for DiskInvalidBlockHook {
+    fn on_invalid_block(&self, invalid_block: &InvalidBlock) {
+        match self.write(invalid_block) {
+            Ok(file) => tracing::info!(
+                target: "blockchain_tree::invalid_block_hook",
+                file = %file.display(),
+                "Wrote invalid block"
+            ),
+            Err(err) => tracing::warn!(
+                target: "blockchain_tree::invalid_block_hook",
+                %err,
+                "Failed to write invalid block"
+            ),
+        }
+    }
+}
+
+/// An [InvalidBlockHook] that posts the invalid blocks as JSON to a webhook.
+///
+/// The requests are sent in the background on the tokio runtime the hook was created on.
+#[derive(Clone, Debug)]
+pub struct WebhookInvalidBlockHook {
+    /// The URL of the webhook.
+    url: reqwest::Url,
+    /// The HTTP client.
+    client: reqwest::Client,
+    /// The runtime the requests are sent on.
+    runtime: tokio::runtime::Handle,
+}
+
+impl WebhookInvalidBlockHook {
+    /// Creates a new hook that posts to the given URL.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of a tokio runtime.
+    pub fn new(url: reqwest::Url) -> Self {
+        Self { url, client: reqwest::Client::new(), runtime: tokio::runtime::Handle::current() }
+    }
+}
+
+impl InvalidBlockHook for WebhookInvalidBlockHook {
+    fn on_invalid_block(&self, invalid_block: &InvalidBlock) {
+        let body = match serde_json::to_vec(invalid_block) {
+            Ok(body) => body,
+            Err(err) => {
+                tracing::warn!(
+                    target: "blockchain_tree::invalid_block_hook",
+                    %err,
+                    "Failed to serialize invalid block"
+                );
+                return
+            }
+        };
+        let request = self
+            .client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body);
+        let block_hash = invalid_block.block.hash();
+        self.runtime.spawn(async move {
+            match request.send().await.and_then(|response| response.error_for_status()) {
+                Ok(_) => tracing::debug!(
+                    target: "blockchain_tree::invalid_block_hook",
+                    %block_hash,
+                    "Posted invalid block to webhook"
+                ),
+                Err(err) => tracing::warn!(
+                    target: "blockchain_tree::invalid_block_hook",
+                    %block_hash,
+                    %err,
+                    "Failed to post invalid block to webhook"
+                ),
+            }
+        });
+    }
+}
+
+/// A built-in [InvalidBlockHook], parsed from `disk=<DIR>` or `webhook=<URL>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvalidBlockHookKind {
+    /// Write the invalid blocks to a directory, see [DiskInvalidBlockHook].
+    Disk(PathBuf),
+    /// Post the invalid blocks to a webhook, see [WebhookInvalidBlockHook].
+    Webhook(reqwest::Url),
+}
+
+impl InvalidBlockHookKind {
+    /// Creates the hook.
+    ///
+    /// # Panics
+    ///
+    /// If the hook is a webhook and this is called outside of a tokio runtime.
+    pub fn into_hook(self) -> Box<dyn InvalidBlockHook> {
+        match self {
+            Self::Disk(dir) => Box::new(DiskInvalidBlockHook::new(dir)),
+            Self::Webhook(url) => Box::new(WebhookInvalidBlockHook::new(url)),
+        }
+    }
+}
+
+impl FromStr for InvalidBlockHookKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some(("disk", dir)) if !dir.is_empty() => Ok(Self::Disk(dir.into())),
+            Some(("webhook", url)) => url
+                .parse()
+                .map(Self::Webhook)
+                .map_err(|err| format!("invalid webhook url {url}: {err}")),
+            _ => Err(format!("invalid block hook: {s}, expected disk=<DIR> or webhook=<URL>")),
+        }
+    }
+}
+
+impl fmt::Display for InvalidBlockHookKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Disk(dir) => write!(f, "disk={}", dir.display()),
+            Self::Webhook(url) => write!(f, "webhook={url}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn records_witness() {
+        let address = Address::random();
+        let slot = B256::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::from(1)).extend_storage([(slot, U256::from(2))]),
+        );
+
+        let recorder = WitnessRecorder::new(provider);
+        recorder.basic_account(address).unwrap();
+        recorder.basic_account(Address::random()).unwrap();
+        recorder.storage(address, slot).unwrap();
+        recorder.storage(address, B256::with_last_byte(2)).unwrap();
+
+        let witness = recorder.into_witness();
+        assert_eq!(witness.accounts.len(), 2);
+        assert_eq!(witness.accounts[&address].unwrap().balance, U256::from(1));
+        assert_eq!(
+            witness.storage[&address],
+            BTreeMap::from([(slot, U256::from(2)), (B256::with_last_byte(2), U256::ZERO)])
+        );
+    }
+
+    #[test]
+    fn roundtrip_invalid_block() {
+        let invalid_block = InvalidBlock {
+            block: SealedBlock::default(),
+            senders: Vec::new(),
+            error: "block state root mismatch".to_string(),
+            state_root: RootDiff { expected: B256::random(), computed: Some(B256::random()) },
+            receipts_root: RootDiff { expected: B256::random(), computed: None },
+            witness: ExecutionWitness::default(),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = DiskInvalidBlockHook::new(dir.path().join("invalid")).write(&invalid_block);
+        let read = DiskInvalidBlockHook::read(path.unwrap()).unwrap();
+        assert_eq!(read, invalid_block);
+    }
+
+    #[test]
+    fn parse_hook_kind() {
+        assert_eq!(
+            "disk=/tmp/invalid".parse::<InvalidBlockHookKind>().unwrap(),
+            InvalidBlockHookKind::Disk("/tmp/invalid".into())
+        );
+        assert_eq!(
+            "webhook=http://localhost:8080/invalid".parse::<InvalidBlockHookKind>().unwrap(),
+            InvalidBlockHookKind::Webhook("http://localhost:8080/invalid".parse().unwrap())
+        );
+        assert!("webhook=invalid".parse::<InvalidBlockHookKind>().is_err());
+        assert!("disk".parse::<InvalidBlockHookKind>().is_err());
+    }
+}
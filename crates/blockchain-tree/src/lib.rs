@@ -24,15 +24,33 @@ pub use blockchain_tree::BlockchainTree;
 pub mod block_indices;
 pub use block_indices::BlockIndices;
 
+pub mod checkpoints;
+pub use checkpoints::{TrieCheckpoint, TrieCheckpoints};
+
 pub mod chain;
 pub use chain::AppendableChain;
 
 pub mod config;
-pub use config::BlockchainTreeConfig;
+pub use config::{BlockchainTreeConfig, StateRootStrategy, TrieCheckpointsConfig};
 
 pub mod externals;
 pub use externals::TreeExternals;
 
+pub mod execution_cache;
+pub use execution_cache::{CachedStateProvider, ExecutionCache};
+
+pub mod state_root_capture;
+pub use state_root_capture::{StateRootCapture, StateRootInput};
+
+pub mod invalid_block_hook;
+pub use invalid_block_hook::{
+    DiskInvalidBlockHook, InvalidBlock, InvalidBlockHook, InvalidBlockHookKind,
+    WebhookInvalidBlockHook,
+};
+
+pub mod spill;
+pub use spill::ChainSpill;
+
 pub mod shareable;
 pub use shareable::ShareableBlockchainTree;
 
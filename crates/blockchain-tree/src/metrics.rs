@@ -23,6 +23,12 @@ pub struct TreeMetrics {
     pub trie_updates_insert_cached: Counter,
     /// The number of times trie updates were recomputed for insert.
     pub trie_updates_insert_recomputed: Counter,
+    /// The number of retained trie checkpoints
+    pub trie_checkpoints: Gauge,
+    /// The number of reorgs that forked off below the in-memory window of canonical hashes
+    pub deep_reorgs: Counter,
+    /// The number of cached trie node seeks shared between state root computations
+    pub trie_node_cache_entries: Gauge,
 }
 
 /// Metrics for the blockchain tree block buffer
@@ -33,6 +39,22 @@ pub struct BlockBufferMetrics {
     pub blocks: Gauge,
 }
 
+/// Metrics for the sidechains of the blockchain tree that were spilled to disk
+#[derive(Metrics)]
+#[metrics(scope = "blockchain_tree.spill")]
+pub struct ChainSpillMetrics {
+    /// Number of sidechains currently spilled to disk
+    pub chains: Gauge,
+    /// Number of blocks currently spilled to disk
+    pub blocks: Gauge,
+    /// Total number of blocks spilled to disk
+    pub spilled_blocks: Counter,
+    /// Total number of blocks reloaded from disk
+    pub reloaded_blocks: Counter,
+    /// Time it took to reload a spilled sidechain
+    pub reload_duration: Histogram,
+}
+
 #[derive(Debug)]
 pub(crate) struct MakeCanonicalDurationsRecorder {
     start: Instant,
@@ -115,3 +137,41 @@ struct MakeCanonicalMetrics {
     /// The time it took to execute an action
     duration: Histogram,
 }
+
+/// The path that was taken to compute the state root of a block that extends the canonical chain.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum StateRootPath {
+    /// The parallel state root matched the block.
+    Parallel,
+    /// The state root was computed synchronously.
+    Synchronous,
+    /// The parallel state root failed or mismatched and was recomputed synchronously.
+    Fallback,
+}
+
+impl StateRootPath {
+    fn as_str(&self) -> &'static str {
+        match self {
+            StateRootPath::Parallel => "parallel",
+            StateRootPath::Synchronous => "synchronous",
+            StateRootPath::Fallback => "fallback",
+        }
+    }
+
+    /// Reports the computation of a state root on this path as a metric with `path` label.
+    pub(crate) fn record(&self, duration: Duration) {
+        let metrics = StateRootMetrics::new_with_labels(&[("path", self.as_str())]);
+        metrics.computations.increment(1);
+        metrics.duration.record(duration);
+    }
+}
+
+#[derive(Metrics)]
+#[metrics(scope = "blockchain_tree.state_root")]
+/// State root computation metrics
+struct StateRootMetrics {
+    /// The number of state roots computed on the path
+    computations: Counter,
+    /// The time it took to compute the state root on the path
+    duration: Histogram,
+}
@@ -1,8 +1,8 @@
 use reth_interfaces::{
     blockchain_tree::{
         error::{BlockchainTreeError, CanonicalError, InsertBlockError},
-        BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer, CanonicalOutcome,
-        InsertPayloadOk,
+        BlockStatus, BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer,
+        CanonicalOutcome, InsertPayloadOk,
     },
     provider::ProviderError,
     RethResult,
@@ -40,6 +40,16 @@ impl BlockchainTreeEngine for NoopBlockchainTree {
         ))
     }
 
+    fn validate_block_without_insert(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BlockStatus, InsertBlockError> {
+        Err(InsertBlockError::tree_error(
+            BlockchainTreeError::BlockHashNotFoundInChain { block_hash: block.hash() },
+            block.block,
+        ))
+    }
+
     fn finalize_block(&self, _finalized_block: BlockNumber) {}
 
     fn connect_buffered_blocks_to_canonical_hashes_and_finalize(
@@ -6,8 +6,8 @@ use reth_db::database::Database;
 use reth_interfaces::{
     blockchain_tree::{
         error::{CanonicalError, InsertBlockError},
-        BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer, CanonicalOutcome,
-        InsertPayloadOk,
+        BlockStatus, BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer,
+        CanonicalOutcome, InsertPayloadOk,
     },
     RethResult,
 };
@@ -63,6 +63,14 @@ where
         res
     }
 
+    fn validate_block_without_insert(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BlockStatus, InsertBlockError> {
+        trace!(target: "blockchain_tree", hash = %block.hash(), number = block.number, parent_hash = %block.parent_hash, "Validating block without insert");
+        self.tree.read().validate_block_without_insert(block)
+    }
+
     fn finalize_block(&self, finalized_block: BlockNumber) {
         trace!(target: "blockchain_tree", finalized_block, "Finalizing block");
         let mut tree = self.tree.write();
@@ -111,17 +119,26 @@ where
 
     fn header_by_hash(&self, hash: BlockHash) -> Option<SealedHeader> {
         trace!(target: "blockchain_tree", ?hash, "Returning header by hash");
-        self.tree.read().block_by_hash(hash).map(|b| b.header.clone())
+        let tree = self.tree.read();
+        tree.block_by_hash(hash)
+            .map(|b| b.header.clone())
+            .or_else(|| tree.spilled_header_by_hash(hash).cloned())
     }
 
     fn block_by_hash(&self, block_hash: BlockHash) -> Option<SealedBlock> {
         trace!(target: "blockchain_tree", ?block_hash, "Returning block by hash");
-        self.tree.read().block_by_hash(block_hash).cloned()
+        let tree = self.tree.read();
+        tree.block_by_hash(block_hash).cloned().or_else(|| {
+            tree.spilled_block_with_senders_by_hash(block_hash).map(|block| block.block)
+        })
     }
 
     fn block_with_senders_by_hash(&self, block_hash: BlockHash) -> Option<SealedBlockWithSenders> {
         trace!(target: "blockchain_tree", ?block_hash, "Returning block by hash");
-        self.tree.read().block_with_senders_by_hash(block_hash).cloned()
+        let tree = self.tree.read();
+        tree.block_with_senders_by_hash(block_hash)
+            .cloned()
+            .or_else(|| tree.spilled_block_with_senders_by_hash(block_hash))
     }
 
     fn buffered_block_by_hash(&self, block_hash: BlockHash) -> Option<SealedBlock> {
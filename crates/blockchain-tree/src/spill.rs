@@ -0,0 +1,340 @@
+//! Spilling of stale sidechains of the [`BlockchainTree`](crate::BlockchainTree) to disk.
+//!
+//! Sidechains that fall too far behind the canonical tip are unlikely to become canonical, but
+//! keeping their executed state in memory is expensive during long periods of non-finality. Such
+//! chains are written to a temporary directory, along with their executed state, and are inserted
+//! back into the tree when they are needed again.
+
+use crate::metrics::ChainSpillMetrics;
+use reth_db::table::{Compress, Decompress};
+use reth_primitives::{
+    Address, BlockHash, BlockNumHash, BlockNumber, Receipt, Receipts, SealedBlock,
+    SealedBlockWithSenders, SealedHeader,
+};
+use reth_provider::{BundleStateWithReceipts, Chain};
+use revm::db::BundleState;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// A block of a spilled sidechain.
+#[derive(Debug, Serialize, Deserialize)]
+struct SpilledBlock {
+    block: SealedBlock,
+    senders: Vec<Address>,
+}
+
+/// The executed state of a spilled sidechain.
+///
+/// Receipts are stored with the same compact encoding as in the database.
+#[derive(Debug, Serialize, Deserialize)]
+struct SpilledState {
+    bundle: BundleState,
+    receipts: Vec<Vec<Option<Vec<u8>>>>,
+    first_block: BlockNumber,
+}
+
+impl SpilledState {
+    fn new(mut state: BundleStateWithReceipts) -> Self {
+        let first_block = state.first_block();
+        let bundle = std::mem::take(state.state_mut());
+        let receipts = std::mem::take(state.receipts_mut())
+            .receipt_vec
+            .into_iter()
+            .map(|block| block.into_iter().map(|receipt| receipt.map(Compress::compress)).collect())
+            .collect();
+        Self { bundle, receipts, first_block }
+    }
+
+    fn into_state(self) -> io::Result<BundleStateWithReceipts> {
+        let receipt_vec = self
+            .receipts
+            .into_iter()
+            .map(|block| {
+                block
+                    .into_iter()
+                    .map(|receipt| receipt.map(|receipt| Receipt::decompress(receipt)).transpose())
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(BundleStateWithReceipts::new(self.bundle, Receipts { receipt_vec }, self.first_block))
+    }
+}
+
+/// A sidechain that was written to disk.
+#[derive(Debug)]
+struct SpilledChain {
+    /// File the blocks of the chain were written to.
+    blocks_path: PathBuf,
+    /// File the executed state of the chain was written to.
+    state_path: PathBuf,
+    /// The block the chain forks from.
+    fork: BlockNumHash,
+    /// Hashes of the blocks of the chain.
+    blocks: Vec<BlockHash>,
+}
+
+/// Sidechains of the tree that were spilled to disk because their tip is more than `depth` blocks
+/// behind the canonical tip.
+///
+/// The blocks and the executed state of a chain are written to separate files, so that the blocks
+/// of a spilled chain can be looked up without reading its state. The headers of the spilled
+/// blocks are kept in memory.
+#[derive(Debug)]
+pub struct ChainSpill {
+    /// Directory the spilled chains are written to.
+    dir: PathBuf,
+    /// Depth below the canonical tip at which sidechains are spilled.
+    depth: u64,
+    /// Identifier of the next spilled chain.
+    next_id: u64,
+    /// Spilled chains by their identifier.
+    chains: HashMap<u64, SpilledChain>,
+    /// Identifier of the spilled chain and header of each spilled block.
+    blocks: HashMap<BlockHash, (u64, SealedHeader)>,
+    /// Metrics for spill activity.
+    metrics: ChainSpillMetrics,
+}
+
+impl ChainSpill {
+    /// Creates a new spill in the given directory.
+    ///
+    /// The directory is only used for the lifetime of the spill, any existing content is removed.
+    pub fn new(dir: impl Into<PathBuf>, depth: u64) -> io::Result<Self> {
+        let dir = dir.into();
+        if let Err(err) = fs::remove_dir_all(&dir) {
+            if err.kind() != io::ErrorKind::NotFound {
+                return Err(err)
+            }
+        }
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            depth,
+            next_id: 0,
+            chains: HashMap::new(),
+            blocks: HashMap::new(),
+            metrics: ChainSpillMetrics::default(),
+        })
+    }
+
+    /// Returns the directory the spilled chains are written to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the depth below the canonical tip at which sidechains are spilled.
+    pub fn depth(&self) -> u64 {
+        self.depth
+    }
+
+    /// Returns `true` if the block is part of a spilled chain.
+    pub fn contains(&self, block_hash: &BlockHash) -> bool {
+        self.blocks.contains_key(block_hash)
+    }
+
+    /// Returns the header of a spilled block.
+    pub fn header(&self, block_hash: &BlockHash) -> Option<&SealedHeader> {
+        self.blocks.get(block_hash).map(|(_, header)| header)
+    }
+
+    /// Reads a spilled block from disk.
+    ///
+    /// Returns `None` if the block is not part of a spilled chain.
+    pub fn block(&self, block_hash: &BlockHash) -> io::Result<Option<SealedBlockWithSenders>> {
+        let Some((id, _)) = self.blocks.get(block_hash) else { return Ok(None) };
+        let chain = self.chains.get(id).expect("Spilled chain should exist");
+        let blocks: Vec<SpilledBlock> = read(&chain.blocks_path)?;
+        blocks
+            .into_iter()
+            .find(|spilled| spilled.block.hash() == *block_hash)
+            .map(into_block_with_senders)
+            .transpose()
+    }
+
+    /// Returns the number of spilled chains.
+    pub fn len(&self) -> usize {
+        self.chains.len()
+    }
+
+    /// Returns `true` if no chains are spilled.
+    pub fn is_empty(&self) -> bool {
+        self.chains.is_empty()
+    }
+
+    /// Writes the blocks and the executed state of a chain to disk.
+    pub(crate) fn spill(&mut self, chain: Chain) -> io::Result<()> {
+        let Some(first) = chain.blocks().values().next() else { return Ok(()) };
+        let fork = first.parent_num_hash();
+
+        let id = self.next_id;
+        let blocks_path = self.dir.join(format!("{id}.blocks"));
+        let state_path = self.dir.join(format!("{id}.state"));
+        let headers = chain.headers().collect::<Vec<_>>();
+        let (blocks, state, _) = chain.into_inner();
+        let blocks = blocks
+            .into_blocks()
+            .map(|block| {
+                let (block, senders) = block.into_components();
+                SpilledBlock { block, senders }
+            })
+            .collect::<Vec<_>>();
+        write(&blocks_path, &blocks)?;
+        if let Err(err) = write(&state_path, &SpilledState::new(state)) {
+            let _ = fs::remove_file(&blocks_path);
+            return Err(err)
+        }
+
+        self.next_id += 1;
+        self.metrics.spilled_blocks.increment(headers.len() as u64);
+        let hashes = headers.iter().map(|header| header.hash()).collect();
+        self.blocks.extend(headers.into_iter().map(|header| (header.hash(), (id, header))));
+        self.chains.insert(id, SpilledChain { blocks_path, state_path, fork, blocks: hashes });
+        self.update_metrics();
+        Ok(())
+    }
+
+    /// Removes the spilled chain that contains the given block and reads it back from disk.
+    ///
+    /// Returns `None` if the block is not part of a spilled chain.
+    pub(crate) fn take(&mut self, block_hash: &BlockHash) -> io::Result<Option<Chain>> {
+        let Some(id) = self.blocks.get(block_hash).map(|(id, _)| *id) else { return Ok(None) };
+        let chain = self.remove_chain(id).expect("Spilled chain should exist");
+
+        let blocks: io::Result<Vec<SpilledBlock>> = read(&chain.blocks_path);
+        let state: io::Result<SpilledState> = read(&chain.state_path);
+        chain.remove_files();
+        let blocks =
+            blocks?.into_iter().map(into_block_with_senders).collect::<io::Result<Vec<_>>>()?;
+        let state = state?.into_state()?;
+
+        self.metrics.reloaded_blocks.increment(blocks.len() as u64);
+        Ok(Some(Chain::new(blocks, state, None)))
+    }
+
+    /// Removes all spilled chains that fork below the finalized block, as they can not become
+    /// canonical anymore.
+    pub fn finalize(&mut self, finalized: BlockNumber) {
+        let finalized_chains = self
+            .chains
+            .iter()
+            .filter(|(_, chain)| chain.fork.number < finalized)
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for id in finalized_chains {
+            if let Some(chain) = self.remove_chain(id) {
+                chain.remove_files();
+            }
+        }
+    }
+
+    /// Records the time it took to reload a spilled chain.
+    pub(crate) fn record_reload(&self, duration: std::time::Duration) {
+        self.metrics.reload_duration.record(duration);
+    }
+
+    fn remove_chain(&mut self, id: u64) -> Option<SpilledChain> {
+        let chain = self.chains.remove(&id)?;
+        for hash in &chain.blocks {
+            self.blocks.remove(hash);
+        }
+        self.update_metrics();
+        Some(chain)
+    }
+
+    fn update_metrics(&self) {
+        self.metrics.chains.set(self.chains.len() as f64);
+        self.metrics.blocks.set(self.blocks.len() as f64);
+    }
+}
+
+impl SpilledChain {
+    fn remove_files(&self) {
+        let _ = fs::remove_file(&self.blocks_path);
+        let _ = fs::remove_file(&self.state_path);
+    }
+}
+
+fn into_block_with_senders(spilled: SpilledBlock) -> io::Result<SealedBlockWithSenders> {
+    SealedBlockWithSenders::new(spilled.block, spilled.senders)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "mismatching number of senders"))
+}
+
+fn write<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let content =
+        bincode::serialize(value).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    fs::write(path, content)
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> io::Result<T> {
+    let content = fs::read(path)?;
+    bincode::deserialize(&content).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, Log, TxType, B256};
+
+    fn block(number: BlockNumber, parent_hash: B256) -> SealedBlockWithSenders {
+        let header = Header { number, parent_hash, ..Default::default() }.seal_slow();
+        SealedBlockWithSenders::new(SealedBlock { header, ..Default::default() }, vec![]).unwrap()
+    }
+
+    fn chain(blocks: Vec<SealedBlockWithSenders>) -> Chain {
+        let first_block = blocks[0].number;
+        #[allow(clippy::needless_update)] // side-effect of optimism fields
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log::new_unchecked(
+                Address::random(),
+                vec![B256::random()],
+                Default::default(),
+            )],
+            ..Default::default()
+        };
+        let receipts = blocks.iter().map(|_| vec![Some(receipt.clone()), None]).collect::<Vec<_>>();
+        let state = BundleStateWithReceipts::new(
+            BundleState::default(),
+            Receipts { receipt_vec: receipts },
+            first_block,
+        );
+        Chain::new(blocks, state, None)
+    }
+
+    #[test]
+    fn spill_take_and_finalize() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spill = ChainSpill::new(dir.path().join("spill"), 64).unwrap();
+
+        let block1 = block(10, B256::with_last_byte(1));
+        let block2 = block(11, block1.hash());
+        let chain1 = chain(vec![block1.clone(), block2.clone()]);
+        spill.spill(chain1.clone()).unwrap();
+        let block3 = block(5, B256::with_last_byte(2));
+        spill.spill(chain(vec![block3.clone()])).unwrap();
+        assert_eq!(spill.len(), 2);
+        assert!(spill.contains(&block2.hash()));
+
+        // blocks can be looked up while they are spilled
+        assert_eq!(spill.header(&block2.hash()), Some(&block2.header));
+        assert_eq!(spill.block(&block1.hash()).unwrap(), Some(block1.clone()));
+
+        assert_eq!(spill.take(&block2.hash()).unwrap(), Some(chain1));
+        assert!(!spill.contains(&block1.hash()));
+        assert!(spill.take(&block1.hash()).unwrap().is_none());
+        assert!(spill.block(&block1.hash()).unwrap().is_none());
+
+        spill.finalize(5);
+        assert!(spill.contains(&block3.hash()));
+        spill.finalize(6);
+        assert!(spill.is_empty());
+        assert_eq!(fs::read_dir(spill.dir()).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,157 @@
+//! Capture of the inputs of state root computations, for replaying them offline.
+
+use reth_primitives::{Account, BlockNumber, B256, U256};
+use reth_trie::{HashedPostState, HashedStorage};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The inputs of the state root computation of a block that extends the canonical chain, along
+/// with the state roots that were computed from them.
+///
+/// The state root is computed on top of the database state at `tip_number`, so it can only be
+/// replayed on a database at the same state.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateRootInput {
+    /// The number of the block.
+    pub block_number: BlockNumber,
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// The number of the canonical tip of the database.
+    pub tip_number: BlockNumber,
+    /// The hash of the canonical tip of the database.
+    pub tip_hash: B256,
+    /// The state root of the block.
+    pub expected_state_root: B256,
+    /// The state root computed in parallel, if the parallel computation succeeded.
+    pub parallel_state_root: Option<B256>,
+    /// The state root computed synchronously.
+    pub synchronous_state_root: B256,
+    /// The changed state on top of the database.
+    pub state: HashedStateInput,
+}
+
+impl StateRootInput {
+    /// Reads a captured input from the given JSON file.
+    pub fn read(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+}
+
+/// The serializable form of a [HashedPostState].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashedStateInput {
+    /// The changed accounts, keyed by hashed address. `None` if destroyed.
+    pub accounts: BTreeMap<B256, Option<Account>>,
+    /// The changed storages, keyed by hashed address.
+    pub storages: BTreeMap<B256, HashedStorageInput>,
+}
+
+/// The serializable form of a [HashedStorage].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashedStorageInput {
+    /// Whether the storage was wiped.
+    pub wiped: bool,
+    /// The changed slots, keyed by hashed slot.
+    pub storage: BTreeMap<B256, U256>,
+}
+
+impl From<&HashedPostState> for HashedStateInput {
+    fn from(state: &HashedPostState) -> Self {
+        Self {
+            accounts: state.accounts.iter().map(|(k, v)| (*k, *v)).collect(),
+            storages: state
+                .storages
+                .iter()
+                .map(|(hashed_address, storage)| {
+                    let storage = HashedStorageInput {
+                        wiped: storage.wiped,
+                        storage: storage.storage.iter().map(|(k, v)| (*k, *v)).collect(),
+                    };
+                    (*hashed_address, storage)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl HashedStateInput {
+    /// Returns the state as a [HashedPostState].
+    pub fn to_hashed_state(&self) -> HashedPostState {
+        HashedPostState {
+            accounts: self.accounts.iter().map(|(k, v)| (*k, *v)).collect(),
+            storages: self
+                .storages
+                .iter()
+                .map(|(hashed_address, storage)| {
+                    let storage = HashedStorage::from_iter(
+                        storage.wiped,
+                        storage.storage.iter().map(|(k, v)| (*k, *v)),
+                    );
+                    (*hashed_address, storage)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Writes the [StateRootInput]s of state root computations to a directory.
+#[derive(Clone, Debug)]
+pub struct StateRootCapture {
+    /// The directory the inputs are written to.
+    dir: PathBuf,
+}
+
+impl StateRootCapture {
+    /// Creates a new capture that writes to the given directory.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Writes the input to `<block_number>-<block_hash>.json` in the capture directory and returns
+    /// the path of the file.
+    pub fn write(&self, input: &StateRootInput) -> io::Result<PathBuf> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.dir.join(format!("{}-{}.json", input.block_number, input.block_hash));
+        fs::write(&path, serde_json::to_vec(input)?)?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip_input() {
+        let mut hashed_state = HashedPostState::default();
+        hashed_state.accounts.insert(B256::random(), Some(Account::default()));
+        hashed_state.accounts.insert(B256::random(), None);
+        hashed_state.storages.insert(
+            B256::random(),
+            HashedStorage::from_iter(true, [(B256::random(), U256::from(1))]),
+        );
+        let input = StateRootInput {
+            block_number: 1,
+            block_hash: B256::random(),
+            tip_number: 0,
+            tip_hash: B256::random(),
+            expected_state_root: B256::random(),
+            parallel_state_root: None,
+            synchronous_state_root: B256::random(),
+            state: HashedStateInput::from(&hashed_state),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = StateRootCapture::new(dir.path().join("captures")).write(&input).unwrap();
+        let read = StateRootInput::read(path).unwrap();
+        assert_eq!(read, input);
+        assert_eq!(read.state.to_hashed_state(), hashed_state);
+    }
+}
@@ -2,7 +2,7 @@
 
 use reth_discv4::Discv4Config;
 use reth_network::{NetworkConfigBuilder, PeersConfig, SessionsConfig};
-use reth_primitives::PruneModes;
+use reth_primitives::{PruneModes, U256};
 use secp256k1::SecretKey;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
@@ -24,9 +24,27 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for logging.
+    pub log: LogConfig,
+    /// Configuration for the RPC server.
+    pub rpc: RpcConfig,
 }
 
 impl Config {
+    /// Returns the keys of the settings that differ between the two configs, in dotted notation,
+    /// e.g. `peers.connection_info.max_outbound`.
+    ///
+    /// Tables are compared recursively, other values, including arrays, are compared as a whole.
+    pub fn changed_keys(&self, other: &Config) -> Vec<String> {
+        let (Ok(this), Ok(other)) = (toml::Value::try_from(self), toml::Value::try_from(other))
+        else {
+            return Vec::new()
+        };
+        let mut keys = Vec::new();
+        collect_changed_keys("", Some(&this), Some(&other), &mut keys);
+        keys
+    }
+
     /// Initializes network config from read data
     pub fn network_config(
         &self,
@@ -305,6 +323,68 @@ impl Default for PruneConfig {
     }
 }
 
+/// Logging configuration.
+///
+/// The log settings can be changed while the node is running, see `admin_reloadConfig`.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct LogConfig {
+    /// Filter directives of the stdout log, e.g. `info,net=debug`. Ignored if the
+    /// `--log.stdout.filter` argument is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout_filter: Option<String>,
+}
+
+/// RPC server configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct RpcConfig {
+    /// Settings of the gas price oracle.
+    pub gas_price_oracle: GasPriceOracleSettings,
+}
+
+/// Settings of the gas price oracle, used for the `--gpo.*` arguments that are not set.
+///
+/// The settings can be changed while the node is running, see `admin_reloadConfig`.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct GasPriceOracleSettings {
+    /// The number of populated blocks to produce the gas price estimate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocks: Option<u32>,
+    /// The percentile of gas prices to use for the estimate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percentile: Option<u32>,
+    /// The maximum gas price to use for the estimate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_price: Option<U256>,
+    /// The minimum gas price, under which the sample will be ignored.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore_price: Option<U256>,
+}
+
+/// Collects the keys of the values that differ between the two values into `keys`.
+fn collect_changed_keys(
+    prefix: &str,
+    old: Option<&toml::Value>,
+    new: Option<&toml::Value>,
+    keys: &mut Vec<String>,
+) {
+    match (old, new) {
+        (Some(toml::Value::Table(old)), Some(toml::Value::Table(new))) => {
+            let mut names = old.keys().chain(new.keys()).collect::<Vec<_>>();
+            names.sort_unstable();
+            names.dedup();
+            for name in names {
+                let key = if prefix.is_empty() { name.clone() } else { format!("{prefix}.{name}") };
+                collect_changed_keys(&key, old.get(name), new.get(name), keys);
+            }
+        }
+        (old, new) if old != new => keys.push(prefix.to_string()),
+        _ => {}
+    }
+}
+
 /// Helper type to support older versions of Duration deserialization.
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
 where
@@ -326,6 +406,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::Config;
+    use reth_primitives::{PruneMode, PruneModes, U256};
     use std::time::Duration;
 
     const EXTENSION: &str = "toml";
@@ -358,6 +439,30 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_changed_keys() {
+        let config = Config::default();
+        assert!(config.changed_keys(&config).is_empty());
+
+        let mut changed = config.clone();
+        changed.peers = changed.peers.with_max_outbound(10);
+        changed.log.stdout_filter = Some("debug".to_string());
+        changed.rpc.gas_price_oracle.max_price = Some(U256::from(1));
+        changed.prune = Some(super::PruneConfig {
+            block_interval: 5,
+            segments: PruneModes { receipts: Some(PruneMode::Distance(64)), ..PruneModes::none() },
+        });
+        assert_eq!(
+            config.changed_keys(&changed),
+            vec![
+                "log.stdout_filter",
+                "peers.connection_info.max_outbound",
+                "prune",
+                "rpc.gas_price_oracle.max_price",
+            ]
+        );
+    }
+
     #[test]
     fn test_load_execution_stage() {
         with_tempdir("config-load-test", |config_path| {
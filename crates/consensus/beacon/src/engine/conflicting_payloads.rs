@@ -0,0 +1,81 @@
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{SealedHeader, B256};
+use schnellru::{ByLength, LruMap};
+use tracing::warn;
+
+/// Keeps track of the payloads received from the CL by their parent and timestamp, to detect
+/// conflicting payloads.
+///
+/// Two different payloads built on the same parent with the same timestamp are a sign of
+/// equivocation, e.g. by a misconfigured sequencer or validator, which causes confusing chain
+/// splits.
+pub(crate) struct ConflictingPayloadTracker {
+    /// Maps the parent hash and the timestamp of a payload to the hash of the first payload that
+    /// was received for them.
+    payloads: LruMap<(B256, u64), B256>,
+    /// Metrics for the tracker.
+    metrics: ConflictingPayloadMetrics,
+}
+
+impl ConflictingPayloadTracker {
+    pub(crate) fn new(max_length: u32) -> Self {
+        Self { payloads: LruMap::new(ByLength::new(max_length)), metrics: Default::default() }
+    }
+
+    /// Records the payload and returns the hash of the previously received payload if it
+    /// conflicts with the given payload.
+    pub(crate) fn on_new_payload(&mut self, header: &SealedHeader) -> Option<B256> {
+        let key = (header.parent_hash, header.timestamp);
+        if let Some(existing) = self.payloads.get(&key) {
+            if *existing == header.hash() {
+                return None
+            }
+            let existing = *existing;
+            warn!(
+                target: "consensus::engine",
+                number = header.number,
+                parent_hash = ?header.parent_hash,
+                timestamp = header.timestamp,
+                hash = ?header.hash(),
+                ?existing,
+                "Received conflicting payload for the same parent and timestamp"
+            );
+            self.metrics.conflicting_payloads.increment(1);
+            return Some(existing)
+        }
+
+        self.payloads.insert(key, header.hash());
+        None
+    }
+}
+
+/// Metrics for the conflicting payload tracker.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.engine.beacon")]
+struct ConflictingPayloadMetrics {
+    /// The number of payloads received that conflict with a previously received payload.
+    conflicting_payloads: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Header;
+
+    #[test]
+    fn detects_conflicting_payload() {
+        let mut tracker = ConflictingPayloadTracker::new(10);
+        let parent_hash = B256::with_last_byte(1);
+        let first = Header { parent_hash, timestamp: 12, ..Default::default() }.seal_slow();
+        let second =
+            Header { parent_hash, timestamp: 12, gas_used: 1, ..Default::default() }.seal_slow();
+        let later =
+            Header { parent_hash, timestamp: 24, gas_used: 1, ..Default::default() }.seal_slow();
+
+        assert_eq!(tracker.on_new_payload(&first), None);
+        // the same payload can be received multiple times
+        assert_eq!(tracker.on_new_payload(&first), None);
+        assert_eq!(tracker.on_new_payload(&second), Some(first.hash()));
+        assert_eq!(tracker.on_new_payload(&later), None);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::engine::hooks::EngineHookError;
 use reth_interfaces::RethError;
+use reth_primitives::BlockNumber;
 use reth_rpc_types::engine::ForkchoiceUpdateError;
 use reth_stages_api::PipelineError;
 
@@ -87,3 +88,31 @@ pub enum BeaconOnNewPayloadError {
     #[error(transparent)]
     Internal(Box<dyn std::error::Error + Send + Sync>),
 }
+
+/// Represents all error cases when rewinding the canonical chain.
+#[derive(Debug, thiserror::Error)]
+pub enum BeaconRewindError {
+    /// Thrown when the engine task is unavailable/stopped.
+    #[error("beacon consensus engine task stopped")]
+    EngineUnavailable,
+    /// Thrown when the pipeline or a hook with database write access is running.
+    #[error("the database is in use by the pipeline or a hook, try again later")]
+    DatabaseBusy,
+    /// Thrown when the target is not below the canonical head.
+    #[error("rewind target {target} is not below the canonical head {head}")]
+    InvalidTarget {
+        /// The requested target block.
+        target: BlockNumber,
+        /// The current canonical head.
+        head: BlockNumber,
+    },
+    /// An internal error occurred, e.g. while unwinding the stages.
+    #[error(transparent)]
+    Internal(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl From<RethError> for BeaconRewindError {
+    fn from(e: RethError) -> Self {
+        Self::Internal(Box::new(e))
+    }
+}
@@ -2,11 +2,12 @@
 
 use crate::{
     engine::message::OnForkChoiceUpdated, BeaconConsensusEngineEvent, BeaconEngineMessage,
-    BeaconForkChoiceUpdateError, BeaconOnNewPayloadError,
+    BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, BeaconRewindError,
 };
 use futures::TryFutureExt;
 use reth_engine_primitives::EngineTypes;
 use reth_interfaces::RethResult;
+use reth_primitives::{BlockNumHash, BlockNumber};
 use reth_rpc_types::engine::{
     CancunPayloadFields, ExecutionPayload, ForkchoiceState, ForkchoiceUpdated, PayloadStatus,
 };
@@ -96,6 +97,17 @@ where
         let _ = self.to_engine.send(BeaconEngineMessage::TransitionConfigurationExchanged);
     }
 
+    /// Sends a message to rewind the canonical chain to the given block and waits for the new
+    /// canonical head.
+    ///
+    /// This unwinds all stages, so the blocks above the target are removed from the database. It
+    /// is meant for test networks, the node syncs forward again on the next forkchoice update.
+    pub async fn rewind(&self, target: BlockNumber) -> Result<BlockNumHash, BeaconRewindError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::Rewind { target, tx });
+        rx.await.map_err(|_| BeaconRewindError::EngineUnavailable)?
+    }
+
     /// Creates a new [`BeaconConsensusEngineEvent`] listener stream.
     pub fn event_listener(&self) -> UnboundedReceiverStream<BeaconConsensusEngineEvent> {
         let (tx, rx) = mpsc::unbounded_channel();
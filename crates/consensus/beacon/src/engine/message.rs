@@ -1,11 +1,15 @@
 use crate::{
-    engine::{error::BeaconOnNewPayloadError, forkchoice::ForkchoiceStatus},
+    engine::{
+        error::{BeaconOnNewPayloadError, BeaconRewindError},
+        forkchoice::ForkchoiceStatus,
+    },
     BeaconConsensusEngineEvent,
 };
 use futures::{future::Either, FutureExt};
 use reth_engine_primitives::EngineTypes;
 use reth_interfaces::RethResult;
 use reth_payload_builder::error::PayloadBuilderError;
+use reth_primitives::{BlockNumHash, BlockNumber};
 use reth_rpc_types::engine::{
     CancunPayloadFields, ExecutionPayload, ForkChoiceUpdateResult, ForkchoiceState,
     ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId, PayloadStatus, PayloadStatusEnum,
@@ -49,6 +53,12 @@ impl OnForkChoiceUpdated {
         }
     }
 
+    /// Creates a new instance of `OnForkChoiceUpdated` with the given status that resolves to the
+    /// given, already computed result.
+    pub fn resolved(forkchoice_status: ForkchoiceStatus, result: ForkChoiceUpdateResult) -> Self {
+        Self { forkchoice_status, fut: Either::Left(futures::future::ready(result)) }
+    }
+
     /// Creates a new instance of `OnForkChoiceUpdated` if the forkchoice update succeeded and no
     /// payload attributes were provided.
     pub(crate) fn valid(status: PayloadStatus) -> Self {
@@ -164,4 +174,11 @@ pub enum BeaconEngineMessage<Engine: EngineTypes> {
     TransitionConfigurationExchanged,
     /// Add a new listener for [`BeaconEngineMessage`].
     EventListener(UnboundedSender<BeaconConsensusEngineEvent>),
+    /// Message to rewind the canonical chain to the given block by unwinding the stages.
+    Rewind {
+        /// The block to rewind to, this becomes the new canonical head.
+        target: BlockNumber,
+        /// The sender for returning the new canonical head.
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconRewindError>>,
+    },
 }
@@ -23,7 +23,7 @@ use reth_interfaces::{
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_primitives::{
     constants::EPOCH_SLOTS, stage::StageId, BlockNumHash, BlockNumber, Head, Header, SealedBlock,
-    SealedHeader, B256,
+    SealedBlockWithSenders, SealedHeader, B256,
 };
 use reth_provider::{
     BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider, ProviderError,
@@ -56,12 +56,15 @@ pub use message::{BeaconEngineMessage, OnForkChoiceUpdated};
 mod error;
 pub use error::{
     BeaconConsensusEngineError, BeaconEngineResult, BeaconForkChoiceUpdateError,
-    BeaconOnNewPayloadError,
+    BeaconOnNewPayloadError, BeaconRewindError,
 };
 
 mod invalid_headers;
 use invalid_headers::InvalidHeaderCache;
 
+mod conflicting_payloads;
+use conflicting_payloads::ConflictingPayloadTracker;
+
 mod event;
 pub use event::{BeaconConsensusEngineEvent, ConsensusEngineLiveSyncProgress};
 
@@ -88,6 +91,9 @@ pub mod test_utils;
 /// The maximum number of invalid headers that can be tracked by the engine.
 const MAX_INVALID_HEADERS: u32 = 512u32;
 
+/// The maximum number of received payloads that are tracked to detect conflicting payloads.
+const MAX_TRACKED_PAYLOADS: u32 = 512u32;
+
 /// The largest gap for which the tree will be used for sync. See docs for `pipeline_run_threshold`
 /// for more information.
 ///
@@ -195,6 +201,9 @@ where
     /// Tracks the header of invalid payloads that were rejected by the engine because they're
     /// invalid.
     invalid_headers: InvalidHeaderCache,
+    /// Tracks the received payloads to detect conflicting payloads for the same parent and
+    /// timestamp.
+    conflicting_payloads: ConflictingPayloadTracker,
     /// Consensus engine metrics.
     metrics: EngineMetrics,
     /// After downloading a block corresponding to a recent forkchoice update, the engine will
@@ -210,6 +219,8 @@ where
     /// be used to download and execute the missing blocks.
     pipeline_run_threshold: u64,
     hooks: EngineHooksController,
+    /// The sender for the result of the requested rewind, if the pipeline is unwinding on request.
+    pending_rewind: Option<oneshot::Sender<Result<BlockNumHash, BeaconRewindError>>>,
 }
 
 impl<DB, BT, Client, EngineT> BeaconConsensusEngine<DB, BT, Client, EngineT>
@@ -308,9 +319,11 @@ where
             payload_builder,
             listeners,
             invalid_headers: InvalidHeaderCache::new(MAX_INVALID_HEADERS),
+            conflicting_payloads: ConflictingPayloadTracker::new(MAX_TRACKED_PAYLOADS),
             metrics: EngineMetrics::default(),
             pipeline_run_threshold,
             hooks: EngineHooksController::new(hooks),
+            pending_rewind: None,
         };
 
         let maybe_pipeline_target = match target {
@@ -633,6 +646,73 @@ where
         self.handle.clone()
     }
 
+    /// Schedules a rewind of the canonical chain to the given block.
+    ///
+    /// The rewind is rejected if the pipeline or a hook with database write access is running,
+    /// because unwinding the stages requires exclusive access to the database.
+    fn on_rewind(
+        &mut self,
+        target: BlockNumber,
+        tx: oneshot::Sender<Result<BlockNumHash, BeaconRewindError>>,
+    ) {
+        if self.sync.is_pipeline_active() ||
+            self.hooks.active_db_write_hook().is_some() ||
+            self.pending_rewind.is_some()
+        {
+            let _ = tx.send(Err(BeaconRewindError::DatabaseBusy));
+            return
+        }
+
+        let head = match self.blockchain.best_block_number() {
+            Ok(head) => head,
+            Err(err) => {
+                let _ = tx.send(Err(RethError::from(err).into()));
+                return
+            }
+        };
+        if target >= head {
+            let _ = tx.send(Err(BeaconRewindError::InvalidTarget { target, head }));
+            return
+        }
+
+        warn!(target: "consensus::engine", ?target, ?head, "Rewinding canonical chain");
+        self.sync.set_pipeline_unwind_target(target);
+        self.pending_rewind = Some(tx);
+    }
+
+    /// Invoked when the pipeline unwound to the given block on request.
+    ///
+    /// Restores the blockchain tree from the unwound database and makes the target the new
+    /// canonical head.
+    fn on_pipeline_unwound(&mut self, target: BlockNumber) -> RethResult<BlockNumHash> {
+        let head = self
+            .blockchain
+            .sealed_header(target)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(target.into()))?;
+
+        let finalized = self.blockchain.finalized_block_number()?;
+        let safe = self.blockchain.safe_block_number()?;
+        self.blockchain.connect_buffered_blocks_to_canonical_hashes_and_finalize(
+            finalized.unwrap_or_default().min(target),
+        )?;
+
+        self.blockchain.set_canonical_head(head.clone());
+        if safe.is_some_and(|safe| safe > target) {
+            self.blockchain.set_safe(head.clone());
+        }
+        if finalized.is_some_and(|finalized| finalized > target) {
+            self.blockchain.set_finalized(head.clone());
+        }
+
+        info!(
+            target: "consensus::engine",
+            number = target,
+            hash = ?head.hash(),
+            "Rewound canonical chain"
+        );
+        Ok(head.num_hash())
+    }
+
     /// Pushes an [UnboundedSender] to the engine's listeners. Also pushes an [UnboundedSender] to
     /// the sync controller's listeners.
     pub(crate) fn push_listener(&mut self, listener: UnboundedSender<BeaconConsensusEngineEvent>) {
@@ -1074,27 +1154,35 @@ where
             Ok(block) => block,
             Err(status) => return Ok(status),
         };
-        let block_hash = block.hash();
-        let block_num_hash = block.num_hash();
+        let header = match &block {
+            Ok(block) => &block.header,
+            Err(block) => &block.header,
+        };
+        let block_hash = header.hash();
+        let block_num_hash = header.num_hash();
+        let parent_hash = header.parent_hash;
+
+        // conflicting payloads are still processed, they are only reported
+        self.conflicting_payloads.on_new_payload(header);
 
-        let mut lowest_buffered_ancestor = self.lowest_buffered_ancestor_or(block.hash());
-        if lowest_buffered_ancestor == block.hash() {
-            lowest_buffered_ancestor = block.parent_hash;
+        let mut lowest_buffered_ancestor = self.lowest_buffered_ancestor_or(block_hash);
+        if lowest_buffered_ancestor == block_hash {
+            lowest_buffered_ancestor = parent_hash;
         }
 
         // now check the block itself
         if let Some(status) =
-            self.check_invalid_ancestor_with_head(lowest_buffered_ancestor, block.hash())
+            self.check_invalid_ancestor_with_head(lowest_buffered_ancestor, block_hash)
         {
             return Ok(status)
         }
 
-        let res = if self.sync.is_pipeline_idle() {
+        let res = match block {
             // we can only insert new payloads if the pipeline is _not_ running, because it holds
             // exclusive access to the database
-            self.try_insert_new_payload(block)
-        } else {
-            self.try_buffer_payload(block)
+            Ok(block) if self.sync.is_pipeline_idle() => self.try_insert_new_payload(block),
+            Ok(block) => self.try_buffer_payload(block),
+            Err(block) => Err(InsertBlockError::sender_recovery_error(block)),
         };
 
         let status = match res {
@@ -1166,6 +1254,10 @@ where
     ///    versioned hashes
     ///    - the block does not contain blob transactions if it is pre-cancun
     ///
+    /// The transactions of the payload are decoded and their senders recovered in parallel with
+    /// the validation of the header. If the payload is well-formed but the senders could not be
+    /// recovered, the sealed block is returned as the error of the inner result.
+    ///
     /// This validates the following engine API rule:
     ///
     /// 3. Given the expected array of blob versioned hashes client software **MUST** run its
@@ -1185,10 +1277,13 @@ where
         &self,
         payload: ExecutionPayload,
         cancun_fields: Option<CancunPayloadFields>,
-    ) -> Result<SealedBlock, PayloadStatus> {
+    ) -> Result<Result<SealedBlockWithSenders, SealedBlock>, PayloadStatus> {
         let parent_hash = payload.parent_hash();
 
-        match self.payload_validator.ensure_well_formed_payload(payload, cancun_fields.into()) {
+        match self
+            .payload_validator
+            .ensure_well_formed_payload_with_senders(payload, cancun_fields.into())
+        {
             Ok(block) => Ok(block),
             Err(error) => {
                 error!(target: "consensus::engine", %error, "Invalid payload");
@@ -1274,9 +1369,9 @@ where
     #[instrument(level = "trace", skip_all, target = "consensus::engine", ret)]
     fn try_buffer_payload(
         &mut self,
-        block: SealedBlock,
+        block: SealedBlockWithSenders,
     ) -> Result<PayloadStatus, InsertBlockError> {
-        self.blockchain.buffer_block_without_senders(block)?;
+        self.blockchain.buffer_block(block)?;
         Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
     }
 
@@ -1286,19 +1381,18 @@ where
     #[instrument(level = "trace", skip_all, target = "consensus::engine", ret)]
     fn try_insert_new_payload(
         &mut self,
-        block: SealedBlock,
+        block: SealedBlockWithSenders,
     ) -> Result<PayloadStatus, InsertBlockError> {
         debug_assert!(self.sync.is_pipeline_idle(), "pipeline must be idle");
 
         let block_hash = block.hash();
         let start = Instant::now();
-        let status = self
-            .blockchain
-            .insert_block_without_senders(block.clone(), BlockValidationKind::Exhaustive)?;
+        let status =
+            self.blockchain.insert_block(block.clone(), BlockValidationKind::Exhaustive)?;
 
         let elapsed = start.elapsed();
         let mut latest_valid_hash = None;
-        let block = Arc::new(block);
+        let block = Arc::new(block.block);
         let status = match status {
             InsertPayloadOk::Inserted(BlockStatus::Valid(attachment)) => {
                 latest_valid_hash = Some(block_hash);
@@ -1558,6 +1652,29 @@ where
                 error!(target: "consensus::engine", "Failed to receive spawned pipeline");
                 return Err(BeaconConsensusEngineError::PipelineChannelClosed)
             }
+            EngineSyncEvent::PipelineUnwindStarted(target) => {
+                trace!(target: "consensus::engine", ?target, "Started unwinding the pipeline");
+                self.sync_state_updater.update_sync_state(SyncState::Syncing);
+                SyncEventOutcome::Processed
+            }
+            EngineSyncEvent::PipelineUnwound { target, result } => {
+                trace!(target: "consensus::engine", ?target, ?result, "Pipeline unwound");
+                let rewind_tx = self.pending_rewind.take();
+                if let Err(err) = result {
+                    if let Some(tx) = rewind_tx {
+                        let _ = tx.send(Err(BeaconRewindError::Internal(err.to_string().into())));
+                    }
+                    // Any pipeline error at this point is fatal.
+                    return Err(err.into())
+                }
+
+                let outcome = self.on_pipeline_unwound(target);
+                self.sync_state_updater.update_sync_state(SyncState::Idle);
+                if let Some(tx) = rewind_tx {
+                    let _ = tx.send(outcome.map_err(Into::into));
+                }
+                SyncEventOutcome::Processed
+            }
         };
 
         Ok(outcome)
@@ -1779,6 +1896,7 @@ where
                             this.blockchain.on_transition_configuration_exchanged();
                         }
                         BeaconEngineMessage::EventListener(tx) => this.push_listener(tx),
+                        BeaconEngineMessage::Rewind { target, tx } => this.on_rewind(target, tx),
                     }
                     continue
                 }
@@ -45,6 +45,10 @@ where
     pipeline_state: PipelineState<DB>,
     /// Pending target block for the pipeline to sync
     pending_pipeline_target: Option<B256>,
+    /// Pending target block for the pipeline to unwind to, takes precedence over the sync target.
+    pending_pipeline_unwind: Option<BlockNumber>,
+    /// The target block of the running pipeline unwind, if the pipeline is unwinding.
+    active_pipeline_unwind: Option<BlockNumber>,
     /// In-flight full block requests in progress.
     inflight_full_block_requests: Vec<FetchFullBlockFuture<Client>>,
     /// In-flight full block _range_ requests in progress.
@@ -86,6 +90,8 @@ where
             pipeline_task_spawner,
             pipeline_state: PipelineState::Idle(Some(pipeline)),
             pending_pipeline_target: None,
+            pending_pipeline_unwind: None,
+            active_pipeline_unwind: None,
             inflight_full_block_requests: Vec::new(),
             inflight_block_range_requests: Vec::new(),
             range_buffered_blocks: BinaryHeap::new(),
@@ -224,6 +230,13 @@ where
         self.pending_pipeline_target = Some(target);
     }
 
+    /// Sets a block to unwind the pipeline to.
+    ///
+    /// The unwind is started once the pipeline is idle, before any pending sync target.
+    pub(crate) fn set_pipeline_unwind_target(&mut self, target: BlockNumber) {
+        self.pending_pipeline_unwind = Some(target);
+    }
+
     /// Check if the engine reached max block as specified by `max_block` parameter.
     ///
     /// Note: this is mainly for debugging purposes.
@@ -252,6 +265,11 @@ where
             }
         };
         let ev = match res {
+            Ok((pipeline, result)) if self.active_pipeline_unwind.is_some() => {
+                let target = self.active_pipeline_unwind.take().expect("is some");
+                self.pipeline_state = PipelineState::Idle(Some(pipeline));
+                EngineSyncEvent::PipelineUnwound { target, result: result.map(|_| ()) }
+            }
             Ok((pipeline, result)) => {
                 let minimum_block_number = pipeline.minimum_block_number();
                 let reached_max_block =
@@ -272,6 +290,28 @@ where
     fn try_spawn_pipeline(&mut self) -> Option<EngineSyncEvent> {
         match &mut self.pipeline_state {
             PipelineState::Idle(pipeline) => {
+                if let Some(target) = self.pending_pipeline_unwind.take() {
+                    let (tx, rx) = oneshot::channel();
+
+                    let mut pipeline = pipeline.take().expect("exists");
+                    self.pipeline_task_spawner.spawn_critical_blocking(
+                        "pipeline unwind task",
+                        Box::pin(async move {
+                            let result = pipeline
+                                .unwind(target, None)
+                                .map(|_| ControlFlow::NoProgress { block_number: Some(target) });
+                            let _ = tx.send((pipeline, result));
+                        }),
+                    );
+                    self.pipeline_state = PipelineState::Running(rx);
+                    self.active_pipeline_unwind = Some(target);
+
+                    // the downloaded blocks are either unwound or descendants of unwound blocks
+                    self.clear_block_download_requests();
+
+                    return Some(EngineSyncEvent::PipelineUnwindStarted(target))
+                }
+
                 let target = self.pending_pipeline_target.take();
 
                 if target.is_none() && !self.run_pipeline_continuously {
@@ -399,6 +439,17 @@ pub(crate) enum EngineSyncEvent {
     /// Pipeline task was dropped after it was started, unable to receive it because channel
     /// closed. This would indicate a panicked pipeline task
     PipelineTaskDropped,
+    /// Pipeline started unwinding to the given block.
+    PipelineUnwindStarted(BlockNumber),
+    /// Pipeline finished unwinding
+    ///
+    /// If this is returned, the pipeline is idle.
+    PipelineUnwound {
+        /// The block the pipeline unwound to.
+        target: BlockNumber,
+        /// Result of the unwind.
+        result: Result<(), PipelineError>,
+    },
 }
 
 /// The possible pipeline states within the sync controller.
@@ -611,6 +662,39 @@ mod tests {
         });
     }
 
+    #[tokio::test]
+    async fn pipeline_unwind_takes_precedence_over_sync_target() {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(MAINNET.genesis.clone())
+                .paris_activated()
+                .build(),
+        );
+
+        let client = TestFullBlockClient::default();
+        insert_headers_into_client(&client, SealedHeader::default(), 0..10);
+        let pipeline = TestPipelineBuilder::new().build(chain_spec.clone());
+
+        let mut sync_controller = TestSyncControllerBuilder::new()
+            .with_client(client.clone())
+            .build(pipeline, chain_spec);
+
+        let tip = client.highest_block().expect("there should be blocks here");
+        sync_controller.set_pipeline_sync_target(tip.hash());
+        sync_controller.set_pipeline_unwind_target(0);
+
+        let sync_future = poll_fn(|cx| sync_controller.poll(cx));
+        let next_event = poll!(sync_future);
+
+        // the pending unwind is spawned before the pipeline sync
+        assert_matches!(next_event, Poll::Ready(EngineSyncEvent::PipelineUnwindStarted(0)));
+
+        let sync_future = poll_fn(|cx| sync_controller.poll(cx));
+        let next_ready = sync_future.await;
+        assert_matches!(next_ready, EngineSyncEvent::PipelineUnwound { target: 0, result: Ok(()) });
+    }
+
     fn insert_headers_into_client(
         client: &TestFullBlockClient,
         genesis_header: SealedHeader,
@@ -57,12 +57,15 @@ impl<E: EngineTypes + 'static> PayloadHelper<E> {
     }
 
     /// Expects the next event to be a built payload event or panics
+    ///
+    /// Better payload events that are emitted while the payload is being built are skipped.
     pub async fn expect_built_payload(&mut self) -> eyre::Result<E::BuiltPayload> {
-        let second_event = self.payload_event_stream.next().await.unwrap()?;
-        if let reth::payload::Events::BuiltPayload(payload) = second_event {
-            Ok(payload)
-        } else {
-            panic!("Expect a built payload event.");
+        loop {
+            match self.payload_event_stream.next().await.unwrap()? {
+                reth::payload::Events::BuiltPayload(payload) => return Ok(payload),
+                reth::payload::Events::BetterPayload(_) => continue,
+                _ => panic!("Expect a built payload event."),
+            }
         }
     }
 }
@@ -8,18 +8,32 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use reth_evm::{
+    precompile::{CustomPrecompileError, CustomPrecompiles},
+    ConfigureEvm, ConfigureEvmEnv,
+};
 use reth_primitives::{
     revm::{config::revm_spec, env::fill_tx_env},
     revm_primitives::{AnalysisKind, CfgEnvWithHandlerCfg, TxEnv},
     Address, ChainSpec, Head, Header, Transaction, U256,
 };
+use reth_revm::{inspector_handle_register, Database, Evm, EvmBuilder, GetInspector};
 pub mod execute;
 
 /// Ethereum-related EVM configuration.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-pub struct EthEvmConfig;
+pub struct EthEvmConfig {
+    /// The additional precompiles of the chain.
+    precompiles: CustomPrecompiles,
+}
+
+impl EthEvmConfig {
+    /// Creates a new configuration with the additional precompiles declared in the chain spec.
+    pub fn new(chain_spec: &ChainSpec) -> Result<Self, CustomPrecompileError> {
+        Ok(Self { precompiles: CustomPrecompiles::from_chain_spec(chain_spec)? })
+    }
+}
 
 impl ConfigureEvmEnv for EthEvmConfig {
     type TxMeta = ();
@@ -55,7 +69,35 @@ impl ConfigureEvmEnv for EthEvmConfig {
     }
 }
 
-impl ConfigureEvm for EthEvmConfig {}
+impl ConfigureEvm for EthEvmConfig {
+    fn with_custom_precompiles(self, precompiles: CustomPrecompiles) -> Self {
+        Self { precompiles }
+    }
+
+    fn evm<'a, DB: Database + 'a>(&self, db: DB) -> Evm<'a, (), DB> {
+        let precompiles = self.precompiles.clone();
+        EvmBuilder::default()
+            .with_db(db)
+            // add the precompiles of the chain spec
+            .append_handler_register_box(Box::new(move |handler| precompiles.apply(handler)))
+            .build()
+    }
+
+    fn evm_with_inspector<'a, DB, I>(&self, db: DB, inspector: I) -> Evm<'a, I, DB>
+    where
+        DB: Database + 'a,
+        I: GetInspector<DB>,
+    {
+        let precompiles = self.precompiles.clone();
+        EvmBuilder::default()
+            .with_db(db)
+            .with_external_context(inspector)
+            // add the precompiles of the chain spec
+            .append_handler_register_box(Box::new(move |handler| precompiles.apply(handler)))
+            .append_handler_register(inspector_handle_register)
+            .build()
+    }
+}
 
 #[cfg(test)]
 mod tests {
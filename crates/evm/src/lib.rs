@@ -13,9 +13,20 @@ use revm::{inspector_handle_register, Database, Evm, EvmBuilder, GetInspector};
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, SpecId, TxEnv};
 
 pub mod execute;
+pub mod precompile;
+
+use precompile::CustomPrecompiles;
 
 /// Trait for configuring the EVM for executing full blocks.
 pub trait ConfigureEvm: ConfigureEvmEnv {
+    /// Returns the configuration with the additional precompiles of the chain installed into every
+    /// EVM it creates.
+    ///
+    /// By default the precompiles are ignored.
+    fn with_custom_precompiles(self, _precompiles: CustomPrecompiles) -> Self {
+        self
+    }
+
     /// Returns new EVM with the given database
     ///
     /// This does not automatically configure the EVM with [ConfigureEvmEnv] methods. It is up to
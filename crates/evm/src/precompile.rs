@@ -0,0 +1,278 @@
+//! Support for the additional precompiles declared in the chain spec.
+
+use reth_primitives::{
+    Address, BuiltinPrecompile, ChainSpec, CustomPrecompile, Hardfork, PrecompileImplementation,
+};
+use revm::{
+    handler::register::EvmHandler,
+    precompile::{Precompile, PrecompileSpecId, Precompiles},
+    Database,
+};
+use revm_primitives::SpecId;
+use std::{path::PathBuf, sync::Arc};
+
+/// Errors that can occur when loading the custom precompiles of a chain spec.
+#[derive(Debug, thiserror::Error)]
+pub enum CustomPrecompileError {
+    /// The precompile is activated by a hardfork that has no EVM spec.
+    #[error("precompile at {address} can not be activated by {hardfork}")]
+    UnsupportedActivation {
+        /// The address of the precompile.
+        address: Address,
+        /// The activation hardfork.
+        hardfork: Hardfork,
+    },
+    /// The builtin precompile does not exist in the latest EVM spec.
+    #[error("builtin precompile {0:?} is not available")]
+    UnavailableBuiltin(BuiltinPrecompile),
+    /// The node was built without support for WASM precompiles.
+    #[error("precompile {0:?} requires the `wasm-precompiles` feature")]
+    WasmDisabled(PathBuf),
+    /// The WASM module could not be loaded.
+    #[error("failed to load WASM precompile {path:?}: {message}")]
+    Wasm {
+        /// The path of the module.
+        path: PathBuf,
+        /// The error message.
+        message: String,
+    },
+}
+
+/// The additional precompiles of a chain, installed into the EVM handler by
+/// [CustomPrecompiles::apply].
+#[derive(Debug, Clone, Default)]
+pub struct CustomPrecompiles {
+    precompiles: Arc<Vec<(SpecId, Address, Precompile)>>,
+}
+
+impl CustomPrecompiles {
+    /// Loads the given precompiles.
+    ///
+    /// WASM modules are read from disk and compiled once.
+    pub fn new(precompiles: &[CustomPrecompile]) -> Result<Self, CustomPrecompileError> {
+        let precompiles = precompiles
+            .iter()
+            .map(|precompile| {
+                let activation = match precompile.activation {
+                    Some(hardfork) => hardfork_spec_id(hardfork).ok_or(
+                        CustomPrecompileError::UnsupportedActivation {
+                            address: precompile.address,
+                            hardfork,
+                        },
+                    )?,
+                    None => SpecId::FRONTIER,
+                };
+                let implementation = match &precompile.implementation {
+                    PrecompileImplementation::Builtin(builtin) => Precompiles::latest()
+                        .inner
+                        .get(&builtin.address())
+                        .cloned()
+                        .ok_or(CustomPrecompileError::UnavailableBuiltin(*builtin))?,
+                    PrecompileImplementation::Wasm(wasm) => load_wasm(wasm)?,
+                };
+                Ok((activation, precompile.address, implementation))
+            })
+            .collect::<Result<_, CustomPrecompileError>>()?;
+
+        Ok(Self { precompiles: Arc::new(precompiles) })
+    }
+
+    /// Loads the precompiles declared in the chain spec.
+    pub fn from_chain_spec(chain_spec: &ChainSpec) -> Result<Self, CustomPrecompileError> {
+        Self::new(&chain_spec.precompiles)
+    }
+
+    /// Returns `true` if there are no additional precompiles.
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty()
+    }
+
+    /// Installs the precompiles that are active at the spec of the handler, in addition to the
+    /// precompiles of the spec.
+    ///
+    /// The handler is left untouched if there are no additional precompiles.
+    pub fn apply<EXT, DB: Database>(&self, handler: &mut EvmHandler<'_, EXT, DB>) {
+        if self.is_empty() {
+            return
+        }
+
+        let spec_id = handler.cfg.spec_id;
+        let precompiles = self.precompiles.clone();
+
+        handler.pre_execution.load_precompiles = Arc::new(move || {
+            let mut active = Precompiles::new(PrecompileSpecId::from_spec_id(spec_id)).clone();
+            for (activation, address, precompile) in precompiles.iter() {
+                if SpecId::enabled(spec_id, *activation) {
+                    active.inner.insert(*address, precompile.clone());
+                }
+            }
+            active.into()
+        });
+    }
+}
+
+/// Returns the EVM spec introduced by the hardfork.
+const fn hardfork_spec_id(hardfork: Hardfork) -> Option<SpecId> {
+    Some(match hardfork {
+        Hardfork::Frontier => SpecId::FRONTIER,
+        Hardfork::Homestead => SpecId::HOMESTEAD,
+        Hardfork::Dao => SpecId::DAO_FORK,
+        Hardfork::Tangerine => SpecId::TANGERINE,
+        Hardfork::SpuriousDragon => SpecId::SPURIOUS_DRAGON,
+        Hardfork::Byzantium => SpecId::BYZANTIUM,
+        Hardfork::Constantinople => SpecId::CONSTANTINOPLE,
+        Hardfork::Petersburg => SpecId::PETERSBURG,
+        Hardfork::Istanbul => SpecId::ISTANBUL,
+        Hardfork::MuirGlacier => SpecId::MUIR_GLACIER,
+        Hardfork::Berlin => SpecId::BERLIN,
+        Hardfork::London => SpecId::LONDON,
+        Hardfork::ArrowGlacier => SpecId::ARROW_GLACIER,
+        Hardfork::GrayGlacier => SpecId::GRAY_GLACIER,
+        Hardfork::Paris => SpecId::MERGE,
+        Hardfork::Shanghai => SpecId::SHANGHAI,
+        Hardfork::Cancun => SpecId::CANCUN,
+        Hardfork::Prague => SpecId::PRAGUE,
+        _ => return None,
+    })
+}
+
+#[cfg(feature = "wasm-precompiles")]
+fn load_wasm(
+    precompile: &reth_primitives::WasmPrecompile,
+) -> Result<Precompile, CustomPrecompileError> {
+    let wasm = wasm::WasmModule::load(precompile).map_err(|err| CustomPrecompileError::Wasm {
+        path: precompile.path.clone(),
+        message: err.to_string(),
+    })?;
+    Ok(Precompile::Stateful(Arc::new(wasm)))
+}
+
+#[cfg(not(feature = "wasm-precompiles"))]
+fn load_wasm(
+    precompile: &reth_primitives::WasmPrecompile,
+) -> Result<Precompile, CustomPrecompileError> {
+    Err(CustomPrecompileError::WasmDisabled(precompile.path.clone()))
+}
+
+#[cfg(feature = "wasm-precompiles")]
+mod wasm {
+    use reth_primitives::{fs, Bytes, WasmPrecompile};
+    use revm_primitives::{Env, PrecompileError, PrecompileResult, StatefulPrecompile};
+    use wasmi::{Config, Engine, Linker, Module, Store};
+
+    /// A precompile executing a compiled WASM module, metering its execution with fuel.
+    ///
+    /// See [WasmPrecompile] for the interface of the module.
+    pub(super) struct WasmModule {
+        engine: Engine,
+        module: Module,
+        base_gas: u64,
+    }
+
+    impl WasmModule {
+        /// Reads and compiles the module.
+        pub(super) fn load(precompile: &WasmPrecompile) -> eyre::Result<Self> {
+            let bytes = fs::read(&precompile.path)?;
+            let mut config = Config::default();
+            config.consume_fuel(true);
+            let engine = Engine::new(&config);
+            let module = Module::new(&engine, bytes.as_slice())?;
+            Ok(Self { engine, module, base_gas: precompile.base_gas })
+        }
+
+        /// Executes the module with the given fuel, returning the output and the consumed fuel.
+        fn execute(&self, input: &[u8], fuel: u64) -> (eyre::Result<Bytes>, u64) {
+            let mut store = Store::new(&self.engine, ());
+            let result = self.execute_in(&mut store, input, fuel);
+            (result, store.fuel_consumed().unwrap_or_default())
+        }
+
+        fn execute_in(
+            &self,
+            store: &mut Store<()>,
+            input: &[u8],
+            fuel: u64,
+        ) -> eyre::Result<Bytes> {
+            store.add_fuel(fuel)?;
+            let instance = <Linker<()>>::new(&self.engine)
+                .instantiate(&mut *store, &self.module)?
+                .start(&mut *store)?;
+            let memory = instance
+                .get_memory(&*store, "memory")
+                .ok_or_else(|| eyre::eyre!("module does not export its memory"))?;
+            let alloc = instance.get_typed_func::<i32, i32>(&*store, "alloc")?;
+            let call = instance.get_typed_func::<(i32, i32), i64>(&*store, "call")?;
+
+            let len = i32::try_from(input.len())?;
+            let ptr = alloc.call(&mut *store, len)?;
+            memory.write(&mut *store, ptr as usize, input)?;
+
+            let output = call.call(&mut *store, (ptr, len))?;
+            if output < 0 {
+                eyre::bail!("call failed with {output}")
+            }
+            let mut buf = vec![0; (output & 0xffff_ffff) as usize];
+            memory.read(&*store, (output >> 32) as usize, &mut buf)?;
+            Ok(buf.into())
+        }
+    }
+
+    impl StatefulPrecompile for WasmModule {
+        fn call(&self, bytes: &Bytes, gas_limit: u64, _env: &Env) -> PrecompileResult {
+            let Some(fuel) = gas_limit.checked_sub(self.base_gas) else {
+                return Err(PrecompileError::OutOfGas)
+            };
+            match self.execute(bytes, fuel) {
+                (Ok(output), consumed) => Ok((self.base_gas + consumed, output)),
+                (Err(_), consumed) if consumed >= fuel => Err(PrecompileError::OutOfGas),
+                (Err(err), _) => Err(PrecompileError::Other(err.to_string())),
+            }
+        }
+    }
+
+    impl std::fmt::Debug for WasmModule {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("WasmModule").field("base_gas", &self.base_gas).finish_non_exhaustive()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::address;
+
+    #[test]
+    fn load_builtin_precompiles() {
+        let precompiles = CustomPrecompiles::new(&[
+            CustomPrecompile {
+                address: address!("0000000000000000000000000000000000000100"),
+                activation: None,
+                implementation: PrecompileImplementation::Builtin(BuiltinPrecompile::Sha256),
+            },
+            CustomPrecompile {
+                address: address!("0000000000000000000000000000000000000101"),
+                activation: Some(Hardfork::Cancun),
+                implementation: PrecompileImplementation::Builtin(BuiltinPrecompile::Identity),
+            },
+        ])
+        .unwrap();
+        assert_eq!(precompiles.precompiles.len(), 2);
+        assert_eq!(precompiles.precompiles[1].0, SpecId::CANCUN);
+    }
+
+    #[cfg(not(feature = "wasm-precompiles"))]
+    #[test]
+    fn reject_wasm_precompiles_without_feature() {
+        let err = CustomPrecompiles::new(&[CustomPrecompile {
+            address: address!("0000000000000000000000000000000000000100"),
+            activation: None,
+            implementation: PrecompileImplementation::Wasm(reth_primitives::WasmPrecompile {
+                path: "hash.wasm".into(),
+                base_gas: 0,
+            }),
+        }])
+        .unwrap_err();
+        assert!(matches!(err, CustomPrecompileError::WasmDisabled(_)));
+    }
+}
@@ -0,0 +1,93 @@
+//! Built-in index of the transactions sent from or to each address.
+
+use crate::ChainIndex;
+use reth_db::database::Database;
+use reth_interfaces::provider::{ProviderError, ProviderResult};
+use reth_primitives::{Address, BlockNumber, TxNumber};
+use reth_provider::{
+    AddressTransactionsReader, AddressTransactionsWriter, BlockReader, Chain, DatabaseProviderRO,
+    DatabaseProviderRW,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+pub use reth_provider::ADDRESS_TRANSACTIONS_EXEX_ID;
+
+/// The index of the canonical transactions sent from or to each address, including the contracts
+/// created by a transaction, see [`AddressTransactionsReader`].
+///
+/// Maintained by an [`IndexExEx`](crate::IndexExEx).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AddressTransactionsIndex;
+
+impl ChainIndex for AddressTransactionsIndex {
+    const NAME: &'static str = "address_transactions";
+
+    fn checkpoint<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        provider.address_transactions_checkpoint()
+    }
+
+    fn backfill<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let end = *range.end();
+        let transactions = provider.address_transactions_by_block_range(range)?;
+        provider.insert_address_transactions(transactions, end)
+    }
+
+    fn commit<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        chain: &Chain,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()> {
+        // the transactions of the chain are numbered after the ones of the indexed blocks
+        let mut tx_number = provider
+            .block_body_indices(checkpoint)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(checkpoint))?
+            .next_tx_num();
+        let mut transactions = BTreeMap::<Address, Vec<TxNumber>>::new();
+        for (sender, other) in transaction_addresses(chain, checkpoint) {
+            transactions.entry(sender).or_default().push(tx_number);
+            if other != sender {
+                transactions.entry(other).or_default().push(tx_number);
+            }
+            tx_number += 1;
+        }
+        provider.insert_address_transactions(transactions, chain.tip().number)
+    }
+
+    fn unwind<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        chain: &Chain,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
+        let addresses = transaction_addresses(chain, block)
+            .flat_map(|(sender, other)| [sender, other])
+            .collect::<BTreeSet<_>>();
+        provider.unwind_address_transactions(addresses, block)
+    }
+}
+
+/// Returns the sender and the recipient, or the created contract, of the transactions of the
+/// blocks of the chain above `checkpoint`.
+fn transaction_addresses(
+    chain: &Chain,
+    checkpoint: BlockNumber,
+) -> impl Iterator<Item = (Address, Address)> + '_ {
+    chain
+        .blocks_iter()
+        .filter(move |block| block.number > checkpoint)
+        .flat_map(|block| block.transactions_with_sender())
+        .map(|(sender, tx)| {
+            (*sender, tx.transaction.to().unwrap_or_else(|| sender.create(tx.nonce())))
+        })
+}
@@ -0,0 +1,215 @@
+use crate::{ExExContext, ExExEvent, ExExNotification};
+use reth_db::database::Database;
+use reth_interfaces::provider::ProviderResult;
+use reth_node_api::FullNodeComponents;
+use reth_primitives::BlockNumber;
+use reth_provider::{Chain, DatabaseProviderRO, DatabaseProviderRW, ProviderFactory};
+use reth_tracing::tracing::{debug, info};
+use std::{
+    ops::RangeInclusive,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The number of blocks inserted into the index at once during backfill.
+const BACKFILL_CHUNK_SIZE: u64 = 100;
+
+/// The time after which a backfill transaction is committed, to not hold the write lock of the
+/// database for long while the node commits canonical blocks.
+const BACKFILL_COMMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+/// An index derived from the canonical chain and stored in the database, maintained by an
+/// [`IndexExEx`].
+pub trait ChainIndex: Send + Sync + 'static {
+    /// The name of the index, used in logs.
+    const NAME: &'static str;
+
+    /// Returns the highest indexed block, or `None` if the index is not enabled.
+    fn checkpoint<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+    ) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Reads the blocks of the range from the database, inserts them into the index and sets the
+    /// highest indexed block to the end of the range.
+    fn backfill<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()>;
+
+    /// Inserts the blocks of the committed chain above `checkpoint` into the index and sets the
+    /// highest indexed block to the tip of the chain.
+    fn commit<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        chain: &Chain,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()>;
+
+    /// Removes the blocks of the reverted chain above `block` from the index and sets the highest
+    /// indexed block to `block`.
+    fn unwind<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        chain: &Chain,
+        block: BlockNumber,
+    ) -> ProviderResult<()>;
+}
+
+/// An ExEx maintaining a [`ChainIndex`].
+///
+/// On launch, the blocks between the last indexed block and the head are backfilled from the
+/// database. Reverted blocks are removed from the index, and committed blocks are inserted into
+/// it. All database work runs on the blocking thread pool.
+#[derive(Debug)]
+pub struct IndexExEx<Node: FullNodeComponents, I> {
+    ctx: ExExContext<Node>,
+    provider_factory: ProviderFactory<Node::DB>,
+    index: I,
+}
+
+impl<Node: FullNodeComponents, I: ChainIndex> IndexExEx<Node, I> {
+    /// Creates a new instance of the ExEx, writing the index with the given provider factory.
+    pub fn new(
+        ctx: ExExContext<Node>,
+        provider_factory: ProviderFactory<Node::DB>,
+        index: I,
+    ) -> Self {
+        Self { ctx, provider_factory, index }
+    }
+
+    /// Backfills the index up to the head and then follows the notifications of the node.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let mut indexer =
+            Indexer::new(self.provider_factory, self.index, self.ctx.head.number).await?;
+        self.ctx.events.send(ExExEvent::FinishedHeight(indexer.checkpoint()))?;
+
+        while let Some(notification) = self.ctx.notifications.recv().await {
+            indexer.on_notification(&notification).await?;
+            self.ctx.events.send(ExExEvent::FinishedHeight(indexer.checkpoint()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies the notifications of the node to a [`ChainIndex`] and tracks its checkpoint.
+#[derive(Debug)]
+pub(crate) struct Indexer<DB, I> {
+    provider_factory: ProviderFactory<DB>,
+    index: Arc<I>,
+    checkpoint: BlockNumber,
+}
+
+impl<DB: Database + 'static, I: ChainIndex> Indexer<DB, I> {
+    /// Loads the checkpoint of the index and backfills the index up to `head`.
+    pub(crate) async fn new(
+        provider_factory: ProviderFactory<DB>,
+        index: I,
+        head: BlockNumber,
+    ) -> eyre::Result<Self> {
+        let mut indexer = Self { provider_factory, index: Arc::new(index), checkpoint: 0 };
+        indexer.checkpoint = indexer
+            .spawn_blocking(|factory, index| {
+                Ok(index.checkpoint(&factory.provider()?)?.unwrap_or_default())
+            })
+            .await?;
+        if indexer.checkpoint < head {
+            indexer.backfill(indexer.checkpoint + 1..=head).await?;
+        }
+        Ok(indexer)
+    }
+
+    /// Returns the highest indexed block.
+    pub(crate) const fn checkpoint(&self) -> BlockNumber {
+        self.checkpoint
+    }
+
+    /// Removes the reverted blocks of the notification from the index and inserts the committed
+    /// ones, backfilling any gap between the checkpoint and the committed chain.
+    pub(crate) async fn on_notification(
+        &mut self,
+        notification: &ExExNotification,
+    ) -> eyre::Result<()> {
+        if let Some(reverted) = notification.reverted_chain() {
+            let first = reverted.first().number;
+            if first <= self.checkpoint {
+                let block = first - 1;
+                self.spawn_blocking(move |factory, index| {
+                    let provider = factory.provider_rw()?;
+                    index.unwind(&provider, &reverted, block)?;
+                    provider.commit()?;
+                    Ok(())
+                })
+                .await?;
+                self.checkpoint = block;
+                debug!(target: "exex::index", index = I::NAME, checkpoint = block, "Unwound index");
+            }
+        }
+
+        if let Some(committed) = notification.committed_chain() {
+            let first = committed.first().number;
+            if first > self.checkpoint + 1 {
+                self.backfill(self.checkpoint + 1..=first - 1).await?;
+            }
+
+            let tip = committed.tip().number;
+            if tip > self.checkpoint {
+                let checkpoint = self.checkpoint;
+                self.spawn_blocking(move |factory, index| {
+                    let provider = factory.provider_rw()?;
+                    index.commit(&provider, &committed, checkpoint)?;
+                    provider.commit()?;
+                    Ok(())
+                })
+                .await?;
+                self.checkpoint = tip;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Backfills the blocks of the range from the database in chunks of [BACKFILL_CHUNK_SIZE]
+    /// blocks, committing the index once [BACKFILL_COMMIT_INTERVAL] has passed and yielding to
+    /// the other tasks between commits.
+    async fn backfill(&mut self, range: RangeInclusive<BlockNumber>) -> eyre::Result<()> {
+        info!(target: "exex::index", index = I::NAME, ?range, "Backfilling index");
+        let last = *range.end();
+        let mut start = *range.start();
+        while start <= last {
+            let end = self
+                .spawn_blocking(move |factory, index| {
+                    let provider = factory.provider_rw()?;
+                    let started = Instant::now();
+                    let mut end = start - 1;
+                    while end < last && started.elapsed() < BACKFILL_COMMIT_INTERVAL {
+                        let chunk_end = (end + BACKFILL_CHUNK_SIZE).min(last);
+                        index.backfill(&provider, end + 1..=chunk_end)?;
+                        end = chunk_end;
+                    }
+                    provider.commit()?;
+                    Ok(end)
+                })
+                .await?;
+            self.checkpoint = end;
+
+            debug!(target: "exex::index", index = I::NAME, start, end, "Backfilled index");
+            start = end + 1;
+            tokio::task::yield_now().await;
+        }
+        Ok(())
+    }
+
+    /// Runs the closure with the provider factory and the index on the blocking thread pool.
+    async fn spawn_blocking<T, F>(&self, f: F) -> eyre::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&ProviderFactory<DB>, &I) -> eyre::Result<T> + Send + 'static,
+    {
+        let provider_factory = self.provider_factory.clone();
+        let index = Arc::clone(&self.index);
+        tokio::task::spawn_blocking(move || f(&provider_factory, &index)).await?
+    }
+}
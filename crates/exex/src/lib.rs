@@ -45,3 +45,18 @@ pub use manager::*;
 
 mod notification;
 pub use notification::*;
+
+mod index;
+pub use index::*;
+
+#[cfg(feature = "token-transfers")]
+pub mod token_transfers;
+
+#[cfg(feature = "sender-nonces")]
+pub mod sender_nonces;
+
+#[cfg(feature = "log-index")]
+pub mod log_index;
+
+#[cfg(feature = "address-transactions")]
+pub mod address_transactions;
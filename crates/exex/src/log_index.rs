@@ -0,0 +1,181 @@
+//! Built-in index of the blocks with logs by address and first topic.
+
+use crate::ChainIndex;
+use reth_db::database::Database;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_provider::{
+    Chain, DatabaseProviderRO, DatabaseProviderRW, LogIndexReader, LogIndexWriter,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+pub use reth_provider::LOG_INDEX_EXEX_ID;
+
+/// The blocks with logs of each address and first topic.
+type LogIndexUpdates = (BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>);
+
+/// The index of the canonical blocks with logs by address and first topic, see
+/// [`LogIndexReader`].
+///
+/// Maintained by an [`IndexExEx`](crate::IndexExEx).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogIndex;
+
+impl ChainIndex for LogIndex {
+    const NAME: &'static str = "log_index";
+
+    fn checkpoint<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        provider.log_index_checkpoint()
+    }
+
+    fn backfill<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let end = *range.end();
+        let (addresses, topics) = provider.log_index_by_block_range(range)?;
+        provider.insert_log_index(addresses, topics, end)
+    }
+
+    fn commit<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        chain: &Chain,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()> {
+        let (addresses, topics) = log_index_updates(chain, checkpoint);
+        provider.insert_log_index(addresses, topics, chain.tip().number)
+    }
+
+    fn unwind<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        chain: &Chain,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
+        let (addresses, topics) = log_index_updates(chain, block);
+        provider.unwind_log_index(
+            addresses.into_keys().collect::<BTreeSet<_>>(),
+            topics.into_keys().collect::<BTreeSet<_>>(),
+            block,
+        )
+    }
+}
+
+/// Returns the blocks of the chain above `checkpoint` with logs of each address and first topic.
+fn log_index_updates(chain: &Chain, checkpoint: BlockNumber) -> LogIndexUpdates {
+    let mut addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+    let mut topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+    for (block, receipts) in chain.blocks_and_receipts() {
+        if block.number <= checkpoint {
+            continue
+        }
+        let logs = receipts.iter().flatten().flat_map(|receipt| &receipt.logs);
+        for log in logs {
+            let blocks = addresses.entry(log.address).or_default();
+            if blocks.last() != Some(&block.number) {
+                blocks.push(block.number);
+            }
+            if let Some(topic) = log.topics().first() {
+                let blocks = topics.entry(*topic).or_default();
+                if blocks.last() != Some(&block.number) {
+                    blocks.push(block.number);
+                }
+            }
+        }
+    }
+    (addresses, topics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExExNotification, Indexer};
+    use reth_primitives::SealedBlockWithSenders;
+    use reth_provider::{
+        test_utils::{blocks::BlockChainTestData, create_test_provider_factory},
+        BundleStateWithReceipts, OriginalValuesKnown,
+    };
+    use std::sync::Arc;
+
+    /// Returns the chain of the blocks with their merged execution state.
+    fn chain(blocks: &[(SealedBlockWithSenders, BundleStateWithReceipts)]) -> Chain {
+        let mut state = blocks[0].1.clone();
+        for (_, block_state) in &blocks[1..] {
+            state.extend(block_state.clone());
+        }
+        Chain::new(blocks.iter().map(|(block, _)| block.clone()), state, None)
+    }
+
+    #[tokio::test]
+    async fn backfills_commits_and_unwinds_reorged_blocks() {
+        let factory = create_test_provider_factory();
+        let data = BlockChainTestData::default();
+        let (first, second) = data.blocks.split_at(3);
+
+        // blocks 1 to 3 are in the database before the index is enabled
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .insert_historical_block(
+                SealedBlockWithSenders { block: data.genesis.clone(), senders: Vec::new() },
+                None,
+            )
+            .unwrap();
+        let persisted = chain(first);
+        for block in persisted.blocks_iter() {
+            provider_rw.insert_historical_block(block.clone(), None).unwrap();
+        }
+        persisted
+            .state()
+            .clone()
+            .write_to_storage(provider_rw.tx_ref(), None, OriginalValuesKnown::No)
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let first_address = Address::new([0x60; 20]);
+        let first_topic = B256::with_last_byte(1);
+        let address = Address::new([0x61; 20]);
+        let topic = B256::with_last_byte(3);
+
+        // the index is backfilled up to the head
+        assert_eq!(factory.log_index_checkpoint().unwrap(), None);
+        let mut indexer = Indexer::new(factory.clone(), LogIndex, 3).await.unwrap();
+        assert_eq!(indexer.checkpoint(), 3);
+        assert_eq!(factory.log_index_checkpoint().unwrap(), Some(3));
+        assert_eq!(factory.blocks_with_logs(&[first_address], &[], 0..=3).unwrap(), vec![1]);
+        assert_eq!(factory.blocks_with_logs(&[address], &[], 0..=3).unwrap(), vec![2, 3]);
+        assert_eq!(factory.blocks_with_logs(&[], &[topic], 0..=3).unwrap(), vec![2, 3]);
+
+        // committed blocks are appended
+        let committed = Arc::new(chain(second));
+        indexer
+            .on_notification(&ExExNotification::ChainCommitted { new: committed.clone() })
+            .await
+            .unwrap();
+        assert_eq!(indexer.checkpoint(), 5);
+        assert_eq!(factory.log_index_checkpoint().unwrap(), Some(5));
+        assert_eq!(factory.blocks_with_logs(&[address], &[], 0..=5).unwrap(), vec![2, 3, 4, 5]);
+        assert_eq!(factory.blocks_with_logs(&[address], &[topic], 3..=4).unwrap(), vec![3, 4]);
+        assert_eq!(factory.blocks_with_logs(&[first_address], &[topic], 0..=5).unwrap(), vec![]);
+
+        // blocks 4 and 5 are reorged out by a block 4 with the logs of the first block
+        let reorged = BlockChainTestData::default_from_number(4);
+        let new = Arc::new(chain(&reorged.blocks[..1]));
+        indexer
+            .on_notification(&ExExNotification::ChainReorged { old: committed, new })
+            .await
+            .unwrap();
+        assert_eq!(indexer.checkpoint(), 4);
+        assert_eq!(factory.log_index_checkpoint().unwrap(), Some(4));
+        assert_eq!(factory.blocks_with_logs(&[address], &[], 0..=5).unwrap(), vec![2, 3]);
+        assert_eq!(factory.blocks_with_logs(&[first_address], &[], 0..=5).unwrap(), vec![1, 4]);
+        assert_eq!(factory.blocks_with_logs(&[], &[first_topic], 2..=5).unwrap(), vec![4]);
+    }
+}
@@ -0,0 +1,79 @@
+//! Built-in index of the canonical transactions by sender and nonce.
+
+use crate::ChainIndex;
+use reth_db::database::Database;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, TxHash};
+use reth_provider::{
+    Chain, DatabaseProviderRO, DatabaseProviderRW, SenderNoncesReader, SenderNoncesWriter,
+};
+use reth_tracing::tracing::debug;
+use std::ops::RangeInclusive;
+
+pub use reth_provider::SENDER_NONCES_EXEX_ID;
+
+/// The index of the transactions of canonical blocks by sender and nonce, see
+/// [`SenderNoncesReader`].
+///
+/// Maintained by an [`IndexExEx`](crate::IndexExEx).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SenderNoncesIndex;
+
+impl ChainIndex for SenderNoncesIndex {
+    const NAME: &'static str = "sender_nonces";
+
+    fn checkpoint<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        provider.sender_nonces_checkpoint()
+    }
+
+    fn backfill<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let end = *range.end();
+        let transactions = provider.sender_nonces_by_block_range(range)?;
+        provider.insert_sender_nonces(transactions, end)
+    }
+
+    fn commit<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        chain: &Chain,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()> {
+        provider
+            .insert_sender_nonces(sender_nonces(chain, checkpoint).collect(), chain.tip().number)
+    }
+
+    fn unwind<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        chain: &Chain,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
+        let reverted = sender_nonces(chain, block)
+            .map(|(sender, nonce, _)| (sender, nonce))
+            .collect::<Vec<_>>();
+        let removed = reverted.len();
+        provider.unwind_sender_nonces(reverted, block)?;
+        debug!(target: "exex::index", index = Self::NAME, block, removed, "Removed sender nonces");
+        Ok(())
+    }
+}
+
+/// Returns the sender, nonce and hash of the transactions of the blocks of the chain above
+/// `checkpoint`.
+fn sender_nonces(
+    chain: &Chain,
+    checkpoint: BlockNumber,
+) -> impl Iterator<Item = (Address, u64, TxHash)> + '_ {
+    chain
+        .blocks_iter()
+        .filter(move |block| block.number > checkpoint)
+        .flat_map(|block| block.transactions_with_sender())
+        .map(|(sender, tx)| (*sender, tx.nonce(), tx.hash()))
+}
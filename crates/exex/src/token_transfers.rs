@@ -0,0 +1,78 @@
+//! Built-in index of the ERC-20 and ERC-721 token transfers of each address.
+
+use crate::ChainIndex;
+use reth_db::database::Database;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{BlockNumber, TokenTransfer};
+use reth_provider::{
+    Chain, DatabaseProviderRO, DatabaseProviderRW, TokenTransfersReader, TokenTransfersWriter,
+};
+use reth_tracing::tracing::debug;
+use std::ops::RangeInclusive;
+
+pub use reth_provider::TOKEN_TRANSFERS_EXEX_ID;
+
+/// The index decoding the `Transfer` events of canonical blocks into token transfers, see
+/// [`TokenTransfersReader`].
+///
+/// Maintained by an [`IndexExEx`](crate::IndexExEx).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokenTransfersIndex;
+
+impl ChainIndex for TokenTransfersIndex {
+    const NAME: &'static str = "token_transfers";
+
+    fn checkpoint<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRO<DB>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        provider.token_transfers_checkpoint()
+    }
+
+    fn backfill<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let end = *range.end();
+        let transfers = provider.token_transfers_by_block_range(range)?;
+        provider.insert_token_transfers(transfers, end)
+    }
+
+    fn commit<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        chain: &Chain,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()> {
+        let transfers = chain
+            .blocks_and_receipts()
+            .filter(|(block, _)| block.number > checkpoint)
+            .flat_map(|(block, receipts)| {
+                TokenTransfer::from_receipts(
+                    block.number,
+                    block.body.iter().map(|tx| tx.hash()),
+                    receipts.iter().map(Option::as_ref),
+                )
+            })
+            .collect();
+        provider.insert_token_transfers(transfers, chain.tip().number)
+    }
+
+    fn unwind<DB: Database>(
+        &self,
+        provider: &DatabaseProviderRW<DB>,
+        _chain: &Chain,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
+        let removed = provider.unwind_token_transfers(block)?;
+        debug!(
+            target: "exex::index",
+            index = Self::NAME,
+            block,
+            removed,
+            "Removed token transfers"
+        );
+        Ok(())
+    }
+}
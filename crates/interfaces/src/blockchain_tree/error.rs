@@ -47,6 +47,13 @@ pub enum BlockchainTreeError {
         /// The block hash of the block that failed to buffer.
         block_hash: BlockHash,
     },
+    /// Thrown if a block forks off below the in-memory window and there is no trie checkpoint
+    /// at or below the fork block to reconstruct its state from.
+    #[error("no trie checkpoint found at or below fork block #{block_number}")]
+    TrieCheckpointNotFound {
+        /// The block number of the fork block.
+        block_number: BlockNumber,
+    },
 }
 
 /// Canonical Errors
@@ -277,7 +284,8 @@ impl InsertBlockErrorKind {
                     BlockchainTreeError::CanonicalChain { .. } |
                     BlockchainTreeError::BlockNumberNotFoundInChain { .. } |
                     BlockchainTreeError::BlockHashNotFoundInChain { .. } |
-                    BlockchainTreeError::BlockBufferingFailed { .. } => false,
+                    BlockchainTreeError::BlockBufferingFailed { .. } |
+                    BlockchainTreeError::TrieCheckpointNotFound { .. } => false,
                 }
             }
             InsertBlockErrorKind::Provider(_) | InsertBlockErrorKind::Internal(_) => {
@@ -59,6 +59,16 @@ pub trait BlockchainTreeEngine: BlockchainTreeViewer + Send + Sync {
         validation_kind: BlockValidationKind,
     ) -> Result<InsertPayloadOk, InsertBlockError>;
 
+    /// Validates and executes the block like [BlockchainTreeEngine::insert_block] with
+    /// [BlockValidationKind::Exhaustive], without inserting it into the tree or affecting the
+    /// canonical chain.
+    ///
+    /// Returns [BlockStatus::Disconnected] if the parent of the block is unknown.
+    fn validate_block_without_insert(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BlockStatus, InsertBlockError>;
+
     /// Finalize blocks up until and including `finalized_block`, and remove them from the tree.
     fn finalize_block(&self, finalized_block: BlockNumber);
 
@@ -18,6 +18,14 @@ impl BlockResponse {
         }
     }
 
+    /// Consumes the response and returns the header
+    pub fn into_header(self) -> SealedHeader {
+        match self {
+            BlockResponse::Full(block) => block.header,
+            BlockResponse::Empty(header) => header,
+        }
+    }
+
     /// Calculates a heuristic for the in-memory size of the [BlockResponse].
     #[inline]
     pub fn size(&self) -> usize {
@@ -1,9 +1,10 @@
 use crate::metrics::{BodyDownloaderMetrics, ResponseMetrics};
 use futures::{Future, FutureExt};
-use reth_consensus::Consensus;
+use rayon::prelude::*;
+use reth_consensus::{Consensus, ConsensusError};
 use reth_interfaces::p2p::{
     bodies::{client::BodiesClient, response::BlockResponse},
-    error::{DownloadError, DownloadResult},
+    error::{DownloadError, DownloadResult, RequestError},
     priority::Priority,
 };
 use reth_primitives::{
@@ -16,6 +17,10 @@ use std::{
     sync::Arc,
     task::{ready, Context, Poll},
 };
+use tokio::sync::oneshot;
+
+/// Block responses paired with the result of their validation, in the order of the request.
+type ValidatedResponses = Vec<(BlockResponse, Result<(), ConsensusError>)>;
 
 /// Body request implemented as a [Future].
 ///
@@ -23,8 +28,10 @@ use std::{
 /// If the response arrived with insufficient number of bodies, the future
 /// will issue another request until all bodies are collected.
 ///
-/// It then proceeds to verify the downloaded bodies. In case of an validation error,
-/// the future will start over.
+/// It then proceeds to verify the downloaded bodies. The validation runs on the rayon thread pool
+/// so that it doesn't block the task polling the future, the next request is already in flight
+/// while a response is being validated. In case of an validation error, the future will start
+/// over.
 ///
 /// The future will filter out any empty headers (see [reth_primitives::Header::is_empty]) from the
 /// request. If [BodiesRequestFuture] was initialized with all empty headers, no request will be
@@ -48,6 +55,10 @@ pub(crate) struct BodiesRequestFuture<B: BodiesClient> {
     /// Internal buffer for all blocks
     buffer: Vec<BlockResponse>,
     fut: Option<B::Output>,
+    /// The validation of the last response and the peer that sent it.
+    ///
+    /// The response is buffered before the next response is processed.
+    validation: Option<(PeerId, oneshot::Receiver<ValidatedResponses>)>,
     /// Tracks how many bodies we requested in the last request.
     last_request_len: Option<usize>,
 }
@@ -71,6 +82,7 @@ where
             buffer: Default::default(),
             last_request_len: None,
             fut: None,
+            validation: None,
         }
     }
 
@@ -139,10 +151,12 @@ where
             }))
         }
 
-        // Buffer block responses
-        self.try_buffer_blocks(bodies)?;
+        // Validate block responses off the polling task
+        let responses = self.take_responses(bodies);
+        self.validation = Some((peer_id, spawn_validation(Arc::clone(&self.consensus), responses)));
 
-        // Submit next request if any
+        // Submit next request if any, its response is only processed once the validated responses
+        // are buffered.
         if let Some(req) = self.next_request() {
             self.submit_request(req, Priority::High);
         } else {
@@ -152,43 +166,34 @@ where
         Ok(())
     }
 
-    /// Attempt to buffer body responses. Returns an error if body response fails validation.
-    /// Every body preceding the failed one will be buffered.
+    /// Pair the bodies with the pending headers in the order of the request.
     ///
-    /// This method removes headers from the internal collection.
-    /// If the response fails validation, then the header will be put back.
-    fn try_buffer_blocks(&mut self, bodies: Vec<BlockBody>) -> DownloadResult<()> {
+    /// This method removes headers from the internal collection, they are put back by
+    /// [Self::try_buffer_blocks] if the validation fails.
+    fn take_responses(&mut self, bodies: Vec<BlockBody>) -> Vec<BlockResponse> {
         let bodies_capacity = bodies.capacity();
         let bodies_len = bodies.len();
         let mut bodies = bodies.into_iter().peekable();
 
         let mut total_size = bodies_capacity * mem::size_of::<BlockBody>();
+        let mut responses = Vec::with_capacity(bodies_len);
         while bodies.peek().is_some() {
             let next_header = match self.pending_headers.pop_front() {
                 Some(header) => header,
-                None => return Ok(()), // no more headers
+                None => break, // no more headers
             };
 
             if next_header.is_empty() {
                 // increment empty block body metric
                 total_size += mem::size_of::<BlockBody>();
-                self.buffer.push(BlockResponse::Empty(next_header));
+                responses.push(BlockResponse::Empty(next_header));
             } else {
                 let next_body = bodies.next().unwrap();
 
                 // increment full block body metric
                 total_size += next_body.size();
 
-                let block = SealedBlock::new(next_header, next_body);
-
-                if let Err(error) = self.consensus.validate_block(&block) {
-                    // Body is invalid, put the header back and return an error
-                    let hash = block.hash();
-                    self.pending_headers.push_front(block.header);
-                    return Err(DownloadError::BodyValidation { hash, error: Box::new(error) })
-                }
-
-                self.buffer.push(BlockResponse::Full(block));
+                responses.push(BlockResponse::Full(SealedBlock::new(next_header, next_body)));
             }
         }
 
@@ -196,10 +201,58 @@ where
         self.response_metrics.response_size_bytes.set(total_size as f64);
         self.response_metrics.response_length.set(bodies_len as f64);
 
+        responses
+    }
+
+    /// Attempt to buffer validated body responses. Returns an error if a body response failed
+    /// validation. Every body preceding the failed one will be buffered.
+    ///
+    /// If the response failed validation, then the header will be put back along with every
+    /// header following it.
+    fn try_buffer_blocks(&mut self, validated: ValidatedResponses) -> DownloadResult<()> {
+        let mut validated = validated.into_iter();
+        while let Some((response, result)) = validated.next() {
+            if let Err(error) = result {
+                // Body is invalid, put the headers back and return an error
+                let hash = response.header().hash();
+                for (response, _) in validated.rev() {
+                    self.pending_headers.push_front(response.into_header());
+                }
+                self.pending_headers.push_front(response.into_header());
+                return Err(DownloadError::BodyValidation { hash, error: Box::new(error) })
+            }
+            self.buffer.push(response);
+        }
+
         Ok(())
     }
 }
 
+/// Validate the block responses in parallel on the rayon thread pool.
+///
+/// The returned receiver resolves to the responses paired with their validation results, in the
+/// order of the given responses.
+fn spawn_validation(
+    consensus: Arc<dyn Consensus>,
+    responses: Vec<BlockResponse>,
+) -> oneshot::Receiver<ValidatedResponses> {
+    let (tx, rx) = oneshot::channel();
+    rayon::spawn(move || {
+        let validated = responses
+            .into_par_iter()
+            .map(|response| {
+                let result = match &response {
+                    BlockResponse::Full(block) => consensus.validate_block(block),
+                    BlockResponse::Empty(_) => Ok(()),
+                };
+                (response, result)
+            })
+            .collect();
+        let _ = tx.send(validated);
+    });
+    rx
+}
+
 impl<B> Future for BodiesRequestFuture<B>
 where
     B: BodiesClient + 'static,
@@ -210,6 +263,22 @@ where
         let this = self.get_mut();
 
         loop {
+            // Buffer the validated responses before processing the next response to keep the
+            // order of the request.
+            if let Some((peer_id, validation)) = this.validation.as_mut() {
+                let peer_id = *peer_id;
+                let validated = ready!(validation.poll_unpin(cx));
+                this.validation = None;
+                match validated {
+                    Ok(validated) => {
+                        if let Err(error) = this.try_buffer_blocks(validated) {
+                            this.on_error(error, Some(peer_id));
+                        }
+                    }
+                    Err(_) => return Poll::Ready(Err(RequestError::ChannelClosed.into())),
+                }
+            }
+
             if this.pending_headers.is_empty() {
                 return Poll::Ready(Ok(std::mem::take(&mut this.buffer)))
             }
@@ -234,8 +303,10 @@ where
                 }
             }
 
-            // Buffer any empty headers
-            while this.pending_headers.front().map(|h| h.is_empty()).unwrap_or_default() {
+            // Buffer any empty headers, unless they follow a response that is being validated
+            while this.validation.is_none() &&
+                this.pending_headers.front().map(|h| h.is_empty()).unwrap_or_default()
+            {
                 let header = this.pending_headers.pop_front().unwrap();
                 this.buffer.push(BlockResponse::Empty(header));
             }
@@ -250,6 +321,7 @@ mod tests {
         bodies::test_utils::zip_blocks,
         test_utils::{generate_bodies, TestBodiesClient},
     };
+    use assert_matches::assert_matches;
     use reth_consensus::test_utils::TestConsensus;
     use reth_interfaces::test_utils::{generators, generators::random_header_range};
 
@@ -298,4 +370,36 @@ mod tests {
             (headers.into_iter().filter(|h| !h.is_empty()).count() as u64 + 1) / 2
         );
     }
+
+    /// Check that headers are put back in order if a body fails validation.
+    #[tokio::test]
+    async fn request_puts_back_headers_on_invalid_body() {
+        let (headers, mut bodies) = generate_bodies(0..=19);
+
+        let consensus = TestConsensus::default();
+        consensus.set_fail_validation(true);
+        let mut fut = BodiesRequestFuture::new(
+            Arc::new(TestBodiesClient::default()),
+            Arc::new(consensus),
+            BodyDownloaderMetrics::default(),
+        );
+        fut.pending_headers = VecDeque::from(headers.clone());
+
+        let response = headers
+            .iter()
+            .filter(|header| !header.is_empty())
+            .map(|header| bodies.remove(&header.hash()).unwrap())
+            .collect::<Vec<_>>();
+        let responses = fut.take_responses(response);
+        let validated = spawn_validation(fut.consensus.clone(), responses).await.unwrap();
+        assert_matches!(
+            fut.try_buffer_blocks(validated),
+            Err(DownloadError::BodyValidation { .. })
+        );
+
+        // only the empty headers preceding the first full one are buffered
+        let first_full = headers.iter().position(|header| !header.is_empty()).unwrap();
+        assert_eq!(fut.buffer.len(), first_full);
+        assert_eq!(fut.pending_headers, headers[first_full..].to_vec());
+    }
 }
@@ -76,6 +76,14 @@ impl PeersHandle {
 
         rx.await.unwrap_or_default()
     }
+
+    /// Replaces the maximum number of inbound and outbound connections and concurrent outbound
+    /// dials with the limits of the given [`ConnectionInfo`].
+    ///
+    /// Established connections exceeding the new limits are kept.
+    pub fn set_connection_limits(&self, limits: ConnectionInfo) {
+        self.send(PeerCommand::SetConnectionLimits(limits));
+    }
 }
 
 /// Maintains the state of _all_ the peers known to the network.
@@ -872,6 +880,9 @@ impl PeersManager {
                     PeerCommand::GetPeers(tx) => {
                         let _ = tx.send(self.iter_peers().collect());
                     }
+                    PeerCommand::SetConnectionLimits(limits) => {
+                        self.connection_info.set_limits(&limits)
+                    }
                 }
             }
 
@@ -946,6 +957,13 @@ pub struct ConnectionInfo {
 // === impl ConnectionInfo ===
 
 impl ConnectionInfo {
+    /// Copies the connection limits of `other`, keeping the connection counters.
+    fn set_limits(&mut self, other: &ConnectionInfo) {
+        self.max_outbound = other.max_outbound;
+        self.max_inbound = other.max_inbound;
+        self.max_concurrent_outbound_dials = other.max_concurrent_outbound_dials;
+    }
+
     ///  Returns `true` if there's still capacity for a new outgoing connection.
     fn has_out_capacity(&self) -> bool {
         self.num_pending_out < self.max_concurrent_outbound_dials &&
@@ -1209,6 +1227,8 @@ pub(crate) enum PeerCommand {
     GetPeer(PeerId, oneshot::Sender<Option<Peer>>),
     /// Get node information on all peers
     GetPeers(oneshot::Sender<Vec<NodeRecord>>),
+    /// Replace the connection limits
+    SetConnectionLimits(ConnectionInfo),
 }
 
 /// Actions the peer manager can trigger.
@@ -2782,6 +2802,31 @@ mod tests {
         assert_eq!(dials, peer_manager.connection_info.max_concurrent_outbound_dials);
     }
 
+    #[tokio::test]
+    async fn test_set_connection_limits() {
+        let config = PeersConfig::default();
+        let mut peer_manager = PeersManager::new(config);
+        let handle = peer_manager.handle();
+        let ip = IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2));
+        let socket_addr = SocketAddr::new(ip, 8008);
+        for _ in 0..10 {
+            peer_manager.add_peer(PeerId::random(), socket_addr, None);
+        }
+
+        peer_manager.queued_actions.clear();
+
+        handle.set_connection_limits(PeersConfig::default().with_max_outbound(3).connection_info);
+        poll_fn(|cx| {
+            let _ = peer_manager.poll(cx);
+            Poll::Ready(())
+        })
+        .await;
+        assert_eq!(peer_manager.connection_info.max_outbound, 3);
+
+        peer_manager.fill_outbound_slots();
+        assert_eq!(peer_manager.connection_info.num_pending_out, 3);
+    }
+
     #[tokio::test]
     async fn test_max_num_of_pending_dials() {
         let config = PeersConfig::default();
@@ -4,11 +4,12 @@
 
 use crate::{
     components::{ComponentsBuilder, NodeComponents, NodeComponentsBuilder, PoolBuilder},
+    config_reload::ConfigReloader,
     exex::BoxedLaunchExEx,
     hooks::NodeHooks,
     node::FullNode,
     rpc::{RethRpcServerHandles, RpcContext, RpcHooks},
-    Node, NodeHandle,
+    self_check, Node, NodeHandle,
 };
 use eyre::Context;
 use futures::{future, future::Either, stream, stream_select, Future, StreamExt};
@@ -19,7 +20,8 @@ use reth_beacon_consensus::{
     BeaconConsensus, BeaconConsensusEngine,
 };
 use reth_blockchain_tree::{
-    BlockchainTree, BlockchainTreeConfig, ShareableBlockchainTree, TreeExternals,
+    BlockchainTree, BlockchainTreeConfig, ChainSpill, ExecutionCache, ShareableBlockchainTree,
+    StateRootCapture, TreeExternals,
 };
 use reth_config::config::EtlConfig;
 use reth_consensus::Consensus;
@@ -29,6 +31,7 @@ use reth_db::{
     test_utils::{create_test_rw_db, TempDatabase},
     DatabaseEnv,
 };
+use reth_evm::{precompile::CustomPrecompiles, ConfigureEvm};
 use reth_exex::{ExExContext, ExExHandle, ExExManager, ExExManagerHandle};
 use reth_interfaces::p2p::either::EitherDownloader;
 use reth_network::{NetworkBuilder, NetworkConfig, NetworkEvents, NetworkHandle};
@@ -39,6 +42,7 @@ use reth_node_core::{
     cli::config::{PayloadBuilderConfig, RethRpcConfig, RethTransactionPoolConfig},
     dirs::{ChainPath, DataDirPath, MaybePlatformPath},
     engine_api_store::EngineApiStore,
+    engine_journal::EngineJournal,
     engine_skip_fcu::EngineApiSkipFcu,
     exit::NodeExitFuture,
     init::init_genesis,
@@ -49,7 +53,9 @@ use reth_node_core::{
 use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
 use reth_primitives::{constants::eip4844::MAINNET_KZG_TRUSTED_SETUP, format_ether, ChainSpec};
 use reth_provider::{
-    providers::BlockchainProvider, CanonStateSubscriptions, ChainSpecProvider, ProviderFactory,
+    providers::BlockchainProvider, AddressTransactionsReader, AddressTransactionsWriter,
+    CanonStateSubscriptions, ChainSpecProvider, LogIndexReader, LogIndexWriter, ProviderFactory,
+    SenderNoncesReader, SenderNoncesWriter, TokenTransfersReader, TokenTransfersWriter,
 };
 use reth_prune::PrunerBuilder;
 use reth_revm::EvmProcessorFactory;
@@ -57,8 +63,8 @@ use reth_rpc_engine_api::EngineApi;
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, error, info};
-use reth_transaction_pool::{PoolConfig, TransactionPool};
-use std::{cmp::max, str::FromStr, sync::Arc, thread::available_parallelism};
+use reth_transaction_pool::{maintain::MaintainPoolConfig, PoolConfig, TransactionPool};
+use std::{cmp::max, str::FromStr, sync::Arc, thread::available_parallelism, time::Duration};
 use tokio::sync::{mpsc::unbounded_channel, oneshot};
 
 /// The builtin provider type of the reth node.
@@ -478,6 +484,17 @@ where
     > {
         // get config from file
         let reth_config = self.load_config(&data_dir)?;
+        let config_reloader = ConfigReloader::load(
+            self.config.config.clone().unwrap_or_else(|| data_dir.config_path()),
+        )?;
+
+        // the log filter of the config file only applies if the cli argument is not set
+        if let Some(filter) = &reth_config.log.stdout_filter {
+            reth_tracing::reload_stdout_filter(Some(filter))?;
+        }
+        config_reloader.on_change("log.stdout_filter", |reth_config| {
+            reth_tracing::reload_stdout_filter(reth_config.log.stdout_filter.as_deref())
+        });
 
         let Self {
             config,
@@ -502,7 +519,8 @@ where
             Arc::clone(&config.chain),
             data_dir.static_files_path(),
         )?
-        .with_static_files_metrics();
+        .with_static_files_metrics()
+        .with_static_files_reader_mode(config.static_files.reader_mode());
         info!(target: "reth::cli", "Database opened");
 
         let prometheus_handle = config.install_prometheus_recorder()?;
@@ -534,21 +552,71 @@ where
         executor.spawn_critical("stages metrics listener task", sync_metrics_listener);
 
         let prune_config = config.prune_config()?.or_else(|| reth_config.prune.clone());
+        if let Some(prune_config) = &prune_config {
+            if !config.pruning.allow_tightening {
+                crate::setup::ensure_pruning_not_tightened(
+                    &provider_factory,
+                    &prune_config.segments,
+                )?;
+            }
+        }
 
         // Configure the blockchain tree for the node
-        let evm_config = types.evm_config();
-        let tree_config = BlockchainTreeConfig::default();
-        let tree_externals = TreeExternals::new(
+        let evm_config = types
+            .evm_config()
+            .with_custom_precompiles(CustomPrecompiles::from_chain_spec(&config.chain)?);
+
+        if let Some(blocks) = config.debug.startup_check_blocks {
+            let mismatches =
+                self_check::check_recent_blocks(&provider_factory, evm_config.clone(), blocks)?;
+            if !mismatches.is_empty() && config.debug.startup_check_fail_fast {
+                eyre::bail!("Startup self-check found {} mismatches", mismatches.len())
+            }
+        }
+        let default_tree_config = BlockchainTreeConfig::default();
+        let mut tree_config = BlockchainTreeConfig::new(
+            config.engine.max_reorg_depth,
+            // has to be greater than the `max_reorg_depth`
+            config.engine.max_reorg_depth + 1,
+            default_tree_config.num_of_additional_canonical_block_hashes(),
+            default_tree_config.max_unconnected_blocks(),
+        )
+        .with_reorg_history_retention(config.engine.reorg_history_retention)
+        .with_state_root_strategy(config.engine.state_root_strategy)
+        .with_detailed_state_root_metrics(config.engine.state_root_task_metrics);
+        if let Some(interval) = config.engine.trie_checkpoint_interval {
+            if config.engine.max_checkpoint_depth < config.engine.max_reorg_depth {
+                eyre::bail!(
+                    "--engine.max-checkpoint-depth must be at least --engine.max-reorg-depth"
+                );
+            }
+            tree_config =
+                tree_config.with_trie_checkpoints(interval, config.engine.max_checkpoint_depth);
+        }
+        let mut tree_externals = TreeExternals::new(
             provider_factory.clone(),
             consensus.clone(),
             EvmProcessorFactory::new(config.chain.clone(), evm_config.clone()),
         );
-        let tree = BlockchainTree::new(
+        let execution_cache = config.engine.prewarm.then(ExecutionCache::default);
+        if let Some(execution_cache) = &execution_cache {
+            tree_externals = tree_externals.with_execution_cache(execution_cache.clone());
+        }
+        if let Some(dir) = config.debug.state_root_capture.clone() {
+            tree_externals = tree_externals.with_state_root_capture(StateRootCapture::new(dir));
+        }
+        for hook in config.engine.invalid_block_hooks.iter().cloned() {
+            tree_externals = tree_externals.with_invalid_block_hook(hook.into_hook());
+        }
+        let mut tree = BlockchainTree::new(
             tree_externals,
             tree_config,
             prune_config.as_ref().map(|config| config.segments.clone()),
         )?
         .with_sync_metrics_tx(sync_metrics_tx.clone());
+        if let Some(depth) = config.engine.spill_depth {
+            tree = tree.with_chain_spill(ChainSpill::new(data_dir.tree_spill_path(), depth)?);
+        }
 
         let canon_state_notification_sender = tree.canon_state_notification_sender();
         let blockchain_tree = Arc::new(ShareableBlockchainTree::new(tree));
@@ -595,13 +663,177 @@ where
             payload_builder: payload_builder.clone(),
             executor: executor.clone(),
         };
+        // apply the peer limits of a reloaded config, the cli arguments take precedence
+        let peers_handle = network.peers_handle().clone();
+        let (max_inbound, max_outbound) =
+            (config.network.max_inbound_peers, config.network.max_outbound_peers);
+        config_reloader.on_change("peers.connection_info", move |reth_config| {
+            let peers_config = reth_config
+                .peers
+                .clone()
+                .with_max_inbound_opt(max_inbound)
+                .with_max_outbound_opt(max_outbound);
+            peers_handle.set_connection_limits(peers_config.connection_info);
+            Ok(())
+        });
+
+        if let Some(execution_cache) = execution_cache {
+            debug!(target: "reth::cli", "Spawning prewarm task");
+            executor.spawn_critical(
+                "prewarm task",
+                crate::prewarm::prewarm_execution_cache(
+                    provider_factory.clone(),
+                    transaction_pool.clone(),
+                    evm_config.clone(),
+                    execution_cache,
+                    Duration::from_millis(config.engine.prewarm_budget),
+                    blockchain_db.subscribe_to_canonical_state(),
+                ),
+            );
+        }
+
         debug!(target: "reth::cli", "calling on_component_initialized hook");
         on_component_initialized.on_event(node_components.clone())?;
 
+        // install the built-in exexs, the index of a disabled exex is removed
+        #[cfg_attr(
+            not(any(
+                feature = "token-transfers",
+                feature = "sender-nonces",
+                feature = "log-index",
+                feature = "address-transactions"
+            )),
+            allow(unused_mut)
+        )]
+        let mut installed_exexs = self.state.exexs;
+        if config.exex.token_transfers_enabled() {
+            #[cfg(feature = "token-transfers")]
+            {
+                use reth_exex::{
+                    token_transfers::{TokenTransfersIndex, TOKEN_TRANSFERS_EXEX_ID},
+                    IndexExEx,
+                };
+
+                let provider_factory = provider_factory.clone();
+                installed_exexs.push((
+                    TOKEN_TRANSFERS_EXEX_ID.to_string(),
+                    Box::new(
+                        move |ctx: ExExContext<
+                            FullNodeComponentsAdapter<
+                                FullNodeTypesAdapter<Types, DB, RethFullProviderType<DB>>,
+                                Components::Pool,
+                            >,
+                        >| async move {
+                            eyre::Ok(
+                                IndexExEx::new(ctx, provider_factory, TokenTransfersIndex).run(),
+                            )
+                        },
+                    ),
+                ));
+            }
+        } else if provider_factory.token_transfers_checkpoint()?.is_some() {
+            info!(target: "reth::cli", "Token transfers index is disabled, removing it");
+            let provider = provider_factory.provider_rw()?;
+            provider.remove_token_transfers()?;
+            provider.commit()?;
+        }
+        if config.exex.sender_nonces_enabled() {
+            #[cfg(feature = "sender-nonces")]
+            {
+                use reth_exex::{
+                    sender_nonces::{SenderNoncesIndex, SENDER_NONCES_EXEX_ID},
+                    IndexExEx,
+                };
+
+                let provider_factory = provider_factory.clone();
+                installed_exexs.push((
+                    SENDER_NONCES_EXEX_ID.to_string(),
+                    Box::new(
+                        move |ctx: ExExContext<
+                            FullNodeComponentsAdapter<
+                                FullNodeTypesAdapter<Types, DB, RethFullProviderType<DB>>,
+                                Components::Pool,
+                            >,
+                        >| async move {
+                            eyre::Ok(IndexExEx::new(ctx, provider_factory, SenderNoncesIndex).run())
+                        },
+                    ),
+                ));
+            }
+        } else if provider_factory.sender_nonces_checkpoint()?.is_some() {
+            info!(target: "reth::cli", "Sender nonces index is disabled, removing it");
+            let provider = provider_factory.provider_rw()?;
+            provider.remove_sender_nonces()?;
+            provider.commit()?;
+        }
+        if config.exex.log_index_enabled() {
+            #[cfg(feature = "log-index")]
+            {
+                use reth_exex::{
+                    log_index::{LogIndex, LOG_INDEX_EXEX_ID},
+                    IndexExEx,
+                };
+
+                let provider_factory = provider_factory.clone();
+                installed_exexs.push((
+                    LOG_INDEX_EXEX_ID.to_string(),
+                    Box::new(
+                        move |ctx: ExExContext<
+                            FullNodeComponentsAdapter<
+                                FullNodeTypesAdapter<Types, DB, RethFullProviderType<DB>>,
+                                Components::Pool,
+                            >,
+                        >| async move {
+                            eyre::Ok(IndexExEx::new(ctx, provider_factory, LogIndex).run())
+                        },
+                    ),
+                ));
+            }
+        } else if provider_factory.log_index_checkpoint()?.is_some() {
+            info!(target: "reth::cli", "Log index is disabled, removing it");
+            let provider = provider_factory.provider_rw()?;
+            provider.remove_log_index()?;
+            provider.commit()?;
+        }
+        if config.exex.address_transactions_enabled() {
+            #[cfg(feature = "address-transactions")]
+            {
+                use reth_exex::{
+                    address_transactions::{
+                        AddressTransactionsIndex, ADDRESS_TRANSACTIONS_EXEX_ID,
+                    },
+                    IndexExEx,
+                };
+
+                let provider_factory = provider_factory.clone();
+                installed_exexs.push((
+                    ADDRESS_TRANSACTIONS_EXEX_ID.to_string(),
+                    Box::new(
+                        move |ctx: ExExContext<
+                            FullNodeComponentsAdapter<
+                                FullNodeTypesAdapter<Types, DB, RethFullProviderType<DB>>,
+                                Components::Pool,
+                            >,
+                        >| async move {
+                            eyre::Ok(
+                                IndexExEx::new(ctx, provider_factory, AddressTransactionsIndex)
+                                    .run(),
+                            )
+                        },
+                    ),
+                ));
+            }
+        } else if provider_factory.address_transactions_checkpoint()?.is_some() {
+            info!(target: "reth::cli", "Address transactions index is disabled, removing it");
+            let provider = provider_factory.provider_rw()?;
+            provider.remove_address_transactions()?;
+            provider.commit()?;
+        }
+
         // spawn exexs
-        let mut exex_handles = Vec::with_capacity(self.state.exexs.len());
-        let mut exexs = Vec::with_capacity(self.state.exexs.len());
-        for (id, exex) in self.state.exexs {
+        let mut exex_handles = Vec::with_capacity(installed_exexs.len());
+        let mut exexs = Vec::with_capacity(installed_exexs.len());
+        for (id, exex) in installed_exexs {
             // create a new exex handle
             let (handle, events, notifications) = ExExHandle::new(id.clone());
             exex_handles.push(handle);
@@ -696,6 +928,23 @@ where
             consensus_engine_rx = engine_intercept_rx;
         };
 
+        if let Some(journal_path) = config.debug.engine_journal.clone() {
+            debug!(target: "reth::cli", "spawning engine journal");
+            let (engine_journal_tx, engine_journal_rx) = unbounded_channel();
+            let mut engine_journal = EngineJournal::new(journal_path);
+            if let Some(file_size) = config.debug.engine_journal_file_size {
+                engine_journal = engine_journal.with_max_file_size_mb(file_size);
+            }
+            if let Some(max_files) = config.debug.engine_journal_max_files {
+                engine_journal = engine_journal.with_max_files(max_files);
+            }
+            executor.spawn_critical(
+                "engine journal interceptor",
+                engine_journal.intercept(consensus_engine_rx, engine_journal_tx),
+            );
+            consensus_engine_rx = engine_journal_rx;
+        }
+
         let max_block = config.max_block(&network_client, provider_factory.clone()).await?;
         let mut hooks = EngineHooks::new();
 
@@ -849,8 +1098,9 @@ where
         let engine_api = EngineApi::new(
             blockchain_db.clone(),
             config.chain.clone(),
-            beacon_engine_handle,
+            beacon_engine_handle.clone(),
             payload_builder.into(),
+            transaction_pool.clone(),
             Box::new(executor.clone()),
         );
         info!(target: "reth::cli", "Engine API handler initialized");
@@ -867,17 +1117,68 @@ where
         let (rpc_server_handles, mut rpc_registry) = crate::rpc::launch_rpc_servers(
             node_components.clone(),
             engine_api,
+            beacon_engine_handle,
             &config,
             jwt_secret,
             rpc,
+            &config_reloader,
         )
         .await?;
 
+        // the gas price oracle settings of the config file apply to the cli arguments not set
+        let eth_api = rpc_registry.eth_api();
+        if reth_config.rpc.gas_price_oracle != Default::default() {
+            eth_api.gas_oracle().set_config(
+                config
+                    .rpc
+                    .gas_price_oracle
+                    .gas_price_oracle_config_with(&reth_config.rpc.gas_price_oracle),
+            );
+        }
+        let gas_price_oracle_args = config.rpc.gas_price_oracle;
+        config_reloader.on_change("rpc.gas_price_oracle", move |reth_config| {
+            eth_api.gas_oracle().set_config(
+                gas_price_oracle_args
+                    .gas_price_oracle_config_with(&reth_config.rpc.gas_price_oracle),
+            );
+            Ok(())
+        });
+
+        #[cfg(unix)]
+        executor.spawn(Box::pin(config_reloader.reload_on_sighup()));
+
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             rpc_registry.eth_api().with_dev_accounts();
         }
 
+        if let Some(keystore) = &config.rpc.keystore {
+            let passphrase = config.rpc.keystore_passphrase()?;
+            let accounts = rpc_registry.eth_api().with_keystore(
+                keystore,
+                passphrase.as_deref(),
+                config.rpc.keystore_unlock_duration,
+            )?;
+            info!(target: "reth::cli", ?keystore, accounts, "Loaded keystore accounts");
+        }
+
+        if let Some(url) = &config.rpc.signer_remote_url {
+            let auth_token = config.rpc.signer_remote_auth_token()?;
+            let api = config.rpc.signer_remote_api;
+            let accounts =
+                rpc_registry.eth_api().with_remote_signer(url, api, auth_token.as_deref()).await?;
+            info!(target: "reth::cli", %url, %api, accounts, "Connected to remote signer");
+        }
+
+        #[cfg(feature = "hardware-wallets")]
+        if let Some(wallet) = config.rpc.signer_hardware {
+            let accounts = rpc_registry
+                .eth_api()
+                .with_hardware_wallet(wallet, config.rpc.signer_hardware_accounts)
+                .await?;
+            info!(target: "reth::cli", %wallet, ?accounts, "Connected to hardware wallet");
+        }
+
         // Run consensus engine to completion
         let (tx, rx) = oneshot::channel();
         info!(target: "reth::cli", "Starting consensus engine");
@@ -1296,6 +1597,11 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().txpool.pool_config()
     }
 
+    /// Returns the config of the transaction pool maintenance task of the node.
+    pub fn maintain_pool_config(&self) -> MaintainPoolConfig {
+        self.config().txpool.maintain_pool_config()
+    }
+
     /// Loads `MAINNET_KZG_TRUSTED_SETUP`.
     pub fn kzg_settings(&self) -> eyre::Result<Arc<KzgSettings>> {
         Ok(Arc::clone(&MAINNET_KZG_TRUSTED_SETUP))
@@ -0,0 +1,196 @@
+//! Support for reloading the configuration file while the node is running.
+
+use eyre::Context;
+use jsonrpsee::{
+    core::{async_trait, RpcResult},
+    types::{error::INTERNAL_ERROR_CODE, ErrorObject},
+};
+use parking_lot::Mutex;
+use reth_config::Config;
+use reth_node_core::rpc::{api::ConfigReloadApiServer, types::ConfigReload};
+use reth_tracing::tracing::{info, warn};
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Applies the settings of a reloaded configuration.
+type ApplySettings = Box<dyn Fn(&Config) -> eyre::Result<()> + Send + Sync>;
+
+/// Reloads a resource outside of the configuration file, returning `true` if it changed.
+type ReloadResource = Box<dyn Fn() -> eyre::Result<bool> + Send + Sync>;
+
+/// Re-reads the configuration file of the node and applies the changed settings that can be
+/// changed while the node is running.
+///
+/// The components of the node register a handler for the settings they can apply with
+/// [ConfigReloader::on_change]. Changed settings without a handler are reported as requiring a
+/// restart.
+///
+/// A reload is triggered by `admin_reloadConfig` or, on unix, by a `SIGHUP`, see
+/// [ConfigReloader::reload_on_sighup].
+#[derive(Clone)]
+pub struct ConfigReloader {
+    inner: Arc<ConfigReloaderInner>,
+}
+
+struct ConfigReloaderInner {
+    /// The path of the configuration file.
+    path: PathBuf,
+    /// The last loaded configuration.
+    config: Mutex<Config>,
+    /// The handlers of the settings, by the key of the settings.
+    settings: Mutex<Vec<(&'static str, ApplySettings)>>,
+    /// The handlers of the resources outside of the configuration file, by the reported key.
+    resources: Mutex<Vec<(&'static str, ReloadResource)>>,
+}
+
+impl ConfigReloader {
+    /// Loads the configuration file at the given path.
+    pub fn load(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let config = load_config(&path)?;
+        Ok(Self {
+            inner: Arc::new(ConfigReloaderInner {
+                path,
+                config: Mutex::new(config),
+                settings: Default::default(),
+                resources: Default::default(),
+            }),
+        })
+    }
+
+    /// Returns the path of the configuration file.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Registers a handler for the settings under the given key, in dotted notation, e.g.
+    /// `peers.connection_info`.
+    ///
+    /// The handler is called with the reloaded configuration if any of the settings changed.
+    pub fn on_change<F>(&self, key: &'static str, apply: F)
+    where
+        F: Fn(&Config) -> eyre::Result<()> + Send + Sync + 'static,
+    {
+        self.inner.settings.lock().push((key, Box::new(apply)));
+    }
+
+    /// Registers a resource outside of the configuration file that is reloaded with the
+    /// configuration, e.g. the API keys file of the RPC server.
+    ///
+    /// The key is reported as applied if the handler returns `true`.
+    pub fn on_reload<F>(&self, key: &'static str, reload: F)
+    where
+        F: Fn() -> eyre::Result<bool> + Send + Sync + 'static,
+    {
+        self.inner.resources.lock().push((key, Box::new(reload)));
+    }
+
+    /// Re-reads the configuration file and applies the changed settings.
+    ///
+    /// If a handler fails, the error is returned and the changes are applied again on the next
+    /// reload.
+    ///
+    /// This reads files and blocks, see [ConfigReloader::reload_blocking] for async contexts.
+    pub fn reload(&self) -> eyre::Result<ConfigReload> {
+        let mut current = self.inner.config.lock();
+        let config = load_config(&self.inner.path)?;
+        let changed = current.changed_keys(&config);
+
+        let mut result = ConfigReload::default();
+        let settings = self.inner.settings.lock();
+        for (key, apply) in settings.iter() {
+            if changed.iter().any(|changed| overlaps(changed, key)) {
+                apply(&config).wrap_err_with(|| format!("Failed to apply {key}"))?;
+            }
+        }
+        for changed in changed {
+            if settings.iter().any(|(key, _)| is_under(&changed, key)) {
+                result.applied.push(changed);
+            } else {
+                result.requires_restart.push(changed);
+            }
+        }
+        drop(settings);
+
+        for (key, reload) in self.inner.resources.lock().iter() {
+            if reload().wrap_err_with(|| format!("Failed to reload {key}"))? {
+                result.applied.push(key.to_string());
+            }
+        }
+
+        *current = config;
+        Ok(result)
+    }
+
+    /// Runs [ConfigReloader::reload] on the blocking thread pool.
+    pub async fn reload_blocking(&self) -> eyre::Result<ConfigReload> {
+        let reloader = self.clone();
+        tokio::task::spawn_blocking(move || reloader.reload())
+            .await
+            .wrap_err("Configuration reload task failed")?
+    }
+
+    /// Reloads the configuration whenever the process receives a `SIGHUP`, logging the result.
+    #[cfg(unix)]
+    pub async fn reload_on_sighup(self) {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangup = match signal(SignalKind::hangup()) {
+            Ok(hangup) => hangup,
+            Err(err) => {
+                warn!(target: "reth::cli", %err, "Failed to listen for SIGHUP");
+                return
+            }
+        };
+
+        while hangup.recv().await.is_some() {
+            match self.reload_blocking().await {
+                Ok(ConfigReload { applied, requires_restart }) => {
+                    info!(target: "reth::cli", path = ?self.path(), ?applied, ?requires_restart, "Configuration reloaded");
+                }
+                Err(err) => {
+                    warn!(target: "reth::cli", path = ?self.path(), %err, "Failed to reload configuration");
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ConfigReloader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigReloader").field("path", &self.inner.path).finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl ConfigReloadApiServer for ConfigReloader {
+    /// Handler for `admin_reloadConfig`
+    async fn reload_config(&self) -> RpcResult<ConfigReload> {
+        self.reload_blocking()
+            .await
+            .map_err(|err| ErrorObject::owned(INTERNAL_ERROR_CODE, format!("{err:#}"), None::<()>))
+    }
+}
+
+/// Loads the configuration file, which must exist.
+///
+/// Unlike `confy`, this doesn't write a default configuration if the file is missing, e.g. because
+/// it was moved while the node is running.
+fn load_config(path: &Path) -> eyre::Result<Config> {
+    let contents = std::fs::read_to_string(path)
+        .wrap_err_with(|| format!("Could not read config file {path:?}"))?;
+    toml::from_str(&contents).wrap_err_with(|| format!("Could not parse config file {path:?}"))
+}
+
+/// Returns `true` if `key` is `prefix` or one of the settings under it.
+fn is_under(key: &str, prefix: &str) -> bool {
+    key.strip_prefix(prefix).map_or(false, |rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+/// Returns `true` if one of the keys contains the other.
+fn overlaps(a: &str, b: &str) -> bool {
+    is_under(a, b) || is_under(b, a)
+}
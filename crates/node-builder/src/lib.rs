@@ -21,9 +21,15 @@ pub mod components;
 mod builder;
 pub use builder::*;
 
+pub mod config_reload;
+
 mod handle;
 pub use handle::NodeHandle;
 
+mod prewarm;
+
+mod self_check;
+
 pub mod rpc;
 
 pub mod setup;
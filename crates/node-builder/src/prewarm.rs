@@ -0,0 +1,127 @@
+//! Prewarming of the execution cache with the best transactions of the pool.
+
+use reth_blockchain_tree::{CachedStateProvider, ExecutionCache};
+use reth_db::database::Database;
+use reth_interfaces::provider::{ProviderError, ProviderResult};
+use reth_node_api::ConfigureEvm;
+use reth_primitives::{revm::env::tx_env_with_recovered, SealedHeader};
+use reth_provider::{CanonStateNotifications, ChainSpecProvider, HeaderProvider, ProviderFactory};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    primitives::{BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, SpecId},
+    DatabaseCommit,
+};
+use reth_tracing::tracing::{debug, trace};
+use reth_transaction_pool::TransactionPool;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Prewarms the execution cache for the next payload on top of every new canonical block.
+///
+/// The best transactions of the pool are speculatively executed on top of the latest state until
+/// the `budget` is exhausted, so that the accounts, storage slots and bytecodes they read are
+/// already cached when the next payload, which likely includes them, is executed. The results of
+/// the executions are discarded.
+pub(crate) async fn prewarm_execution_cache<DB, Pool, EvmConfig>(
+    provider_factory: ProviderFactory<DB>,
+    pool: Pool,
+    evm_config: EvmConfig,
+    cache: ExecutionCache,
+    budget: Duration,
+    mut canon_state_notifications: CanonStateNotifications,
+) where
+    DB: Database + 'static,
+    Pool: TransactionPool + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    loop {
+        let head = match canon_state_notifications.recv().await {
+            Ok(notification) => notification.tip().header.clone(),
+            // only the latest canonical block is of interest
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return,
+        };
+
+        let prewarm = tokio::task::spawn_blocking({
+            let (provider_factory, pool, evm_config, cache) =
+                (provider_factory.clone(), pool.clone(), evm_config.clone(), cache.clone());
+            move || prewarm(&provider_factory, &pool, &evm_config, &cache, &head, budget)
+        });
+        match prewarm.await {
+            Ok(Ok(executed)) => {
+                trace!(target: "reth::prewarm", executed, cached = cache.len(), "Prewarmed cache")
+            }
+            Ok(Err(err)) => debug!(target: "reth::prewarm", %err, "Failed to prewarm cache"),
+            Err(err) => debug!(target: "reth::prewarm", %err, "Prewarm task failed"),
+        }
+    }
+}
+
+/// Executes the best transactions of the pool in a block on top of `head` until the `budget` is
+/// exhausted and returns the number of executed transactions.
+fn prewarm<DB, Pool, EvmConfig>(
+    provider_factory: &ProviderFactory<DB>,
+    pool: &Pool,
+    evm_config: &EvmConfig,
+    cache: &ExecutionCache,
+    head: &SealedHeader,
+    budget: Duration,
+) -> ProviderResult<usize>
+where
+    DB: Database,
+    Pool: TransactionPool,
+    EvmConfig: ConfigureEvm,
+{
+    let deadline = Instant::now() + budget;
+
+    // the next block is assumed to be in the next slot: 12s
+    let chain_spec = provider_factory.chain_spec();
+    let mut header = head.header().clone();
+    header.parent_hash = head.hash();
+    header.number += 1;
+    header.timestamp += 12;
+    header.base_fee_per_gas =
+        head.next_block_base_fee(chain_spec.base_fee_params_at_timestamp(header.timestamp));
+    header.excess_blob_gas = head.next_block_excess_blob_gas();
+    let total_difficulty = provider_factory
+        .header_td_by_number(head.number)?
+        .ok_or(ProviderError::TotalDifficultyNotFound(head.number))?;
+
+    let mut cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LATEST);
+    let mut block_env = BlockEnv::default();
+    EvmConfig::fill_cfg_and_block_env(
+        &mut cfg,
+        &mut block_env,
+        &chain_spec,
+        &header,
+        total_difficulty,
+    );
+
+    // the epoch must be read before the latest state is opened
+    let epoch = cache.epoch();
+    let state = CachedStateProvider::new(provider_factory.latest()?, cache.clone(), epoch);
+    let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+    let mut executed = 0;
+    for tx in pool.best_transactions() {
+        if Instant::now() >= deadline {
+            break
+        }
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            cfg.clone(),
+            block_env.clone(),
+            tx_env_with_recovered(&tx.to_recovered_transaction()),
+        );
+        let mut evm = evm_config.evm_with_env(&mut db, env);
+        // invalid transactions are skipped, the transactions that depend on the state changes of
+        // the executed ones are executed on top of them
+        if let Ok(result) = evm.transact() {
+            drop(evm);
+            db.commit(result.state);
+        }
+        executed += 1;
+    }
+
+    Ok(executed)
+}
@@ -1,21 +1,24 @@
 //! Builder support for rpc components.
 
-use futures::TryFutureExt;
+use crate::config_reload::ConfigReloader;
+use futures::{StreamExt, TryFutureExt};
+use reth_beacon_consensus::BeaconConsensusEngineHandle;
 use reth_network::NetworkHandle;
-use reth_node_api::FullNodeComponents;
+use reth_node_api::{BuiltPayload, FullNodeComponents};
 use reth_node_core::{
     cli::config::RethRpcConfig,
     node_config::NodeConfig,
     rpc::{
-        api::EngineApiServer,
+        api::{ConfigReloadApiServer, DebugRewindApiServer, EngineApiServer},
         builder::{
             auth::{AuthRpcModule, AuthServerHandle},
-            RethModuleRegistry, RpcModuleBuilder, RpcServerHandle, TransportRpcModules,
+            RethModuleRegistry, RethRpcModule, RpcModuleBuilder, RpcServerHandle,
+            TransportRpcModules,
         },
     },
 };
-use reth_payload_builder::PayloadBuilderHandle;
-use reth_rpc::JwtSecret;
+use reth_payload_builder::{Events, PayloadBuilderHandle};
+use reth_rpc::{eth::preconfirmations_task, DebugRewindApi, JwtSecret};
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, info};
 use std::{
@@ -250,9 +253,11 @@ impl<'a, Node: FullNodeComponents> RpcContext<'a, Node> {
 pub(crate) async fn launch_rpc_servers<Node, Engine>(
     node: Node,
     engine_api: Engine,
+    beacon_engine_handle: BeaconConsensusEngineHandle<Node::Engine>,
     config: &NodeConfig,
     jwt_secret: JwtSecret,
     hooks: RpcHooks<Node>,
+    config_reloader: &ConfigReloader,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node>)>
 where
     Node: FullNodeComponents + Clone,
@@ -272,8 +277,31 @@ where
         .with_executor(node.task_executor().clone())
         .with_evm_config(node.evm_config())
         .build_with_auth_server(module_config, engine_api);
+    modules.merge_if_module_configured(RethRpcModule::Admin, config_reloader.clone().into_rpc())?;
+    if config.rpc.rpc_unsafe_debug {
+        let rewind_api = DebugRewindApi::new(node.provider().clone(), beacon_engine_handle);
+        modules.merge_if_module_configured(RethRpcModule::Debug, rewind_api.into_rpc())?;
+    }
 
     let mut registry = RpcRegistry { registry };
+
+    // serve `reth_subscribePreconfirmations` from the better payloads built by the node
+    let payloads =
+        node.payload_builder().subscribe().await?.into_stream().filter_map(|event| async move {
+            match event {
+                Ok(Events::BetterPayload(payload)) => Some(payload.block().clone()),
+                _ => None,
+            }
+        });
+    let preconfirmations = registry.preconfirmations().clone();
+    let eth_api = registry.eth_api();
+    node.task_executor().spawn_critical(
+        "preconfirmations task",
+        Box::pin(async move {
+            preconfirmations_task(preconfirmations, eth_api, Box::pin(payloads)).await;
+        }),
+    );
+
     let ctx = RpcContext {
         node: node.clone(),
         config,
@@ -284,7 +312,22 @@ where
 
     extend_rpc_modules.extend_rpc_modules(ctx)?;
 
-    let server_config = config.rpc.rpc_server_config();
+    let server_config = config
+        .rpc
+        .rpc_server_config()
+        .with_response_cache(
+            config.rpc.response_cache_config().map(|config| registry.response_cache(config)),
+        )
+        .with_graphql(config.rpc.graphql_config().map(|config| registry.graphql(config)));
+    #[cfg(feature = "rest")]
+    let server_config =
+        server_config.with_rest(config.rpc.rest_config().map(|config| registry.rest(config)));
+    if let Some(api_keys) = server_config.api_keys().cloned() {
+        config_reloader.on_reload("rpc.api_keys", move || Ok(api_keys.reload()?));
+    }
+    if let Some(rate_limiter) = server_config.rate_limiter().cloned() {
+        config_reloader.on_reload("rpc.rate_limits", move || Ok(rate_limiter.reload()?));
+    }
     let launch_rpc = modules.clone().start_server(server_config).map_ok(|handle| {
         if let Some(url) = handle.ipc_endpoint() {
             info!(target: "reth::cli", url=%url, "RPC IPC server started");
@@ -0,0 +1,106 @@
+//! Startup self-check that re-executes the most recent canonical blocks.
+
+use reth_db::database::Database;
+use reth_interfaces::provider::ProviderError;
+use reth_node_api::ConfigureEvm;
+use reth_primitives::{revm::compat::into_reth_acc, B256};
+use reth_provider::{
+    AccountReader, BlockNumReader, BlockReader, ChainSpecProvider, ExecutorFactory, HeaderProvider,
+    ProviderFactory, StateProvider, TransactionVariant,
+};
+use reth_revm::EvmProcessorFactory;
+use reth_tracing::tracing::{info, warn};
+use std::time::Instant;
+
+/// Re-executes the most recent `blocks` canonical blocks against the stored state and compares the
+/// receipts, the resulting state and the state root of the tip with the ones in the database.
+///
+/// Returns a description of every mismatch that was found, which indicates corruption of the
+/// database.
+pub(crate) fn check_recent_blocks<DB, EvmConfig>(
+    provider_factory: &ProviderFactory<DB>,
+    evm_config: EvmConfig,
+    blocks: u64,
+) -> eyre::Result<Vec<String>>
+where
+    DB: Database,
+    EvmConfig: ConfigureEvm + Clone + 'static,
+{
+    let provider = provider_factory.provider()?;
+    let tip = provider.best_block_number()?;
+    // the genesis block is never re-executed
+    let from = tip.saturating_sub(blocks) + 1;
+    if from > tip {
+        return Ok(Vec::new())
+    }
+
+    info!(target: "reth::cli", from, tip, "Re-executing recent blocks");
+    let start = Instant::now();
+    let executor_factory = EvmProcessorFactory::new(provider_factory.chain_spec(), evm_config);
+
+    let mut mismatches = Vec::new();
+    let mut tip_state = None;
+    for number in from..=tip {
+        let block = provider
+            .block_with_senders(number.into(), TransactionVariant::WithHash)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+        let td = provider
+            .header_td_by_number(number)?
+            .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+
+        let mut executor =
+            executor_factory.with_state(provider_factory.history_by_block_number(number - 1)?);
+        if let Err(err) = executor.execute_and_verify_receipt(&block, td) {
+            mismatches.push(format!("block {number}: {err}"));
+            continue
+        }
+        let output = executor.take_output_state();
+
+        // the re-executed changes must match the stored state after the block
+        let stored = provider_factory.history_by_block_number(number)?;
+        for (address, account) in &output.state().state {
+            let expected = account.info.clone().map(into_reth_acc);
+            if stored.basic_account(*address)? != expected {
+                mismatches.push(format!("block {number}: account {address} does not match"));
+            }
+            for (slot, value) in &account.storage {
+                let slot = B256::from(*slot);
+                if stored.storage(*address, slot)?.unwrap_or_default() != value.present_value {
+                    mismatches.push(format!(
+                        "block {number}: storage slot {slot} of account {address} does not match"
+                    ));
+                }
+            }
+        }
+
+        if number == tip {
+            tip_state = Some(output);
+        }
+    }
+
+    // applying the changes of the tip block on top of the stored state must not change its root
+    if let Some(output) = tip_state {
+        let expected = provider
+            .header_by_number(tip)?
+            .ok_or(ProviderError::HeaderNotFound(tip.into()))?
+            .state_root;
+        let state_root = output.hash_state_slow().state_root(provider.tx_ref())?;
+        if state_root != expected {
+            mismatches.push(format!(
+                "block {tip}: state root {state_root} does not match stored state root {expected}"
+            ));
+        }
+    }
+
+    info!(
+        target: "reth::cli",
+        blocks = tip - from + 1,
+        mismatches = mismatches.len(),
+        elapsed = ?start.elapsed(),
+        "Re-executed recent blocks"
+    );
+    for mismatch in &mismatches {
+        warn!(target: "reth::cli", %mismatch, "Startup self-check found a mismatch");
+    }
+    Ok(mismatches)
+}
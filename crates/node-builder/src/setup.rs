@@ -15,9 +15,9 @@ use reth_interfaces::p2p::{
 };
 use reth_node_core::{
     node_config::NodeConfig,
-    primitives::{BlockNumber, B256},
+    primitives::{BlockNumber, PruneMode, PruneModes, PruneSegment, B256},
 };
-use reth_provider::{HeaderSyncMode, ProviderFactory};
+use reth_provider::{BlockNumReader, HeaderSyncMode, ProviderFactory, PruneCheckpointReader};
 use reth_revm::stack::{Hook, InspectorStackConfig};
 use reth_stages::{
     prelude::DefaultStages,
@@ -203,3 +203,41 @@ where
 
     Ok(pipeline)
 }
+
+/// Ensures that the prune modes don't prune data that the prune modes of the previous runs kept,
+/// since pruned data can't be restored without resyncing.
+///
+/// The previous prune mode of a segment is the one stored in its prune checkpoint.
+pub fn ensure_pruning_not_tightened<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    prune_modes: &PruneModes,
+) -> eyre::Result<()> {
+    let provider = provider_factory.provider()?;
+    let tip = provider.best_block_number()?;
+    let segments = [
+        (PruneSegment::SenderRecovery, prune_modes.sender_recovery),
+        (PruneSegment::TransactionLookup, prune_modes.transaction_lookup),
+        (PruneSegment::Receipts, prune_modes.receipts),
+        (PruneSegment::AccountHistory, prune_modes.account_history),
+        (PruneSegment::StorageHistory, prune_modes.storage_history),
+    ];
+    for (segment, mode) in segments {
+        let (Some(mode), Some(checkpoint)) = (mode, provider.get_prune_checkpoint(segment)?) else {
+            continue
+        };
+        let previous = checkpoint.prune_mode;
+        // the lowest block kept by the previous prune mode
+        let lowest_kept = match previous {
+            PruneMode::Full => continue,
+            PruneMode::Distance(distance) => tip.saturating_sub(distance),
+            PruneMode::Before(block) => block,
+        };
+        if mode.should_prune(lowest_kept, tip) {
+            eyre::bail!(
+                "the prune mode {mode:?} of the {segment} segment prunes data kept by the previous \
+                 prune mode {previous:?}, pass --prune.allow-tightening to prune it anyway"
+            )
+        }
+    }
+    Ok(())
+}
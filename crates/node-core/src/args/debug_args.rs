@@ -68,6 +68,49 @@ pub struct DebugArgs {
     /// will be written to specified location.
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
+
+    /// The directory to journal engine API requests and their responses to.
+    ///
+    /// If specified, all `engine_forkchoiceUpdated` and `engine_newPayload` requests are recorded
+    /// with their responses and timestamps, so that they can be replayed with
+    /// `reth debug engine-replay`.
+    #[arg(long = "debug.engine-journal", help_heading = "Debug", value_name = "DIR")]
+    pub engine_journal: Option<PathBuf>,
+
+    /// The size in megabytes after which a new engine journal file is started.
+    ///
+    /// Defaults to 64.
+    #[arg(long = "debug.engine-journal-file-size", help_heading = "Debug", value_name = "MB")]
+    pub engine_journal_file_size: Option<u64>,
+
+    /// The number of engine journal files that are kept, older files are removed.
+    ///
+    /// Defaults to 8.
+    #[arg(long = "debug.engine-journal-max-files", help_heading = "Debug", value_name = "N")]
+    pub engine_journal_max_files: Option<usize>,
+
+    /// The directory to capture the inputs of mismatching state roots to.
+    ///
+    /// If the state root of a payload that extends the canonical chain does not match, the
+    /// changed state and the computed state roots are written to this directory, so that the
+    /// computation can be replayed with `reth debug replay-state-root`.
+    #[arg(long = "debug.state-root-capture", help_heading = "Debug", value_name = "DIR")]
+    pub state_root_capture: Option<PathBuf>,
+
+    /// Re-execute the most recent N canonical blocks on startup and compare the receipts, the
+    /// resulting state and the state root with the ones in the database.
+    ///
+    /// Mismatches indicate corruption of the database and are logged as warnings.
+    #[arg(long = "debug.startup-check-blocks", help_heading = "Debug", value_name = "N")]
+    pub startup_check_blocks: Option<u64>,
+
+    /// Abort startup if the startup self-check finds a mismatch, instead of only logging it.
+    #[arg(
+        long = "debug.startup-check-fail-fast",
+        help_heading = "Debug",
+        requires = "startup_check_blocks"
+    )]
+    pub startup_check_fail_fast: bool,
 }
 
 #[cfg(test)]
@@ -88,4 +131,49 @@ mod tests {
         let args = CommandParser::<DebugArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn test_parse_startup_check_args() {
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.startup-check-blocks",
+            "64",
+            "--debug.startup-check-fail-fast",
+        ])
+        .args;
+        assert_eq!(args.startup_check_blocks, Some(64));
+        assert!(args.startup_check_fail_fast);
+
+        let res =
+            CommandParser::<DebugArgs>::try_parse_from(["reth", "--debug.startup-check-fail-fast"]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_parse_engine_journal_args() {
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.engine-journal",
+            "/tmp/engine-journal",
+            "--debug.engine-journal-file-size",
+            "16",
+            "--debug.engine-journal-max-files",
+            "4",
+        ])
+        .args;
+        assert_eq!(args.engine_journal, Some(PathBuf::from("/tmp/engine-journal")));
+        assert_eq!(args.engine_journal_file_size, Some(16));
+        assert_eq!(args.engine_journal_max_files, Some(4));
+    }
+
+    #[test]
+    fn test_parse_state_root_capture_args() {
+        let args = CommandParser::<DebugArgs>::parse_from([
+            "reth",
+            "--debug.state-root-capture",
+            "/tmp/state-roots",
+        ])
+        .args;
+        assert_eq!(args.state_root_capture, Some(PathBuf::from("/tmp/state-roots")));
+    }
 }
@@ -0,0 +1,230 @@
+//! clap [Args](clap::Args) for engine configuration
+
+use clap::Args;
+use reth_blockchain_tree::{InvalidBlockHookKind, StateRootStrategy};
+
+/// Default number of blocks that can be reorged without trie checkpoints.
+pub const DEFAULT_MAX_REORG_DEPTH: u64 = 64;
+
+/// Default maximum depth below the canonical tip for which trie checkpoints are retained.
+pub const DEFAULT_MAX_CHECKPOINT_DEPTH: u64 = 8192;
+
+/// Default number of processed reorgs kept in the reorg history.
+pub const DEFAULT_REORG_HISTORY_RETENTION: u64 = 1000;
+
+/// Default time in milliseconds spent prewarming the execution cache after each canonical block.
+pub const DEFAULT_PREWARM_BUDGET_MILLIS: u64 = 100;
+
+/// Parameters for configuring the engine and its blockchain tree
+#[derive(Debug, Args, PartialEq, Eq, Clone)]
+#[command(next_help_heading = "Engine")]
+pub struct EngineArgs {
+    /// Number of blocks kept in memory that can be reorged.
+    #[arg(
+        long = "engine.max-reorg-depth",
+        default_value_t = DEFAULT_MAX_REORG_DEPTH,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub max_reorg_depth: u64,
+
+    /// Record a trie checkpoint of the canonical chain every N blocks.
+    ///
+    /// Blocks that fork off deeper than `--engine.max-reorg-depth` are validated on top of the
+    /// state reconstructed from the checkpoints instead of being rejected.
+    #[arg(
+        long = "engine.trie-checkpoint-interval",
+        value_name = "BLOCKS",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub trie_checkpoint_interval: Option<u64>,
+
+    /// Maximum depth below the canonical tip for which trie checkpoints are retained.
+    ///
+    /// This bounds the deepest reorg that can be handled with trie checkpoints.
+    #[arg(
+        long = "engine.max-checkpoint-depth",
+        value_name = "BLOCKS",
+        default_value_t = DEFAULT_MAX_CHECKPOINT_DEPTH,
+        requires = "trie_checkpoint_interval"
+    )]
+    pub max_checkpoint_depth: u64,
+
+    /// Number of processed reorgs kept in the reorg history, queryable with
+    /// `reth_getReorgHistory`.
+    ///
+    /// Set to 0 to disable recording reorgs.
+    #[arg(
+        long = "engine.reorg-history-retention",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_REORG_HISTORY_RETENTION
+    )]
+    pub reorg_history_retention: u64,
+
+    /// How the state root of payloads that extend the canonical chain is computed, `parallel` or
+    /// `synchronous`.
+    ///
+    /// A parallel state root that fails or does not match the payload is recomputed
+    /// synchronously before the payload is rejected.
+    #[arg(
+        long = "engine.state-root-strategy",
+        value_name = "STRATEGY",
+        default_value_t = StateRootStrategy::Parallel
+    )]
+    pub state_root_strategy: StateRootStrategy,
+
+    /// Record detailed metrics of the parallel state root computation of payloads, e.g. the number
+    /// of storage roots in flight and the time spent in each phase, to diagnose state root lag.
+    #[arg(long = "engine.state-root-task-metrics", default_value_t = false)]
+    pub state_root_task_metrics: bool,
+
+    /// Prewarm the state of the next payload by speculatively executing the best transactions of
+    /// the pool on top of every new canonical block.
+    ///
+    /// The accounts, storage slots and bytecodes that are read are cached for the execution of
+    /// the next payload.
+    #[arg(long = "engine.prewarm", default_value_t = false)]
+    pub prewarm: bool,
+
+    /// Maximum time in milliseconds spent executing pool transactions after each canonical block,
+    /// which bounds the CPU time of prewarming.
+    #[arg(
+        long = "engine.prewarm-budget",
+        value_name = "MILLIS",
+        default_value_t = DEFAULT_PREWARM_BUDGET_MILLIS,
+        requires = "prewarm"
+    )]
+    pub prewarm_budget: u64,
+
+    /// A hook that is invoked with every payload that fails execution or whose state root or
+    /// receipts root does not match, along with the state it read during execution.
+    ///
+    /// Either `disk=<DIR>` to write debug bundles to a directory, or `webhook=<URL>` to post them
+    /// as JSON. Can be specified multiple times.
+    #[arg(long = "engine.invalid-block-hook", value_name = "HOOK")]
+    pub invalid_block_hooks: Vec<InvalidBlockHookKind>,
+
+    /// Spill sidechains whose tip is more than N blocks behind the canonical tip to disk.
+    ///
+    /// Spilled sidechains are dropped from memory, along with their executed state, and reloaded
+    /// when they are extended or become canonical, which bounds memory usage during long periods
+    /// of non-finality.
+    #[arg(long = "engine.spill-depth", value_name = "BLOCKS")]
+    pub spill_depth: Option<u64>,
+}
+
+impl Default for EngineArgs {
+    fn default() -> Self {
+        Self {
+            max_reorg_depth: DEFAULT_MAX_REORG_DEPTH,
+            trie_checkpoint_interval: None,
+            max_checkpoint_depth: DEFAULT_MAX_CHECKPOINT_DEPTH,
+            reorg_history_retention: DEFAULT_REORG_HISTORY_RETENTION,
+            state_root_strategy: StateRootStrategy::Parallel,
+            state_root_task_metrics: false,
+            prewarm: false,
+            prewarm_budget: DEFAULT_PREWARM_BUDGET_MILLIS,
+            invalid_block_hooks: Vec::new(),
+            spill_depth: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_engine_args() {
+        let default_args = EngineArgs::default();
+        let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.max-reorg-depth",
+            "128",
+            "--engine.trie-checkpoint-interval",
+            "1000",
+        ])
+        .args;
+        assert_eq!(args.max_reorg_depth, 128);
+        assert_eq!(args.trie_checkpoint_interval, Some(1000));
+        assert_eq!(args.max_checkpoint_depth, DEFAULT_MAX_CHECKPOINT_DEPTH);
+        assert_eq!(args.reorg_history_retention, DEFAULT_REORG_HISTORY_RETENTION);
+
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.reorg-history-retention",
+            "0",
+        ])
+        .args;
+        assert_eq!(args.reorg_history_retention, 0);
+
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.state-root-strategy",
+            "synchronous",
+            "--engine.state-root-task-metrics",
+        ])
+        .args;
+        assert_eq!(args.state_root_strategy, StateRootStrategy::Synchronous);
+        assert!(args.state_root_task_metrics);
+
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.prewarm",
+            "--engine.prewarm-budget",
+            "250",
+        ])
+        .args;
+        assert!(args.prewarm);
+        assert_eq!(args.prewarm_budget, 250);
+
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.invalid-block-hook",
+            "disk=/tmp/invalid-blocks",
+            "--engine.invalid-block-hook",
+            "webhook=http://localhost:8080/invalid-block",
+        ])
+        .args;
+        assert_eq!(
+            args.invalid_block_hooks,
+            vec![
+                InvalidBlockHookKind::Disk("/tmp/invalid-blocks".into()),
+                InvalidBlockHookKind::Webhook(
+                    "http://localhost:8080/invalid-block".parse().unwrap()
+                ),
+            ]
+        );
+
+        let args =
+            CommandParser::<EngineArgs>::parse_from(["reth", "--engine.spill-depth", "32"]).args;
+        assert_eq!(args.spill_depth, Some(32));
+    }
+
+    #[test]
+    fn test_checkpoint_depth_requires_interval() {
+        let res = CommandParser::<EngineArgs>::try_parse_from([
+            "reth",
+            "--engine.max-checkpoint-depth",
+            "100",
+        ]);
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_prewarm_budget_requires_prewarm() {
+        let res =
+            CommandParser::<EngineArgs>::try_parse_from(["reth", "--engine.prewarm-budget", "250"]);
+        assert!(res.is_err());
+    }
+}
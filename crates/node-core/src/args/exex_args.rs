@@ -0,0 +1,117 @@
+//! clap [Args](clap::Args) for the built-in execution extensions
+
+use clap::Args;
+
+/// Parameters for enabling the built-in execution extensions (ExEx)
+#[derive(Debug, Args, PartialEq, Eq, Clone, Copy, Default)]
+#[command(next_help_heading = "ExEx")]
+pub struct ExExArgs {
+    /// Index the ERC-20 and ERC-721 token transfers of each address, queryable with
+    /// `reth_getTokenTransfers`.
+    ///
+    /// Blocks committed before the index was enabled are backfilled on startup. Disabling the
+    /// index removes it from the database.
+    #[cfg(feature = "token-transfers")]
+    #[arg(long = "exex.token-transfers")]
+    pub token_transfers: bool,
+
+    /// Index the canonical transactions by sender and nonce, used by
+    /// `ots_getTransactionBySenderAndNonce`.
+    ///
+    /// Blocks committed before the index was enabled are backfilled on startup. Disabling the
+    /// index removes it from the database.
+    #[cfg(feature = "sender-nonces")]
+    #[arg(long = "exex.sender-nonces")]
+    pub sender_nonces: bool,
+
+    /// Index the blocks with logs by address and first topic, used by `eth_getLogs` and log
+    /// filters to skip blocks without matching logs.
+    ///
+    /// Blocks committed before the index was enabled are backfilled on startup. Disabling the
+    /// index removes it from the database.
+    #[cfg(feature = "log-index")]
+    #[arg(long = "exex.log-index")]
+    pub log_index: bool,
+
+    /// Index the transactions sent from or to each address, including the contracts created by a
+    /// transaction, queryable with `reth_getTransactionsByAddress`.
+    ///
+    /// Blocks committed before the index was enabled are backfilled on startup. Disabling the
+    /// index removes it from the database.
+    #[cfg(feature = "address-transactions")]
+    #[arg(long = "exex.address-transactions")]
+    pub address_transactions: bool,
+}
+
+impl ExExArgs {
+    /// Returns whether the token transfers index is enabled.
+    #[cfg(feature = "token-transfers")]
+    pub const fn token_transfers_enabled(&self) -> bool {
+        self.token_transfers
+    }
+
+    /// Returns whether the token transfers index is enabled.
+    #[cfg(not(feature = "token-transfers"))]
+    pub const fn token_transfers_enabled(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the sender nonces index is enabled.
+    #[cfg(feature = "sender-nonces")]
+    pub const fn sender_nonces_enabled(&self) -> bool {
+        self.sender_nonces
+    }
+
+    /// Returns whether the sender nonces index is enabled.
+    #[cfg(not(feature = "sender-nonces"))]
+    pub const fn sender_nonces_enabled(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the log index is enabled.
+    #[cfg(feature = "log-index")]
+    pub const fn log_index_enabled(&self) -> bool {
+        self.log_index
+    }
+
+    /// Returns whether the log index is enabled.
+    #[cfg(not(feature = "log-index"))]
+    pub const fn log_index_enabled(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the address transactions index is enabled.
+    #[cfg(feature = "address-transactions")]
+    pub const fn address_transactions_enabled(&self) -> bool {
+        self.address_transactions
+    }
+
+    /// Returns whether the address transactions index is enabled.
+    #[cfg(not(feature = "address-transactions"))]
+    pub const fn address_transactions_enabled(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn exex_args_default_sanity_test() {
+        let args = CommandParser::<ExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ExExArgs::default());
+        assert!(!args.token_transfers_enabled());
+        assert!(!args.sender_nonces_enabled());
+        assert!(!args.log_index_enabled());
+        assert!(!args.address_transactions_enabled());
+    }
+}
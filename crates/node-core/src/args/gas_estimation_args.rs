@@ -0,0 +1,96 @@
+use clap::Args;
+use reth_rpc::eth::{GasEstimationConfig, DEFAULT_ESTIMATE_GAS_ERROR_TOLERANCE_BPS};
+
+/// Parameters to configure the binary search of `eth_estimateGas`
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Gas Estimation")]
+pub struct GasEstimationArgs {
+    /// Only use the gas limit of the request as the first guess instead of as the highest
+    /// possible gas limit.
+    ///
+    /// If the call fails with the gas limit of the request, the estimation continues with the gas
+    /// limit of the block.
+    #[arg(long = "rpc.estimate-gas.initial-guess-from-request")]
+    pub initial_guess_from_request: bool,
+
+    /// The allowed error of the estimate in basis points of the estimated gas
+    #[arg(long = "rpc.estimate-gas.error-tolerance", value_name = "BPS", default_value_t = DEFAULT_ESTIMATE_GAS_ERROR_TOLERANCE_BPS)]
+    pub error_tolerance_bps: u64,
+
+    /// The maximum number of executions of the binary search, unlimited by default.
+    ///
+    /// If the limit is reached, the lowest gas limit the call is known to succeed with is
+    /// returned.
+    #[arg(long = "rpc.estimate-gas.max-iterations", value_name = "COUNT")]
+    pub max_iterations: Option<u64>,
+
+    /// Disable trying the gas used plus the gas refund of the first execution before the binary
+    /// search
+    #[arg(long = "rpc.estimate-gas.disable-optimistic")]
+    pub disable_optimistic: bool,
+}
+
+impl GasEstimationArgs {
+    /// Returns a [GasEstimationConfig] from the arguments.
+    pub fn gas_estimation_config(&self) -> GasEstimationConfig {
+        let Self {
+            initial_guess_from_request,
+            error_tolerance_bps,
+            max_iterations,
+            disable_optimistic,
+        } = *self;
+        GasEstimationConfig::default()
+            .initial_guess_from_request(initial_guess_from_request)
+            .error_tolerance_bps(error_tolerance_bps)
+            .max_iterations(max_iterations)
+            .optimistic_first(!disable_optimistic)
+    }
+}
+
+impl Default for GasEstimationArgs {
+    fn default() -> Self {
+        Self {
+            initial_guess_from_request: false,
+            error_tolerance_bps: DEFAULT_ESTIMATE_GAS_ERROR_TOLERANCE_BPS,
+            max_iterations: None,
+            disable_optimistic: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn gas_estimation_args_default_sanity_test() {
+        let args = CommandParser::<GasEstimationArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, GasEstimationArgs::default());
+        assert_eq!(args.gas_estimation_config(), GasEstimationConfig::default());
+    }
+
+    #[test]
+    fn test_parse_gas_estimation_args() {
+        let args = CommandParser::<GasEstimationArgs>::parse_from([
+            "reth",
+            "--rpc.estimate-gas.error-tolerance",
+            "0",
+            "--rpc.estimate-gas.max-iterations",
+            "10",
+            "--rpc.estimate-gas.disable-optimistic",
+        ])
+        .args;
+        let config = args.gas_estimation_config();
+        assert_eq!(config.error_tolerance_bps, 0);
+        assert_eq!(config.max_iterations, Some(10));
+        assert!(!config.optimistic_first);
+        assert!(!config.initial_guess_from_request);
+    }
+}
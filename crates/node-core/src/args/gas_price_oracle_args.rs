@@ -1,6 +1,7 @@
 use crate::primitives::U256;
 use clap::Args;
-use reth_rpc::eth::gas_oracle::GasPriceOracleConfig;
+use reth_config::config::GasPriceOracleSettings;
+use reth_rpc::eth::gas_oracle::{GasPriceOracleConfig, GasPriceOracleStrategy};
 use reth_rpc_builder::constants::{
     DEFAULT_GAS_PRICE_BLOCKS, DEFAULT_GAS_PRICE_PERCENTILE, DEFAULT_IGNORE_GAS_PRICE,
     DEFAULT_MAX_GAS_PRICE,
@@ -25,20 +26,62 @@ pub struct GasPriceOracleArgs {
     /// The percentile of gas prices to use for the estimate
     #[arg(long = "gpo.percentile", default_value_t = DEFAULT_GAS_PRICE_PERCENTILE)]
     pub percentile: u32,
+
+    /// The algorithm the priority fee is suggested with.
+    ///
+    /// `percentile` uses the percentile of the recent blocks, `pool-pressure` raises it to the
+    /// tip that is required to be included in the next block if the pending transactions of the
+    /// pool don't fit into it.
+    #[arg(long = "gpo.strategy", value_name = "STRATEGY", default_value_t = GasPriceOracleStrategy::Percentile)]
+    pub strategy: GasPriceOracleStrategy,
 }
 
 impl GasPriceOracleArgs {
     /// Returns a [GasPriceOracleConfig] from the arguments.
     pub fn gas_price_oracle_config(&self) -> GasPriceOracleConfig {
-        let Self { blocks, ignore_price, max_price, percentile } = self;
+        let Self { blocks, ignore_price, max_price, percentile, strategy } = self;
         GasPriceOracleConfig {
             max_price: Some(U256::from(*max_price)),
             ignore_price: Some(U256::from(*ignore_price)),
             percentile: *percentile,
             blocks: *blocks,
+            strategy: *strategy,
             ..Default::default()
         }
     }
+
+    /// Returns a [GasPriceOracleConfig] from the arguments and the settings of the configuration
+    /// file.
+    ///
+    /// The arguments that are set to a non-default value take precedence over the settings.
+    pub fn gas_price_oracle_config_with(
+        &self,
+        settings: &GasPriceOracleSettings,
+    ) -> GasPriceOracleConfig {
+        let default = Self::default();
+        let config = self.gas_price_oracle_config();
+        GasPriceOracleConfig {
+            blocks: match settings.blocks {
+                Some(blocks) if self.blocks == default.blocks => blocks,
+                _ => config.blocks,
+            },
+            percentile: match settings.percentile {
+                Some(percentile) if self.percentile == default.percentile => percentile,
+                _ => config.percentile,
+            },
+            max_price: match settings.max_price {
+                Some(max_price) if self.max_price == default.max_price => Some(max_price),
+                _ => config.max_price,
+            },
+            ignore_price: match settings.ignore_price {
+                Some(ignore_price) if self.ignore_price == default.ignore_price => {
+                    Some(ignore_price)
+                }
+                _ => config.ignore_price,
+            },
+            ..config
+        }
+    }
 }
 
 impl Default for GasPriceOracleArgs {
@@ -48,6 +91,7 @@ impl Default for GasPriceOracleArgs {
             ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
             max_price: DEFAULT_MAX_GAS_PRICE.to(),
             percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+            strategy: GasPriceOracleStrategy::Percentile,
         }
     }
 }
@@ -73,6 +117,7 @@ mod tests {
                 ignore_price: DEFAULT_IGNORE_GAS_PRICE.to(),
                 max_price: DEFAULT_MAX_GAS_PRICE.to(),
                 percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+                strategy: GasPriceOracleStrategy::Percentile,
             }
         );
     }
@@ -83,4 +128,21 @@ mod tests {
         let args = CommandParser::<GasPriceOracleArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn gpo_args_take_precedence_over_settings() {
+        let settings = GasPriceOracleSettings {
+            blocks: Some(10),
+            percentile: Some(30),
+            max_price: None,
+            ignore_price: Some(U256::from(7)),
+        };
+        let args =
+            CommandParser::<GasPriceOracleArgs>::parse_from(["reth", "--gpo.blocks", "40"]).args;
+        let config = args.gas_price_oracle_config_with(&settings);
+        assert_eq!(config.blocks, 40);
+        assert_eq!(config.percentile, 30);
+        assert_eq!(config.max_price, Some(DEFAULT_MAX_GAS_PRICE));
+        assert_eq!(config.ignore_price, Some(U256::from(7)));
+    }
 }
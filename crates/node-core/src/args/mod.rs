@@ -20,6 +20,10 @@ pub use debug_args::DebugArgs;
 mod database_args;
 pub use database_args::DatabaseArgs;
 
+/// StaticFilesArgs struct for configuring how static files are read
+mod static_files_args;
+pub use static_files_args::{StaticFilesArgs, StaticFilesMmapAdvice, StaticFilesReader};
+
 /// LogArgs struct for configuring the logger
 mod log_args;
 pub use log_args::{ColorMode, LogArgs};
@@ -39,6 +43,10 @@ pub use stage_args::StageEnum;
 mod gas_price_oracle_args;
 pub use gas_price_oracle_args::GasPriceOracleArgs;
 
+/// Gas estimation related arguments
+mod gas_estimation_args;
+pub use gas_estimation_args::GasEstimationArgs;
+
 /// TxPoolArgs for configuring the transaction pool
 mod txpool_args;
 pub use txpool_args::TxPoolArgs;
@@ -51,6 +59,14 @@ pub use dev_args::DevArgs;
 mod pruning_args;
 pub use pruning_args::PruningArgs;
 
+/// EngineArgs for configuring the engine and its blockchain tree
+mod engine_args;
+pub use engine_args::EngineArgs;
+
+/// ExExArgs for enabling the built-in execution extensions
+mod exex_args;
+pub use exex_args::ExExArgs;
+
 pub mod utils;
 
 pub mod types;
@@ -6,10 +6,12 @@ use clap::{
     builder::{RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
-use reth_primitives::constants::{
-    ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION,
+use reth_primitives::{
+    constants::{ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION},
+    Address,
 };
-use std::{borrow::Cow, ffi::OsStr, time::Duration};
+use reth_transaction_pool::{PayloadInclusionPolicy, PayloadOrderingPolicy};
+use std::{borrow::Cow, ffi::OsStr, io, path::PathBuf, time::Duration};
 
 /// Parameters for configuring the Payload Builder
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
@@ -34,6 +36,31 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// The order in which the transactions of the pool are tried for inclusion in a payload.
+    ///
+    /// One of `effective-tip` (highest effective tip first), `sender-round-robin` (one
+    /// transaction of every sender per round) or `fifo` (in the order they were added to the
+    /// pool).
+    #[arg(long = "builder.ordering", default_value_t = PayloadOrderingPolicy::EffectiveTip, value_name = "POLICY")]
+    pub ordering: PayloadOrderingPolicy,
+
+    /// Comma separated list of senders whose transactions, e.g. system transactions, are tried
+    /// for inclusion before all other transactions.
+    #[arg(long = "builder.include-senders", value_name = "ADDRESSES", value_delimiter = ',')]
+    pub include_senders: Vec<Address>,
+
+    /// Comma separated list of senders whose transactions are never included in a payload.
+    #[arg(long = "builder.exclude-senders", value_name = "ADDRESSES", value_delimiter = ',')]
+    pub exclude_senders: Vec<Address>,
+
+    /// File with one address per line, transactions from or to these addresses are never
+    /// included in a payload.
+    ///
+    /// Empty lines and lines starting with `#` are ignored. The file is checked for changes every 10
+    /// seconds.
+    #[arg(long = "builder.blocklist", value_name = "FILE")]
+    pub blocklist: Option<PathBuf>,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -44,6 +71,10 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            ordering: PayloadOrderingPolicy::EffectiveTip,
+            include_senders: Vec::new(),
+            exclude_senders: Vec::new(),
+            blocklist: None,
         }
     }
 }
@@ -68,6 +99,21 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     fn max_payload_tasks(&self) -> usize {
         self.max_payload_tasks
     }
+
+    fn ordering(&self) -> PayloadOrderingPolicy {
+        self.ordering
+    }
+
+    fn inclusion_policy(&self) -> io::Result<PayloadInclusionPolicy> {
+        let policy = PayloadInclusionPolicy::new(
+            self.include_senders.iter().copied(),
+            self.exclude_senders.iter().copied(),
+        );
+        match &self.blocklist {
+            Some(path) => policy.with_blocklist(path),
+            None => Ok(policy),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -127,6 +173,51 @@ mod tests {
         .is_err());
     }
 
+    #[test]
+    fn test_args_with_ordering() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.ordering",
+            "sender-round-robin",
+        ])
+        .args;
+        assert_eq!(args.ordering, PayloadOrderingPolicy::SenderRoundRobin);
+
+        assert!(CommandParser::<PayloadBuilderArgs>::try_parse_from([
+            "reth",
+            "--builder.ordering",
+            "lifo"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_args_with_inclusion_policy() {
+        let system = Address::with_last_byte(1);
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.include-senders",
+            &system.to_string(),
+            "--builder.exclude-senders",
+            "0x0000000000000000000000000000000000000002,0x0000000000000000000000000000000000000003",
+        ])
+        .args;
+        assert_eq!(args.include_senders, vec![system]);
+        assert_eq!(
+            args.exclude_senders,
+            vec![Address::with_last_byte(2), Address::with_last_byte(3)]
+        );
+        assert!(args.inclusion_policy().unwrap().is_included(&system));
+
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.blocklist",
+            "/does/not/exist",
+        ])
+        .args;
+        assert!(args.inclusion_policy().is_err());
+    }
+
     #[test]
     fn test_default_extradata() {
         let extradata = default_extradata();
@@ -15,24 +15,41 @@ pub struct PruningArgs {
     /// This flag takes priority over pruning configuration in reth.toml.
     #[arg(long, default_value_t = false)]
     pub full: bool,
+
+    /// Number of most recent blocks to retain receipts and logs for when running a full node,
+    /// independent of other pruning. Logs of older blocks are not available via `eth_getLogs`.
+    ///
+    /// By default, a full node only retains the receipts of the most recent
+    /// [`MINIMUM_PRUNING_DISTANCE`] blocks and the receipts of the deposit contract.
+    #[arg(
+        long = "prune.receipts.distance",
+        value_name = "BLOCKS",
+        requires = "full",
+        value_parser = clap::value_parser!(u64).range(MINIMUM_PRUNING_DISTANCE..)
+    )]
+    pub receipts_distance: Option<u64>,
+
+    /// Allow pruning data that was kept by the pruning configuration of the previous runs.
+    ///
+    /// Pruned data can only be restored by resyncing the node, so by default the node refuses to
+    /// start if its pruning configuration prunes more than the previous one.
+    #[arg(long = "prune.allow-tightening", default_value_t = false)]
+    pub allow_tightening: bool,
 }
 
 impl PruningArgs {
     /// Returns pruning configuration.
     pub fn prune_config(&self, chain_spec: Arc<ChainSpec>) -> eyre::Result<Option<PruneConfig>> {
         Ok(if self.full {
-            Some(PruneConfig {
-                block_interval: 5,
-                segments: PruneModes {
-                    sender_recovery: Some(PruneMode::Full),
-                    transaction_lookup: None,
-                    receipts: chain_spec
+            // A receipts retention window overrides the contract logs filter.
+            let (receipts, receipts_log_filter) = match self.receipts_distance {
+                Some(distance) => (Some(PruneMode::Distance(distance)), Default::default()),
+                None => (
+                    chain_spec
                         .deposit_contract
                         .as_ref()
                         .map(|contract| PruneMode::Before(contract.block)),
-                    account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
-                    storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
-                    receipts_log_filter: ReceiptsLogPruneConfig(
+                    ReceiptsLogPruneConfig(
                         chain_spec
                             .deposit_contract
                             .as_ref()
@@ -40,6 +57,18 @@ impl PruningArgs {
                             .into_iter()
                             .collect(),
                     ),
+                ),
+            };
+
+            Some(PruneConfig {
+                block_interval: 5,
+                segments: PruneModes {
+                    sender_recovery: Some(PruneMode::Full),
+                    transaction_lookup: None,
+                    receipts,
+                    account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    receipts_log_filter,
                 },
             })
         } else {
@@ -66,4 +95,35 @@ mod tests {
         let args = CommandParser::<PruningArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn pruning_args_receipts_distance() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--full",
+            "--prune.receipts.distance",
+            "1000000",
+        ])
+        .args;
+        assert_eq!(args.receipts_distance, Some(1_000_000));
+
+        let config = args.prune_config(reth_primitives::MAINNET.clone()).unwrap().unwrap();
+        assert_eq!(config.segments.receipts, Some(PruneMode::Distance(1_000_000)));
+        assert!(config.segments.receipts_log_filter.is_empty());
+
+        // requires `--full` and at least the minimum pruning distance
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--prune.receipts.distance",
+            "1000000"
+        ])
+        .is_err());
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--full",
+            "--prune.receipts.distance",
+            "64"
+        ])
+        .is_err());
+    }
 }
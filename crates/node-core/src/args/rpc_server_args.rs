@@ -3,33 +3,54 @@
 use crate::{
     args::{
         types::{MaxU32, ZeroAsNoneU64},
-        GasPriceOracleArgs, RpcStateCacheArgs,
+        utils::parse_duration_from_secs,
+        GasEstimationArgs, GasPriceOracleArgs, RpcStateCacheArgs,
     },
     cli::config::RethRpcConfig,
-    utils::get_or_create_jwt_secret_from_path,
+    utils::{get_or_create_jwt_secret_from_path, prompt_password},
 };
 use clap::{
-    builder::{PossibleValue, RangedU64ValueParser, TypedValueParser},
+    builder::{PossibleValue, PossibleValuesParser, RangedU64ValueParser, TypedValueParser},
     Arg, Args, Command,
 };
+use hyper::Uri;
 use rand::Rng;
 use reth_engine_primitives::EngineTypes;
 use reth_evm::ConfigureEvm;
 use reth_network_api::{NetworkInfo, Peers};
+use reth_primitives::Address;
 use reth_provider::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    EvmEnvProvider, HeaderProvider, StateProviderFactory,
+    AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, LogIndexReader,
+    PruneCheckpointReader, ReorgHistoryReader, SenderNoncesReader, StateProviderFactory,
+    TokenTransfersReader,
 };
+#[cfg(feature = "hardware-wallets")]
+use reth_rpc::eth::HardwareWallet;
+#[cfg(feature = "rest")]
+use reth_rpc::rest::{RestConfig, DEFAULT_REST_MAX_PAGE_SIZE, DEFAULT_REST_PAGE_SIZE};
 use reth_rpc::{
-    eth::{cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig, RPC_DEFAULT_GAS_CAP},
-    JwtError, JwtSecret,
+    eth::{
+        cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig,
+        revert::RevertErrorRegistryError, user_operation::DEFAULT_MAX_USER_OPERATIONS,
+        NonceStrategy, RemoteSignerApi, RevertErrorRegistry, SubscriptionOverflow,
+        UserOperationConfig, DEFAULT_MAX_BUFFERED_NOTIFICATIONS, DEFAULT_MAX_PERSISTED_FILTERS,
+        DEFAULT_TX_CONFIRMATION_TIMEOUT, ENTRY_POINT_V06, RPC_DEFAULT_GAS_CAP,
+    },
+    graphql::GraphQlConfig,
+    ApiKeys, ApiKeysConfig, ApiKeysConfigError, JwtError, JwtSecret, PersonalPolicy,
+    RateLimitConfig, RateLimitConfigError, RateLimiter, DEFAULT_TRACE_CACHE_SIZE_MB,
+    DEFAULT_TRACE_CHAIN_CONCURRENCY, DEFAULT_TRACE_FILTER_MAX_BLOCKS,
+    DEFAULT_TRACE_FILTER_MAX_TRACES, PERSONAL_METHODS,
 };
 use reth_rpc_builder::{
     auth::{AuthServerConfig, AuthServerHandle},
     constants,
     error::RpcError,
-    EthConfig, Identity, IpcServerBuilder, RethRpcModule, RpcModuleConfig, RpcModuleSelection,
-    RpcServerConfig, RpcServerHandle, ServerBuilder, TransportRpcModuleConfig,
+    EthConfig, Identity, IpcServerBuilder, MethodTimeout, RethRpcModule, RpcAuditConfig,
+    RpcAuditSink, RpcModuleConfig, RpcModuleSelection, RpcResponseCacheConfig, RpcServerConfig,
+    RpcServerHandle, RpcTimeouts, ServerBuilder, TransportRpcModuleConfig,
+    DEFAULT_RESPONSE_CACHE_MAX_ENTRIES,
 };
 use reth_rpc_engine_api::EngineApi;
 use reth_tasks::TaskSpawner;
@@ -38,6 +59,7 @@ use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::PathBuf,
+    time::Duration,
 };
 use tracing::debug;
 
@@ -79,6 +101,20 @@ pub struct RpcServerArgs {
     #[arg(long = "http.corsdomain")]
     pub http_corsdomain: Option<String>,
 
+    /// Enable the GraphQL API (EIP-1767) on the HTTP server at `/graphql`
+    #[arg(long)]
+    pub graphql: bool,
+
+    /// Enable the REST API for block explorers on the HTTP server
+    #[cfg(feature = "rest")]
+    #[arg(long = "http.rest")]
+    pub http_rest: bool,
+
+    /// Maximum number of transactions per page of the REST API
+    #[cfg(feature = "rest")]
+    #[arg(long = "http.rest.max-page-size", value_name = "COUNT", default_value_t = DEFAULT_REST_MAX_PAGE_SIZE)]
+    pub http_rest_max_page_size: usize,
+
     /// Enable the WS-RPC server
     #[arg(long)]
     pub ws: bool,
@@ -140,6 +176,65 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.jwtsecret", value_name = "HEX", global = true, required = false)]
     pub rpc_jwtsecret: Option<JwtSecret>,
 
+    /// Path to a JSON file with the API keys that are required for HTTP and WS requests, and their
+    /// daily and monthly compute unit quotas.
+    ///
+    /// The key is read from the `X-Api-Key` header. Every HTTP and WS call is charged to the key,
+    /// calls that exceed a quota are rejected with the JSON-RPC error code `-32005`. The usage of
+    /// the keys is available via `admin_apiKeyUsage`.
+    #[arg(long = "rpc.api-keys", value_name = "PATH", value_parser = parse_api_keys_config)]
+    pub rpc_api_keys: Option<ApiKeysConfig>,
+
+    /// Path to a TOML file with the rate limits of HTTP and WS calls: token buckets per client
+    /// IP, per API key and per method.
+    ///
+    /// The client IP is the peer address of the connection. The `X-Forwarded-For` and `X-Real-IP`
+    /// headers are only read from the reverse proxies listed in `trusted_proxies`. Calls that
+    /// exceed a limit are rejected with the JSON-RPC error code `-32005`.
+    #[arg(long = "rpc.rate-limit-config", value_name = "PATH", value_parser = parse_rate_limit_config)]
+    pub rpc_rate_limit_config: Option<RateLimitConfig>,
+
+    /// Path to a file the RPC audit log is written to as JSON lines.
+    ///
+    /// Each recorded call has its method, the hash of its params, its latency, the size of its
+    /// response and the class of its error. Applies to the HTTP, WS and IPC servers.
+    #[arg(long = "rpc.audit-log.file", value_name = "PATH", conflicts_with = "rpc_audit_log_otlp")]
+    pub rpc_audit_log_file: Option<PathBuf>,
+
+    /// The maximum size (in MB) of one RPC audit log file.
+    #[arg(long = "rpc.audit-log.max-size", value_name = "SIZE", default_value_t = 200)]
+    pub rpc_audit_log_max_size: u64,
+
+    /// The maximum amount of rotated RPC audit log files that are kept.
+    #[arg(long = "rpc.audit-log.max-files", value_name = "COUNT", default_value_t = 5)]
+    pub rpc_audit_log_max_files: usize,
+
+    /// OTLP/HTTP logs endpoint of a collector the RPC audit log is exported to, e.g.
+    /// `http://localhost:4318/v1/logs`.
+    #[arg(long = "rpc.audit-log.otlp", value_name = "URL")]
+    pub rpc_audit_log_otlp: Option<Uri>,
+
+    /// The fraction of the RPC calls that are recorded in the audit log, between 0.0 and 1.0.
+    #[arg(long = "rpc.audit-log.sample-rate", value_name = "RATE", default_value_t = 1.0, value_parser = parse_sample_rate)]
+    pub rpc_audit_log_sample_rate: f64,
+
+    /// RPC calls that take at least this long are always recorded in the audit log, regardless of
+    /// the sample rate, e.g. `500ms`.
+    #[arg(long = "rpc.audit-log.slow-threshold", value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub rpc_audit_log_slow_threshold: Option<Duration>,
+
+    /// Maximum execution time of an RPC call, e.g. `30s`. Calls that exceed it are aborted with
+    /// the JSON-RPC error code `-32002` and their blocking work is cancelled.
+    ///
+    /// Applies to all methods without a timeout set by `--rpc.method-timeout`.
+    #[arg(long = "rpc.call-timeout", value_name = "DURATION", value_parser = humantime::parse_duration)]
+    pub rpc_call_timeout: Option<Duration>,
+
+    /// Maximum execution time of the calls of a method, e.g.
+    /// `debug_traceBlockByNumber=2m,eth_getLogs=20s`.
+    #[arg(long = "rpc.method-timeout", value_name = "METHOD=DURATION", value_delimiter = ',')]
+    pub rpc_method_timeouts: Vec<MethodTimeout>,
+
     /// Set the maximum RPC request payload size for both HTTP and WS in megabytes.
     #[arg(long = "rpc.max-request-size", alias = "rpc-max-request-size", default_value_t = RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into())]
     pub rpc_max_request_size: MaxU32,
@@ -152,6 +247,18 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-subscriptions-per-connection", alias = "rpc-max-subscriptions-per-connection", default_value_t = RPC_DEFAULT_MAX_SUBS_PER_CONN.into())]
     pub rpc_max_subscriptions_per_connection: MaxU32,
 
+    /// Maximum number of notifications that are buffered per subscription for a client that does
+    /// not keep up.
+    #[arg(long = "rpc.max-buffered-notifications", value_name = "COUNT", default_value_t = DEFAULT_MAX_BUFFERED_NOTIFICATIONS)]
+    pub rpc_max_buffered_notifications: usize,
+
+    /// What happens to a subscription once its buffer is full.
+    ///
+    /// `drop` drops the subscription, `missed` discards notifications until the buffer has room
+    /// again and then sends a `{"missedNotifications": N}` notification.
+    #[arg(long = "rpc.subscription-overflow", value_name = "POLICY", default_value_t = SubscriptionOverflow::Drop)]
+    pub rpc_subscription_overflow: SubscriptionOverflow,
+
     /// Maximum number of RPC server connections.
     #[arg(long = "rpc.max-connections", alias = "rpc-max-connections", value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONNECTIONS.into())]
     pub rpc_max_connections: MaxU32,
@@ -160,6 +267,36 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-tracing-requests", alias = "rpc-max-tracing-requests", value_name = "COUNT", default_value_t = constants::default_max_tracing_requests())]
     pub rpc_max_tracing_requests: usize,
 
+    /// Maximum size of the cache of `trace_block` and `trace_transaction` results in megabytes.
+    /// (0 = disabled)
+    #[arg(long = "rpc.trace-cache-size", value_name = "MB", default_value_t = DEFAULT_TRACE_CACHE_SIZE_MB)]
+    pub rpc_trace_cache_size: usize,
+
+    /// Maximum number of blocks that are traced by a `trace_filter` request.
+    ///
+    /// Limits the block range of the filter, or the number of blocks with transactions of the
+    /// addresses of the filter if the address transactions index is enabled.
+    #[arg(long = "rpc.trace-filter-max-blocks", value_name = "COUNT", default_value_t = DEFAULT_TRACE_FILTER_MAX_BLOCKS)]
+    pub rpc_trace_filter_max_blocks: u64,
+
+    /// Maximum number of traces that are returned by a `trace_filter` request, larger results
+    /// have to be paginated with `after` and `count`.
+    #[arg(long = "rpc.trace-filter-max-traces", value_name = "COUNT", default_value_t = DEFAULT_TRACE_FILTER_MAX_TRACES)]
+    pub rpc_trace_filter_max_traces: usize,
+
+    /// Number of blocks that are traced concurrently for a `debug_traceChain` subscription.
+    #[arg(long = "rpc.trace-chain-concurrency", value_name = "COUNT", default_value_t = DEFAULT_TRACE_CHAIN_CONCURRENCY)]
+    pub rpc_trace_chain_concurrency: usize,
+
+    /// Maximum size of the cache of responses of calls for finalized blocks, receipts and traces
+    /// in megabytes, e.g. `eth_getBlockByNumber` or `trace_block`. (0 = disabled)
+    #[arg(long = "rpc.response-cache-size", value_name = "MB", default_value_t = 0)]
+    pub rpc_response_cache_size: usize,
+
+    /// Maximum number of responses in the cache of responses of calls for finalized data.
+    #[arg(long = "rpc.response-cache-max-entries", value_name = "COUNT", default_value_t = DEFAULT_RESPONSE_CACHE_MAX_ENTRIES)]
+    pub rpc_response_cache_max_entries: u32,
+
     /// Maximum number of blocks that could be scanned per filter request. (0 = entire chain)
     #[arg(long = "rpc.max-blocks-per-filter", alias = "rpc-max-blocks-per-filter", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_BLOCKS_PER_FILTER))]
     pub rpc_max_blocks_per_filter: ZeroAsNoneU64,
@@ -168,6 +305,20 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maximum time an `eth_getLogs` query over a range of blocks may take, before it is aborted
+    /// with a smaller range to retry with.
+    #[arg(long = "rpc.logs-query-timeout", value_name = "SECONDS", value_parser = parse_duration_from_secs)]
+    pub rpc_logs_query_timeout: Option<Duration>,
+
+    /// Path to a file the log and block filters are persisted in, so clients polling them with
+    /// `eth_getFilterChanges` can continue after the node restarted.
+    #[arg(long = "rpc.filter-store", value_name = "PATH")]
+    pub rpc_filter_store: Option<PathBuf>,
+
+    /// Maximum number of filters that are persisted, the most recently polled ones are kept.
+    #[arg(long = "rpc.max-persisted-filters", value_name = "COUNT", default_value_t = DEFAULT_MAX_PERSISTED_FILTERS)]
+    pub rpc_max_persisted_filters: usize,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long = "rpc.gascap",
@@ -178,6 +329,152 @@ pub struct RpcServerArgs {
     )]
     pub rpc_gas_cap: u64,
 
+    /// Maximum time `eth_sendRawTransactionSync` waits for the transaction to be included in a
+    /// block.
+    #[arg(
+        long = "rpc.tx-confirmation-timeout",
+        value_name = "SECONDS",
+        value_parser = parse_duration_from_secs,
+        default_value = "30"
+    )]
+    pub rpc_tx_confirmation_timeout: Duration,
+
+    /// How the nonce of transactions signed by `eth_sendTransaction` and `eth_signTransaction` is
+    /// chosen if the request doesn't set one.
+    ///
+    /// `next-onchain` uses the next nonce of the account in the latest block,
+    /// `next-including-pool` also counts the account's transactions in the pool, and `explicit`
+    /// rejects requests without a nonce and queues transactions with a nonce gap in the pool.
+    #[arg(long = "rpc.nonce-strategy", value_name = "STRATEGY", default_value_t = NonceStrategy::NextIncludingPool)]
+    pub rpc_nonce_strategy: NonceStrategy,
+
+    /// Path to a JSON ABI file with custom errors that the revert reasons of `eth_call` and
+    /// `eth_estimateGas` are decoded with, in addition to `Error(string)` and `Panic(uint256)`.
+    #[arg(long = "rpc.revert-abi", value_name = "PATH", value_parser = parse_revert_error_registry)]
+    pub rpc_revert_abi: Option<RevertErrorRegistry>,
+
+    /// Include the decoded revert reason in the data of the error of reverted calls.
+    ///
+    /// The data is an object with the `output` of the call and the decoded `reason`, instead of
+    /// only the output.
+    #[arg(long = "rpc.structured-revert-data")]
+    pub rpc_structured_revert_data: bool,
+
+    /// Comma separated list of the ERC-4337 EntryPoints supported by the `eth-user-operation`
+    /// module.
+    #[arg(
+        long = "rpc.user-operation-entry-points",
+        value_name = "ADDRESSES",
+        value_delimiter = ',',
+        default_value = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789"
+    )]
+    pub rpc_user_operation_entry_points: Vec<Address>,
+
+    /// Maximum number of user operations in the mempool of the `eth-user-operation` module.
+    #[arg(long = "rpc.user-operation-pool-size", value_name = "COUNT", default_value_t = DEFAULT_MAX_USER_OPERATIONS)]
+    pub rpc_user_operation_pool_size: usize,
+
+    /// Enable the `debug_setHead` and `debug_rewindToBlock` methods, which rewind the canonical
+    /// chain by unwinding all stages.
+    ///
+    /// This is destructive and only intended for private and test networks. The methods are
+    /// served as part of the `debug` namespace.
+    #[arg(long = "rpc.unsafe-debug")]
+    pub rpc_unsafe_debug: bool,
+
+    /// Directory of geth compatible V3 keystore files to load accounts for the RPC signer from.
+    ///
+    /// The accounts are listed by `eth_accounts`, but can only sign if they are unlocked with
+    /// `--keystore.password-file` or `--keystore.password-prompt`.
+    #[arg(long = "keystore", visible_alias = "signer.keystore-dir", value_name = "DIR")]
+    pub keystore: Option<PathBuf>,
+
+    /// Path to a file containing the passphrase to unlock the keystore accounts with.
+    ///
+    /// Only the first line of the file is used.
+    #[arg(long = "keystore.password-file", value_name = "PATH", requires = "keystore")]
+    pub keystore_password_file: Option<PathBuf>,
+
+    /// Prompt for the passphrase to unlock the keystore accounts with on startup.
+    #[arg(
+        long = "keystore.password-prompt",
+        requires = "keystore",
+        conflicts_with = "keystore_password_file"
+    )]
+    pub keystore_password_prompt: bool,
+
+    /// Duration the keystore accounts are unlocked for on startup, in seconds.
+    ///
+    /// By default the accounts stay unlocked until shutdown. Locked accounts can be unlocked again
+    /// with `personal_unlockAccount`.
+    #[arg(
+        long = "keystore.unlock-duration",
+        value_name = "SECONDS",
+        value_parser = parse_duration_from_secs,
+        requires = "keystore"
+    )]
+    pub keystore_unlock_duration: Option<Duration>,
+
+    /// Enable the deprecated `personal` namespace for the keystore accounts.
+    ///
+    /// This is only intended for legacy tooling. The namespace must still be selected via
+    /// `--http.api` or `--ws.api` to be served over HTTP or WS, IPC serves it by default.
+    #[arg(long = "rpc.enable-personal", requires = "keystore")]
+    pub rpc_enable_personal: bool,
+
+    /// Comma separated list of methods of the `personal` namespace to disable, e.g.
+    /// `personal_newAccount,personal_importRawKey`.
+    #[arg(
+        long = "rpc.personal-disabled-methods",
+        value_name = "METHODS",
+        value_delimiter = ',',
+        value_parser = PossibleValuesParser::new(PERSONAL_METHODS),
+        requires = "rpc_enable_personal"
+    )]
+    pub rpc_personal_disabled_methods: Vec<String>,
+
+    /// URL of an external signing service that signs for its accounts, so that no keys are kept
+    /// in the node.
+    ///
+    /// The accounts of the service are listed by `eth_accounts`, and signing requests for them
+    /// are forwarded to the service.
+    #[arg(long = "signer.remote-url", value_name = "URL")]
+    pub signer_remote_url: Option<String>,
+
+    /// The API of the external signing service, `web3signer` or `clef`.
+    #[arg(
+        long = "signer.remote-api",
+        value_name = "API",
+        default_value_t = RemoteSignerApi::Web3Signer,
+        requires = "signer_remote_url"
+    )]
+    pub signer_remote_api: RemoteSignerApi,
+
+    /// Path to a file containing the bearer token that is sent in the `Authorization` header of
+    /// requests to the external signing service.
+    ///
+    /// Only the first line of the file is used.
+    #[arg(long = "signer.remote-auth-file", value_name = "PATH", requires = "signer_remote_url")]
+    pub signer_remote_auth_file: Option<PathBuf>,
+
+    /// Hardware wallet connected over USB to sign with for its accounts, `ledger` or `trezor`.
+    ///
+    /// Every signing request must be confirmed on the device.
+    #[cfg(feature = "hardware-wallets")]
+    #[arg(long = "signer.hardware", value_name = "WALLET")]
+    pub signer_hardware: Option<HardwareWallet>,
+
+    /// Number of accounts of the hardware wallet to use, derived with the default derivation path
+    /// of the wallet.
+    #[cfg(feature = "hardware-wallets")]
+    #[arg(
+        long = "signer.hardware-accounts",
+        value_name = "COUNT",
+        default_value_t = 1,
+        requires = "signer_hardware"
+    )]
+    pub signer_hardware_accounts: usize,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -185,6 +482,10 @@ pub struct RpcServerArgs {
     /// Gas price oracle configuration.
     #[command(flatten)]
     pub gas_price_oracle: GasPriceOracleArgs,
+
+    /// Gas estimation configuration.
+    #[command(flatten)]
+    pub gas_estimation: GasEstimationArgs,
 }
 
 impl RpcServerArgs {
@@ -206,6 +507,43 @@ impl RpcServerArgs {
         self
     }
 
+    /// Returns the passphrase for the keystore accounts, read from `--keystore.password-file` or
+    /// prompted for with `--keystore.password-prompt`, if set.
+    pub fn keystore_passphrase(&self) -> eyre::Result<Option<String>> {
+        if self.keystore_password_prompt {
+            return Ok(Some(prompt_password("Keystore passphrase: ")?))
+        }
+        let Some(path) = &self.keystore_password_file else { return Ok(None) };
+        let passphrase = reth_primitives::fs::read_to_string(path)?;
+        Ok(Some(passphrase.lines().next().unwrap_or_default().to_string()))
+    }
+
+    /// Reads the bearer token for the external signing service from `--signer.remote-auth-file`,
+    /// if set.
+    pub fn signer_remote_auth_token(
+        &self,
+    ) -> Result<Option<String>, reth_primitives::fs::FsPathError> {
+        let Some(path) = &self.signer_remote_auth_file else { return Ok(None) };
+        let token = reth_primitives::fs::read_to_string(path)?;
+        Ok(Some(token.lines().next().unwrap_or_default().to_string()))
+    }
+
+    /// Removes the `personal` namespace from the selection, unless it's enabled via
+    /// `--rpc.enable-personal`.
+    fn personal_selection(&self, selection: RpcModuleSelection) -> RpcModuleSelection {
+        if self.rpc_enable_personal ||
+            !selection.iter_selection().any(|module| module == RethRpcModule::Personal)
+        {
+            return selection
+        }
+        selection
+            .into_selection()
+            .into_iter()
+            .filter(|module| *module != RethRpcModule::Personal)
+            .collect::<Vec<_>>()
+            .into()
+    }
+
     /// Change rpc port numbers based on the instance number.
     /// * The `auth_port` is scaled by a factor of `instance * 100`
     /// * The `http_port` is scaled by a factor of `-instance`
@@ -296,6 +634,12 @@ impl RpcServerArgs {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + ReorgHistoryReader
+            + TokenTransfersReader
+            + SenderNoncesReader
+            + LogIndexReader
+            + AddressTransactionsReader
+            + PruneCheckpointReader
             + Clone
             + Unpin
             + 'static,
@@ -326,7 +670,7 @@ impl RpcServerArgs {
         pool: Pool,
         network: Network,
         executor: Tasks,
-        engine_api: EngineApi<Provider, EngineT>,
+        engine_api: EngineApi<Provider, EngineT, Pool>,
         jwt_secret: JwtSecret,
         evm_config: EvmConfig,
     ) -> Result<AuthServerHandle, RpcError>
@@ -335,6 +679,7 @@ impl RpcServerArgs {
             + ChainSpecProvider
             + EvmEnvProvider
             + HeaderProvider
+            + PruneCheckpointReader
             + StateProviderFactory
             + Clone
             + Unpin
@@ -376,9 +721,32 @@ impl RethRpcConfig for RpcServerArgs {
             .max_tracing_requests(self.rpc_max_tracing_requests)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
+            .logs_query_timeout(self.rpc_logs_query_timeout)
+            .filter_store(self.rpc_filter_store.clone())
+            .max_persisted_filters(self.rpc_max_persisted_filters)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .tx_confirmation_timeout(self.rpc_tx_confirmation_timeout)
+            .nonce_strategy(self.rpc_nonce_strategy)
+            .personal_policy(
+                PersonalPolicy::new(&self.rpc_personal_disabled_methods)
+                    .expect("methods are validated when parsing the arguments"),
+            )
+            .revert_errors(self.rpc_revert_abi.clone())
+            .structured_revert_data(self.rpc_structured_revert_data)
+            .user_operation_config(
+                UserOperationConfig::default()
+                    .entry_points(self.rpc_user_operation_entry_points.clone())
+                    .max_user_operations(self.rpc_user_operation_pool_size),
+            )
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
+            .gas_estimation(self.gas_estimation.gas_estimation_config())
+            .max_buffered_notifications(self.rpc_max_buffered_notifications)
+            .subscription_overflow(self.rpc_subscription_overflow)
+            .trace_cache_size_mb(self.rpc_trace_cache_size)
+            .trace_filter_max_blocks(self.rpc_trace_filter_max_blocks)
+            .trace_filter_max_traces(self.rpc_trace_filter_max_traces)
+            .trace_chain_concurrency(self.rpc_trace_chain_concurrency)
     }
 
     fn state_cache_config(&self) -> EthStateCacheConfig {
@@ -386,6 +754,7 @@ impl RethRpcConfig for RpcServerArgs {
             max_blocks: self.rpc_state_cache.max_blocks,
             max_receipts: self.rpc_state_cache.max_receipts,
             max_envs: self.rpc_state_cache.max_envs,
+            max_transactions: self.rpc_state_cache.max_transactions,
             max_concurrent_db_requests: self.rpc_state_cache.max_concurrent_db_requests,
         }
     }
@@ -408,22 +777,28 @@ impl RethRpcConfig for RpcServerArgs {
 
         if self.http {
             config = config.with_http(
-                self.http_api
-                    .clone()
-                    .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
+                self.personal_selection(
+                    self.http_api
+                        .clone()
+                        .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
+                ),
             );
         }
 
         if self.ws {
             config = config.with_ws(
-                self.ws_api
-                    .clone()
-                    .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
+                self.personal_selection(
+                    self.ws_api
+                        .clone()
+                        .unwrap_or_else(|| RpcModuleSelection::standard_modules().into()),
+                ),
             );
         }
 
         if self.is_ipc_enabled() {
-            config = config.with_ipc(RpcModuleSelection::default_ipc_modules());
+            config = config.with_ipc(
+                self.personal_selection(RpcModuleSelection::default_ipc_modules().into()),
+            );
         }
 
         config
@@ -446,7 +821,20 @@ impl RethRpcConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let api_keys = self.rpc_api_keys.clone().map(ApiKeys::new);
+        let rate_limiter = self.rpc_rate_limit_config.clone().map(RateLimiter::new);
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_api_keys(api_keys)
+            .with_rate_limiter(rate_limiter)
+            .with_audit_log(self.rpc_audit_config())
+            .with_call_timeouts(
+                self.rpc_method_timeouts
+                    .iter()
+                    .cloned()
+                    .collect::<RpcTimeouts>()
+                    .with_default(self.rpc_call_timeout),
+            );
 
         if self.http {
             let socket_address = SocketAddr::new(self.http_addr, self.http_port);
@@ -470,6 +858,49 @@ impl RethRpcConfig for RpcServerArgs {
         config
     }
 
+    fn rpc_audit_config(&self) -> Option<RpcAuditConfig> {
+        let sink = if let Some(path) = &self.rpc_audit_log_file {
+            RpcAuditSink::File {
+                path: path.clone(),
+                max_size_bytes: self.rpc_audit_log_max_size * 1024 * 1024,
+                max_files: self.rpc_audit_log_max_files,
+            }
+        } else {
+            RpcAuditSink::Otlp { endpoint: self.rpc_audit_log_otlp.clone()? }
+        };
+        Some(
+            RpcAuditConfig::new(sink)
+                .with_sample_rate(self.rpc_audit_log_sample_rate)
+                .with_slow_call_threshold(self.rpc_audit_log_slow_threshold),
+        )
+    }
+
+    fn response_cache_config(&self) -> Option<RpcResponseCacheConfig> {
+        (self.rpc_response_cache_size > 0).then(|| {
+            RpcResponseCacheConfig::new(self.rpc_response_cache_size * 1024 * 1024)
+                .with_max_entries(self.rpc_response_cache_max_entries)
+        })
+    }
+
+    fn graphql_config(&self) -> Option<GraphQlConfig> {
+        (self.http && self.graphql).then(|| {
+            GraphQlConfig::default()
+                .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
+                .max_request_body_size(self.rpc_max_request_size_bytes())
+        })
+    }
+
+    #[cfg(feature = "rest")]
+    fn rest_config(&self) -> Option<RestConfig> {
+        (self.http && self.http_rest).then(|| {
+            let max_page_size = self.http_rest_max_page_size;
+            RestConfig {
+                default_page_size: DEFAULT_REST_PAGE_SIZE.min(max_page_size),
+                max_page_size,
+            }
+        })
+    }
+
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
@@ -503,6 +934,11 @@ impl Default for RpcServerArgs {
             http_port: constants::DEFAULT_HTTP_RPC_PORT,
             http_api: None,
             http_corsdomain: None,
+            graphql: false,
+            #[cfg(feature = "rest")]
+            http_rest: false,
+            #[cfg(feature = "rest")]
+            http_rest_max_page_size: DEFAULT_REST_MAX_PAGE_SIZE,
             ws: false,
             ws_addr: Ipv4Addr::LOCALHOST.into(),
             ws_port: constants::DEFAULT_WS_RPC_PORT,
@@ -516,20 +952,88 @@ impl Default for RpcServerArgs {
             auth_ipc: false,
             auth_ipc_path: constants::DEFAULT_ENGINE_API_IPC_ENDPOINT.to_string(),
             rpc_jwtsecret: None,
+            rpc_api_keys: None,
+            rpc_rate_limit_config: None,
+            rpc_audit_log_file: None,
+            rpc_audit_log_max_size: 200,
+            rpc_audit_log_max_files: 5,
+            rpc_audit_log_otlp: None,
+            rpc_audit_log_sample_rate: 1.0,
+            rpc_audit_log_slow_threshold: None,
+            rpc_call_timeout: None,
+            rpc_method_timeouts: Vec::new(),
             rpc_max_request_size: RPC_DEFAULT_MAX_REQUEST_SIZE_MB.into(),
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
+            rpc_max_buffered_notifications: DEFAULT_MAX_BUFFERED_NOTIFICATIONS,
+            rpc_subscription_overflow: SubscriptionOverflow::Drop,
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
+            rpc_trace_cache_size: DEFAULT_TRACE_CACHE_SIZE_MB,
+            rpc_trace_filter_max_blocks: DEFAULT_TRACE_FILTER_MAX_BLOCKS,
+            rpc_trace_filter_max_traces: DEFAULT_TRACE_FILTER_MAX_TRACES,
+            rpc_trace_chain_concurrency: DEFAULT_TRACE_CHAIN_CONCURRENCY,
+            rpc_response_cache_size: 0,
+            rpc_response_cache_max_entries: DEFAULT_RESPONSE_CACHE_MAX_ENTRIES,
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_logs_query_timeout: None,
+            rpc_filter_store: None,
+            rpc_max_persisted_filters: DEFAULT_MAX_PERSISTED_FILTERS,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_tx_confirmation_timeout: DEFAULT_TX_CONFIRMATION_TIMEOUT,
+            rpc_nonce_strategy: NonceStrategy::NextIncludingPool,
+            rpc_revert_abi: None,
+            rpc_structured_revert_data: false,
+            rpc_user_operation_entry_points: vec![ENTRY_POINT_V06],
+            rpc_user_operation_pool_size: DEFAULT_MAX_USER_OPERATIONS,
+            rpc_unsafe_debug: false,
+            keystore: None,
+            keystore_password_file: None,
+            keystore_password_prompt: false,
+            keystore_unlock_duration: None,
+            rpc_enable_personal: false,
+            rpc_personal_disabled_methods: Vec::new(),
+            signer_remote_url: None,
+            signer_remote_api: RemoteSignerApi::Web3Signer,
+            signer_remote_auth_file: None,
+            #[cfg(feature = "hardware-wallets")]
+            signer_hardware: None,
+            #[cfg(feature = "hardware-wallets")]
+            signer_hardware_accounts: 1,
             gas_price_oracle: GasPriceOracleArgs::default(),
+            gas_estimation: GasEstimationArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
         }
     }
 }
 
+/// clap value parser for [ApiKeysConfig], loads the config from the file at the given path.
+fn parse_api_keys_config(path: &str) -> Result<ApiKeysConfig, ApiKeysConfigError> {
+    ApiKeysConfig::from_file(path)
+}
+
+/// clap value parser for [RateLimitConfig], loads the config from the file at the given path.
+fn parse_rate_limit_config(path: &str) -> Result<RateLimitConfig, RateLimitConfigError> {
+    RateLimitConfig::from_file(path)
+}
+
+/// clap value parser for the sample rate of the RPC audit log.
+fn parse_sample_rate(value: &str) -> Result<f64, String> {
+    let rate: f64 = value.parse().map_err(|err| format!("{err}"))?;
+    if !(0.0..=1.0).contains(&rate) {
+        return Err(format!("sample rate {rate} is not between 0.0 and 1.0"))
+    }
+    Ok(rate)
+}
+
+/// clap value parser for [RevertErrorRegistry], loads the errors of the ABI file at the given path.
+fn parse_revert_error_registry(
+    path: &str,
+) -> Result<RevertErrorRegistry, RevertErrorRegistryError> {
+    RevertErrorRegistry::from_file(path)
+}
+
 /// clap value parser for [RpcModuleSelection].
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
@@ -591,6 +1095,461 @@ mod tests {
         assert!(args.is_err());
     }
 
+    #[test]
+    fn test_rpc_tx_confirmation_timeout() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().tx_confirmation.timeout, DEFAULT_TX_CONFIRMATION_TIMEOUT);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.tx-confirmation-timeout",
+            "120",
+        ])
+        .args;
+        assert_eq!(args.eth_config().tx_confirmation.timeout, Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_rpc_nonce_strategy() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().nonce_strategy, NonceStrategy::NextIncludingPool);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.nonce-strategy",
+            "explicit",
+        ])
+        .args;
+        assert_eq!(args.eth_config().nonce_strategy, NonceStrategy::Explicit);
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.nonce-strategy",
+            "latest",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_graphql_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth", "--graphql"]).args;
+        assert!(args.graphql_config().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth", "--http", "--graphql"]).args;
+        let config = args.graphql_config().unwrap();
+        assert_eq!(config.max_blocks_per_filter, constants::DEFAULT_MAX_BLOCKS_PER_FILTER);
+        assert_eq!(config.max_request_body_size, args.rpc_max_request_size_bytes());
+    }
+
+    #[cfg(feature = "rest")]
+    #[test]
+    fn test_rest_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth", "--http.rest"]).args;
+        assert!(args.rest_config().is_none());
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--http", "--http.rest"]).args;
+        assert_eq!(args.rest_config(), Some(RestConfig::default()));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http",
+            "--http.rest",
+            "--http.rest.max-page-size",
+            "10",
+        ])
+        .args;
+        let config = args.rest_config().unwrap();
+        assert_eq!(config.max_page_size, 10);
+        assert_eq!(config.default_page_size, 10);
+    }
+
+    #[test]
+    fn test_rpc_trace_cache_size_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().trace_cache_size_mb, DEFAULT_TRACE_CACHE_SIZE_MB);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.trace-cache-size", "0"])
+                .args;
+        assert_eq!(args.eth_config().trace_cache_size_mb, 0);
+    }
+
+    #[test]
+    fn test_rpc_trace_filter_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().trace_filter.max_blocks, DEFAULT_TRACE_FILTER_MAX_BLOCKS);
+        assert_eq!(args.eth_config().trace_filter.max_traces, DEFAULT_TRACE_FILTER_MAX_TRACES);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.trace-filter-max-blocks",
+            "1000",
+            "--rpc.trace-filter-max-traces",
+            "500",
+        ])
+        .args;
+        assert_eq!(args.eth_config().trace_filter.max_blocks, 1000);
+        assert_eq!(args.eth_config().trace_filter.max_traces, 500);
+    }
+
+    #[test]
+    fn test_rpc_trace_chain_concurrency_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().trace_chain_concurrency, DEFAULT_TRACE_CHAIN_CONCURRENCY);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.trace-chain-concurrency",
+            "16",
+        ])
+        .args;
+        assert_eq!(args.eth_config().trace_chain_concurrency, 16);
+    }
+
+    #[test]
+    fn test_rpc_response_cache_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.response_cache_config().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.response-cache-size",
+            "64",
+            "--rpc.response-cache-max-entries",
+            "100",
+        ])
+        .args;
+        let config = args.response_cache_config().unwrap();
+        assert_eq!(config.max_bytes, 64 * 1024 * 1024);
+        assert_eq!(config.max_entries, 100);
+    }
+
+    #[test]
+    fn test_rpc_subscription_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        let config = args.eth_config().pubsub_config();
+        assert_eq!(config.max_buffered_notifications, DEFAULT_MAX_BUFFERED_NOTIFICATIONS);
+        assert_eq!(config.overflow, SubscriptionOverflow::Drop);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.max-buffered-notifications",
+            "10",
+            "--rpc.subscription-overflow",
+            "missed",
+        ])
+        .args;
+        let config = args.eth_config().pubsub_config();
+        assert_eq!(config.max_buffered_notifications, 10);
+        assert_eq!(config.overflow, SubscriptionOverflow::Missed);
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.subscription-overflow",
+            "block",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_rpc_keystore_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--keystore",
+            "keystore",
+            "--keystore.password-file",
+            "password.txt",
+        ])
+        .args;
+        assert_eq!(args.keystore, Some(PathBuf::from("keystore")));
+        assert_eq!(args.keystore_password_file, Some(PathBuf::from("password.txt")));
+        assert_eq!(args.keystore_unlock_duration, None);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--signer.keystore-dir",
+            "keystore",
+            "--keystore.password-prompt",
+            "--keystore.unlock-duration",
+            "60",
+        ])
+        .args;
+        assert_eq!(args.keystore, Some(PathBuf::from("keystore")));
+        assert!(args.keystore_password_prompt);
+        assert_eq!(args.keystore_unlock_duration, Some(Duration::from_secs(60)));
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--keystore",
+            "keystore",
+            "--keystore.password-file",
+            "password.txt",
+            "--keystore.password-prompt",
+        ]);
+        assert!(args.is_err());
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--keystore.password-file",
+            "password.txt",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_rpc_remote_signer_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.signer_remote_url, None);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--signer.remote-url",
+            "http://localhost:8550",
+            "--signer.remote-api",
+            "clef",
+            "--signer.remote-auth-file",
+            "token.txt",
+        ])
+        .args;
+        assert_eq!(args.signer_remote_url.as_deref(), Some("http://localhost:8550"));
+        assert_eq!(args.signer_remote_api, RemoteSignerApi::Clef);
+        assert_eq!(args.signer_remote_auth_file, Some(PathBuf::from("token.txt")));
+
+        let args =
+            CommandParser::<RpcServerArgs>::try_parse_from(["reth", "--signer.remote-api", "clef"]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_rpc_api_keys_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rpc_server_config().api_keys().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.api-keys",
+            "does-not-exist.json",
+        ]);
+        assert!(args.is_err());
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), r#"{"keys":{"alice":{"dailyLimit":100}}}"#).unwrap();
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.api-keys",
+            file.path().to_str().unwrap(),
+        ])
+        .args;
+        let api_keys = args.rpc_server_config().api_keys().cloned().unwrap();
+        assert!(api_keys.contains("alice"));
+        assert_eq!(api_keys.config().keys["alice"].daily_limit, Some(100));
+    }
+
+    #[test]
+    fn test_rpc_rate_limit_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rpc_server_config().rate_limiter().is_none());
+
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(
+            file.path(),
+            "trusted_proxies = [\"10.0.0.1\"]\n[methods.eth_call]\nrequests_per_second = 10\n",
+        )
+        .unwrap();
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.rate-limit-config",
+            file.path().to_str().unwrap(),
+        ])
+        .args;
+        let rate_limiter = args.rpc_server_config().rate_limiter().cloned().unwrap();
+        assert_eq!(rate_limiter.config().methods["eth_call"].requests_per_second, 10.);
+        assert!(rate_limiter.config().per_ip.is_none());
+        assert_eq!(rate_limiter.config().trusted_proxies, vec!["10.0.0.1".parse().unwrap()]);
+
+        std::fs::write(file.path(), "[per_ip]\nrequests = 10\n").unwrap();
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.rate-limit-config",
+            file.path().to_str().unwrap(),
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_rpc_audit_log_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rpc_server_config().audit_log().is_none());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.audit-log.file",
+            "/tmp/audit.jsonl",
+            "--rpc.audit-log.max-size",
+            "10",
+            "--rpc.audit-log.sample-rate",
+            "0.25",
+            "--rpc.audit-log.slow-threshold",
+            "500ms",
+        ])
+        .args;
+        let config = args.rpc_server_config().audit_log().cloned().unwrap();
+        assert_eq!(
+            config.sink,
+            RpcAuditSink::File {
+                path: "/tmp/audit.jsonl".into(),
+                max_size_bytes: 10 * 1024 * 1024,
+                max_files: 5
+            }
+        );
+        assert_eq!(config.sample_rate, 0.25);
+        assert_eq!(config.slow_call_threshold, Some(Duration::from_millis(500)));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.audit-log.otlp",
+            "http://localhost:4318/v1/logs",
+        ])
+        .args;
+        let config = args.rpc_server_config().audit_log().cloned().unwrap();
+        assert_eq!(
+            config.sink,
+            RpcAuditSink::Otlp { endpoint: "http://localhost:4318/v1/logs".parse().unwrap() }
+        );
+
+        for invalid in [
+            &["reth", "--rpc.audit-log.sample-rate", "1.5"][..],
+            &["reth", "--rpc.audit-log.file", "a", "--rpc.audit-log.otlp", "http://localhost"],
+        ] {
+            assert!(CommandParser::<RpcServerArgs>::try_parse_from(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn test_rpc_timeout_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(args.rpc_server_config().call_timeouts().is_empty());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.call-timeout",
+            "30s",
+            "--rpc.method-timeout",
+            "debug_traceBlockByNumber=2m,eth_getLogs=20s",
+        ])
+        .args;
+        let config = args.rpc_server_config();
+        let timeouts = config.call_timeouts();
+        assert_eq!(timeouts.timeout("debug_traceBlockByNumber"), Some(Duration::from_secs(120)));
+        assert_eq!(timeouts.timeout("eth_getLogs"), Some(Duration::from_secs(20)));
+        assert_eq!(timeouts.timeout("eth_call"), Some(Duration::from_secs(30)));
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.method-timeout",
+            "eth_getLogs",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_rpc_personal_selection() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http",
+            "--http.api",
+            "eth,personal",
+            "--keystore",
+            "keystore",
+        ])
+        .args;
+        let config = args.transport_rpc_module_config();
+        assert_eq!(config.http().cloned(), Some(RpcModuleSelection::from([RethRpcModule::Eth])));
+        assert!(!config
+            .ipc()
+            .unwrap()
+            .iter_selection()
+            .any(|module| module == RethRpcModule::Personal));
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--http",
+            "--http.api",
+            "eth,personal",
+            "--keystore",
+            "keystore",
+            "--rpc.enable-personal",
+        ])
+        .args;
+        let config = args.transport_rpc_module_config();
+        assert_eq!(
+            config.http().cloned(),
+            Some(RpcModuleSelection::from([RethRpcModule::Eth, RethRpcModule::Personal]))
+        );
+        assert!(config
+            .ipc()
+            .unwrap()
+            .iter_selection()
+            .any(|module| module == RethRpcModule::Personal));
+    }
+
+    #[test]
+    fn test_rpc_personal_disabled_methods() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--keystore",
+            "keystore",
+            "--rpc.enable-personal",
+            "--rpc.personal-disabled-methods",
+            "personal_newAccount,personal_importRawKey",
+        ])
+        .args;
+        let policy = args.eth_config().personal_policy;
+        assert!(!policy.is_enabled("personal_newAccount"));
+        assert!(!policy.is_enabled("personal_importRawKey"));
+        assert!(policy.is_enabled("personal_unlockAccount"));
+
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--rpc.personal-disabled-methods",
+            "personal_newAccount",
+        ]);
+        assert!(args.is_err());
+
+        // unknown methods are rejected instead of leaving the intended method enabled
+        let args = CommandParser::<RpcServerArgs>::try_parse_from([
+            "reth",
+            "--keystore",
+            "keystore",
+            "--rpc.enable-personal",
+            "--rpc.personal-disabled-methods",
+            "personal_importRawkey",
+        ]);
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn test_rpc_user_operation_args() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().user_operation, UserOperationConfig::default());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.user-operation-entry-points",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+            "--rpc.user-operation-pool-size",
+            "10",
+        ])
+        .args;
+        let config = args.eth_config().user_operation;
+        assert_eq!(
+            config.entry_points,
+            vec![Address::with_last_byte(1), Address::with_last_byte(2)]
+        );
+        assert_eq!(config.max_user_operations, 10);
+    }
+
     #[test]
     fn test_rpc_server_args_parser() {
         let args =
@@ -744,6 +1703,38 @@ mod tests {
         let config = args.eth_config().filter_config();
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
+        assert_eq!(config.logs_query_timeout, None);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.logs-query-timeout", "10"])
+                .args;
+        let config = args.eth_config().filter_config();
+        assert_eq!(config.logs_query_timeout, Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn test_filter_store() {
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.filter-store",
+            "filters.json",
+            "--rpc.max-persisted-filters",
+            "10",
+        ])
+        .args;
+
+        let config = args.eth_config().filter_config();
+        assert_eq!(config.filter_store_path, Some(PathBuf::from("filters.json")));
+        assert_eq!(config.max_persisted_filters, 10);
+    }
+
+    #[test]
+    fn test_rpc_unsafe_debug() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert!(!args.rpc_unsafe_debug);
+
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.unsafe-debug"]).args;
+        assert!(args.rpc_unsafe_debug);
     }
 
     #[test]
@@ -0,0 +1,128 @@
+//! clap [Args](clap::Args) for static files configuration
+
+use clap::{Args, ValueEnum};
+use reth_nippy_jar::{DataReaderMode, MmapAdvice};
+
+/// Default size of the block cache used by [`StaticFilesReader::Direct`], in megabytes.
+pub const DEFAULT_STATIC_FILES_BLOCK_CACHE_MB: usize = 512;
+
+/// Parameters for static files configuration
+#[derive(Debug, Args, PartialEq, Eq, Clone, Copy)]
+#[command(next_help_heading = "Static files")]
+pub struct StaticFilesArgs {
+    /// How static files are read from disk.
+    ///
+    /// `mmap` memory-maps the files, `direct` uses positioned reads with `O_DIRECT` and an
+    /// in-process block cache, which behaves better on some network filesystems and containers.
+    #[arg(long = "static-files.reader", value_enum, default_value_t = StaticFilesReader::Mmap)]
+    pub reader: StaticFilesReader,
+
+    /// Access pattern hint passed to `madvise` when static files are read through `mmap`.
+    #[arg(
+        long = "static-files.mmap-advice",
+        value_enum,
+        default_value_t = StaticFilesMmapAdvice::Normal
+    )]
+    pub mmap_advice: StaticFilesMmapAdvice,
+
+    /// Size of the block cache in megabytes when static files are read through `direct` I/O.
+    #[arg(
+        long = "static-files.block-cache-mb",
+        value_name = "MB",
+        default_value_t = DEFAULT_STATIC_FILES_BLOCK_CACHE_MB
+    )]
+    pub block_cache_mb: usize,
+}
+
+impl StaticFilesArgs {
+    /// Returns the [`DataReaderMode`] used to read static files.
+    pub fn reader_mode(&self) -> DataReaderMode {
+        match self.reader {
+            StaticFilesReader::Mmap => DataReaderMode::Mmap(self.mmap_advice.into()),
+            StaticFilesReader::Direct => DataReaderMode::direct(self.block_cache_mb * 1024 * 1024),
+        }
+    }
+}
+
+impl Default for StaticFilesArgs {
+    fn default() -> Self {
+        Self {
+            reader: StaticFilesReader::Mmap,
+            mmap_advice: StaticFilesMmapAdvice::Normal,
+            block_cache_mb: DEFAULT_STATIC_FILES_BLOCK_CACHE_MB,
+        }
+    }
+}
+
+/// How static files are read from disk.
+#[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum StaticFilesReader {
+    /// Memory-map static files
+    Mmap,
+    /// Read static files with `pread` and `O_DIRECT`, backed by an in-process block cache
+    Direct,
+}
+
+/// Access pattern hint passed to `madvise` for memory-mapped static files.
+#[derive(Debug, Copy, Clone, ValueEnum, Eq, PartialEq)]
+pub enum StaticFilesMmapAdvice {
+    /// No special treatment
+    Normal,
+    /// Random access, disables readahead
+    Random,
+    /// Sequential access, enables aggressive readahead
+    Sequential,
+    /// Prefetch the whole file
+    WillNeed,
+}
+
+impl From<StaticFilesMmapAdvice> for MmapAdvice {
+    fn from(advice: StaticFilesMmapAdvice) -> Self {
+        match advice {
+            StaticFilesMmapAdvice::Normal => MmapAdvice::Normal,
+            StaticFilesMmapAdvice::Random => MmapAdvice::Random,
+            StaticFilesMmapAdvice::Sequential => MmapAdvice::Sequential,
+            StaticFilesMmapAdvice::WillNeed => MmapAdvice::WillNeed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_static_files_args() {
+        let default_args = StaticFilesArgs::default();
+        let args = CommandParser::<StaticFilesArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let args = CommandParser::<StaticFilesArgs>::parse_from([
+            "reth",
+            "--static-files.reader",
+            "direct",
+            "--static-files.block-cache-mb",
+            "64",
+        ])
+        .args;
+        assert_eq!(args.reader, StaticFilesReader::Direct);
+        assert_eq!(args.block_cache_mb, 64);
+        assert!(matches!(args.reader_mode(), DataReaderMode::Direct(_)));
+
+        let args = CommandParser::<StaticFilesArgs>::parse_from([
+            "reth",
+            "--static-files.mmap-advice",
+            "random",
+        ])
+        .args;
+        assert!(matches!(args.reader_mode(), DataReaderMode::Mmap(MmapAdvice::Random)));
+    }
+}
@@ -4,9 +4,9 @@ use crate::cli::config::RethTransactionPoolConfig;
 use clap::Args;
 use reth_primitives::Address;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS, validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS, maintain::MaintainPoolConfig,
+    validate::DEFAULT_MAX_TX_INPUT_BYTES, LocalTransactionConfig, PoolConfig, PriceBumpConfig,
+    SubPoolLimit, DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
 /// Parameters for debugging purposes
@@ -46,6 +46,11 @@ pub struct TxPoolArgs {
     #[arg(long = "blobpool.pricebump", default_value_t = REPLACE_BLOB_PRICE_BUMP)]
     pub blob_transaction_price_bump: u128,
 
+    /// Number of blocks the blob sidecars of mined transactions are kept after their block is
+    /// finalized, to serve them for historical transactions.
+    #[arg(long = "blobpool.retention", value_name = "BLOCKS", default_value_t = 0)]
+    pub blob_retention: u64,
+
     /// Max size in bytes of a single transaction allowed to enter the pool
     #[arg(long = "txpool.max-tx-input-bytes", alias = "txpool.max_tx_input_bytes", default_value_t = DEFAULT_MAX_TX_INPUT_BYTES)]
     pub max_tx_input_bytes: usize,
@@ -77,6 +82,7 @@ impl Default for TxPoolArgs {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
             price_bump: DEFAULT_PRICE_BUMP,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
+            blob_retention: 0,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
             max_cached_entries: DEFAULT_MAX_CACHED_BLOBS,
             no_locals: false,
@@ -118,6 +124,11 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             },
         }
     }
+
+    /// Returns the configuration of the transaction pool maintenance task.
+    fn maintain_pool_config(&self) -> MaintainPoolConfig {
+        MaintainPoolConfig { blob_retention: self.blob_retention, ..Default::default() }
+    }
 }
 
 #[cfg(test)]
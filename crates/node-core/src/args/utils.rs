@@ -104,8 +104,22 @@ pub fn genesis_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error
 
             // both serialized Genesis and ChainSpec structs supported
             let genesis: AllGenesisFormats = serde_json::from_str(&raw)?;
+            let mut chain_spec: ChainSpec = genesis.into();
 
-            Arc::new(genesis.into())
+            // geth's chain config has no field for the custom precompiles, so they are read from
+            // the raw json
+            if chain_spec.precompiles.is_empty() {
+                let mut value: serde_json::Value = serde_json::from_str(&raw)?;
+                if let Some(precompiles) = value
+                    .get_mut("config")
+                    .and_then(|config| config.get_mut("precompiles"))
+                    .map(serde_json::Value::take)
+                {
+                    chain_spec.precompiles = serde_json::from_value(precompiles)?;
+                }
+            }
+
+            Arc::new(chain_spec)
         }
     })
 }
@@ -168,7 +182,8 @@ mod tests {
     use super::*;
     use proptest::prelude::Rng;
     use reth_primitives::{
-        hex, Address, ChainConfig, ChainSpecBuilder, Genesis, GenesisAccount, U256,
+        address, hex, Address, BuiltinPrecompile, ChainConfig, ChainSpecBuilder, CustomPrecompile,
+        Genesis, GenesisAccount, PrecompileImplementation, U256,
     };
     use secp256k1::rand::thread_rng;
     use std::collections::HashMap;
@@ -272,6 +287,35 @@ mod tests {
         assert_eq!(custom_genesis_from_spec.chain(), chain_from_struct.chain());
     }
 
+    #[test]
+    fn parse_precompiles_from_geth_genesis() {
+        let custom_genesis_from_json = r#"
+{
+    "nonce": "0x0",
+    "timestamp": "0x0",
+    "gasLimit": "0x1388",
+    "difficulty": "0x0",
+    "alloc": {},
+    "config": {
+        "chainId": 2600,
+        "precompiles": [
+            { "address": "0x0000000000000000000000000000000000000100", "builtin": "sha256" }
+        ]
+    }
+}
+"#;
+
+        let chain_spec = genesis_value_parser(custom_genesis_from_json).unwrap();
+        assert_eq!(
+            chain_spec.precompiles,
+            vec![CustomPrecompile {
+                address: address!("0000000000000000000000000000000000000100"),
+                activation: None,
+                implementation: PrecompileImplementation::Builtin(BuiltinPrecompile::Sha256),
+            }]
+        );
+    }
+
     #[test]
     fn parse_socket_addresses() {
         for value in ["localhost:9000", ":9000", "9000"] {
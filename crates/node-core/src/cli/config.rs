@@ -2,16 +2,21 @@
 
 use reth_network::protocol::IntoRlpxSubProtocol;
 use reth_primitives::Bytes;
+#[cfg(feature = "rest")]
+use reth_rpc::rest::RestConfig;
 use reth_rpc::{
     eth::{cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig},
+    graphql::GraphQlConfig,
     JwtError, JwtSecret,
 };
 use reth_rpc_builder::{
     auth::AuthServerConfig, error::RpcError, EthConfig, Identity, IpcServerBuilder,
-    RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
+    RpcResponseCacheConfig, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
 };
-use reth_transaction_pool::PoolConfig;
-use std::{borrow::Cow, path::PathBuf, time::Duration};
+use reth_transaction_pool::{
+    maintain::MaintainPoolConfig, PayloadInclusionPolicy, PayloadOrderingPolicy, PoolConfig,
+};
+use std::{borrow::Cow, io, path::PathBuf, time::Duration};
 
 /// A trait that provides a configured RPC server.
 ///
@@ -54,6 +59,16 @@ pub trait RethRpcConfig {
     /// Creates the [RpcServerConfig] from cli args.
     fn rpc_server_config(&self) -> RpcServerConfig;
 
+    /// Returns the config of the cache of responses of calls for finalized data, if it is enabled.
+    fn response_cache_config(&self) -> Option<RpcResponseCacheConfig>;
+
+    /// Returns the config of the GraphQL API if it is enabled on the http server.
+    fn graphql_config(&self) -> Option<GraphQlConfig>;
+
+    /// Returns the config of the REST API if it is enabled on the http server.
+    #[cfg(feature = "rest")]
+    fn rest_config(&self) -> Option<RestConfig>;
+
     /// Creates the [AuthServerConfig] from cli args.
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError>;
 
@@ -102,6 +117,14 @@ pub trait PayloadBuilderConfig {
 
     /// Maximum number of tasks to spawn for building a payload.
     fn max_payload_tasks(&self) -> usize;
+
+    /// The order in which the transactions of the pool are tried for inclusion in a payload.
+    fn ordering(&self) -> PayloadOrderingPolicy;
+
+    /// The senders and addresses that are forced into or kept out of payloads.
+    ///
+    /// Returns an error if the blocklist file can not be read.
+    fn inclusion_policy(&self) -> io::Result<PayloadInclusionPolicy>;
 }
 
 /// A trait that represents the configured network and can be used to apply additional configuration
@@ -137,4 +160,7 @@ impl<C> RethNetworkConfig for reth_network::NetworkManager<C> {
 pub trait RethTransactionPoolConfig {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig;
+
+    /// Returns the configuration of the transaction pool maintenance task.
+    fn maintain_pool_config(&self) -> MaintainPoolConfig;
 }
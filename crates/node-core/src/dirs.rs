@@ -309,6 +309,14 @@ impl<D> ChainPath<D> {
         self.0.join("blobstore").into()
     }
 
+    /// Returns the path to the directory for this chain where sidechains of the blockchain tree
+    /// that fell behind the canonical tip are spilled to.
+    ///
+    /// `<DIR>/<CHAIN_ID>/tree-spill`
+    pub fn tree_spill_path(&self) -> PathBuf {
+        self.0.join("tree-spill").into()
+    }
+
     /// Returns the path to the local transactions backup file
     ///
     /// `<DIR>/<CHAIN_ID>/txpool-transactions-backup.rlp`
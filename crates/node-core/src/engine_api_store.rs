@@ -84,7 +84,8 @@ impl EngineApiStore {
             }
             // noop
             BeaconEngineMessage::TransitionConfigurationExchanged |
-            BeaconEngineMessage::EventListener(_) => (),
+            BeaconEngineMessage::EventListener(_) |
+            BeaconEngineMessage::Rewind { .. } => (),
         };
         Ok(())
     }
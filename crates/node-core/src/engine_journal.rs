@@ -0,0 +1,379 @@
+//! Journal of engine API requests and their responses.
+//!
+//! The journal is written to a directory of rotating files. Each file is a sequence of records,
+//! where every record is a little-endian `u32` length followed by the JSON encoded
+//! [EngineJournalEntry].
+
+use crate::engine_api_store::StoredEngineApiMessage;
+use futures::{future::BoxFuture, stream::FuturesUnordered, StreamExt};
+use reth_beacon_consensus::{BeaconEngineMessage, OnForkChoiceUpdated};
+use reth_engine_primitives::EngineTypes;
+use reth_primitives::fs;
+use reth_rpc_types::engine::{ForkchoiceUpdated, PayloadStatus};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tokio::sync::{
+    mpsc::{UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
+use tracing::*;
+
+/// The default maximum size of a single journal file in megabytes.
+pub const DEFAULT_MAX_FILE_SIZE_MB: u64 = 64;
+
+/// The default number of journal files that are kept.
+pub const DEFAULT_MAX_FILES: usize = 8;
+
+/// Prefix of the journal file names.
+const FILE_PREFIX: &str = "engine-journal-";
+
+/// Extension of the journal file names.
+const FILE_EXTENSION: &str = "bin";
+
+/// An engine API request and its response, as recorded in the journal.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EngineJournalEntry<Attributes> {
+    /// The sequence number of the request, in the order the requests were received since the
+    /// journal was opened.
+    pub seq: u64,
+    /// The time the request was received, in milliseconds since the unix epoch.
+    pub received_at: u64,
+    /// The time the response was returned, in milliseconds since the unix epoch.
+    pub responded_at: u64,
+    /// The request.
+    pub request: StoredEngineApiMessage<Attributes>,
+    /// The response to the request.
+    pub response: EngineJournalResponse,
+}
+
+/// The response to a journaled engine API request.
+///
+/// Errors are only recorded by their message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EngineJournalResponse {
+    /// The response to an `engine_forkchoiceUpdated` call.
+    ForkchoiceUpdated(Result<ForkchoiceUpdated, String>),
+    /// The response to an `engine_newPayload` call.
+    NewPayload(Result<PayloadStatus, String>),
+}
+
+/// Records engine API requests and their responses to a directory of rotating files.
+#[derive(Debug)]
+pub struct EngineJournal {
+    /// The directory the journal files are written to.
+    dir: PathBuf,
+    /// The size in bytes after which a new journal file is started.
+    max_file_size: u64,
+    /// The number of journal files that are kept, older files are removed.
+    max_files: usize,
+    /// The journal file that is currently written to.
+    file: Option<File>,
+    /// The index of the current journal file.
+    file_index: u64,
+    /// The number of bytes written to the current journal file.
+    file_size: u64,
+    /// The sequence number of the next request.
+    next_seq: u64,
+}
+
+impl EngineJournal {
+    /// Creates a new [EngineJournal] in the given directory.
+    ///
+    /// Existing journal files are kept, new entries are written to a new file.
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            max_file_size: DEFAULT_MAX_FILE_SIZE_MB * 1024 * 1024,
+            max_files: DEFAULT_MAX_FILES,
+            file: None,
+            file_index: 0,
+            file_size: 0,
+            next_seq: 0,
+        }
+    }
+
+    /// Sets the size in megabytes after which a new journal file is started.
+    pub fn with_max_file_size_mb(mut self, max_file_size_mb: u64) -> Self {
+        self.max_file_size = max_file_size_mb * 1024 * 1024;
+        self
+    }
+
+    /// Sets the number of journal files that are kept.
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files.max(1);
+        self
+    }
+
+    /// Appends an entry to the journal, starting a new journal file if the current one is full.
+    pub fn append<Attributes: Serialize>(
+        &mut self,
+        entry: &EngineJournalEntry<Attributes>,
+    ) -> eyre::Result<()> {
+        let json = serde_json::to_vec(entry)?;
+        let mut record = Vec::with_capacity(json.len() + 4);
+        record.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        record.extend_from_slice(&json);
+
+        if self.file.is_none() || self.file_size + record.len() as u64 > self.max_file_size {
+            self.rotate()?;
+        }
+        let file = self.file.as_mut().expect("journal file is open");
+        file.write_all(&record)?;
+        self.file_size += record.len() as u64;
+        Ok(())
+    }
+
+    /// Starts a new journal file and removes the oldest files exceeding the maximum number of
+    /// files.
+    fn rotate(&mut self) -> eyre::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let files = journal_files(&self.dir)?;
+        self.file_index = files.last().map(|(index, _)| index + 1).unwrap_or_default();
+
+        let path = self.dir.join(format!("{FILE_PREFIX}{:08}.{FILE_EXTENSION}", self.file_index));
+        self.file = Some(File::create(&path)?);
+        self.file_size = 0;
+        debug!(
+            target: "engine::journal",
+            path = %path.display(),
+            "Started new engine journal file"
+        );
+
+        let keep = self.max_files - 1;
+        for (_, path) in files.iter().take(files.len().saturating_sub(keep)) {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Reads all entries of the journal in the given directory, ordered by the time the requests
+    /// were received.
+    ///
+    /// A truncated record at the end of a journal file is skipped.
+    pub fn read_entries<Attributes: DeserializeOwned>(
+        dir: &Path,
+    ) -> eyre::Result<Vec<EngineJournalEntry<Attributes>>> {
+        let mut entries = Vec::new();
+        for (_, path) in journal_files(dir)? {
+            let content = fs::read(&path)?;
+            let mut remaining = content.as_slice();
+            while remaining.len() >= 4 {
+                let len = u32::from_le_bytes(remaining[..4].try_into().unwrap()) as usize;
+                let Some(record) = remaining.get(4..4 + len) else {
+                    warn!(
+                        target: "engine::journal",
+                        path = %path.display(),
+                        "Skipping truncated engine journal record"
+                    );
+                    break
+                };
+                entries.push(serde_json::from_slice(record)?);
+                remaining = &remaining[4 + len..];
+            }
+        }
+        entries
+            .sort_by_key(|entry: &EngineJournalEntry<Attributes>| (entry.received_at, entry.seq));
+        Ok(entries)
+    }
+
+    /// Intercepts incoming engine API messages, forwarding them to the engine channel and
+    /// recording `engine_forkchoiceUpdated` and `engine_newPayload` requests together with their
+    /// responses.
+    pub async fn intercept<Engine>(
+        mut self,
+        mut rx: UnboundedReceiver<BeaconEngineMessage<Engine>>,
+        to_engine: UnboundedSender<BeaconEngineMessage<Engine>>,
+    ) where
+        Engine: EngineTypes,
+    {
+        let mut pending = FuturesUnordered::new();
+        loop {
+            tokio::select! {
+                msg = rx.recv() => {
+                    let Some(msg) = msg else { break };
+                    let (msg, entry) = self.track(msg);
+                    pending.extend(entry);
+                    let _ = to_engine.send(msg);
+                }
+                Some(entry) = pending.next() => self.on_entry(entry),
+            }
+        }
+
+        // record the responses to the remaining requests
+        while let Some(entry) = pending.next().await {
+            self.on_entry(entry);
+        }
+    }
+
+    /// Replaces the response channel of a request with one that records the response before
+    /// forwarding it.
+    ///
+    /// Returns the message to forward to the engine and a future that resolves to the journal
+    /// entry once the engine responded, if the message is journaled.
+    fn track<Engine>(
+        &mut self,
+        msg: BeaconEngineMessage<Engine>,
+    ) -> (
+        BeaconEngineMessage<Engine>,
+        Option<BoxFuture<'static, EngineJournalEntry<Engine::PayloadAttributes>>>,
+    )
+    where
+        Engine: EngineTypes,
+    {
+        let seq = self.next_seq;
+        let received_at = unix_millis();
+        match msg {
+            BeaconEngineMessage::NewPayload { payload, cancun_fields, tx } => {
+                self.next_seq += 1;
+                let request = StoredEngineApiMessage::NewPayload {
+                    payload: payload.clone(),
+                    cancun_fields: cancun_fields.clone(),
+                };
+                let (journal_tx, journal_rx) = oneshot::channel();
+                let entry = async move {
+                    let response = match journal_rx.await {
+                        Ok(res) => {
+                            let recorded = res.as_ref().cloned().map_err(ToString::to_string);
+                            let _ = tx.send(res);
+                            recorded
+                        }
+                        Err(err) => Err(err.to_string()),
+                    };
+                    EngineJournalEntry {
+                        seq,
+                        received_at,
+                        responded_at: unix_millis(),
+                        request,
+                        response: EngineJournalResponse::NewPayload(response),
+                    }
+                };
+                (
+                    BeaconEngineMessage::NewPayload { payload, cancun_fields, tx: journal_tx },
+                    Some(Box::pin(entry)),
+                )
+            }
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx } => {
+                self.next_seq += 1;
+                let request = StoredEngineApiMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs: payload_attrs.clone(),
+                };
+                let (journal_tx, journal_rx) = oneshot::channel();
+                let entry = async move {
+                    let response = match journal_rx.await {
+                        Ok(Ok(on_updated)) => {
+                            // resolve the payload id, so that the full response is recorded
+                            let status = on_updated.forkchoice_status();
+                            let res = on_updated.await;
+                            let recorded = res.as_ref().cloned().map_err(ToString::to_string);
+                            let _ = tx.send(Ok(OnForkChoiceUpdated::resolved(status, res)));
+                            recorded
+                        }
+                        Ok(Err(err)) => {
+                            let recorded = Err(err.to_string());
+                            let _ = tx.send(Err(err));
+                            recorded
+                        }
+                        Err(err) => Err(err.to_string()),
+                    };
+                    EngineJournalEntry {
+                        seq,
+                        received_at,
+                        responded_at: unix_millis(),
+                        request,
+                        response: EngineJournalResponse::ForkchoiceUpdated(response),
+                    }
+                };
+                (
+                    BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx: journal_tx },
+                    Some(Box::pin(entry)),
+                )
+            }
+            msg => (msg, None),
+        }
+    }
+
+    fn on_entry<Attributes: Serialize>(&mut self, entry: EngineJournalEntry<Attributes>) {
+        if let Err(error) = self.append(&entry) {
+            error!(
+                target: "engine::journal",
+                seq = entry.seq,
+                %error,
+                "Failed to write engine journal entry"
+            );
+        }
+    }
+}
+
+/// Returns the journal files in the directory with their index, ordered by index.
+fn journal_files(dir: &Path) -> eyre::Result<Vec<(u64, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let index = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(FILE_PREFIX))
+            .and_then(|name| name.strip_suffix(FILE_EXTENSION))
+            .and_then(|name| name.strip_suffix('.'))
+            .and_then(|index| index.parse::<u64>().ok());
+        if let Some(index) = index {
+            files.push((index, path));
+        }
+    }
+    files.sort_unstable();
+    Ok(files)
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::engine::{ForkchoiceState, PayloadStatusEnum};
+
+    fn entry(seq: u64) -> EngineJournalEntry<()> {
+        EngineJournalEntry {
+            seq,
+            received_at: seq,
+            responded_at: seq,
+            request: StoredEngineApiMessage::ForkchoiceUpdated {
+                state: ForkchoiceState::default(),
+                payload_attrs: None,
+            },
+            response: EngineJournalResponse::ForkchoiceUpdated(Ok(ForkchoiceUpdated::new(
+                PayloadStatus::from_status(PayloadStatusEnum::Syncing),
+            ))),
+        }
+    }
+
+    #[test]
+    fn append_rotate_and_read() {
+        let dir = tempfile::tempdir().unwrap();
+        // every entry exceeds the maximum file size and starts a new file
+        let mut journal =
+            EngineJournal::new(dir.path().to_path_buf()).with_max_file_size_mb(0).with_max_files(2);
+        for seq in 0..3 {
+            journal.append(&entry(seq)).unwrap();
+        }
+        assert_eq!(journal_files(dir.path()).unwrap().len(), 2);
+
+        let entries = EngineJournal::read_entries::<()>(dir.path()).unwrap();
+        assert_eq!(entries.iter().map(|entry| entry.seq).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(entries[0].response, entry(1).response);
+
+        // a new journal continues after the existing files
+        let mut journal = EngineJournal::new(dir.path().to_path_buf());
+        journal.append(&entry(3)).unwrap();
+        assert_eq!(journal_files(dir.path()).unwrap().last().unwrap().0, 3);
+    }
+}
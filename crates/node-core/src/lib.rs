@@ -12,6 +12,7 @@ pub mod args;
 pub mod cli;
 pub mod dirs;
 pub mod engine_api_store;
+pub mod engine_journal;
 pub mod engine_skip_fcu;
 pub mod exit;
 pub mod init;
@@ -2,8 +2,8 @@
 
 use crate::{
     args::{
-        get_secret_key, DatabaseArgs, DebugArgs, DevArgs, DiscoveryArgs, NetworkArgs,
-        PayloadBuilderArgs, PruningArgs, RpcServerArgs, TxPoolArgs,
+        get_secret_key, DatabaseArgs, DebugArgs, DevArgs, DiscoveryArgs, EngineArgs, ExExArgs,
+        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, StaticFilesArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
     metrics::prometheus_exporter,
@@ -138,11 +138,20 @@ pub struct NodeConfig {
     /// All database related arguments
     pub db: DatabaseArgs,
 
+    /// All static files related arguments
+    pub static_files: StaticFilesArgs,
+
     /// All dev related arguments with --dev prefix
     pub dev: DevArgs,
 
     /// All pruning related arguments
     pub pruning: PruningArgs,
+
+    /// All engine related arguments with --engine prefix
+    pub engine: EngineArgs,
+
+    /// All built-in execution extensions related arguments with --exex prefix
+    pub exex: ExExArgs,
 }
 
 impl NodeConfig {
@@ -219,6 +228,12 @@ impl NodeConfig {
         self
     }
 
+    /// Set the static files args for the node
+    pub fn with_static_files(mut self, static_files: StaticFilesArgs) -> Self {
+        self.static_files = static_files;
+        self
+    }
+
     /// Set the dev args for the node
     pub fn with_dev(mut self, dev: DevArgs) -> Self {
         self.dev = dev;
@@ -231,6 +246,18 @@ impl NodeConfig {
         self
     }
 
+    /// Set the engine args for the node
+    pub fn with_engine(mut self, engine: EngineArgs) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Set the built-in execution extensions args for the node
+    pub fn with_exex(mut self, exex: ExExArgs) -> Self {
+        self.exex = exex;
+        self
+    }
+
     /// Get the network secret from the given data dir
     pub fn network_secret(&self, data_dir: &ChainPath<DataDirPath>) -> eyre::Result<SecretKey> {
         let network_secret_path =
@@ -513,8 +540,11 @@ impl Default for NodeConfig {
             builder: PayloadBuilderArgs::default(),
             debug: DebugArgs::default(),
             db: DatabaseArgs::default(),
+            static_files: StaticFilesArgs::default(),
             dev: DevArgs::default(),
             pruning: PruningArgs::default(),
+            engine: EngineArgs::default(),
+            exex: ExExArgs::default(),
         }
     }
 }
@@ -1,6 +1,10 @@
 //! Utility functions for node startup and shutdown, for example path parsing and retrieving single
 //! blocks from the network.
 
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal,
+};
 use eyre::Result;
 use reth_consensus_common::validation::validate_block_standalone;
 use reth_interfaces::p2p::{
@@ -16,6 +20,7 @@ use reth_provider::BlockReader;
 use reth_rpc::{JwtError, JwtSecret};
 use std::{
     env::VarError,
+    io::{self, Write},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -27,6 +32,41 @@ pub fn parse_path(value: &str) -> Result<PathBuf, shellexpand::LookupError<VarEr
     shellexpand::full(value).map(|path| PathBuf::from(path.into_owned()))
 }
 
+/// Prompts for a password on the terminal, without echoing the input.
+pub fn prompt_password(prompt: &str) -> Result<String> {
+    eprint!("{prompt}");
+    io::stderr().flush()?;
+
+    terminal::enable_raw_mode()?;
+    let password = read_password();
+    terminal::disable_raw_mode()?;
+    eprintln!();
+    password
+}
+
+/// Reads keys from the terminal in raw mode until enter is pressed.
+fn read_password() -> Result<String> {
+    let mut password = String::new();
+    loop {
+        let Event::Key(KeyEvent { code, modifiers, kind: KeyEventKind::Press, .. }) =
+            event::read()?
+        else {
+            continue
+        };
+        match code {
+            KeyCode::Enter => return Ok(password),
+            KeyCode::Backspace => {
+                password.pop();
+            }
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                eyre::bail!("password prompt aborted")
+            }
+            KeyCode::Char(c) => password.push(c),
+            _ => {}
+        }
+    }
+}
+
 /// Attempts to retrieve or create a JWT secret from the specified path.
 pub fn get_or_create_jwt_secret_from_path(path: &Path) -> Result<JwtSecret, JwtError> {
     if path.exists() {
@@ -126,7 +126,7 @@ where
                     pool,
                     chain_events,
                     ctx.task_executor().clone(),
-                    Default::default(),
+                    ctx.maintain_pool_config(),
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
@@ -151,8 +151,15 @@ where
         ctx: &BuilderContext<Node>,
         pool: Pool,
     ) -> eyre::Result<PayloadBuilderHandle<Node::Engine>> {
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
         let conf = ctx.payload_builder_config();
+        let inclusion_policy = conf.inclusion_policy()?;
+        ctx.task_executor().spawn(Box::pin(
+            reth_transaction_pool::payload_policy::refresh_blocklist_task(inclusion_policy.clone()),
+        ));
+        let payload_builder =
+            reth_ethereum_payload_builder::EthereumPayloadBuilder::new(ctx.evm_config().clone())
+                .with_ordering(conf.ordering())
+                .with_inclusion_policy(inclusion_policy);
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval())
@@ -28,7 +28,9 @@ use reth_revm::state_change::{
     apply_beacon_root_contract_call, post_block_withdrawals_balance_increments,
 };
 use reth_tasks::TaskSpawner;
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{
+    NewTransactionEvent, SubPool, TransactionListenerKind, TransactionPool,
+};
 use revm::{
     primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg},
     Database, DatabaseCommit, Evm, State,
@@ -41,13 +43,20 @@ use std::{
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{oneshot, Semaphore},
-    time::{Interval, Sleep},
+    sync::{mpsc::Receiver, oneshot, Semaphore},
+    time::{Instant, Interval, Sleep},
 };
 use tracing::{debug, trace, warn};
 
 mod metrics;
 
+/// The gas of the cheapest possible transaction, a plain transfer.
+const MIN_TRANSACTION_GAS: u64 = 21_000;
+
+/// The default fees new pending transactions must add to the best payload to trigger a rebuild
+/// before the next interval: 0.0001 ether.
+pub const DEFAULT_MIN_REBID_FEES: u128 = 100_000_000_000_000;
+
 /// The [`PayloadJobGenerator`] that creates [`BasicPayloadJob`]s.
 #[derive(Debug)]
 pub struct BasicPayloadJobGenerator<Client, Pool, Tasks, Builder> {
@@ -112,11 +121,18 @@ impl<Client, Pool, Tasks, Builder> BasicPayloadJobGenerator<Client, Pool, Tasks,
         self.config.deadline + duration_until_timestamp
     }
 
-    /// Returns the [Instant](tokio::time::Instant) at which the job should be terminated because it
-    /// is considered timed out.
+    /// Returns the [Instant] at which the job should be terminated because it is considered timed
+    /// out.
+    #[inline]
+    fn job_deadline(&self, unix_timestamp: u64) -> Instant {
+        Instant::now() + self.max_job_duration(unix_timestamp)
+    }
+
+    /// Returns the [Instant] at which the payload is expected to be requested, which is the
+    /// timestamp of the payload.
     #[inline]
-    fn job_deadline(&self, unix_timestamp: u64) -> tokio::time::Instant {
-        tokio::time::Instant::now() + self.max_job_duration(unix_timestamp)
+    fn payload_deadline(&self, unix_timestamp: u64) -> Instant {
+        Instant::now() + duration_until(unix_timestamp).min(self.config.deadline)
     }
 
     /// Returns a reference to the tasks type
@@ -174,6 +190,7 @@ where
 
         let until = self.job_deadline(config.attributes.timestamp());
         let deadline = Box::pin(tokio::time::sleep_until(until));
+        let payload_deadline = self.payload_deadline(config.attributes.timestamp());
 
         let cached_reads = self.maybe_pre_cached(config.parent_block.hash());
 
@@ -183,9 +200,21 @@ where
             pool: self.pool.clone(),
             executor: self.executor.clone(),
             deadline,
+            payload_deadline,
             interval: tokio::time::interval(self.config.interval),
+            new_transactions: self.pool.new_transactions_listener_for(TransactionListenerKind::All),
             best_payload: None,
+            better_payload: false,
+            rebid_threshold: None,
+            min_rebid_fees: self.config.min_rebid_fees,
+            rebid_fees: 0,
+            rebid: false,
             pending_block: None,
+            build_started: None,
+            last_build_duration: None,
+            iterations: 0,
+            first_fees: None,
+            best_fees: U256::ZERO,
             cached_reads,
             payload_task_guard: self.payload_task_guard.clone(),
             metrics: Default::default(),
@@ -251,6 +280,11 @@ pub struct BasicPayloadJobGeneratorConfig {
     deadline: Duration,
     /// Maximum number of tasks to spawn for building a payload.
     max_payload_tasks: usize,
+    /// The fees, in wei, new pending transactions must add to the best payload to trigger a
+    /// rebuild before the next interval.
+    ///
+    /// By default this is [DEFAULT_MIN_REBID_FEES].
+    min_rebid_fees: u128,
 }
 
 // === impl BasicPayloadJobGeneratorConfig ===
@@ -279,6 +313,15 @@ impl BasicPayloadJobGeneratorConfig {
         self
     }
 
+    /// Sets the fees, in wei, new pending transactions must add to the best payload to trigger a
+    /// rebuild before the next interval.
+    ///
+    /// Defaults to [DEFAULT_MIN_REBID_FEES].
+    pub fn min_rebid_fees(mut self, min_rebid_fees: u128) -> Self {
+        self.min_rebid_fees = min_rebid_fees;
+        self
+    }
+
     /// Sets the data to include in the block's extra data field.
     ///
     /// Defaults to the current client version: `rlp(RETH_CLIENT_VERSION)`.
@@ -305,14 +348,21 @@ impl Default for BasicPayloadJobGeneratorConfig {
             // 12s slot time
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            min_rebid_fees: DEFAULT_MIN_REBID_FEES,
         }
     }
 }
 
 /// A basic payload job that continuously builds a payload with the best transactions from the pool.
+///
+/// The payload is rebuilt at every interval and right away when the pending transactions that
+/// arrived since the last build may add at least the configured minimum fees to the best payload,
+/// either because they fit into it or because they pay a higher tip than its cheapest transaction.
+/// A new build is not started if it is not expected to finish before the payload is requested.
 #[derive(Debug)]
 pub struct BasicPayloadJob<Client, Pool, Tasks, Builder>
 where
+    Pool: TransactionPool,
     Builder: PayloadBuilder<Pool, Client>,
 {
     /// The configuration for how the payload will be created.
@@ -325,12 +375,39 @@ where
     executor: Tasks,
     /// The deadline when this job should resolve.
     deadline: Pin<Box<Sleep>>,
+    /// The time at which the payload is expected to be requested.
+    payload_deadline: Instant,
     /// The interval at which the job should build a new payload after the last.
     interval: Interval,
+    /// Listener for new transactions that may improve the best payload.
+    new_transactions: Receiver<NewTransactionEvent<Pool::Transaction>>,
     /// The best payload so far.
     best_payload: Option<Builder::BuiltPayload>,
+    /// Whether the best payload was replaced since it was last taken with
+    /// [PayloadJob::take_better_payload].
+    better_payload: bool,
+    /// What a new transaction must pay to improve the best payload.
+    rebid_threshold: Option<RebidThreshold>,
+    /// The fees new transactions must add to the best payload to trigger a rebuild.
+    min_rebid_fees: u128,
+    /// The fees the transactions that arrived since the last build was started may add to the
+    /// best payload.
+    rebid_fees: u128,
+    /// Whether the transactions that arrived since the last build was started may improve the best
+    /// payload by at least `min_rebid_fees`.
+    rebid: bool,
     /// Receiver for the block that is currently being built.
     pending_block: Option<PendingPayload<Builder::BuiltPayload>>,
+    /// When the block that is currently being built was started.
+    build_started: Option<Instant>,
+    /// How long the last build attempt took.
+    last_build_duration: Option<Duration>,
+    /// The number of completed build attempts.
+    iterations: u64,
+    /// The fees of the first payload that was built.
+    first_fees: Option<U256>,
+    /// The fees of the best payload so far.
+    best_fees: U256,
     /// Restricts how many generator tasks can be executed at once.
     payload_task_guard: PayloadTaskGuard,
     /// Caches all disk reads for the state the new payloads builds on
@@ -366,66 +443,92 @@ where
             return Poll::Ready(Ok(()))
         }
 
+        // check for new pending transactions that would improve the best payload
+        while let Poll::Ready(Some(event)) = this.new_transactions.poll_recv(cx) {
+            if event.subpool != SubPool::Pending {
+                continue
+            }
+            if let Some(threshold) = this.rebid_threshold {
+                let tip = event.transaction.effective_tip_per_gas(threshold.base_fee);
+                let fees = threshold.improvement(tip, event.transaction.gas_limit());
+                this.rebid_fees = this.rebid_fees.saturating_add(fees);
+                if fees > 0 && this.rebid_fees >= this.min_rebid_fees {
+                    this.rebid = true;
+                }
+            }
+        }
+
         // check if the interval is reached
+        let mut build = false;
         while this.interval.poll_tick(cx).is_ready() {
-            // start a new job if there is no pending block and we haven't reached the deadline
-            if this.pending_block.is_none() {
-                trace!(target: "payload_builder", "spawn new payload build task");
-                let (tx, rx) = oneshot::channel();
-                let client = this.client.clone();
-                let pool = this.pool.clone();
-                let cancel = Cancelled::default();
-                let _cancel = cancel.clone();
-                let guard = this.payload_task_guard.clone();
-                let payload_config = this.config.clone();
-                let best_payload = this.best_payload.clone();
-                this.metrics.inc_initiated_payload_builds();
-                let cached_reads = this.cached_reads.take().unwrap_or_default();
-                let builder = this.builder.clone();
-                this.executor.spawn_blocking(Box::pin(async move {
-                    // acquire the permit for executing the task
-                    let _permit = guard.0.acquire().await;
-                    let args = BuildArguments {
-                        client,
-                        pool,
-                        cached_reads,
-                        config: payload_config,
-                        cancel,
-                        best_payload,
-                    };
-                    let result = builder.try_build(args);
-                    let _ = tx.send(result);
-                }));
-
-                this.pending_block = Some(PendingPayload { _cancel, payload: rx });
+            build = true;
+        }
+
+        // start a new job if there is no pending block and a new build is due
+        if (build || this.rebid) && this.pending_block.is_none() {
+            if this.can_finish_before_payload_deadline() {
+                if !build {
+                    trace!(target: "payload_builder", "re-bidding for better transactions");
+                    this.metrics.inc_payload_rebids();
+                }
+                this.spawn_build();
+            } else {
+                trace!(
+                    target: "payload_builder",
+                    "skipping payload build that would finish after the payload is requested"
+                );
+                this.metrics.inc_skipped_payload_builds();
+                this.rebid = false;
+                this.rebid_fees = 0;
             }
         }
 
         // poll the pending block
         if let Some(mut fut) = this.pending_block.take() {
             match fut.poll_unpin(cx) {
-                Poll::Ready(Ok(outcome)) => {
-                    this.interval.reset();
-                    match outcome {
-                        BuildOutcome::Better { payload, cached_reads } => {
-                            this.cached_reads = Some(cached_reads);
-                            debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
-                            let payload = payload;
-                            this.best_payload = Some(payload);
+                Poll::Ready(res) => {
+                    this.on_build_finished();
+                    match res {
+                        Ok(outcome) => {
+                            this.interval.reset();
+                            match outcome {
+                                BuildOutcome::Better { payload, cached_reads } => {
+                                    this.cached_reads = Some(cached_reads);
+                                    debug!(
+                                        target: "payload_builder",
+                                        value = %payload.fees(),
+                                        "built better payload"
+                                    );
+                                    this.on_better_payload(payload);
+                                }
+                                BuildOutcome::Aborted { fees, cached_reads } => {
+                                    this.cached_reads = Some(cached_reads);
+                                    trace!(
+                                        target: "payload_builder",
+                                        worse_fees = %fees,
+                                        "skipped payload build of worse block"
+                                    );
+                                }
+                                BuildOutcome::Cancelled => {
+                                    unreachable!("the cancel signal never fired")
+                                }
+                            }
                         }
-                        BuildOutcome::Aborted { fees, cached_reads } => {
-                            this.cached_reads = Some(cached_reads);
-                            trace!(target: "payload_builder", worse_fees = %fees, "skipped payload build of worse block");
-                        }
-                        BuildOutcome::Cancelled => {
-                            unreachable!("the cancel signal never fired")
+                        Err(error) => {
+                            // job failed, but we simply try again next interval
+                            debug!(
+                                target: "payload_builder",
+                                %error,
+                                "payload build attempt failed"
+                            );
+                            this.metrics.inc_failed_payload_builds();
                         }
                     }
-                }
-                Poll::Ready(Err(error)) => {
-                    // job failed, but we simply try again next interval
-                    debug!(target: "payload_builder", %error, "payload build attempt failed");
-                    this.metrics.inc_failed_payload_builds();
+
+                    // better transactions arrived while building, re-bid right away
+                    if this.rebid {
+                        cx.waker().wake_by_ref();
+                    }
                 }
                 Poll::Pending => {
                     this.pending_block = Some(fut);
@@ -437,6 +540,100 @@ where
     }
 }
 
+impl<Client, Pool, Tasks, Builder> BasicPayloadJob<Client, Pool, Tasks, Builder>
+where
+    Client: StateProviderFactory + Clone + Unpin + 'static,
+    Pool: TransactionPool + Unpin + 'static,
+    Tasks: TaskSpawner + Clone + 'static,
+    Builder: PayloadBuilder<Pool, Client> + Unpin + 'static,
+    <Builder as PayloadBuilder<Pool, Client>>::Attributes: Unpin + Clone,
+    <Builder as PayloadBuilder<Pool, Client>>::BuiltPayload: Unpin + Clone,
+{
+    /// Spawns a new payload build task with the best transactions of the pool.
+    fn spawn_build(&mut self) {
+        trace!(target: "payload_builder", "spawn new payload build task");
+        let (tx, rx) = oneshot::channel();
+        let client = self.client.clone();
+        let pool = self.pool.clone();
+        let cancel = Cancelled::default();
+        let _cancel = cancel.clone();
+        let guard = self.payload_task_guard.clone();
+        let payload_config = self.config.clone();
+        let best_payload = self.best_payload.clone();
+        self.metrics.inc_initiated_payload_builds();
+        let cached_reads = self.cached_reads.take().unwrap_or_default();
+        let builder = self.builder.clone();
+        self.executor.spawn_blocking(Box::pin(async move {
+            // acquire the permit for executing the task
+            let _permit = guard.0.acquire().await;
+            let args = BuildArguments {
+                client,
+                pool,
+                cached_reads,
+                config: payload_config,
+                cancel,
+                best_payload,
+            };
+            let result = builder.try_build(args);
+            let _ = tx.send(result);
+        }));
+
+        self.pending_block = Some(PendingPayload { _cancel, payload: rx });
+        self.build_started = Some(Instant::now());
+        // the new build includes all transactions that arrived so far
+        self.rebid = false;
+        self.rebid_fees = 0;
+    }
+}
+
+impl<Client, Pool, Tasks, Builder> BasicPayloadJob<Client, Pool, Tasks, Builder>
+where
+    Pool: TransactionPool,
+    Builder: PayloadBuilder<Pool, Client>,
+{
+    /// Returns `true` if a new build is expected to finish before the payload is requested.
+    ///
+    /// This is always the case if there is no payload yet or if the payload was not requested in
+    /// time, otherwise the duration of the last build is used as the estimate.
+    fn can_finish_before_payload_deadline(&self) -> bool {
+        can_finish_before(
+            Instant::now(),
+            self.payload_deadline,
+            self.best_payload.is_some().then_some(self.last_build_duration).flatten(),
+        )
+    }
+
+    /// Records the completion of a build attempt.
+    fn on_build_finished(&mut self) {
+        self.iterations += 1;
+        if let Some(started) = self.build_started.take() {
+            self.last_build_duration = Some(started.elapsed());
+        }
+    }
+
+    /// Replaces the best payload with the given better payload.
+    fn on_better_payload(&mut self, payload: Builder::BuiltPayload) {
+        self.first_fees.get_or_insert(payload.fees());
+        self.best_fees = payload.fees();
+        self.rebid_threshold = Some(RebidThreshold::new(payload.block()));
+        self.best_payload = Some(payload);
+        self.better_payload = true;
+    }
+}
+
+impl<Client, Pool, Tasks, Builder> Drop for BasicPayloadJob<Client, Pool, Tasks, Builder>
+where
+    Pool: TransactionPool,
+    Builder: PayloadBuilder<Pool, Client>,
+{
+    fn drop(&mut self) {
+        self.metrics.record_payload_build_iterations(self.iterations);
+        if let Some(first_fees) = self.first_fees {
+            self.metrics.record_payload_revenue_delta(self.best_fees.saturating_sub(first_fees));
+        }
+    }
+}
+
 impl<Client, Pool, Tasks, Builder> PayloadJob for BasicPayloadJob<Client, Pool, Tasks, Builder>
 where
     Client: StateProviderFactory + Clone + Unpin + 'static,
@@ -468,6 +665,13 @@ where
         Ok(self.config.attributes.clone())
     }
 
+    fn take_better_payload(&mut self) -> Option<Self::BuiltPayload> {
+        if !std::mem::take(&mut self.better_payload) {
+            return None
+        }
+        self.best_payload.clone()
+    }
+
     fn resolve(&mut self) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
         let best_payload = self.best_payload.take();
         let maybe_better = self.pending_block.take();
@@ -893,6 +1097,55 @@ pub fn is_better_payload(best_payload: Option<impl BuiltPayload>, new_fees: U256
     }
 }
 
+/// What a new transaction must pay to improve a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RebidThreshold {
+    /// The base fee of the block.
+    base_fee: u64,
+    /// The effective tip a new transaction must exceed to improve the block.
+    ///
+    /// This is zero if the block has room for another transaction.
+    min_tip: u128,
+}
+
+impl RebidThreshold {
+    /// Returns the threshold of the given block.
+    fn new(block: &SealedBlock) -> Self {
+        let base_fee = block.base_fee_per_gas.unwrap_or_default();
+        if block.gas_limit.saturating_sub(block.gas_used) >= MIN_TRANSACTION_GAS {
+            return Self { base_fee, min_tip: 0 }
+        }
+        let min_tip = block
+            .body
+            .iter()
+            .filter_map(|tx| tx.effective_tip_per_gas(Some(base_fee)))
+            .min()
+            .unwrap_or_default();
+        Self { base_fee, min_tip }
+    }
+
+    /// Returns the fees a transaction with the given effective tip and gas limit may add to the
+    /// block, at most the tip above `min_tip` for its entire gas limit.
+    fn improvement(&self, tip: Option<u128>, gas_limit: u64) -> u128 {
+        tip.map_or(0, |tip| tip.saturating_sub(self.min_tip).saturating_mul(gas_limit as u128))
+    }
+}
+
+/// Returns `true` if a build started `now` is expected to finish before the payload is requested
+/// at `payload_deadline`, given the duration of the last build that produced a payload.
+///
+/// Without a previous build, or once the deadline has passed, a build is always started.
+fn can_finish_before(
+    now: Instant,
+    payload_deadline: Instant,
+    last_build_duration: Option<Duration>,
+) -> bool {
+    match last_build_duration {
+        Some(duration) if now < payload_deadline => now + duration <= payload_deadline,
+        _ => true,
+    }
+}
+
 /// Returns the duration until the given unix timestamp in seconds.
 ///
 /// Returns `Duration::ZERO` if the given timestamp is in the past.
@@ -901,3 +1154,279 @@ fn duration_until(unix_timestamp_secs: u64) -> Duration {
     let timestamp = Duration::from_secs(unix_timestamp_secs);
     timestamp.saturating_sub(unix_now)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::task::noop_waker_ref;
+    use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
+    use reth_primitives::{
+        Address, Block, Header, Signature, Transaction, TransactionKind, TransactionSigned,
+        TxEip1559, MAINNET,
+    };
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::test_utils::{
+        testing_pool, MockTransaction, MockTransactionFactory, TestPool,
+    };
+    use std::{
+        collections::VecDeque,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+    };
+    use tokio::sync::mpsc::Sender;
+
+    type TestJob = BasicPayloadJob<MockEthProvider, TestPool, TokioTaskExecutor, MockBuilder>;
+
+    /// A builder that builds payloads with the queued fees.
+    #[derive(Debug, Clone, Default)]
+    struct MockBuilder {
+        fees: Arc<Mutex<VecDeque<u64>>>,
+        builds: Arc<AtomicUsize>,
+    }
+
+    impl MockBuilder {
+        fn with_fees(fees: impl IntoIterator<Item = u64>) -> Self {
+            Self { fees: Arc::new(Mutex::new(fees.into_iter().collect())), ..Default::default() }
+        }
+
+        fn builds(&self) -> usize {
+            self.builds.load(Ordering::Relaxed)
+        }
+    }
+
+    impl PayloadBuilder<TestPool, MockEthProvider> for MockBuilder {
+        type Attributes = EthPayloadBuilderAttributes;
+        type BuiltPayload = EthBuiltPayload;
+
+        fn try_build(
+            &self,
+            args: BuildArguments<TestPool, MockEthProvider, Self::Attributes, Self::BuiltPayload>,
+        ) -> Result<BuildOutcome<Self::BuiltPayload>, PayloadBuilderError> {
+            self.builds.fetch_add(1, Ordering::Relaxed);
+            let fees = U256::from(self.fees.lock().unwrap().pop_front().unwrap_or_default());
+            if !is_better_payload(args.best_payload, fees) {
+                return Ok(BuildOutcome::Aborted { fees, cached_reads: args.cached_reads })
+            }
+            let payload = EthBuiltPayload::new(args.config.payload_id(), block(0, vec![]), fees);
+            Ok(BuildOutcome::Better { payload, cached_reads: args.cached_reads })
+        }
+
+        fn build_empty_payload(
+            _client: &MockEthProvider,
+            config: PayloadConfig<Self::Attributes>,
+        ) -> Result<Self::BuiltPayload, PayloadBuilderError> {
+            Ok(EthBuiltPayload::new(config.payload_id(), block(0, vec![]), U256::ZERO))
+        }
+    }
+
+    /// Returns a job whose interval and deadlines are a minute away, and the sender of its pending
+    /// transactions.
+    fn payload_job(
+        builder: MockBuilder,
+    ) -> (TestJob, Sender<NewTransactionEvent<MockTransaction>>) {
+        let (events, new_transactions) = tokio::sync::mpsc::channel(16);
+        let attributes = EthPayloadBuilderAttributes {
+            id: PayloadId::new([0; 8]),
+            parent: B256::ZERO,
+            timestamp: 0,
+            suggested_fee_recipient: Address::ZERO,
+            prev_randao: B256::ZERO,
+            withdrawals: Default::default(),
+            parent_beacon_block_root: None,
+        };
+        let job = BasicPayloadJob {
+            config: PayloadConfig::new(
+                Arc::new(SealedBlock::default()),
+                Bytes::default(),
+                attributes,
+                MAINNET.clone(),
+            ),
+            client: MockEthProvider::default(),
+            pool: testing_pool(),
+            executor: TokioTaskExecutor::default(),
+            deadline: Box::pin(tokio::time::sleep(Duration::from_secs(60))),
+            payload_deadline: Instant::now() + Duration::from_secs(60),
+            interval: tokio::time::interval(Duration::from_secs(60)),
+            new_transactions,
+            best_payload: None,
+            better_payload: false,
+            rebid_threshold: None,
+            min_rebid_fees: 1_000_000,
+            rebid_fees: 0,
+            rebid: false,
+            pending_block: None,
+            build_started: None,
+            last_build_duration: None,
+            iterations: 0,
+            first_fees: None,
+            best_fees: U256::ZERO,
+            payload_task_guard: PayloadTaskGuard::new(1),
+            cached_reads: None,
+            metrics: Default::default(),
+            builder,
+        };
+        (job, events)
+    }
+
+    /// Returns the event of a pending transfer with the given tip above the base fee of the built
+    /// blocks.
+    fn pending_transfer(tip: u128) -> NewTransactionEvent<MockTransaction> {
+        let transaction = MockTransaction::eip1559()
+            .with_max_fee(10 + tip)
+            .with_priority_fee(tip)
+            .with_gas_limit(MIN_TRANSACTION_GAS);
+        NewTransactionEvent {
+            subpool: SubPool::Pending,
+            transaction: MockTransactionFactory::default().validated_arc(transaction),
+        }
+    }
+
+    fn poll_once(job: &mut TestJob) {
+        let mut cx = Context::from_waker(noop_waker_ref());
+        assert!(job.poll_unpin(&mut cx).is_pending());
+    }
+
+    /// Polls the job until the condition holds.
+    async fn poll_until(job: &mut TestJob, condition: impl Fn(&TestJob) -> bool) {
+        let poll = async {
+            while !condition(job) {
+                poll_once(job);
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(10), poll).await.expect("condition not reached");
+    }
+
+    fn transaction(max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> TransactionSigned {
+        TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip1559(TxEip1559 {
+                gas_limit: MIN_TRANSACTION_GAS,
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+                to: TransactionKind::Call(Default::default()),
+                ..Default::default()
+            }),
+            Signature::default(),
+        )
+    }
+
+    fn block(gas_used: u64, body: Vec<TransactionSigned>) -> SealedBlock {
+        Block {
+            header: Header {
+                gas_limit: 100_000,
+                gas_used,
+                base_fee_per_gas: Some(10),
+                ..Default::default()
+            },
+            body,
+            ..Default::default()
+        }
+        .seal_slow()
+    }
+
+    #[test]
+    fn rebid_threshold_of_block_with_room() {
+        let threshold = RebidThreshold::new(&block(50_000, vec![transaction(20, 5)]));
+        assert_eq!(threshold, RebidThreshold { base_fee: 10, min_tip: 0 });
+
+        // any tip may improve the block, up to the entire gas limit of the transaction
+        assert_eq!(threshold.improvement(Some(2), MIN_TRANSACTION_GAS), 42_000);
+        assert_eq!(threshold.improvement(Some(0), MIN_TRANSACTION_GAS), 0);
+        // transactions that can't pay the base fee don't improve the block
+        assert_eq!(threshold.improvement(None, MIN_TRANSACTION_GAS), 0);
+    }
+
+    #[test]
+    fn rebid_threshold_of_full_block() {
+        let body = vec![transaction(20, 5), transaction(13, 5), transaction(30, 8)];
+        let threshold = RebidThreshold::new(&block(90_000, body));
+        // the cheapest transaction pays a tip of 3 above the base fee
+        assert_eq!(threshold, RebidThreshold { base_fee: 10, min_tip: 3 });
+
+        assert_eq!(threshold.improvement(Some(3), MIN_TRANSACTION_GAS), 0);
+        assert_eq!(threshold.improvement(Some(5), MIN_TRANSACTION_GAS), 42_000);
+    }
+
+    #[test]
+    fn rebid_requires_minimum_improvement() {
+        let threshold = RebidThreshold::new(&block(0, vec![]));
+        let min_rebid_fees = DEFAULT_MIN_REBID_FEES;
+
+        // a transfer with a 1 gwei tip does not trigger a rebuild on its own
+        let fees = threshold.improvement(Some(1_000_000_000), MIN_TRANSACTION_GAS);
+        assert!(fees < min_rebid_fees);
+
+        // but enough of them do
+        let transfers = min_rebid_fees.div_ceil(fees);
+        assert!(fees * transfers >= min_rebid_fees);
+        assert!(fees * (transfers - 1) < min_rebid_fees);
+    }
+
+    #[test]
+    fn payload_deadline_cutoff() {
+        let now = Instant::now();
+        let deadline = now + Duration::from_millis(500);
+
+        // without a previous build there is no estimate
+        assert!(can_finish_before(now, deadline, None));
+        // the last build took less time than is left
+        assert!(can_finish_before(now, deadline, Some(Duration::from_millis(200))));
+        assert!(can_finish_before(now, deadline, Some(Duration::from_millis(500))));
+        // the last build took longer than is left
+        assert!(!can_finish_before(now, deadline, Some(Duration::from_millis(501))));
+        assert!(!can_finish_before(
+            now + Duration::from_millis(400),
+            deadline,
+            Some(Duration::from_millis(200))
+        ));
+        // the payload was not requested in time, keep building
+        assert!(can_finish_before(deadline, deadline, Some(Duration::from_secs(1))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn job_rebids_on_better_transactions() {
+        let builder = MockBuilder::with_fees([100, 50, 200]);
+        let (mut job, events) = payload_job(builder.clone());
+
+        // the first tick of the interval starts a build right away
+        poll_until(&mut job, |job| job.iterations == 1).await;
+        assert_eq!(job.take_better_payload().unwrap().fees(), U256::from(100));
+
+        // a transaction that adds less than the minimum fees doesn't start a build
+        events.send(pending_transfer(1)).await.unwrap();
+        poll_once(&mut job);
+        assert!(job.pending_block.is_none());
+        assert_eq!(job.rebid_fees, 21_000);
+
+        // enough fees start a build, but a worse payload doesn't replace the best one
+        events.send(pending_transfer(100)).await.unwrap();
+        poll_until(&mut job, |job| job.iterations == 2).await;
+        assert!(job.take_better_payload().is_none());
+        assert_eq!(job.best_payload().unwrap().fees(), U256::from(100));
+
+        // a better payload does
+        events.send(pending_transfer(100)).await.unwrap();
+        poll_until(&mut job, |job| job.iterations == 3).await;
+        assert_eq!(job.take_better_payload().unwrap().fees(), U256::from(200));
+        assert_eq!(builder.builds(), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn job_skips_rebid_that_would_finish_too_late() {
+        let builder = MockBuilder::with_fees([100, 200]);
+        let (mut job, events) = payload_job(builder.clone());
+        poll_until(&mut job, |job| job.iterations == 1).await;
+
+        // the last build took longer than is left until the payload is requested
+        job.last_build_duration = Some(Duration::from_secs(120));
+        events.send(pending_transfer(100)).await.unwrap();
+        poll_once(&mut job);
+        assert!(job.pending_block.is_none());
+        assert!(!job.rebid);
+        assert_eq!(builder.builds(), 1);
+        assert_eq!(job.best_payload().unwrap().fees(), U256::from(100));
+    }
+}
@@ -1,6 +1,10 @@
 //! Metrics for the payload builder impl
 
-use reth_metrics::{metrics::Counter, Metrics};
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+use reth_primitives::U256;
 
 /// Transaction pool metrics
 #[derive(Metrics)]
@@ -12,6 +16,15 @@ pub(crate) struct PayloadBuilderMetrics {
     pub(crate) initiated_payload_builds: Counter,
     /// Total number of failed payload build attempts
     pub(crate) failed_payload_builds: Counter,
+    /// Total number of payload build attempts started early because of better transactions
+    pub(crate) payload_rebids: Counter,
+    /// Total number of payload build attempts skipped because they would finish after the payload
+    /// is requested
+    pub(crate) skipped_payload_builds: Counter,
+    /// Number of completed build attempts per payload job
+    pub(crate) payload_build_iterations: Histogram,
+    /// Fees of the best payload of a payload job minus the fees of its first payload, in gwei
+    pub(crate) payload_revenue_delta: Histogram,
 }
 
 impl PayloadBuilderMetrics {
@@ -26,4 +39,21 @@ impl PayloadBuilderMetrics {
     pub(crate) fn inc_failed_payload_builds(&self) {
         self.failed_payload_builds.increment(1);
     }
+
+    pub(crate) fn inc_payload_rebids(&self) {
+        self.payload_rebids.increment(1);
+    }
+
+    pub(crate) fn inc_skipped_payload_builds(&self) {
+        self.skipped_payload_builds.increment(1);
+    }
+
+    pub(crate) fn record_payload_build_iterations(&self, iterations: u64) {
+        self.payload_build_iterations.record(iterations as f64);
+    }
+
+    pub(crate) fn record_payload_revenue_delta(&self, delta: U256) {
+        let gwei = delta / U256::from(1_000_000_000u64);
+        self.payload_revenue_delta.record(u128::try_from(gwei).unwrap_or(u128::MAX) as f64);
+    }
 }
@@ -15,6 +15,11 @@ pub enum Events<Engine: EngineTypes> {
     /// Triggered by the CL whenever it asks for an execution payload.
     /// This event is only thrown if the CL is a validator.
     BuiltPayload(Engine::BuiltPayload),
+    /// A better payload that a payload job built for the attributes, before the payload is
+    /// resolved.
+    ///
+    /// This is thrown whenever a job replaced its best payload.
+    BetterPayload(Engine::BuiltPayload),
 }
 
 /// Represents a receiver for various payload events.
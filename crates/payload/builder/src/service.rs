@@ -11,13 +11,16 @@ use crate::{
     KeepPayloadJobAlive, PayloadJob,
 };
 use futures_util::{future::FutureExt, Stream, StreamExt};
+use parking_lot::Mutex;
 use reth_engine_primitives::{BuiltPayload, EngineTypes, PayloadBuilderAttributes};
 use reth_provider::CanonStateNotification;
 use reth_rpc_types::engine::PayloadId;
 use std::{
+    collections::VecDeque,
     fmt,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 use tokio::sync::{
@@ -227,10 +230,17 @@ where
     chain_events: St,
     /// Payload events handler, used to broadcast and subscribe to payload events.
     payload_events: broadcast::Sender<Events<Engine>>,
+    /// The most recently resolved payloads.
+    ///
+    /// Used to refuse building a second, different payload for the same identifier.
+    resolved_payloads: Arc<Mutex<ResolvedPayloads<Engine::BuiltPayload>>>,
 }
 
 const PAYLOAD_EVENTS_BUFFER_SIZE: usize = 20;
 
+/// The number of resolved payloads that are remembered by the [PayloadBuilderService].
+const MAX_RESOLVED_PAYLOADS: usize = 64;
+
 // === impl PayloadBuilderService ===
 
 impl<Gen, St, Engine> PayloadBuilderService<Gen, St, Engine>
@@ -257,6 +267,7 @@ where
             metrics: Default::default(),
             chain_events,
             payload_events,
+            resolved_payloads: Default::default(),
         };
 
         let handle = service.handle();
@@ -282,7 +293,8 @@ where
             .payload_jobs
             .iter()
             .find(|(_, job_id)| *job_id == id)
-            .map(|(j, _)| j.best_payload().map(|p| p.into()));
+            .map(|(j, _)| j.best_payload().map(|p| p.into()))
+            .or_else(|| self.resolved_payload(id).map(Ok));
         if let Some(Ok(ref best)) = res {
             self.metrics.set_best_revenue(best.block().number, f64::from(best.fees()));
         }
@@ -295,7 +307,12 @@ where
     fn resolve(&mut self, id: PayloadId) -> Option<PayloadFuture<Engine::BuiltPayload>> {
         trace!(%id, "resolving payload job");
 
-        let job = self.payload_jobs.iter().position(|(_, job_id)| *job_id == id)?;
+        let Some(job) = self.payload_jobs.iter().position(|(_, job_id)| *job_id == id) else {
+            // the job may have been terminated after it was resolved
+            let payload = self.resolved_payload(id)?;
+            trace!(%id, "returning already resolved payload");
+            return Some(Box::pin(futures_util::future::ready(Ok(payload))))
+        };
         let (fut, keep_alive) = self.payload_jobs[job].0.resolve();
 
         if keep_alive == KeepPayloadJobAlive::No {
@@ -307,20 +324,34 @@ where
         // the future in a new future that will update the metrics.
         let resolved_metrics = self.metrics.clone();
         let payload_events = self.payload_events.clone();
+        let resolved_payloads = self.resolved_payloads.clone();
 
         let fut = async move {
-            let res = fut.await;
-            if let Ok(ref payload) = res {
-                payload_events.send(Events::BuiltPayload(payload.clone().into())).ok();
+            let payload: Engine::BuiltPayload = fut.await?.into();
+
+            // never hand out two different payloads for the same identifier
+            let payload = match resolved_payloads.lock().insert(id, payload) {
+                Ok(payload) => payload,
+                Err(existing) => {
+                    warn!(%id, hash = %existing.block().hash(), "Refusing to return a conflicting payload, returning the already resolved payload");
+                    resolved_metrics.inc_conflicting_payloads();
+                    existing
+                }
+            };
 
-                resolved_metrics
-                    .set_resolved_revenue(payload.block().number, f64::from(payload.fees()));
-            }
-            res.map(|p| p.into())
+            payload_events.send(Events::BuiltPayload(payload.clone())).ok();
+            resolved_metrics
+                .set_resolved_revenue(payload.block().number, f64::from(payload.fees()));
+            Ok(payload)
         };
 
         Some(Box::pin(fut))
     }
+
+    /// Returns the payload that was already resolved for the given identifier.
+    fn resolved_payload(&self, id: PayloadId) -> Option<Engine::BuiltPayload> {
+        self.resolved_payloads.lock().get(id).cloned()
+    }
 }
 
 impl<Gen, St, Engine> PayloadBuilderService<Gen, St, Engine>
@@ -386,6 +417,10 @@ where
                         this.metrics.set_active_jobs(this.payload_jobs.len());
                     }
                     Poll::Pending => {
+                        if let Some(payload) = job.take_better_payload() {
+                            trace!(%id, "payload job built a better payload");
+                            this.payload_events.send(Events::BetterPayload(payload.into())).ok();
+                        }
                         // still pending, put it back
                         this.payload_jobs.push((job, id));
                     }
@@ -404,6 +439,10 @@ where
 
                         if this.contains_payload(id) {
                             debug!(%id, parent = %attr.parent(), "Payload job already in progress, ignoring.");
+                        } else if this.resolved_payload(id).is_some() {
+                            // building a new payload for the same parent and attributes could
+                            // produce a different payload, which would equivocate
+                            debug!(%id, parent = %attr.parent(), "Payload already resolved, ignoring.");
                         } else {
                             // no job for this payload yet, create one
                             let parent = attr.parent();
@@ -450,6 +489,47 @@ where
     }
 }
 
+/// A bounded record of the most recently resolved payloads.
+#[derive(Debug)]
+struct ResolvedPayloads<P> {
+    payloads: VecDeque<(PayloadId, P)>,
+}
+
+impl<P: BuiltPayload> ResolvedPayloads<P> {
+    /// Returns the resolved payload for the given identifier.
+    fn get(&self, id: PayloadId) -> Option<&P> {
+        self.payloads.iter().find(|(payload_id, _)| *payload_id == id).map(|(_, payload)| payload)
+    }
+
+    /// Records the resolved payload.
+    ///
+    /// Returns the previously resolved payload as an error if a different payload was already
+    /// resolved for the identifier.
+    fn insert(&mut self, id: PayloadId, payload: P) -> Result<P, P>
+    where
+        P: Clone,
+    {
+        if let Some(existing) = self.get(id) {
+            if existing.block().hash() != payload.block().hash() {
+                return Err(existing.clone())
+            }
+            return Ok(payload)
+        }
+
+        if self.payloads.len() == MAX_RESOLVED_PAYLOADS {
+            self.payloads.pop_front();
+        }
+        self.payloads.push_back((id, payload.clone()));
+        Ok(payload)
+    }
+}
+
+impl<P> Default for ResolvedPayloads<P> {
+    fn default() -> Self {
+        Self { payloads: VecDeque::with_capacity(MAX_RESOLVED_PAYLOADS) }
+    }
+}
+
 /// Message type for the [PayloadBuilderService].
 pub enum PayloadServiceCommand<Engine: EngineTypes> {
     /// Start building a new payload.
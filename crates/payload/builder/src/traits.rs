@@ -35,6 +35,14 @@ pub trait PayloadJob: Future<Output = Result<(), PayloadBuilderError>> + Send +
     /// Returns the payload attributes for the payload being built.
     fn payload_attributes(&self) -> Result<Self::PayloadAttributes, PayloadBuilderError>;
 
+    /// Returns the best payload if a better payload was built since the last call.
+    ///
+    /// This is polled by the payload builder service after the job was polled, to notify
+    /// subscribers about better payloads before the payload is resolved.
+    fn take_better_payload(&mut self) -> Option<Self::BuiltPayload> {
+        None
+    }
+
     /// Called when the payload is requested by the CL.
     ///
     /// This is invoked on [`engine_getPayloadV2`](https://github.com/ethereum/execution-apis/blob/main/src/engine/shanghai.md#engine_getpayloadv2) and [`engine_getPayloadV1`](https://github.com/ethereum/execution-apis/blob/main/src/engine/paris.md#engine_getpayloadv1).
@@ -0,0 +1,176 @@
+//! Atomic execution of the bundles of the bundle pool at the top of the block.
+
+use reth_evm::ConfigureEvm;
+use reth_payload_builder::error::PayloadBuilderError;
+use reth_primitives::{
+    revm::env::tx_env_with_recovered, Address, Bytecode, Receipt, TransactionSigned, B256, U256,
+};
+use reth_provider::ProviderError;
+use reth_transaction_pool::TransactionBundle;
+use revm::{
+    db::CacheDB,
+    primitives::{
+        AccountInfo, BlockEnv, CfgEnvWithHandlerCfg, EVMError, EnvWithHandlerCfg, ResultAndState,
+        State as EvmState,
+    },
+    Database, DatabaseCommit, DatabaseRef, State,
+};
+use std::cell::RefCell;
+use tracing::trace;
+
+/// A bundle that was executed without any of its transactions failing.
+#[derive(Debug)]
+pub(crate) struct ExecutedBundle {
+    /// The transactions of the bundle.
+    pub(crate) transactions: Vec<TransactionSigned>,
+    /// The receipts of the transactions, with the cumulative gas used of the block.
+    pub(crate) receipts: Vec<Receipt>,
+    /// Gas used by all transactions of the bundle.
+    pub(crate) gas_used: u64,
+    /// Increase of the balance of the coinbase, including priority fees and direct transfers.
+    pub(crate) coinbase_profit: U256,
+    /// The state changes of the transactions, in order.
+    state_changes: Vec<EvmState>,
+}
+
+impl ExecutedBundle {
+    /// Returns the coinbase profit per gas the bundle pays, the score bundles are ordered by.
+    pub(crate) fn score(&self) -> U256 {
+        self.coinbase_profit.checked_div(U256::from(self.gas_used)).unwrap_or_default()
+    }
+
+    /// Commits the state changes of the bundle to the state it was executed on.
+    pub(crate) fn commit<DB>(&mut self, db: &mut State<DB>)
+    where
+        DB: Database,
+    {
+        for state in self.state_changes.drain(..) {
+            db.commit(state);
+        }
+    }
+}
+
+/// Read access to a [State] for the [CacheDB] overlay bundles are executed on.
+///
+/// Reads go through the cache of the state, so the accounts and slots a bundle touches are loaded
+/// into it before the changes of the bundle are committed.
+struct StateRef<'a, DB> {
+    inner: RefCell<&'a mut State<DB>>,
+}
+
+impl<'a, DB: Database> DatabaseRef for StateRef<'a, DB> {
+    type Error = DB::Error;
+
+    fn basic_ref(&self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.inner.borrow_mut().basic(address)
+    }
+
+    fn code_by_hash_ref(&self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.inner.borrow_mut().code_by_hash(code_hash)
+    }
+
+    fn storage_ref(&self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.inner.borrow_mut().storage(address, index)
+    }
+
+    fn block_hash_ref(&self, number: U256) -> Result<B256, Self::Error> {
+        self.inner.borrow_mut().block_hash(number)
+    }
+}
+
+/// Executes the transactions of the bundle in order on top of the given state.
+///
+/// Returns `None` if the bundle does not fit into the block, or if one of its transactions is
+/// invalid or reverts without being allowed to. The transactions are executed on an overlay of the
+/// state, which is left untouched until the bundle is included with [ExecutedBundle::commit].
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute_bundle<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    db: &mut State<DB>,
+    initialized_cfg: &CfgEnvWithHandlerCfg,
+    initialized_block_env: &BlockEnv,
+    bundle: &TransactionBundle,
+    mut cumulative_gas_used: u64,
+    block_gas_limit: u64,
+) -> Result<Option<ExecutedBundle>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm,
+    DB: Database<Error = ProviderError>,
+{
+    if cumulative_gas_used + bundle.gas_limit() > block_gas_limit {
+        trace!(
+            target: "payload_builder",
+            bundle=?bundle.hash,
+            "skipping bundle that exceeds the block gas limit"
+        );
+        return Ok(None)
+    }
+
+    let mut db = CacheDB::new(StateRef { inner: RefCell::new(db) });
+
+    let coinbase = initialized_block_env.coinbase;
+    let balance_before = db.basic(coinbase)?.map(|account| account.balance).unwrap_or_default();
+
+    let mut transactions = Vec::with_capacity(bundle.transactions.len());
+    let mut receipts = Vec::with_capacity(bundle.transactions.len());
+    let mut state_changes = Vec::with_capacity(bundle.transactions.len());
+    let mut gas_used = 0;
+    for tx in &bundle.transactions {
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            initialized_cfg.clone(),
+            initialized_block_env.clone(),
+            tx_env_with_recovered(tx),
+        );
+        let mut evm = evm_config.evm_with_env(&mut db, env);
+
+        let ResultAndState { result, state } = match evm.transact() {
+            Ok(res) => res,
+            Err(EVMError::Transaction(err)) => {
+                trace!(
+                    target: "payload_builder",
+                    %err,
+                    bundle=?bundle.hash,
+                    tx=?tx.hash,
+                    "skipping bundle with invalid transaction"
+                );
+                return Ok(None)
+            }
+            Err(err) => return Err(PayloadBuilderError::EvmExecutionError(err)),
+        };
+        drop(evm);
+
+        if !result.is_success() && !bundle.can_revert(&tx.hash) {
+            trace!(
+                target: "payload_builder",
+                bundle=?bundle.hash,
+                tx=?tx.hash,
+                "skipping bundle with reverted transaction"
+            );
+            return Ok(None)
+        }
+        db.commit(state.clone());
+        state_changes.push(state);
+
+        gas_used += result.gas_used();
+        cumulative_gas_used += result.gas_used();
+
+        #[allow(clippy::needless_update)] // side-effect of optimism fields
+        receipts.push(Receipt {
+            tx_type: tx.tx_type(),
+            success: result.is_success(),
+            cumulative_gas_used,
+            logs: result.into_logs().into_iter().map(Into::into).collect(),
+            ..Default::default()
+        });
+        transactions.push(tx.clone().into_signed());
+    }
+
+    let balance_after = db.basic(coinbase)?.map(|account| account.balance).unwrap_or_default();
+    Ok(Some(ExecutedBundle {
+        transactions,
+        receipts,
+        gas_used,
+        coinbase_profit: balance_after.saturating_sub(balance_before),
+        state_changes,
+    }))
+}
@@ -9,10 +9,13 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![allow(clippy::useless_let_if_seq)]
 
+use crate::bundle::execute_bundle;
 use reth_basic_payload_builder::{
     commit_withdrawals, is_better_payload, pre_block_beacon_root_contract_call, BuildArguments,
     BuildOutcome, PayloadBuilder, PayloadConfig, WithdrawalsOutcome,
 };
+use reth_evm::ConfigureEvm;
+use reth_evm_ethereum::EthEvmConfig;
 use reth_payload_builder::{
     error::PayloadBuilderError, EthBuiltPayload, EthPayloadBuilderAttributes,
 };
@@ -27,22 +30,62 @@ use reth_primitives::{
 };
 use reth_provider::{BundleStateWithReceipts, StateProviderFactory};
 use reth_revm::database::StateProviderDatabase;
-use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+use reth_transaction_pool::{
+    BestTransactionsAttributes, PayloadInclusionPolicy, PayloadOrderingPolicy,
+    PayloadTransactionOrdering, TransactionPool,
+};
 use revm::{
     db::states::bundle_state::BundleRetention,
     primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
     DatabaseCommit, State,
 };
+use std::collections::HashSet;
 use tracing::{debug, trace, warn};
 
+mod bundle;
+
 /// Ethereum payload builder
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig, Ordering = PayloadOrderingPolicy> {
+    /// The EVM configuration used to execute the transactions of the payload.
+    evm_config: EvmConfig,
+    /// The order in which the transactions of the pool are tried for inclusion.
+    ordering: Ordering,
+    /// The senders and addresses that are forced into or kept out of the payload.
+    policy: PayloadInclusionPolicy,
+}
+
+impl<EvmConfig> EthereumPayloadBuilder<EvmConfig> {
+    /// Creates a new payload builder executing transactions with the given EVM configuration.
+    pub const fn new(evm_config: EvmConfig) -> Self {
+        Self {
+            evm_config,
+            ordering: PayloadOrderingPolicy::EffectiveTip,
+            policy: PayloadInclusionPolicy::default(),
+        }
+    }
+}
+
+impl<EvmConfig, Ordering> EthereumPayloadBuilder<EvmConfig, Ordering> {
+    /// Sets the order in which the transactions of the pool are tried for inclusion.
+    pub fn with_ordering<O>(self, ordering: O) -> EthereumPayloadBuilder<EvmConfig, O> {
+        EthereumPayloadBuilder { evm_config: self.evm_config, ordering, policy: self.policy }
+    }
+
+    /// Sets the senders and addresses that are forced into or kept out of the payload.
+    pub fn with_inclusion_policy(mut self, policy: PayloadInclusionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
 
 // Default implementation of [PayloadBuilder] for unit type
-impl<Pool, Client> PayloadBuilder<Pool, Client> for EthereumPayloadBuilder
+impl<EvmConfig, Ordering, Pool, Client> PayloadBuilder<Pool, Client>
+    for EthereumPayloadBuilder<EvmConfig, Ordering>
 where
+    EvmConfig: ConfigureEvm,
+    Ordering: PayloadTransactionOrdering + Clone,
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
@@ -53,7 +96,7 @@ where
         &self,
         args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-        default_ethereum_payload_builder(args)
+        default_ethereum_payload_builder(&self.evm_config, &self.ordering, &self.policy, args)
     }
 
     fn build_empty_payload(
@@ -162,16 +205,32 @@ where
     }
 }
 
-/// Constructs an Ethereum transaction payload using the best transactions from the pool.
+/// Constructs an Ethereum transaction payload using the best transactions from the pool, tried in
+/// the order of the given [PayloadTransactionOrdering].
+///
+/// The bundles of the pool that target the block are included at the top of the block, ordered by
+/// the coinbase profit per gas they pay, before the pool transactions. Pool transactions whose
+/// nonces were used by an included bundle are skipped, as are pool transactions whose
+/// [TransactionConditions](reth_transaction_pool::TransactionConditions) don't hold for the block
+/// and the state of its parent.
+///
+/// The [PayloadInclusionPolicy] is applied to the bundles and the transactions of the pool:
+/// transactions of included senders are tried first, bundles and transactions of excluded senders
+/// or from or to a blocklisted address are skipped.
 ///
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
 #[inline]
-pub fn default_ethereum_payload_builder<Pool, Client>(
+pub fn default_ethereum_payload_builder<EvmConfig, Ordering, Pool, Client>(
+    evm_config: &EvmConfig,
+    ordering: &Ordering,
+    policy: &PayloadInclusionPolicy,
     args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
+    EvmConfig: ConfigureEvm,
+    Ordering: PayloadTransactionOrdering,
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
@@ -199,10 +258,15 @@ where
 
     let mut executed_txs = Vec::new();
 
-    let mut best_txs = pool.best_transactions_with_attributes(BestTransactionsAttributes::new(
+    let best_txs_attributes = BestTransactionsAttributes::new(
         base_fee,
         initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    );
+    let mut best_txs = policy.apply(
+        &pool,
+        best_txs_attributes,
+        ordering.best_transactions(&pool, best_txs_attributes),
+    );
 
     let mut total_fees = U256::ZERO;
 
@@ -219,7 +283,93 @@ where
     )?;
 
     let mut receipts = Vec::new();
+
+    // simulate the bundles at the top of the block and order them by the profit per gas they pay
+    // to the coinbase
+    let mut bundles = Vec::new();
+    for bundle in pool.bundles_for_block(block_number, attributes.timestamp) {
+        if bundle.transactions.iter().any(|tx| policy.is_excluded(&tx.signer(), tx.to())) {
+            trace!(target: "payload_builder", bundle=?bundle.hash, "skipping excluded bundle");
+            policy.on_excluded_bundle();
+            continue
+        }
+        let executed = execute_bundle(
+            evm_config,
+            &mut db,
+            &initialized_cfg,
+            &initialized_block_env,
+            &bundle,
+            0,
+            block_gas_limit,
+        )?;
+        if let Some(executed) = executed {
+            bundles.push((executed.score(), bundle));
+        }
+    }
+    bundles.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+
+    // include the bundles atomically, a bundle that fails on top of the previously included
+    // bundles is skipped
+    let mut bundle_nonces = HashSet::new();
+    for (_, bundle) in bundles {
+        if cancel.is_cancelled() {
+            return Ok(BuildOutcome::Cancelled)
+        }
+
+        let Some(mut executed) = execute_bundle(
+            evm_config,
+            &mut db,
+            &initialized_cfg,
+            &initialized_block_env,
+            &bundle,
+            cumulative_gas_used,
+            block_gas_limit,
+        )?
+        else {
+            continue
+        };
+        executed.commit(&mut db);
+
+        trace!(
+            target: "payload_builder",
+            bundle=?bundle.hash,
+            profit=%executed.coinbase_profit,
+            "including bundle"
+        );
+        cumulative_gas_used += executed.gas_used;
+        total_fees += executed.coinbase_profit;
+        receipts.extend(executed.receipts.into_iter().map(Some));
+        executed_txs.extend(executed.transactions);
+        bundle_nonces.extend(bundle.transactions.iter().map(|tx| (tx.signer(), tx.nonce())));
+    }
+
     while let Some(pool_tx) = best_txs.next() {
+        // the nonce was already used by an included bundle, the sender's next transactions may
+        // still be valid
+        if bundle_nonces.contains(&(pool_tx.sender(), pool_tx.nonce())) {
+            trace!(
+                target: "payload_builder",
+                tx=?pool_tx.hash(),
+                "skipping transaction with nonce used by a bundle"
+            );
+            continue
+        }
+
+        // the conditions of a conditional transaction must hold for this block and its parent state
+        if let Some(conditions) = pool.transaction_conditions(pool_tx.hash()) {
+            if let Err(err) = conditions.check(block_number, attributes.timestamp, &*state_provider)
+            {
+                trace!(
+                    target: "payload_builder",
+                    %err,
+                    tx=?pool_tx.hash(),
+                    "skipping transaction whose conditions don't hold"
+                );
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+        }
+
         // ensure we still have capacity for this transaction
         if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
@@ -253,14 +403,12 @@ where
         }
 
         // Configure the environment for the block.
-        let mut evm = revm::Evm::builder()
-            .with_db(&mut db)
-            .with_env_with_handler_cfg(EnvWithHandlerCfg::new_with_cfg_env(
-                initialized_cfg.clone(),
-                initialized_block_env.clone(),
-                tx_env_with_recovered(&tx),
-            ))
-            .build();
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            initialized_cfg.clone(),
+            initialized_block_env.clone(),
+            tx_env_with_recovered(&tx),
+        );
+        let mut evm = evm_config.evm_with_env(&mut db, env);
 
         let ResultAndState { result, state } = match evm.transact() {
             Ok(res) => res,
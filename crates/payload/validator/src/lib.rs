@@ -8,7 +8,11 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-use reth_primitives::{ChainSpec, SealedBlock};
+use rayon::prelude::*;
+use reth_primitives::{
+    proofs, Address, Block, Bytes, ChainSpec, SealedBlock, SealedBlockWithSenders,
+    TransactionSigned, B256,
+};
 use reth_rpc_types::{engine::MaybeCancunPayloadFields, ExecutionPayload, PayloadError};
 use reth_rpc_types_compat::engine::payload::{try_into_block, validate_block_hash};
 use std::sync::Arc;
@@ -105,6 +109,54 @@ impl ExecutionPayloadValidator {
         // First parse the block
         let block = try_into_block(payload, cancun_fields.parent_beacon_block_root())?;
 
+        self.ensure_well_formed_block(block_hash, block, &cancun_fields)
+    }
+
+    /// Same as [Self::ensure_well_formed_payload], but also recovers the senders of the payload's
+    /// transactions.
+    ///
+    /// The transactions are decoded and their senders are recovered in parallel on the rayon pool,
+    /// concurrently with the validation of the payload's header fields.
+    ///
+    /// If the payload is well-formed but the signature of any transaction is invalid, the sealed
+    /// block is returned as the error of the inner result.
+    pub fn ensure_well_formed_payload_with_senders(
+        &self,
+        mut payload: ExecutionPayload,
+        cancun_fields: MaybeCancunPayloadFields,
+    ) -> Result<Result<SealedBlockWithSenders, SealedBlock>, PayloadError> {
+        let block_hash = payload.block_hash();
+        let transactions = match &mut payload {
+            ExecutionPayload::V1(payload) => &mut payload.transactions,
+            ExecutionPayload::V2(payload) => &mut payload.payload_inner.transactions,
+            ExecutionPayload::V3(payload) => &mut payload.payload_inner.payload_inner.transactions,
+        };
+        let transactions = std::mem::take(transactions);
+
+        // Parse the header and the transactions of the block concurrently
+        let (block, transactions) = rayon::join(
+            || try_into_block(payload, cancun_fields.parent_beacon_block_root()),
+            || decode_transactions_with_senders(transactions),
+        );
+        let mut block = block?;
+        let (transactions, senders) = transactions?;
+        block.header.transactions_root = proofs::calculate_transaction_root(&transactions);
+        block.body = transactions;
+
+        let sealed_block = self.ensure_well_formed_block(block_hash, block, &cancun_fields)?;
+        Ok(match senders {
+            Some(senders) => Ok(SealedBlockWithSenders { block: sealed_block, senders }),
+            None => Err(sealed_block),
+        })
+    }
+
+    /// Validates the parsed block of a payload, see [Self::ensure_well_formed_payload].
+    fn ensure_well_formed_block(
+        &self,
+        block_hash: B256,
+        block: Block,
+        cancun_fields: &MaybeCancunPayloadFields,
+    ) -> Result<SealedBlock, PayloadError> {
         let cancun_active = self.is_cancun_active_at_timestamp(block.timestamp);
 
         if !cancun_active && block.has_blob_transactions() {
@@ -116,8 +168,27 @@ impl ExecutionPayloadValidator {
         let sealed_block = validate_block_hash(block_hash, block)?;
 
         // EIP-4844 checks
-        self.ensure_matching_blob_versioned_hashes(&sealed_block, &cancun_fields)?;
+        self.ensure_matching_blob_versioned_hashes(&sealed_block, cancun_fields)?;
 
         Ok(sealed_block)
     }
 }
+
+/// Decodes the EIP-2718 encoded transactions of a payload and recovers their senders in parallel.
+///
+/// The senders are `None` if the signature of any transaction is invalid.
+fn decode_transactions_with_senders(
+    transactions: Vec<Bytes>,
+) -> Result<(Vec<TransactionSigned>, Option<Vec<Address>>), PayloadError> {
+    let (transactions, senders): (Vec<_>, Vec<_>) = transactions
+        .par_iter()
+        .map(|tx| {
+            let tx = TransactionSigned::decode_enveloped(&mut tx.as_ref())?;
+            let sender = tx.recover_signer();
+            Ok((tx, sender))
+        })
+        .collect::<Result<Vec<_>, PayloadError>>()?
+        .into_iter()
+        .unzip();
+    Ok((transactions, senders.into_iter().collect()))
+}
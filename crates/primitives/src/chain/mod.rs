@@ -1,5 +1,8 @@
 pub use alloy_chains::{Chain, NamedChain};
 pub use info::ChainInfo;
+pub use precompile::{
+    BuiltinPrecompile, CustomPrecompile, PrecompileImplementation, WasmPrecompile,
+};
 pub use spec::{
     AllGenesisFormats, BaseFeeParams, BaseFeeParamsKind, ChainSpec, ChainSpecBuilder,
     DisplayHardforks, ForkBaseFeeParams, ForkCondition, ForkTimestamps, DEV, GOERLI, HOLESKY,
@@ -16,6 +19,8 @@ pub(crate) use spec::{OP_BASE_FEE_PARAMS, OP_SEPOLIA_BASE_FEE_PARAMS};
 mod spec;
 // The chain info module.
 mod info;
+// The custom precompiles module.
+mod precompile;
 
 #[cfg(test)]
 mod tests {
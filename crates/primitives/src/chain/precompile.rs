@@ -0,0 +1,139 @@
+use crate::{address, Address, Hardfork};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// An additional precompile declared in the chain spec.
+///
+/// In a geth genesis file, the precompiles are declared in the `precompiles` array of the chain
+/// config:
+///
+/// ```json
+/// "precompiles": [
+///     { "address": "0x0000000000000000000000000000000000000100", "builtin": "sha256" },
+///     {
+///         "address": "0x0000000000000000000000000000000000000101",
+///         "activation": "Cancun",
+///         "wasm": { "path": "precompiles/hash.wasm", "baseGas": 100 }
+///     }
+/// ]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomPrecompile {
+    /// The address of the precompile.
+    pub address: Address,
+    /// The hardfork that activates the precompile, active from genesis if `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub activation: Option<Hardfork>,
+    /// The implementation of the precompile.
+    #[serde(flatten)]
+    pub implementation: PrecompileImplementation,
+}
+
+/// The implementation of a [CustomPrecompile].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PrecompileImplementation {
+    /// A precompile of the EVM, deployed at another address.
+    Builtin(BuiltinPrecompile),
+    /// A WASM module.
+    Wasm(WasmPrecompile),
+}
+
+/// A WASM module implementing a precompile.
+///
+/// The module exports its `memory`, an `alloc(len: i32) -> i32` function that reserves memory for
+/// the input and a `call(ptr: i32, len: i32) -> i64` function that returns the pointer to the
+/// output in the upper and its length in the lower 32 bits, or a negative value if the call fails.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WasmPrecompile {
+    /// The path of the module.
+    pub path: PathBuf,
+    /// The gas charged for every call, in addition to the fuel consumed by the module.
+    #[serde(default)]
+    pub base_gas: u64,
+}
+
+/// The precompiles of the EVM that can be deployed at another address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuiltinPrecompile {
+    /// `ecrecover`
+    Ecrecover,
+    /// `sha256`
+    Sha256,
+    /// `ripemd160`
+    Ripemd160,
+    /// `identity`
+    Identity,
+    /// `modexp`
+    Modexp,
+    /// `bn256_add`
+    Bn256Add,
+    /// `bn256_mul`
+    Bn256Mul,
+    /// `bn256_pairing`
+    Bn256Pairing,
+    /// `blake2f`
+    Blake2f,
+    /// `point_evaluation`
+    PointEvaluation,
+}
+
+impl BuiltinPrecompile {
+    /// Returns the address of the precompile in the EVM.
+    pub const fn address(&self) -> Address {
+        match self {
+            Self::Ecrecover => address!("0000000000000000000000000000000000000001"),
+            Self::Sha256 => address!("0000000000000000000000000000000000000002"),
+            Self::Ripemd160 => address!("0000000000000000000000000000000000000003"),
+            Self::Identity => address!("0000000000000000000000000000000000000004"),
+            Self::Modexp => address!("0000000000000000000000000000000000000005"),
+            Self::Bn256Add => address!("0000000000000000000000000000000000000006"),
+            Self::Bn256Mul => address!("0000000000000000000000000000000000000007"),
+            Self::Bn256Pairing => address!("0000000000000000000000000000000000000008"),
+            Self::Blake2f => address!("0000000000000000000000000000000000000009"),
+            Self::PointEvaluation => address!("000000000000000000000000000000000000000a"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_custom_precompiles() {
+        let precompiles: Vec<CustomPrecompile> = serde_json::from_str(
+            r#"[
+                { "address": "0x0000000000000000000000000000000000000100", "builtin": "sha256" },
+                {
+                    "address": "0x0000000000000000000000000000000000000101",
+                    "activation": "Cancun",
+                    "wasm": { "path": "precompiles/hash.wasm", "baseGas": 100 }
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            precompiles,
+            vec![
+                CustomPrecompile {
+                    address: address!("0000000000000000000000000000000000000100"),
+                    activation: None,
+                    implementation: PrecompileImplementation::Builtin(BuiltinPrecompile::Sha256),
+                },
+                CustomPrecompile {
+                    address: address!("0000000000000000000000000000000000000101"),
+                    activation: Some(Hardfork::Cancun),
+                    implementation: PrecompileImplementation::Wasm(WasmPrecompile {
+                        path: "precompiles/hash.wasm".into(),
+                        base_gas: 100,
+                    }),
+                },
+            ]
+        );
+    }
+}
@@ -4,8 +4,9 @@ use crate::{
     net::{goerli_nodes, mainnet_nodes, sepolia_nodes},
     proofs::state_root_ref_unhashed,
     revm_primitives::{address, b256},
-    Address, BlockNumber, Chain, ForkFilter, ForkFilterKey, ForkHash, ForkId, Genesis, Hardfork,
-    Head, Header, NamedChain, NodeRecord, SealedHeader, B256, EMPTY_OMMER_ROOT_HASH, U256,
+    Address, BlockNumber, Chain, CustomPrecompile, ForkFilter, ForkFilterKey, ForkHash, ForkId,
+    Genesis, Hardfork, Head, Header, NamedChain, NodeRecord, SealedHeader, B256,
+    EMPTY_OMMER_ROOT_HASH, U256,
 };
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
@@ -74,6 +75,7 @@ pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 3500,
+        precompiles: Vec::new(),
     }
     .into()
 });
@@ -117,6 +119,7 @@ pub static GOERLI: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        precompiles: Vec::new(),
     }
     .into()
 });
@@ -164,6 +167,7 @@ pub static SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        precompiles: Vec::new(),
     }
     .into()
 });
@@ -206,6 +210,7 @@ pub static HOLESKY: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        precompiles: Vec::new(),
     }
     .into()
 });
@@ -304,6 +309,7 @@ pub static OP_MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             .into(),
         ),
         prune_delete_limit: 1700,
+        precompiles: Vec::new(),
         ..Default::default()
     }
     .into()
@@ -358,6 +364,7 @@ pub static OP_SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             .into(),
         ),
         prune_delete_limit: 1700,
+        precompiles: Vec::new(),
         ..Default::default()
     }
     .into()
@@ -412,6 +419,7 @@ pub static BASE_SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             .into(),
         ),
         prune_delete_limit: 1700,
+        precompiles: Vec::new(),
         ..Default::default()
     }
     .into()
@@ -466,6 +474,7 @@ pub static BASE_MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
             .into(),
         ),
         prune_delete_limit: 1700,
+        precompiles: Vec::new(),
         ..Default::default()
     }
     .into()
@@ -553,6 +562,10 @@ pub struct ChainSpec {
     /// data coming in.
     #[serde(default)]
     pub prune_delete_limit: usize,
+
+    /// The additional precompiles of the chain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub precompiles: Vec<CustomPrecompile>,
 }
 
 impl Default for ChainSpec {
@@ -567,6 +580,7 @@ impl Default for ChainSpec {
             deposit_contract: Default::default(),
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
             prune_delete_limit: MAINNET.prune_delete_limit,
+            precompiles: Default::default(),
         }
     }
 }
@@ -37,12 +37,14 @@ mod peer;
 pub mod proofs;
 mod prune;
 mod receipt;
+mod reorg;
 /// Helpers for working with revm
 pub mod revm;
 pub mod serde_helper;
 pub mod stage;
 pub mod static_file;
 mod storage;
+mod token_transfer;
 /// Helpers for working with transactions
 pub mod transaction;
 pub mod trie;
@@ -56,9 +58,10 @@ pub use block::{
     ForkBlock, RpcBlockHash, SealedBlock, SealedBlockWithSenders,
 };
 pub use chain::{
-    AllGenesisFormats, BaseFeeParams, BaseFeeParamsKind, Chain, ChainInfo, ChainSpec,
-    ChainSpecBuilder, DisplayHardforks, ForkBaseFeeParams, ForkCondition, ForkTimestamps,
-    NamedChain, DEV, GOERLI, HOLESKY, MAINNET, SEPOLIA,
+    AllGenesisFormats, BaseFeeParams, BaseFeeParamsKind, BuiltinPrecompile, Chain, ChainInfo,
+    ChainSpec, ChainSpecBuilder, CustomPrecompile, DisplayHardforks, ForkBaseFeeParams,
+    ForkCondition, ForkTimestamps, NamedChain, PrecompileImplementation, WasmPrecompile, DEV,
+    GOERLI, HOLESKY, MAINNET, SEPOLIA,
 };
 #[cfg(feature = "zstd-codec")]
 pub use compression::*;
@@ -84,8 +87,10 @@ pub use prune::{
     MINIMUM_PRUNING_DISTANCE,
 };
 pub use receipt::{Receipt, ReceiptWithBloom, ReceiptWithBloomRef, Receipts};
+pub use reorg::ReorgRecord;
 pub use static_file::StaticFileSegment;
 pub use storage::StorageEntry;
+pub use token_transfer::{TokenTransfer, TRANSFER_EVENT_TOPIC};
 
 #[cfg(feature = "c-kzg")]
 pub use transaction::{
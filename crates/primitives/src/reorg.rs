@@ -0,0 +1,26 @@
+use crate::{BlockNumber, B256};
+use reth_codecs::{main_codec, Compact};
+
+/// A reorg of the canonical chain that was processed by the node.
+#[main_codec]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorgRecord {
+    /// Unix timestamp in seconds at which the reorg was processed.
+    pub timestamp: u64,
+    /// Number of the highest block shared by the old and the new canonical chain.
+    pub fork_block: BlockNumber,
+    /// Number of the canonical tip before the reorg.
+    pub old_tip_number: BlockNumber,
+    /// Hash of the canonical tip before the reorg.
+    pub old_tip_hash: B256,
+    /// Number of the canonical tip after the reorg.
+    pub new_tip_number: BlockNumber,
+    /// Hash of the canonical tip after the reorg.
+    pub new_tip_hash: B256,
+    /// Number of blocks that were removed from the canonical chain.
+    pub depth: u64,
+    /// Hashes of the transactions of the removed blocks that are not part of the new canonical
+    /// chain.
+    pub reorged_transactions: Vec<B256>,
+}
@@ -0,0 +1,168 @@
+use crate::{b256, Address, BlockNumber, Log, Receipt, TxHash, B256, U256};
+use reth_codecs::{main_codec, Compact};
+
+/// Topic of the `Transfer(address,address,uint256)` event emitted by ERC-20 and ERC-721 tokens.
+pub const TRANSFER_EVENT_TOPIC: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// A transfer of an ERC-20 or ERC-721 token, decoded from a `Transfer` event.
+#[main_codec]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfer {
+    /// Address of the token contract that emitted the event.
+    pub token: Address,
+    /// Sender of the tokens.
+    pub from: Address,
+    /// Recipient of the tokens.
+    pub to: Address,
+    /// Transferred amount of an ERC-20 token or id of the transferred ERC-721 token.
+    pub value: U256,
+    /// Whether the token is an ERC-721 token.
+    pub erc721: bool,
+    /// Number of the block that contains the transfer.
+    pub block_number: BlockNumber,
+    /// Hash of the transaction that emitted the event.
+    pub transaction_hash: TxHash,
+    /// Index of the event in the block.
+    pub log_index: u64,
+}
+
+impl TokenTransfer {
+    /// Decodes a `Transfer` event.
+    ///
+    /// ERC-20 and ERC-721 tokens emit the same event, the amount of an ERC-20 transfer is the data
+    /// of the event while the id of an ERC-721 token is indexed.
+    ///
+    /// Returns `None` if the log is not a `Transfer` event.
+    pub fn from_log(
+        log: &Log,
+        block_number: BlockNumber,
+        transaction_hash: TxHash,
+        log_index: u64,
+    ) -> Option<Self> {
+        let (from, to, value, erc721) = match (log.topics(), log.data.data.len()) {
+            ([topic, from, to], 32) if *topic == TRANSFER_EVENT_TOPIC => {
+                (from, to, U256::from_be_slice(&log.data.data), false)
+            }
+            ([topic, from, to, token_id], 0) if *topic == TRANSFER_EVENT_TOPIC => {
+                (from, to, U256::from_be_bytes(token_id.0), true)
+            }
+            _ => return None,
+        };
+        Some(Self {
+            token: log.address,
+            from: Address::from_word(*from),
+            to: Address::from_word(*to),
+            value,
+            erc721,
+            block_number,
+            transaction_hash,
+            log_index,
+        })
+    }
+
+    /// Decodes the `Transfer` events of the receipts of a block.
+    ///
+    /// The transaction hashes and the receipts are expected in the order of the block, with `None`
+    /// for every pruned receipt. The index of a log in the block can't be derived past a pruned
+    /// receipt, so the events of the transactions after it are skipped.
+    pub fn from_receipts<'a>(
+        block_number: BlockNumber,
+        transaction_hashes: impl IntoIterator<Item = TxHash>,
+        receipts: impl IntoIterator<Item = Option<&'a Receipt>>,
+    ) -> Vec<Self> {
+        transaction_hashes
+            .into_iter()
+            .zip(receipts)
+            .map_while(|(hash, receipt)| receipt.map(|receipt| (hash, receipt)))
+            .flat_map(|(hash, receipt)| receipt.logs.iter().map(move |log| (hash, log)))
+            .enumerate()
+            .filter_map(|(log_index, (hash, log))| {
+                Self::from_log(log, block_number, hash, log_index as u64)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Bytes;
+
+    #[test]
+    fn decode_transfer_events() {
+        let token = Address::with_last_byte(1);
+        let from = Address::with_last_byte(2);
+        let to = Address::with_last_byte(3);
+        let hash = B256::with_last_byte(4);
+
+        let erc20 = Log::new_unchecked(
+            token,
+            vec![TRANSFER_EVENT_TOPIC, from.into_word(), to.into_word()],
+            Bytes::from(U256::from(100).to_be_bytes::<32>()),
+        );
+        let transfer = TokenTransfer::from_log(&erc20, 1, hash, 2).unwrap();
+        assert_eq!(
+            transfer,
+            TokenTransfer {
+                token,
+                from,
+                to,
+                value: U256::from(100),
+                erc721: false,
+                block_number: 1,
+                transaction_hash: hash,
+                log_index: 2
+            }
+        );
+
+        let erc721 = Log::new_unchecked(
+            token,
+            vec![TRANSFER_EVENT_TOPIC, from.into_word(), to.into_word(), B256::with_last_byte(7)],
+            Bytes::new(),
+        );
+        let transfer = TokenTransfer::from_log(&erc721, 1, hash, 2).unwrap();
+        assert!(transfer.erc721);
+        assert_eq!(transfer.value, U256::from(7));
+
+        let other = Log::new_unchecked(token, vec![B256::ZERO], Bytes::new());
+        assert!(TokenTransfer::from_log(&other, 1, hash, 2).is_none());
+    }
+
+    #[test]
+    fn decode_transfer_events_of_receipts() {
+        let transfer = |to: u8| {
+            Log::new_unchecked(
+                Address::with_last_byte(1),
+                vec![
+                    TRANSFER_EVENT_TOPIC,
+                    Address::with_last_byte(2).into_word(),
+                    Address::with_last_byte(to).into_word(),
+                ],
+                Bytes::from(U256::from(100).to_be_bytes::<32>()),
+            )
+        };
+        let receipt = |logs| Receipt { logs, ..Default::default() };
+        let hashes = [B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3)];
+
+        let receipts = [
+            receipt(vec![Log::new_unchecked(Address::ZERO, vec![], Bytes::new()), transfer(3)]),
+            receipt(vec![]),
+            receipt(vec![transfer(4)]),
+        ];
+        let transfers = TokenTransfer::from_receipts(1, hashes, receipts.iter().map(Some));
+        assert_eq!(
+            transfers.iter().map(|t| (t.transaction_hash, t.log_index)).collect::<Vec<_>>(),
+            vec![(hashes[0], 1), (hashes[2], 2)]
+        );
+
+        // the events after a pruned receipt are not attributed to the wrong transaction
+        let receipts = [Some(&receipts[0]), None, Some(&receipts[2])];
+        let transfers = TokenTransfer::from_receipts(1, hashes, receipts);
+        assert_eq!(
+            transfers.iter().map(|t| (t.transaction_hash, t.log_index)).collect::<Vec<_>>(),
+            vec![(hashes[0], 1)]
+        );
+    }
+}
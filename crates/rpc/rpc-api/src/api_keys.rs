@@ -0,0 +1,12 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_types::ApiKeyUsage;
+
+/// Admin rpc interface for the usage of the API keys of the rpc server.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait ApiKeysApi {
+    /// Returns the compute units used by the given API key, or by all API keys if no key is
+    /// given.
+    #[method(name = "apiKeyUsage")]
+    fn api_key_usage(&self, key: Option<String>) -> RpcResult<Vec<ApiKeyUsage>>;
+}
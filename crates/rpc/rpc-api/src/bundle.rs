@@ -22,6 +22,23 @@ pub trait EthCallBundleApi {
     ) -> jsonrpsee::core::RpcResult<EthCallBundleResponse>;
 }
 
+/// A subset of the [EthBundleApi] API interface that only supports submitting bundles to the
+/// local payload builder with `eth_sendBundle` and `eth_cancelBundle`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthSendBundleApi {
+    /// `eth_sendBundle` can be used to send your bundles to the builder.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(&self, bundle: EthSendBundle)
+        -> jsonrpsee::core::RpcResult<EthBundleHash>;
+
+    /// `eth_cancelBundle` is used to prevent a submitted bundle from being included on-chain.
+    ///
+    /// The bundle is identified by the `replacementUuid` it was submitted with.
+    #[method(name = "cancelBundle")]
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> jsonrpsee::core::RpcResult<()>;
+}
+
 /// The __full__ Eth bundle rpc interface.
 ///
 /// See also <https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint>
@@ -0,0 +1,12 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_types::ConfigReload;
+
+/// Admin rpc interface for reloading the configuration file of the node.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait ConfigReloadApi {
+    /// Re-reads the configuration file and applies the changed settings that can be changed while
+    /// the node is running.
+    #[method(name = "reloadConfig")]
+    async fn reload_config(&self) -> RpcResult<ConfigReload>;
+}
@@ -1,11 +1,11 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, B256, U64};
 use reth_rpc_types::{
     trace::geth::{
-        BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace,
-        TraceResult,
+        BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions,
+        GethDefaultTracingOptions, GethTrace, TraceResult,
     },
-    Bundle, RichBlock, StateContext, TransactionRequest,
+    Bundle, ExecutionWitness, RichBlock, StateContext, StructLogsChunk, TransactionRequest,
 };
 
 /// Debug rpc interface.
@@ -38,14 +38,22 @@ pub trait DebugApi {
     #[method(name = "getBadBlocks")]
     async fn bad_blocks(&self) -> RpcResult<Vec<RichBlock>>;
 
-    /// Returns the structured logs created during the execution of EVM between two blocks
-    /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
+    /// Creates a subscription that replays the blocks between two blocks (excluding start) and
+    /// emits the traces of every block in order, configured like `debug_traceBlockByNumber`.
+    ///
+    /// Multiple blocks are traced concurrently, but only as far ahead of the subscriber as the
+    /// node's concurrency limit allows.
+    #[subscription(
+        name = "traceChain",
+        unsubscribe = "unsubscribeTraceChain",
+        item = BlockTraceResult
+    )]
     async fn debug_trace_chain(
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -81,6 +89,23 @@ pub trait DebugApi {
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<Vec<TraceResult>>;
 
+    /// Creates a subscription that replays the block like `debug_traceBlockByHash` and emits the
+    /// trace of each transaction, in order.
+    ///
+    /// The traces are serialized while the block is replayed and buffered on disk if they're
+    /// large, e.g. `prestateTracer` traces of blocks that touch a lot of storage slots, so the
+    /// traces are never held in memory at once.
+    #[subscription(
+        name = "subscribeTraceBlock",
+        unsubscribe = "unsubscribeTraceBlock",
+        item = TraceResult
+    )]
+    async fn debug_subscribe_trace_block(
+        &self,
+        block: BlockId,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
     /// The `debug_traceTransaction` debugging method will attempt to run the transaction in the
     /// exact same manner as it was executed on the network. It will replay any transaction that
     /// may have been executed prior to this one before it will finally attempt to execute the
@@ -92,6 +117,23 @@ pub trait DebugApi {
         opts: Option<GethDebugTracingOptions>,
     ) -> RpcResult<GethTrace>;
 
+    /// Creates a subscription that traces the transaction with the structlog tracer, like
+    /// `debug_traceTransaction`, and emits the structured logs in chunks while the transaction is
+    /// replayed.
+    ///
+    /// The last chunk carries the result of the transaction. This avoids building the entire
+    /// trace in memory for transactions that execute a large number of opcodes.
+    #[subscription(
+        name = "subscribeTraceTransaction",
+        unsubscribe = "unsubscribeTraceTransaction",
+        item = StructLogsChunk
+    )]
+    async fn debug_subscribe_trace_transaction(
+        &self,
+        tx_hash: B256,
+        opts: Option<GethDefaultTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
+
     /// The `debug_traceCall` method lets you run an `eth_call` within the context of the given
     /// block execution using the final state of parent block as the base.
     ///
@@ -132,6 +174,13 @@ pub trait DebugApi {
         opts: Option<GethDebugTracingCallOptions>,
     ) -> RpcResult<Vec<Vec<GethTrace>>>;
 
+    /// Re-executes the block on top of its parent's state and returns the stateless execution
+    /// witness of the block: the trie nodes, bytecodes and key preimages of all accessed state and
+    /// the headers of the accessed ancestors.
+    #[method(name = "executionWitness")]
+    async fn debug_execution_witness(&self, block: BlockNumberOrTag)
+        -> RpcResult<ExecutionWitness>;
+
     /// Sets the logging backtrace location. When a backtrace location is set and a log message is
     /// emitted at that location, the stack of the goroutine executing the log statement will
     /// be printed to stderr.
@@ -280,11 +329,6 @@ pub trait DebugApi {
     #[method(name = "setGCPercent")]
     async fn debug_set_gc_percent(&self, v: i32) -> RpcResult<()>;
 
-    /// Sets the current head of the local chain by block number. Note, this is a destructive action
-    /// and may severely damage your chain. Use with extreme caution.
-    #[method(name = "setHead")]
-    async fn debug_set_head(&self, number: u64) -> RpcResult<()>;
-
     /// Sets the rate of mutex profiling.
     #[method(name = "setMutexProfileFraction")]
     async fn debug_set_mutex_profile_fraction(&self, rate: i32) -> RpcResult<()>;
@@ -376,3 +420,22 @@ pub trait DebugApi {
     #[method(name = "writeMutexProfile")]
     async fn debug_write_mutex_profile(&self, file: String) -> RpcResult<()>;
 }
+
+/// Debug rpc methods that rewind the canonical chain.
+///
+/// These are destructive and only meant for private and test networks, so they are served
+/// separately from [DebugApiServer] and only if explicitly enabled.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
+pub trait DebugRewindApi {
+    /// Sets the current head of the local chain by block number. Note, this is a destructive action
+    /// and may severely damage your chain. Use with extreme caution.
+    #[method(name = "setHead")]
+    async fn debug_set_head(&self, number: U64) -> RpcResult<()>;
+
+    /// Rewinds the canonical chain to the given block and returns the hash of the new head.
+    ///
+    /// All canonical blocks above the given block are unwound.
+    #[method(name = "rewindToBlock")]
+    async fn debug_rewind_to_block(&self, block_id: BlockId) -> RpcResult<B256>;
+}
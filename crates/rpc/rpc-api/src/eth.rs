@@ -4,9 +4,11 @@ use reth_primitives::{
     Address, BlockId, BlockNumberOrTag, Bytes, B256, B64, U256, U64,
 };
 use reth_rpc_types::{
-    state::StateOverride, AccessListWithGasUsed, AnyTransactionReceipt, BlockOverrides, Bundle,
-    EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Header, Index, RichBlock,
-    StateContext, SyncStatus, Transaction, TransactionRequest, Work,
+    state::StateOverride, AnyTransactionReceipt, AuthorizationRequest, BlockOverrides, Bundle,
+    CreateAccessListOptions, CreateAccessListResult, EIP1186AccountProofResponse, EthCallResponse,
+    FeeHistory, Header, Index, RawTransactionResult, RichBlock, SignedAuthorization,
+    SimulatePayload, SimulatedBlock, StateContext, SyncStatus, Transaction, TransactionBlobSidecar,
+    TransactionConditional, TransactionRequest, Work,
 };
 
 /// Eth rpc interface: <https://ethereum.github.io/execution-apis/api-documentation/>
@@ -78,6 +80,14 @@ pub trait EthApi {
         block_id: BlockId,
     ) -> RpcResult<Option<Vec<AnyTransactionReceipt>>>;
 
+    /// Returns the blob sidecars of the blob transactions in the given block that are still kept
+    /// by the node.
+    #[method(name = "getBlobSidecars")]
+    async fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Option<Vec<TransactionBlobSidecar>>>;
+
     /// Returns an uncle block of the given block and index.
     #[method(name = "getUncleByBlockHashAndIndex")]
     async fn uncle_by_block_hash_and_index(
@@ -194,6 +204,14 @@ pub trait EthApi {
         state_override: Option<StateOverride>,
     ) -> RpcResult<Vec<EthCallResponse>>;
 
+    /// Simulates blocks of calls on top of a block, with block and state overrides per block.
+    #[method(name = "simulateV1")]
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_number: Option<BlockId>,
+    ) -> RpcResult<Vec<SimulatedBlock>>;
+
     /// Generates an access list for a transaction.
     ///
     /// This method creates an [EIP2930](https://eips.ethereum.org/EIPS/eip-2930) type accessList based on a given Transaction.
@@ -208,12 +226,16 @@ pub trait EthApi {
     /// when the transaction is actually mined. Adding an accessList to your transaction does
     /// not necessary result in lower gas usage compared to a transaction without an access
     /// list.
+    ///
+    /// The optional options can request the gas used without the access list as well, and that
+    /// the call is executed again with the generated access list until the list converges.
     #[method(name = "createAccessList")]
     async fn create_access_list(
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-    ) -> RpcResult<AccessListWithGasUsed>;
+        options: Option<CreateAccessListOptions>,
+    ) -> RpcResult<CreateAccessListResult>;
 
     /// Generates and returns an estimate of how much gas is necessary to allow the transaction to
     /// complete.
@@ -286,6 +308,24 @@ pub trait EthApi {
     #[method(name = "sendRawTransaction")]
     async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256>;
 
+    /// Sends signed transaction and waits until it is included in a block, returning its receipt.
+    #[method(name = "sendRawTransactionSync")]
+    async fn send_raw_transaction_sync(&self, bytes: Bytes) -> RpcResult<AnyTransactionReceipt>;
+
+    /// Sends multiple signed transactions, returning the hash or the error of every transaction.
+    #[method(name = "sendRawTransactions")]
+    async fn send_raw_transactions(&self, txs: Vec<Bytes>) -> RpcResult<Vec<RawTransactionResult>>;
+
+    /// Sends signed transaction if the conditions hold for the latest block, returning its hash.
+    ///
+    /// The transaction is dropped once the conditions no longer hold.
+    #[method(name = "sendRawTransactionConditional")]
+    async fn send_raw_transaction_conditional(
+        &self,
+        bytes: Bytes,
+        conditional: TransactionConditional,
+    ) -> RpcResult<B256>;
+
     /// Returns an Ethereum specific signature with: sign(keccak256("\x19Ethereum Signed Message:\n"
     /// + len(message) + message))).
     #[method(name = "sign")]
@@ -300,6 +340,15 @@ pub trait EthApi {
     #[method(name = "signTypedData")]
     async fn sign_typed_data(&self, address: Address, data: serde_json::Value) -> RpcResult<Bytes>;
 
+    /// Signs an [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702) authorization that delegates
+    /// the code of the account to the address of the request.
+    #[method(name = "signAuthorization")]
+    async fn sign_authorization(
+        &self,
+        address: Address,
+        request: AuthorizationRequest,
+    ) -> RpcResult<SignedAuthorization>;
+
     /// Returns the account and storage values of the specified account including the Merkle-proof.
     /// This call can be used to verify that the data you are pulling from is not tampered with.
     #[method(name = "getProof")]
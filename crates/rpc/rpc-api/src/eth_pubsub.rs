@@ -1,5 +1,5 @@
 use jsonrpsee::proc_macros::rpc;
-use reth_rpc_types::pubsub::{Params, SubscriptionKind};
+use reth_rpc_types::{EthSubscriptionKind, EthSubscriptionParams};
 
 /// Ethereum pub-sub rpc interface.
 #[rpc(server, namespace = "eth")]
@@ -12,7 +12,7 @@ pub trait EthPubSubApi {
     )]
     async fn subscribe(
         &self,
-        kind: SubscriptionKind,
-        params: Option<Params>,
+        kind: EthSubscriptionKind,
+        params: Option<EthSubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -16,7 +16,9 @@
 
 mod admin;
 mod anvil;
+mod api_keys;
 mod bundle;
+mod config_reload;
 mod debug;
 mod engine;
 mod eth;
@@ -28,10 +30,12 @@ mod mev;
 mod net;
 mod optimism;
 mod otterscan;
+mod personal;
 mod reth;
 mod rpc;
 mod trace;
 mod txpool;
+mod user_operation;
 mod validation;
 mod web3;
 
@@ -42,19 +46,23 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
-        bundle::{EthBundleApiServer, EthCallBundleApiServer},
-        debug::DebugApiServer,
+        api_keys::ApiKeysApiServer,
+        bundle::{EthBundleApiServer, EthCallBundleApiServer, EthSendBundleApiServer},
+        config_reload::ConfigReloadApiServer,
+        debug::{DebugApiServer, DebugRewindApiServer},
         engine::{EngineApiServer, EngineEthApiServer},
         eth::EthApiServer,
         eth_filter::EthFilterApiServer,
         eth_pubsub::EthPubSubApiServer,
-        mev::MevApiServer,
+        mev::{MevApiServer, MevSendBundleApiServer},
         net::NetApiServer,
         otterscan::OtterscanServer,
+        personal::PersonalApiServer,
         reth::RethApiServer,
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
+        user_operation::EthUserOperationApiServer,
         validation::BlockSubmissionValidationApiServer,
         web3::Web3ApiServer,
     };
@@ -70,19 +78,23 @@ pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
         anvil::AnvilApiClient,
-        bundle::{EthBundleApiClient, EthCallBundleApiClient},
-        debug::DebugApiClient,
+        api_keys::ApiKeysApiClient,
+        bundle::{EthBundleApiClient, EthCallBundleApiClient, EthSendBundleApiClient},
+        config_reload::ConfigReloadApiClient,
+        debug::{DebugApiClient, DebugRewindApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
         eth::EthApiClient,
         eth_filter::EthFilterApiClient,
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
-        mev::MevApiClient,
+        mev::{MevApiClient, MevSendBundleApiClient},
         net::NetApiClient,
         otterscan::OtterscanClient,
+        personal::PersonalApiClient,
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
+        user_operation::EthUserOperationApiClient,
         validation::BlockSubmissionValidationApiClient,
         web3::Web3ApiClient,
     };
@@ -3,6 +3,19 @@ use reth_rpc_types::{
     SendBundleRequest, SendBundleResponse, SimBundleOverrides, SimBundleResponse,
 };
 
+/// A subset of the [MevApi] API interface that only supports `mev_sendBundle`.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "mev"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "mev"))]
+pub trait MevSendBundleApi {
+    /// Submitting bundles to the local payload builder. It takes in a bundle and provides a
+    /// bundle hash as a return value.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(
+        &self,
+        request: SendBundleRequest,
+    ) -> jsonrpsee::core::RpcResult<SendBundleResponse>;
+}
+
 /// Mev rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "mev"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "mev"))]
@@ -0,0 +1,51 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::{Address, B256};
+use reth_rpc_types::TransactionRequest;
+
+/// Personal rpc interface.
+///
+/// This is a deprecated namespace of geth for account management that is only provided for
+/// compatibility with legacy tooling.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "personal"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "personal"))]
+pub trait PersonalApi {
+    /// Returns the addresses of all accounts in the keystore.
+    #[method(name = "listAccounts")]
+    async fn list_accounts(&self) -> RpcResult<Vec<Address>>;
+
+    /// Creates a new account in the keystore, encrypted with the given passphrase, and returns its
+    /// address.
+    #[method(name = "newAccount")]
+    async fn new_account(&self, passphrase: String) -> RpcResult<Address>;
+
+    /// Imports the hex encoded private key into the keystore, encrypted with the given passphrase,
+    /// and returns the address of its account.
+    #[method(name = "importRawKey")]
+    async fn import_raw_key(&self, private_key: String, passphrase: String) -> RpcResult<Address>;
+
+    /// Unlocks the keystore account with the passphrase for the given duration in seconds.
+    ///
+    /// Defaults to 300 seconds, a duration of 0 keeps the account unlocked until shutdown.
+    #[method(name = "unlockAccount")]
+    async fn unlock_account(
+        &self,
+        address: Address,
+        passphrase: String,
+        duration: Option<u64>,
+    ) -> RpcResult<bool>;
+
+    /// Locks the keystore account again, so it can't sign until it's unlocked.
+    #[method(name = "lockAccount")]
+    async fn lock_account(&self, address: Address) -> RpcResult<bool>;
+
+    /// Signs the transaction with the keystore account of `from`, decrypted with the passphrase,
+    /// and submits it to the pool.
+    ///
+    /// The account is not unlocked by this call.
+    #[method(name = "sendTransaction")]
+    async fn send_transaction(
+        &self,
+        request: TransactionRequest,
+        passphrase: String,
+    ) -> RpcResult<B256>;
+}
@@ -1,5 +1,9 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_primitives::{Address, BlockId, Bytes, ReorgRecord, B256, U256};
+use reth_rpc_types::{
+    engine::PayloadStatus, AnyTransactionReceipt, BlobWithProof, ExecutionPayload, FeeEstimate,
+    Preconfirmation, TokenTransfersPage, TransactionStatus, TransactionsPage,
+};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +16,112 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Stores the blob for blob transactions signed by the node and returns its versioned hash.
+    ///
+    /// The returned versioned hash can be referenced in the `blobVersionedHashes` of
+    /// `eth_sendTransaction` and `eth_signTransaction` requests.
+    #[method(name = "storeBlob")]
+    async fn reth_store_blob(&self, blob: Bytes) -> RpcResult<B256>;
+
+    /// Returns the most recent reorgs processed by the node, newest first.
+    ///
+    /// Returns at most `limit` reorgs, defaults to 100.
+    #[method(name = "getReorgHistory")]
+    async fn reth_get_reorg_history(&self, limit: Option<u64>) -> RpcResult<Vec<ReorgRecord>>;
+
+    /// Returns a page of the transactions sent from or to the address, including the transaction
+    /// that created the address if it is a contract, the most recent first.
+    ///
+    /// Pages start at 0 and contain at most `page_size` transactions, defaults to 25.
+    ///
+    /// Requires the optional address transactions index to be enabled.
+    #[method(name = "getTransactionsByAddress")]
+    async fn reth_get_transactions_by_address(
+        &self,
+        address: Address,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> RpcResult<TransactionsPage>;
+
+    /// Returns a page of the ERC-20 and ERC-721 token transfers sent from or to the address, the
+    /// most recent first.
+    ///
+    /// Pages start at 0 and contain at most 25 transfers.
+    ///
+    /// Requires the optional token transfers index to be enabled.
+    #[method(name = "getTokenTransfers")]
+    async fn reth_get_token_transfers(
+        &self,
+        address: Address,
+        page: Option<u64>,
+    ) -> RpcResult<TokenTransfersPage>;
+
+    /// Estimates the fees of a transaction to be included within `target_blocks` blocks, by
+    /// building hypothetical next blocks from the transaction pool.
+    ///
+    /// The `confidence` is a percentage, higher values result in higher fees and default to 90.
+    /// The `target_blocks` default to 1 and are at most 10.
+    #[method(name = "estimateFees")]
+    async fn reth_estimate_fees(
+        &self,
+        confidence: Option<f64>,
+        target_blocks: Option<u64>,
+    ) -> RpcResult<FeeEstimate>;
+
+    /// Returns the receipts of the transactions in the order of the hashes, `null` for
+    /// transactions that are not mined.
+    ///
+    /// The receipts of each block are loaded once for all of its transactions, which is cheaper
+    /// than requesting the receipts one by one. At most 1000 hashes are allowed.
+    #[method(name = "getTransactionReceipts")]
+    async fn reth_get_transaction_receipts(
+        &self,
+        hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<AnyTransactionReceipt>>>;
+
+    /// Returns the status of the transaction: whether it is pending or queued in the pool, mined,
+    /// or was replaced or dropped from the pool.
+    ///
+    /// Replaced and dropped transactions are only known for a bounded number of the most recently
+    /// removed transactions.
+    #[method(name = "getTransactionStatus")]
+    async fn reth_get_transaction_status(&self, hash: B256) -> RpcResult<TransactionStatus>;
+
+    /// Returns the blob with the given versioned hash and its KZG commitment and proof.
+    ///
+    /// Only blobs of canonical blob transactions whose sidecars are still kept by the node are
+    /// available, which is until the block is finalized or the end of the configured blob
+    /// retention window.
+    #[method(name = "getBlobByVersionedHash")]
+    async fn reth_get_blob_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> RpcResult<Option<BlobWithProof>>;
+
+    /// Validates the payload like `engine_newPayload`, including its execution and state root,
+    /// without inserting the block or affecting fork choice.
+    ///
+    /// The `versioned_hashes` and `parent_beacon_block_root` are the additional parameters of
+    /// `engine_newPayloadV3` and are required for Cancun payloads. Returns `SYNCING` if the parent
+    /// of the payload is unknown.
+    #[method(name = "validatePayload")]
+    async fn reth_validate_payload(
+        &self,
+        payload: ExecutionPayload,
+        versioned_hashes: Option<Vec<B256>>,
+        parent_beacon_block_root: Option<B256>,
+    ) -> RpcResult<PayloadStatus>;
+
+    /// Creates a subscription that emits the transactions as soon as they are selected into the
+    /// payload the node is building, with their receipts simulated on top of the parent block.
+    ///
+    /// Every transaction is emitted once per parent block, when it first appears in a better
+    /// payload. This is only useful if the node builds the blocks of the chain.
+    #[subscription(
+        name = "subscribePreconfirmations",
+        unsubscribe = "unsubscribePreconfirmations",
+        item = Preconfirmation
+    )]
+    async fn reth_subscribe_preconfirmations(&self) -> jsonrpsee::core::SubscriptionResult;
 }
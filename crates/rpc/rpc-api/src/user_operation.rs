@@ -0,0 +1,38 @@
+//! Additional `eth_` functions for [ERC-4337](https://eips.ethereum.org/EIPS/eip-4337) account
+//! abstraction, see also the [bundler RPC spec](https://github.com/eth-infinitism/bundler-spec).
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::{Address, B256};
+use reth_rpc_types::{UserOperation, UserOperationGasEstimation, UserOperationReceipt};
+
+/// The `eth_` user operation rpc interface of a bundler.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthUserOperationApi {
+    /// Validates the user operation against the EntryPoint and adds it to the user operation
+    /// mempool.
+    ///
+    /// Returns the hash of the user operation.
+    #[method(name = "sendUserOperation")]
+    async fn send_user_operation(
+        &self,
+        user_operation: UserOperation,
+        entry_point: Address,
+    ) -> RpcResult<B256>;
+
+    /// Estimates the gas values of the user operation.
+    #[method(name = "estimateUserOperationGas")]
+    async fn estimate_user_operation_gas(
+        &self,
+        user_operation: UserOperation,
+        entry_point: Address,
+    ) -> RpcResult<UserOperationGasEstimation>;
+
+    /// Returns the receipt of the user operation with the given hash, if it was included.
+    #[method(name = "getUserOperationReceipt")]
+    async fn user_operation_receipt(&self, hash: B256) -> RpcResult<Option<UserOperationReceipt>>;
+
+    /// Returns the EntryPoints that are supported.
+    #[method(name = "supportedEntryPoints")]
+    fn supported_entry_points(&self) -> RpcResult<Vec<Address>>;
+}
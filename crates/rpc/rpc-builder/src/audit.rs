@@ -0,0 +1,638 @@
+//! Audit log of the RPC calls served by the http, ws and ipc servers.
+//!
+//! The audit log records the method, the hash of the params, the latency, the size of the response
+//! and the class of the error of a sample of the calls, and writes them to a [RpcAuditSink] on a
+//! dedicated thread, so the calls are never blocked by the sink.
+
+use crate::metrics::RpcTransport;
+use alloy_primitives::{keccak256, B256};
+use hyper::{header::CONTENT_TYPE, Body, Client, Method, Request as HttpRequest, Uri};
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse};
+use reth_metrics::{metrics::Counter, Metrics};
+use rolling_file::{RollingConditionBasic, RollingFileAppender};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{
+    future::Future,
+    io::{self, Write},
+    path::PathBuf,
+    pin::Pin,
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, SyncSender, TrySendError},
+        Arc,
+    },
+    task::{Context, Poll},
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tower::Layer;
+use tracing::{debug, warn};
+
+/// The number of records that are buffered for the sink, records are dropped if the sink falls
+/// behind.
+const AUDIT_LOG_CHANNEL_SIZE: usize = 8192;
+
+/// The maximum number of records that are exported to an OTLP collector in one request.
+const OTLP_MAX_BATCH_SIZE: usize = 512;
+
+/// The maximum time a record is buffered before it is exported to an OTLP collector.
+const OTLP_EXPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The timeout of an export request to an OTLP collector.
+const OTLP_EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The destination of the audit log records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RpcAuditSink {
+    /// Records are appended as JSON lines to a file that is rotated when it exceeds the maximum
+    /// size.
+    File {
+        /// Path of the file.
+        path: PathBuf,
+        /// Size in bytes after which the file is rotated.
+        max_size_bytes: u64,
+        /// Maximum number of rotated files that are kept.
+        max_files: usize,
+    },
+    /// Records are exported as log records to the OTLP/HTTP logs endpoint of a collector, e.g.
+    /// `http://localhost:4318/v1/logs`, using the JSON encoding.
+    Otlp {
+        /// The logs endpoint of the collector.
+        endpoint: Uri,
+    },
+}
+
+/// Configuration of the RPC audit log.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcAuditConfig {
+    /// Where the records are written to.
+    pub sink: RpcAuditSink,
+    /// The fraction of the calls that are recorded, between `0.0` and `1.0`.
+    pub sample_rate: f64,
+    /// Calls that take at least this long are always recorded, regardless of the sample rate.
+    pub slow_call_threshold: Option<Duration>,
+}
+
+impl RpcAuditConfig {
+    /// Creates a config that records every call to the given sink.
+    pub fn new(sink: RpcAuditSink) -> Self {
+        Self { sink, sample_rate: 1.0, slow_call_threshold: None }
+    }
+
+    /// Sets the fraction of the calls that are recorded, clamped to `0.0..=1.0`.
+    pub fn with_sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = sample_rate.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the latency above which calls are always recorded.
+    pub fn with_slow_call_threshold(mut self, threshold: Option<Duration>) -> Self {
+        self.slow_call_threshold = threshold;
+        self
+    }
+}
+
+/// The class of the error of a failed call, derived from the JSON-RPC error code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcErrorClass {
+    /// The request is not valid JSON.
+    ParseError,
+    /// The request is not a valid JSON-RPC request.
+    InvalidRequest,
+    /// The method does not exist or is not available.
+    MethodNotFound,
+    /// The params of the method are invalid.
+    InvalidParams,
+    /// An internal error of the server.
+    InternalError,
+    /// The call was rejected because a limit was exceeded.
+    LimitExceeded,
+    /// The execution of a transaction or call reverted.
+    ExecutionReverted,
+    /// Any other error of the server, e.g. an unknown block or a pool error.
+    ServerError,
+}
+
+impl RpcErrorClass {
+    /// Returns the class of the given JSON-RPC error code.
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            -32700 => Self::ParseError,
+            -32600 => Self::InvalidRequest,
+            -32601 => Self::MethodNotFound,
+            -32602 => Self::InvalidParams,
+            -32603 => Self::InternalError,
+            -32005 => Self::LimitExceeded,
+            3 => Self::ExecutionReverted,
+            _ => Self::ServerError,
+        }
+    }
+
+    /// Returns the string representation of the class.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::ParseError => "parse_error",
+            Self::InvalidRequest => "invalid_request",
+            Self::MethodNotFound => "method_not_found",
+            Self::InvalidParams => "invalid_params",
+            Self::InternalError => "internal_error",
+            Self::LimitExceeded => "limit_exceeded",
+            Self::ExecutionReverted => "execution_reverted",
+            Self::ServerError => "server_error",
+        }
+    }
+}
+
+/// A recorded RPC call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RpcAuditRecord {
+    /// Unix timestamp in milliseconds of when the call was received.
+    pub timestamp_ms: u64,
+    /// The transport the call was made over.
+    pub transport: &'static str,
+    /// The called method.
+    pub method: String,
+    /// Keccak256 hash of the raw params, if the call has params.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params_hash: Option<B256>,
+    /// Time it took to process the call, in microseconds.
+    pub latency_us: u64,
+    /// Size of the serialized response in bytes.
+    pub response_size: usize,
+    /// The JSON-RPC error code, if the call failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<i32>,
+    /// The class of the error, if the call failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_class: Option<RpcErrorClass>,
+}
+
+/// Handle to a running RPC audit log.
+///
+/// The records are sent to a dedicated thread that writes them to the sink. The thread exits once
+/// all handles are dropped.
+#[derive(Debug, Clone)]
+pub struct RpcAuditLog {
+    inner: Arc<RpcAuditLogInner>,
+}
+
+#[derive(Debug)]
+struct RpcAuditLogInner {
+    /// Sends records to the sink thread
+    records: SyncSender<RpcAuditRecord>,
+    /// The fraction of the calls that are recorded
+    sample_rate: f64,
+    /// Calls at least this slow are always recorded
+    slow_call_threshold: Option<Duration>,
+    /// Metrics of the audit log
+    metrics: RpcAuditMetrics,
+}
+
+impl RpcAuditLog {
+    /// Opens the sink of the config and spawns the thread that writes the records to it.
+    pub fn spawn(config: RpcAuditConfig) -> io::Result<Self> {
+        let RpcAuditConfig { sink, sample_rate, slow_call_threshold } = config;
+        let sink = match sink {
+            RpcAuditSink::File { path, max_size_bytes, max_files } => {
+                if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(dir)?;
+                }
+                let condition = RollingConditionBasic::new().max_size(max_size_bytes);
+                Sink::File(RollingFileAppender::new(path, condition, max_files)?)
+            }
+            RpcAuditSink::Otlp { endpoint } => Sink::Otlp(OtlpExporter::new(endpoint)?),
+        };
+
+        let (records, rx) = mpsc::sync_channel(AUDIT_LOG_CHANNEL_SIZE);
+        let metrics = RpcAuditMetrics::default();
+        let sink_metrics = metrics.clone();
+        thread::Builder::new()
+            .name("rpc-audit-log".to_string())
+            .spawn(move || sink.run(rx, sink_metrics))?;
+
+        Ok(Self {
+            inner: Arc::new(RpcAuditLogInner {
+                records,
+                sample_rate: sample_rate.clamp(0.0, 1.0),
+                slow_call_threshold,
+                metrics,
+            }),
+        })
+    }
+
+    /// Returns true if a call is sampled.
+    fn sample(&self) -> bool {
+        let rate = self.inner.sample_rate;
+        rate >= 1.0 || (rate > 0.0 && rand::random::<f64>() < rate)
+    }
+
+    /// Returns true if a call that took `elapsed` is recorded.
+    fn should_record(&self, sampled: bool, elapsed: Duration) -> bool {
+        sampled || self.inner.slow_call_threshold.is_some_and(|threshold| elapsed >= threshold)
+    }
+
+    /// Hands the record over to the sink thread, the record is dropped if the sink falls behind.
+    fn record(&self, record: RpcAuditRecord) {
+        match self.inner.records.try_send(record) {
+            Ok(()) => self.inner.metrics.records.increment(1),
+            Err(TrySendError::Full(_) | TrySendError::Disconnected(_)) => {
+                self.inner.metrics.dropped_records.increment(1)
+            }
+        }
+    }
+}
+
+/// A [RpcServiceT] middleware layer that records the calls of a connection in the [RpcAuditLog].
+#[derive(Debug, Clone)]
+pub(crate) struct RpcAuditLayer {
+    log: RpcAuditLog,
+    transport: RpcTransport,
+}
+
+impl RpcAuditLayer {
+    pub(crate) fn new(log: RpcAuditLog, transport: RpcTransport) -> Self {
+        Self { log, transport }
+    }
+
+    /// Creates the audit layer for HTTP.
+    pub(crate) fn http(log: &RpcAuditLog) -> Self {
+        Self::new(log.clone(), RpcTransport::Http)
+    }
+
+    /// Creates the audit layer for Ws.
+    pub(crate) fn ws(log: &RpcAuditLog) -> Self {
+        Self::new(log.clone(), RpcTransport::WebSocket)
+    }
+
+    /// Creates the audit layer for Ipc.
+    pub(crate) fn ipc(log: &RpcAuditLog) -> Self {
+        Self::new(log.clone(), RpcTransport::Ipc)
+    }
+}
+
+impl<S> Layer<S> for RpcAuditLayer {
+    type Service = RpcAuditService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcAuditService { layer: self.clone(), inner }
+    }
+}
+
+/// A [RpcServiceT] middleware that records the calls in the [RpcAuditLog].
+#[derive(Debug, Clone)]
+pub(crate) struct RpcAuditService<S> {
+    layer: RpcAuditLayer,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcAuditService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = AuditedRequestFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let log = &self.layer.log;
+        let sampled = log.sample();
+        // the params are hashed up front, because the request is consumed by the call
+        let call = (sampled || log.inner.slow_call_threshold.is_some()).then(|| AuditedCall {
+            log: log.clone(),
+            sampled,
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u64,
+            transport: self.layer.transport.as_str(),
+            method: req.method.to_string(),
+            params_hash: req.params.as_ref().map(|params| keccak256(params.get())),
+        });
+        AuditedRequestFuture { fut: self.inner.call(req), started_at: Instant::now(), call }
+    }
+}
+
+/// A call that may be recorded once it completes.
+#[derive(Debug)]
+struct AuditedCall {
+    log: RpcAuditLog,
+    sampled: bool,
+    timestamp_ms: u64,
+    transport: &'static str,
+    method: String,
+    params_hash: Option<B256>,
+}
+
+/// Response future that records the call once it completes.
+#[pin_project::pin_project]
+pub(crate) struct AuditedRequestFuture<F> {
+    #[pin]
+    fut: F,
+    /// time when the request started
+    started_at: Instant,
+    /// the call to record, if it can be recorded
+    call: Option<AuditedCall>,
+}
+
+impl<F> std::fmt::Debug for AuditedRequestFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AuditedRequestFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for AuditedRequestFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let res = this.fut.poll(cx);
+        if let Poll::Ready(resp) = &res {
+            let elapsed = this.started_at.elapsed();
+            if let Some(call) =
+                this.call.take().filter(|call| call.log.should_record(call.sampled, elapsed))
+            {
+                let error_code = resp.as_error_code();
+                call.log.record(RpcAuditRecord {
+                    timestamp_ms: call.timestamp_ms,
+                    transport: call.transport,
+                    method: call.method,
+                    params_hash: call.params_hash,
+                    latency_us: elapsed.as_micros() as u64,
+                    response_size: resp.as_result().len(),
+                    error_code,
+                    error_class: error_code.map(RpcErrorClass::from_code),
+                });
+            }
+        }
+        res
+    }
+}
+
+/// The opened sink the records are written to.
+enum Sink {
+    File(RollingFileAppender<RollingConditionBasic>),
+    Otlp(OtlpExporter),
+}
+
+impl Sink {
+    /// Writes the received records to the sink until all senders are dropped.
+    fn run(self, records: Receiver<RpcAuditRecord>, metrics: RpcAuditMetrics) {
+        match self {
+            Sink::File(mut file) => {
+                while let Ok(record) = records.recv() {
+                    // drain the buffered records before the file is flushed
+                    for record in std::iter::once(record).chain(records.try_iter()) {
+                        if let Err(err) = write_json_line(&mut file, &record) {
+                            metrics.failed_writes.increment(1);
+                            warn!(target: "rpc::audit", %err, "Failed to write RPC audit record");
+                        }
+                    }
+                    if let Err(err) = file.flush() {
+                        warn!(target: "rpc::audit", %err, "Failed to flush RPC audit log");
+                    }
+                }
+            }
+            Sink::Otlp(exporter) => {
+                let mut disconnected = false;
+                while !disconnected {
+                    let Ok(record) = records.recv() else { break };
+                    let mut batch = vec![record];
+                    let deadline = Instant::now() + OTLP_EXPORT_INTERVAL;
+                    while batch.len() < OTLP_MAX_BATCH_SIZE {
+                        let timeout = deadline.saturating_duration_since(Instant::now());
+                        match records.recv_timeout(timeout) {
+                            Ok(record) => batch.push(record),
+                            Err(RecvTimeoutError::Timeout) => break,
+                            Err(RecvTimeoutError::Disconnected) => {
+                                disconnected = true;
+                                break
+                            }
+                        }
+                    }
+                    if let Err(err) = exporter.export(&batch) {
+                        metrics.failed_writes.increment(batch.len() as u64);
+                        warn!(
+                            target: "rpc::audit",
+                            %err,
+                            records = batch.len(),
+                            "Failed to export RPC audit records"
+                        );
+                    }
+                }
+            }
+        }
+        debug!(target: "rpc::audit", "RPC audit log closed");
+    }
+}
+
+/// Writes the record as a single JSON line.
+fn write_json_line<W: Write>(writer: &mut W, record: &RpcAuditRecord) -> io::Result<()> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    writer.write_all(&line)
+}
+
+/// Exports records to the OTLP/HTTP logs endpoint of a collector.
+struct OtlpExporter {
+    endpoint: Uri,
+    client: Client<hyper::client::HttpConnector>,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl OtlpExporter {
+    fn new(endpoint: Uri) -> io::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+        Ok(Self { endpoint, client: Client::new(), runtime })
+    }
+
+    /// Sends the records to the collector.
+    fn export(&self, records: &[RpcAuditRecord]) -> io::Result<()> {
+        let body = otlp_logs_request(records).to_string();
+        let request = HttpRequest::builder()
+            .method(Method::POST)
+            .uri(self.endpoint.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .map_err(io::Error::other)?;
+        let response = self
+            .runtime
+            .block_on(async {
+                tokio::time::timeout(OTLP_EXPORT_TIMEOUT, self.client.request(request)).await
+            })
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "OTLP export timed out"))?
+            .map_err(io::Error::other)?;
+        if !response.status().is_success() {
+            return Err(io::Error::other(format!("OTLP collector responded {}", response.status())))
+        }
+        Ok(())
+    }
+}
+
+/// Returns the OTLP `ExportLogsServiceRequest` of the records, in the JSON encoding.
+fn otlp_logs_request(records: &[RpcAuditRecord]) -> Value {
+    let string = |key: &str, value: &str| json!({ "key": key, "value": { "stringValue": value } });
+    // 64 bit integers are encoded as strings
+    let int =
+        |key: &str, value: u64| json!({ "key": key, "value": { "intValue": value.to_string() } });
+    let log_records = records
+        .iter()
+        .map(|record| {
+            let mut attributes = vec![
+                string("rpc.system", "jsonrpc"),
+                string("rpc.method", &record.method),
+                string("rpc.transport", record.transport),
+                int("rpc.latency_us", record.latency_us),
+                int("rpc.response_size", record.response_size as u64),
+            ];
+            if let Some(hash) = record.params_hash {
+                attributes.push(string("rpc.params_hash", &hash.to_string()));
+            }
+            if let Some(code) = record.error_code {
+                attributes.push(json!({
+                    "key": "rpc.jsonrpc.error_code",
+                    "value": { "intValue": code.to_string() }
+                }));
+            }
+            if let Some(class) = record.error_class {
+                attributes.push(string("rpc.error_class", class.as_str()));
+            }
+            let (severity_number, severity_text) =
+                if record.error_code.is_some() { (13, "WARN") } else { (9, "INFO") };
+            json!({
+                "timeUnixNano": (u128::from(record.timestamp_ms) * 1_000_000).to_string(),
+                "severityNumber": severity_number,
+                "severityText": severity_text,
+                "body": { "stringValue": record.method },
+                "attributes": attributes,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "resourceLogs": [{
+            "resource": { "attributes": [string("service.name", "reth")] },
+            "scopeLogs": [{
+                "scope": { "name": "reth-rpc-audit" },
+                "logRecords": log_records,
+            }],
+        }],
+    })
+}
+
+/// Metrics of the RPC audit log.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.audit")]
+struct RpcAuditMetrics {
+    /// The number of records handed over to the sink
+    records: Counter,
+    /// The number of records that were dropped because the sink fell behind
+    dropped_records: Counter,
+    /// The number of records that failed to be written to the sink
+    failed_writes: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{
+        server::{RpcServiceBuilder, ServerBuilder},
+        RpcModule,
+    };
+    use std::net::SocketAddr;
+
+    #[test]
+    fn error_classes() {
+        assert_eq!(RpcErrorClass::from_code(-32601), RpcErrorClass::MethodNotFound);
+        assert_eq!(RpcErrorClass::from_code(-32005), RpcErrorClass::LimitExceeded);
+        assert_eq!(RpcErrorClass::from_code(3), RpcErrorClass::ExecutionReverted);
+        assert_eq!(RpcErrorClass::from_code(-32000), RpcErrorClass::ServerError);
+    }
+
+    #[test]
+    fn serialize_record() {
+        let record = RpcAuditRecord {
+            timestamp_ms: 1,
+            transport: "http",
+            method: "eth_call".to_string(),
+            params_hash: None,
+            latency_us: 250,
+            response_size: 42,
+            error_code: Some(3),
+            error_class: Some(RpcErrorClass::ExecutionReverted),
+        };
+        assert_eq!(
+            serde_json::to_value(&record).unwrap(),
+            json!({
+                "timestamp_ms": 1,
+                "transport": "http",
+                "method": "eth_call",
+                "latency_us": 250,
+                "response_size": 42,
+                "error_code": 3,
+                "error_class": "execution_reverted"
+            })
+        );
+
+        let request = otlp_logs_request(&[record]);
+        let log_record = &request["resourceLogs"][0]["scopeLogs"][0]["logRecords"][0];
+        assert_eq!(log_record["timeUnixNano"], "1000000");
+        assert_eq!(log_record["severityText"], "WARN");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn records_calls_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let log = RpcAuditLog::spawn(RpcAuditConfig::new(RpcAuditSink::File {
+            path: path.clone(),
+            max_size_bytes: 1024 * 1024,
+            max_files: 1,
+        }))
+        .unwrap();
+
+        let server = ServerBuilder::default()
+            .set_rpc_middleware(RpcServiceBuilder::new().layer(RpcAuditLayer::http(&log)))
+            .build("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+        let mut module = RpcModule::new(());
+        module.register_method("greet_hello", |_, _| "hello").unwrap();
+        let handle = server.start(module);
+
+        let client = hyper::Client::new();
+        for method in ["greet_hello", "greet_unknown"] {
+            let body = format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":[1]}}"#);
+            let req = HttpRequest::builder()
+                .method(Method::POST)
+                .uri(format!("http://{addr}"))
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body))
+                .unwrap();
+            client.request(req).await.unwrap();
+        }
+        handle.stop().unwrap();
+        handle.stopped().await;
+        drop(log);
+
+        // wait for the sink thread to write the records
+        let mut lines = Vec::new();
+        for _ in 0..50 {
+            lines = std::fs::read_to_string(&path)
+                .unwrap_or_default()
+                .lines()
+                .map(|line| serde_json::from_str::<Value>(line).unwrap())
+                .collect();
+            if lines.len() == 2 {
+                break
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["method"], "greet_hello");
+        assert_eq!(lines[0]["params_hash"], keccak256(b"[1]").to_string());
+        assert!(lines[0].get("error_code").is_none());
+        assert_eq!(lines[1]["method"], "greet_unknown");
+        assert_eq!(lines[1]["error_class"], "method_not_found");
+    }
+}
@@ -19,8 +19,8 @@ use reth_engine_primitives::EngineTypes;
 use reth_evm::ConfigureEvm;
 use reth_network_api::{NetworkInfo, Peers};
 use reth_provider::{
-    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, ReceiptProviderIdExt,
-    StateProviderFactory,
+    BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider, LogIndexReader,
+    PruneCheckpointReader, ReceiptProviderIdExt, StateProviderFactory,
 };
 use reth_rpc::{
     eth::{
@@ -57,6 +57,8 @@ where
         + ChainSpecProvider
         + EvmEnvProvider
         + HeaderProvider
+        + PruneCheckpointReader
+        + LogIndexReader
         + ReceiptProviderIdExt
         + StateProviderFactory
         + Clone
@@ -93,6 +95,11 @@ where
         fee_history_cache,
         evm_config,
         None,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
     );
     let config = EthFilterConfig::default()
         .max_logs_per_response(DEFAULT_MAX_LOGS_PER_RESPONSE)
@@ -115,6 +122,8 @@ where
         + ChainSpecProvider
         + EvmEnvProvider
         + HeaderProvider
+        + PruneCheckpointReader
+        + LogIndexReader
         + StateProviderFactory
         + Clone
         + Unpin
@@ -0,0 +1,578 @@
+//! Identification of the clients of the http and ws servers, and the limits of their calls.
+
+use crate::{ClientLimitsHttpMiddleware, RpcMiddleware};
+use hyper::{
+    server::conn::{AddrIncoming, AddrStream},
+    service::{make_service_fn, service_fn},
+    Body, HeaderMap, Request, Response, StatusCode,
+};
+use jsonrpsee::{
+    server::{
+        middleware::rpc::RpcServiceT, stop_channel, RpcServiceBuilder, ServerHandle,
+        TowerServiceBuilder,
+    },
+    types::{ErrorObject, Request as RpcRequest},
+    MethodResponse, Methods,
+};
+use reth_rpc::{
+    api_key_id, ApiKeys, RateLimitedRequest, RateLimiter, API_KEY_HEADER, RATE_LIMIT_EXCEEDED_CODE,
+};
+use std::{
+    convert::Infallible,
+    fmt,
+    future::Future,
+    io,
+    net::{IpAddr, SocketAddr},
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{layer::util::Identity, BoxError, Layer, Service};
+use tracing::{debug, error};
+
+/// The limits of the calls of the clients of a http or ws server.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClientLimits {
+    /// The API keys the calls are charged to, requests without an accepted key are rejected.
+    api_keys: Option<ApiKeys>,
+    /// The rate limits the calls are checked against.
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl ClientLimits {
+    pub(crate) fn new(api_keys: Option<ApiKeys>, rate_limiter: Option<RateLimiter>) -> Self {
+        Self { api_keys, rate_limiter }
+    }
+
+    /// Returns true if the calls are not limited, so the clients don't need to be identified.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.api_keys.is_none() && self.rate_limiter.is_none()
+    }
+
+    /// Identifies the client of a http request or of the websocket connection it establishes.
+    ///
+    /// The API key is read from the [API_KEY_HEADER] header, requests without an accepted key are
+    /// rejected with `401`. The IP of the client is resolved by [client_ip] with the trusted
+    /// proxies of the rate limits.
+    fn identify(&self, peer: SocketAddr, headers: &HeaderMap) -> Result<RpcClient, Response<Body>> {
+        let api_key = headers.get(API_KEY_HEADER).and_then(|key| key.to_str().ok());
+        if let Some(api_keys) = &self.api_keys {
+            if !api_key.is_some_and(|key| api_keys.contains(key)) {
+                debug!(
+                    target: "rpc::api-keys",
+                    %peer,
+                    "Rejected request without an accepted API key"
+                );
+                return Err(Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .body(Body::from("missing or invalid API key"))
+                    .expect("This should never happen"))
+            }
+        }
+        let trusted_proxies =
+            self.rate_limiter.as_ref().map(RateLimiter::trusted_proxies).unwrap_or_default();
+        let ip = client_ip(peer.ip(), headers, &trusted_proxies);
+        Ok(RpcClient { peer, ip, api_key: api_key.map(str::to_string) })
+    }
+}
+
+/// Returns the IP of the client of a connection with the peer.
+///
+/// The `X-Forwarded-For` and `X-Real-IP` headers are only read if the peer is a trusted proxy,
+/// otherwise any client could choose its IP. The client is the last address of `X-Forwarded-For`
+/// that is not a trusted proxy, the addresses before it are set by the client itself.
+fn client_ip(peer: IpAddr, headers: &HeaderMap, trusted_proxies: &[IpAddr]) -> IpAddr {
+    let peer = peer.to_canonical();
+    if !trusted_proxies.contains(&peer) {
+        return peer
+    }
+    if let Some(forwarded) = headers.get("x-forwarded-for").and_then(|value| value.to_str().ok()) {
+        let mut client = peer;
+        for addr in forwarded.rsplit(',') {
+            let Ok(ip) = addr.trim().parse::<IpAddr>() else { break };
+            client = ip.to_canonical();
+            if !trusted_proxies.contains(&client) {
+                break
+            }
+        }
+        return client
+    }
+    headers
+        .get("x-real-ip")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<IpAddr>().ok())
+        .map_or(peer, |ip| ip.to_canonical())
+}
+
+/// The client of a http request or websocket connection.
+#[derive(Debug)]
+pub(crate) struct RpcClient {
+    /// The address of the peer of the connection.
+    peer: SocketAddr,
+    /// The IP of the client, which differs from the peer if the peer is a trusted proxy.
+    ip: IpAddr,
+    /// The API key of the client, if any.
+    api_key: Option<String>,
+}
+
+/// A [RpcServiceT] middleware layer that enforces the [ClientLimits] on the calls of a client.
+///
+/// The layer is created per http request and per websocket connection, so every call made over a
+/// websocket connection is limited like a http call.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcClientLimitsLayer {
+    limits: ClientLimits,
+    client: Arc<RpcClient>,
+}
+
+impl RpcClientLimitsLayer {
+    pub(crate) fn new(limits: ClientLimits, client: RpcClient) -> Self {
+        Self { limits, client: Arc::new(client) }
+    }
+}
+
+impl<S> Layer<S> for RpcClientLimitsLayer {
+    type Service = RpcClientLimitsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcClientLimitsService { limits: self.limits.clone(), client: self.client.clone(), inner }
+    }
+}
+
+/// A [RpcServiceT] middleware that rejects the calls of a client that exceed its limits.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcClientLimitsService<S> {
+    limits: ClientLimits,
+    client: Arc<RpcClient>,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcClientLimitsService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = LimitedCallFuture<S::Future>;
+
+    fn call(&self, req: RpcRequest<'a>) -> Self::Future {
+        if let Some(rate_limiter) = &self.limits.rate_limiter {
+            let methods = [req.method.to_string()];
+            let request = RateLimitedRequest {
+                ip: Some(self.client.ip),
+                api_key: self.client.api_key.as_deref(),
+                methods: &methods,
+            };
+            if let Err(err) = rate_limiter.check(request) {
+                debug!(
+                    target: "rpc::rate-limit",
+                    ip = %self.client.ip,
+                    method = %req.method.as_ref(),
+                    %err,
+                    "Rejected call"
+                );
+                let err = ErrorObject::owned(RATE_LIMIT_EXCEEDED_CODE, err.to_string(), None::<()>);
+                return LimitedCallFuture::rejected(MethodResponse::error(req.id, err))
+            }
+        }
+        if let (Some(api_keys), Some(key)) = (&self.limits.api_keys, &self.client.api_key) {
+            let cost = api_keys.method_cost(req.method.as_ref());
+            if let Err(err) = api_keys.charge(key, cost) {
+                debug!(
+                    target: "rpc::api-keys",
+                    key = %api_key_id(key),
+                    peer = %self.client.peer,
+                    method = %req.method.as_ref(),
+                    cost,
+                    %err,
+                    "Rejected call"
+                );
+                let err = ErrorObject::owned(RATE_LIMIT_EXCEEDED_CODE, err.to_string(), None::<()>);
+                return LimitedCallFuture::rejected(MethodResponse::error(req.id, err))
+            }
+        }
+        LimitedCallFuture::call(self.inner.call(req))
+    }
+}
+
+/// Response future of a call that is rejected if it exceeds a limit of its client.
+#[pin_project::pin_project]
+pub(crate) struct LimitedCallFuture<F> {
+    /// the error response of a rejected call
+    rejected: Option<MethodResponse>,
+    /// the call if it's within the limits
+    #[pin]
+    fut: Option<F>,
+}
+
+impl<F> LimitedCallFuture<F> {
+    fn rejected(response: MethodResponse) -> Self {
+        Self { rejected: Some(response), fut: None }
+    }
+
+    fn call(fut: F) -> Self {
+        Self { rejected: None, fut: Some(fut) }
+    }
+}
+
+impl<F> fmt::Debug for LimitedCallFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LimitedCallFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for LimitedCallFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Some(response) = this.rejected.take() {
+            return Poll::Ready(response)
+        }
+        this.fut.as_pin_mut().expect("future polled after completion").poll(cx)
+    }
+}
+
+/// A http and ws server that enforces the [ClientLimits] on the calls of its clients.
+///
+/// The jsonrpsee server doesn't expose the connection of a request to its middleware, so this
+/// server accepts the connections itself and builds the rpc middleware of every http request and
+/// websocket connection with the [RpcClient] it belongs to.
+pub(crate) struct ClientLimitsServer {
+    incoming: AddrIncoming,
+    local_addr: SocketAddr,
+    service_builder: TowerServiceBuilder<Identity, ClientLimitsHttpMiddleware>,
+    rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
+    limits: ClientLimits,
+}
+
+impl ClientLimitsServer {
+    /// Binds the server to the socket address.
+    pub(crate) async fn bind(
+        service_builder: TowerServiceBuilder<Identity, ClientLimitsHttpMiddleware>,
+        socket_addr: SocketAddr,
+        rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
+        limits: ClientLimits,
+    ) -> io::Result<Self> {
+        let listener = tokio::net::TcpListener::bind(socket_addr).await?;
+        let local_addr = listener.local_addr()?;
+        let mut incoming = AddrIncoming::from_listener(listener)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        incoming.set_nodelay(true);
+        Ok(Self { incoming, local_addr, service_builder, rpc_middleware, limits })
+    }
+
+    /// Returns the address the server is bound to.
+    pub(crate) fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Starts the server and returns the handle.
+    pub(crate) fn start(self, methods: impl Into<Methods>) -> ServerHandle {
+        let Self { incoming, service_builder, rpc_middleware, limits, .. } = self;
+        let methods = methods.into();
+        let (stop_handle, server_handle) = stop_channel();
+        let shutdown = stop_handle.clone();
+
+        let make_service = make_service_fn(move |conn: &AddrStream| {
+            let peer = conn.remote_addr();
+            let methods = methods.clone();
+            let stop_handle = stop_handle.clone();
+            let service_builder = service_builder.clone();
+            let rpc_middleware = rpc_middleware.clone();
+            let limits = limits.clone();
+
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let client = match limits.identify(peer, req.headers()) {
+                        Ok(client) => client,
+                        Err(response) => return ServiceFuture::ready(response),
+                    };
+                    let rpc_middleware = rpc_middleware
+                        .clone()
+                        .layer(RpcClientLimitsLayer::new(limits.clone(), client));
+                    let mut service = service_builder
+                        .clone()
+                        .set_rpc_middleware(rpc_middleware)
+                        .build(methods.clone(), stop_handle.clone());
+                    ServiceFuture(Box::pin(async move { service.call(req).await }))
+                }))
+            }
+        });
+
+        let server = hyper::Server::builder(incoming)
+            .serve(make_service)
+            .with_graceful_shutdown(async move { shutdown.shutdown().await });
+        tokio::spawn(async move {
+            if let Err(err) = server.await {
+                error!(target: "rpc", %err, "Server failed");
+            }
+        });
+
+        server_handle
+    }
+}
+
+impl fmt::Debug for ClientLimitsServer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientLimitsServer")
+            .field("local_addr", &self.local_addr)
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Response future of a request to a [ClientLimitsServer].
+struct ServiceFuture(Pin<Box<dyn Future<Output = Result<Response<Body>, BoxError>> + Send>>);
+
+impl ServiceFuture {
+    fn ready(response: Response<Body>) -> Self {
+        Self(Box::pin(async move { Ok(response) }))
+    }
+}
+
+impl Future for ServiceFuture {
+    type Output = Result<Response<Body>, BoxError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{metrics::RpcRequestMetrics, RpcServerConfig};
+    use jsonrpsee::{
+        core::{client::ClientT, ClientError},
+        rpc_params,
+        server::ServerBuilder,
+        ws_client::WsClientBuilder,
+        RpcModule,
+    };
+    use reth_rpc::{
+        ApiKeysConfig, AuthLayer, GraphQlLayer, JwtAuthValidator, RateLimit, RateLimitConfig,
+    };
+    use std::net::Ipv4Addr;
+    use tower::util::option_layer;
+    use tower_http::cors::CorsLayer;
+
+    async fn spawn_server(limits: ClientLimits) -> (ServerHandle, SocketAddr) {
+        let middleware = tower::ServiceBuilder::new()
+            .layer(option_layer(None::<CorsLayer>))
+            .layer(option_layer(None::<AuthLayer<JwtAuthValidator>>))
+            .layer(option_layer(None::<GraphQlLayer>))
+            .layer(RpcServerConfig::default().rest_middleware(false));
+        let service_builder =
+            ServerBuilder::default().set_http_middleware(middleware).to_service_builder();
+        let rpc_middleware = crate::rpc_middleware(RpcRequestMetrics::default(), None, None, None);
+        let server = ClientLimitsServer::bind(
+            service_builder,
+            "127.0.0.1:0".parse().unwrap(),
+            rpc_middleware,
+            limits,
+        )
+        .await
+        .unwrap();
+        let addr = server.local_addr();
+
+        let mut module = RpcModule::new(());
+        module.register_method("greet_hello", |_, _| "hello").unwrap();
+        module.register_method("greet_expensive", |_, _| "hello").unwrap();
+        (server.start(module), addr)
+    }
+
+    async fn send_request(
+        addr: SocketAddr,
+        key: Option<&str>,
+        method: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        send_forwarded_request(addr, key, None, method).await
+    }
+
+    async fn send_forwarded_request(
+        addr: SocketAddr,
+        key: Option<&str>,
+        forwarded_for: Option<&str>,
+        method: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut req = Request::builder()
+            .method(hyper::Method::POST)
+            .uri(format!("http://{addr}"))
+            .header(hyper::header::CONTENT_TYPE, "application/json");
+        if let Some(key) = key {
+            req = req.header(API_KEY_HEADER, key);
+        }
+        if let Some(forwarded_for) = forwarded_for {
+            req = req.header("x-forwarded-for", forwarded_for);
+        }
+        let body = format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":[]}}"#);
+        let res = hyper::Client::new().request(req.body(Body::from(body)).unwrap()).await.unwrap();
+        let status = res.status();
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap_or_default())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn charges_calls_to_api_keys() {
+        let config: ApiKeysConfig = serde_json::from_str(
+            r#"{
+                "keys": { "alice": { "dailyLimit": 10 } },
+                "methodWeights": { "greet_expensive": 8 }
+            }"#,
+        )
+        .unwrap();
+        let api_keys = ApiKeys::new(config);
+        let (handle, addr) = spawn_server(ClientLimits::new(Some(api_keys.clone()), None)).await;
+
+        assert_eq!(send_request(addr, None, "greet_hello").await.0, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            send_request(addr, Some("bob"), "greet_hello").await.0,
+            StatusCode::UNAUTHORIZED
+        );
+        let (status, body) = send_request(addr, Some("alice"), "greet_hello").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["result"], "hello");
+        assert_eq!(send_request(addr, Some("alice"), "greet_expensive").await.1["result"], "hello");
+        let (_, body) = send_request(addr, Some("alice"), "greet_expensive").await;
+        assert_eq!(body["error"]["code"], RATE_LIMIT_EXCEEDED_CODE);
+
+        let usage = api_keys.usage(Some("alice"));
+        assert_eq!(usage[0].daily_used, 9);
+        assert_eq!(usage[0].requests, 2);
+        assert_eq!(usage[0].rejected_requests, 1);
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn charges_ws_calls_to_api_keys() {
+        let config: ApiKeysConfig = serde_json::from_str(
+            r#"{
+                "keys": { "alice": { "dailyLimit": 10 } },
+                "methodWeights": { "greet_expensive": 8 }
+            }"#,
+        )
+        .unwrap();
+        let api_keys = ApiKeys::new(config);
+        let (handle, addr) = spawn_server(ClientLimits::new(Some(api_keys.clone()), None)).await;
+        let url = format!("ws://{addr}");
+
+        // the connection requires an accepted key
+        assert!(WsClientBuilder::default().build(&url).await.is_err());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(API_KEY_HEADER, "alice".parse().unwrap());
+        let client = WsClientBuilder::default().set_headers(headers).build(&url).await.unwrap();
+        let res: String = client.request("greet_expensive", rpc_params![]).await.unwrap();
+        assert_eq!(res, "hello");
+        // every call over the connection is charged
+        match client.request::<String, _>("greet_expensive", rpc_params![]).await.unwrap_err() {
+            ClientError::Call(err) => assert_eq!(err.code(), RATE_LIMIT_EXCEEDED_CODE),
+            err => panic!("unexpected error {err:?}"),
+        }
+        assert_eq!(api_keys.usage(Some("alice"))[0].daily_used, 8);
+
+        handle.stop().unwrap();
+    }
+
+    fn rate_limiter(trusted_proxies: Vec<IpAddr>) -> RateLimiter {
+        let limit = RateLimit { requests_per_second: 0.001, burst: Some(2) };
+        RateLimiter::new(RateLimitConfig {
+            trusted_proxies,
+            per_ip: Some(limit),
+            methods: [("greet_expensive".to_string(), RateLimit { burst: Some(1), ..limit })]
+                .into(),
+            ..Default::default()
+        })
+    }
+
+    fn is_rate_limited(body: &serde_json::Value) -> bool {
+        body["error"]["code"] == RATE_LIMIT_EXCEEDED_CODE
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn limits_calls_of_peer() {
+        let (handle, addr) =
+            spawn_server(ClientLimits::new(None, Some(rate_limiter(Vec::new())))).await;
+
+        // the forwarding headers of an untrusted peer are ignored
+        let (_, body) = send_forwarded_request(addr, None, Some("1.1.1.1"), "greet_hello").await;
+        assert_eq!(body["result"], "hello");
+        let (_, body) = send_forwarded_request(addr, None, Some("2.2.2.2"), "greet_hello").await;
+        assert_eq!(body["result"], "hello");
+        let (_, body) = send_forwarded_request(addr, None, Some("3.3.3.3"), "greet_hello").await;
+        assert!(is_rate_limited(&body));
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn limits_calls_of_forwarded_clients() {
+        let limiter = rate_limiter(vec![IpAddr::from([127, 0, 0, 1])]);
+        let (handle, addr) = spawn_server(ClientLimits::new(None, Some(limiter))).await;
+
+        // a trusted proxy forwards the calls of different clients
+        for _ in 0..2 {
+            let (_, body) =
+                send_forwarded_request(addr, None, Some("1.1.1.1"), "greet_hello").await;
+            assert_eq!(body["result"], "hello");
+        }
+        let (_, body) = send_forwarded_request(addr, None, Some("1.1.1.1"), "greet_hello").await;
+        assert!(is_rate_limited(&body));
+        // a client can't choose its IP by prepending addresses
+        let (_, body) =
+            send_forwarded_request(addr, None, Some("9.9.9.9, 1.1.1.1"), "greet_hello").await;
+        assert!(is_rate_limited(&body));
+        let (_, body) = send_forwarded_request(addr, None, Some("2.2.2.2"), "greet_hello").await;
+        assert_eq!(body["result"], "hello");
+
+        // method limits are shared by all clients
+        let (_, body) =
+            send_forwarded_request(addr, None, Some("3.3.3.3"), "greet_expensive").await;
+        assert_eq!(body["result"], "hello");
+        let (_, body) =
+            send_forwarded_request(addr, None, Some("4.4.4.4"), "greet_expensive").await;
+        assert!(is_rate_limited(&body));
+
+        handle.stop().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn limits_ws_calls() {
+        let (handle, addr) =
+            spawn_server(ClientLimits::new(None, Some(rate_limiter(Vec::new())))).await;
+        let client = WsClientBuilder::default().build(format!("ws://{addr}")).await.unwrap();
+
+        for _ in 0..2 {
+            let res: String = client.request("greet_hello", rpc_params![]).await.unwrap();
+            assert_eq!(res, "hello");
+        }
+        match client.request::<String, _>("greet_hello", rpc_params![]).await.unwrap_err() {
+            ClientError::Call(err) => assert_eq!(err.code(), RATE_LIMIT_EXCEEDED_CODE),
+            err => panic!("unexpected error {err:?}"),
+        }
+
+        handle.stop().unwrap();
+    }
+
+    #[test]
+    fn resolve_client_ip() {
+        let peer = IpAddr::from([10, 0, 0, 1]);
+        let proxy = IpAddr::from([10, 0, 0, 2]);
+        let trusted = [peer, proxy];
+        let mut headers = HeaderMap::new();
+        assert_eq!(client_ip(peer, &headers, &trusted), peer);
+
+        headers.insert("x-real-ip", "1.2.3.4".parse().unwrap());
+        assert_eq!(client_ip(peer, &headers, &trusted), IpAddr::from([1, 2, 3, 4]));
+        // the headers of untrusted peers are ignored
+        assert_eq!(client_ip(peer, &headers, &[]), peer);
+
+        headers.insert("x-forwarded-for", "9.9.9.9, 1.2.3.5, 10.0.0.2".parse().unwrap());
+        assert_eq!(client_ip(peer, &headers, &trusted), IpAddr::from([1, 2, 3, 5]));
+        assert_eq!(client_ip(peer, &headers, &[peer]), proxy);
+
+        // ipv4 peers of a dual stack socket are mapped to ipv6
+        let mapped = IpAddr::from(Ipv4Addr::new(10, 0, 0, 3).to_ipv6_mapped());
+        assert_eq!(client_ip(mapped, &HeaderMap::new(), &[]), IpAddr::from([10, 0, 0, 3]));
+    }
+}
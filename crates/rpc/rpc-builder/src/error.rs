@@ -63,6 +63,9 @@ pub enum RpcError {
     /// Thrown when IPC server fails to start.
     #[error(transparent)]
     IpcServerError(#[from] IpcServerStartError),
+    /// Thrown when the RPC audit log fails to open its sink.
+    #[error("Failed to start RPC audit log: {0}")]
+    AuditLog(io::Error),
     /// Custom error.
     #[error("{0}")]
     Custom(String),
@@ -5,9 +5,13 @@ use reth_rpc::{
     eth::{
         cache::{EthStateCache, EthStateCacheConfig},
         gas_oracle::GasPriceOracleConfig,
-        EthFilterConfig, FeeHistoryCacheConfig, RPC_DEFAULT_GAS_CAP,
+        EthFilterConfig, EthPubSubConfig, FeeHistoryCacheConfig, GasEstimationConfig,
+        NonceStrategy, RevertDecoder, RevertErrorRegistry, SubscriptionOverflow,
+        TransactionConfirmationConfig, UserOperationConfig, DEFAULT_MAX_BUFFERED_NOTIFICATIONS,
+        DEFAULT_MAX_PERSISTED_FILTERS, RPC_DEFAULT_GAS_CAP,
     },
-    EthApi, EthFilter, EthPubSub,
+    EthApi, EthFilter, EthPubSub, PersonalPolicy, TraceFilterConfig, DEFAULT_TRACE_CACHE_SIZE_MB,
+    DEFAULT_TRACE_CHAIN_CONCURRENCY,
 };
 use reth_tasks::pool::BlockingTaskPool;
 use serde::{Deserialize, Serialize};
@@ -40,6 +44,8 @@ pub struct EthConfig {
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum duration of an `eth_getLogs` query over a range of blocks
+    pub logs_query_timeout: Option<std::time::Duration>,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [RPC_DEFAULT_GAS_CAP]
@@ -47,8 +53,37 @@ pub struct EthConfig {
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: std::time::Duration,
+    /// The file the installed filters are persisted in, if any
+    pub filter_store: Option<std::path::PathBuf>,
+    /// Maximum number of filters that are persisted
+    pub max_persisted_filters: usize,
     /// Settings for the fee history cache
     pub fee_history_cache: FeeHistoryCacheConfig,
+    /// Maximum number of notifications that are buffered per subscription.
+    pub max_buffered_notifications: usize,
+    /// What happens to a subscription whose client does not keep up.
+    pub subscription_overflow: SubscriptionOverflow,
+    /// Maximum size of the cache of `trace_block` and `trace_transaction` results in megabytes.
+    pub trace_cache_size_mb: usize,
+    /// Limits of `trace_filter` requests
+    pub trace_filter: TraceFilterConfig,
+    /// Number of blocks that are traced concurrently for a `debug_traceChain` subscription
+    pub trace_chain_concurrency: usize,
+    /// Settings for waiting for the receipt in `eth_sendRawTransactionSync`
+    pub tx_confirmation: TransactionConfirmationConfig,
+    /// How the nonce of transactions signed by the node is chosen if the request doesn't set one
+    pub nonce_strategy: NonceStrategy,
+    /// Custom errors that the revert reasons of `eth_call` and `eth_estimateGas` are decoded with
+    #[serde(skip)]
+    pub revert_errors: Option<RevertErrorRegistry>,
+    /// Whether the decoded revert reason is included in the data of the error of a reverted call
+    pub structured_revert_data: bool,
+    /// Settings of the binary search of `eth_estimateGas`
+    pub gas_estimation: GasEstimationConfig,
+    /// Which methods of the `personal` namespace are available
+    pub personal_policy: PersonalPolicy,
+    /// Settings of the ERC-4337 user operation handlers
+    pub user_operation: UserOperationConfig,
 }
 
 impl EthConfig {
@@ -57,7 +92,22 @@ impl EthConfig {
         EthFilterConfig::default()
             .max_blocks_per_filter(self.max_blocks_per_filter)
             .max_logs_per_response(self.max_logs_per_response)
+            .logs_query_timeout(self.logs_query_timeout)
             .stale_filter_ttl(self.stale_filter_ttl)
+            .filter_store_path(self.filter_store.clone())
+            .max_persisted_filters(self.max_persisted_filters)
+    }
+
+    /// Returns the pubsub config for the `eth_subscribe` handler.
+    pub fn pubsub_config(&self) -> EthPubSubConfig {
+        EthPubSubConfig::default()
+            .max_buffered_notifications(self.max_buffered_notifications)
+            .overflow(self.subscription_overflow)
+    }
+
+    /// Returns the decoder of the revert reasons of reverted calls.
+    pub fn revert_decoder(&self) -> RevertDecoder {
+        RevertDecoder::new(self.revert_errors.clone(), self.structured_revert_data)
     }
 }
 
@@ -72,9 +122,24 @@ impl Default for EthConfig {
             max_tracing_requests: default_max_tracing_requests(),
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            logs_query_timeout: None,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
+            filter_store: None,
+            max_persisted_filters: DEFAULT_MAX_PERSISTED_FILTERS,
             fee_history_cache: FeeHistoryCacheConfig::default(),
+            max_buffered_notifications: DEFAULT_MAX_BUFFERED_NOTIFICATIONS,
+            subscription_overflow: SubscriptionOverflow::default(),
+            trace_cache_size_mb: DEFAULT_TRACE_CACHE_SIZE_MB,
+            trace_filter: TraceFilterConfig::default(),
+            trace_chain_concurrency: DEFAULT_TRACE_CHAIN_CONCURRENCY,
+            tx_confirmation: TransactionConfirmationConfig::default(),
+            nonce_strategy: NonceStrategy::default(),
+            revert_errors: None,
+            structured_revert_data: false,
+            gas_estimation: GasEstimationConfig::default(),
+            personal_policy: PersonalPolicy::default(),
+            user_operation: UserOperationConfig::default(),
         }
     }
 }
@@ -110,9 +175,112 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum duration of an `eth_getLogs` query over a range of blocks
+    pub fn logs_query_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.logs_query_timeout = timeout;
+        self
+    }
+
+    /// Configures the file the installed filters are persisted in
+    pub fn filter_store(mut self, path: Option<std::path::PathBuf>) -> Self {
+        self.filter_store = path;
+        self
+    }
+
+    /// Configures the maximum number of filters that are persisted
+    pub fn max_persisted_filters(mut self, max_filters: usize) -> Self {
+        self.max_persisted_filters = max_filters;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+
+    /// Configures the maximum number of notifications that are buffered per subscription
+    pub fn max_buffered_notifications(mut self, max_notifications: usize) -> Self {
+        self.max_buffered_notifications = max_notifications;
+        self
+    }
+
+    /// Configures what happens to a subscription whose client does not keep up
+    pub fn subscription_overflow(mut self, overflow: SubscriptionOverflow) -> Self {
+        self.subscription_overflow = overflow;
+        self
+    }
+
+    /// Configures the maximum size of the parity trace cache in megabytes, 0 disables the cache
+    pub fn trace_cache_size_mb(mut self, size_mb: usize) -> Self {
+        self.trace_cache_size_mb = size_mb;
+        self
+    }
+
+    /// Configures the maximum number of blocks that are traced by a `trace_filter` request
+    pub fn trace_filter_max_blocks(mut self, max_blocks: u64) -> Self {
+        self.trace_filter.max_blocks = max_blocks;
+        self
+    }
+
+    /// Configures the maximum number of traces that are returned by a `trace_filter` request
+    pub fn trace_filter_max_traces(mut self, max_traces: usize) -> Self {
+        self.trace_filter.max_traces = max_traces;
+        self
+    }
+
+    /// Configures the number of blocks that are traced concurrently for a `debug_traceChain`
+    /// subscription
+    pub fn trace_chain_concurrency(mut self, concurrency: usize) -> Self {
+        self.trace_chain_concurrency = concurrency;
+        self
+    }
+
+    /// Configures how long `eth_sendRawTransactionSync` waits for the receipt
+    pub fn tx_confirmation_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.tx_confirmation = self.tx_confirmation.timeout(timeout);
+        self
+    }
+
+    /// Configures how often `eth_sendRawTransactionSync` checks for the receipt
+    pub fn tx_confirmation_poll_interval(mut self, poll_interval: std::time::Duration) -> Self {
+        self.tx_confirmation = self.tx_confirmation.poll_interval(poll_interval);
+        self
+    }
+
+    /// Configures how the nonce of transactions signed by the node is chosen
+    pub fn nonce_strategy(mut self, strategy: NonceStrategy) -> Self {
+        self.nonce_strategy = strategy;
+        self
+    }
+
+    /// Configures the custom errors that revert reasons are decoded with
+    pub fn revert_errors(mut self, registry: Option<RevertErrorRegistry>) -> Self {
+        self.revert_errors = registry;
+        self
+    }
+
+    /// Configures whether the decoded revert reason is included in the error data
+    pub fn structured_revert_data(mut self, enabled: bool) -> Self {
+        self.structured_revert_data = enabled;
+        self
+    }
+
+    /// Configures the settings of the binary search of `eth_estimateGas`
+    pub fn gas_estimation(mut self, config: GasEstimationConfig) -> Self {
+        self.gas_estimation = config;
+        self
+    }
+
+    /// Configures which methods of the `personal` namespace are available
+    pub fn personal_policy(mut self, policy: PersonalPolicy) -> Self {
+        self.personal_policy = policy;
+        self
+    }
+
+    /// Configures the ERC-4337 user operation handlers
+    pub fn user_operation_config(mut self, config: UserOperationConfig) -> Self {
+        self.user_operation = config;
+        self
+    }
 }
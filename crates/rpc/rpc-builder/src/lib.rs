@@ -18,10 +18,12 @@
 //!
 //! ```
 //! use reth_evm::ConfigureEvm;
+//! use reth_interfaces::blockchain_tree::BlockchainTreeEngine;
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, LogIndexReader, PruneCheckpointReader,
+//!     ReorgHistoryReader, SenderNoncesReader, StateProviderFactory, TokenTransfersReader,
 //! };
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -39,6 +41,13 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + ReorgHistoryReader
+//!         + BlockchainTreeEngine
+//!         + TokenTransfersReader
+//!         + SenderNoncesReader
+//!         + LogIndexReader
+//!         + AddressTransactionsReader
+//!         + PruneCheckpointReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -79,10 +88,12 @@
 //! ```
 //! use reth_engine_primitives::EngineTypes;
 //! use reth_evm::ConfigureEvm;
+//! use reth_interfaces::blockchain_tree::BlockchainTreeEngine;
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
-//!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+//!     ChainSpecProvider, ChangeSetReader, EvmEnvProvider, LogIndexReader, PruneCheckpointReader,
+//!     ReorgHistoryReader, SenderNoncesReader, StateProviderFactory, TokenTransfersReader,
 //! };
 //! use reth_rpc::JwtSecret;
 //! use reth_rpc_api::EngineApiServer;
@@ -105,6 +116,13 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + ReorgHistoryReader
+//!         + BlockchainTreeEngine
+//!         + TokenTransfersReader
+//!         + SenderNoncesReader
+//!         + LogIndexReader
+//!         + AddressTransactionsReader
+//!         + PruneCheckpointReader
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -156,7 +174,13 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use crate::{
-    auth::AuthRpcModule, error::WsHttpSamePortError, metrics::RpcRequestMetrics,
+    audit::RpcAuditLayer,
+    auth::AuthRpcModule,
+    client_limits::{ClientLimits, ClientLimitsServer},
+    error::WsHttpSamePortError,
+    metrics::RpcRequestMetrics,
+    response_cache::RpcResponseCacheLayer,
+    timeout::RpcTimeoutLayer,
     RpcModuleSelection::Selection,
 };
 use constants::*;
@@ -165,31 +189,46 @@ use hyper::{header::AUTHORIZATION, HeaderMap};
 pub use jsonrpsee::server::ServerBuilder;
 use jsonrpsee::{
     core::RegisterMethodError,
-    server::{AlreadyStoppedError, IdProvider, RpcServiceBuilder, Server, ServerHandle},
+    server::{
+        middleware::rpc::either::Either as RpcEither, AlreadyStoppedError, IdProvider,
+        RpcServiceBuilder, Server, ServerHandle,
+    },
     Methods, RpcModule,
 };
 use reth_engine_primitives::EngineTypes;
 use reth_evm::ConfigureEvm;
+use reth_interfaces::blockchain_tree::BlockchainTreeEngine;
 use reth_ipc::server::IpcServer;
 pub use reth_ipc::server::{
     Builder as IpcServerBuilder, Endpoint, RpcServiceBuilder as IpcRpcServiceBuilder,
 };
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_provider::{
-    AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+    AccountReader, AddressTransactionsReader, BlockReader, BlockReaderIdExt,
+    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, LogIndexReader,
+    PruneCheckpointReader, ReorgHistoryReader, SenderNoncesReader, StateProviderFactory,
+    TokenTransfersReader,
 };
 use reth_rpc::{
     eth::{
+        blob_index::blob_sidecar_index_task,
         cache::{cache_new_blocks_task, EthStateCache},
+        conditional::conditional_transactions_eviction_task,
         fee_history_cache_new_blocks_task,
         gas_oracle::GasPriceOracle,
         traits::RawTransactionForwarder,
-        EthBundle, FeeHistoryCache,
+        BlobSidecarIndex, EthBundle, EthUserOperation, FeeHistoryCache, LocalBlobStore,
+        Preconfirmations, SendBundle,
     },
-    AdminApi, AuthLayer, Claims, DebugApi, EngineEthApi, EthApi, EthFilter, EthPubSub,
-    EthSubscriptionIdProvider, JwtAuthValidator, JwtSecret, NetApi, OtterscanApi, RPCApi, RethApi,
-    TraceApi, TxPoolApi, Web3Api,
+    graphql::{GraphQl, GraphQlConfig},
+    AdminApi, ApiKeys, AuthLayer, Claims, DebugApi, EngineEthApi, EthApi, EthFilter, EthPubSub,
+    EthSubscriptionIdProvider, GraphQlLayer, JwtAuthValidator, JwtSecret, NetApi, OtterscanApi,
+    ParityTraceCache, PersonalApi, RPCApi, RateLimiter, RethApi, TraceApi, TxPoolApi, Web3Api,
+};
+#[cfg(feature = "rest")]
+use reth_rpc::{
+    rest::{RestApi, RestConfig},
+    RestLayer,
 };
 use reth_rpc_api::servers::*;
 use reth_tasks::{
@@ -208,15 +247,29 @@ use std::{
 };
 use strum::{AsRefStr, EnumIter, IntoStaticStr, ParseError, VariantArray, VariantNames};
 pub use tower::layer::util::{Identity, Stack};
+use tower::util::{option_layer, Either};
 use tower_http::cors::CorsLayer;
 use tracing::{instrument, trace};
 
 // re-export for convenience
-pub use crate::eth::{EthConfig, EthHandlers};
+pub use crate::{
+    audit::{RpcAuditConfig, RpcAuditLog, RpcAuditRecord, RpcAuditSink, RpcErrorClass},
+    eth::{EthConfig, EthHandlers},
+    response_cache::{
+        RpcResponseCache, RpcResponseCacheConfig, DEFAULT_RESPONSE_CACHE_MAX_ENTRIES,
+    },
+    timeout::{MethodTimeout, RpcTimeouts, CALL_TIMEOUT_CODE},
+};
+
+/// Audit log of the RPC calls.
+mod audit;
 
 /// Auth server utilities.
 pub mod auth;
 
+/// Identification of the clients of the http and ws servers, and the limits of their calls.
+mod client_limits;
+
 /// Cors utilities.
 mod cors;
 
@@ -232,6 +285,12 @@ pub mod constants;
 // Rpc server metrics
 mod metrics;
 
+/// Cache of the responses of calls for finalized data.
+mod response_cache;
+
+/// Execution timeouts of the RPC calls.
+mod timeout;
+
 /// Convenience function for starting a server in one step.
 #[allow(clippy::too_many_arguments)]
 pub async fn launch<Provider, Pool, Network, Tasks, Events, EvmConfig>(
@@ -251,6 +310,13 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + ReorgHistoryReader
+        + BlockchainTreeEngine
+        + TokenTransfersReader
+        + SenderNoncesReader
+        + LogIndexReader
+        + AddressTransactionsReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -442,6 +508,13 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + ReorgHistoryReader
+        + BlockchainTreeEngine
+        + TokenTransfersReader
+        + SenderNoncesReader
+        + LogIndexReader
+        + AddressTransactionsReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -738,6 +811,13 @@ impl RpcModuleSelection {
             + EvmEnvProvider
             + ChainSpecProvider
             + ChangeSetReader
+            + ReorgHistoryReader
+            + BlockchainTreeEngine
+            + TokenTransfersReader
+            + SenderNoncesReader
+            + LogIndexReader
+            + AddressTransactionsReader
+            + PruneCheckpointReader
             + Clone
             + Unpin
             + 'static,
@@ -866,6 +946,25 @@ pub enum RethRpcModule {
     /// This is separate from [RethRpcModule::Eth] because it is a non standardized call that
     /// should be opt-in.
     EthCallBundle,
+    /// `personal_` module
+    ///
+    /// Deprecated account management namespace of geth, only served if explicitly enabled.
+    Personal,
+    /// ERC-4337 user operation calls of the `eth_` namespace, e.g. `eth_sendUserOperation`
+    ///
+    /// This is separate from [RethRpcModule::Eth] because it is only needed by bundlers and
+    /// should be opt-in.
+    EthUserOperation,
+    /// Bundle submission to the local payload builder with `eth_sendBundle` and
+    /// `eth_cancelBundle`
+    ///
+    /// This is separate from [RethRpcModule::Eth] because it is a non standardized call that
+    /// should be opt-in.
+    EthSendBundle,
+    /// `mev_` module
+    ///
+    /// Only supports `mev_sendBundle` to submit bundles to the local payload builder.
+    Mev,
 }
 
 // === impl RethRpcModule ===
@@ -910,6 +1009,10 @@ impl FromStr for RethRpcModule {
             "reth" => RethRpcModule::Reth,
             "ots" => RethRpcModule::Ots,
             "eth-call-bundle" | "eth_callBundle" => RethRpcModule::EthCallBundle,
+            "personal" => RethRpcModule::Personal,
+            "eth-user-operation" => RethRpcModule::EthUserOperation,
+            "eth-send-bundle" | "eth_sendBundle" => RethRpcModule::EthSendBundle,
+            "mev" => RethRpcModule::Mev,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -958,6 +1061,14 @@ pub struct RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
     /// Optional forwarder for `eth_sendRawTransaction`
     // TODO(mattsse): find a more ergonomic way to configure eth/rpc customizations
     eth_raw_transaction_forwarder: Option<Arc<dyn RawTransactionForwarder>>,
+    /// Blobs uploaded via `reth_storeBlob` for blob transactions signed by the node
+    local_blob_store: LocalBlobStore,
+    /// Blobs of canonical blob transactions whose sidecars are still in the blob store
+    blob_sidecar_index: BlobSidecarIndex,
+    /// Preconfirmations of the transactions selected into the payloads built by the node
+    preconfirmations: Preconfirmations,
+    /// Results of `trace_block` and `trace_transaction` shared by all trace handlers
+    trace_cache: ParityTraceCache,
 }
 
 // === impl RethModuleRegistry ===
@@ -984,9 +1095,13 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
             executor,
             modules: Default::default(),
             blocking_pool_guard: BlockingTaskGuard::new(config.eth.max_tracing_requests),
+            trace_cache: ParityTraceCache::new(config.eth.trace_cache_size_mb * 1024 * 1024),
             config,
             events,
             eth_raw_transaction_forwarder: None,
+            local_blob_store: LocalBlobStore::default(),
+            blob_sidecar_index: BlobSidecarIndex::default(),
+            preconfirmations: Preconfirmations::default(),
         }
     }
 
@@ -1020,6 +1135,14 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
         &self.provider
     }
 
+    /// Returns the preconfirmations served by `reth_subscribePreconfirmations`.
+    ///
+    /// They are only produced if the payloads built by the node are fed to
+    /// [preconfirmations_task](reth_rpc::eth::preconfirmations_task).
+    pub fn preconfirmations(&self) -> &Preconfirmations {
+        &self.preconfirmations
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -1074,6 +1197,13 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + ReorgHistoryReader
+        + BlockchainTreeEngine
+        + TokenTransfersReader
+        + SenderNoncesReader
+        + LogIndexReader
+        + AddressTransactionsReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static,
@@ -1231,6 +1361,8 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            Box::new(self.executor.clone()),
+                            self.config.eth.trace_chain_concurrency,
                         )
                         .into_rpc()
                         .into(),
@@ -1249,6 +1381,8 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.trace_cache.clone(),
+                            self.config.eth.trace_filter,
                         )
                         .into_rpc()
                         .into(),
@@ -1264,17 +1398,45 @@ where
                         )
                         .into_rpc()
                         .into(),
-                        RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+                        RethRpcModule::Ots => {
+                            OtterscanApi::new(self.provider.clone(), eth_api.clone())
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Reth => RethApi::new(
+                            self.provider.clone(),
+                            self.pool.clone(),
+                            Box::new(self.executor.clone()),
+                            self.local_blob_store.clone(),
+                            self.blob_sidecar_index.clone(),
+                            self.preconfirmations.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::EthCallBundle => {
                             EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Personal => PersonalApi::new(eth_api.clone())
+                            .with_policy(self.config.eth.personal_policy.clone())
+                            .into_rpc()
+                            .into(),
+                        RethRpcModule::EthUserOperation => EthUserOperation::new(
+                            eth_api.clone(),
+                            eth_filter.clone(),
+                            self.config.eth.user_operation.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
+                        RethRpcModule::EthSendBundle => {
+                            EthSendBundleApiServer::into_rpc(SendBundle::new(self.pool.clone()))
+                                .into()
+                        }
+                        RethRpcModule::Mev => {
+                            MevSendBundleApiServer::into_rpc(SendBundle::new(self.pool.clone()))
+                                .into()
+                        }
                     })
                     .clone()
             })
@@ -1294,6 +1456,8 @@ where
     /// This will spawn the required service tasks for [EthApi] for:
     ///   - [EthStateCache]
     ///   - [FeeHistoryCache]
+    ///   - the eviction of stale conditional transactions
+    ///   - [BlobSidecarIndex]
     fn with_eth<F, R>(&mut self, f: F) -> R
     where
         F: FnOnce(&EthHandlers<Provider, Pool, Network, Events, EvmConfig>) -> R,
@@ -1352,7 +1516,33 @@ where
             fee_history_cache,
             self.evm_config.clone(),
             self.eth_raw_transaction_forwarder.clone(),
+            self.local_blob_store.clone(),
+            self.config.eth.tx_confirmation,
+            self.config.eth.nonce_strategy,
+            self.config.eth.revert_decoder(),
+            self.config.eth.gas_estimation,
+        );
+
+        let new_canonical_blocks = self.events.canonical_state_stream();
+        let provider = self.provider.clone();
+        let pool = self.pool.clone();
+        self.executor.spawn_critical(
+            "evict stale conditional transactions task",
+            Box::pin(async move {
+                conditional_transactions_eviction_task(new_canonical_blocks, provider, pool).await;
+            }),
         );
+
+        let index = self.blob_sidecar_index.clone();
+        let new_canonical_blocks = self.events.canonical_state_stream();
+        let pool = self.pool.clone();
+        self.executor.spawn_critical(
+            "index blob sidecars task",
+            Box::pin(async move {
+                blob_sidecar_index_task(index, new_canonical_blocks, pool).await;
+            }),
+        );
+
         let filter = EthFilter::new(
             self.provider.clone(),
             self.pool.clone(),
@@ -1367,6 +1557,7 @@ where
             self.events.clone(),
             self.network.clone(),
             executor,
+            self.config.eth.pubsub_config(),
         );
 
         EthHandlers { api, cache, filter, pubsub, blocking_task_pool }
@@ -1392,6 +1583,29 @@ where
         self.with_eth(|handlers| handlers.api.clone())
     }
 
+    /// Instantiates the [GraphQl] API.
+    ///
+    /// The API can be served by the http server, see [RpcServerConfig::with_graphql].
+    pub fn graphql(&self, config: GraphQlConfig) -> GraphQl {
+        GraphQl::new(self.provider.clone(), config)
+    }
+
+    /// Instantiates the [RpcResponseCache] that caches responses of calls for finalized blocks.
+    ///
+    /// The cache is used by the servers, see [RpcServerConfig::with_response_cache].
+    pub fn response_cache(&self, config: RpcResponseCacheConfig) -> RpcResponseCache {
+        let provider = self.provider.clone();
+        RpcResponseCache::new(config, move || provider.finalized_block_number().ok().flatten())
+    }
+
+    /// Instantiates the [RestApi].
+    ///
+    /// The API can be served by the http server, see [RpcServerConfig::with_rest].
+    #[cfg(feature = "rest")]
+    pub fn rest(&self, config: RestConfig) -> RestApi {
+        RestApi::new(self.provider.clone(), config)
+    }
+
     /// Instantiates TraceApi
     ///
     /// # Panics
@@ -1399,7 +1613,13 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn trace_api(&mut self) -> TraceApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth = self.eth_handlers();
-        TraceApi::new(self.provider.clone(), eth.api, self.blocking_pool_guard.clone())
+        TraceApi::new(
+            self.provider.clone(),
+            eth.api,
+            self.blocking_pool_guard.clone(),
+            self.trace_cache.clone(),
+            self.config.eth.trace_filter,
+        )
     }
 
     /// Instantiates [EthBundle] Api
@@ -1417,9 +1637,11 @@ where
     /// # Panics
     ///
     /// If called outside of the tokio runtime. See also [Self::eth_api]
-    pub fn otterscan_api(&mut self) -> OtterscanApi<EthApi<Provider, Pool, Network, EvmConfig>> {
+    pub fn otterscan_api(
+        &mut self,
+    ) -> OtterscanApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        OtterscanApi::new(eth_api)
+        OtterscanApi::new(self.provider.clone(), eth_api)
     }
 
     /// Instantiates DebugApi
@@ -1429,7 +1651,13 @@ where
     /// If called outside of the tokio runtime. See also [Self::eth_api]
     pub fn debug_api(&mut self) -> DebugApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth_api = self.eth_api();
-        DebugApi::new(self.provider.clone(), eth_api, self.blocking_pool_guard.clone())
+        DebugApi::new(
+            self.provider.clone(),
+            eth_api,
+            self.blocking_pool_guard.clone(),
+            Box::new(self.executor.clone()),
+            self.config.eth.trace_chain_concurrency,
+        )
     }
 
     /// Instantiates NetApi
@@ -1443,8 +1671,21 @@ where
     }
 
     /// Instantiates RethApi
-    pub fn reth_api(&mut self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [Self::eth_api]
+    pub fn reth_api(&mut self) -> RethApi<Provider, Pool> {
+        // the blob sidecar index is maintained by a task spawned with the eth handlers
+        self.with_eth(|_| ());
+        RethApi::new(
+            self.provider.clone(),
+            self.pool.clone(),
+            Box::new(self.executor.clone()),
+            self.local_blob_store.clone(),
+            self.blob_sidecar_index.clone(),
+            self.preconfirmations.clone(),
+        )
     }
 }
 
@@ -1479,11 +1720,27 @@ pub struct RpcServerConfig {
     ipc_endpoint: Option<Endpoint>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// API keys that http and ws calls are metered by
+    api_keys: Option<ApiKeys>,
+    /// Rate limits of http and ws calls
+    rate_limiter: Option<RateLimiter>,
+    /// Audit log of the calls of all transports
+    audit_log: Option<RpcAuditConfig>,
+    /// Execution timeouts of the calls of all transports
+    call_timeouts: RpcTimeouts,
+    /// Cache of the responses of calls for finalized data of all transports
+    response_cache: Option<RpcResponseCache>,
+    /// GraphQL API served by the http server
+    graphql: Option<GraphQl>,
+    /// REST API served by the http server
+    #[cfg(feature = "rest")]
+    rest: Option<RestApi>,
 }
 
 impl fmt::Debug for RpcServerConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("RpcServerConfig")
+        let mut debug = f.debug_struct("RpcServerConfig");
+        debug
             .field("http_server_config", &self.http_server_config)
             .field("http_cors_domains", &self.http_cors_domains)
             .field("http_addr", &self.http_addr)
@@ -1492,7 +1749,15 @@ impl fmt::Debug for RpcServerConfig {
             .field("ipc_server_config", &self.ipc_server_config)
             .field("ipc_endpoint", &self.ipc_endpoint.as_ref().map(|endpoint| endpoint.path()))
             .field("jwt_secret", &self.jwt_secret)
-            .finish()
+            .field("api_keys", &self.api_keys)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("audit_log", &self.audit_log)
+            .field("call_timeouts", &self.call_timeouts)
+            .field("response_cache", &self.response_cache)
+            .field("graphql", &self.graphql);
+        #[cfg(feature = "rest")]
+        debug.field("rest", &self.rest);
+        debug.finish()
     }
 }
 
@@ -1609,6 +1874,113 @@ impl RpcServerConfig {
         self
     }
 
+    /// Configures the API keys that http and ws requests require and every call is metered by.
+    ///
+    /// If configured, the `admin_apiKeyUsage` endpoint is installed on all transports that have
+    /// the `admin` module enabled.
+    pub fn with_api_keys(mut self, api_keys: Option<ApiKeys>) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
+
+    /// Returns the configured API keys, if any.
+    pub fn api_keys(&self) -> Option<&ApiKeys> {
+        self.api_keys.as_ref()
+    }
+
+    /// Configures the rate limits of http and ws calls.
+    pub fn with_rate_limiter(mut self, rate_limiter: Option<RateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Returns the configured rate limiter, if any.
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.rate_limiter.as_ref()
+    }
+
+    /// Configures the audit log that records the calls of the http, ws and ipc servers.
+    ///
+    /// The sink of the audit log is opened when the servers are built.
+    pub fn with_audit_log(mut self, audit_log: Option<RpcAuditConfig>) -> Self {
+        self.audit_log = audit_log;
+        self
+    }
+
+    /// Returns the configured audit log, if any.
+    pub fn audit_log(&self) -> Option<&RpcAuditConfig> {
+        self.audit_log.as_ref()
+    }
+
+    /// Configures the execution timeouts of the calls of the http, ws and ipc servers.
+    ///
+    /// Calls that exceed their timeout are aborted with a [CALL_TIMEOUT_CODE] error and the
+    /// blocking tasks they spawned are cancelled.
+    pub fn with_call_timeouts(mut self, call_timeouts: RpcTimeouts) -> Self {
+        self.call_timeouts = call_timeouts;
+        self
+    }
+
+    /// Returns the configured execution timeouts of the calls.
+    pub fn call_timeouts(&self) -> &RpcTimeouts {
+        &self.call_timeouts
+    }
+
+    /// Configures the cache of the responses of calls for finalized blocks, receipts and traces
+    /// that is used by the http, ws and ipc servers.
+    ///
+    /// See also [RethModuleRegistry::response_cache].
+    pub fn with_response_cache(mut self, response_cache: Option<RpcResponseCache>) -> Self {
+        self.response_cache = response_cache;
+        self
+    }
+
+    /// Returns the configured response cache, if any.
+    pub fn response_cache(&self) -> Option<&RpcResponseCache> {
+        self.response_cache.as_ref()
+    }
+
+    /// Configures the GraphQL API that is served by the http server at
+    /// [GRAPHQL_PATH](reth_rpc::GRAPHQL_PATH).
+    ///
+    /// See also [RethModuleRegistry::graphql].
+    pub fn with_graphql(mut self, graphql: Option<GraphQl>) -> Self {
+        self.graphql = graphql;
+        self
+    }
+
+    /// Returns the configured GraphQL API, if any.
+    pub fn graphql(&self) -> Option<&GraphQl> {
+        self.graphql.as_ref()
+    }
+
+    /// Configures the REST API that is served by the http server.
+    ///
+    /// See also [RethModuleRegistry::rest].
+    #[cfg(feature = "rest")]
+    pub fn with_rest(mut self, rest: Option<RestApi>) -> Self {
+        self.rest = rest;
+        self
+    }
+
+    /// Returns the configured REST API, if any.
+    #[cfg(feature = "rest")]
+    pub fn rest(&self) -> Option<&RestApi> {
+        self.rest.as_ref()
+    }
+
+    /// Returns the REST layer of the server, the REST API is not served by a ws only server.
+    #[cfg(feature = "rest")]
+    fn rest_middleware(&self, http: bool) -> RestMiddleware {
+        option_layer(self.rest.clone().filter(|_| http).map(RestLayer::new))
+    }
+
+    /// Returns the REST layer of the server, which is a no-op without the `rest` feature.
+    #[cfg(not(feature = "rest"))]
+    fn rest_middleware(&self, _http: bool) -> RestMiddleware {
+        Identity::new()
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be be launched on [RpcServerConfig::start].
@@ -1634,7 +2006,15 @@ impl RpcServerConfig {
     }
 
     /// Convenience function to do [RpcServerConfig::build] and [RpcServer::start] in one step
-    pub async fn start(self, modules: TransportRpcModules) -> Result<RpcServerHandle, RpcError> {
+    pub async fn start(
+        self,
+        mut modules: TransportRpcModules,
+    ) -> Result<RpcServerHandle, RpcError> {
+        if let Some(api_keys) = &self.api_keys {
+            modules
+                .merge_if_module_configured(RethRpcModule::Admin, api_keys.clone().into_rpc())
+                .map_err(|err| RpcError::Custom(err.to_string()))?;
+        }
         self.build(&modules).await?.start(modules).await
     }
 
@@ -1644,6 +2024,9 @@ impl RpcServerConfig {
     async fn build_ws_http(
         &mut self,
         modules: &TransportRpcModules,
+        audit_log: Option<&RpcAuditLog>,
+        response_cache: Option<&RpcResponseCacheLayer>,
+        timeouts: Option<&RpcTimeoutLayer>,
     ) -> Result<WsHttpServer, RpcError> {
         let http_socket_addr = self.http_addr.unwrap_or(SocketAddr::V4(SocketAddrV4::new(
             Ipv4Addr::LOCALHOST,
@@ -1688,13 +2071,21 @@ impl RpcServerConfig {
                 http_socket_addr,
                 cors,
                 secret,
+                ClientLimits::new(self.api_keys.clone(), self.rate_limiter.clone()),
+                self.graphql.clone(),
+                self.rest_middleware(true),
                 ServerKind::WsHttp(http_socket_addr),
-                modules
-                    .http
-                    .as_ref()
-                    .or(modules.ws.as_ref())
-                    .map(RpcRequestMetrics::same_port)
-                    .unwrap_or_default(),
+                rpc_middleware(
+                    modules
+                        .http
+                        .as_ref()
+                        .or(modules.ws.as_ref())
+                        .map(RpcRequestMetrics::same_port)
+                        .unwrap_or_default(),
+                    audit_log.map(RpcAuditLayer::http),
+                    response_cache.cloned(),
+                    timeouts.cloned(),
+                ),
             )
             .await?;
             return Ok(WsHttpServer {
@@ -1717,8 +2108,16 @@ impl RpcServerConfig {
                 ws_socket_addr,
                 self.ws_cors_domains.take(),
                 self.jwt_secret.clone(),
+                ClientLimits::new(self.api_keys.clone(), self.rate_limiter.clone()),
+                None,
+                self.rest_middleware(false),
                 ServerKind::WS(ws_socket_addr),
-                modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default(),
+                rpc_middleware(
+                    modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default(),
+                    audit_log.map(RpcAuditLayer::ws),
+                    response_cache.cloned(),
+                    timeouts.cloned(),
+                ),
             )
             .await?;
             ws_local_addr = Some(addr);
@@ -1732,8 +2131,16 @@ impl RpcServerConfig {
                 http_socket_addr,
                 self.http_cors_domains.take(),
                 self.jwt_secret.clone(),
+                ClientLimits::new(self.api_keys.clone(), self.rate_limiter.clone()),
+                self.graphql.clone(),
+                self.rest_middleware(true),
                 ServerKind::Http(http_socket_addr),
-                modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                rpc_middleware(
+                    modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                    audit_log.map(RpcAuditLayer::http),
+                    response_cache.cloned(),
+                    timeouts.cloned(),
+                ),
             )
             .await?;
             http_local_addr = Some(addr);
@@ -1754,8 +2161,22 @@ impl RpcServerConfig {
     ///
     /// Note: The server is not started and does nothing unless polled, See also [RpcServer::start]
     pub async fn build(mut self, modules: &TransportRpcModules) -> Result<RpcServer, RpcError> {
+        let audit_log = self
+            .audit_log
+            .take()
+            .map(RpcAuditLog::spawn)
+            .transpose()
+            .map_err(RpcError::AuditLog)?;
+
+        let timeouts = (!self.call_timeouts.is_empty())
+            .then(|| RpcTimeoutLayer::new(std::mem::take(&mut self.call_timeouts)));
+
+        let response_cache = self.response_cache.take().map(RpcResponseCacheLayer::new);
+
         let mut server = RpcServer::empty();
-        server.ws_http = self.build_ws_http(modules).await?;
+        server.ws_http = self
+            .build_ws_http(modules, audit_log.as_ref(), response_cache.as_ref(), timeouts.as_ref())
+            .await?;
 
         if let Some(builder) = self.ipc_server_config {
             let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
@@ -1763,7 +2184,13 @@ impl RpcServerConfig {
                 .ipc_endpoint
                 .unwrap_or_else(|| Endpoint::new(DEFAULT_IPC_ENDPOINT.to_string()));
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new()
+                        .layer(metrics)
+                        .option_layer(audit_log.as_ref().map(RpcAuditLayer::ipc))
+                        .option_layer(response_cache)
+                        .option_layer(timeouts),
+                )
                 .build(ipc_path.path());
             server.ipc = Some(ipc);
         }
@@ -1929,6 +2356,31 @@ impl TransportRpcModules {
         Ok(false)
     }
 
+    /// Merge the given [Methods] in the methods of all transports that have the given module
+    /// configured.
+    ///
+    /// Fails if any of the methods in other is present already.
+    pub fn merge_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        let contains = |selection: Option<&RpcModuleSelection>| {
+            selection.map_or(false, |selection| selection.iter_selection().any(|m| m == module))
+        };
+        if contains(self.config.http()) {
+            self.merge_http(other.clone())?;
+        }
+        if contains(self.config.ws()) {
+            self.merge_ws(other.clone())?;
+        }
+        if contains(self.config.ipc()) {
+            self.merge_ipc(other)?;
+        }
+        Ok(())
+    }
+
     /// Merge the given [Methods] in all configured methods.
     ///
     /// Fails if any of the methods in other is present already.
@@ -2018,24 +2470,94 @@ impl Default for WsHttpServers {
     }
 }
 
+/// The optional GraphQL layer of the http servers.
+type GraphQlMiddleware = Either<GraphQlLayer, Identity>;
+
+/// The optional REST layer of the http servers.
+#[cfg(feature = "rest")]
+type RestMiddleware = Either<RestLayer, Identity>;
+
+/// The REST layer of the http servers is a no-op without the `rest` feature.
+#[cfg(not(feature = "rest"))]
+type RestMiddleware = Identity;
+
+/// The http middleware of the servers that identify their clients, see [ClientLimitsServer].
+type ClientLimitsHttpMiddleware = Stack<
+    RestMiddleware,
+    Stack<
+        GraphQlMiddleware,
+        Stack<
+            Either<AuthLayer<JwtAuthValidator>, Identity>,
+            Stack<Either<CorsLayer, Identity>, Identity>,
+        >,
+    >,
+>;
+
+/// The rpc middleware of the servers, the metrics, the optional audit log, the optional response
+/// cache and the optional timeouts.
+type RpcMiddleware = Stack<
+    RpcEither<RpcTimeoutLayer, Identity>,
+    Stack<
+        RpcEither<RpcResponseCacheLayer, Identity>,
+        Stack<RpcEither<RpcAuditLayer, Identity>, Stack<RpcRequestMetrics, Identity>>,
+    >,
+>;
+
+/// Returns the rpc middleware of a server with the given metrics, audit log, response cache and
+/// timeouts.
+///
+/// The timeouts are the innermost layer, so timed out calls are metered and audited. Calls served
+/// from the response cache are metered and audited as well, but never time out.
+fn rpc_middleware(
+    metrics: RpcRequestMetrics,
+    audit: Option<RpcAuditLayer>,
+    response_cache: Option<RpcResponseCacheLayer>,
+    timeouts: Option<RpcTimeoutLayer>,
+) -> RpcServiceBuilder<RpcMiddleware> {
+    RpcServiceBuilder::new()
+        .layer(metrics)
+        .option_layer(audit)
+        .option_layer(response_cache)
+        .option_layer(timeouts)
+}
+
 /// Http Servers Enum
 #[allow(clippy::type_complexity)]
 enum WsHttpServerKind {
     /// Http server
-    Plain(Server<Identity, Stack<RpcRequestMetrics, Identity>>),
+    Plain(Server<Stack<RestMiddleware, Stack<GraphQlMiddleware, Identity>>, RpcMiddleware>),
     /// Http server with cors
-    WithCors(Server<Stack<CorsLayer, Identity>, Stack<RpcRequestMetrics, Identity>>),
+    WithCors(
+        Server<
+            Stack<RestMiddleware, Stack<GraphQlMiddleware, Stack<CorsLayer, Identity>>>,
+            RpcMiddleware,
+        >,
+    ),
     /// Http server with auth
     WithAuth(
-        Server<Stack<AuthLayer<JwtAuthValidator>, Identity>, Stack<RpcRequestMetrics, Identity>>,
+        Server<
+            Stack<
+                RestMiddleware,
+                Stack<GraphQlMiddleware, Stack<AuthLayer<JwtAuthValidator>, Identity>>,
+            >,
+            RpcMiddleware,
+        >,
     ),
     /// Http server with cors and auth
     WithCorsAuth(
         Server<
-            Stack<AuthLayer<JwtAuthValidator>, Stack<CorsLayer, Identity>>,
-            Stack<RpcRequestMetrics, Identity>,
+            Stack<
+                RestMiddleware,
+                Stack<
+                    GraphQlMiddleware,
+                    Stack<AuthLayer<JwtAuthValidator>, Stack<CorsLayer, Identity>>,
+                >,
+            >,
+            RpcMiddleware,
         >,
     ),
+    /// Http server that identifies its clients to limit their calls, with optional cors and auth
+    WithClientLimits(ClientLimitsServer),
 }
 
 // === impl WsHttpServerKind ===
@@ -2048,20 +2570,51 @@ impl WsHttpServerKind {
             WsHttpServerKind::WithCors(server) => server.start(module),
             WsHttpServerKind::WithAuth(server) => server.start(module),
             WsHttpServerKind::WithCorsAuth(server) => server.start(module),
+            WsHttpServerKind::WithClientLimits(server) => server.start(module),
         }
     }
 
     /// Builds the server according to the given config parameters.
     ///
     /// Returns the address of the started server.
+    #[allow(clippy::too_many_arguments)]
     async fn build(
         builder: ServerBuilder<Identity, Identity>,
         socket_addr: SocketAddr,
         cors_domains: Option<String>,
         jwt_secret: Option<JwtSecret>,
+        client_limits: ClientLimits,
+        graphql: Option<GraphQl>,
+        rest: RestMiddleware,
         server_kind: ServerKind,
-        metrics: RpcRequestMetrics,
+        rpc_middleware: RpcServiceBuilder<RpcMiddleware>,
     ) -> Result<(Self, SocketAddr), RpcError> {
+        let graphql = option_layer(graphql.map(GraphQlLayer::new));
+
+        if !client_limits.is_empty() {
+            let cors = cors_domains
+                .as_deref()
+                .map(cors::create_cors_layer)
+                .transpose()
+                .map_err(|err| RpcError::Custom(err.to_string()))?;
+            let auth = jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)));
+            let middleware = tower::ServiceBuilder::new()
+                .layer(option_layer(cors))
+                .layer(option_layer(auth))
+                .layer(graphql)
+                .layer(rest);
+            let server = ClientLimitsServer::bind(
+                builder.set_http_middleware(middleware).to_service_builder(),
+                socket_addr,
+                rpc_middleware,
+                client_limits,
+            )
+            .await
+            .map_err(|err| RpcError::server_error(err, server_kind))?;
+            let local_addr = server.local_addr();
+            return Ok((WsHttpServerKind::WithClientLimits(server), local_addr))
+        }
+
         if let Some(cors) = cors_domains.as_deref().map(cors::create_cors_layer) {
             let cors = cors.map_err(|err| RpcError::Custom(err.to_string()))?;
 
@@ -2069,11 +2622,13 @@ impl WsHttpServerKind {
                 // stack cors and auth layers
                 let middleware = tower::ServiceBuilder::new()
                     .layer(cors)
-                    .layer(AuthLayer::new(JwtAuthValidator::new(secret.clone())));
+                    .layer(AuthLayer::new(JwtAuthValidator::new(secret.clone())))
+                    .layer(graphql)
+                    .layer(rest);
 
                 let server = builder
                     .set_http_middleware(middleware)
-                    .set_rpc_middleware(RpcServiceBuilder::new().layer(metrics))
+                    .set_rpc_middleware(rpc_middleware)
                     .build(socket_addr)
                     .await
                     .map_err(|err| RpcError::server_error(err, server_kind))?;
@@ -2082,10 +2637,11 @@ impl WsHttpServerKind {
                 let server = WsHttpServerKind::WithCorsAuth(server);
                 Ok((server, local_addr))
             } else {
-                let middleware = tower::ServiceBuilder::new().layer(cors);
+                let middleware =
+                    tower::ServiceBuilder::new().layer(cors).layer(graphql).layer(rest);
                 let server = builder
                     .set_http_middleware(middleware)
-                    .set_rpc_middleware(RpcServiceBuilder::new().layer(metrics))
+                    .set_rpc_middleware(rpc_middleware)
                     .build(socket_addr)
                     .await
                     .map_err(|err| RpcError::server_error(err, server_kind))?;
@@ -2097,10 +2653,12 @@ impl WsHttpServerKind {
         } else if let Some(secret) = jwt_secret {
             // jwt auth layered service
             let middleware = tower::ServiceBuilder::new()
-                .layer(AuthLayer::new(JwtAuthValidator::new(secret.clone())));
+                .layer(AuthLayer::new(JwtAuthValidator::new(secret.clone())))
+                .layer(graphql)
+                .layer(rest);
             let server = builder
                 .set_http_middleware(middleware)
-                .set_rpc_middleware(RpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(rpc_middleware)
                 .build(socket_addr)
                 .await
                 .map_err(|err| RpcError::server_error(err, ServerKind::Auth(socket_addr)))?;
@@ -2109,9 +2667,11 @@ impl WsHttpServerKind {
             let server = WsHttpServerKind::WithAuth(server);
             Ok((server, local_addr))
         } else {
-            // plain server without any middleware
+            // plain server without any middleware besides the optional graphql and rest
+            let middleware = tower::ServiceBuilder::new().layer(graphql).layer(rest);
             let server = builder
-                .set_rpc_middleware(RpcServiceBuilder::new().layer(metrics))
+                .set_http_middleware(middleware)
+                .set_rpc_middleware(rpc_middleware)
                 .build(socket_addr)
                 .await
                 .map_err(|err| RpcError::server_error(err, server_kind))?;
@@ -2128,7 +2688,7 @@ pub struct RpcServer {
     /// Configured ws,http servers
     ws_http: WsHttpServer,
     /// ipc server
-    ipc: Option<IpcServer<Identity, Stack<RpcRequestMetrics, Identity>>>,
+    ipc: Option<IpcServer<Identity, RpcMiddleware>>,
 }
 
 // === impl RpcServer ===
@@ -2406,6 +2966,10 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "personal" => RethRpcModule::Personal,
+                "eth-user-operation" => RethRpcModule::EthUserOperation,
+                "eth-send-bundle" => RethRpcModule::EthSendBundle,
+                "mev" => RethRpcModule::Mev,
             );
     }
 
@@ -0,0 +1,485 @@
+//! Cache of the responses of RPC calls for data that is immutable once it's finalized.
+
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{Request, ResponsePayload},
+    MethodResponse,
+};
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use schnellru::{LruMap, Unlimited};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::{
+    collections::HashMap,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::Layer;
+
+/// Default maximum number of cached responses.
+pub const DEFAULT_RESPONSE_CACHE_MAX_ENTRIES: u32 = 10_000;
+
+/// How the block of a cacheable call is determined, the response of the call is only cached if
+/// the block is finalized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockOf {
+    /// The block number param at the index, calls with a block tag or hash are not cached.
+    Param(usize),
+    /// The block number field of the response, or of the first element if it's an array.
+    ResponseField(&'static str),
+}
+
+/// The methods whose responses are cached.
+const CACHEABLE_METHODS: &[(&str, BlockOf)] = &[
+    ("eth_getBlockByNumber", BlockOf::Param(0)),
+    ("eth_getBlockByHash", BlockOf::ResponseField("number")),
+    ("eth_getBlockReceipts", BlockOf::Param(0)),
+    ("eth_getBlockTransactionCountByNumber", BlockOf::Param(0)),
+    ("eth_getTransactionByBlockNumberAndIndex", BlockOf::Param(0)),
+    ("eth_getTransactionByHash", BlockOf::ResponseField("blockNumber")),
+    ("eth_getTransactionReceipt", BlockOf::ResponseField("blockNumber")),
+    ("trace_block", BlockOf::Param(0)),
+    ("trace_replayBlockTransactions", BlockOf::Param(0)),
+    ("trace_transaction", BlockOf::ResponseField("blockNumber")),
+    ("debug_traceBlockByNumber", BlockOf::Param(0)),
+];
+
+/// Configuration of the [RpcResponseCache].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcResponseCacheConfig {
+    /// Maximum size of all cached responses in bytes.
+    pub max_bytes: usize,
+    /// Maximum number of cached responses.
+    pub max_entries: u32,
+}
+
+impl RpcResponseCacheConfig {
+    /// Creates a config with the given maximum size and the default maximum number of responses.
+    pub const fn new(max_bytes: usize) -> Self {
+        Self { max_bytes, max_entries: DEFAULT_RESPONSE_CACHE_MAX_ENTRIES }
+    }
+
+    /// Sets the maximum number of cached responses.
+    pub const fn with_max_entries(mut self, max_entries: u32) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+}
+
+/// Returns the number of the highest finalized block.
+type FinalizedBlock = dyn Fn() -> Option<u64> + Send + Sync;
+
+/// A memory bounded LRU cache of the responses of calls for blocks, receipts and traces that are
+/// immutable because their block is finalized.
+///
+/// The responses are keyed by method and raw params. A response is only cached once its block is
+/// at or below the finalized block, so cached responses can't be affected by reorgs and are served
+/// without calling the method. Nothing is cached while there's no finalized block.
+///
+/// See also [RethModuleRegistry::response_cache](crate::RethModuleRegistry::response_cache).
+#[derive(Clone)]
+pub struct RpcResponseCache {
+    inner: Arc<Mutex<RpcResponseCacheInner>>,
+    finalized: Arc<FinalizedBlock>,
+    metrics: RpcResponseCacheMetrics,
+}
+
+impl RpcResponseCache {
+    /// Creates a new cache that reads the number of the highest finalized block from the function.
+    pub fn new<F>(config: RpcResponseCacheConfig, finalized: F) -> Self
+    where
+        F: Fn() -> Option<u64> + Send + Sync + 'static,
+    {
+        Self {
+            inner: Arc::new(Mutex::new(RpcResponseCacheInner {
+                responses: LruMap::new(Unlimited),
+                size: 0,
+                config,
+            })),
+            finalized: Arc::new(finalized),
+            metrics: Default::default(),
+        }
+    }
+
+    /// Returns the number of cached responses.
+    pub fn len(&self) -> usize {
+        self.inner.lock().responses.len()
+    }
+
+    /// Returns true if no response is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the block is finalized.
+    fn is_finalized(&self, block: u64) -> bool {
+        (self.finalized)().is_some_and(|finalized| block <= finalized)
+    }
+
+    /// Returns the cached result of the call, if any.
+    fn get(&self, key: &CacheKey) -> Option<Arc<RawValue>> {
+        let result = self.inner.lock().responses.get(key).cloned();
+        if result.is_some() {
+            self.metrics.hits.increment(1);
+        } else {
+            self.metrics.misses.increment(1);
+        }
+        result
+    }
+
+    /// Caches the result of the call.
+    ///
+    /// Results that are larger than the maximum size of the cache are not cached.
+    fn insert(&self, key: CacheKey, result: Arc<RawValue>) {
+        let size = key.size() + result.get().len();
+
+        let mut inner = self.inner.lock();
+        if size > inner.config.max_bytes {
+            return
+        }
+        if let Some(previous) = inner.responses.peek(&key) {
+            let previous = key.size() + previous.get().len();
+            inner.size -= previous;
+        }
+        inner.responses.insert(key, result);
+        inner.size += size;
+        while inner.size > inner.config.max_bytes ||
+            inner.responses.len() > inner.config.max_entries as usize
+        {
+            let Some((key, evicted)) = inner.responses.pop_oldest() else { break };
+            inner.size -= key.size() + evicted.get().len();
+        }
+
+        self.metrics.cached_count.set(inner.responses.len() as f64);
+        self.metrics.cached_bytes.set(inner.size as f64);
+    }
+}
+
+impl fmt::Debug for RpcResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let inner = self.inner.lock();
+        f.debug_struct("RpcResponseCache")
+            .field("len", &inner.responses.len())
+            .field("size", &inner.size)
+            .field("config", &inner.config)
+            .finish_non_exhaustive()
+    }
+}
+
+struct RpcResponseCacheInner {
+    responses: LruMap<CacheKey, Arc<RawValue>, Unlimited>,
+    /// The size of all cached responses and their keys in bytes.
+    size: usize,
+    config: RpcResponseCacheConfig,
+}
+
+/// The method and raw params of a call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    method: &'static str,
+    params: Box<str>,
+}
+
+impl CacheKey {
+    /// Returns the size of the key in bytes.
+    fn size(&self) -> usize {
+        self.method.len() + self.params.len()
+    }
+}
+
+/// A [RpcServiceT] middleware layer that serves calls from the [RpcResponseCache].
+#[derive(Debug, Clone)]
+pub(crate) struct RpcResponseCacheLayer {
+    cache: RpcResponseCache,
+}
+
+impl RpcResponseCacheLayer {
+    pub(crate) fn new(cache: RpcResponseCache) -> Self {
+        Self { cache }
+    }
+}
+
+impl<S> Layer<S> for RpcResponseCacheLayer {
+    type Service = RpcResponseCacheService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcResponseCacheService { cache: self.cache.clone(), inner }
+    }
+}
+
+/// A [RpcServiceT] middleware that serves calls from the [RpcResponseCache] and caches the
+/// responses of the calls for finalized blocks.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcResponseCacheService<S> {
+    cache: RpcResponseCache,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcResponseCacheService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = ResponseCacheFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let Some(&(method, block_of)) =
+            CACHEABLE_METHODS.iter().find(|(method, _)| *method == req.method.as_ref())
+        else {
+            return ResponseCacheFuture::call(self.inner.call(req), None)
+        };
+        let params = req.params.as_ref().map_or("", |params| params.get());
+
+        // calls for blocks that are not finalized yet are neither cached nor served from the cache
+        if let BlockOf::Param(index) = block_of {
+            if !block_param(params, index).is_some_and(|block| self.cache.is_finalized(block)) {
+                return ResponseCacheFuture::call(self.inner.call(req), None)
+            }
+        }
+
+        let key = CacheKey { method, params: params.into() };
+        if let Some(result) = self.cache.get(&key) {
+            let response = MethodResponse::response(
+                req.id.clone(),
+                ResponsePayload::success(result.as_ref()),
+                usize::MAX,
+            );
+            return ResponseCacheFuture::cached(response)
+        }
+
+        let insert = PendingInsert { cache: self.cache.clone(), key, block_of };
+        ResponseCacheFuture::call(self.inner.call(req), Some(insert))
+    }
+}
+
+/// A response that is cached once the call completes.
+struct PendingInsert {
+    cache: RpcResponseCache,
+    key: CacheKey,
+    block_of: BlockOf,
+}
+
+impl PendingInsert {
+    /// Caches the result of the response if it's for a finalized block.
+    fn insert(self, response: &MethodResponse) {
+        if !response.is_success() {
+            return
+        }
+        let Some(result) = response_result(response.as_result()) else { return };
+        if result.get() == "null" {
+            return
+        }
+        if let BlockOf::ResponseField(field) = self.block_of {
+            if !response_block(&result, field).is_some_and(|block| self.cache.is_finalized(block)) {
+                return
+            }
+        }
+        self.cache.insert(self.key, result);
+    }
+}
+
+/// Response future of a call that may be served from the cache.
+#[pin_project::pin_project]
+pub(crate) struct ResponseCacheFuture<F> {
+    /// the cached response
+    cached: Option<MethodResponse>,
+    /// the call if the response is not cached
+    #[pin]
+    fut: Option<F>,
+    /// caches the response of the call
+    insert: Option<PendingInsert>,
+}
+
+impl<F> ResponseCacheFuture<F> {
+    fn cached(response: MethodResponse) -> Self {
+        Self { cached: Some(response), fut: None, insert: None }
+    }
+
+    fn call(fut: F, insert: Option<PendingInsert>) -> Self {
+        Self { cached: None, fut: Some(fut), insert }
+    }
+}
+
+impl<F> fmt::Debug for ResponseCacheFuture<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ResponseCacheFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for ResponseCacheFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Some(response) = this.cached.take() {
+            return Poll::Ready(response)
+        }
+
+        let fut = this.fut.as_pin_mut().expect("future polled after completion");
+        let res = fut.poll(cx);
+        if let Poll::Ready(response) = &res {
+            if let Some(insert) = this.insert.take() {
+                insert.insert(response);
+            }
+        }
+        res
+    }
+}
+
+/// Returns the block number param at the index of the raw params, if it's a number.
+fn block_param(params: &str, index: usize) -> Option<u64> {
+    let params: Vec<&RawValue> = serde_json::from_str(params).ok()?;
+    block_number(params.get(index)?)
+}
+
+/// Returns the block number of the raw result, read from the field of the result or of its first
+/// element if the result is an array.
+fn response_block(result: &RawValue, field: &str) -> Option<u64> {
+    let object = if result.get().starts_with('[') {
+        let elements: Vec<&RawValue> = serde_json::from_str(result.get()).ok()?;
+        *elements.first()?
+    } else {
+        result
+    };
+    let fields: HashMap<&str, &RawValue> = serde_json::from_str(object.get()).ok()?;
+    block_number(fields.get(field)?)
+}
+
+/// Parses a block number that is either a quantity or a JSON number.
+fn block_number(value: &RawValue) -> Option<u64> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Number<'a> {
+        Quantity(&'a str),
+        Number(u64),
+    }
+    match serde_json::from_str(value.get()).ok()? {
+        Number::Quantity(quantity) => u64::from_str_radix(quantity.strip_prefix("0x")?, 16).ok(),
+        Number::Number(number) => Some(number),
+    }
+}
+
+/// Returns the raw result of a serialized successful response.
+fn response_result(response: &str) -> Option<Arc<RawValue>> {
+    #[derive(Deserialize)]
+    struct Success<'a> {
+        #[serde(borrow)]
+        result: &'a RawValue,
+    }
+    let Success { result } = serde_json::from_str(response).ok()?;
+    Some(Arc::from(result.to_owned()))
+}
+
+/// Metrics of the RPC response cache.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc_server.response_cache")]
+struct RpcResponseCacheMetrics {
+    /// The number of calls served from the cache
+    hits: Counter,
+    /// The number of cacheable calls that were not cached
+    misses: Counter,
+    /// The number of cached responses
+    cached_count: Gauge,
+    /// The size of the cached responses in bytes
+    cached_bytes: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{
+        core::client::ClientT,
+        http_client::HttpClientBuilder,
+        rpc_params,
+        server::{RpcServiceBuilder, ServerBuilder},
+        RpcModule,
+    };
+    use std::{
+        net::SocketAddr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    #[test]
+    fn parse_block_numbers() {
+        assert_eq!(block_param(r#"["0x10",true]"#, 0), Some(16));
+        assert_eq!(block_param(r#"["latest",true]"#, 0), None);
+        assert_eq!(block_param(r#"[{"blockHash":"0x01"}]"#, 0), None);
+        assert_eq!(block_param("[]", 0), None);
+
+        let result = RawValue::from_string(r#"{"blockNumber":"0x2a"}"#.to_string()).unwrap();
+        assert_eq!(response_block(&result, "blockNumber"), Some(42));
+        let result = RawValue::from_string(r#"[{"blockNumber":42},{}]"#.to_string()).unwrap();
+        assert_eq!(response_block(&result, "blockNumber"), Some(42));
+        let result = RawValue::from_string(r#"{"blockNumber":null}"#.to_string()).unwrap();
+        assert_eq!(response_block(&result, "blockNumber"), None);
+    }
+
+    #[test]
+    fn evicts_oldest_responses() {
+        let cache = RpcResponseCache::new(RpcResponseCacheConfig::new(100), || Some(0));
+        let key =
+            |i: u64| CacheKey { method: "eth_getBlockByNumber", params: i.to_string().into() };
+        let result =
+            || Arc::from(RawValue::from_string(format!("\"{}\"", "a".repeat(20))).unwrap());
+
+        for i in 0..3 {
+            cache.insert(key(i), result());
+        }
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key(0)).is_none());
+        assert!(cache.get(&key(2)).is_some());
+
+        let cache =
+            RpcResponseCache::new(RpcResponseCacheConfig::new(1024).with_max_entries(1), || {
+                Some(0)
+            });
+        cache.insert(key(0), result());
+        cache.insert(key(1), result());
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get(&key(1)).is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serves_finalized_responses_from_cache() {
+        let cache = RpcResponseCache::new(RpcResponseCacheConfig::new(1024 * 1024), || Some(10));
+        let server = ServerBuilder::default()
+            .set_rpc_middleware(
+                RpcServiceBuilder::new().layer(RpcResponseCacheLayer::new(cache.clone())),
+            )
+            .build("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut module = RpcModule::new(calls.clone());
+        module
+            .register_method("eth_getBlockByNumber", |params, calls| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                let (number, _): (String, bool) = params.parse().unwrap();
+                serde_json::json!({ "number": number })
+            })
+            .unwrap();
+        let handle = server.start(module);
+
+        let client = HttpClientBuilder::default().build(format!("http://{addr}")).unwrap();
+        for params in [("0xa", false), ("0xa", false), ("0xb", false), ("0xb", false)] {
+            let block: serde_json::Value = client
+                .request("eth_getBlockByNumber", rpc_params![params.0, params.1])
+                .await
+                .unwrap();
+            assert_eq!(block["number"], params.0);
+        }
+        // the finalized block is served from the cache, the block after it is not cached
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+        assert_eq!(cache.len(), 1);
+
+        handle.stop().unwrap();
+    }
+}
@@ -0,0 +1,247 @@
+//! Execution timeouts of the RPC calls served by the http, ws and ipc servers.
+
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Id, Request},
+    MethodResponse,
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    str::FromStr,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::Sleep;
+use tower::Layer;
+
+/// The JSON-RPC error code of a call that timed out.
+pub const CALL_TIMEOUT_CODE: i32 = -32002;
+
+/// The execution timeouts of the RPC calls.
+///
+/// A call that exceeds its timeout is aborted with a [CALL_TIMEOUT_CODE] error. The call is
+/// dropped, which cancels the blocking tasks it spawned, see
+/// [CancellationToken](reth_tasks::pool::CancellationToken).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcTimeouts {
+    /// The timeout of the calls of methods without a timeout of their own.
+    pub default: Option<Duration>,
+    /// The timeouts of individual methods.
+    pub methods: HashMap<String, Duration>,
+}
+
+impl RpcTimeouts {
+    /// Sets the timeout of the calls of methods without a timeout of their own.
+    pub fn with_default(mut self, timeout: Option<Duration>) -> Self {
+        self.default = timeout;
+        self
+    }
+
+    /// Sets the timeout of the method.
+    pub fn with_method(mut self, method: impl Into<String>, timeout: Duration) -> Self {
+        self.methods.insert(method.into(), timeout);
+        self
+    }
+
+    /// Returns the timeout of the method, if any.
+    pub fn timeout(&self, method: &str) -> Option<Duration> {
+        self.methods.get(method).copied().or(self.default)
+    }
+
+    /// Returns true if no call has a timeout.
+    pub fn is_empty(&self) -> bool {
+        self.default.is_none() && self.methods.is_empty()
+    }
+}
+
+/// The timeout of a method, parsed from `<method>=<duration>`, e.g. `debug_traceBlockByNumber=60s`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodTimeout {
+    /// The name of the method.
+    pub method: String,
+    /// The timeout of the method.
+    pub timeout: Duration,
+}
+
+impl FromStr for MethodTimeout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (method, timeout) =
+            s.split_once('=').ok_or_else(|| format!("expected <method>=<duration>, got {s}"))?;
+        let method = method.trim();
+        if method.is_empty() {
+            return Err(format!("missing method in {s}"))
+        }
+        let timeout = humantime::parse_duration(timeout.trim()).map_err(|err| err.to_string())?;
+        Ok(Self { method: method.to_string(), timeout })
+    }
+}
+
+impl FromIterator<MethodTimeout> for RpcTimeouts {
+    fn from_iter<T: IntoIterator<Item = MethodTimeout>>(iter: T) -> Self {
+        let methods = iter.into_iter().map(|t| (t.method, t.timeout)).collect();
+        Self { default: None, methods }
+    }
+}
+
+/// A [RpcServiceT] middleware layer that aborts calls that exceed their [RpcTimeouts].
+#[derive(Debug, Clone)]
+pub(crate) struct RpcTimeoutLayer {
+    timeouts: Arc<RpcTimeouts>,
+}
+
+impl RpcTimeoutLayer {
+    pub(crate) fn new(timeouts: RpcTimeouts) -> Self {
+        Self { timeouts: Arc::new(timeouts) }
+    }
+}
+
+impl<S> Layer<S> for RpcTimeoutLayer {
+    type Service = RpcTimeoutService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcTimeoutService { timeouts: self.timeouts.clone(), inner }
+    }
+}
+
+/// A [RpcServiceT] middleware that aborts calls that exceed their timeout.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcTimeoutService<S> {
+    timeouts: Arc<RpcTimeouts>,
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcTimeoutService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = TimeoutRequestFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let timeout = self
+            .timeouts
+            .timeout(req.method.as_ref())
+            .map(|timeout| (req.id.clone().into_owned(), timeout));
+        TimeoutRequestFuture {
+            sleep: timeout.as_ref().map(|(_, timeout)| tokio::time::sleep(*timeout)),
+            timeout,
+            fut: self.inner.call(req),
+        }
+    }
+}
+
+/// Response future that resolves to a timeout error once the call exceeds its timeout.
+///
+/// The call is dropped together with this future.
+#[pin_project::pin_project]
+pub(crate) struct TimeoutRequestFuture<F> {
+    #[pin]
+    fut: F,
+    /// fires once the timeout is exceeded
+    #[pin]
+    sleep: Option<Sleep>,
+    /// the id of the request and its timeout
+    timeout: Option<(Id<'static>, Duration)>,
+}
+
+impl<F> std::fmt::Debug for TimeoutRequestFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TimeoutRequestFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for TimeoutRequestFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        if let Poll::Ready(resp) = this.fut.poll(cx) {
+            return Poll::Ready(resp)
+        }
+        if let Some(sleep) = this.sleep.as_pin_mut() {
+            if sleep.poll(cx).is_ready() {
+                let (id, timeout) = this.timeout.take().expect("timeout is set with sleep");
+                let err = ErrorObject::owned(
+                    CALL_TIMEOUT_CODE,
+                    format!("request timed out (timeout = {timeout:?})"),
+                    None::<()>,
+                );
+                return Poll::Ready(MethodResponse::error(id, err))
+            }
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{
+        core::client::ClientT,
+        http_client::HttpClientBuilder,
+        rpc_params,
+        server::{RpcServiceBuilder, ServerBuilder},
+        RpcModule,
+    };
+    use std::{
+        net::SocketAddr,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    #[test]
+    fn parse_method_timeout() {
+        let timeout: MethodTimeout = "debug_traceBlockByNumber=1m".parse().unwrap();
+        assert_eq!(timeout.method, "debug_traceBlockByNumber");
+        assert_eq!(timeout.timeout, Duration::from_secs(60));
+        assert!("debug_traceBlockByNumber".parse::<MethodTimeout>().is_err());
+        assert!("=1s".parse::<MethodTimeout>().is_err());
+
+        let timeouts = RpcTimeouts::from_iter([timeout])
+            .with_default(Some(Duration::from_secs(5)))
+            .with_method("eth_getLogs", Duration::from_secs(10));
+        assert_eq!(timeouts.timeout("debug_traceBlockByNumber"), Some(Duration::from_secs(60)));
+        assert_eq!(timeouts.timeout("eth_getLogs"), Some(Duration::from_secs(10)));
+        assert_eq!(timeouts.timeout("eth_call"), Some(Duration::from_secs(5)));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn aborts_slow_calls() {
+        let timeouts = RpcTimeouts::default().with_method("test_slow", Duration::from_millis(50));
+        let server = ServerBuilder::default()
+            .set_rpc_middleware(RpcServiceBuilder::new().layer(RpcTimeoutLayer::new(timeouts)))
+            .build("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let finished = Arc::new(AtomicBool::new(false));
+        let mut module = RpcModule::new(finished.clone());
+        module
+            .register_async_method("test_slow", |_, finished| async move {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                finished.store(true, Ordering::Relaxed);
+                "done"
+            })
+            .unwrap();
+        module.register_method("test_fast", |_, _| "done").unwrap();
+        let handle = server.start(module);
+
+        let client = HttpClientBuilder::default().build(format!("http://{addr}")).unwrap();
+        let res: String = client.request("test_fast", rpc_params![]).await.unwrap();
+        assert_eq!(res, "done");
+
+        let err = client.request::<String, _>("test_slow", rpc_params![]).await.unwrap_err();
+        match err {
+            jsonrpsee::core::ClientError::Call(err) => assert_eq!(err.code(), CALL_TIMEOUT_CODE),
+            err => panic!("unexpected error {err:?}"),
+        }
+        assert!(!finished.load(Ordering::Relaxed));
+
+        handle.stop().unwrap();
+    }
+}
@@ -183,10 +183,11 @@ where
     EthApiClient::sign_typed_data(client, address, jsonrpsee::core::JsonValue::Null)
         .await
         .unwrap_err();
+    EthApiClient::sign_authorization(client, address, Default::default()).await.unwrap_err();
     EthApiClient::transaction_by_hash(client, tx_hash).await.unwrap();
     EthApiClient::transaction_by_block_hash_and_index(client, hash, index).await.unwrap();
     EthApiClient::transaction_by_block_number_and_index(client, block_number, index).await.unwrap();
-    EthApiClient::create_access_list(client, call_request.clone(), Some(block_number.into()))
+    EthApiClient::create_access_list(client, call_request.clone(), Some(block_number.into()), None)
         .await
         .unwrap();
     EthApiClient::estimate_gas(client, call_request.clone(), Some(block_number.into()), None)
@@ -227,6 +228,7 @@ where
     DebugApiClient::raw_header(client, block_id).await.unwrap();
     DebugApiClient::raw_block(client, block_id).await.unwrap();
     DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
+    DebugApiClient::raw_transactions(client, block_id).await.unwrap();
     DebugApiClient::raw_receipts(client, block_id).await.unwrap();
     assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
 }
@@ -313,27 +315,19 @@ where
         .err()
         .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_before(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_after(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_transaction_by_sender_and_nonce(client, sender, nonce,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_contract_creator(client, address).await.err().unwrap()
-    ));
+    // the address transactions index is not enabled
+    OtterscanClient::search_transactions_before(client, address, block_number, page_size)
+        .await
+        .err()
+        .unwrap();
+    OtterscanClient::search_transactions_after(client, address, block_number, page_size)
+        .await
+        .err()
+        .unwrap();
+
+    OtterscanClient::get_transaction_by_sender_and_nonce(client, sender, nonce).await.unwrap();
+
+    OtterscanClient::get_contract_creator(client, address).await.unwrap();
 }
 
 #[tokio::test(flavor = "multi_thread")]
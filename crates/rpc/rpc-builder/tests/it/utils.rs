@@ -31,6 +31,7 @@ pub async fn launch_auth(secret: JwtSecret) -> AuthServerHandle {
         MAINNET.clone(),
         beacon_engine_handle,
         spawn_test_payload_service().into(),
+        TestPoolBuilder::default().into(),
         Box::<TokioTaskExecutor>::default(),
     );
     let module = AuthRpcModule::new(engine_api);
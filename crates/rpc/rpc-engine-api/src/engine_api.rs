@@ -3,22 +3,29 @@ use async_trait::async_trait;
 use jsonrpsee_core::RpcResult;
 use reth_beacon_consensus::BeaconConsensusEngineHandle;
 use reth_engine_primitives::{
-    validate_payload_timestamp, EngineApiMessageVersion, EngineTypes, PayloadAttributes,
-    PayloadBuilderAttributes, PayloadOrAttributes,
+    validate_payload_timestamp, EngineApiMessageVersion, EngineObjectValidationError, EngineTypes,
+    PayloadAttributes, PayloadBuilderAttributes, PayloadOrAttributes,
 };
 use reth_payload_builder::PayloadStore;
-use reth_primitives::{BlockHash, BlockHashOrNumber, BlockNumber, ChainSpec, Hardfork, B256, U64};
+use reth_primitives::{
+    alloy_primitives::FixedBytes, BlockHash, BlockHashOrNumber, BlockNumber, Bytes, ChainSpec,
+    Hardfork, B256, U64,
+};
 use reth_provider::{BlockReader, EvmEnvProvider, HeaderProvider, StateProviderFactory};
 use reth_rpc_api::EngineApiServer;
-use reth_rpc_types::engine::{
-    CancunPayloadFields, ExecutionPayload, ExecutionPayloadBodiesV1, ExecutionPayloadInputV2,
-    ExecutionPayloadV1, ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated, PayloadId,
-    PayloadStatus, TransitionConfiguration, CAPABILITIES,
+use reth_rpc_types::{
+    engine::{
+        CancunPayloadFields, ExecutionPayload, ExecutionPayloadBodiesV1, ExecutionPayloadInputV2,
+        ExecutionPayloadV1, ExecutionPayloadV3, ForkchoiceState, ForkchoiceUpdated, PayloadId,
+        PayloadStatus, TransitionConfiguration, CAPABILITIES,
+    },
+    BlobAndProofV1, BlobAndProofV2,
 };
 use reth_rpc_types_compat::engine::payload::{
     convert_payload_input_v2_to_payload, convert_to_payload_body_v1,
 };
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::{BlobAndProof, TransactionPool};
 use std::{sync::Arc, time::Instant};
 use tokio::sync::oneshot;
 use tracing::{trace, warn};
@@ -29,13 +36,19 @@ pub type EngineApiSender<Ok> = oneshot::Sender<EngineApiResult<Ok>>;
 /// The upper limit for payload bodies request.
 const MAX_PAYLOAD_BODIES_LIMIT: u64 = 1024;
 
+/// The upper limit for blobs in `engine_getBlobsV*` requests.
+const MAX_BLOB_LIMIT: u64 = 128;
+
+/// The capabilities served in addition to [CAPABILITIES].
+const BLOB_CAPABILITIES: [&str; 1] = ["engine_getBlobsV1"];
+
 /// The Engine API implementation that grants the Consensus layer access to data and
 /// functions in the Execution layer that are crucial for the consensus process.
-pub struct EngineApi<Provider, EngineT: EngineTypes> {
-    inner: Arc<EngineApiInner<Provider, EngineT>>,
+pub struct EngineApi<Provider, EngineT: EngineTypes, Pool> {
+    inner: Arc<EngineApiInner<Provider, EngineT, Pool>>,
 }
 
-struct EngineApiInner<Provider, EngineT: EngineTypes> {
+struct EngineApiInner<Provider, EngineT: EngineTypes, Pool> {
     /// The provider to interact with the chain.
     provider: Provider,
     /// Consensus configuration
@@ -44,16 +57,19 @@ struct EngineApiInner<Provider, EngineT: EngineTypes> {
     beacon_consensus: BeaconConsensusEngineHandle<EngineT>,
     /// The type that can communicate with the payload service to retrieve payloads.
     payload_store: PayloadStore<EngineT>,
+    /// The transaction pool the blobs are served from.
+    pool: Pool,
     /// For spawning and executing async tasks
     task_spawner: Box<dyn TaskSpawner>,
     /// The latency and response type metrics for engine api calls
     metrics: EngineApiMetrics,
 }
 
-impl<Provider, EngineT> EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> EngineApi<Provider, EngineT, Pool>
 where
     Provider: HeaderProvider + BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     EngineT: EngineTypes + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Create new instance of [EngineApi].
     pub fn new(
@@ -61,6 +77,7 @@ where
         chain_spec: Arc<ChainSpec>,
         beacon_consensus: BeaconConsensusEngineHandle<EngineT>,
         payload_store: PayloadStore<EngineT>,
+        pool: Pool,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
         let inner = Arc::new(EngineApiInner {
@@ -68,6 +85,7 @@ where
             chain_spec,
             beacon_consensus,
             payload_store,
+            pool,
             task_spawner,
             metrics: EngineApiMetrics::default(),
         });
@@ -426,6 +444,64 @@ where
         }
     }
 
+    /// Returns the blobs and their proofs for the given versioned hashes from the blob pool.
+    ///
+    /// Blobs that are not in the pool are returned as `None`.
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getblobsv1>
+    pub async fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EngineApiResult<Vec<Option<BlobAndProofV1>>> {
+        let len = versioned_hashes.len() as u64;
+        if len > MAX_BLOB_LIMIT {
+            return Err(EngineApiError::PayloadRequestTooLarge { len })
+        }
+
+        let (tx, rx) = oneshot::channel();
+        let inner = self.inner.clone();
+
+        // blobs may have to be read from disk
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let res = inner
+                .pool
+                .get_blobs_for_versioned_hashes(&versioned_hashes)
+                .map_err(|err| EngineApiError::Internal(Box::new(err)));
+            tx.send(res).ok();
+        }));
+        let blobs = rx.await.map_err(|err| EngineApiError::Internal(Box::new(err)))??;
+
+        let hits = blobs.iter().filter(|blob| blob.is_some()).count();
+        self.inner.metrics.blobs.update_blob_metrics(blobs.len(), hits);
+
+        Ok(blobs
+            .into_iter()
+            .map(|blob| {
+                blob.map(|BlobAndProof { blob, proof }| BlobAndProofV1 {
+                    blob: Bytes::copy_from_slice(blob.as_slice()),
+                    proof: FixedBytes::from_slice(proof.as_slice()),
+                })
+            })
+            .collect())
+    }
+
+    /// Returns the blobs and their cell proofs for the given versioned hashes from the blob pool.
+    ///
+    /// Cell proofs are introduced with Osaka, which is not supported yet: the blob pool only
+    /// stores the single KZG proof of each blob, so this always returns an unsupported fork error.
+    ///
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/osaka.md#engine_getblobsv2>
+    pub fn get_blobs_v2(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EngineApiResult<Option<Vec<BlobAndProofV2>>> {
+        let len = versioned_hashes.len() as u64;
+        if len > MAX_BLOB_LIMIT {
+            return Err(EngineApiError::PayloadRequestTooLarge { len })
+        }
+        Err(EngineObjectValidationError::UnsupportedFork.into())
+    }
+
     /// Validates the `engine_forkchoiceUpdated` payload attributes and executes the forkchoice
     /// update.
     ///
@@ -478,10 +554,11 @@ where
 }
 
 #[async_trait]
-impl<Provider, EngineT> EngineApiServer<EngineT> for EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> EngineApiServer<EngineT> for EngineApi<Provider, EngineT, Pool>
 where
     Provider: HeaderProvider + BlockReader + StateProviderFactory + EvmEnvProvider + 'static,
     EngineT: EngineTypes + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Handler for `engine_newPayloadV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/3d627c95a4d3510a8187dd02e0250ecb4331d27e/src/engine/paris.md#engine_newpayloadv1>
@@ -694,11 +771,34 @@ where
     /// Handler for `engine_exchangeCapabilitiesV1`
     /// See also <https://github.com/ethereum/execution-apis/blob/6452a6b194d7db269bf1dbd087a267251d3cc7f8/src/engine/common.md#capabilities>
     async fn exchange_capabilities(&self, _capabilities: Vec<String>) -> RpcResult<Vec<String>> {
-        Ok(CAPABILITIES.into_iter().map(str::to_owned).collect())
+        Ok(CAPABILITIES.into_iter().chain(BLOB_CAPABILITIES).map(str::to_owned).collect())
+    }
+
+    /// Handler for `engine_getBlobsV1`
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#engine_getblobsv1>
+    async fn get_blobs_v1(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<BlobAndProofV1>>> {
+        trace!(target: "rpc::engine", "Serving engine_getBlobsV1");
+        let start = Instant::now();
+        let res = EngineApi::get_blobs_v1(self, versioned_hashes).await;
+        self.inner.metrics.latency.get_blobs_v1.record(start.elapsed());
+        Ok(res?)
+    }
+
+    /// Handler for `engine_getBlobsV2`
+    /// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/osaka.md#engine_getblobsv2>
+    async fn get_blobs_v2(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Option<Vec<BlobAndProofV2>>> {
+        trace!(target: "rpc::engine", "Serving engine_getBlobsV2");
+        Ok(EngineApi::get_blobs_v2(self, versioned_hashes)?)
     }
 }
 
-impl<Provider, EngineT> std::fmt::Debug for EngineApi<Provider, EngineT>
+impl<Provider, EngineT, Pool> std::fmt::Debug for EngineApi<Provider, EngineT, Pool>
 where
     EngineT: EngineTypes,
 {
@@ -719,9 +819,11 @@ mod tests {
     use reth_provider::test_utils::MockEthProvider;
     use reth_rpc_types_compat::engine::payload::execution_payload_from_sealed_block;
     use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::noop::NoopTransactionPool;
     use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
 
-    fn setup_engine_api() -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>, EthEngineTypes>)
+    fn setup_engine_api(
+    ) -> (EngineApiTestHandle, EngineApi<Arc<MockEthProvider>, EthEngineTypes, NoopTransactionPool>)
     {
         let chain_spec: Arc<ChainSpec> = MAINNET.clone();
         let provider = Arc::new(MockEthProvider::default());
@@ -733,6 +835,7 @@ mod tests {
             chain_spec.clone(),
             BeaconConsensusEngineHandle::new(to_engine),
             payload_store.into(),
+            NoopTransactionPool::default(),
             task_executor,
         );
         let handle = EngineApiTestHandle { chain_spec, provider, from_api: engine_rx };
@@ -757,6 +860,36 @@ mod tests {
         assert_matches!(handle.from_api.recv().await, Some(BeaconEngineMessage::NewPayload { .. }));
     }
 
+    // tests covering `engine_getBlobsV1` and `engine_getBlobsV2`
+    mod get_blobs {
+        use super::*;
+
+        #[tokio::test]
+        async fn request_too_large() {
+            let (_, api) = setup_engine_api();
+
+            let versioned_hashes = vec![B256::ZERO; MAX_BLOB_LIMIT as usize + 1];
+            let res = api.get_blobs_v1(versioned_hashes.clone()).await;
+            assert_matches!(res, Err(EngineApiError::PayloadRequestTooLarge { .. }));
+            let res = api.get_blobs_v2(versioned_hashes);
+            assert_matches!(res, Err(EngineApiError::PayloadRequestTooLarge { .. }));
+        }
+
+        #[tokio::test]
+        async fn missing_blobs() {
+            let (_, api) = setup_engine_api();
+
+            let res = api.get_blobs_v1(vec![B256::ZERO, B256::with_last_byte(1)]).await.unwrap();
+            assert_eq!(res, vec![None, None]);
+            assert_matches!(
+                api.get_blobs_v2(vec![B256::ZERO]),
+                Err(EngineApiError::EngineObjectValidationError(
+                    EngineObjectValidationError::UnsupportedFork
+                ))
+            );
+        }
+    }
+
     // tests covering `engine_getPayloadBodiesByRange` and `engine_getPayloadBodiesByHash`
     mod get_payload_bodies {
         use super::*;
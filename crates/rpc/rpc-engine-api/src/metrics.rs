@@ -12,6 +12,8 @@ pub(crate) struct EngineApiMetrics {
     pub(crate) fcu_response: ForkchoiceUpdatedResponseMetrics,
     /// Engine API newPayload response type metrics
     pub(crate) new_payload_response: NewPayloadStatusResponseMetrics,
+    /// Engine API getBlobs metrics
+    pub(crate) blobs: BlobMetrics,
 }
 
 /// Beacon consensus engine latency metrics.
@@ -42,6 +44,8 @@ pub(crate) struct EngineApiLatencyMetrics {
     pub(crate) get_payload_bodies_by_hash_v1: Histogram,
     /// Latency for `engine_exchangeTransitionConfigurationV1`
     pub(crate) exchange_transition_configuration: Histogram,
+    /// Latency for `engine_getBlobsV1`
+    pub(crate) get_blobs_v1: Histogram,
 }
 
 /// Metrics for engine API forkchoiceUpdated responses.
@@ -67,6 +71,27 @@ pub(crate) struct ForkchoiceUpdatedResponseMetrics {
     pub(crate) forkchoice_updated_error: Counter,
 }
 
+/// Metrics for engine API getBlobs requests.
+#[derive(Metrics)]
+#[metrics(scope = "engine.rpc.blobs")]
+pub(crate) struct BlobMetrics {
+    /// The total count of blobs requested.
+    pub(crate) blob_count: Counter,
+    /// The total count of requested blobs that were found in the blob pool.
+    pub(crate) blob_hits: Counter,
+    /// The total count of requested blobs that were not found in the blob pool.
+    pub(crate) blob_misses: Counter,
+}
+
+impl BlobMetrics {
+    /// Increment the blob counters based on the number of requested and found blobs.
+    pub(crate) fn update_blob_metrics(&self, requested: usize, hits: usize) {
+        self.blob_count.increment(requested as u64);
+        self.blob_hits.increment(hits as u64);
+        self.blob_misses.increment(requested.saturating_sub(hits) as u64);
+    }
+}
+
 /// Metrics for engine API newPayload responses.
 #[derive(Metrics)]
 #[metrics(scope = "engine.rpc")]
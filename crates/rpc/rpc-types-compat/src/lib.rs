@@ -14,4 +14,5 @@ pub mod block;
 pub mod engine;
 pub mod log;
 pub mod proof;
+pub mod token_transfer;
 pub mod transaction;
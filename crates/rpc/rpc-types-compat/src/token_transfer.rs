@@ -0,0 +1,20 @@
+//! Compatibility functions for rpc `TokenTransfer` type.
+
+use reth_primitives::U64;
+
+/// Creates a new rpc token transfer from a primitive token transfer type from DB
+#[inline]
+pub fn from_primitive_token_transfer(
+    transfer: reth_primitives::TokenTransfer,
+) -> reth_rpc_types::TokenTransfer {
+    reth_rpc_types::TokenTransfer {
+        token: transfer.token,
+        from: transfer.from,
+        to: transfer.to,
+        value: transfer.value,
+        erc721: transfer.erc721,
+        block_number: U64::from(transfer.block_number),
+        transaction_hash: transfer.transaction_hash,
+        log_index: U64::from(transfer.log_index),
+    }
+}
@@ -2,8 +2,8 @@
 use alloy_rpc_types::request::{TransactionInput, TransactionRequest};
 
 use reth_primitives::{
-    BlockNumber, Transaction as PrimitiveTransaction, TransactionKind as PrimitiveTransactionKind,
-    TransactionSignedEcRecovered, TxType, B256,
+    Address, BlockNumber, Transaction as PrimitiveTransaction,
+    TransactionKind as PrimitiveTransactionKind, TransactionSignedEcRecovered, TxType, B256,
 };
 #[cfg(feature = "optimism")]
 use reth_rpc_types::optimism::OptimismTransactionFields;
@@ -180,17 +180,21 @@ pub fn from_primitive_access_list(
 
 /// Convert [TransactionSignedEcRecovered] to [TransactionRequest]
 pub fn transaction_to_call_request(tx: TransactionSignedEcRecovered) -> TransactionRequest {
-    let from = tx.signer();
-    let to = tx.transaction.to();
-    let gas = tx.transaction.gas_limit();
-    let value = tx.transaction.value();
-    let input = tx.transaction.input().clone();
-    let nonce = tx.transaction.nonce();
-    let chain_id = tx.transaction.chain_id();
-    let access_list = tx.transaction.access_list().cloned().map(from_primitive_access_list);
-    let max_fee_per_blob_gas = tx.transaction.max_fee_per_blob_gas();
-    let blob_versioned_hashes = tx.transaction.blob_versioned_hashes();
-    let tx_type = tx.transaction.tx_type();
+    transaction_to_request(&tx.transaction, tx.signer())
+}
+
+/// Convert an unsigned [PrimitiveTransaction] of the given sender to [TransactionRequest]
+pub fn transaction_to_request(tx: &PrimitiveTransaction, from: Address) -> TransactionRequest {
+    let to = tx.to();
+    let gas = tx.gas_limit();
+    let value = tx.value();
+    let input = tx.input().clone();
+    let nonce = tx.nonce();
+    let chain_id = tx.chain_id();
+    let access_list = tx.access_list().cloned().map(from_primitive_access_list);
+    let max_fee_per_blob_gas = tx.max_fee_per_blob_gas();
+    let blob_versioned_hashes = tx.blob_versioned_hashes();
+    let tx_type = tx.tx_type();
 
     // fees depending on the transaction type
     let (gas_price, max_fee_per_gas) = if tx.is_dynamic_fee() {
@@ -198,7 +202,7 @@ pub fn transaction_to_call_request(tx: TransactionSignedEcRecovered) -> Transact
     } else {
         (Some(tx.max_fee_per_gas()), None)
     };
-    let max_priority_fee_per_gas = tx.transaction.max_priority_fee_per_gas();
+    let max_priority_fee_per_gas = tx.max_priority_fee_per_gas();
 
     TransactionRequest {
         from: Some(from),
@@ -0,0 +1,79 @@
+use alloy_primitives::U256;
+use alloy_rpc_types::AccessListWithGasUsed;
+use serde::{Deserialize, Serialize};
+
+/// Options of `eth_createAccessList`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAccessListOptions {
+    /// Whether the gas used without the generated access list is estimated as well.
+    #[serde(default)]
+    pub include_gas_without_access_list: bool,
+    /// Whether the call is executed again with the generated access list until the list no
+    /// longer changes.
+    ///
+    /// The gas costs of a call change with its access list, which can change the code paths it
+    /// takes and thus the accessed addresses and storage keys.
+    #[serde(default)]
+    pub converge: bool,
+}
+
+/// The response of `eth_createAccessList`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAccessListResult {
+    /// The generated access list and the gas used with it.
+    #[serde(flatten)]
+    pub inner: AccessListWithGasUsed,
+    /// The gas used without the generated access list, if requested with
+    /// [CreateAccessListOptions::include_gas_without_access_list].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_used_without_access_list: Option<U256>,
+    /// Whether the access list converged, if requested with [CreateAccessListOptions::converge].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converged: Option<bool>,
+}
+
+impl From<AccessListWithGasUsed> for CreateAccessListResult {
+    fn from(inner: AccessListWithGasUsed) -> Self {
+        Self { inner, gas_used_without_access_list: None, converged: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types::AccessList;
+
+    #[test]
+    fn serde_create_access_list_result() {
+        let options = serde_json::from_str::<CreateAccessListOptions>(
+            r#"{"includeGasWithoutAccessList":true}"#,
+        )
+        .unwrap();
+        assert!(options.include_gas_without_access_list);
+        assert!(!options.converge);
+
+        let result: CreateAccessListResult = AccessListWithGasUsed {
+            access_list: AccessList::default(),
+            gas_used: U256::from(21000),
+        }
+        .into();
+        assert_eq!(
+            serde_json::to_string(&result).unwrap(),
+            r#"{"accessList":[],"gasUsed":"0x5208"}"#
+        );
+
+        let result = CreateAccessListResult {
+            gas_used_without_access_list: Some(U256::from(23000)),
+            converged: Some(true),
+            ..result
+        };
+        let serialized = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"accessList":[],"gasUsed":"0x5208","gasUsedWithoutAccessList":"0x59d8","converged":true}"#
+        );
+        assert_eq!(serde_json::from_str::<CreateAccessListResult>(&serialized).unwrap(), result);
+    }
+}
@@ -0,0 +1,15 @@
+use crate::Transaction;
+use serde::{Deserialize, Serialize};
+
+/// A page of the transactions sent from or to an address, returned by
+/// `reth_getTransactionsByAddress`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionsPage {
+    /// The transactions of the page, the most recent first.
+    pub transactions: Vec<Transaction>,
+    /// The requested page, starting at 0.
+    pub page: u64,
+    /// The next page, `None` if there are no more transactions.
+    pub next_page: Option<u64>,
+}
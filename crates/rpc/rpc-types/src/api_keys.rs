@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// The compute units used by an API key, returned by `admin_apiKeyUsage`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyUsage {
+    /// The API key.
+    pub key: String,
+    /// The compute units used in the current UTC day.
+    pub daily_used: u64,
+    /// The daily compute unit quota of the key, `None` if unlimited.
+    pub daily_limit: Option<u64>,
+    /// The compute units used in the current UTC month.
+    pub monthly_used: u64,
+    /// The monthly compute unit quota of the key, `None` if unlimited.
+    pub monthly_limit: Option<u64>,
+    /// The compute units used since the node was started.
+    pub total_used: u64,
+    /// The number of requests served since the node was started.
+    pub requests: u64,
+    /// The number of requests rejected because a quota was exceeded.
+    pub rejected_requests: u64,
+}
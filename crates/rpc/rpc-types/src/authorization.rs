@@ -0,0 +1,102 @@
+use alloy_primitives::{keccak256, Address, B256, U256, U64};
+use alloy_rlp::{Encodable, RlpEncodable};
+use serde::{Deserialize, Serialize};
+
+/// The magic byte that prefixes the RLP encoded authorization of EIP-7702 when it is signed.
+pub const EIP7702_AUTHORIZATION_MAGIC: u8 = 0x05;
+
+/// An EIP-7702 authorization, that delegates the code of the signing account to `address`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, RlpEncodable)]
+#[serde(rename_all = "camelCase")]
+pub struct Authorization {
+    /// The chain the authorization is valid on, `0` for all chains.
+    pub chain_id: U256,
+    /// The address of the code the account delegates to.
+    pub address: Address,
+    /// The nonce of the signing account the authorization is valid for.
+    pub nonce: U64,
+}
+
+impl Authorization {
+    /// Returns the hash that is signed, `keccak256(MAGIC || rlp([chain_id, address, nonce]))`.
+    pub fn signature_hash(&self) -> B256 {
+        let mut buf = Vec::with_capacity(self.length() + 1);
+        buf.push(EIP7702_AUTHORIZATION_MAGIC);
+        self.encode(&mut buf);
+        keccak256(buf)
+    }
+}
+
+/// The request of `eth_signAuthorization`, the fields that are not set are filled by the node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorizationRequest {
+    /// The chain the authorization is valid on, defaults to the chain of the node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<U256>,
+    /// The address of the code the account delegates to.
+    pub address: Address,
+    /// The nonce of the signing account, defaults to its next nonce including its pending
+    /// transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+}
+
+/// An EIP-7702 authorization together with the signature of the signing account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedAuthorization {
+    /// The signed authorization.
+    #[serde(flatten)]
+    pub authorization: Authorization,
+    /// The parity of the `y` coordinate of the signature, `0` or `1`.
+    pub y_parity: U64,
+    /// The `r` value of the signature.
+    pub r: U256,
+    /// The `s` value of the signature.
+    pub s: U256,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn authorization_signature_hash() {
+        let authorization =
+            Authorization { chain_id: U256::from(1), address: Address::ZERO, nonce: U64::from(7) };
+        let mut expected = vec![EIP7702_AUTHORIZATION_MAGIC];
+        // rlp([1, 0x00..00, 7])
+        expected.extend_from_slice(&[0xd7, 0x01, 0x94]);
+        expected.extend_from_slice(&[0; 20]);
+        expected.push(0x07);
+        assert_eq!(authorization.signature_hash(), keccak256(expected));
+    }
+
+    #[test]
+    fn serde_signed_authorization() {
+        let signed = SignedAuthorization {
+            authorization: Authorization {
+                chain_id: U256::from(1),
+                address: Address::with_last_byte(1),
+                nonce: U64::from(2),
+            },
+            y_parity: U64::from(1),
+            r: U256::from(3),
+            s: U256::from(4),
+        };
+        let serialized = serde_json::to_string(&signed).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"chainId":"0x1","address":"0x0000000000000000000000000000000000000001","nonce":"0x2","yParity":"0x1","r":"0x3","s":"0x4"}"#
+        );
+        assert_eq!(serde_json::from_str::<SignedAuthorization>(&serialized).unwrap(), signed);
+
+        let request = serde_json::from_str::<AuthorizationRequest>(
+            r#"{"address":"0x0000000000000000000000000000000000000001"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.chain_id, None);
+        assert_eq!(request.nonce, None);
+    }
+}
@@ -0,0 +1,67 @@
+use alloy_primitives::{Bytes, FixedBytes, B256};
+use alloy_rpc_types::BlobTransactionSidecar;
+use serde::{Deserialize, Serialize};
+
+/// The blob sidecar of a blob transaction of a block, returned by `eth_getBlobSidecars`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBlobSidecar {
+    /// The hash of the blob transaction.
+    pub transaction_hash: B256,
+    /// The blobs of the transaction with their KZG commitments and proofs.
+    #[serde(flatten)]
+    pub sidecar: BlobTransactionSidecar,
+}
+
+/// A blob with its KZG commitment and proof, returned by `reth_getBlobByVersionedHash`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobWithProof {
+    /// The hash of the blob transaction that carried the blob.
+    pub transaction_hash: B256,
+    /// The blob data.
+    pub blob: Bytes,
+    /// The KZG commitment of the blob.
+    pub commitment: FixedBytes<48>,
+    /// The KZG proof of the blob.
+    pub proof: FixedBytes<48>,
+}
+
+/// A blob with its KZG proof, returned by `engine_getBlobsV1`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobAndProofV1 {
+    /// The blob data.
+    pub blob: Bytes,
+    /// The KZG proof of the blob.
+    pub proof: FixedBytes<48>,
+}
+
+/// A blob with the KZG proofs of its cells, returned by `engine_getBlobsV2`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlobAndProofV2 {
+    /// The blob data.
+    pub blob: Bytes,
+    /// The KZG proofs of the cells of the extended blob.
+    pub proofs: Vec<FixedBytes<48>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_transaction_blob_sidecar() {
+        let sidecar = TransactionBlobSidecar {
+            transaction_hash: B256::with_last_byte(1),
+            sidecar: BlobTransactionSidecar::default(),
+        };
+        let serialized = serde_json::to_string(&sidecar).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"transactionHash":"0x0000000000000000000000000000000000000000000000000000000000000001","blobs":[],"commitments":[],"proofs":[]}"#
+        );
+        assert_eq!(serde_json::from_str::<TransactionBlobSidecar>(&serialized).unwrap(), sidecar);
+    }
+}
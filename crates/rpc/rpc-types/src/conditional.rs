@@ -0,0 +1,92 @@
+use alloy_primitives::{Address, B256, U64};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The conditions under which a transaction submitted with `eth_sendRawTransactionConditional`
+/// can be included in a block.
+///
+/// The block number and timestamp bounds are checked against the block the transaction is included
+/// in, the known accounts against the state of its parent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionConditional {
+    /// The expected storage of accounts the transaction depends on.
+    #[serde(default)]
+    pub known_accounts: HashMap<Address, KnownAccount>,
+    /// The minimum block number, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number_min: Option<U64>,
+    /// The maximum block number, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number_max: Option<U64>,
+    /// The minimum block timestamp, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_min: Option<U64>,
+    /// The maximum block timestamp, inclusive.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp_max: Option<U64>,
+}
+
+impl TransactionConditional {
+    /// Returns the cost of checking the known accounts: one per storage root and one per slot.
+    pub fn cost(&self) -> usize {
+        self.known_accounts.values().map(KnownAccount::cost).sum()
+    }
+}
+
+/// The expected storage of an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KnownAccount {
+    /// The expected root of the storage trie.
+    StorageRoot(B256),
+    /// The expected values of storage slots.
+    Slots(HashMap<B256, B256>),
+}
+
+impl KnownAccount {
+    /// Returns the cost of checking the storage.
+    pub fn cost(&self) -> usize {
+        match self {
+            KnownAccount::StorageRoot(_) => 1,
+            KnownAccount::Slots(slots) => slots.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    #[test]
+    fn deserialize_conditional() {
+        let s = r#"{
+            "knownAccounts": {
+                "0x000000000000000000000000000000000000dead": "0x56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421",
+                "0x000000000000000000000000000000000000beef": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                    "0x0000000000000000000000000000000000000000000000000000000000000003": "0x0000000000000000000000000000000000000000000000000000000000000004"
+                }
+            },
+            "blockNumberMax": "0x10",
+            "timestampMin": "0x64"
+        }"#;
+        let conditional: TransactionConditional = serde_json::from_str(s).unwrap();
+        assert_eq!(conditional.known_accounts.len(), 2);
+        assert!(matches!(
+            conditional.known_accounts[&address!("000000000000000000000000000000000000dead")],
+            KnownAccount::StorageRoot(_)
+        ));
+        assert_eq!(conditional.cost(), 3);
+        assert_eq!(conditional.block_number_min, None);
+        assert_eq!(conditional.block_number_max, Some(U64::from(16)));
+        assert_eq!(conditional.timestamp_min, Some(U64::from(100)));
+
+        let serialized = serde_json::to_string(&conditional).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TransactionConditional>(&serialized).unwrap(),
+            conditional
+        );
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// The result of a configuration reload, returned by `admin_reloadConfig`.
+///
+/// The keys are the dotted paths of the changed settings of the configuration file, e.g.
+/// `peers.connection_info.max_outbound`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigReload {
+    /// The changed settings that were applied to the running node.
+    pub applied: Vec<String>,
+    /// The changed settings that only take effect after a restart of the node.
+    pub requires_restart: Vec<String>,
+}
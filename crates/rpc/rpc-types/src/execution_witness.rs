@@ -0,0 +1,23 @@
+use alloy_primitives::{Bytes, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The data that is required to execute a block without access to the state, returned by
+/// `debug_executionWitness`.
+///
+/// The trie nodes are the nodes of the state and storage tries of the parent block that are on the
+/// paths to the accounts and storage slots accessed by the block.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionWitness {
+    /// The RLP encoded trie nodes, keyed by their hash.
+    pub state: BTreeMap<B256, Bytes>,
+    /// The bytecodes of the accessed contracts, keyed by their code hash.
+    pub codes: BTreeMap<B256, Bytes>,
+    /// The preimages of the hashed trie keys, i.e. the accessed addresses and storage slots,
+    /// keyed by their hash.
+    pub keys: BTreeMap<B256, Bytes>,
+    /// The RLP encoded headers of the parent block and of the ancestors whose hashes were
+    /// accessed, in ascending order.
+    pub headers: Vec<Bytes>,
+}
@@ -0,0 +1,32 @@
+use alloy_primitives::{U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// Fee estimate returned by `reth_estimateFees`, derived from hypothetical blocks built from the
+/// transaction pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEstimate {
+    /// The number of blocks within which the transaction should be included.
+    pub target_blocks: U64,
+    /// The requested confidence, in percent.
+    pub confidence: f64,
+    /// The projected base fee of the target block.
+    pub base_fee_per_gas: U256,
+    /// The suggested max fee per gas.
+    pub max_fee_per_gas: U256,
+    /// The suggested max priority fee per gas.
+    pub max_priority_fee_per_gas: U256,
+    /// The interval of the max fee per gas for the requested confidence.
+    pub max_fee_per_gas_interval: FeeInterval,
+    /// The interval of the max priority fee per gas for the requested confidence.
+    pub max_priority_fee_per_gas_interval: FeeInterval,
+}
+
+/// An interval of fees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeInterval {
+    /// The lower bound of the interval.
+    pub lower: U256,
+    /// The upper bound of the interval.
+    pub upper: U256,
+}
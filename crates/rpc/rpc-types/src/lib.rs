@@ -9,13 +9,30 @@
 )]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod access_list;
+mod address_transactions;
+mod api_keys;
+mod authorization;
 pub mod beacon;
+mod blob_sidecars;
+mod conditional;
+mod config_reload;
 mod eth;
+mod execution_witness;
+mod fee_estimate;
 mod mev;
 mod net;
 mod peer;
+mod pending_transactions_filter;
+mod preconfirmation;
+mod raw_transactions;
 pub mod relay;
 mod rpc;
+mod simulate;
+mod struct_logs;
+mod token_transfers;
+mod transaction_status;
+mod user_operation;
 
 // re-export for convenience
 pub use alloy_rpc_types::serde_helpers;
@@ -40,7 +57,24 @@ pub use eth::{
     transaction::{self, TransactionKind, TransactionRequest, TypedTransactionRequest},
 };
 
+pub use access_list::*;
+pub use address_transactions::*;
+pub use api_keys::*;
+pub use authorization::*;
+pub use blob_sidecars::*;
+pub use conditional::*;
+pub use config_reload::*;
+pub use execution_witness::*;
+pub use fee_estimate::*;
 pub use mev::*;
 pub use net::*;
 pub use peer::*;
+pub use pending_transactions_filter::*;
+pub use preconfirmation::*;
+pub use raw_transactions::*;
 pub use rpc::*;
+pub use simulate::*;
+pub use struct_logs::*;
+pub use token_transfers::*;
+pub use transaction_status::*;
+pub use user_operation::*;
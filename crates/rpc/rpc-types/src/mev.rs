@@ -631,6 +631,9 @@ pub struct EthCallBundle {
     /// the timestamp to use for this bundle simulation, in seconds since the unix epoch
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<u64>,
+    /// the coinbase to use for this bundle simulation, defaults to the coinbase of the state block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coinbase: Option<Address>,
 }
 
 /// Response for `eth_callBundle`
@@ -691,6 +694,10 @@ pub struct EthCallBundleTransactionResult {
     /// Contains the return data if the transaction reverted
     #[serde(skip_serializing_if = "Option::is_none")]
     pub revert: Option<Bytes>,
+    /// The reason the transaction failed, including the decoded revert reason if the transaction
+    /// reverted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 mod u256_numeric_string {
@@ -995,4 +1002,23 @@ mod tests {
             assert_eq!(strip_whitespaces(serialized), strip_whitespaces(serialized_expected));
         }
     }
+
+    #[test]
+    fn can_deserialize_call_bundle() {
+        let str = r#"{
+            "txs": ["0x02f86b0180843b9aca00852ecc889a0082520894c87037874aed04e51c29f582394217a0a2b89d808080c080a0a463985c616dd8ee17d7ef9112af4e6e06a27b071525b42182fe7b0b5c8b4925a00af5ca177ffef2ff28449292505d41be578bebb77110dfc09361d2fb56998260"],
+            "blockNumber": "0x10",
+            "stateBlockNumber": "latest",
+            "timestamp": 1700000000,
+            "coinbase": "0x0000000000000000000000000000000000000001"
+        }"#;
+        let bundle = serde_json::from_str::<EthCallBundle>(str).unwrap();
+        assert_eq!(bundle.block_number, U64::from(16));
+        assert_eq!(bundle.timestamp, Some(1700000000));
+        assert_eq!(bundle.coinbase, Some(Address::with_last_byte(1)));
+
+        let str = r#"{"txs": [], "blockNumber": "0x10", "stateBlockNumber": "latest"}"#;
+        let bundle = serde_json::from_str::<EthCallBundle>(str).unwrap();
+        assert_eq!(bundle.coinbase, None);
+    }
 }
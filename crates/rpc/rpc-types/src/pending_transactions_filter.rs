@@ -0,0 +1,163 @@
+use alloy_primitives::Address;
+use alloy_rpc_types::pubsub::{Params, SubscriptionKind};
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+use std::collections::HashSet;
+
+/// The fields that distinguish a [PendingTransactionsFilter] from the standard [Params].
+const PENDING_TRANSACTIONS_FILTER_FIELDS: [&str; 3] = ["fromAddress", "toAddress", "hashesOnly"];
+
+/// The kind of an `eth_subscribe` subscription, either one of the standard kinds or a kind added by
+/// reth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EthSubscriptionKind {
+    /// A standard subscription kind.
+    Standard(SubscriptionKind),
+    /// A subscription kind added by reth.
+    Extension(SubscriptionKindExtension),
+}
+
+impl From<SubscriptionKind> for EthSubscriptionKind {
+    fn from(kind: SubscriptionKind) -> Self {
+        Self::Standard(kind)
+    }
+}
+
+/// The subscription kinds that are not part of the standard `eth_subscribe` kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SubscriptionKindExtension {
+    /// Pending transactions that match a [PendingTransactionsFilter].
+    #[serde(rename = "alchemy_pendingTransactions")]
+    AlchemyPendingTransactions,
+}
+
+/// The params of an `eth_subscribe` subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum EthSubscriptionParams {
+    /// The filter of an `alchemy_pendingTransactions` subscription.
+    PendingTransactions(PendingTransactionsFilter),
+    /// The params of a standard subscription.
+    Standard(Params),
+}
+
+impl From<Params> for EthSubscriptionParams {
+    fn from(params: Params) -> Self {
+        Self::Standard(params)
+    }
+}
+
+impl<'de> Deserialize<'de> for EthSubscriptionParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let is_filter = value.as_object().map_or(false, |object| {
+            PENDING_TRANSACTIONS_FILTER_FIELDS.iter().any(|field| object.contains_key(*field))
+        });
+        if is_filter {
+            serde_json::from_value(value).map(Self::PendingTransactions).map_err(D::Error::custom)
+        } else {
+            serde_json::from_value(value).map(Self::Standard).map_err(D::Error::custom)
+        }
+    }
+}
+
+/// Filter of an `alchemy_pendingTransactions` subscription.
+///
+/// A transaction matches if it is sent from one of the `from_address` addresses or to one of the
+/// `to_address` addresses. If both are empty, all transactions match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTransactionsFilter {
+    /// The senders to match, a single address or a list of addresses.
+    #[serde(
+        default,
+        skip_serializing_if = "HashSet::is_empty",
+        deserialize_with = "deserialize_addresses"
+    )]
+    pub from_address: HashSet<Address>,
+    /// The recipients to match, a single address or a list of addresses.
+    #[serde(
+        default,
+        skip_serializing_if = "HashSet::is_empty",
+        deserialize_with = "deserialize_addresses"
+    )]
+    pub to_address: HashSet<Address>,
+    /// Whether only the transaction hashes are sent instead of the full transactions.
+    #[serde(default)]
+    pub hashes_only: bool,
+}
+
+impl PendingTransactionsFilter {
+    /// Returns `true` if a transaction with the sender and recipient matches the filter.
+    pub fn matches(&self, from: Address, to: Option<Address>) -> bool {
+        if self.from_address.is_empty() && self.to_address.is_empty() {
+            return true
+        }
+        self.from_address.contains(&from) || to.map_or(false, |to| self.to_address.contains(&to))
+    }
+}
+
+/// Deserializes a single address or a list of addresses.
+fn deserialize_addresses<'de, D>(deserializer: D) -> Result<HashSet<Address>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Addresses {
+        Single(Address),
+        Multiple(HashSet<Address>),
+    }
+
+    Ok(match Option::<Addresses>::deserialize(deserializer)? {
+        Some(Addresses::Single(address)) => HashSet::from([address]),
+        Some(Addresses::Multiple(addresses)) => addresses,
+        None => HashSet::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_subscription_kind() {
+        let kind = serde_json::from_str::<EthSubscriptionKind>(r#""newHeads""#).unwrap();
+        assert_eq!(kind, EthSubscriptionKind::Standard(SubscriptionKind::NewHeads));
+
+        let kind = serde_json::from_str::<EthSubscriptionKind>(r#""alchemy_pendingTransactions""#)
+            .unwrap();
+        assert_eq!(
+            kind,
+            EthSubscriptionKind::Extension(SubscriptionKindExtension::AlchemyPendingTransactions)
+        );
+        assert_eq!(serde_json::to_string(&kind).unwrap(), r#""alchemy_pendingTransactions""#);
+    }
+
+    #[test]
+    fn serde_subscription_params() {
+        let params = serde_json::from_str::<EthSubscriptionParams>(
+            r#"{"fromAddress":"0x0000000000000000000000000000000000000001","toAddress":["0x0000000000000000000000000000000000000002","0x0000000000000000000000000000000000000003"],"hashesOnly":true}"#,
+        )
+        .unwrap();
+        let EthSubscriptionParams::PendingTransactions(filter) = params else {
+            panic!("expected pending transactions filter")
+        };
+        assert!(filter.hashes_only);
+        assert!(filter.matches(Address::with_last_byte(1), None));
+        assert!(filter.matches(Address::ZERO, Some(Address::with_last_byte(3))));
+        assert!(!filter.matches(Address::with_last_byte(2), Some(Address::with_last_byte(1))));
+        assert!(PendingTransactionsFilter::default().matches(Address::ZERO, None));
+
+        let params = serde_json::from_str::<EthSubscriptionParams>("true").unwrap();
+        assert_eq!(params, EthSubscriptionParams::Standard(Params::Bool(true)));
+        let params = serde_json::from_str::<EthSubscriptionParams>(
+            r#"{"address":"0x0000000000000000000000000000000000000001"}"#,
+        )
+        .unwrap();
+        assert!(matches!(params, EthSubscriptionParams::Standard(Params::Logs(_))));
+    }
+}
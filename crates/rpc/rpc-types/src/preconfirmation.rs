@@ -0,0 +1,17 @@
+use alloy_primitives::B256;
+use alloy_rpc_types::AnyTransactionReceipt;
+use serde::{Deserialize, Serialize};
+
+/// A transaction that was selected into the payload built by the node, emitted by
+/// `reth_subscribePreconfirmations` before the block is canonical.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Preconfirmation {
+    /// The hash of the parent block of the payload.
+    pub parent_hash: B256,
+    /// The receipt of the transaction, simulated on top of the parent block.
+    ///
+    /// The block hash and number are the ones of the payload, which only becomes canonical if it
+    /// is proposed as built.
+    pub receipt: AnyTransactionReceipt,
+}
@@ -0,0 +1,44 @@
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+/// The result of submitting one of the transactions of `eth_sendRawTransactions`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RawTransactionResult {
+    /// The transaction was added to the pool.
+    Hash(B256),
+    /// The transaction was rejected.
+    Error(RawTransactionError),
+}
+
+/// The error of a rejected transaction of `eth_sendRawTransactions`, the same error
+/// `eth_sendRawTransaction` would have returned for the transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawTransactionError {
+    /// The JSON-RPC error code.
+    pub code: i32,
+    /// The error message.
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_raw_transaction_results() {
+        let results = vec![
+            RawTransactionResult::Hash(B256::with_last_byte(1)),
+            RawTransactionResult::Error(RawTransactionError {
+                code: -32000,
+                message: "nonce too low".to_string(),
+            }),
+        ];
+        let s = serde_json::to_string(&results).unwrap();
+        assert_eq!(
+            s,
+            r#"[{"hash":"0x0000000000000000000000000000000000000000000000000000000000000001"},{"error":{"code":-32000,"message":"nonce too low"}}]"#
+        );
+        assert_eq!(serde_json::from_str::<Vec<RawTransactionResult>>(&s).unwrap(), results);
+    }
+}
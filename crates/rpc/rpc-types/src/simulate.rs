@@ -0,0 +1,119 @@
+use alloy_primitives::{Bytes, U64};
+use alloy_rpc_types::{state::StateOverride, BlockOverrides, Header, Log, TransactionRequest};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of blocks that can be simulated with `eth_simulateV1`.
+pub const MAX_SIMULATE_BLOCKS: usize = 256;
+
+/// The error code of a simulated call that reverted.
+pub const SIMULATE_REVERT_CODE: i32 = 3;
+
+/// The error code of a simulated call that halted, e.g. ran out of gas.
+pub const SIMULATE_VM_ERROR_CODE: i32 = -32015;
+
+/// The payload of `eth_simulateV1`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatePayload {
+    /// The blocks to simulate on top of each other.
+    pub block_state_calls: Vec<SimulateBlock>,
+    /// Whether ETH transfers are returned as logs of the `0xeeee…eeee` address.
+    #[serde(default)]
+    pub trace_transfers: bool,
+    /// Whether the calls are validated like transactions, e.g. checking the nonce and base fee.
+    #[serde(default)]
+    pub validation: bool,
+}
+
+/// A block of calls that are simulated with `eth_simulateV1`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateBlock {
+    /// Overrides of the header fields of the simulated block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_overrides: Option<BlockOverrides>,
+    /// Overrides of the state that are applied before the calls of the block.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+    /// The calls of the block, executed in order.
+    #[serde(default)]
+    pub calls: Vec<TransactionRequest>,
+}
+
+/// A block simulated with `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedBlock {
+    /// The header of the simulated block.
+    #[serde(flatten)]
+    pub header: Header,
+    /// The results of the calls of the block.
+    pub calls: Vec<SimulateCallResult>,
+}
+
+/// The result of a call simulated with `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulateCallResult {
+    /// The data returned by the call.
+    pub return_data: Bytes,
+    /// The logs emitted by the call, including the ETH transfers if they are traced.
+    pub logs: Vec<Log>,
+    /// The gas used by the call.
+    pub gas_used: U64,
+    /// `1` if the call succeeded, `0` if it failed.
+    pub status: U64,
+    /// The error of a failed call.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<SimulateError>,
+}
+
+/// The error of a call simulated with `eth_simulateV1`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulateError {
+    /// The error code, see [SIMULATE_REVERT_CODE] and [SIMULATE_VM_ERROR_CODE].
+    pub code: i32,
+    /// The error message.
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, U256};
+
+    #[test]
+    fn serde_simulate_payload() {
+        let s = r#"{"blockStateCalls":[{"blockOverrides":{"number":"0x10"},"stateOverrides":{"0x0000000000000000000000000000000000000001":{"balance":"0x1"}},"calls":[{"from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x1"}]},{}],"traceTransfers":true}"#;
+        let payload = serde_json::from_str::<SimulatePayload>(s).unwrap();
+        assert!(payload.trace_transfers);
+        assert!(!payload.validation);
+        assert_eq!(payload.block_state_calls.len(), 2);
+
+        let block = &payload.block_state_calls[0];
+        assert_eq!(block.block_overrides.as_ref().unwrap().number, Some(U256::from(16)));
+        assert!(block.state_overrides.as_ref().unwrap().contains_key(&Address::with_last_byte(1)));
+        assert_eq!(block.calls[0].to, Some(Address::with_last_byte(2)));
+        assert_eq!(payload.block_state_calls[1], SimulateBlock::default());
+    }
+
+    #[test]
+    fn serde_simulate_call_result() {
+        let result = SimulateCallResult {
+            return_data: Bytes::new(),
+            logs: vec![],
+            gas_used: U64::from(21000),
+            status: U64::from(0),
+            error: Some(SimulateError {
+                code: SIMULATE_REVERT_CODE,
+                message: "execution reverted".to_string(),
+            }),
+        };
+        let serialized = serde_json::to_string(&result).unwrap();
+        assert_eq!(
+            serialized,
+            r#"{"returnData":"0x","logs":[],"gasUsed":"0x5208","status":"0x0","error":{"code":3,"message":"execution reverted"}}"#
+        );
+        assert_eq!(serde_json::from_str::<SimulateCallResult>(&serialized).unwrap(), result);
+    }
+}
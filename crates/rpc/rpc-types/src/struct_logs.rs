@@ -0,0 +1,29 @@
+use alloy_primitives::Bytes;
+use alloy_rpc_types_trace::geth::StructLog;
+use serde::{Deserialize, Serialize};
+
+/// A chunk of the structured logs of a transaction, emitted by `debug_subscribeTraceTransaction`.
+///
+/// The logs are emitted in execution order. The last chunk of a trace carries the
+/// [StructLogsResult] of the transaction.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogsChunk {
+    /// The structured logs of the executed opcodes.
+    pub struct_logs: Vec<StructLog>,
+    /// The result of the transaction, only set on the last chunk.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<StructLogsResult>,
+}
+
+/// The outcome of a transaction traced with the structlog tracer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogsResult {
+    /// Whether the transaction failed.
+    pub failed: bool,
+    /// The gas used by the transaction.
+    pub gas: u64,
+    /// The output of the transaction.
+    pub return_value: Bytes,
+}
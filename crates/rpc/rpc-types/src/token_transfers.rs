@@ -0,0 +1,37 @@
+use alloy_primitives::{Address, B256, U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// A transfer of an ERC-20 or ERC-721 token, decoded from a `Transfer` event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfer {
+    /// Address of the token contract.
+    pub token: Address,
+    /// Sender of the tokens.
+    pub from: Address,
+    /// Recipient of the tokens.
+    pub to: Address,
+    /// Transferred amount of an ERC-20 token or id of the transferred ERC-721 token.
+    pub value: U256,
+    /// Whether the token is an ERC-721 token.
+    pub erc721: bool,
+    /// Number of the block that contains the transfer.
+    pub block_number: U64,
+    /// Hash of the transaction that emitted the event.
+    pub transaction_hash: B256,
+    /// Index of the event in the block.
+    pub log_index: U64,
+}
+
+/// A page of the token transfers sent from or to an address, returned by
+/// `reth_getTokenTransfers`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenTransfersPage {
+    /// The transfers of the page, the most recent first.
+    pub transfers: Vec<TokenTransfer>,
+    /// The requested page, starting at 0.
+    pub page: u64,
+    /// The next page, `None` if there are no more transfers.
+    pub next_page: Option<u64>,
+}
@@ -0,0 +1,82 @@
+use alloy_primitives::{B256, U256, U64};
+use serde::{Deserialize, Serialize};
+
+/// The status of a transaction returned by `reth_getTransactionStatus`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum TransactionStatus {
+    /// The transaction is in the pool and can be included in the next block.
+    Pending {
+        /// The position of the transaction in the order in which the pool would include its
+        /// transactions in the next block.
+        #[serde(rename = "poolPosition")]
+        pool_position: U64,
+        /// The priority fee per gas the transaction pays at the base fee of the next block.
+        #[serde(rename = "effectiveTip")]
+        effective_tip: U256,
+    },
+    /// The transaction is in the pool but can't be included in the next block yet, e.g. because
+    /// of a nonce gap or a fee cap below the base fee.
+    Queued,
+    /// The transaction is included in a canonical block.
+    Mined {
+        /// The hash of the block.
+        #[serde(rename = "blockHash")]
+        block_hash: B256,
+        /// The number of the block.
+        #[serde(rename = "blockNumber")]
+        block_number: U64,
+    },
+    /// The transaction was replaced in the pool by another transaction with the same sender and
+    /// nonce.
+    Replaced {
+        /// The hash of the replacing transaction.
+        by: B256,
+    },
+    /// The transaction was dropped from the pool.
+    Dropped {
+        /// Why the transaction was dropped.
+        reason: DropReason,
+    },
+    /// The transaction is not known to the node, or was dropped too long ago.
+    Unknown,
+}
+
+/// Why a transaction was dropped from the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DropReason {
+    /// The transaction failed validation.
+    Invalid,
+    /// The pool exceeded its size limits and evicted the transaction.
+    PoolOverflow,
+    /// The transaction can no longer be included after its sender's account changed, e.g. its
+    /// nonce is too low.
+    Outdated,
+    /// The transaction was removed from the pool on request.
+    Removed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_transaction_status() {
+        let status = TransactionStatus::Pending {
+            pool_position: U64::from(3),
+            effective_tip: U256::from(1_000_000_000u64),
+        };
+        let s = serde_json::to_string(&status).unwrap();
+        assert_eq!(s, r#"{"status":"pending","poolPosition":"0x3","effectiveTip":"0x3b9aca00"}"#);
+        assert_eq!(serde_json::from_str::<TransactionStatus>(&s).unwrap(), status);
+
+        let status = TransactionStatus::Dropped { reason: DropReason::PoolOverflow };
+        let s = serde_json::to_string(&status).unwrap();
+        assert_eq!(s, r#"{"status":"dropped","reason":"poolOverflow"}"#);
+        assert_eq!(serde_json::from_str::<TransactionStatus>(&s).unwrap(), status);
+
+        let s = serde_json::to_string(&TransactionStatus::Unknown).unwrap();
+        assert_eq!(s, r#"{"status":"unknown"}"#);
+    }
+}
@@ -0,0 +1,136 @@
+//! Types of the [ERC-4337](https://eips.ethereum.org/EIPS/eip-4337) account abstraction
+//! `eth_` methods.
+
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use alloy_rpc_types::{AnyTransactionReceipt, Log};
+use serde::{Deserialize, Serialize};
+
+/// A user operation of the v0.6 EntryPoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    /// The account making the operation.
+    pub sender: Address,
+    /// The anti-replay nonce of the account.
+    pub nonce: U256,
+    /// The factory address and calldata that deploys the account, if it doesn't exist yet.
+    pub init_code: Bytes,
+    /// The calldata the account is called with.
+    pub call_data: Bytes,
+    /// The gas limit of the call of the account.
+    pub call_gas_limit: U256,
+    /// The gas limit of the validation of the account and the paymaster.
+    pub verification_gas_limit: U256,
+    /// The gas that compensates the bundler for the calldata and the overhead of the bundle.
+    pub pre_verification_gas: U256,
+    /// The maximum fee per gas, like EIP-1559.
+    pub max_fee_per_gas: U256,
+    /// The maximum priority fee per gas, like EIP-1559.
+    pub max_priority_fee_per_gas: U256,
+    /// The paymaster address and its data, empty if the account pays for itself.
+    pub paymaster_and_data: Bytes,
+    /// The signature that is verified by the account.
+    pub signature: Bytes,
+}
+
+impl UserOperation {
+    /// Returns the hash of the operation for the EntryPoint on the chain, which is the identifier
+    /// of the operation and what is signed by the account.
+    ///
+    /// This is `keccak256(abi.encode(keccak256(pack(op)), entryPoint, chainId))`, the signature is
+    /// not part of the hash.
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> B256 {
+        let packed = [
+            self.sender.into_word(),
+            self.nonce.into(),
+            keccak256(&self.init_code),
+            keccak256(&self.call_data),
+            self.call_gas_limit.into(),
+            self.verification_gas_limit.into(),
+            self.pre_verification_gas.into(),
+            self.max_fee_per_gas.into(),
+            self.max_priority_fee_per_gas.into(),
+            keccak256(&self.paymaster_and_data),
+        ];
+        let encoded =
+            [keccak256(concat(&packed)), entry_point.into_word(), U256::from(chain_id).into()];
+        keccak256(concat(&encoded))
+    }
+}
+
+/// Concatenates the ABI encoded words.
+fn concat(words: &[B256]) -> Vec<u8> {
+    words.iter().flat_map(|word| word.0).collect()
+}
+
+/// The result of `eth_estimateUserOperationGas`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGasEstimation {
+    /// The estimated pre-verification gas.
+    pub pre_verification_gas: U256,
+    /// The estimated gas limit of the validation.
+    pub verification_gas_limit: U256,
+    /// The estimated gas limit of the call of the account.
+    pub call_gas_limit: U256,
+}
+
+/// The result of `eth_getUserOperationReceipt`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+    /// The hash of the operation.
+    pub user_op_hash: B256,
+    /// The EntryPoint that executed the operation.
+    pub entry_point: Address,
+    /// The account of the operation.
+    pub sender: Address,
+    /// The nonce of the operation.
+    pub nonce: U256,
+    /// The paymaster that paid for the operation, zero if the account paid for itself.
+    pub paymaster: Address,
+    /// The gas cost that was paid for the operation.
+    pub actual_gas_cost: U256,
+    /// The gas that was used by the operation.
+    pub actual_gas_used: U256,
+    /// Whether the call of the account succeeded.
+    pub success: bool,
+    /// The revert data of the call of the account, if it reverted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<Bytes>,
+    /// The logs emitted during the execution of the operation.
+    pub logs: Vec<Log>,
+    /// The receipt of the bundle transaction that included the operation.
+    pub receipt: AnyTransactionReceipt,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user_operation_hash() {
+        let op = UserOperation {
+            sender: Address::with_last_byte(1),
+            nonce: U256::from(1),
+            call_data: Bytes::from_static(&[1, 2, 3]),
+            ..Default::default()
+        };
+        let entry_point = Address::with_last_byte(2);
+        let hash = op.hash(entry_point, 1);
+
+        // the signature is not part of the hash, the entry point and the chain are
+        let signed = UserOperation { signature: Bytes::from_static(&[4; 65]), ..op.clone() };
+        assert_eq!(signed.hash(entry_point, 1), hash);
+        assert_ne!(op.hash(Address::with_last_byte(3), 1), hash);
+        assert_ne!(op.hash(entry_point, 5), hash);
+    }
+
+    #[test]
+    fn serde_user_operation() {
+        let s = r#"{"sender":"0x0000000000000000000000000000000000000001","nonce":"0x1","initCode":"0x","callData":"0x010203","callGasLimit":"0x5208","verificationGasLimit":"0x186a0","preVerificationGas":"0xc350","maxFeePerGas":"0x3b9aca00","maxPriorityFeePerGas":"0x3b9aca00","paymasterAndData":"0x","signature":"0x"}"#;
+        let op: UserOperation = serde_json::from_str(s).unwrap();
+        assert_eq!(op.call_gas_limit, U256::from(21000));
+        assert_eq!(serde_json::to_string(&op).unwrap(), s);
+    }
+}
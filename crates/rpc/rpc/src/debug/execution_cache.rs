@@ -0,0 +1,141 @@
+//! Cache for the execution results of recently traced blocks.
+
+use reth_primitives::B256;
+use revm_primitives::{Account, Address, State, StorageSlot, U256};
+use schnellru::{LruMap, Unlimited};
+use std::{mem, sync::Arc};
+
+/// Default memory budget of the [ExecutionCache]: 256MB
+pub(crate) const DEFAULT_EXECUTION_CACHE_MAX_BYTES: usize = 256 * 1024 * 1024;
+
+/// State changes of the transactions of a block, in order of execution.
+///
+/// This can be a prefix of the block's transactions, if only some of them were executed.
+pub(crate) type BlockStateChanges = Arc<Vec<State>>;
+
+/// A memory-bounded LRU cache for the state changes of the transactions of recently traced blocks.
+///
+/// Tracing a transaction requires executing all prior transactions of its block first. With the
+/// cached state changes these prerequisites can be applied to the database directly, so that
+/// repeated tracing of the same block with different tracer configs only executes the traced
+/// transactions.
+pub(crate) struct ExecutionCache {
+    /// Cached state changes by block hash.
+    blocks: LruMap<B256, (BlockStateChanges, usize), Unlimited>,
+    /// Estimated memory usage of all cached state changes.
+    size: usize,
+    /// Maximum estimated memory usage.
+    max_bytes: usize,
+}
+
+impl ExecutionCache {
+    /// Creates a new cache with the given memory budget.
+    pub(crate) fn new(max_bytes: usize) -> Self {
+        Self { blocks: LruMap::new(Unlimited), size: 0, max_bytes }
+    }
+
+    /// Returns the cached state changes of the block and marks it as recently used.
+    pub(crate) fn get(&mut self, block_hash: &B256) -> Option<BlockStateChanges> {
+        self.blocks.get(block_hash).map(|(changes, _)| Arc::clone(changes))
+    }
+
+    /// Caches the state changes of the block.
+    ///
+    /// Existing state changes are only replaced if they cover fewer transactions. Least recently
+    /// used blocks are evicted until the state changes fit into the memory budget.
+    pub(crate) fn insert(&mut self, block_hash: B256, changes: Vec<State>) {
+        if self.blocks.peek(&block_hash).is_some_and(|(cached, _)| cached.len() >= changes.len()) {
+            return
+        }
+
+        let size = changes.iter().map(state_size).sum::<usize>();
+        if size > self.max_bytes {
+            return
+        }
+
+        if let Some((_, cached_size)) = self.blocks.remove(&block_hash) {
+            self.size -= cached_size;
+        }
+        while self.size + size > self.max_bytes {
+            let Some((_, (_, evicted_size))) = self.blocks.pop_oldest() else { break };
+            self.size -= evicted_size;
+        }
+
+        self.blocks.insert(block_hash, (Arc::new(changes), size));
+        self.size += size;
+    }
+
+    /// Returns the number of cached blocks.
+    pub(crate) fn len(&self) -> usize {
+        self.blocks.len()
+    }
+}
+
+impl std::fmt::Debug for ExecutionCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExecutionCache")
+            .field("blocks", &self.len())
+            .field("size", &self.size)
+            .field("max_bytes", &self.max_bytes)
+            .finish()
+    }
+}
+
+/// Estimates the memory usage of the state changes of a transaction.
+fn state_size(state: &State) -> usize {
+    state
+        .values()
+        .map(|account| {
+            mem::size_of::<(Address, Account)>() +
+                account.storage.len() * mem::size_of::<(U256, StorageSlot)>() +
+                account.info.code.as_ref().map_or(0, |code| code.len())
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(accounts: usize) -> Vec<State> {
+        vec![(0..accounts)
+            .map(|i| (Address::with_last_byte(i as u8), Account::default()))
+            .collect()]
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let block_size = changes(2).iter().map(state_size).sum::<usize>();
+        let mut cache = ExecutionCache::new(block_size * 2);
+
+        cache.insert(B256::with_last_byte(1), changes(2));
+        cache.insert(B256::with_last_byte(2), changes(2));
+        assert_eq!(cache.len(), 2);
+
+        // mark the first block as recently used
+        assert!(cache.get(&B256::with_last_byte(1)).is_some());
+
+        cache.insert(B256::with_last_byte(3), changes(2));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&B256::with_last_byte(1)).is_some());
+        assert!(cache.get(&B256::with_last_byte(2)).is_none());
+
+        // state changes that exceed the budget are not cached
+        cache.insert(B256::with_last_byte(4), changes(5));
+        assert!(cache.get(&B256::with_last_byte(4)).is_none());
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn keeps_longer_prefix() {
+        let mut cache = ExecutionCache::new(DEFAULT_EXECUTION_CACHE_MAX_BYTES);
+        let block_hash = B256::with_last_byte(1);
+
+        cache.insert(block_hash, vec![State::default(); 3]);
+        cache.insert(block_hash, vec![State::default(); 1]);
+        assert_eq!(cache.get(&block_hash).unwrap().len(), 3);
+
+        cache.insert(block_hash, vec![State::default(); 5]);
+        assert_eq!(cache.get(&block_hash).unwrap().len(), 5);
+    }
+}
@@ -1,4 +1,10 @@
 use crate::{
+    debug::{
+        execution_cache::{ExecutionCache, DEFAULT_EXECUTION_CACHE_MAX_BYTES},
+        spill::{TraceResults, TraceSpill, TRACE_SPILL_THRESHOLD_BYTES},
+        struct_logger::StructLogger,
+        witness::WitnessRecorder,
+    },
     eth::{
         error::{EthApiError, EthResult},
         revm_utils::{prepare_call_env, EvmOverrides},
@@ -9,34 +15,66 @@ use crate::{
 };
 use alloy_rlp::{Decodable, Encodable};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::{stream, StreamExt};
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink,
+};
+use parking_lot::Mutex;
 use reth_primitives::{
-    revm::env::tx_env_with_recovered, Address, Block, BlockId, BlockNumberOrTag, Bytes,
-    TransactionSignedEcRecovered, Withdrawals, B256, U256,
+    revm::env::tx_env_with_recovered, Address, Block, BlockId, BlockNumber, BlockNumberOrTag,
+    Bytes, TransactionSignedEcRecovered, Withdrawals, B256, U256,
 };
 use reth_provider::{
     BlockReaderIdExt, ChainSpecProvider, HeaderProvider, StateProviderBox, TransactionVariant,
+    TransactionsProvider,
+};
+use reth_revm::{
+    database::{StateProviderDatabase, SubState},
+    state_change::apply_beacon_root_contract_call,
 };
-use reth_revm::database::{StateProviderDatabase, SubState};
 use reth_rpc_api::DebugApiServer;
 use reth_rpc_types::{
     trace::geth::{
         BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType, GethDebugTracerType,
-        GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, NoopFrame, TraceResult,
+        GethDebugTracingCallOptions, GethDebugTracingOptions, GethDefaultTracingOptions, GethTrace,
+        NoopFrame, TraceResult,
     },
-    BlockError, Bundle, RichBlock, StateContext, TransactionRequest,
+    BlockError, Bundle, ExecutionWitness, RichBlock, StateContext, StructLogsChunk,
+    TransactionInfo, TransactionRequest,
+};
+use reth_tasks::{
+    pool::{is_current_task_cancelled, BlockingTaskGuard},
+    TaskSpawner,
 };
-use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
     db::CacheDB,
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
 };
+#[cfg(feature = "js-tracer")]
+use revm_inspectors::tracing::js::{JsInspector, TransactionContext};
 use revm_inspectors::tracing::{
-    js::{JsInspector, TransactionContext},
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig,
 };
-use std::sync::Arc;
-use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use std::{ops::RangeInclusive, sync::Arc};
+use tokio::sync::{mpsc, AcquireError, OwnedSemaphorePermit};
+use tracing::debug;
+
+mod execution_cache;
+mod rewind;
+mod spill;
+mod struct_logger;
+mod witness;
+
+pub use rewind::DebugRewindApi;
+
+/// The default number of blocks that are traced concurrently for a `debug_traceChain`
+/// subscription.
+pub const DEFAULT_TRACE_CHAIN_CONCURRENCY: usize = 4;
+
+/// The number of structlog chunks that are buffered for a subscriber of
+/// `debug_subscribeTraceTransaction` before tracing waits for the subscriber.
+const STRUCT_LOGS_CHANNEL_CAPACITY: usize = 4;
 
 /// `debug` API implementation.
 ///
@@ -49,8 +87,21 @@ pub struct DebugApi<Provider, Eth> {
 
 impl<Provider, Eth> DebugApi<Provider, Eth> {
     /// Create a new instance of the [DebugApi]
-    pub fn new(provider: Provider, eth: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(DebugApiInner { provider, eth_api: eth, blocking_task_guard });
+    pub fn new(
+        provider: Provider,
+        eth: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        task_spawner: Box<dyn TaskSpawner>,
+        trace_chain_concurrency: usize,
+    ) -> Self {
+        let inner = Arc::new(DebugApiInner {
+            provider,
+            eth_api: eth,
+            blocking_task_guard,
+            task_spawner,
+            trace_chain_concurrency,
+            execution_cache: Mutex::new(ExecutionCache::new(DEFAULT_EXECUTION_CACHE_MAX_BYTES)),
+        });
         Self { inner }
     }
 
@@ -73,28 +124,55 @@ where
     }
 
     /// Trace the entire block asynchronously
+    ///
+    /// If the hash of the traced block is given, the state changes of its transactions are cached,
+    /// so they don't need to be executed again when transactions of the block are traced later.
     async fn trace_block_with(
         &self,
         at: BlockId,
+        traced_block_hash: Option<B256>,
         transactions: Vec<TransactionSignedEcRecovered>,
         cfg: CfgEnvWithHandlerCfg,
         block_env: BlockEnv,
         opts: GethDebugTracingOptions,
     ) -> EthResult<Vec<TraceResult>> {
+        let results = Vec::with_capacity(transactions.len());
+        self.trace_block_into(at, traced_block_hash, transactions, cfg, block_env, opts, results)
+            .await
+    }
+
+    /// Same as [Self::trace_block_with], but appends the trace results to the given collection.
+    #[allow(clippy::too_many_arguments)]
+    async fn trace_block_into<R: TraceResults>(
+        &self,
+        at: BlockId,
+        traced_block_hash: Option<B256>,
+        transactions: Vec<TransactionSignedEcRecovered>,
+        cfg: CfgEnvWithHandlerCfg,
+        block_env: BlockEnv,
+        opts: GethDebugTracingOptions,
+        mut results: R,
+    ) -> EthResult<R> {
         if transactions.is_empty() {
             // nothing to trace
-            return Ok(Vec::new())
+            return Ok(results)
         }
 
+        // the noop tracer doesn't execute the transactions, so there are no state changes to cache
+        let traced_block_hash = traced_block_hash.filter(|_| !is_noop_tracer(&opts));
+
         // replay all transactions of the block
         let this = self.clone();
         self.eth_api()
             .spawn_with_state_at_block(at, move |state| {
                 let block_hash = at.as_block_hash();
-                let mut results = Vec::with_capacity(transactions.len());
+                let mut block_state_changes = Vec::with_capacity(transactions.len());
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
                 let mut transactions = transactions.into_iter().enumerate().peekable();
                 while let Some((index, tx)) = transactions.next() {
+                    if is_current_task_cancelled() {
+                        return Err(EthApiError::Cancelled)
+                    }
                     let tx_hash = tx.hash;
                     let tx = tx_env_with_recovered(&tx);
                     let env = EnvWithHandlerCfg {
@@ -106,28 +184,40 @@ where
                             opts.clone(),
                             env,
                             &mut db,
-                            Some(TransactionContext {
+                            Some(TransactionInfo {
+                                hash: Some(tx_hash),
+                                index: Some(index as u64),
                                 block_hash,
-                                tx_hash: Some(tx_hash),
-                                tx_index: Some(index),
+                                block_number: Some(block_env.number.to()),
+                                base_fee: Some(block_env.basefee.saturating_to()),
                             }),
                         )
                         .map_err(|err| {
-                            results.push(TraceResult::Error {
+                            let _ = results.push_result(TraceResult::Error {
                                 error: err.to_string(),
                                 tx_hash: Some(tx_hash),
                             });
                             err
                         })?;
 
-                    results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+                    results.push_result(TraceResult::Success { result, tx_hash: Some(tx_hash) })?;
                     if transactions.peek().is_some() {
+                        if traced_block_hash.is_some() {
+                            block_state_changes.push(state_changes.clone());
+                        }
                         // need to apply the state changes of this transaction before executing the
                         // next transaction
                         db.commit(state_changes)
                     }
                 }
 
+                if let Some(traced_block_hash) = traced_block_hash {
+                    this.inner
+                        .execution_cache
+                        .lock()
+                        .insert(traced_block_hash, block_state_changes);
+                }
+
                 Ok(results)
             })
             .await
@@ -172,7 +262,86 @@ where
                     .collect::<EthResult<Vec<_>>>()?
             };
 
-        self.trace_block_with(parent.into(), transactions, cfg, block_env, opts).await
+        self.trace_block_with(parent.into(), None, transactions, cfg, block_env, opts).await
+    }
+
+    /// Resolves the blocks traced by `debug_traceChain`, the start block is excluded.
+    fn trace_chain_range(
+        &self,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+    ) -> EthResult<RangeInclusive<BlockNumber>> {
+        let start = self
+            .inner
+            .provider
+            .convert_block_number(start_exclusive)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let end = self
+            .inner
+            .provider
+            .convert_block_number(end_inclusive)?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        if end <= start {
+            return Err(EthApiError::InvalidParams(format!(
+                "end block {end} must be greater than start block {start}"
+            )))
+        }
+        Ok(start + 1..=end)
+    }
+
+    /// Replays the blocks of the range and sends the traces of each block to the subscriber, in
+    /// order.
+    ///
+    /// Up to the configured number of blocks are traced concurrently. Blocks are only traced ahead
+    /// of the subscriber within that limit, so a slow subscriber slows down the tracing. Tracing
+    /// stops at the first block that fails or once the subscription is dropped.
+    async fn pipe_chain_traces(
+        &self,
+        sink: SubscriptionSink,
+        blocks: RangeInclusive<BlockNumber>,
+        opts: GethDebugTracingOptions,
+    ) {
+        let mut traces = stream::iter(blocks)
+            .map(|number| {
+                let this = self.clone();
+                let opts = opts.clone();
+                async move {
+                    let _permit = this.acquire_trace_permit().await;
+                    let hash = this
+                        .inner
+                        .provider
+                        .block_hash(number)?
+                        .ok_or(EthApiError::UnknownBlockNumber)?;
+                    let traces = this.debug_trace_block(hash.into(), opts).await?;
+                    Ok::<_, EthApiError>(BlockTraceResult {
+                        block: U256::from(number),
+                        hash,
+                        traces,
+                    })
+                }
+            })
+            .buffered(self.inner.trace_chain_concurrency.max(1));
+
+        loop {
+            let result = tokio::select! {
+                _ = sink.closed() => break,
+                item = traces.next() => match item {
+                    Some(result) => result,
+                    None => break,
+                },
+            };
+            let block_traces = match result {
+                Ok(block_traces) => block_traces,
+                Err(err) => {
+                    debug!(target: "rpc::debug", %err, "Failed to trace chain");
+                    break
+                }
+            };
+            let Ok(msg) = SubscriptionMessage::from_json(&block_traces) else { break };
+            if sink.send(msg).await.is_err() {
+                break
+            }
+        }
     }
 
     /// Replays a block and returns the trace of each transaction.
@@ -181,6 +350,16 @@ where
         block_id: BlockId,
         opts: GethDebugTracingOptions,
     ) -> EthResult<Vec<TraceResult>> {
+        self.debug_trace_block_into(block_id, opts, Vec::new()).await
+    }
+
+    /// Same as [Self::debug_trace_block], but appends the trace results to the given collection.
+    async fn debug_trace_block_into<R: TraceResults>(
+        &self,
+        block_id: BlockId,
+        opts: GethDebugTracingOptions,
+        results: R,
+    ) -> EthResult<R> {
         let block_hash = self
             .inner
             .provider
@@ -197,16 +376,121 @@ where
         // its parent block's state
         let state_at = block.parent_hash;
 
-        self.trace_block_with(
+        self.trace_block_into(
             state_at.into(),
+            Some(block_hash),
             block.into_transactions_ecrecovered().collect(),
             cfg,
             block_env,
             opts,
+            results,
         )
         .await
     }
 
+    /// Re-executes the block on top of its parent's state and returns the witness of all accessed
+    /// state, which is sufficient to execute the block statelessly.
+    ///
+    /// Besides the state that is accessed by the transactions, this includes the EIP-4788 beacon
+    /// root contract and the recipients of the block rewards and withdrawals.
+    pub async fn debug_execution_witness(
+        &self,
+        block_id: BlockNumberOrTag,
+    ) -> EthResult<ExecutionWitness> {
+        let block_hash = self
+            .inner
+            .provider
+            .block_hash_for_id(block_id.into())?
+            .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+
+        let ((cfg, block_env, _), block) = futures::try_join!(
+            self.inner.eth_api.evm_env_at(block_hash.into()),
+            self.inner.eth_api.block_by_id_with_senders(block_hash.into()),
+        )?;
+        let block = block.ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+
+        let chain_spec = self.inner.provider.chain_spec();
+        let parent_hash = block.parent_hash;
+        let block_number = block.number;
+        let parent_beacon_block_root = block.parent_beacon_block_root;
+        let reward_recipients =
+            std::iter::once(block.beneficiary)
+                .chain(block.ommers.iter().map(|ommer| ommer.beneficiary))
+                .chain(block.withdrawals.iter().flat_map(|withdrawals| {
+                    withdrawals.iter().map(|withdrawal| withdrawal.address)
+                }))
+                .collect::<Vec<_>>();
+        let transactions = block.into_transactions_ecrecovered().collect::<Vec<_>>();
+
+        let this = self.clone();
+        let (mut witness, mut block_numbers) = self
+            .eth_api()
+            .spawn_with_state_at_block(parent_hash.into(), move |state| {
+                let recorder = WitnessRecorder::new(state);
+                let mut db = CacheDB::new(StateProviderDatabase::new(&recorder));
+
+                let mut evm_pre_block = revm::Evm::builder()
+                    .with_db(&mut db)
+                    .with_env_with_handler_cfg(EnvWithHandlerCfg::new_with_cfg_env(
+                        cfg.clone(),
+                        block_env.clone(),
+                        Default::default(),
+                    ))
+                    .build();
+                apply_beacon_root_contract_call(
+                    &chain_spec,
+                    block_env.timestamp.to::<u64>(),
+                    block_number,
+                    parent_beacon_block_root,
+                    &mut evm_pre_block,
+                )
+                .map_err(|err| EthApiError::Internal(err.into()))?;
+                drop(evm_pre_block);
+
+                for tx in transactions {
+                    if is_current_task_cancelled() {
+                        return Err(EthApiError::Cancelled)
+                    }
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            tx_env_with_recovered(&tx),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().transact(&mut db, env)?;
+                    db.commit(res.state);
+                }
+                drop(db);
+
+                for address in reward_recipients {
+                    recorder.touch_account(address);
+                }
+
+                let block_numbers = recorder.accessed_block_numbers();
+                Ok((recorder.into_witness()?, block_numbers))
+            })
+            .await?;
+
+        // the parent header is always required to verify the pre state root
+        block_numbers.push(block_number.saturating_sub(1));
+        block_numbers.sort_unstable();
+        block_numbers.dedup();
+        for number in block_numbers {
+            let header = self
+                .inner
+                .provider
+                .header_by_number(number)?
+                .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+            let mut rlp = Vec::new();
+            header.encode(&mut rlp);
+            witness.headers.push(rlp.into());
+        }
+
+        Ok(witness)
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -215,6 +499,46 @@ where
         tx_hash: B256,
         opts: GethDebugTracingOptions,
     ) -> EthResult<GethTrace> {
+        self.spawn_with_transaction(tx_hash, move |this, env, db, tx_info| {
+            this.trace_transaction(opts, env, db, Some(tx_info)).map(|(trace, _)| trace)
+        })
+        .await
+    }
+
+    /// Traces the transaction with the structlog tracer and sends the logs in chunks to the given
+    /// channel while the transaction is replayed.
+    ///
+    /// The last chunk carries the result of the transaction. Tracing stops early if the receiver
+    /// is dropped.
+    pub async fn debug_trace_transaction_chunked(
+        &self,
+        tx_hash: B256,
+        config: GethDefaultTracingOptions,
+        sender: mpsc::Sender<StructLogsChunk>,
+    ) -> EthResult<()> {
+        self.spawn_with_transaction(tx_hash, move |this, env, db, _| {
+            let mut inspector = StructLogger::streaming(&config, sender);
+            let (res, _) = this.eth_api().inspect(db, env, &mut inspector)?;
+            inspector.finish(&res.result);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Replays the transactions of the block of the given transaction that precede it, and then
+    /// invokes the closure with the environment of the transaction and the state it executes on.
+    async fn spawn_with_transaction<F, R>(&self, tx_hash: B256, f: F) -> EthResult<R>
+    where
+        F: FnOnce(
+                Self,
+                EnvWithHandlerCfg,
+                &mut SubState<StateProviderBox>,
+                TransactionInfo,
+            ) -> EthResult<R>
+            + Send
+            + 'static,
+        R: Send + 'static,
+    {
         let (transaction, block) = match self.inner.eth_api.transaction_and_block(tx_hash).await? {
             None => return Err(EthApiError::TransactionNotFound),
             Some(res) => res,
@@ -225,7 +549,9 @@ where
         // block the transaction is included in
         let state_at: BlockId = block.parent_hash.into();
         let block_hash = block.hash();
-        let block_txs = block.into_transactions_ecrecovered();
+        let block_number = block.number;
+        let base_fee = block.base_fee_per_gas;
+        let block_txs = block.into_transactions_ecrecovered().collect::<Vec<_>>();
 
         let this = self.clone();
         self.inner
@@ -236,11 +562,12 @@ where
 
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
                 // replay all transactions prior to the targeted transaction
-                let index = this.eth_api().replay_transactions_until(
+                let index = this.replay_block_transactions_until(
                     &mut db,
+                    block_hash,
                     cfg.clone(),
                     block_env.clone(),
-                    block_txs,
+                    &block_txs,
                     tx.hash,
                 )?;
 
@@ -249,17 +576,14 @@ where
                     handler_cfg: cfg.handler_cfg,
                 };
 
-                this.trace_transaction(
-                    opts,
-                    env,
-                    &mut db,
-                    Some(TransactionContext {
-                        block_hash: Some(block_hash),
-                        tx_index: Some(index),
-                        tx_hash: Some(tx.hash),
-                    }),
-                )
-                .map(|(trace, _)| trace)
+                let tx_info = TransactionInfo {
+                    hash: Some(tx.hash),
+                    index: Some(index as u64),
+                    block_hash: Some(block_hash),
+                    block_number: Some(block_number),
+                    base_fee: base_fee.map(u128::from),
+                };
+                f(this, env, &mut db, tx_info)
             })
             .await
     }
@@ -368,6 +692,11 @@ where
                         return Ok(frame)
                     }
                 },
+                #[cfg(not(feature = "js-tracer"))]
+                GethDebugTracerType::JsTracer(_) => {
+                    Err(EthApiError::Unsupported(JS_TRACER_DISABLED))
+                }
+                #[cfg(feature = "js-tracer")]
                 GethDebugTracerType::JsTracer(code) => {
                     let config = tracer_config.into_json();
 
@@ -393,9 +722,7 @@ where
         }
 
         // default structlog tracer
-        let inspector_config = TracingInspectorConfig::from_geth_config(&config);
-
-        let mut inspector = TracingInspector::new(inspector_config);
+        let mut inspector = StructLogger::new(&config);
 
         let (res, inspector) = self
             .inner
@@ -405,11 +732,8 @@ where
                 Ok((res, inspector))
             })
             .await?;
-        let gas_used = res.result.gas_used();
-        let return_value = res.result.into_output().unwrap_or_default();
-        let frame = inspector.into_geth_builder().geth_traces(gas_used, return_value, config);
 
-        Ok(frame.into())
+        Ok(inspector.into_frame(&res.result).into())
     }
 
     /// The debug_traceCallMany method lets you run an `eth_callMany` within the context of the
@@ -523,21 +847,79 @@ where
             .await
     }
 
+    /// Applies the state changes of all transactions of the block prior to the targeted
+    /// transaction to the database and returns the index of the targeted transaction.
+    ///
+    /// State changes of the block that are cached are applied without executing the transactions
+    /// again. The remaining transactions are replayed and their state changes are cached.
+    ///
+    /// Caution: this is blocking and should be performed on a blocking task.
+    fn replay_block_transactions_until(
+        &self,
+        db: &mut SubState<StateProviderBox>,
+        block_hash: B256,
+        cfg: CfgEnvWithHandlerCfg,
+        block_env: BlockEnv,
+        transactions: &[TransactionSignedEcRecovered],
+        target_tx_hash: B256,
+    ) -> EthResult<usize> {
+        let index = transactions
+            .iter()
+            .position(|tx| tx.hash == target_tx_hash)
+            .unwrap_or(transactions.len());
+
+        let cached = self.inner.execution_cache.lock().get(&block_hash).unwrap_or_default();
+        let cached_len = cached.len().min(index);
+        for state_changes in &cached[..cached_len] {
+            db.commit(state_changes.clone());
+        }
+
+        if cached_len == index {
+            return Ok(index)
+        }
+
+        let mut block_state_changes = cached[..cached_len].to_vec();
+        for tx in &transactions[cached_len..index] {
+            let env = EnvWithHandlerCfg {
+                env: Env::boxed(cfg.cfg_env.clone(), block_env.clone(), tx_env_with_recovered(tx)),
+                handler_cfg: cfg.handler_cfg,
+            };
+            let (res, _) = self.eth_api().transact(&mut *db, env)?;
+            block_state_changes.push(res.state.clone());
+            db.commit(res.state);
+        }
+        self.inner.execution_cache.lock().insert(block_hash, block_state_changes);
+
+        Ok(index)
+    }
+
     /// Executes the configured transaction with the environment on the given database.
     ///
     /// Returns the trace frame and the state that got updated after executing the transaction.
     ///
     /// Note: this does not apply any state overrides if they're configured in the `opts`.
     ///
+    /// The transaction info is only exposed to JavaScript tracers.
+    ///
     /// Caution: this is blocking and should be performed on a blocking task.
     fn trace_transaction(
         &self,
         opts: GethDebugTracingOptions,
         env: EnvWithHandlerCfg,
         db: &mut SubState<StateProviderBox>,
-        transaction_context: Option<TransactionContext>,
+        tx_info: Option<TransactionInfo>,
     ) -> EthResult<(GethTrace, revm_primitives::State)> {
         let GethDebugTracingOptions { config, tracer, tracer_config, .. } = opts;
+        #[cfg(feature = "js-tracer")]
+        let transaction_context = tx_info
+            .map(|info| TransactionContext {
+                block_hash: info.block_hash,
+                tx_index: info.index.map(|index| index as usize),
+                tx_hash: info.hash,
+            })
+            .unwrap_or_default();
+        #[cfg(not(feature = "js-tracer"))]
+        let _ = tx_info;
 
         if let Some(tracer) = tracer {
             return match tracer {
@@ -599,13 +981,15 @@ where
                         return Ok((frame.into(), res.state))
                     }
                 },
+                #[cfg(not(feature = "js-tracer"))]
+                GethDebugTracerType::JsTracer(_) => {
+                    Err(EthApiError::Unsupported(JS_TRACER_DISABLED))
+                }
+                #[cfg(feature = "js-tracer")]
                 GethDebugTracerType::JsTracer(code) => {
                     let config = tracer_config.into_json();
-                    let mut inspector = JsInspector::with_transaction_context(
-                        code,
-                        config,
-                        transaction_context.unwrap_or_default(),
-                    )?;
+                    let mut inspector =
+                        JsInspector::with_transaction_context(code, config, transaction_context)?;
                     let (res, env, db) =
                         self.eth_api().inspect_and_return_db(db, env, &mut inspector)?;
 
@@ -617,14 +1001,10 @@ where
         }
 
         // default structlog tracer
-        let inspector_config = TracingInspectorConfig::from_geth_config(&config);
-
-        let mut inspector = TracingInspector::new(inspector_config);
+        let mut inspector = StructLogger::new(&config);
 
         let (res, _) = self.eth_api().inspect(db, env, &mut inspector)?;
-        let gas_used = res.result.gas_used();
-        let return_value = res.result.into_output().unwrap_or_default();
-        let frame = inspector.into_geth_builder().geth_traces(gas_used, return_value, config);
+        let frame = inspector.into_frame(&res.result);
 
         Ok((frame.into(), res.state))
     }
@@ -638,18 +1018,7 @@ where
 {
     /// Handler for `debug_getRawHeader`
     async fn raw_header(&self, block_id: BlockId) -> RpcResult<Bytes> {
-        let header = match block_id {
-            BlockId::Hash(hash) => self.inner.provider.header(&hash.into()).to_rpc_result()?,
-            BlockId::Number(number_or_tag) => {
-                let number = self
-                    .inner
-                    .provider
-                    .convert_block_number(number_or_tag)
-                    .to_rpc_result()?
-                    .ok_or_else(|| internal_rpc_err("Pending block not supported".to_string()))?;
-                self.inner.provider.header_by_number(number).to_rpc_result()?
-            }
-        };
+        let header = self.inner.provider.header_by_id(block_id).to_rpc_result()?;
 
         let mut res = Vec::new();
         if let Some(header) = header {
@@ -665,8 +1034,9 @@ where
 
         let mut res = Vec::new();
         if let Some(mut block) = block {
-            // In RPC withdrawals are always present
-            if block.withdrawals.is_none() {
+            // the withdrawals are part of the encoding if the header commits to them, even if the
+            // block has none, but must not be added to pre-shanghai blocks
+            if block.header.withdrawals_root.is_some() && block.withdrawals.is_none() {
                 block.withdrawals = Some(Withdrawals::default());
             }
             block.encode(&mut res);
@@ -685,7 +1055,8 @@ where
     }
 
     /// Handler for `debug_getRawTransactions`
-    /// Returns the bytes of the transaction for the given hash.
+    ///
+    /// Returns the EIP-2718 binary-encoded transactions of the given block.
     async fn raw_transactions(&self, block_id: BlockId) -> RpcResult<Vec<Bytes>> {
         let block = self
             .inner
@@ -717,10 +1088,25 @@ where
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> SubscriptionResult {
+        let blocks = match self.trace_chain_range(start_exclusive, end_inclusive) {
+            Ok(blocks) => blocks,
+            Err(err) => {
+                pending.reject(err).await;
+                return Ok(())
+            }
+        };
+        let sink = pending.accept().await?;
+
+        let this = self.clone();
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            this.pipe_chain_traces(sink, blocks, opts.unwrap_or_default()).await;
+        }));
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -753,6 +1139,53 @@ where
         Ok(DebugApi::debug_trace_block(self, block.into(), opts.unwrap_or_default()).await?)
     }
 
+    /// Handler for `debug_subscribeTraceBlock`
+    async fn debug_subscribe_trace_block(
+        &self,
+        pending: PendingSubscriptionSink,
+        block_id: BlockId,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> SubscriptionResult {
+        let permit = self.acquire_trace_permit().await;
+        let spill = TraceSpill::new(TRACE_SPILL_THRESHOLD_BYTES);
+        let results =
+            match self.debug_trace_block_into(block_id, opts.unwrap_or_default(), spill).await {
+                Ok(results) => results,
+                Err(err) => {
+                    pending.reject(err).await;
+                    return Ok(())
+                }
+            };
+        drop(permit);
+        debug!(
+            target: "rpc::debug",
+            ?block_id,
+            traces = results.len(),
+            spilled = results.is_spilled(),
+            "Traced block for subscription"
+        );
+
+        let sink = pending.accept().await?;
+        // the spilled results are read from disk, which is blocking
+        self.inner.task_spawner.spawn_blocking(Box::pin(async move {
+            let results = match results.into_results() {
+                Ok(results) => results,
+                Err(err) => {
+                    debug!(target: "rpc::debug", %err, "Failed to read spilled traces");
+                    return
+                }
+            };
+            for result in results {
+                let Ok(json) = result else { break };
+                let Ok(msg) = SubscriptionMessage::from_json(&json) else { break };
+                if sink.send(msg).await.is_err() {
+                    break
+                }
+            }
+        }));
+        Ok(())
+    }
+
     /// Handler for `debug_traceTransaction`
     async fn debug_trace_transaction(
         &self,
@@ -763,6 +1196,42 @@ where
         Ok(DebugApi::debug_trace_transaction(self, tx_hash, opts.unwrap_or_default()).await?)
     }
 
+    /// Handler for `debug_subscribeTraceTransaction`
+    async fn debug_subscribe_trace_transaction(
+        &self,
+        pending: PendingSubscriptionSink,
+        tx_hash: B256,
+        opts: Option<GethDefaultTracingOptions>,
+    ) -> SubscriptionResult {
+        if self.inner.provider.transaction_id(tx_hash)?.is_none() {
+            pending.reject(EthApiError::TransactionNotFound).await;
+            return Ok(())
+        }
+        let sink = pending.accept().await?;
+
+        let (sender, mut receiver) = mpsc::channel(STRUCT_LOGS_CHANNEL_CAPACITY);
+        let this = self.clone();
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            let _permit = this.acquire_trace_permit().await;
+            let trace =
+                this.debug_trace_transaction_chunked(tx_hash, opts.unwrap_or_default(), sender);
+            let pipe = async move {
+                // dropping the receiver stops the tracing if the subscription is gone
+                while let Some(chunk) = receiver.recv().await {
+                    let Ok(msg) = SubscriptionMessage::from_json(&chunk) else { break };
+                    if sink.send(msg).await.is_err() {
+                        break
+                    }
+                }
+            };
+            let (res, _) = futures::join!(trace, pipe);
+            if let Err(err) = res {
+                debug!(target: "rpc::debug", %tx_hash, %err, "Failed to stream structlog trace");
+            }
+        }));
+        Ok(())
+    }
+
     /// Handler for `debug_traceCall`
     async fn debug_trace_call(
         &self,
@@ -785,6 +1254,15 @@ where
         Ok(DebugApi::debug_trace_call_many(self, bundles, state_context, opts).await?)
     }
 
+    /// Handler for `debug_executionWitness`
+    async fn debug_execution_witness(
+        &self,
+        block: BlockNumberOrTag,
+    ) -> RpcResult<ExecutionWitness> {
+        let _permit = self.acquire_trace_permit().await;
+        Ok(DebugApi::debug_execution_witness(self, block).await?)
+    }
+
     async fn debug_backtrace_at(&self, _location: &str) -> RpcResult<()> {
         Ok(())
     }
@@ -909,10 +1387,6 @@ where
         Ok(())
     }
 
-    async fn debug_set_head(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
-    }
-
     async fn debug_set_mutex_profile_fraction(&self, _rate: i32) -> RpcResult<()> {
         Ok(())
     }
@@ -997,6 +1471,18 @@ where
     }
 }
 
+/// The error message of JavaScript tracers if the `js-tracer` feature is disabled.
+#[cfg(not(feature = "js-tracer"))]
+const JS_TRACER_DISABLED: &str = "JavaScript tracers are not supported by this node";
+
+/// Returns `true` if the noop tracer is configured, which doesn't execute transactions.
+fn is_noop_tracer(opts: &GethDebugTracingOptions) -> bool {
+    matches!(
+        opts.tracer,
+        Some(GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::NoopTracer))
+    )
+}
+
 impl<Provider, Eth> std::fmt::Debug for DebugApi<Provider, Eth> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1016,4 +1502,76 @@ struct DebugApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to blocking calls
     blocking_task_guard: BlockingTaskGuard,
+    /// The type that can spawn tasks, used to stream traces to subscribers
+    task_spawner: Box<dyn TaskSpawner>,
+    /// The number of blocks that are traced concurrently for a `debug_traceChain` subscription
+    trace_chain_concurrency: usize,
+    /// State changes of the transactions of recently traced blocks
+    execution_cache: Mutex<ExecutionCache>,
+}
+
+#[cfg(all(test, not(feature = "js-tracer")))]
+mod tests {
+    use super::*;
+    use crate::{
+        eth::{
+            cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache,
+            FeeHistoryCacheConfig,
+        },
+        EthApi,
+    };
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::constants::ETHEREUM_BLOCK_GAS_LIMIT;
+    use reth_provider::{test_utils::MockEthProvider, StateProviderFactory};
+    use reth_tasks::{pool::BlockingTaskPool, TokioTaskExecutor};
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    type TestEthApi = EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>;
+
+    fn debug_api(provider: MockEthProvider) -> DebugApi<MockEthProvider, TestEthApi> {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config.clone());
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider.clone(), Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+            None,
+        );
+        DebugApi::new(
+            provider,
+            eth_api,
+            BlockingTaskGuard::new(1),
+            Box::<TokioTaskExecutor>::default(),
+            DEFAULT_TRACE_CHAIN_CONCURRENCY,
+        )
+    }
+
+    #[tokio::test]
+    async fn js_tracer_is_unsupported_without_feature() {
+        let provider = MockEthProvider::default();
+        let api = debug_api(provider.clone());
+        let mut db = CacheDB::new(StateProviderDatabase::new(provider.latest().unwrap()));
+        let opts = GethDebugTracingOptions {
+            tracer: Some(GethDebugTracerType::JsTracer("{}".to_string())),
+            ..Default::default()
+        };
+
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            CfgEnvWithHandlerCfg::new(Default::default(), Default::default()),
+            BlockEnv::default(),
+            Default::default(),
+        );
+
+        let err = api.trace_transaction(opts, env, &mut db, None).unwrap_err();
+        assert_eq!(err.to_string(), EthApiError::Unsupported(JS_TRACER_DISABLED).to_string());
+    }
 }
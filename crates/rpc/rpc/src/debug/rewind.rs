@@ -0,0 +1,73 @@
+//! `debug` methods that rewind the canonical chain.
+
+use crate::result::{internal_rpc_err, invalid_params_rpc_err, ToRpcResult};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use reth_beacon_consensus::{BeaconConsensusEngineHandle, BeaconRewindError};
+use reth_engine_primitives::EngineTypes;
+use reth_primitives::{BlockId, BlockNumber, B256, U64};
+use reth_provider::BlockIdReader;
+use reth_rpc_api::DebugRewindApiServer;
+use tracing::info;
+
+/// `debug` API implementation of the methods that rewind the canonical chain.
+///
+/// The rewind is performed by the consensus engine, which unwinds all stages to the target block.
+/// This is only meant for private and test networks.
+pub struct DebugRewindApi<Provider, EngineT: EngineTypes> {
+    /// The provider used to resolve block ids.
+    provider: Provider,
+    /// Handle to the consensus engine that performs the rewind.
+    beacon_consensus: BeaconConsensusEngineHandle<EngineT>,
+}
+
+impl<Provider, EngineT: EngineTypes> DebugRewindApi<Provider, EngineT> {
+    /// Creates a new instance of `DebugRewindApi`.
+    pub fn new(provider: Provider, beacon_consensus: BeaconConsensusEngineHandle<EngineT>) -> Self {
+        Self { provider, beacon_consensus }
+    }
+}
+
+impl<Provider, EngineT> DebugRewindApi<Provider, EngineT>
+where
+    EngineT: EngineTypes + 'static,
+{
+    /// Rewinds the canonical chain to the given block and returns the hash of the new head.
+    async fn rewind(&self, target: BlockNumber) -> RpcResult<B256> {
+        info!(target: "rpc::debug", %target, "Rewinding canonical chain");
+        let head = self.beacon_consensus.rewind(target).await.map_err(|err| match err {
+            BeaconRewindError::InvalidTarget { .. } => invalid_params_rpc_err(err.to_string()),
+            err => internal_rpc_err(err.to_string()),
+        })?;
+        Ok(head.hash)
+    }
+}
+
+#[async_trait]
+impl<Provider, EngineT> DebugRewindApiServer for DebugRewindApi<Provider, EngineT>
+where
+    Provider: BlockIdReader + 'static,
+    EngineT: EngineTypes + 'static,
+{
+    /// Handler for `debug_setHead`
+    async fn debug_set_head(&self, number: U64) -> RpcResult<()> {
+        self.rewind(number.to()).await?;
+        Ok(())
+    }
+
+    /// Handler for `debug_rewindToBlock`
+    async fn debug_rewind_to_block(&self, block_id: BlockId) -> RpcResult<B256> {
+        let target = self
+            .provider
+            .block_number_for_id(block_id)
+            .to_rpc_result()?
+            .ok_or_else(|| invalid_params_rpc_err("unknown block"))?;
+        self.rewind(target).await
+    }
+}
+
+impl<Provider, EngineT: EngineTypes> std::fmt::Debug for DebugRewindApi<Provider, EngineT> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugRewindApi").finish_non_exhaustive()
+    }
+}
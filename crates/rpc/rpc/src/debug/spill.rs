@@ -0,0 +1,172 @@
+//! Buffering of large trace results on disk.
+
+use crate::eth::error::{EthApiError, EthResult};
+use reth_interfaces::RethError;
+use reth_rpc_types::trace::geth::TraceResult;
+use serde::Serialize;
+use serde_json::value::RawValue;
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+};
+
+/// The serialized size of the trace results of a request after which they're written to disk.
+pub(crate) const TRACE_SPILL_THRESHOLD_BYTES: usize = 64 * 1024 * 1024;
+
+/// A buffer of serialized trace results that spills to an anonymous temporary file once the
+/// results exceed a size threshold.
+///
+/// Serialized results are a lot smaller than the traces they were created from, and once spilled,
+/// memory usage is bounded regardless of how many slots a traced block touches. The file is
+/// removed by the OS once the buffer is dropped.
+#[derive(Debug)]
+pub(crate) struct TraceSpill {
+    /// The size after which the results are written to disk.
+    threshold: usize,
+    /// The serialized results that were not written to disk.
+    buffered: Vec<String>,
+    /// The size of the buffered results.
+    buffered_size: usize,
+    /// The file the results were written to, one result per line.
+    file: Option<BufWriter<File>>,
+    /// The number of results.
+    len: usize,
+}
+
+impl TraceSpill {
+    /// Creates a new buffer that spills to disk once the serialized results exceed the threshold.
+    pub(crate) fn new(threshold: usize) -> Self {
+        Self { threshold, buffered: Vec::new(), buffered_size: 0, file: None, len: 0 }
+    }
+
+    /// Returns the number of results.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the results were written to disk.
+    pub(crate) fn is_spilled(&self) -> bool {
+        self.file.is_some()
+    }
+
+    /// Serializes and appends the result.
+    pub(crate) fn push<T: Serialize>(&mut self, result: &T) -> io::Result<()> {
+        // serialized JSON never contains unescaped newlines, so it can be stored line by line
+        let json = serde_json::to_string(result)?;
+        self.len += 1;
+
+        if let Some(file) = &mut self.file {
+            return write_line(file, &json)
+        }
+
+        self.buffered_size += json.len();
+        self.buffered.push(json);
+        if self.buffered_size > self.threshold {
+            let mut file = BufWriter::new(tempfile::tempfile()?);
+            for json in self.buffered.drain(..) {
+                write_line(&mut file, &json)?;
+            }
+            self.buffered_size = 0;
+            self.file = Some(file);
+        }
+        Ok(())
+    }
+
+    /// Consumes the buffer and returns the results in the order they were appended.
+    pub(crate) fn into_results(self) -> io::Result<SpilledResults> {
+        let results = match self.file {
+            Some(file) => {
+                let mut file = file.into_inner().map_err(|err| err.into_error())?;
+                file.seek(SeekFrom::Start(0))?;
+                SpilledResults::File(BufReader::new(file).lines())
+            }
+            None => SpilledResults::Memory(self.buffered.into_iter()),
+        };
+        Ok(results)
+    }
+}
+
+/// A collection the trace results of a block are appended to.
+pub(crate) trait TraceResults: Send + 'static {
+    /// Appends the trace result of the next transaction.
+    fn push_result(&mut self, result: TraceResult) -> EthResult<()>;
+}
+
+impl TraceResults for Vec<TraceResult> {
+    fn push_result(&mut self, result: TraceResult) -> EthResult<()> {
+        self.push(result);
+        Ok(())
+    }
+}
+
+impl TraceResults for TraceSpill {
+    fn push_result(&mut self, result: TraceResult) -> EthResult<()> {
+        self.push(&result).map_err(|err| {
+            EthApiError::Internal(RethError::Custom(format!("failed to buffer trace: {err}")))
+        })
+    }
+}
+
+/// Writes the serialized result as a line.
+fn write_line(file: &mut BufWriter<File>, json: &str) -> io::Result<()> {
+    file.write_all(json.as_bytes())?;
+    file.write_all(b"\n")
+}
+
+/// The serialized results of a [TraceSpill], read back from memory or disk.
+#[derive(Debug)]
+pub(crate) enum SpilledResults {
+    /// The results were kept in memory.
+    Memory(std::vec::IntoIter<String>),
+    /// The results are read from the temporary file.
+    File(io::Lines<BufReader<File>>),
+}
+
+impl Iterator for SpilledResults {
+    type Item = io::Result<Box<RawValue>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let json = match self {
+            Self::Memory(results) => results.next()?,
+            Self::File(lines) => match lines.next()? {
+                Ok(json) => json,
+                Err(err) => return Some(Err(err)),
+            },
+        };
+        Some(RawValue::from_string(json).map_err(Into::into))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(spill: TraceSpill) -> Vec<String> {
+        spill.into_results().unwrap().map(|json| json.unwrap().get().to_string()).collect()
+    }
+
+    #[test]
+    fn keeps_small_results_in_memory() {
+        let mut spill = TraceSpill::new(1024);
+        spill.push(&vec![1, 2]).unwrap();
+        spill.push(&"a\nb").unwrap();
+        assert!(!spill.is_spilled());
+        assert_eq!(spill.len(), 2);
+        assert_eq!(collect(spill), vec!["[1,2]".to_string(), r#""a\nb""#.to_string()]);
+    }
+
+    #[test]
+    fn spills_large_results_to_disk() {
+        let mut spill = TraceSpill::new(8);
+        spill.push(&"first").unwrap();
+        assert!(!spill.is_spilled());
+        spill.push(&"second").unwrap();
+        assert!(spill.is_spilled());
+        spill.push(&"third").unwrap();
+        assert_eq!(spill.len(), 3);
+        assert_eq!(
+            collect(spill),
+            vec![r#""first""#.to_string(), r#""second""#.to_string(), r#""third""#.to_string()]
+        );
+    }
+}
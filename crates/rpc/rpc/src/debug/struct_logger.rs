@@ -0,0 +1,240 @@
+//! A native structlog tracer, the default tracer of the `debug` trace endpoints.
+
+use reth_primitives::{hex, Address, B256};
+use reth_rpc_types::{
+    trace::geth::{DefaultFrame, GethDefaultTracingOptions, StructLog},
+    StructLogsChunk, StructLogsResult,
+};
+use revm::{
+    interpreter::{opcode, Interpreter, OpCode},
+    primitives::ExecutionResult,
+    Database, EvmContext, Inspector,
+};
+use std::collections::{BTreeMap, HashMap};
+use tokio::sync::mpsc;
+
+/// The number of structured logs that are sent at once if the logs are streamed.
+pub(crate) const STRUCT_LOGS_CHUNK_SIZE: usize = 1_000;
+
+/// An inspector that records the structured logs of the executed opcodes, see
+/// <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers#struct-opcode-logger>.
+///
+/// Only the step data that's enabled by the [GethDefaultTracingOptions] is captured, and recording
+/// stops once the configured `limit` of logs is reached.
+///
+/// If the logger streams to a channel, the logs are sent in chunks of [STRUCT_LOGS_CHUNK_SIZE]
+/// while the transaction executes, so the whole trace is never held in memory. Recording stops if
+/// the receiver is dropped.
+#[derive(Debug)]
+pub(crate) struct StructLogger {
+    /// Whether the memory is captured.
+    memory: bool,
+    /// Whether the stack is captured.
+    stack: bool,
+    /// Whether the accessed storage is captured.
+    storage: bool,
+    /// Whether the return data of the last call is captured.
+    return_data: bool,
+    /// The maximum number of logs to record, unlimited if `0`.
+    limit: u64,
+    /// The number of logs recorded so far, including the ones already sent.
+    recorded: u64,
+    /// The logs that were not sent yet.
+    logs: Vec<StructLog>,
+    /// The storage slots that were read or written so far, per contract.
+    accessed_storage: HashMap<Address, BTreeMap<B256, B256>>,
+    /// The storage slot and the written value of the current `SLOAD` or `SSTORE` step.
+    ///
+    /// The value of an `SLOAD` is only known once the step is executed.
+    pending_slot: Option<(Address, B256, Option<B256>)>,
+    /// The remaining gas before the current step.
+    gas_before_step: u64,
+    /// Whether the current step is recorded.
+    recording_step: bool,
+    /// The channel the logs are streamed to.
+    sender: Option<mpsc::Sender<StructLogsChunk>>,
+}
+
+impl StructLogger {
+    /// Creates a new logger that records the logs in memory.
+    pub(crate) fn new(config: &GethDefaultTracingOptions) -> Self {
+        Self {
+            memory: config.is_memory_enabled(),
+            stack: config.is_stack_enabled(),
+            storage: config.is_storage_enabled(),
+            return_data: config.is_return_data_enabled(),
+            limit: config.limit.unwrap_or_default(),
+            recorded: 0,
+            logs: Vec::new(),
+            accessed_storage: HashMap::new(),
+            pending_slot: None,
+            gas_before_step: 0,
+            recording_step: false,
+            sender: None,
+        }
+    }
+
+    /// Creates a new logger that streams the logs to the given channel.
+    ///
+    /// Caution: sending blocks the current thread if the channel is full, this must be used on a
+    /// blocking task.
+    pub(crate) fn streaming(
+        config: &GethDefaultTracingOptions,
+        sender: mpsc::Sender<StructLogsChunk>,
+    ) -> Self {
+        Self { sender: Some(sender), ..Self::new(config) }
+    }
+
+    /// Returns `true` if no more logs are recorded.
+    fn is_done(&self) -> bool {
+        (self.limit != 0 && self.recorded >= self.limit) ||
+            self.sender.as_ref().is_some_and(|sender| sender.is_closed())
+    }
+
+    /// Sends the recorded logs if a full chunk is ready.
+    fn send_chunk(&mut self) {
+        let Some(sender) = &self.sender else { return };
+        if self.logs.len() < STRUCT_LOGS_CHUNK_SIZE {
+            return
+        }
+        let chunk = StructLogsChunk { struct_logs: std::mem::take(&mut self.logs), result: None };
+        if sender.blocking_send(chunk).is_err() {
+            // the receiver is gone, nothing is recorded anymore
+            self.sender = None;
+            self.limit = self.recorded;
+        }
+    }
+
+    /// Consumes the logger and returns the trace of the executed transaction.
+    pub(crate) fn into_frame(self, result: &ExecutionResult) -> DefaultFrame {
+        DefaultFrame {
+            failed: !result.is_success(),
+            gas: result.gas_used(),
+            return_value: result.output().cloned().unwrap_or_default(),
+            struct_logs: self.logs,
+        }
+    }
+
+    /// Consumes the logger and sends the remaining logs together with the result of the
+    /// transaction.
+    ///
+    /// Caution: this blocks the current thread if the channel is full.
+    pub(crate) fn finish(self, result: &ExecutionResult) {
+        let Some(sender) = self.sender else { return };
+        let chunk = StructLogsChunk {
+            struct_logs: self.logs,
+            result: Some(StructLogsResult {
+                failed: !result.is_success(),
+                gas: result.gas_used(),
+                return_value: result.output().cloned().unwrap_or_default(),
+            }),
+        };
+        let _ = sender.blocking_send(chunk);
+    }
+}
+
+impl<DB> Inspector<DB> for StructLogger
+where
+    DB: Database,
+{
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        self.recording_step = !self.is_done();
+        if !self.recording_step {
+            return
+        }
+
+        let op = interp.current_opcode();
+        self.gas_before_step = interp.gas.remaining();
+        self.pending_slot = None;
+        if self.storage && (op == opcode::SLOAD || op == opcode::SSTORE) {
+            if let Ok(slot) = interp.stack.peek(0) {
+                // the written value is below the slot, the loaded value is only known after the
+                // step
+                let value = (op == opcode::SSTORE)
+                    .then(|| interp.stack.peek(1).ok())
+                    .flatten()
+                    .map(B256::from);
+                self.pending_slot = Some((interp.contract.address, B256::from(slot), value));
+            }
+        }
+
+        self.logs.push(StructLog {
+            pc: interp.program_counter() as u64,
+            op: OpCode::new(op)
+                .map(|op| op.as_str().to_string())
+                .unwrap_or_else(|| format!("opcode {op:#x} not defined")),
+            gas: self.gas_before_step,
+            gas_cost: 0,
+            depth: context.journaled_state.depth() as u64,
+            error: None,
+            stack: self.stack.then(|| interp.stack.data().clone()),
+            return_data: self.return_data.then(|| interp.return_data_buffer.clone()),
+            memory: self.memory.then(|| memory_words(interp.shared_memory.context_memory())),
+            memory_size: Some(interp.shared_memory.len() as u64),
+            storage: None,
+            refund_counter: (interp.gas.refunded() > 0).then(|| interp.gas.refunded() as u64),
+        });
+        self.recorded += 1;
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if !std::mem::take(&mut self.recording_step) {
+            return
+        }
+        let Some(log) = self.logs.last_mut() else { return };
+
+        log.gas_cost = self.gas_before_step.saturating_sub(interp.gas.remaining());
+        if interp.instruction_result.is_error() {
+            log.error = Some(format!("{:?}", interp.instruction_result));
+        }
+
+        if let Some((address, slot, value)) = self.pending_slot.take() {
+            // the loaded value is on top of the stack once the `SLOAD` was executed
+            let value = value.or_else(|| interp.stack.peek(0).ok().map(B256::from));
+            if let Some(value) = value {
+                let storage = self.accessed_storage.entry(address).or_default();
+                storage.insert(slot, value);
+                log.storage = Some(storage.clone());
+            }
+        }
+
+        self.send_chunk();
+    }
+}
+
+/// Formats the memory as hex encoded 32 byte words, like geth.
+fn memory_words(memory: &[u8]) -> Vec<String> {
+    memory.chunks(32).map(hex::encode).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_memory_words() {
+        let mut memory = vec![0u8; 64];
+        memory[31] = 1;
+        memory[63] = 0xff;
+        assert_eq!(
+            memory_words(&memory),
+            vec![
+                "0000000000000000000000000000000000000000000000000000000000000001".to_string(),
+                "00000000000000000000000000000000000000000000000000000000000000ff".to_string(),
+            ]
+        );
+        assert!(memory_words(&[]).is_empty());
+    }
+
+    #[test]
+    fn respects_limit() {
+        let config = GethDefaultTracingOptions { limit: Some(2), ..Default::default() };
+        let mut logger = StructLogger::new(&config);
+        assert!(!logger.is_done());
+        logger.recorded = 2;
+        assert!(logger.is_done());
+
+        let logger = StructLogger::new(&GethDefaultTracingOptions::default());
+        assert!(!logger.is_done());
+    }
+}
@@ -0,0 +1,174 @@
+//! Recording of the state that is accessed while a block is executed.
+
+use parking_lot::Mutex;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{
+    keccak256, trie::AccountProof, Account, Address, BlockNumber, Bytecode, Bytes, StorageKey,
+    StorageValue, B256,
+};
+use reth_provider::{AccountReader, BlockHashReader, StateProvider, StateRootProvider};
+use reth_rpc_types::ExecutionWitness;
+use reth_trie::updates::TrieUpdates;
+use revm::db::BundleState;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The state that was read through a [WitnessRecorder].
+#[derive(Debug, Default)]
+struct AccessedState {
+    /// The accessed accounts and their accessed storage slots.
+    storage: BTreeMap<Address, BTreeSet<B256>>,
+    /// The loaded bytecodes, keyed by code hash.
+    codes: BTreeMap<B256, Bytes>,
+    /// The numbers of the blocks whose hashes were loaded.
+    block_numbers: BTreeSet<BlockNumber>,
+}
+
+/// A [StateProvider] that records all accounts, storage slots, bytecodes and block hashes that are
+/// read from the wrapped state provider.
+///
+/// This is used to build the [ExecutionWitness] of a block, by executing the block on top of the
+/// recorder and generating the proofs of everything that was accessed afterwards.
+#[derive(Debug)]
+pub(crate) struct WitnessRecorder<SP> {
+    /// The state the block is executed on.
+    state: SP,
+    /// The state that was accessed so far.
+    accessed: Mutex<AccessedState>,
+}
+
+impl<SP: StateProvider> WitnessRecorder<SP> {
+    /// Creates a new recorder on top of the given state.
+    pub(crate) fn new(state: SP) -> Self {
+        Self { state, accessed: Mutex::new(AccessedState::default()) }
+    }
+
+    /// Records the account as accessed, e.g. because it receives a balance increment outside of a
+    /// transaction.
+    pub(crate) fn touch_account(&self, address: Address) {
+        self.accessed.lock().storage.entry(address).or_default();
+    }
+
+    /// Returns the numbers of the blocks whose hashes were loaded, in ascending order.
+    pub(crate) fn accessed_block_numbers(&self) -> Vec<BlockNumber> {
+        self.accessed.lock().block_numbers.iter().copied().collect()
+    }
+
+    /// Consumes the recorder and returns the witness of the accessed state, without headers.
+    ///
+    /// This generates the proofs of all accessed accounts and slots in the wrapped state, which can
+    /// be expensive if the wrapped state is an old historical state.
+    pub(crate) fn into_witness(self) -> ProviderResult<ExecutionWitness> {
+        let accessed = self.accessed.into_inner();
+        let mut witness = ExecutionWitness { codes: accessed.codes, ..Default::default() };
+
+        for (address, slots) in accessed.storage {
+            let slots = slots.into_iter().collect::<Vec<_>>();
+            let proof = self.state.proof(address, &slots)?;
+
+            witness.keys.insert(keccak256(address), Bytes::copy_from_slice(address.as_slice()));
+            for node in proof.proof {
+                witness.state.insert(keccak256(&node), node);
+            }
+            for storage_proof in proof.storage_proofs {
+                witness.keys.insert(
+                    keccak256(storage_proof.key),
+                    Bytes::copy_from_slice(storage_proof.key.as_slice()),
+                );
+                for node in storage_proof.proof {
+                    witness.state.insert(keccak256(&node), node);
+                }
+            }
+        }
+
+        Ok(witness)
+    }
+}
+
+impl<SP: StateProvider> BlockHashReader for WitnessRecorder<SP> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.accessed.lock().block_numbers.insert(number);
+        self.state.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.accessed.lock().block_numbers.extend(start..end);
+        self.state.canonical_hashes_range(start, end)
+    }
+}
+
+impl<SP: StateProvider> AccountReader for WitnessRecorder<SP> {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        self.touch_account(address);
+        self.state.basic_account(address)
+    }
+}
+
+impl<SP: StateProvider> StateRootProvider for WitnessRecorder<SP> {
+    fn state_root(&self, bundle_state: &BundleState) -> ProviderResult<B256> {
+        self.state.state_root(bundle_state)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        bundle_state: &BundleState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state.state_root_with_updates(bundle_state)
+    }
+}
+
+impl<SP: StateProvider> StateProvider for WitnessRecorder<SP> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        self.accessed.lock().storage.entry(account).or_default().insert(storage_key);
+        self.state.storage(account, storage_key)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        let bytecode = self.state.bytecode_by_hash(code_hash)?;
+        if let Some(bytecode) = &bytecode {
+            self.accessed.lock().codes.insert(code_hash, bytecode.original_bytes());
+        }
+        Ok(bytecode)
+    }
+
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.state.proof(address, keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn records_accessed_state() {
+        let address = Address::random();
+        let slot = B256::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::from(1)).extend_storage([(slot, U256::from(2))]),
+        );
+
+        let recorder = WitnessRecorder::new(provider);
+        assert!(recorder.basic_account(address).unwrap().is_some());
+        assert_eq!(recorder.storage(address, slot).unwrap(), Some(U256::from(2)));
+        let _ = recorder.block_hash(7).unwrap();
+        recorder.touch_account(Address::ZERO);
+
+        assert_eq!(recorder.accessed_block_numbers(), vec![7]);
+        let accessed = recorder.accessed.into_inner();
+        assert_eq!(accessed.storage.len(), 2);
+        assert_eq!(accessed.storage[&address], BTreeSet::from([slot]));
+        assert!(accessed.storage[&Address::ZERO].is_empty());
+    }
+}
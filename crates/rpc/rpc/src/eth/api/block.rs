@@ -11,7 +11,7 @@ use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{BlockId, TransactionMeta};
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
-use reth_rpc_types::{AnyTransactionReceipt, Header, Index, RichBlock};
+use reth_rpc_types::{AnyTransactionReceipt, Header, Index, RichBlock, TransactionBlobSidecar};
 use reth_rpc_types_compat::block::{from_block, uncle_block_from_header};
 use reth_transaction_pool::TransactionPool;
 use std::sync::Arc;
@@ -124,6 +124,40 @@ where
         Ok(None)
     }
 
+    /// Returns the blob sidecars of the blob transactions in the block that are still in the blob
+    /// store of the pool.
+    ///
+    /// Sidecars are deleted from the blob store once the block is finalized, or later if a blob
+    /// retention window is configured.
+    ///
+    /// Returns `None` if the block wasn't found.
+    pub(crate) async fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<Option<Vec<TransactionBlobSidecar>>> {
+        let Some(block) = self.block(block_id).await? else { return Ok(None) };
+        let blob_transactions =
+            block.body.iter().filter(|tx| tx.is_eip4844()).map(|tx| tx.hash).collect::<Vec<_>>();
+        if blob_transactions.is_empty() {
+            return Ok(Some(Vec::new()))
+        }
+
+        let sidecars = self
+            .on_blocking_task(
+                |this| async move { Ok(this.pool().get_all_blobs(blob_transactions)?) },
+            )
+            .await?;
+        Ok(Some(
+            sidecars
+                .into_iter()
+                .map(|(transaction_hash, sidecar)| TransactionBlobSidecar {
+                    transaction_hash,
+                    sidecar: sidecar.into(),
+                })
+                .collect(),
+        ))
+    }
+
     /// Returns the number transactions in the given block.
     ///
     /// Returns `None` if the block does not exist
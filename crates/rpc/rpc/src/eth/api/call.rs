@@ -4,24 +4,29 @@ use crate::{
     eth::{
         error::{ensure_success, EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
         revm_utils::{
-            apply_state_overrides, build_call_evm_env, caller_gas_allowance,
+            apply_block_overrides, apply_state_overrides, build_call_evm_env, caller_gas_allowance,
             cap_tx_gas_limit_with_caller_allowance, get_precompiles, prepare_call_env,
             EvmOverrides,
         },
+        simulate::{seal_simulated_block, TransferInspector, SIMULATE_BLOCK_TIME},
         EthTransactions,
     },
     EthApi,
 };
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
-use reth_primitives::{revm::env::tx_env_with_recovered, BlockId, BlockNumberOrTag, Bytes, U256};
+use reth_primitives::{
+    constants::EMPTY_OMMER_ROOT_HASH, revm::env::tx_env_with_recovered, Address, BlockId,
+    BlockNumberOrTag, Bytes, Header, B256, U256,
+};
 use reth_provider::{
     BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProvider, StateProviderFactory,
 };
 use reth_revm::{access_list::AccessListInspector, database::StateProviderDatabase};
 use reth_rpc_types::{
-    state::StateOverride, AccessListWithGasUsed, Bundle, EthCallResponse, StateContext,
-    TransactionRequest,
+    state::StateOverride, AccessList, AccessListWithGasUsed, Bundle, CreateAccessListOptions,
+    CreateAccessListResult, EthCallResponse, SimulateBlock, SimulatePayload, SimulatedBlock,
+    StateContext, TransactionRequest, MAX_SIMULATE_BLOCKS,
 };
 use reth_transaction_pool::TransactionPool;
 use revm::{
@@ -31,14 +36,91 @@ use revm::{
     },
     DatabaseCommit,
 };
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use tracing::trace;
 
 // Gas per transaction not creating a contract.
 const MIN_TRANSACTION_GAS: u64 = 21_000u64;
-/// Allowed error ratio for gas estimation
+/// Allowed error of the gas estimation in basis points of the estimated gas.
 /// Taken from Geth's implementation in order to pass the hive tests
 /// <https://github.com/ethereum/go-ethereum/blob/a5a4fa7032bb248f5a7c40f4e8df2b131c4186a4/internal/ethapi/api.go#L56>
-const ESTIMATE_GAS_ERROR_RATIO: f64 = 0.015;
+pub const DEFAULT_ESTIMATE_GAS_ERROR_TOLERANCE_BPS: u64 = 150;
+/// The maximum number of executions of `eth_createAccessList` until the access list converges.
+const MAX_ACCESS_LIST_ITERATIONS: usize = 10;
+
+/// Settings of the binary search of `eth_estimateGas`.
+///
+/// The call is always executed with the highest possible gas limit first, so that failing calls
+/// are rejected right away. The estimate is then narrowed down between the gas used by that
+/// execution and the highest gas limit.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GasEstimationConfig {
+    /// Whether the gas limit of the request is only used as the first guess instead of as the
+    /// highest possible gas limit.
+    ///
+    /// If the call fails with the gas limit of the request, the estimation continues with the
+    /// gas limit of the block.
+    pub initial_guess_from_request: bool,
+    /// The allowed error of the estimate in basis points, the binary search stops once the
+    /// remaining range is smaller than this fraction of the current estimate.
+    pub error_tolerance_bps: u64,
+    /// The maximum number of executions of the binary search, unlimited if `None`.
+    ///
+    /// If the limit is reached, the lowest gas limit the call is known to succeed with is
+    /// returned.
+    pub max_iterations: Option<u64>,
+    /// Whether the gas used plus the gas refund of the first execution is tried before the binary
+    /// search, which is enough for most calls.
+    pub optimistic_first: bool,
+}
+
+impl GasEstimationConfig {
+    /// Sets whether the gas limit of the request is only used as the first guess.
+    pub fn initial_guess_from_request(mut self, enabled: bool) -> Self {
+        self.initial_guess_from_request = enabled;
+        self
+    }
+
+    /// Sets the allowed error of the estimate in basis points.
+    pub fn error_tolerance_bps(mut self, bps: u64) -> Self {
+        self.error_tolerance_bps = bps;
+        self
+    }
+
+    /// Sets the maximum number of executions of the binary search.
+    pub fn max_iterations(mut self, max_iterations: Option<u64>) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Sets whether the optimistic gas limit is tried before the binary search.
+    pub fn optimistic_first(mut self, enabled: bool) -> Self {
+        self.optimistic_first = enabled;
+        self
+    }
+
+    /// Returns `true` if the estimate is within the allowed error, given the range of the binary
+    /// search.
+    fn is_within_tolerance(&self, highest_gas_limit: u64, lowest_gas_limit: u64) -> bool {
+        // An estimation error is allowed once the current gas limit range used in the binary
+        // search is small enough
+        // <https://github.com/ethereum/go-ethereum/blob/a5a4fa7032bb248f5a7c40f4e8df2b131c4186a4/eth/gasestimator/gasestimator.go#L152
+        let range = highest_gas_limit.saturating_sub(lowest_gas_limit) as u128;
+        range * 10_000 < highest_gas_limit as u128 * self.error_tolerance_bps as u128
+    }
+}
+
+impl Default for GasEstimationConfig {
+    fn default() -> Self {
+        Self {
+            initial_guess_from_request: false,
+            error_tolerance_bps: DEFAULT_ESTIMATE_GAS_ERROR_TOLERANCE_BPS,
+            max_iterations: None,
+            optimistic_first: true,
+        }
+    }
+}
 
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
 where
@@ -62,6 +144,7 @@ where
             this.estimate_gas_with(cfg, block_env, request, state, state_override)
         })
         .await
+        .map_err(|err| self.revert_decoder().decode_error(err))
     }
 
     /// Executes the call request (`eth_call`) and returns the output
@@ -79,7 +162,7 @@ where
             )
             .await?;
 
-        ensure_success(res.result)
+        ensure_success(res.result).map_err(|err| self.revert_decoder().decode_error(err))
     }
 
     /// Simulate arbitrary number of transactions at an arbitrary blockchain index, with the
@@ -179,6 +262,153 @@ where
         .await
     }
 
+    /// Simulates blocks of calls on top of the block (`eth_simulateV1`).
+    ///
+    /// Each block is built on top of the previous one, with the state changes of its calls and
+    /// its state overrides. The header fields of a block default to the next block after its
+    /// parent and can be overridden with its block overrides.
+    pub async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block: Option<BlockId>,
+    ) -> EthResult<Vec<SimulatedBlock>> {
+        let SimulatePayload { block_state_calls, trace_transfers, validation } = payload;
+        if block_state_calls.len() > MAX_SIMULATE_BLOCKS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many blocks, the maximum is {MAX_SIMULATE_BLOCKS}"
+            )))
+        }
+
+        // the state of the pending block is not available, so the blocks are simulated on top
+        // of the latest block instead
+        let block = block
+            .filter(|block| !block.is_pending())
+            .unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+        let (cfg, block_env, at) = self.evm_env_at(block).await?;
+        let parent =
+            self.provider().sealed_header_by_id(at)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let gas_cap = self.inner.gas_cap;
+
+        let this = self.clone();
+        self.spawn_with_state_at_block(at, move |state| {
+            let chain_spec = this.provider().chain_spec();
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+            let mut parent = parent;
+            let mut blocks = Vec::with_capacity(block_state_calls.len());
+
+            for SimulateBlock { block_overrides, state_overrides, calls } in block_state_calls {
+                let mut block_env = block_env.clone();
+                block_env.number = U256::from(parent.number + 1);
+                block_env.timestamp = U256::from(parent.timestamp + SIMULATE_BLOCK_TIME);
+                block_env.gas_limit = U256::from(parent.gas_limit);
+
+                let base_fee_override = block_overrides.as_ref().and_then(|o| o.base_fee);
+                if let Some(mut block_overrides) = block_overrides {
+                    if let Some(block_hashes) = block_overrides.block_hash.take() {
+                        db.block_hashes.extend(
+                            block_hashes.into_iter().map(|(num, hash)| (U256::from(num), hash)),
+                        );
+                    }
+                    apply_block_overrides(block_overrides, &mut block_env);
+                }
+
+                let number: u64 = block_env.number.saturating_to();
+                let timestamp: u64 = block_env.timestamp.saturating_to();
+                if number <= parent.number {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "block number {number} is not greater than the parent block number {}",
+                        parent.number
+                    )))
+                }
+                if timestamp <= parent.timestamp {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "block timestamp {timestamp} is not greater than the parent timestamp {}",
+                        parent.timestamp
+                    )))
+                }
+                if base_fee_override.is_none() {
+                    // without validation the base fee is ignored, like in `eth_call`
+                    block_env.basefee = if validation {
+                        let base_fee_params = chain_spec.base_fee_params_at_timestamp(timestamp);
+                        U256::from(parent.next_block_base_fee(base_fee_params).unwrap_or_default())
+                    } else {
+                        U256::ZERO
+                    };
+                }
+
+                // the simulated blocks are not in the database
+                db.block_hashes.insert(U256::from(parent.number), parent.hash());
+                if let Some(state_overrides) = state_overrides {
+                    apply_state_overrides(state_overrides, &mut db)?;
+                }
+
+                let block_gas_limit: u64 = block_env.gas_limit.saturating_to();
+                let mut gas_used = 0u64;
+                let mut results = Vec::with_capacity(calls.len());
+                for request in calls {
+                    let request_gas = request.gas;
+                    let nonce = request.nonce;
+                    let mut env = prepare_call_env(
+                        cfg.clone(),
+                        block_env.clone(),
+                        request,
+                        gas_cap,
+                        &mut db,
+                        EvmOverrides::default(),
+                    )?;
+
+                    let remaining_gas = block_gas_limit.saturating_sub(gas_used);
+                    if env.tx.gas_limit > remaining_gas {
+                        if request_gas.is_some() {
+                            return Err(EthApiError::InvalidParams(
+                                "block gas limit reached".to_string(),
+                            ))
+                        }
+                        env.tx.gas_limit = remaining_gas;
+                    }
+                    if validation {
+                        env.cfg.disable_base_fee = false;
+                        env.tx.nonce = nonce;
+                    }
+
+                    let (res, logs) = if trace_transfers {
+                        let mut inspector = TransferInspector::default();
+                        let (res, _) = this.inspect(&mut db, env, &mut inspector)?;
+                        (res, inspector.into_logs())
+                    } else {
+                        let (res, _) = this.transact(&mut db, env)?;
+                        let logs = res.result.logs().to_vec();
+                        (res, logs)
+                    };
+                    gas_used += res.result.gas_used();
+                    db.commit(res.state);
+                    results.push((res.result, logs));
+                }
+
+                let header = Header {
+                    parent_hash: parent.hash(),
+                    ommers_hash: EMPTY_OMMER_ROOT_HASH,
+                    beneficiary: block_env.coinbase,
+                    difficulty: block_env.difficulty,
+                    number,
+                    gas_limit: block_gas_limit,
+                    timestamp,
+                    mix_hash: block_env.prevrandao.unwrap_or_default(),
+                    base_fee_per_gas: parent
+                        .base_fee_per_gas
+                        .map(|_| block_env.basefee.saturating_to()),
+                    ..Default::default()
+                };
+                let (header, block) = seal_simulated_block(header, results);
+                parent = header;
+                blocks.push(block);
+            }
+
+            Ok(blocks)
+        })
+        .await
+    }
+
     /// Estimates the gas usage of the `request` with the state.
     ///
     /// This will execute the [TransactionRequest] and find the best gas limit via binary search
@@ -186,7 +416,7 @@ where
         &self,
         mut cfg: CfgEnvWithHandlerCfg,
         block: BlockEnv,
-        request: TransactionRequest,
+        mut request: TransactionRequest,
         state: S,
         state_override: Option<StateOverride>,
     ) -> EthResult<U256>
@@ -202,6 +432,16 @@ where
         // <https://github.com/ethereum/go-ethereum/blob/ee8e83fa5f6cb261dad2ed0a7bbcde4930c41e6c/internal/ethapi/api.go#L985>
         cfg.disable_base_fee = true;
 
+        let config = self.gas_estimation_config();
+
+        // if configured, the request's gas limit is only tried first and the highest possible gas
+        // limit is the block's gas limit
+        let initial_guess = if config.initial_guess_from_request {
+            request.gas.take().and_then(|gas| u64::try_from(gas).ok())
+        } else {
+            None
+        };
+
         // keep a copy of gas related request values
         let request_gas = request.gas;
         let request_gas_price = request.gas_price;
@@ -256,10 +496,23 @@ where
         let gas_limit = std::cmp::min(U256::from(env.tx.gas_limit), highest_gas_limit);
         env.tx.gas_limit = gas_limit.saturating_to();
 
-        trace!(target: "rpc::eth::estimate", ?env, "Starting gas estimation");
+        trace!(target: "rpc::eth::estimate", ?env, ?initial_guess, "Starting gas estimation");
+
+        // if the call succeeds with the first guess, it's the highest gas limit of the search
+        let mut guess_res = None;
+        if let Some(guess) = initial_guess.filter(|guess| *guess < env.tx.gas_limit) {
+            let mut guess_env = env.clone();
+            guess_env.tx.gas_limit = guess;
+            if let Ok((res, guess_env)) = self.transact(&mut db, guess_env) {
+                if res.result.is_success() {
+                    highest_gas_limit = U256::from(guess);
+                    guess_res = Some(Ok((res, guess_env)));
+                }
+            }
+        }
 
         // transact with the highest __possible__ gas limit
-        let ethres = self.transact(&mut db, env.clone());
+        let ethres = guess_res.unwrap_or_else(|| self.transact(&mut db, env.clone()));
 
         // Exceptional case: init used too much gas, we need to increase the gas limit and try
         // again
@@ -305,6 +558,12 @@ where
         // the lowest value is capped by the gas used by the unconstrained transaction
         let mut lowest_gas_limit = gas_used.saturating_sub(1);
 
+        // the highest gas limit is already close enough to the gas used, e.g. because the request
+        // set a gas limit slightly above it
+        if config.is_within_tolerance(highest_gas_limit, lowest_gas_limit) {
+            return Ok(U256::from(highest_gas_limit))
+        }
+
         let gas_refund = match res.result {
             ExecutionResult::Success { gas_refunded, .. } => gas_refunded,
             _ => 0,
@@ -313,7 +572,7 @@ where
         // gas limit to the execution gas used plus the gas refund, so we check this first
         // <https://github.com/ethereum/go-ethereum/blob/a5a4fa7032bb248f5a7c40f4e8df2b131c4186a4/eth/gasestimator/gasestimator.go#L135
         let optimistic_gas_limit = (gas_used + gas_refund) * 64 / 63;
-        if optimistic_gas_limit < highest_gas_limit {
+        if config.optimistic_first && optimistic_gas_limit < highest_gas_limit {
             env.tx.gas_limit = optimistic_gas_limit;
             (res, env) = self.transact(&mut db, env)?;
             update_estimated_gas_range(
@@ -333,15 +592,17 @@ where
         trace!(target: "rpc::eth::estimate", ?env, ?highest_gas_limit, ?lowest_gas_limit, ?mid_gas_limit, "Starting binary search for gas");
 
         // binary search
+        let mut iterations = 0;
         while (highest_gas_limit - lowest_gas_limit) > 1 {
-            // An estimation error is allowed once the current gas limit range used in the binary
-            // search is small enough (less than 1.5% of the highest gas limit)
-            // <https://github.com/ethereum/go-ethereum/blob/a5a4fa7032bb248f5a7c40f4e8df2b131c4186a4/eth/gasestimator/gasestimator.go#L152
-            if (highest_gas_limit - lowest_gas_limit) as f64 / (highest_gas_limit as f64) <
-                ESTIMATE_GAS_ERROR_RATIO
-            {
+            if config.is_within_tolerance(highest_gas_limit, lowest_gas_limit) {
                 break
             };
+            // the highest gas limit is known to succeed, so it's a valid estimate
+            if config.max_iterations.map_or(false, |max| iterations >= max) {
+                trace!(target: "rpc::eth::estimate", ?highest_gas_limit, ?lowest_gas_limit, "Reached max gas estimation iterations");
+                break
+            }
+            iterations += 1;
 
             env.tx.gas_limit = mid_gas_limit;
             let ethres = self.transact(&mut db, env.clone());
@@ -376,9 +637,10 @@ where
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-    ) -> EthResult<AccessListWithGasUsed> {
+        options: CreateAccessListOptions,
+    ) -> EthResult<CreateAccessListResult> {
         self.on_blocking_task(|this| async move {
-            this.create_access_list_with(request, block_number).await
+            this.create_access_list_with(request, block_number, options).await
         })
         .await
     }
@@ -387,7 +649,8 @@ where
         &self,
         mut request: TransactionRequest,
         at: Option<BlockId>,
-    ) -> EthResult<AccessListWithGasUsed> {
+        options: CreateAccessListOptions,
+    ) -> EthResult<CreateAccessListResult> {
         let block_id = at.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
         let (cfg, block, at) = self.evm_env_at(block_id).await?;
         let state = self.state_at(at)?;
@@ -419,34 +682,75 @@ where
         };
 
         // can consume the list since we're not using the request anymore
-        let initial = request.access_list.take().unwrap_or_default();
-
-        let precompiles = get_precompiles(env.handler_cfg.spec_id);
-        let mut inspector = AccessListInspector::new(initial, from, to, precompiles);
-        let (result, env) = self.inspect(&mut db, env, &mut inspector)?;
+        let mut access_list = request.access_list.take().unwrap_or_default();
+
+        let precompiles = get_precompiles(env.handler_cfg.spec_id).into_iter().collect::<Vec<_>>();
+        let mut iterations = 0;
+        let converged = loop {
+            let mut inspector = AccessListInspector::new(
+                access_list.clone(),
+                from,
+                to,
+                precompiles.iter().copied(),
+            );
+            let (result, next_env) = self.inspect(&mut db, env, &mut inspector)?;
+            env = next_env;
+
+            match result.result {
+                ExecutionResult::Halt { reason, .. } => Err(match reason {
+                    HaltReason::NonceOverflow => RpcInvalidTransactionError::NonceMaxValue,
+                    halt => RpcInvalidTransactionError::EvmHalt(halt),
+                }),
+                ExecutionResult::Revert { output, .. } => {
+                    Err(RpcInvalidTransactionError::Revert(RevertError::new(output)))
+                }
+                ExecutionResult::Success { .. } => Ok(()),
+            }?;
 
-        match result.result {
-            ExecutionResult::Halt { reason, .. } => Err(match reason {
-                HaltReason::NonceOverflow => RpcInvalidTransactionError::NonceMaxValue,
-                halt => RpcInvalidTransactionError::EvmHalt(halt),
-            }),
-            ExecutionResult::Revert { output, .. } => {
-                Err(RpcInvalidTransactionError::Revert(RevertError::new(output)))
+            let previous = std::mem::replace(&mut access_list, inspector.into_access_list());
+            iterations += 1;
+            if !options.converge {
+                break None
+            }
+            if access_list_entries(&previous) == access_list_entries(&access_list) {
+                break Some(true)
             }
-            ExecutionResult::Success { .. } => Ok(()),
-        }?;
+            if iterations >= MAX_ACCESS_LIST_ITERATIONS {
+                break Some(false)
+            }
+
+            // the next execution is charged with the generated access list
+            env.tx.access_list = access_list.clone().into_flattened();
+        };
 
-        let access_list = inspector.into_access_list();
+        trace!(target: "rpc::eth::access_list", iterations, ?converged, "Created access list");
 
         let cfg_with_spec_id =
             CfgEnvWithHandlerCfg { cfg_env: env.cfg.clone(), handler_cfg: env.handler_cfg };
 
+        // calculate the gas used without the access list
+        let gas_used_without_access_list = if options.include_gas_without_access_list {
+            Some(self.estimate_gas_with(
+                cfg_with_spec_id.clone(),
+                env.block.clone(),
+                request.clone(),
+                &*db.db,
+                None,
+            )?)
+        } else {
+            None
+        };
+
         // calculate the gas used using the access list
         request.access_list = Some(access_list.clone());
         let gas_used =
             self.estimate_gas_with(cfg_with_spec_id, env.block.clone(), request, &*db.db, None)?;
 
-        Ok(AccessListWithGasUsed { access_list, gas_used })
+        Ok(CreateAccessListResult {
+            inner: AccessListWithGasUsed { access_list, gas_used },
+            gas_used_without_access_list,
+            converged,
+        })
     }
     /// Executes the requests again after an out of gas error to check if the error is gas related
     /// or not
@@ -483,6 +787,15 @@ where
     }
 }
 
+/// Returns the addresses and storage keys of the access list, independent of their order.
+fn access_list_entries(access_list: &AccessList) -> BTreeMap<Address, BTreeSet<B256>> {
+    let mut entries = BTreeMap::<_, BTreeSet<_>>::new();
+    for item in &access_list.0 {
+        entries.entry(item.address).or_default().extend(item.storage_keys.iter().copied());
+    }
+    entries
+}
+
 /// Updates the highest and lowest gas limits for binary search
 ///  based on the result of the execution
 #[inline]
@@ -521,3 +834,19 @@ fn update_estimated_gas_range(
     };
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gas_estimation_tolerance() {
+        let config = GasEstimationConfig::default();
+        // 1.5% of 100_000
+        assert!(!config.is_within_tolerance(100_000, 98_500));
+        assert!(config.is_within_tolerance(100_000, 98_501));
+
+        let config = config.error_tolerance_bps(0);
+        assert!(!config.is_within_tolerance(100_000, 99_999));
+    }
+}
@@ -54,13 +54,16 @@ impl FeeHistoryCache {
         self.config().resolution
     }
 
-    /// Returns all blocks that are missing in the cache in the [lower_bound, upper_bound] range.
+    /// Returns all blocks that are missing in the cache in the range of the last
+    /// [FeeHistoryCacheConfig::max_blocks] blocks up to the given block, newest first.
     ///
-    /// This function is used to populate the cache with missing blocks, which can happen if the
-    /// node switched to stage sync node.
-    async fn missing_consecutive_blocks(&self) -> VecDeque<u64> {
+    /// This function is used to warm up the cache on launch and to populate the cache with missing
+    /// blocks, which can happen if the node switched to stage sync node.
+    async fn missing_consecutive_blocks(&self, upper_bound: u64) -> VecDeque<u64> {
+        let lower_bound =
+            upper_bound.saturating_sub(self.inner.config.max_blocks.saturating_sub(1));
         let entries = self.inner.entries.read().await;
-        (self.lower_bound()..self.upper_bound())
+        (lower_bound..=upper_bound)
             .rev()
             .filter(|&block_number| !entries.contains_key(&block_number))
             .collect()
@@ -199,6 +202,9 @@ struct FeeHistoryCacheInner {
 
 /// Awaits for new chain events and directly inserts them into the cache so they're available
 /// immediately before they need to be fetched from disk.
+///
+/// On launch, the cache is warmed up with the last [FeeHistoryCacheConfig::max_blocks] blocks, so
+/// that `eth_feeHistory` requests over the entire supported range are served from the cache.
 pub async fn fee_history_cache_new_blocks_task<St, Provider>(
     fee_history_cache: FeeHistoryCache,
     mut events: St,
@@ -209,7 +215,10 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
 {
     // We're listening for new blocks emitted when the node is in live sync.
     // If the node transitions to stage sync, we need to fetch the missing blocks
-    let mut missing_blocks = VecDeque::new();
+    let mut missing_blocks = match provider.best_block_number() {
+        Ok(best_block) => fee_history_cache.missing_consecutive_blocks(best_block).await,
+        Err(_) => VecDeque::new(),
+    };
     let mut fetch_missing_block = Fuse::terminated();
 
     loop {
@@ -249,7 +258,8 @@ pub async fn fee_history_cache_new_blocks_task<St, Provider>(
                 fee_history_cache.insert_blocks(blocks.into_iter().zip(receipts)).await;
 
                 // keep track of missing blocks
-                missing_blocks = fee_history_cache.missing_consecutive_blocks().await;
+                let upper_bound = fee_history_cache.upper_bound();
+                missing_blocks = fee_history_cache.missing_consecutive_blocks(upper_bound).await;
             }
         }
     }
@@ -394,3 +404,35 @@ impl FeeHistoryEntry {
         Some(calculate_excess_blob_gas(self.excess_blob_gas?, self.blob_gas_used?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives::Header;
+    use reth_provider::test_utils::NoopProvider;
+
+    #[tokio::test]
+    async fn missing_blocks_within_max_blocks() {
+        let cache = EthStateCache::spawn(
+            NoopProvider::default(),
+            Default::default(),
+            EthEvmConfig::default(),
+        );
+        let config = FeeHistoryCacheConfig { max_blocks: 4, ..Default::default() };
+        let fee_history_cache = FeeHistoryCache::new(cache, config);
+
+        // an empty cache is warmed up with the last blocks
+        assert_eq!(fee_history_cache.missing_consecutive_blocks(10).await, [10, 9, 8, 7]);
+
+        let block = |number| SealedBlock {
+            header: Header { number, gas_limit: 1, ..Default::default() }.seal_slow(),
+            ..Default::default()
+        };
+        fee_history_cache
+            .insert_blocks([8, 10].map(|number| (block(number), Arc::new(Vec::new()))))
+            .await;
+        assert_eq!(fee_history_cache.missing_consecutive_blocks(10).await, [9, 7]);
+        assert_eq!(fee_history_cache.missing_consecutive_blocks(2).await, [2, 1, 0]);
+    }
+}
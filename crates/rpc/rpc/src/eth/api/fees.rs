@@ -4,6 +4,7 @@ use crate::{
     eth::{
         api::fee_history::{calculate_reward_percentiles_for_block, FeeHistoryEntry},
         error::{EthApiError, EthResult},
+        gas_oracle::PriorityFeeInput,
     },
     EthApi,
 };
@@ -12,7 +13,7 @@ use reth_network_api::NetworkInfo;
 use reth_primitives::{BlockNumberOrTag, U256};
 use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_rpc_types::FeeHistory;
-use reth_transaction_pool::TransactionPool;
+use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
 use tracing::debug;
 
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
@@ -43,9 +44,45 @@ where
             .map(U256::from)
     }
 
-    /// Returns a suggestion for the priority fee (the tip)
+    /// Returns a suggestion for the priority fee (the tip), using the strategy of the gas oracle
     pub(crate) async fn suggested_priority_fee(&self) -> EthResult<U256> {
-        self.gas_oracle().suggest_tip_cap().await
+        let oracle = self.gas_oracle();
+        let strategy = oracle.strategy();
+        let mut input = PriorityFeeInput {
+            percentile_tip: oracle.suggest_tip_cap().await?,
+            ..Default::default()
+        };
+
+        if strategy.uses_pending_transactions() {
+            let header = self.provider().latest_header()?.ok_or(EthApiError::UnknownBlockNumber)?;
+            let base_fee = header
+                .next_block_base_fee(
+                    self.provider().chain_spec().base_fee_params_at_timestamp(header.timestamp),
+                )
+                .unwrap_or_default();
+            input.gas_limit = header.gas_limit;
+
+            let mut gas_used = 0u64;
+            for tx in self
+                .pool()
+                .best_transactions_with_attributes(BestTransactionsAttributes::base_fee(base_fee))
+            {
+                if gas_used > header.gas_limit {
+                    break
+                }
+                gas_used = gas_used.saturating_add(tx.gas_limit());
+                input
+                    .pending
+                    .push((tx.effective_tip_per_gas(base_fee).unwrap_or_default(), tx.gas_limit()));
+            }
+        }
+
+        let mut tip = strategy.suggest_tip_cap(&input);
+        // constrain to the max price
+        if let Some(max_price) = oracle.config().max_price {
+            tip = tip.min(max_price);
+        }
+        Ok(tip)
     }
 
     /// Reports the fee history, for the given amount of blocks, up until the given newest block.
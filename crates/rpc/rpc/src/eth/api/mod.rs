@@ -6,9 +6,12 @@ use crate::eth::{
         fee_history::FeeHistoryCache,
         pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin},
     },
+    blobs::LocalBlobStore,
     cache::EthStateCache,
     error::{EthApiError, EthResult},
     gas_oracle::GasPriceOracle,
+    keystore::KeystoreSigner,
+    revert::RevertDecoder,
     signer::EthSigner,
 };
 
@@ -27,6 +30,7 @@ use reth_provider::{
 use reth_rpc_types::{SyncInfo, SyncStatus};
 use reth_tasks::{pool::BlockingTaskPool, TaskSpawner, TokioTaskExecutor};
 use reth_transaction_pool::TransactionPool;
+use revm::db::BundleState;
 use revm_primitives::{CfgEnv, SpecId};
 use std::{
     fmt::Debug,
@@ -50,7 +54,14 @@ mod state;
 mod transactions;
 
 use crate::eth::traits::RawTransactionForwarder;
-pub use transactions::{EthTransactions, TransactionSource};
+pub use call::{GasEstimationConfig, DEFAULT_ESTIMATE_GAS_ERROR_TOLERANCE_BPS};
+#[cfg(feature = "optimism")]
+pub(crate) use transactions::build_op_tx_meta;
+pub(crate) use transactions::build_transaction_receipt_with_block_receipts;
+pub use transactions::{
+    EthTransactions, NonceStrategy, TransactionConfirmationConfig, TransactionSource,
+    DEFAULT_TX_CONFIRMATION_POLL_INTERVAL, DEFAULT_TX_CONFIRMATION_TIMEOUT,
+};
 
 /// `Eth` API trait.
 ///
@@ -119,6 +130,11 @@ where
             fee_history_cache,
             evm_config,
             raw_transaction_forwarder,
+            LocalBlobStore::default(),
+            TransactionConfirmationConfig::default(),
+            NonceStrategy::default(),
+            RevertDecoder::default(),
+            GasEstimationConfig::default(),
         )
     }
 
@@ -136,6 +152,11 @@ where
         fee_history_cache: FeeHistoryCache,
         evm_config: EvmConfig,
         raw_transaction_forwarder: Option<Arc<dyn RawTransactionForwarder>>,
+        local_blob_store: LocalBlobStore,
+        tx_confirmation: TransactionConfirmationConfig,
+        nonce_strategy: NonceStrategy,
+        revert_decoder: RevertDecoder,
+        gas_estimation: GasEstimationConfig,
     ) -> Self {
         // get the block number of the latest block
         let latest_block = provider
@@ -150,6 +171,7 @@ where
             pool,
             network,
             signers: parking_lot::RwLock::new(Default::default()),
+            keystore: parking_lot::RwLock::new(None),
             eth_cache,
             gas_oracle,
             gas_cap,
@@ -160,6 +182,11 @@ where
             fee_history_cache,
             evm_config,
             raw_transaction_forwarder,
+            local_blob_store,
+            tx_confirmation,
+            nonce_strategy,
+            revert_decoder,
+            gas_estimation,
         };
 
         Self { inner: Arc::new(inner) }
@@ -170,6 +197,9 @@ where
     /// This accepts a closure that creates a new future using a clone of this type and spawns the
     /// future onto a new task that is allowed to block.
     ///
+    /// The future is dropped at its next await point once the returned future is dropped, e.g.
+    /// because the call timed out.
+    ///
     /// Note: This is expected for futures that are dominated by blocking IO operations.
     pub(crate) async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
     where
@@ -177,11 +207,15 @@ where
         F: Future<Output = EthResult<R>> + Send + 'static,
         R: Send + 'static,
     {
-        let (tx, rx) = oneshot::channel();
+        let (mut tx, rx) = oneshot::channel();
         let this = self.clone();
         let f = c(this);
         self.inner.task_spawner.spawn_blocking(Box::pin(async move {
-            let res = f.await;
+            let res = tokio::select! {
+                res = f => res,
+                // the result is no longer awaited
+                _ = tx.closed() => return,
+            };
             let _ = tx.send(res);
         }));
         rx.await.map_err(|_| EthApiError::InternalEthError)?
@@ -193,7 +227,7 @@ where
     }
 
     /// Returns the gas oracle frontend
-    pub(crate) fn gas_oracle(&self) -> &GasPriceOracle<Provider> {
+    pub fn gas_oracle(&self) -> &GasPriceOracle<Provider> {
         &self.inner.gas_oracle
     }
 
@@ -221,6 +255,31 @@ where
     pub fn fee_history_cache(&self) -> &FeeHistoryCache {
         &self.inner.fee_history_cache
     }
+
+    /// Returns the store for blobs referenced by blob transactions signed by the node
+    pub fn local_blob_store(&self) -> &LocalBlobStore {
+        &self.inner.local_blob_store
+    }
+
+    /// Returns the settings for waiting for the receipt of a submitted transaction
+    pub fn tx_confirmation_config(&self) -> TransactionConfirmationConfig {
+        self.inner.tx_confirmation
+    }
+
+    /// Returns how the nonce of a signed transaction is chosen if the request doesn't set one
+    pub fn nonce_strategy(&self) -> NonceStrategy {
+        self.inner.nonce_strategy
+    }
+
+    /// Returns the decoder of the revert reasons of reverted calls
+    pub fn revert_decoder(&self) -> &RevertDecoder {
+        &self.inner.revert_decoder
+    }
+
+    /// Returns the settings of the binary search of `eth_estimateGas`
+    pub fn gas_estimation_config(&self) -> GasEstimationConfig {
+        self.inner.gas_estimation
+    }
 }
 
 // === State access helpers ===
@@ -322,9 +381,29 @@ where
 
     /// Returns the locally built pending block
     pub(crate) async fn local_pending_block(&self) -> EthResult<Option<SealedBlockWithSenders>> {
+        Ok(self.local_pending_block_with_state().await?.map(|(block, _)| block))
+    }
+
+    /// Returns the hash of the parent of the locally built pending block and the state changes of
+    /// the pending block on top of it.
+    ///
+    /// Returns `None` if the pending block was received from the CL, since the provider already
+    /// serves its state, or if no pending block could be built.
+    pub(crate) async fn local_pending_state(&self) -> EthResult<Option<(B256, Arc<BundleState>)>> {
+        Ok(self
+            .local_pending_block_with_state()
+            .await?
+            .and_then(|(block, state)| Some((block.parent_hash, state?))))
+    }
+
+    /// Returns the locally built pending block and its state changes on top of the latest block,
+    /// if it was built from the pool.
+    async fn local_pending_block_with_state(
+        &self,
+    ) -> EthResult<Option<(SealedBlockWithSenders, Option<Arc<BundleState>>)>> {
         let pending = self.pending_block_env_and_cfg()?;
         if pending.origin.is_actual_pending() {
-            return Ok(pending.origin.into_actual_pending())
+            return Ok(pending.origin.into_actual_pending().map(|block| (block, None)))
         }
 
         // no pending block from the CL yet, so we need to build it ourselves via txpool
@@ -339,12 +418,15 @@ where
                     pending.origin.header().hash() == pending_block.block.parent_hash &&
                     now <= pending_block.expires_at
                 {
-                    return Ok(Some(pending_block.block.clone()))
+                    return Ok(Some((
+                        pending_block.block.clone(),
+                        Some(pending_block.state.clone()),
+                    )))
                 }
             }
 
             // we rebuild the block
-            let pending_block = match pending.build_block(this.provider(), this.pool()) {
+            let (pending_block, state) = match pending.build_block(this.provider(), this.pool()) {
                 Ok(block) => block,
                 Err(err) => {
                     tracing::debug!(target: "rpc", "Failed to build pending block: {:?}", err);
@@ -352,13 +434,15 @@ where
                 }
             };
 
+            let state = Arc::new(state);
             let now = Instant::now();
             *lock = Some(PendingBlock {
                 block: pending_block.clone(),
+                state: state.clone(),
                 expires_at: now + Duration::from_secs(3),
             });
 
-            Ok(Some(pending_block))
+            Ok(Some((pending_block, Some(state))))
         })
         .await
     }
@@ -472,6 +556,8 @@ struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     network: Network,
     /// All configured Signers
     signers: parking_lot::RwLock<Vec<Box<dyn EthSigner>>>,
+    /// The keystore signer, if configured, also part of `signers`
+    keystore: parking_lot::RwLock<Option<KeystoreSigner>>,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
     /// The async gas oracle frontend for gas price suggestions
@@ -492,4 +578,14 @@ struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     evm_config: EvmConfig,
     /// Allows forwarding received raw transactions
     raw_transaction_forwarder: Option<Arc<dyn RawTransactionForwarder>>,
+    /// Blobs that can be referenced by blob transactions signed by the node
+    local_blob_store: LocalBlobStore,
+    /// How `send_raw_transaction_sync` waits for the receipt of the transaction
+    tx_confirmation: TransactionConfirmationConfig,
+    /// How the nonce of transactions signed by the node is chosen
+    nonce_strategy: NonceStrategy,
+    /// Decodes the revert reasons of reverted calls
+    revert_decoder: RevertDecoder,
+    /// Settings of the binary search of `eth_estimateGas`
+    gas_estimation: GasEstimationConfig,
 }
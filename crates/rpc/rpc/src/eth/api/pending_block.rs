@@ -17,9 +17,12 @@ use reth_revm::{
     state_change::{apply_beacon_root_contract_call, post_block_withdrawals_balance_increments},
 };
 use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
-use revm::{db::states::bundle_state::BundleRetention, Database, DatabaseCommit, State};
+use revm::{
+    db::{states::bundle_state::BundleRetention, BundleState},
+    Database, DatabaseCommit, State,
+};
 use revm_primitives::EnvWithHandlerCfg;
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 /// Configured [BlockEnv] and [CfgEnvWithHandlerCfg] for a pending block
 #[derive(Debug, Clone)]
@@ -39,11 +42,16 @@ impl PendingBlockEnv {
     ///
     /// After Cancun, if the origin is the actual pending block, the block includes the EIP-4788 pre
     /// block contract call using the parent beacon block root received from the CL.
+    ///
+    /// Executable blob transactions of the pool are included within the blob gas limit of the
+    /// block, if Cancun is active.
+    ///
+    /// Returns the block and the state changes of the block on top of its parent.
     pub(crate) fn build_block<Client, Pool>(
         self,
         client: &Client,
         pool: &Pool,
-    ) -> EthResult<SealedBlockWithSenders>
+    ) -> EthResult<(SealedBlockWithSenders, BundleState)>
     where
         Client: StateProviderFactory + ChainSpecProvider,
         Pool: TransactionPool,
@@ -67,6 +75,10 @@ impl PendingBlockEnv {
             base_fee,
             block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
         ));
+        if cfg.handler_cfg.spec_id < SpecId::CANCUN {
+            // blob transactions are only valid after Cancun
+            best_txs.skip_blobs();
+        }
 
         let (withdrawals, withdrawals_root) = match origin {
             PendingBlockEnvOrigin::ActualPending(ref block) => {
@@ -264,7 +276,7 @@ impl PendingBlockEnv {
 
         // seal the block
         let block = Block { header, body: executed_txs, ommers: vec![], withdrawals };
-        Ok(SealedBlockWithSenders { block: block.seal_slow(), senders })
+        Ok((SealedBlockWithSenders { block: block.seal_slow(), senders }, bundle.state().clone()))
     }
 }
 
@@ -371,6 +383,8 @@ impl PendingBlockEnvOrigin {
 pub(crate) struct PendingBlock {
     /// The cached pending block
     pub(crate) block: SealedBlockWithSenders,
+    /// The state changes of the pending block on top of its parent
+    pub(crate) state: Arc<BundleState>,
     /// Timestamp when the pending block is considered outdated
     pub(crate) expires_at: Instant,
 }
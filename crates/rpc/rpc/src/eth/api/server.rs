@@ -1,7 +1,7 @@
 //! Implementation of the [`jsonrpsee`] generated [`reth_rpc_api::EthApiServer`] trait
 //! Handles RPC requests for the `eth_` namespace.
 
-use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::{core::RpcResult as Result, types::ErrorObject};
 use serde_json::Value;
 use tracing::trace;
 
@@ -17,9 +17,11 @@ use reth_provider::{
 };
 use reth_rpc_api::EthApiServer;
 use reth_rpc_types::{
-    state::StateOverride, AccessListWithGasUsed, AnyTransactionReceipt, BlockOverrides, Bundle,
-    EIP1186AccountProofResponse, EthCallResponse, FeeHistory, Header, Index, RichBlock,
-    StateContext, SyncStatus, TransactionRequest, Work,
+    state::StateOverride, AnyTransactionReceipt, AuthorizationRequest, BlockOverrides, Bundle,
+    CreateAccessListOptions, CreateAccessListResult, EIP1186AccountProofResponse, EthCallResponse,
+    FeeHistory, Header, Index, RawTransactionError, RawTransactionResult, RichBlock,
+    SignedAuthorization, SimulatePayload, SimulatedBlock, StateContext, SyncStatus,
+    TransactionBlobSidecar, TransactionConditional, TransactionRequest, Work,
 };
 use reth_transaction_pool::TransactionPool;
 
@@ -139,6 +141,15 @@ where
         Ok(EthApi::block_receipts(self, block_id).await?)
     }
 
+    /// Handler for: `eth_getBlobSidecars`
+    async fn blob_sidecars(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<Vec<TransactionBlobSidecar>>> {
+        trace!(target: "rpc::eth", ?block_id, "Serving eth_getBlobSidecars");
+        Ok(EthApi::blob_sidecars(self, block_id).await?)
+    }
+
     /// Handler for: `eth_getUncleByBlockHashAndIndex`
     async fn uncle_by_block_hash_and_index(
         &self,
@@ -295,14 +306,26 @@ where
         Ok(EthApi::call_many(self, bundle, state_context, state_override).await?)
     }
 
+    /// Handler for: `eth_simulateV1`
+    async fn simulate_v1(
+        &self,
+        payload: SimulatePayload,
+        block_number: Option<BlockId>,
+    ) -> Result<Vec<SimulatedBlock>> {
+        trace!(target: "rpc::eth", ?block_number, "Serving eth_simulateV1");
+        Ok(EthApi::simulate_v1(self, payload, block_number).await?)
+    }
+
     /// Handler for: `eth_createAccessList`
     async fn create_access_list(
         &self,
         request: TransactionRequest,
         block_number: Option<BlockId>,
-    ) -> Result<AccessListWithGasUsed> {
-        trace!(target: "rpc::eth", ?request, ?block_number, "Serving eth_createAccessList");
-        let access_list_with_gas_used = self.create_access_list_at(request, block_number).await?;
+        options: Option<CreateAccessListOptions>,
+    ) -> Result<CreateAccessListResult> {
+        trace!(target: "rpc::eth", ?request, ?block_number, ?options, "Serving eth_createAccessList");
+        let access_list_with_gas_used =
+            self.create_access_list_at(request, block_number, options.unwrap_or_default()).await?;
 
         Ok(access_list_with_gas_used)
     }
@@ -400,6 +423,41 @@ where
         Ok(EthTransactions::send_raw_transaction(self, tx).await?)
     }
 
+    /// Handler for: `eth_sendRawTransactionSync`
+    async fn send_raw_transaction_sync(&self, tx: Bytes) -> Result<AnyTransactionReceipt> {
+        trace!(target: "rpc::eth", ?tx, "Serving eth_sendRawTransactionSync");
+        Ok(EthTransactions::send_raw_transaction_sync(self, tx).await?)
+    }
+
+    /// Handler for: `eth_sendRawTransactions`
+    async fn send_raw_transactions(&self, txs: Vec<Bytes>) -> Result<Vec<RawTransactionResult>> {
+        trace!(target: "rpc::eth", count = txs.len(), "Serving eth_sendRawTransactions");
+        let results = EthTransactions::send_raw_transactions(self, txs).await;
+        Ok(results
+            .into_iter()
+            .map(|result| match result {
+                Ok(hash) => RawTransactionResult::Hash(hash),
+                Err(err) => {
+                    let err = ErrorObject::from(err);
+                    RawTransactionResult::Error(RawTransactionError {
+                        code: err.code(),
+                        message: err.message().to_string(),
+                    })
+                }
+            })
+            .collect())
+    }
+
+    /// Handler for: `eth_sendRawTransactionConditional`
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> Result<B256> {
+        trace!(target: "rpc::eth", ?tx, ?conditional, "Serving eth_sendRawTransactionConditional");
+        Ok(EthTransactions::send_raw_transaction_conditional(self, tx, conditional).await?)
+    }
+
     /// Handler for: `eth_sign`
     async fn sign(&self, address: Address, message: Bytes) -> Result<Bytes> {
         trace!(target: "rpc::eth", ?address, ?message, "Serving eth_sign");
@@ -407,14 +465,25 @@ where
     }
 
     /// Handler for: `eth_signTransaction`
-    async fn sign_transaction(&self, _transaction: TransactionRequest) -> Result<Bytes> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn sign_transaction(&self, request: TransactionRequest) -> Result<Bytes> {
+        trace!(target: "rpc::eth", ?request, "Serving eth_signTransaction");
+        Ok(EthTransactions::sign_transaction(self, request).await?)
     }
 
     /// Handler for: `eth_signTypedData`
     async fn sign_typed_data(&self, address: Address, data: Value) -> Result<Bytes> {
         trace!(target: "rpc::eth", ?address, ?data, "Serving eth_signTypedData");
-        Ok(EthApi::sign_typed_data(self, data, address)?)
+        Ok(EthApi::sign_typed_data(self, data, address).await?)
+    }
+
+    /// Handler for: `eth_signAuthorization`
+    async fn sign_authorization(
+        &self,
+        address: Address,
+        request: AuthorizationRequest,
+    ) -> Result<SignedAuthorization> {
+        trace!(target: "rpc::eth", ?address, ?request, "Serving eth_signAuthorization");
+        Ok(EthApi::sign_authorization(self, address, request).await?)
     }
 
     /// Handler for: `eth_getProof`
@@ -476,7 +545,7 @@ mod tests {
         provider: P,
     ) -> EthApi<P, TestPool, NoopNetwork, EthEvmConfig> {
         let evm_config = EthEvmConfig::default();
-        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config);
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config.clone());
         let fee_history_cache =
             FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
 
@@ -1,29 +1,40 @@
 //! Contains RPC handler implementations specific to sign endpoints
 
+#[cfg(feature = "hardware-wallets")]
+use crate::eth::hardware_signer::{HardwareSigner, HardwareSignerError, HardwareWallet};
 use crate::{
     eth::{
         error::{EthResult, SignError},
+        keystore::{KeystoreError, KeystoreSigner},
+        remote_signer::{RemoteSigner, RemoteSignerApi, RemoteSignerError},
         signer::{DevSigner, EthSigner},
     },
-    EthApi,
+    EthApi, EthApiSpec,
 };
 use alloy_dyn_abi::TypedData;
-use reth_primitives::{Address, Bytes};
+use reth_evm::ConfigureEvm;
+use reth_network_api::NetworkInfo;
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, U256, U64};
+use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
+use reth_rpc_types::{Authorization, AuthorizationRequest, SignedAuthorization};
+use reth_transaction_pool::TransactionPool;
 use serde_json::Value;
+use std::{path::Path, time::Duration};
 
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig> {
     pub(crate) async fn sign(&self, account: Address, message: Bytes) -> EthResult<Bytes> {
         Ok(self.find_signer(&account)?.sign(account, &message).await?.to_hex_bytes())
     }
 
-    pub(crate) fn sign_typed_data(&self, data: Value, account: Address) -> EthResult<Bytes> {
+    pub(crate) async fn sign_typed_data(&self, data: Value, account: Address) -> EthResult<Bytes> {
         Ok(self
             .find_signer(&account)?
             .sign_typed_data(
                 account,
                 &serde_json::from_value::<TypedData>(data)
                     .map_err(|_| SignError::InvalidTypedData)?,
-            )?
+            )
+            .await?
             .to_hex_bytes())
     }
 
@@ -46,4 +57,94 @@ impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConf
         let mut signers = self.inner.signers.write();
         *signers = DevSigner::random_signers(20);
     }
+
+    /// Adds the accounts of the geth compatible keystore directory, unlocked with the passphrase
+    /// for the given duration, or until shutdown if `None`.
+    ///
+    /// Returns the number of loaded accounts.
+    pub fn with_keystore(
+        &mut self,
+        dir: &Path,
+        passphrase: Option<&str>,
+        unlock_duration: Option<Duration>,
+    ) -> Result<usize, KeystoreError> {
+        let signer = KeystoreSigner::load(dir, passphrase, unlock_duration)?;
+        let accounts = signer.accounts().len();
+        self.inner.signers.write().push(Box::new(signer.clone()));
+        *self.inner.keystore.write() = Some(signer);
+        Ok(accounts)
+    }
+
+    /// Adds the accounts of the external signing service at the URL, see [RemoteSignerApi].
+    ///
+    /// Returns the number of accounts of the service.
+    pub async fn with_remote_signer(
+        &mut self,
+        url: &str,
+        api: RemoteSignerApi,
+        auth_token: Option<&str>,
+    ) -> Result<usize, RemoteSignerError> {
+        let signer = RemoteSigner::connect(url, api, auth_token).await?;
+        let accounts = signer.accounts().len();
+        self.inner.signers.write().push(Box::new(signer));
+        Ok(accounts)
+    }
+
+    /// Adds the first `accounts` accounts of the hardware wallet.
+    ///
+    /// Returns the addresses of the accounts.
+    #[cfg(feature = "hardware-wallets")]
+    pub async fn with_hardware_wallet(
+        &mut self,
+        wallet: HardwareWallet,
+        accounts: usize,
+    ) -> Result<Vec<Address>, HardwareSignerError> {
+        let signer = HardwareSigner::connect(wallet, accounts).await?;
+        let addresses = signer.accounts();
+        self.inner.signers.write().push(Box::new(signer));
+        Ok(addresses)
+    }
+
+    /// Returns the keystore signer, if a keystore directory was added.
+    pub(crate) fn keystore(&self) -> Option<KeystoreSigner> {
+        self.inner.keystore.read().clone()
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
+where
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Pool: TransactionPool + Clone + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Signs the EIP-7702 authorization of the request with the account.
+    ///
+    /// If not set, the chain ID defaults to the chain of the node and the nonce to the next nonce
+    /// of the account, including its pending transactions.
+    pub(crate) async fn sign_authorization(
+        &self,
+        account: Address,
+        request: AuthorizationRequest,
+    ) -> EthResult<SignedAuthorization> {
+        let signer = self.find_signer(&account)?;
+        let chain_id = request.chain_id.unwrap_or_else(|| U256::from(self.chain_id().to::<u64>()));
+        let nonce = match request.nonce {
+            Some(nonce) => nonce,
+            None => {
+                let pending = Some(BlockId::Number(BlockNumberOrTag::Pending));
+                U64::from(self.get_transaction_count(account, pending)?.to::<u64>())
+            }
+        };
+
+        let authorization = Authorization { chain_id, address: request.address, nonce };
+        let signature = signer.sign_authorization(account, &authorization).await?;
+        Ok(SignedAuthorization {
+            authorization,
+            y_parity: U64::from(signature.odd_y_parity as u8),
+            r: signature.r,
+            s: signature.s,
+        })
+    }
 }
@@ -134,7 +134,8 @@ mod tests {
         let pool = testing_pool();
         let evm_config = EthEvmConfig::default();
 
-        let cache = EthStateCache::spawn(NoopProvider::default(), Default::default(), evm_config);
+        let cache =
+            EthStateCache::spawn(NoopProvider::default(), Default::default(), evm_config.clone());
         let eth_api = EthApi::new(
             NoopProvider::default(),
             pool.clone(),
@@ -144,7 +145,7 @@ mod tests {
             ETHEREUM_BLOCK_GAS_LIMIT,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
-            evm_config,
+            evm_config.clone(),
             None,
         );
         let address = Address::random();
@@ -159,7 +160,8 @@ mod tests {
         let account = ExtendedAccount::new(0, U256::ZERO).extend_storage(storage);
         mock_provider.add_account(address, account);
 
-        let cache = EthStateCache::spawn(mock_provider.clone(), Default::default(), evm_config);
+        let cache =
+            EthStateCache::spawn(mock_provider.clone(), Default::default(), evm_config.clone());
         let eth_api = EthApi::new(
             mock_provider.clone(),
             pool,
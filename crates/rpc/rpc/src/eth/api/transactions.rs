@@ -2,20 +2,27 @@
 use crate::{
     eth::{
         api::pending_block::PendingBlockEnv,
+        conditional::{
+            check_conditions, transaction_conditions, TransactionConditionalError,
+            MAX_CONDITIONAL_COST,
+        },
         error::{EthApiError, EthResult, RpcInvalidTransactionError, SignError},
-        revm_utils::{prepare_call_env, EvmOverrides},
+        revm_utils::{apply_bundle_state, prepare_call_env, EvmOverrides},
+        signer::EthSigner,
         utils::recover_raw_transaction,
     },
     EthApi, EthApiSpec,
 };
 use async_trait::async_trait;
 use reth_evm::ConfigureEvm;
+use reth_interfaces::provider::ProviderError;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
     eip4844::calc_blob_gasprice,
     revm::env::{fill_block_env_with_coinbase, tx_env_with_recovered},
     Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, Header,
-    IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders,
+    IntoRecoveredTransaction, PooledTransactionsElementEcRecovered, Receipt, SealedBlock,
+    SealedBlockWithSenders,
     TransactionKind::{Call, Create},
     TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, B256, U256,
 };
@@ -32,10 +39,11 @@ use reth_rpc_types::{
         LegacyTransactionRequest,
     },
     AnyReceiptEnvelope, AnyTransactionReceipt, Index, Log, ReceiptWithBloom, Transaction,
-    TransactionInfo, TransactionKind as RpcTransactionKind, TransactionReceipt, TransactionRequest,
-    TypedTransactionRequest, WithOtherFields,
+    TransactionConditional, TransactionInfo, TransactionKind as RpcTransactionKind,
+    TransactionReceipt, TransactionRequest, TypedTransactionRequest, WithOtherFields,
 };
 use reth_rpc_types_compat::transaction::from_recovered_with_block_context;
+use reth_tasks::pool::is_current_task_cancelled;
 use reth_transaction_pool::{TransactionOrigin, TransactionPool};
 use revm::{
     db::CacheDB,
@@ -45,7 +53,8 @@ use revm::{
     },
     GetInspector, Inspector,
 };
-use std::future::Future;
+use serde::{Deserialize, Serialize};
+use std::{fmt, future::Future, str::FromStr, time::Duration};
 
 #[cfg(feature = "optimism")]
 use crate::eth::api::optimism::OptimismTxMeta;
@@ -53,6 +62,8 @@ use crate::eth::api::optimism::OptimismTxMeta;
 use crate::eth::optimism::OptimismEthApiError;
 use crate::eth::revm_utils::FillableTransaction;
 #[cfg(feature = "optimism")]
+use reth_primitives::ChainSpec;
+#[cfg(feature = "optimism")]
 use reth_revm::optimism::RethL1BlockInfo;
 #[cfg(feature = "optimism")]
 use reth_rpc_types::OptimismTransactionReceiptFields;
@@ -63,6 +74,89 @@ use revm_primitives::db::{Database, DatabaseRef};
 /// Helper alias type for the state's [CacheDB]
 pub(crate) type StateCacheDB = CacheDB<StateProviderDatabase<StateProviderBox>>;
 
+/// The default time [EthTransactions::send_raw_transaction_sync] waits for the receipt.
+pub const DEFAULT_TX_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The default interval in which [EthTransactions::send_raw_transaction_sync] checks for the
+/// receipt.
+pub const DEFAULT_TX_CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Settings for waiting for the receipt of a transaction submitted with
+/// [EthTransactions::send_raw_transaction_sync].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TransactionConfirmationConfig {
+    /// How long to wait for the transaction to be included in a canonical block.
+    pub timeout: Duration,
+    /// How often to check whether the transaction was included.
+    pub poll_interval: Duration,
+}
+
+impl TransactionConfirmationConfig {
+    /// Sets how long to wait for the transaction to be included in a canonical block.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how often to check whether the transaction was included.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+impl Default for TransactionConfirmationConfig {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TX_CONFIRMATION_TIMEOUT,
+            poll_interval: DEFAULT_TX_CONFIRMATION_POLL_INTERVAL,
+        }
+    }
+}
+
+/// How the nonce of a transaction signed by the node is chosen if the request doesn't set one,
+/// see `eth_sendTransaction` and `eth_signTransaction`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NonceStrategy {
+    /// The next nonce of the account in the latest block, ignoring transactions of the account in
+    /// the pool. This replaces the lowest pending transaction of the account.
+    NextOnchain,
+    /// The nonce after the highest nonce of the account's transactions in the pool, or the next
+    /// nonce of the account in the latest block if it has none.
+    #[default]
+    NextIncludingPool,
+    /// The request must set the nonce. Transactions with a nonce gap are queued in the pool until
+    /// the gap is filled, so that wallets can pipeline their transactions.
+    Explicit,
+}
+
+impl FromStr for NonceStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "next-onchain" => Ok(Self::NextOnchain),
+            "next-including-pool" => Ok(Self::NextIncludingPool),
+            "explicit" => Ok(Self::Explicit),
+            _ => Err(format!(
+                "invalid nonce strategy: {s}, expected next-onchain, next-including-pool or \
+                 explicit"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for NonceStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NextOnchain => f.write_str("next-onchain"),
+            Self::NextIncludingPool => f.write_str("next-including-pool"),
+            Self::Explicit => f.write_str("explicit"),
+        }
+    }
+}
+
 /// Commonly used transaction related functions for the [EthApi] type in the `eth_` namespace.
 ///
 /// This includes utilities for transaction tracing, transacting and inspection.
@@ -268,10 +362,39 @@ pub trait EthTransactions: Send + Sync {
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256>;
 
+    /// Submits the transaction like [EthTransactions::send_raw_transaction] and waits until it is
+    /// included in a canonical block.
+    ///
+    /// Returns the receipt of the transaction, or an error if it isn't included within the
+    /// configured [TransactionConfirmationConfig::timeout].
+    async fn send_raw_transaction_sync(&self, tx: Bytes) -> EthResult<AnyTransactionReceipt>;
+
+    /// Decodes and recovers the transactions and submits them to the pool in a single batch.
+    ///
+    /// Returns the hash or the error of every transaction, in the order of the given
+    /// transactions.
+    async fn send_raw_transactions(&self, txs: Vec<Bytes>) -> Vec<EthResult<B256>>;
+
+    /// Submits the transaction like [EthTransactions::send_raw_transaction] if the conditions hold
+    /// for the next block and the latest state.
+    ///
+    /// The payload builder only includes the transaction in a block the conditions hold for. The
+    /// transaction is removed from the pool once the conditions no longer hold for the block after
+    /// a new canonical block.
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256>;
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<B256>;
 
+    /// Signs transaction with a matching signer, if any, and returns the signed transaction in its
+    /// network encoding, which includes the sidecar of blob transactions.
+    async fn sign_transaction(&self, request: TransactionRequest) -> EthResult<Bytes>;
+
     /// Prepares the state and env for the given [TransactionRequest] at the given [BlockId] and
     /// executes the closure on a new task returning the result of the closure.
     ///
@@ -738,31 +861,20 @@ where
     }
 
     async fn transaction_by_hash(&self, hash: B256) -> EthResult<Option<TransactionSource>> {
-        // Try to find the transaction on disk
-        let mut resp = self
-            .on_blocking_task(|this| async move {
-                match this.provider().transaction_by_hash_with_meta(hash)? {
-                    None => Ok(None),
-                    Some((tx, meta)) => {
-                        // Note: we assume this transaction is valid, because it's mined (or part of
-                        // pending block) and already. We don't need to
-                        // check for pre EIP-2 because this transaction could be pre-EIP-2.
-                        let transaction = tx
-                            .into_ecrecovered_unchecked()
-                            .ok_or(EthApiError::InvalidTransactionSignature)?;
-
-                        let tx = TransactionSource::Block {
-                            transaction,
-                            index: meta.index,
-                            block_hash: meta.block_hash,
-                            block_number: meta.block_number,
-                            base_fee: meta.base_fee,
-                        };
-                        Ok(Some(tx))
-                    }
-                }
-            })
-            .await?;
+        // Try to find the transaction on disk, the recovered transaction is cached
+        let mut resp = match self.cache().get_transaction(hash).await {
+            Ok(tx) => tx.map(|(transaction, meta)| TransactionSource::Block {
+                transaction,
+                index: meta.index,
+                block_hash: meta.block_hash,
+                block_number: meta.block_number,
+                base_fee: meta.base_fee,
+            }),
+            Err(ProviderError::SenderRecoveryError) => {
+                return Err(EthApiError::InvalidTransactionSignature)
+            }
+            Err(err) => return Err(err.into()),
+        };
 
         if resp.is_none() {
             // tx not found on disk, check pool
@@ -862,210 +974,106 @@ where
         Ok(hash)
     }
 
-    async fn send_transaction(&self, mut request: TransactionRequest) -> EthResult<B256> {
-        let from = match request.from {
-            Some(from) => from,
-            None => return Err(SignError::NoAccount.into()),
-        };
-
-        // set nonce if not already set before
-        if request.nonce.is_none() {
-            let nonce =
-                self.get_transaction_count(from, Some(BlockId::Number(BlockNumberOrTag::Pending)))?;
-            // note: `.to()` can't panic because the nonce is constructed from a `u64`
-            request.nonce = Some(nonce.to::<u64>());
-        }
-
-        let chain_id = self.chain_id();
-
-        let estimated_gas = self
-            .estimate_gas_at(request.clone(), BlockId::Number(BlockNumberOrTag::Pending), None)
-            .await?;
-        let gas_limit = estimated_gas;
-
-        let TransactionRequest {
-            to,
-            gas_price,
-            max_fee_per_gas,
-            max_priority_fee_per_gas,
-            gas,
-            value,
-            input: data,
-            nonce,
-            mut access_list,
-            max_fee_per_blob_gas,
-            blob_versioned_hashes,
-            sidecar,
-            ..
-        } = request;
-
-        // todo: remove this inlining after https://github.com/alloy-rs/alloy/pull/183#issuecomment-1928161285
-        let transaction = match (
-            gas_price,
-            max_fee_per_gas,
-            access_list.take(),
-            max_fee_per_blob_gas,
-            blob_versioned_hashes,
-            sidecar,
-        ) {
-            // legacy transaction
-            // gas price required
-            (Some(_), None, None, None, None, None) => {
-                Some(TypedTransactionRequest::Legacy(LegacyTransactionRequest {
-                    nonce: nonce.unwrap_or_default(),
-                    gas_price: U256::from(gas_price.unwrap_or_default()),
-                    gas_limit: U256::from(gas.unwrap_or_default()),
-                    value: value.unwrap_or_default(),
-                    input: data.into_input().unwrap_or_default(),
-                    kind: match to {
-                        Some(to) => RpcTransactionKind::Call(to),
-                        None => RpcTransactionKind::Create,
-                    },
-                    chain_id: None,
-                }))
-            }
-            // EIP2930
-            // if only accesslist is set, and no eip1599 fees
-            (_, None, Some(access_list), None, None, None) => {
-                Some(TypedTransactionRequest::EIP2930(EIP2930TransactionRequest {
-                    nonce: nonce.unwrap_or_default(),
-                    gas_price: U256::from(gas_price.unwrap_or_default()),
-                    gas_limit: U256::from(gas.unwrap_or_default()),
-                    value: value.unwrap_or_default(),
-                    input: data.into_input().unwrap_or_default(),
-                    kind: match to {
-                        Some(to) => RpcTransactionKind::Call(to),
-                        None => RpcTransactionKind::Create,
-                    },
-                    chain_id: 0,
-                    access_list,
-                }))
-            }
-            // EIP1559
-            // if 4844 fields missing
-            // gas_price, max_fee_per_gas, access_list, max_fee_per_blob_gas, blob_versioned_hashes,
-            // sidecar,
-            (None, _, _, None, None, None) => {
-                // Empty fields fall back to the canonical transaction schema.
-                Some(TypedTransactionRequest::EIP1559(EIP1559TransactionRequest {
-                    nonce: nonce.unwrap_or_default(),
-                    max_fee_per_gas: U256::from(max_fee_per_gas.unwrap_or_default()),
-                    max_priority_fee_per_gas: U256::from(
-                        max_priority_fee_per_gas.unwrap_or_default(),
-                    ),
-                    gas_limit: U256::from(gas.unwrap_or_default()),
-                    value: value.unwrap_or_default(),
-                    input: data.into_input().unwrap_or_default(),
-                    kind: match to {
-                        Some(to) => RpcTransactionKind::Call(to),
-                        None => RpcTransactionKind::Create,
-                    },
-                    chain_id: 0,
-                    access_list: access_list.unwrap_or_default(),
-                }))
-            }
-            // EIP4884
-            // all blob fields required
-            (
-                None,
-                _,
-                _,
-                Some(max_fee_per_blob_gas),
-                Some(blob_versioned_hashes),
-                Some(sidecar),
-            ) => {
-                // As per the EIP, we follow the same semantics as EIP-1559.
-                Some(TypedTransactionRequest::EIP4844(EIP4844TransactionRequest {
-                    chain_id: 0,
-                    nonce: nonce.unwrap_or_default(),
-                    max_priority_fee_per_gas: U256::from(
-                        max_priority_fee_per_gas.unwrap_or_default(),
-                    ),
-                    max_fee_per_gas: U256::from(max_fee_per_gas.unwrap_or_default()),
-                    gas_limit: U256::from(gas.unwrap_or_default()),
-                    value: value.unwrap_or_default(),
-                    input: data.into_input().unwrap_or_default(),
-                    kind: match to {
-                        Some(to) => RpcTransactionKind::Call(to),
-                        None => RpcTransactionKind::Create,
-                    },
-                    access_list: access_list.unwrap_or_default(),
-
-                    // eip-4844 specific.
-                    max_fee_per_blob_gas: U256::from(max_fee_per_blob_gas),
-                    blob_versioned_hashes,
-                    sidecar,
-                }))
-            }
-
-            _ => None,
-        };
-
-        let transaction = match transaction {
-            Some(TypedTransactionRequest::Legacy(mut req)) => {
-                req.chain_id = Some(chain_id.to());
-                req.gas_limit = gas_limit.saturating_to();
-                req.gas_price = self.legacy_gas_price(gas_price.map(U256::from)).await?;
-
-                TypedTransactionRequest::Legacy(req)
-            }
-            Some(TypedTransactionRequest::EIP2930(mut req)) => {
-                req.chain_id = chain_id.to();
-                req.gas_limit = gas_limit.saturating_to();
-                req.gas_price = self.legacy_gas_price(gas_price.map(U256::from)).await?;
-
-                TypedTransactionRequest::EIP2930(req)
+    async fn send_raw_transactions(&self, txs: Vec<Bytes>) -> Vec<EthResult<B256>> {
+        // the results of the transactions that were recovered are set after the batch was added
+        // to the pool
+        let mut results = Vec::with_capacity(txs.len());
+        let mut pool_transactions = Vec::with_capacity(txs.len());
+        for tx in txs {
+            if let Some(client) = self.inner.raw_transaction_forwarder.as_ref() {
+                if let Err(err) = client.forward_raw_transaction(&tx).await {
+                    results.push(Some(Err(err)));
+                    continue
+                }
             }
-            Some(TypedTransactionRequest::EIP1559(mut req)) => {
-                let (max_fee_per_gas, max_priority_fee_per_gas) = self
-                    .eip1559_fees(
-                        max_fee_per_gas.map(U256::from),
-                        max_priority_fee_per_gas.map(U256::from),
-                    )
-                    .await?;
-
-                req.chain_id = chain_id.to();
-                req.gas_limit = gas_limit.saturating_to();
-                req.max_fee_per_gas = max_fee_per_gas.saturating_to();
-                req.max_priority_fee_per_gas = max_priority_fee_per_gas.saturating_to();
-
-                TypedTransactionRequest::EIP1559(req)
+            match recover_raw_transaction(tx) {
+                Ok(recovered) => {
+                    pool_transactions
+                        .push(<Pool::Transaction>::from_recovered_pooled_transaction(recovered));
+                    results.push(None);
+                }
+                Err(err) => results.push(Some(Err(err))),
             }
-            Some(TypedTransactionRequest::EIP4844(mut req)) => {
-                let (max_fee_per_gas, max_priority_fee_per_gas) = self
-                    .eip1559_fees(
-                        max_fee_per_gas.map(U256::from),
-                        max_priority_fee_per_gas.map(U256::from),
-                    )
-                    .await?;
+        }
 
-                req.max_fee_per_gas = max_fee_per_gas;
-                req.max_priority_fee_per_gas = max_priority_fee_per_gas;
-                req.max_fee_per_blob_gas =
-                    self.eip4844_blob_fee(max_fee_per_blob_gas.map(U256::from)).await?;
+        // submit the transactions to the pool with a `Local` origin
+        let mut added = self
+            .pool()
+            .add_transactions(TransactionOrigin::Local, pool_transactions)
+            .await
+            .into_iter();
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    added.next().expect("one result per added transaction").map_err(Into::into)
+                })
+            })
+            .collect()
+    }
 
-                req.chain_id = chain_id.to();
-                req.gas_limit = gas_limit;
+    async fn send_raw_transaction_sync(&self, tx: Bytes) -> EthResult<AnyTransactionReceipt> {
+        let hash = EthTransactions::send_raw_transaction(self, tx).await?;
 
-                TypedTransactionRequest::EIP4844(req)
+        let TransactionConfirmationConfig { timeout, poll_interval } = self.inner.tx_confirmation;
+        let receipt = async {
+            loop {
+                if let Some(receipt) = EthTransactions::transaction_receipt(self, hash).await? {
+                    return EthResult::Ok(receipt)
+                }
+                tokio::time::sleep(poll_interval).await;
             }
-            None => return Err(EthApiError::ConflictingFeeFieldsInRequest),
         };
+        tokio::time::timeout(timeout, receipt)
+            .await
+            .map_err(|_| EthApiError::TransactionConfirmationTimeout { hash, timeout })?
+    }
 
-        let signed_tx = self.sign_request(&from, transaction)?;
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256> {
+        let cost = conditional.cost();
+        if cost > MAX_CONDITIONAL_COST {
+            return Err(TransactionConditionalError::CostExceeded {
+                cost,
+                max: MAX_CONDITIONAL_COST,
+            }
+            .into())
+        }
 
-        let recovered =
-            signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
+        let conditions = transaction_conditions(&conditional);
+        let conditions = self
+            .on_blocking_task(|this| async move {
+                let header = this
+                    .provider()
+                    .latest_header()?
+                    .ok_or_else(|| EthApiError::UnknownBlockNumber)?;
+                let state = this.latest_state()?;
+                check_conditions(&conditions, &header, &state)?;
+                Ok(conditions)
+            })
+            .await?;
 
-        let pool_transaction = match recovered.try_into() {
-            Ok(converted) => <Pool::Transaction>::from_recovered_pooled_transaction(converted),
-            Err(_) => return Err(EthApiError::TransactionConversionError),
-        };
+        // the conditions are tracked before the transaction enters the pool, so the payload
+        // builder never includes it without checking them
+        let hash = *recover_raw_transaction(tx.clone())?.into_transaction().hash();
+        self.pool()
+            .add_transaction_conditions(hash, conditions)
+            .map_err(TransactionConditionalError::from)?;
+        EthTransactions::send_raw_transaction(self, tx).await.inspect_err(|_| {
+            self.pool().remove_transaction_conditions(&hash);
+        })
+    }
 
-        // submit the transaction to the pool with a `Local` origin
-        let hash = self.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await?;
+    async fn send_transaction(&self, request: TransactionRequest) -> EthResult<B256> {
+        self.send_transaction_with_signer(request, None).await
+    }
 
-        Ok(hash)
+    async fn sign_transaction(&self, request: TransactionRequest) -> EthResult<Bytes> {
+        let recovered = self.sign_transaction_request(request, None).await?;
+        Ok(recovered.into_transaction().envelope_encoded())
     }
 
     async fn spawn_with_call_at<F, R>(
@@ -1079,13 +1087,23 @@ where
         F: FnOnce(StateCacheDB, EnvWithHandlerCfg) -> EthResult<R> + Send + 'static,
         R: Send + 'static,
     {
+        // calls on the pending block are executed on top of the transactions of the pool that are
+        // included in the locally built pending block
+        let pending_state = if at.is_pending() { self.local_pending_state().await? } else { None };
         let (cfg, block_env, at) = self.evm_env_at(at).await?;
+        // the latest block could have changed in the meantime
+        let pending_state = pending_state
+            .filter(|(parent_hash, _)| at == BlockId::Hash((*parent_hash).into()))
+            .map(|(_, state)| state);
         let this = self.clone();
         self.inner
             .blocking_task_pool
             .spawn(move || {
                 let state = this.state_at(at)?;
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                if let Some(pending_state) = pending_state {
+                    apply_bundle_state(&pending_state, &mut db);
+                }
 
                 let env = prepare_call_env(
                     cfg,
@@ -1311,6 +1329,9 @@ where
             let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
             while let Some((tx_info, tx)) = transactions.next() {
+                if is_current_task_cancelled() {
+                    return Err(EthApiError::Cancelled)
+                }
                 let env = EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), tx);
 
                 let mut inspector = inspector_setup();
@@ -1343,21 +1364,289 @@ where
     Network: NetworkInfo + Send + Sync + 'static,
     EvmConfig: ConfigureEvm + 'static,
 {
-    /// Spawns the given closure on a new blocking tracing task
-    async fn spawn_tracing_task_with<F, T>(&self, f: F) -> EthResult<T>
-    where
-        F: FnOnce(Self) -> EthResult<T> + Send + 'static,
-        T: Send + 'static,
-    {
-        let this = self.clone();
-        self.inner
-            .blocking_task_pool
-            .spawn(move || f(this))
-            .await
-            .map_err(|_| EthApiError::InternalBlockingTaskError)?
-    }
-}
-
+    /// Signs the transaction request and submits it to the pool.
+    ///
+    /// If no `signer` is given, the configured signer for the `from` account is used.
+    pub(crate) async fn send_transaction_with_signer(
+        &self,
+        request: TransactionRequest,
+        signer: Option<&dyn EthSigner>,
+    ) -> EthResult<B256> {
+        let recovered = self.sign_transaction_request(request, signer).await?;
+        let pool_transaction = <Pool::Transaction>::from_recovered_pooled_transaction(recovered);
+
+        // submit the transaction to the pool with a `Local` origin
+        let hash = self.pool().add_transaction(TransactionOrigin::Local, pool_transaction).await?;
+
+        Ok(hash)
+    }
+
+    /// Fills the missing fields of the request, resolves the blobs of blob transactions and signs
+    /// the transaction with a matching signer.
+    ///
+    /// The blobs of a blob transaction are either provided inline in the sidecar of the request, in
+    /// which case missing commitments and proofs are computed, or referenced by the versioned
+    /// hashes of blobs that were uploaded to the [LocalBlobStore](crate::eth::LocalBlobStore).
+    ///
+    /// If no `signer` is given, the configured signer for the `from` account is used.
+    async fn sign_transaction_request(
+        &self,
+        mut request: TransactionRequest,
+        signer: Option<&dyn EthSigner>,
+    ) -> EthResult<PooledTransactionsElementEcRecovered> {
+        let from = match request.from {
+            Some(from) => from,
+            None => return Err(SignError::NoAccount.into()),
+        };
+
+        // set nonce if not already set before
+        if request.nonce.is_none() {
+            let block_number = match self.nonce_strategy() {
+                NonceStrategy::NextOnchain => BlockNumberOrTag::Latest,
+                NonceStrategy::NextIncludingPool => BlockNumberOrTag::Pending,
+                NonceStrategy::Explicit => return Err(SignError::NoNonce.into()),
+            };
+            let nonce = self.get_transaction_count(from, Some(BlockId::Number(block_number)))?;
+            // note: `.to()` can't panic because the nonce is constructed from a `u64`
+            request.nonce = Some(nonce.to::<u64>());
+        }
+
+        let chain_id = self.chain_id();
+
+        let estimated_gas = self
+            .estimate_gas_at(request.clone(), BlockId::Number(BlockNumberOrTag::Pending), None)
+            .await?;
+        let gas_limit = estimated_gas;
+
+        let TransactionRequest {
+            to,
+            gas_price,
+            max_fee_per_gas,
+            max_priority_fee_per_gas,
+            gas,
+            value,
+            input: data,
+            nonce,
+            mut access_list,
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            sidecar,
+            ..
+        } = request;
+
+        // resolve the blobs of a blob transaction
+        let (blob_versioned_hashes, sidecar): (_, Option<reth_rpc_types::BlobTransactionSidecar>) =
+            match (blob_versioned_hashes, sidecar) {
+                (Some(versioned_hashes), None) => {
+                    let sidecar = self.inner.local_blob_store.sidecar(&versioned_hashes)?;
+                    (Some(versioned_hashes), Some(sidecar.into()))
+                }
+                (versioned_hashes, Some(sidecar)) => {
+                    let (blob_versioned_hashes, sidecar) =
+                        self.inner.local_blob_store.complete_sidecar(sidecar.into())?;
+                    if versioned_hashes.is_some_and(|hashes| hashes != blob_versioned_hashes) {
+                        return Err(SignError::BlobVersionedHashMismatch.into())
+                    }
+                    (Some(blob_versioned_hashes), Some(sidecar.into()))
+                }
+                (None, None) => (None, None),
+            };
+
+        // todo: remove this inlining after https://github.com/alloy-rs/alloy/pull/183#issuecomment-1928161285
+        let transaction = match (
+            gas_price,
+            max_fee_per_gas,
+            access_list.take(),
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            sidecar,
+        ) {
+            // legacy transaction
+            // gas price required
+            (Some(_), None, None, None, None, None) => {
+                Some(TypedTransactionRequest::Legacy(LegacyTransactionRequest {
+                    nonce: nonce.unwrap_or_default(),
+                    gas_price: U256::from(gas_price.unwrap_or_default()),
+                    gas_limit: U256::from(gas.unwrap_or_default()),
+                    value: value.unwrap_or_default(),
+                    input: data.into_input().unwrap_or_default(),
+                    kind: match to {
+                        Some(to) => RpcTransactionKind::Call(to),
+                        None => RpcTransactionKind::Create,
+                    },
+                    chain_id: None,
+                }))
+            }
+            // EIP2930
+            // if only accesslist is set, and no eip1599 fees
+            (_, None, Some(access_list), None, None, None) => {
+                Some(TypedTransactionRequest::EIP2930(EIP2930TransactionRequest {
+                    nonce: nonce.unwrap_or_default(),
+                    gas_price: U256::from(gas_price.unwrap_or_default()),
+                    gas_limit: U256::from(gas.unwrap_or_default()),
+                    value: value.unwrap_or_default(),
+                    input: data.into_input().unwrap_or_default(),
+                    kind: match to {
+                        Some(to) => RpcTransactionKind::Call(to),
+                        None => RpcTransactionKind::Create,
+                    },
+                    chain_id: 0,
+                    access_list,
+                }))
+            }
+            // EIP1559
+            // if 4844 fields missing
+            // gas_price, max_fee_per_gas, access_list, max_fee_per_blob_gas, blob_versioned_hashes,
+            // sidecar,
+            (None, _, _, None, None, None) => {
+                // Empty fields fall back to the canonical transaction schema.
+                Some(TypedTransactionRequest::EIP1559(EIP1559TransactionRequest {
+                    nonce: nonce.unwrap_or_default(),
+                    max_fee_per_gas: U256::from(max_fee_per_gas.unwrap_or_default()),
+                    max_priority_fee_per_gas: U256::from(
+                        max_priority_fee_per_gas.unwrap_or_default(),
+                    ),
+                    gas_limit: U256::from(gas.unwrap_or_default()),
+                    value: value.unwrap_or_default(),
+                    input: data.into_input().unwrap_or_default(),
+                    kind: match to {
+                        Some(to) => RpcTransactionKind::Call(to),
+                        None => RpcTransactionKind::Create,
+                    },
+                    chain_id: 0,
+                    access_list: access_list.unwrap_or_default(),
+                }))
+            }
+            // EIP4884
+            // all blob fields required
+            // the blob fee is filled in below if missing
+            (None, _, _, _, Some(blob_versioned_hashes), Some(sidecar)) => {
+                // As per the EIP, we follow the same semantics as EIP-1559.
+                Some(TypedTransactionRequest::EIP4844(EIP4844TransactionRequest {
+                    chain_id: 0,
+                    nonce: nonce.unwrap_or_default(),
+                    max_priority_fee_per_gas: U256::from(
+                        max_priority_fee_per_gas.unwrap_or_default(),
+                    ),
+                    max_fee_per_gas: U256::from(max_fee_per_gas.unwrap_or_default()),
+                    gas_limit: U256::from(gas.unwrap_or_default()),
+                    value: value.unwrap_or_default(),
+                    input: data.into_input().unwrap_or_default(),
+                    kind: match to {
+                        Some(to) => RpcTransactionKind::Call(to),
+                        None => RpcTransactionKind::Create,
+                    },
+                    access_list: access_list.unwrap_or_default(),
+
+                    // eip-4844 specific.
+                    max_fee_per_blob_gas: U256::from(max_fee_per_blob_gas.unwrap_or_default()),
+                    blob_versioned_hashes,
+                    sidecar,
+                }))
+            }
+
+            _ => None,
+        };
+
+        let transaction = match transaction {
+            Some(TypedTransactionRequest::Legacy(mut req)) => {
+                req.chain_id = Some(chain_id.to());
+                req.gas_limit = gas_limit.saturating_to();
+                req.gas_price = self.legacy_gas_price(gas_price.map(U256::from)).await?;
+
+                TypedTransactionRequest::Legacy(req)
+            }
+            Some(TypedTransactionRequest::EIP2930(mut req)) => {
+                req.chain_id = chain_id.to();
+                req.gas_limit = gas_limit.saturating_to();
+                req.gas_price = self.legacy_gas_price(gas_price.map(U256::from)).await?;
+
+                TypedTransactionRequest::EIP2930(req)
+            }
+            Some(TypedTransactionRequest::EIP1559(mut req)) => {
+                let (max_fee_per_gas, max_priority_fee_per_gas) = self
+                    .eip1559_fees(
+                        max_fee_per_gas.map(U256::from),
+                        max_priority_fee_per_gas.map(U256::from),
+                    )
+                    .await?;
+
+                req.chain_id = chain_id.to();
+                req.gas_limit = gas_limit.saturating_to();
+                req.max_fee_per_gas = max_fee_per_gas.saturating_to();
+                req.max_priority_fee_per_gas = max_priority_fee_per_gas.saturating_to();
+
+                TypedTransactionRequest::EIP1559(req)
+            }
+            Some(TypedTransactionRequest::EIP4844(mut req)) => {
+                let (max_fee_per_gas, max_priority_fee_per_gas) = self
+                    .eip1559_fees(
+                        max_fee_per_gas.map(U256::from),
+                        max_priority_fee_per_gas.map(U256::from),
+                    )
+                    .await?;
+
+                req.max_fee_per_gas = max_fee_per_gas;
+                req.max_priority_fee_per_gas = max_priority_fee_per_gas;
+                req.max_fee_per_blob_gas =
+                    self.eip4844_blob_fee(max_fee_per_blob_gas.map(U256::from)).await?;
+
+                req.chain_id = chain_id.to();
+                req.gas_limit = gas_limit;
+
+                TypedTransactionRequest::EIP4844(req)
+            }
+            None => return Err(EthApiError::ConflictingFeeFieldsInRequest),
+        };
+
+        // the sidecar is not part of the signed transaction
+        let sidecar = match &transaction {
+            TypedTransactionRequest::EIP4844(req) => Some(req.sidecar.clone()),
+            _ => None,
+        };
+
+        let signed_tx = match signer {
+            Some(signer) => signer.sign_transaction(transaction, &from).await?,
+            None => self.sign_request(&from, transaction).await?,
+        };
+
+        let recovered =
+            signed_tx.into_ecrecovered().ok_or(EthApiError::InvalidTransactionSignature)?;
+
+        match sidecar {
+            Some(sidecar) => PooledTransactionsElementEcRecovered::try_from_blob_transaction(
+                recovered,
+                sidecar.into(),
+            )
+            .map_err(|_| EthApiError::TransactionConversionError),
+            None => recovered.try_into().map_err(|_| EthApiError::TransactionConversionError),
+        }
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
+where
+    Pool: TransactionPool + Clone + 'static,
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Spawns the given closure on a new blocking tracing task
+    async fn spawn_tracing_task_with<F, T>(&self, f: F) -> EthResult<T>
+    where
+        F: FnOnce(Self) -> EthResult<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let this = self.clone();
+        self.inner
+            .blocking_task_pool
+            .spawn(move || f(this))
+            .await
+            .map_err(|_| EthApiError::InternalBlockingTaskError)?
+    }
+}
+
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
 where
     Pool: TransactionPool + Clone + 'static,
@@ -1485,32 +1774,38 @@ where
         l1_block_info: Option<L1BlockInfo>,
         block_timestamp: u64,
     ) -> EthResult<OptimismTxMeta> {
-        let Some(l1_block_info) = l1_block_info else { return Ok(OptimismTxMeta::default()) };
-
-        let (l1_fee, l1_data_gas) = if !tx.is_deposit() {
-            let envelope_buf = tx.envelope_encoded();
+        build_op_tx_meta(&self.inner.provider.chain_spec(), tx, l1_block_info, block_timestamp)
+    }
+}
 
-            let inner_l1_fee = l1_block_info
-                .l1_tx_data_fee(
-                    &self.inner.provider.chain_spec(),
-                    block_timestamp,
-                    &envelope_buf,
-                    tx.is_deposit(),
-                )
-                .map_err(|_| OptimismEthApiError::L1BlockFeeError)?;
-            let inner_l1_data_gas = l1_block_info
-                .l1_data_gas(&self.inner.provider.chain_spec(), block_timestamp, &envelope_buf)
-                .map_err(|_| OptimismEthApiError::L1BlockGasError)?;
-            (
-                Some(inner_l1_fee.saturating_to::<u128>()),
-                Some(inner_l1_data_gas.saturating_to::<u128>()),
-            )
-        } else {
-            (None, None)
-        };
+/// Builds the [OptimismTxMeta] of the transaction, see [EthApi::build_op_tx_meta].
+#[cfg(feature = "optimism")]
+pub(crate) fn build_op_tx_meta(
+    chain_spec: &ChainSpec,
+    tx: &TransactionSigned,
+    l1_block_info: Option<L1BlockInfo>,
+    block_timestamp: u64,
+) -> EthResult<OptimismTxMeta> {
+    let Some(l1_block_info) = l1_block_info else { return Ok(OptimismTxMeta::default()) };
+
+    let (l1_fee, l1_data_gas) = if !tx.is_deposit() {
+        let envelope_buf = tx.envelope_encoded();
+
+        let inner_l1_fee = l1_block_info
+            .l1_tx_data_fee(chain_spec, block_timestamp, &envelope_buf, tx.is_deposit())
+            .map_err(|_| OptimismEthApiError::L1BlockFeeError)?;
+        let inner_l1_data_gas = l1_block_info
+            .l1_data_gas(chain_spec, block_timestamp, &envelope_buf)
+            .map_err(|_| OptimismEthApiError::L1BlockGasError)?;
+        (
+            Some(inner_l1_fee.saturating_to::<u128>()),
+            Some(inner_l1_data_gas.saturating_to::<u128>()),
+        )
+    } else {
+        (None, None)
+    };
 
-        Ok(OptimismTxMeta::new(Some(l1_block_info), l1_fee, l1_data_gas))
-    }
+    Ok(OptimismTxMeta::new(Some(l1_block_info), l1_fee, l1_data_gas))
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
@@ -1521,20 +1816,15 @@ where
     Network: NetworkInfo + Send + Sync + 'static,
     EvmConfig: ConfigureEvm + 'static,
 {
-    pub(crate) fn sign_request(
+    pub(crate) async fn sign_request(
         &self,
         from: &Address,
         request: TypedTransactionRequest,
     ) -> EthResult<TransactionSigned> {
-        for signer in self.inner.signers.read().iter() {
-            if signer.is_signer_for(from) {
-                return match signer.sign_transaction(request, from) {
-                    Ok(tx) => Ok(tx),
-                    Err(e) => Err(e.into()),
-                }
-            }
-        }
-        Err(EthApiError::InvalidTransactionSignature)
+        // the signer is cloned out of the lock, since remote signers sign asynchronously
+        let signer =
+            self.find_signer(from).map_err(|_| EthApiError::InvalidTransactionSignature)?;
+        Ok(signer.sign_transaction(request, from).await?)
     }
 
     /// Get Transaction by [BlockId] and the index of the transaction within that Block.
@@ -1791,7 +2081,7 @@ mod tests {
     use reth_network_api::noop::NoopNetwork;
     use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex};
     use reth_provider::test_utils::NoopProvider;
-    use reth_tasks::pool::BlockingTaskPool;
+    use reth_tasks::{pool::BlockingTaskPool, TokioTaskExecutor};
     use reth_transaction_pool::test_utils::testing_pool;
 
     #[tokio::test]
@@ -1802,7 +2092,7 @@ mod tests {
         let pool = testing_pool();
 
         let evm_config = EthEvmConfig::default();
-        let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config);
+        let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config.clone());
         let fee_history_cache =
             FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
         let eth_api = EthApi::new(
@@ -1843,4 +2133,125 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[tokio::test]
+    async fn send_raw_transactions() {
+        let noop_provider = NoopProvider::default();
+        let pool = testing_pool();
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config.clone());
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        let eth_api = EthApi::new(
+            noop_provider,
+            pool.clone(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+            None,
+        );
+
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let tx = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+
+        let results = eth_api
+            .send_raw_transactions(vec![Bytes::new(), tx, Bytes::from_static(&[0x02, 0x01])])
+            .await;
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0], Err(EthApiError::EmptyRawTransactionData)));
+        let hash = results[1].as_ref().unwrap();
+        assert!(pool.get(hash).is_some(), "tx not found in the pool");
+        assert!(matches!(results[2], Err(EthApiError::FailedToDecodeSignedTransaction)));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn send_raw_transaction_sync_timeout() {
+        let noop_provider = NoopProvider::default();
+        let pool = testing_pool();
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config.clone());
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        let timeout = Duration::from_millis(50);
+        let eth_api = EthApi::with_spawner(
+            noop_provider,
+            pool.clone(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            Box::<TokioTaskExecutor>::default(),
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+            None,
+            Default::default(),
+            TransactionConfirmationConfig::default()
+                .timeout(timeout)
+                .poll_interval(Duration::from_millis(10)),
+            NonceStrategy::default(),
+            Default::default(),
+            Default::default(),
+        );
+
+        // https://etherscan.io/tx/0xa694b71e6c128a2ed8e2e0f6770bddbe52e3bb8f10e8472f9a79ab81497a8b5d
+        let tx = Bytes::from(hex!("02f871018303579880850555633d1b82520894eee27662c2b8eba3cd936a23f039f3189633e4c887ad591c62bdaeb180c080a07ea72c68abfb8fca1bd964f0f99132ed9280261bdca3e549546c0205e800f7d0a05b4ef3039e9c9b9babc179a1878fb825b5aaf5aed2fa8744854150157b08d6f3"));
+
+        // the transaction is never mined by the noop provider
+        let err = eth_api.send_raw_transaction_sync(tx).await.unwrap_err();
+        assert!(matches!(
+            err,
+            EthApiError::TransactionConfirmationTimeout { timeout: t, .. } if t == timeout
+        ));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sign_transaction_explicit_nonce() {
+        let noop_provider = NoopProvider::default();
+
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config.clone());
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        let eth_api = EthApi::with_spawner(
+            noop_provider,
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(noop_provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            Box::<TokioTaskExecutor>::default(),
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+            None,
+            Default::default(),
+            Default::default(),
+            NonceStrategy::Explicit,
+            Default::default(),
+            Default::default(),
+        );
+
+        let request = TransactionRequest { from: Some(Address::random()), ..Default::default() };
+        let err = eth_api.sign_transaction(request).await.unwrap_err();
+        assert!(matches!(err, EthApiError::Signing(SignError::NoNonce)));
+    }
+
+    #[test]
+    fn parse_nonce_strategy() {
+        for strategy in
+            [NonceStrategy::NextOnchain, NonceStrategy::NextIncludingPool, NonceStrategy::Explicit]
+        {
+            assert_eq!(strategy.to_string().parse::<NonceStrategy>().unwrap(), strategy);
+        }
+        assert!("pending".parse::<NonceStrategy>().is_err());
+    }
 }
@@ -0,0 +1,147 @@
+//! Index of the blobs of canonical blob transactions whose sidecars are still in the blob store.
+
+use futures::{Stream, StreamExt};
+use parking_lot::RwLock;
+use reth_primitives::{BlockNumber, TxHash, B256};
+use reth_provider::CanonStateNotification;
+use reth_transaction_pool::TransactionPool;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// The blob transactions of a block with the versioned hashes of their blobs.
+type BlockBlobs = Vec<(TxHash, Vec<B256>)>;
+
+/// Maps the versioned hashes of the blobs of canonical blob transactions to the transaction and
+/// the index of the blob in its sidecar, so the sidecar can be looked up in the blob store.
+///
+/// The index covers the blocks whose sidecars are still in the blob store, which keeps them until
+/// the block is finalized, or longer if a blob retention window is configured. It is kept up to
+/// date by [blob_sidecar_index_task].
+///
+/// This type is cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct BlobSidecarIndex {
+    inner: Arc<RwLock<BlobSidecarIndexInner>>,
+}
+
+#[derive(Debug, Default)]
+struct BlobSidecarIndexInner {
+    /// The blob transaction and the index of the blob in its sidecar, by versioned hash.
+    by_versioned_hash: HashMap<B256, (TxHash, usize)>,
+    /// The indexed blob transactions by block number.
+    blocks: BTreeMap<BlockNumber, BlockBlobs>,
+}
+
+impl BlobSidecarIndexInner {
+    fn remove_block(&mut self, number: BlockNumber) {
+        for (_, versioned_hashes) in self.blocks.remove(&number).unwrap_or_default() {
+            for versioned_hash in versioned_hashes {
+                self.by_versioned_hash.remove(&versioned_hash);
+            }
+        }
+    }
+}
+
+impl BlobSidecarIndex {
+    /// Returns the blob transaction with the blob of the versioned hash and the index of the blob
+    /// in its sidecar.
+    pub fn get(&self, versioned_hash: &B256) -> Option<(TxHash, usize)> {
+        self.inner.read().by_versioned_hash.get(versioned_hash).copied()
+    }
+
+    /// Indexes the blob transactions of the block, replacing the block with the same number.
+    pub fn insert_block(&self, number: BlockNumber, transactions: BlockBlobs) {
+        let mut inner = self.inner.write();
+        inner.remove_block(number);
+        if transactions.is_empty() {
+            return
+        }
+        for (tx_hash, versioned_hashes) in &transactions {
+            for (index, versioned_hash) in versioned_hashes.iter().enumerate() {
+                inner.by_versioned_hash.insert(*versioned_hash, (*tx_hash, index));
+            }
+        }
+        inner.blocks.insert(number, transactions);
+    }
+
+    /// Removes the blob transactions of the block from the index.
+    pub fn remove_block(&self, number: BlockNumber) {
+        self.inner.write().remove_block(number);
+    }
+
+    /// Returns the number of indexed blobs.
+    pub fn len(&self) -> usize {
+        self.inner.read().by_versioned_hash.len()
+    }
+
+    /// Returns `true` if no blobs are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().by_versioned_hash.is_empty()
+    }
+
+    /// Returns the oldest indexed block and one of its blob transactions.
+    fn oldest_block(&self) -> Option<(BlockNumber, TxHash)> {
+        let inner = self.inner.read();
+        let (number, transactions) = inner.blocks.first_key_value()?;
+        Some((*number, transactions.first()?.0))
+    }
+}
+
+/// Indexes the blob transactions of new canonical blocks and drops the oldest blocks once their
+/// sidecars were deleted from the blob store of the pool.
+pub async fn blob_sidecar_index_task<St, Pool>(index: BlobSidecarIndex, mut events: St, pool: Pool)
+where
+    St: Stream<Item = CanonStateNotification> + Unpin + 'static,
+    Pool: TransactionPool + 'static,
+{
+    while let Some(event) = events.next().await {
+        if let Some(reverted) = event.reverted() {
+            for number in reverted.blocks().keys() {
+                index.remove_block(*number);
+            }
+        }
+        for (number, block) in event.committed().blocks() {
+            let transactions = block
+                .body
+                .iter()
+                .filter_map(|tx| Some((tx.hash, tx.blob_versioned_hashes()?)))
+                .collect();
+            index.insert_block(*number, transactions);
+        }
+
+        // the blob store deletes the sidecars of finalized blocks in block order
+        while let Some((number, tx_hash)) = index.oldest_block() {
+            if matches!(pool.get_blob(tx_hash), Ok(Some(_))) {
+                break
+            }
+            index.remove_block(number);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_blocks() {
+        let index = BlobSidecarIndex::default();
+        let (a, b) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        index.insert_block(1, vec![(TxHash::with_last_byte(1), vec![a, b])]);
+        index.insert_block(2, vec![]);
+        assert_eq!(index.get(&b), Some((TxHash::with_last_byte(1), 1)));
+        assert_eq!(index.oldest_block(), Some((1, TxHash::with_last_byte(1))));
+
+        // a reorged block replaces the indexed block
+        index.insert_block(1, vec![(TxHash::with_last_byte(2), vec![a])]);
+        assert_eq!(index.get(&a), Some((TxHash::with_last_byte(2), 0)));
+        assert_eq!(index.get(&b), None);
+        assert_eq!(index.len(), 1);
+
+        index.remove_block(1);
+        assert!(index.is_empty());
+        assert_eq!(index.oldest_block(), None);
+    }
+}
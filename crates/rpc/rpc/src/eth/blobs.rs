@@ -0,0 +1,187 @@
+//! Local store for blobs that are referenced by blob transactions signed by the node.
+
+use crate::eth::error::SignError;
+use parking_lot::Mutex;
+use reth_primitives::{
+    constants::eip4844::MAINNET_KZG_TRUSTED_SETUP,
+    eip4844::kzg_to_versioned_hash,
+    kzg::{Blob, Bytes48, KzgCommitment, KzgProof, KzgSettings},
+    BlobTransactionSidecar, Bytes, B256,
+};
+use schnellru::{ByLength, LruMap};
+use std::sync::Arc;
+
+/// The default maximum number of blobs kept in the [LocalBlobStore].
+///
+/// With 128KiB per blob, this is ~16MiB.
+pub const DEFAULT_MAX_LOCAL_BLOBS: u32 = 128;
+
+/// A blob with its KZG commitment and proof.
+struct LocalBlob {
+    blob: Blob,
+    commitment: Bytes48,
+    proof: Bytes48,
+}
+
+/// Stores blobs uploaded via `reth_storeBlob`, so they can be referenced by their versioned hash
+/// in `eth_sendTransaction` and `eth_signTransaction` requests.
+///
+/// KZG commitments and proofs are computed with the configured [KzgSettings] when a blob is added.
+/// The store is bounded, least recently used blobs are evicted first.
+#[derive(Clone)]
+pub struct LocalBlobStore {
+    inner: Arc<LocalBlobStoreInner>,
+}
+
+struct LocalBlobStoreInner {
+    /// The KZG settings used to compute commitments and proofs.
+    kzg_settings: Arc<KzgSettings>,
+    /// Blobs by versioned hash.
+    blobs: Mutex<LruMap<B256, LocalBlob>>,
+}
+
+impl LocalBlobStore {
+    /// Creates a new store that keeps up to `max_blobs` blobs.
+    pub fn new(kzg_settings: Arc<KzgSettings>, max_blobs: u32) -> Self {
+        let inner = LocalBlobStoreInner {
+            kzg_settings,
+            blobs: Mutex::new(LruMap::new(ByLength::new(max_blobs))),
+        };
+        Self { inner: Arc::new(inner) }
+    }
+
+    /// Adds the raw blob to the store and returns its versioned hash.
+    pub fn store(&self, blob: Bytes) -> Result<B256, SignError> {
+        let blob = Blob::from_bytes(&blob).map_err(|_| SignError::InvalidBlob)?;
+        let (versioned_hash, local_blob) = self.commit(blob)?;
+        self.inner.blobs.lock().insert(versioned_hash, local_blob);
+        Ok(versioned_hash)
+    }
+
+    /// Returns the sidecar for the given versioned hashes from the stored blobs.
+    pub fn sidecar(&self, versioned_hashes: &[B256]) -> Result<BlobTransactionSidecar, SignError> {
+        let mut blobs = self.inner.blobs.lock();
+        let mut sidecar = BlobTransactionSidecar::default();
+        for versioned_hash in versioned_hashes {
+            let blob = blobs.get(versioned_hash).ok_or(SignError::UnknownBlob(*versioned_hash))?;
+            sidecar.blobs.push(blob.blob.clone());
+            sidecar.commitments.push(blob.commitment);
+            sidecar.proofs.push(blob.proof);
+        }
+        Ok(sidecar)
+    }
+
+    /// Completes the given sidecar by computing the commitments and proofs of its blobs, if they
+    /// are missing, and returns it together with the versioned hashes of its blobs.
+    ///
+    /// Given commitments and proofs are verified against the blobs.
+    pub fn complete_sidecar(
+        &self,
+        sidecar: BlobTransactionSidecar,
+    ) -> Result<(Vec<B256>, BlobTransactionSidecar), SignError> {
+        if sidecar.commitments.is_empty() && sidecar.proofs.is_empty() {
+            let mut completed = BlobTransactionSidecar::default();
+            let mut versioned_hashes = Vec::with_capacity(sidecar.blobs.len());
+            for blob in sidecar.blobs {
+                let (versioned_hash, blob) = self.commit(blob)?;
+                versioned_hashes.push(versioned_hash);
+                completed.blobs.push(blob.blob);
+                completed.commitments.push(blob.commitment);
+                completed.proofs.push(blob.proof);
+            }
+            return Ok((versioned_hashes, completed))
+        }
+
+        if sidecar.commitments.len() != sidecar.blobs.len() ||
+            sidecar.proofs.len() != sidecar.blobs.len()
+        {
+            return Err(SignError::InvalidBlob)
+        }
+        let valid = KzgProof::verify_blob_kzg_proof_batch(
+            &sidecar.blobs,
+            &sidecar.commitments,
+            &sidecar.proofs,
+            &self.inner.kzg_settings,
+        )
+        .map_err(|_| SignError::InvalidBlob)?;
+        if !valid {
+            return Err(SignError::InvalidBlob)
+        }
+        let versioned_hashes = sidecar
+            .commitments
+            .iter()
+            .map(|commitment| {
+                KzgCommitment::from_bytes(commitment.as_slice())
+                    .map(kzg_to_versioned_hash)
+                    .map_err(|_| SignError::InvalidBlob)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((versioned_hashes, sidecar))
+    }
+
+    /// Computes the commitment and proof of the blob.
+    fn commit(&self, blob: Blob) -> Result<(B256, LocalBlob), SignError> {
+        let kzg_settings = &self.inner.kzg_settings;
+        let commitment = KzgCommitment::blob_to_kzg_commitment(&blob, kzg_settings)
+            .map_err(|_| SignError::InvalidBlob)?;
+        let commitment_bytes = commitment.to_bytes();
+        let proof = KzgProof::compute_blob_kzg_proof(&blob, &commitment_bytes, kzg_settings)
+            .map_err(|_| SignError::InvalidBlob)?;
+
+        Ok((
+            kzg_to_versioned_hash(commitment),
+            LocalBlob { blob, commitment: commitment_bytes, proof: proof.to_bytes() },
+        ))
+    }
+}
+
+impl std::fmt::Debug for LocalBlobStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalBlobStore").field("blobs", &self.inner.blobs.lock().len()).finish()
+    }
+}
+
+impl Default for LocalBlobStore {
+    fn default() -> Self {
+        Self::new(Arc::clone(&MAINNET_KZG_TRUSTED_SETUP), DEFAULT_MAX_LOCAL_BLOBS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::constants::eip4844::BYTES_PER_BLOB;
+
+    #[test]
+    fn store_and_build_sidecar() {
+        let store = LocalBlobStore::default();
+        let blob = Bytes::from(vec![0u8; BYTES_PER_BLOB]);
+
+        let versioned_hash = store.store(blob.clone()).unwrap();
+        let sidecar = store.sidecar(&[versioned_hash]).unwrap();
+        assert_eq!(sidecar.blobs.len(), 1);
+
+        // inline blobs without commitments result in the same sidecar
+        let inline = BlobTransactionSidecar {
+            blobs: vec![Blob::from_bytes(&blob).unwrap()],
+            ..Default::default()
+        };
+        let (versioned_hashes, completed) = store.complete_sidecar(inline).unwrap();
+        assert_eq!(versioned_hashes, vec![versioned_hash]);
+        assert_eq!(completed, sidecar);
+
+        // given commitments and proofs are verified
+        let (_, valid) = store.complete_sidecar(sidecar.clone()).unwrap();
+        assert_eq!(valid, sidecar);
+        let mut other_blob = vec![0u8; BYTES_PER_BLOB];
+        other_blob[31] = 1;
+        let mut invalid = sidecar;
+        invalid.blobs[0] = Blob::from_bytes(&other_blob).unwrap();
+        assert!(matches!(store.complete_sidecar(invalid), Err(SignError::InvalidBlob)));
+
+        assert!(matches!(
+            store.sidecar(&[B256::ZERO]),
+            Err(SignError::UnknownBlob(hash)) if hash == B256::ZERO
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 //! `Eth` bundle implementation and helpers.
 
 use crate::eth::{
-    error::{EthApiError, EthResult, RpcInvalidTransactionError},
+    error::{EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
     revm_utils::FillableTransaction,
     utils::recover_raw_transaction,
     EthTransactions,
@@ -11,18 +11,23 @@ use reth_primitives::{
     constants::eip4844::MAINNET_KZG_TRUSTED_SETUP,
     keccak256,
     revm_primitives::db::{DatabaseCommit, DatabaseRef},
-    PooledTransactionsElement, U256,
+    Bytes, PooledTransactionsElement, TransactionSignedEcRecovered, B256, U256,
 };
 use reth_revm::database::StateProviderDatabase;
-use reth_rpc_api::EthCallBundleApiServer;
-use reth_rpc_types::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
+use reth_rpc_api::{EthCallBundleApiServer, EthSendBundleApiServer, MevSendBundleApiServer};
+use reth_rpc_types::{
+    BundleItem, CancelBundleRequest, EthBundleHash, EthCallBundle, EthCallBundleResponse,
+    EthCallBundleTransactionResult, EthSendBundle, SendBundleRequest, SendBundleResponse,
+};
 use reth_tasks::pool::BlockingTaskGuard;
+use reth_transaction_pool::{TransactionBundle, TransactionPool};
 use revm::{
     db::CacheDB,
-    primitives::{ResultAndState, TxEnv},
+    primitives::{ExecutionResult, ResultAndState, TxEnv},
 };
 use revm_primitives::{EnvWithHandlerCfg, MAX_BLOB_GAS_PER_BLOCK};
 use std::sync::Arc;
+use tracing::trace;
 
 /// `Eth` bundle implementation.
 pub struct EthBundle<Eth> {
@@ -46,7 +51,7 @@ where
     /// state, or it can be used to simulate a past block. The sender is responsible for signing the
     /// transactions and using the correct nonce and ensuring validity
     pub async fn call_bundle(&self, bundle: EthCallBundle) -> EthResult<EthCallBundleResponse> {
-        let EthCallBundle { txs, block_number, state_block_number, timestamp } = bundle;
+        let EthCallBundle { txs, block_number, state_block_number, timestamp, coinbase } = bundle;
         if txs.is_empty() {
             return Err(EthApiError::InvalidParams(
                 EthBundleError::EmptyBundleTransactions.to_string(),
@@ -95,6 +100,10 @@ where
             block_env.timestamp += U256::from(12);
         }
 
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+
         let state_block_number = block_env.number;
         // use the block number of the request
         block_env.number = U256::from(block_number);
@@ -155,13 +164,19 @@ where
                     // update the coinbase balance
                     coinbase_balance_before_tx = coinbase_balance_after_tx;
 
-                    // set the return data for the response
-                    let (value, revert) = if result.is_success() {
-                        let value = result.into_output().unwrap_or_default();
-                        (Some(value), None)
-                    } else {
-                        let revert = result.into_output().unwrap_or_default();
-                        (None, Some(revert))
+                    // set the return data and the failure reason for the response
+                    let (value, revert, error) = match result {
+                        ExecutionResult::Success { output, .. } => {
+                            (Some(output.into_data()), None, None)
+                        }
+                        ExecutionResult::Revert { output, .. } => {
+                            let error = RevertError::new(output.clone()).to_string();
+                            (None, Some(output), Some(error))
+                        }
+                        ExecutionResult::Halt { reason, .. } => {
+                            let error = RpcInvalidTransactionError::halt(reason, tx.gas_limit());
+                            (None, Some(Bytes::new()), Some(error.to_string()))
+                        }
                     };
 
                     let tx_res = EthCallBundleTransactionResult {
@@ -175,6 +190,7 @@ where
                         tx_hash: tx.hash(),
                         value,
                         revert,
+                        error,
                     };
                     results.push(tx_res);
 
@@ -242,6 +258,152 @@ impl<Eth> Clone for EthBundle<Eth> {
     }
 }
 
+/// Submits bundles to the bundle pool of the local payload builder, which includes them at the
+/// top of the blocks it builds.
+///
+/// Serves `eth_sendBundle`, `eth_cancelBundle` and `mev_sendBundle`.
+#[derive(Debug, Clone)]
+pub struct SendBundle<Pool> {
+    /// The transaction pool that holds the bundles.
+    pool: Pool,
+}
+
+impl<Pool> SendBundle<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    /// Create a new `SendBundle` instance.
+    pub const fn new(pool: Pool) -> Self {
+        Self { pool }
+    }
+
+    /// Adds a bundle submitted with `eth_sendBundle` to the bundle pool.
+    pub fn send_bundle(&self, bundle: EthSendBundle) -> EthResult<EthBundleHash> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+        } = bundle;
+        if block_number.to::<u64>() == 0 {
+            return Err(EthApiError::InvalidParams(
+                EthBundleError::BundleMissingBlockNumber.to_string(),
+            ))
+        }
+
+        let transactions = recover_bundle_transactions(txs)?;
+        let bundle = TransactionBundle::new(transactions, block_number.to())
+            .with_timestamps(min_timestamp, max_timestamp)
+            .with_reverting_tx_hashes(reverting_tx_hashes)
+            .with_replacement_uuid(replacement_uuid);
+        let bundle_hash = self.add_bundle(bundle)?;
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Adds a bundle submitted with `mev_sendBundle` to the bundle pool.
+    ///
+    /// Bundles that backrun other transactions or request refunds are rejected, because they
+    /// require matching with transactions the local builder does not know about.
+    pub fn send_mev_bundle(&self, request: SendBundleRequest) -> EthResult<SendBundleResponse> {
+        if request.validity.as_ref().is_some_and(|validity| {
+            validity.refund.as_ref().is_some_and(|refund| !refund.is_empty()) ||
+                validity.refund_config.as_ref().is_some_and(|config| !config.is_empty())
+        }) {
+            return Err(EthApiError::InvalidParams(EthBundleError::RefundsNotSupported.to_string()))
+        }
+
+        let mut txs = Vec::with_capacity(request.bundle_body.len());
+        let mut can_revert = Vec::new();
+        for item in request.bundle_body {
+            match item {
+                BundleItem::Tx { tx, can_revert: true } => {
+                    can_revert.push(txs.len());
+                    txs.push(tx);
+                }
+                BundleItem::Tx { tx, can_revert: false } => txs.push(tx),
+                BundleItem::Hash { .. } => {
+                    return Err(EthApiError::InvalidParams(
+                        EthBundleError::BackrunNotSupported.to_string(),
+                    ))
+                }
+            }
+        }
+
+        let transactions = recover_bundle_transactions(txs)?;
+        let reverting_tx_hashes =
+            can_revert.into_iter().map(|idx| transactions[idx].hash).collect();
+        let block_number = request.inclusion.block_number();
+        let bundle = TransactionBundle::new(transactions, block_number)
+            .with_max_block_number(request.inclusion.max_block_number().unwrap_or(block_number))
+            .with_reverting_tx_hashes(reverting_tx_hashes);
+        let bundle_hash = self.add_bundle(bundle)?;
+        Ok(SendBundleResponse { bundle_hash })
+    }
+
+    /// Removes the bundle with the given replacement uuid from the bundle pool.
+    pub fn cancel_bundle(&self, request: CancelBundleRequest) -> EthResult<()> {
+        if !self.pool.cancel_bundle(&request.bundle_hash) {
+            return Err(EthApiError::InvalidParams(EthBundleError::UnknownBundle.to_string()))
+        }
+        Ok(())
+    }
+
+    fn add_bundle(&self, bundle: TransactionBundle) -> EthResult<B256> {
+        let hash = self
+            .pool
+            .add_bundle(bundle)
+            .map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+        trace!(target: "rpc::eth", ?hash, "Added bundle to the bundle pool");
+        Ok(hash)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Pool> EthSendBundleApiServer for SendBundle<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Ok(SendBundle::send_bundle(self, bundle)?)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        Ok(SendBundle::cancel_bundle(self, request)?)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Pool> MevSendBundleApiServer for SendBundle<Pool>
+where
+    Pool: TransactionPool + 'static,
+{
+    async fn send_bundle(&self, request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        Ok(SendBundle::send_mev_bundle(self, request)?)
+    }
+}
+
+/// Recovers the signers of the raw transactions of a bundle.
+///
+/// Blob transactions are rejected because the bundle pool does not keep their sidecars.
+fn recover_bundle_transactions(txs: Vec<Bytes>) -> EthResult<Vec<TransactionSignedEcRecovered>> {
+    if txs.is_empty() {
+        return Err(EthApiError::InvalidParams(EthBundleError::EmptyBundleTransactions.to_string()))
+    }
+    txs.into_iter()
+        .map(|tx| {
+            let tx = recover_raw_transaction(tx)?.into_ecrecovered_transaction();
+            if tx.is_eip4844() {
+                return Err(EthApiError::InvalidParams(
+                    EthBundleError::Eip4844TransactionsNotSupported.to_string(),
+                ))
+            }
+            Ok(tx)
+        })
+        .collect()
+}
+
 /// [EthBundle] specific errors.
 #[derive(Debug, thiserror::Error)]
 pub enum EthBundleError {
@@ -255,4 +417,16 @@ pub enum EthBundleError {
     /// [MAX_BLOB_GAS_PER_BLOCK].
     #[error("blob gas usage exceeds the limit of {MAX_BLOB_GAS_PER_BLOCK} gas per block.")]
     Eip4844BlobGasExceeded,
+    /// Thrown if a bundle sent to the bundle pool contains a blob transaction.
+    #[error("blob transactions are not supported in bundles")]
+    Eip4844TransactionsNotSupported,
+    /// Thrown if a `mev_sendBundle` bundle backruns a transaction by its hash.
+    #[error("backrunning transactions by hash is not supported")]
+    BackrunNotSupported,
+    /// Thrown if a `mev_sendBundle` bundle requests refunds.
+    #[error("bundle refunds are not supported")]
+    RefundsNotSupported,
+    /// Thrown if no bundle with the replacement uuid of `eth_cancelBundle` exists.
+    #[error("unknown bundle")]
+    UnknownBundle,
 }
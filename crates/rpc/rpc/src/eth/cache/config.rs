@@ -21,6 +21,9 @@ pub const DEFAULT_RECEIPT_CACHE_MAX_LEN: u32 = 2000;
 /// Default cache size for the env cache: 1000 envs.
 pub const DEFAULT_ENV_CACHE_MAX_LEN: u32 = 1000;
 
+/// Default cache size for the transaction cache: 10000 transactions.
+pub const DEFAULT_TRANSACTION_CACHE_MAX_LEN: u32 = 10_000;
+
 /// Default number of concurrent database requests.
 pub const DEFAULT_CONCURRENT_DB_REQUESTS: usize = 512;
 
@@ -40,6 +43,10 @@ pub struct EthStateCacheConfig {
     ///
     /// Default is 1000.
     pub max_envs: u32,
+    /// Max number of recovered transactions in cache.
+    ///
+    /// Default is 10000.
+    pub max_transactions: u32,
     /// Max number of concurrent database requests.
     ///
     /// Default is 512.
@@ -52,6 +59,7 @@ impl Default for EthStateCacheConfig {
             max_blocks: DEFAULT_BLOCK_CACHE_MAX_LEN,
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
+            max_transactions: DEFAULT_TRANSACTION_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
         }
     }
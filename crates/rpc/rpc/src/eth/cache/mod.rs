@@ -5,11 +5,11 @@ use reth_evm::ConfigureEvm;
 use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
     Block, BlockHashOrNumber, BlockWithSenders, Receipt, SealedBlock, SealedBlockWithSenders,
-    TransactionSigned, TransactionSignedEcRecovered, B256,
+    TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TxHash, B256,
 };
 use reth_provider::{
     BlockReader, CanonStateNotification, Chain, EvmEnvProvider, StateProviderFactory,
-    TransactionVariant,
+    TransactionVariant, TransactionsProvider,
 };
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use revm::primitives::{BlockEnv, CfgEnv, CfgEnvWithHandlerCfg, SpecId};
@@ -47,6 +47,12 @@ type ReceiptsResponseSender = oneshot::Sender<ProviderResult<Option<Arc<Vec<Rece
 /// The type that can send the response to a requested env
 type EnvResponseSender = oneshot::Sender<ProviderResult<(CfgEnvWithHandlerCfg, BlockEnv)>>;
 
+/// A mined transaction with its recovered sender and its location in the chain.
+type MinedTransaction = (TransactionSignedEcRecovered, TransactionMeta);
+
+/// The type that can send the response to a requested mined transaction
+type TransactionResponseSender = oneshot::Sender<ProviderResult<Option<MinedTransaction>>>;
+
 type BlockLruCache<L> = MultiConsumerLruCache<
     B256,
     BlockWithSenders,
@@ -60,6 +66,9 @@ type ReceiptsLruCache<L> =
 type EnvLruCache<L> =
     MultiConsumerLruCache<B256, (CfgEnvWithHandlerCfg, BlockEnv), L, EnvResponseSender>;
 
+type TransactionLruCache<L> =
+    MultiConsumerLruCache<TxHash, MinedTransaction, L, TransactionResponseSender>;
+
 /// Provides async access to cached eth data
 ///
 /// This is the frontend for the async caching service which manages cached data on a different
@@ -78,6 +87,7 @@ impl EthStateCache {
         max_blocks: u32,
         max_receipts: u32,
         max_envs: u32,
+        max_transactions: u32,
         max_concurrent_db_operations: usize,
     ) -> (Self, EthStateCacheService<Provider, Tasks, EvmConfig>) {
         let (to_service, rx) = unbounded_channel();
@@ -86,6 +96,7 @@ impl EthStateCache {
             full_block_cache: BlockLruCache::new(max_blocks, "blocks"),
             receipts_cache: ReceiptsLruCache::new(max_receipts, "receipts"),
             evm_env_cache: EnvLruCache::new(max_envs, "evm_env"),
+            transaction_cache: TransactionLruCache::new(max_transactions, "transactions"),
             action_tx: to_service.clone(),
             action_rx: UnboundedReceiverStream::new(rx),
             action_task_spawner,
@@ -127,8 +138,13 @@ impl EthStateCache {
         Tasks: TaskSpawner + Clone + 'static,
         EvmConfig: ConfigureEvm + 'static,
     {
-        let EthStateCacheConfig { max_blocks, max_receipts, max_envs, max_concurrent_db_requests } =
-            config;
+        let EthStateCacheConfig {
+            max_blocks,
+            max_receipts,
+            max_envs,
+            max_transactions,
+            max_concurrent_db_requests,
+        } = config;
         let (this, service) = Self::create(
             provider,
             executor.clone(),
@@ -136,6 +152,7 @@ impl EthStateCache {
             max_blocks,
             max_receipts,
             max_envs,
+            max_transactions,
             max_concurrent_db_requests,
         );
         executor.spawn_critical("eth state cache", Box::pin(service));
@@ -262,6 +279,20 @@ impl EthStateCache {
         let _ = self.to_service.send(CacheAction::GetEnv { block_hash, response_tx });
         rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)?
     }
+
+    /// Requests the mined transaction with its recovered sender and its [TransactionMeta] for the
+    /// transaction hash.
+    ///
+    /// Returns `None` if the transaction is not in a canonical block. Transactions of reorged
+    /// blocks are removed from the cache.
+    pub async fn get_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> ProviderResult<Option<(TransactionSignedEcRecovered, TransactionMeta)>> {
+        let (response_tx, rx) = oneshot::channel();
+        let _ = self.to_service.send(CacheAction::GetTransaction { tx_hash, response_tx });
+        rx.await.map_err(|_| ProviderError::CacheServiceUnavailable)?
+    }
 }
 
 /// A task than manages caches for data required by the `eth` rpc implementation.
@@ -287,10 +318,12 @@ pub(crate) struct EthStateCacheService<
     LimitBlocks = ByLength,
     LimitReceipts = ByLength,
     LimitEnvs = ByLength,
+    LimitTransactions = ByLength,
 > where
     LimitBlocks: Limiter<B256, BlockWithSenders>,
     LimitReceipts: Limiter<B256, Arc<Vec<Receipt>>>,
     LimitEnvs: Limiter<B256, (CfgEnvWithHandlerCfg, BlockEnv)>,
+    LimitTransactions: Limiter<TxHash, MinedTransaction>,
 {
     /// The type used to lookup data from disk
     provider: Provider,
@@ -300,6 +333,8 @@ pub(crate) struct EthStateCacheService<
     receipts_cache: ReceiptsLruCache<LimitReceipts>,
     /// The LRU cache for revm environments
     evm_env_cache: EnvLruCache<LimitEnvs>,
+    /// The LRU cache for recovered mined transactions grouped by their hash
+    transaction_cache: TransactionLruCache<LimitTransactions>,
     /// Sender half of the action channel.
     action_tx: UnboundedSender<CacheAction>,
     /// Receiver half of the action channel.
@@ -392,10 +427,36 @@ where
         }
     }
 
+    fn on_new_transaction(
+        &mut self,
+        tx_hash: TxHash,
+        res: ProviderResult<Option<MinedTransaction>>,
+    ) {
+        if let Some(queued) = self.transaction_cache.remove(&tx_hash) {
+            // send the response to queued senders
+            for tx in queued {
+                let _ = tx.send(res.clone());
+            }
+        }
+
+        // cache mined transaction, a transaction that is not found yet can still be mined
+        if let Ok(Some(transaction)) = res {
+            self.transaction_cache.insert(tx_hash, transaction);
+        }
+    }
+
+    /// Removes the transactions of a reorged block, their [TransactionMeta] is no longer valid.
+    fn on_reorg_transactions(&mut self, block: &SealedBlockWithSenders) {
+        for transaction in &block.body {
+            self.transaction_cache.remove_cached(&transaction.hash());
+        }
+    }
+
     fn update_cached_metrics(&self) {
         self.full_block_cache.update_cached_metrics();
         self.receipts_cache.update_cached_metrics();
         self.evm_env_cache.update_cached_metrics();
+        self.transaction_cache.update_cached_metrics();
     }
 }
 
@@ -533,9 +594,47 @@ where
                                 }));
                             }
                         }
+                        CacheAction::GetTransaction { tx_hash, response_tx } => {
+                            // check if transaction is cached
+                            if let Some(transaction) = this.transaction_cache.get(&tx_hash).cloned()
+                            {
+                                let _ = response_tx.send(Ok(Some(transaction)));
+                                continue
+                            }
+
+                            // transaction is not in the cache, request it if this is the first
+                            // consumer
+                            if this.transaction_cache.queue(tx_hash, response_tx) {
+                                let provider = this.provider.clone();
+                                let action_tx = this.action_tx.clone();
+                                let rate_limiter = this.rate_limiter.clone();
+                                this.action_task_spawner.spawn_blocking(Box::pin(async move {
+                                    // Acquire permit
+                                    let _permit = rate_limiter.acquire().await;
+                                    let res = provider
+                                        .transaction_by_hash_with_meta(tx_hash)
+                                        .and_then(|maybe_tx| {
+                                            maybe_tx
+                                                .map(|(tx, meta)| {
+                                                    // mined transactions are valid, including
+                                                    // pre EIP-2 signatures
+                                                    tx.into_ecrecovered_unchecked()
+                                                        .map(|tx| (tx, meta))
+                                                        .ok_or(ProviderError::SenderRecoveryError)
+                                                })
+                                                .transpose()
+                                        });
+                                    let _ = action_tx
+                                        .send(CacheAction::TransactionResult { tx_hash, res });
+                                }));
+                            }
+                        }
                         CacheAction::ReceiptsResult { block_hash, res } => {
                             this.on_new_receipts(block_hash, res);
                         }
+                        CacheAction::TransactionResult { tx_hash, res } => {
+                            this.on_new_transaction(tx_hash, res);
+                        }
                         CacheAction::BlockWithSendersResult { block_hash, res } => match res {
                             Ok(Some(block_with_senders)) => {
                                 this.on_new_block(block_hash, Ok(Some(block_with_senders)));
@@ -577,6 +676,7 @@ where
                         }
                         CacheAction::RemoveReorgedChain { chain_change } => {
                             for block in chain_change.blocks {
+                                this.on_reorg_transactions(&block);
                                 this.on_reorg_block(block.hash(), Ok(Some(block.unseal())));
                             }
 
@@ -603,9 +703,11 @@ enum CacheAction {
     GetBlockTransactions { block_hash: B256, response_tx: BlockTransactionsResponseSender },
     GetEnv { block_hash: B256, response_tx: EnvResponseSender },
     GetReceipts { block_hash: B256, response_tx: ReceiptsResponseSender },
+    GetTransaction { tx_hash: TxHash, response_tx: TransactionResponseSender },
     BlockWithSendersResult { block_hash: B256, res: ProviderResult<Option<BlockWithSenders>> },
     ReceiptsResult { block_hash: B256, res: ProviderResult<Option<Arc<Vec<Receipt>>>> },
     EnvResult { block_hash: B256, res: Box<ProviderResult<(CfgEnvWithHandlerCfg, BlockEnv)>> },
+    TransactionResult { tx_hash: TxHash, res: ProviderResult<Option<MinedTransaction>> },
     CacheNewCanonicalChain { chain_change: ChainChange },
     RemoveReorgedChain { chain_change: ChainChange },
 }
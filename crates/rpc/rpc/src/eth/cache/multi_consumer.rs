@@ -91,6 +91,11 @@ where
         self.cache.insert(key, value)
     }
 
+    /// Removes the element from the map and returns it.
+    pub fn remove_cached(&mut self, key: &K) -> Option<V> {
+        self.cache.remove(key)
+    }
+
     /// Update metrics for the inner cache.
     #[inline]
     pub fn update_cached_metrics(&self) {
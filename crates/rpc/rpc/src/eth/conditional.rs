@@ -0,0 +1,186 @@
+//! Support for transactions submitted with `eth_sendRawTransactionConditional`.
+
+use crate::eth::error::{EthApiError, EthResult};
+use futures::{Stream, StreamExt};
+use reth_primitives::{BlockNumber, Header};
+use reth_provider::{CanonStateNotification, StateProvider, StateProviderFactory};
+use reth_rpc_types::{KnownAccount, TransactionConditional};
+use reth_transaction_pool::{
+    ConditionalPoolError, ConditionsError, TransactionConditions, TransactionPool,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::{debug, trace};
+
+/// The maximum cost of the known accounts of a conditional transaction, see
+/// [TransactionConditional::cost].
+pub const MAX_CONDITIONAL_COST: usize = 1000;
+
+/// Errors of conditional transactions that are rejected.
+#[derive(Debug, thiserror::Error)]
+pub enum TransactionConditionalError {
+    /// Thrown when the known accounts are too expensive to check
+    #[error("conditional cost {cost} exceeds the maximum of {max}")]
+    CostExceeded {
+        /// The cost of the known accounts.
+        cost: usize,
+        /// The maximum cost.
+        max: usize,
+    },
+    /// Thrown when the conditions don't hold for the next block
+    #[error(transparent)]
+    Conditions(ConditionsError),
+    /// Thrown when the pool can't track the conditions
+    #[error(transparent)]
+    Pool(#[from] ConditionalPoolError),
+}
+
+/// Converts the conditions of `eth_sendRawTransactionConditional` into the conditions the payload
+/// builder checks before including the transaction.
+pub fn transaction_conditions(conditional: &TransactionConditional) -> TransactionConditions {
+    let mut conditions = TransactionConditions {
+        block_number_min: conditional.block_number_min.map(|n| n.to()),
+        block_number_max: conditional.block_number_max.map(|n| n.to()),
+        timestamp_min: conditional.timestamp_min.map(|t| t.to()),
+        timestamp_max: conditional.timestamp_max.map(|t| t.to()),
+        ..Default::default()
+    };
+    for (address, known) in &conditional.known_accounts {
+        match known {
+            KnownAccount::StorageRoot(root) => {
+                conditions.storage_roots.insert(*address, *root);
+            }
+            KnownAccount::Slots(slots) => {
+                conditions.storage_slots.insert(*address, slots.clone());
+            }
+        }
+    }
+    conditions
+}
+
+/// Returns the number and the earliest plausible timestamp of the block after the given block.
+///
+/// A transaction submitted now can't be included before that block, whose timestamp is at least
+/// the current time.
+pub fn next_block(header: &Header) -> (BlockNumber, u64) {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    (header.number + 1, (header.timestamp + 1).max(now))
+}
+
+/// Checks the conditions against the block after the given block and the latest state.
+pub fn check_conditions(
+    conditions: &TransactionConditions,
+    header: &Header,
+    state: &dyn StateProvider,
+) -> EthResult<()> {
+    let (block_number, timestamp) = next_block(header);
+    match conditions.check(block_number, timestamp, state) {
+        Ok(()) => Ok(()),
+        Err(ConditionsError::Provider(err)) => Err(err.into()),
+        Err(err) => Err(TransactionConditionalError::Conditions(err).into()),
+    }
+}
+
+/// Removes conditional transactions from the pool once their conditions no longer hold for the
+/// block after the new canonical tip.
+pub async fn conditional_transactions_eviction_task<St, Provider, Pool>(
+    mut events: St,
+    provider: Provider,
+    pool: Pool,
+) where
+    St: Stream<Item = CanonStateNotification> + Unpin + 'static,
+    Provider: StateProviderFactory + 'static,
+    Pool: TransactionPool + 'static,
+{
+    while let Some(event) = events.next().await {
+        let conditionals = pool.all_transaction_conditions();
+        if conditionals.is_empty() {
+            continue
+        }
+        let state = match provider.latest() {
+            Ok(state) => state,
+            Err(err) => {
+                debug!(target: "rpc::eth", %err, "Failed to get latest state for conditionals");
+                continue
+            }
+        };
+
+        let header = &event.tip().header;
+        let mut stale = Vec::new();
+        for (hash, conditions) in conditionals {
+            match check_conditions(&conditions, header, &state) {
+                Ok(()) => {}
+                Err(EthApiError::TransactionConditional(err)) => {
+                    trace!(target: "rpc::eth", ?hash, %err, "Conditional transaction is stale");
+                    stale.push(hash);
+                }
+                Err(err) => {
+                    debug!(target: "rpc::eth", ?hash, %err, "Failed to check conditional");
+                }
+            }
+        }
+        if !stale.is_empty() {
+            debug!(target: "rpc::eth", count = stale.len(), "Evicting stale conditionals");
+            for hash in &stale {
+                pool.remove_transaction_conditions(hash);
+            }
+            pool.remove_transactions(stale);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, B256, U256, U64};
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn check_conditions_against_next_block() {
+        let address = Address::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(B256::with_last_byte(1), U256::from(2))]),
+        );
+        let header = Header { number: 10, timestamp: 1000, ..Default::default() };
+        let (block_number, timestamp) = next_block(&header);
+        assert_eq!(block_number, 11);
+        assert!(timestamp > header.timestamp);
+
+        let mut conditional =
+            TransactionConditional { block_number_max: Some(U64::from(11)), ..Default::default() };
+        conditional.known_accounts.insert(
+            address,
+            KnownAccount::Slots([(B256::with_last_byte(1), B256::with_last_byte(2))].into()),
+        );
+        check_conditions(&transaction_conditions(&conditional), &header, &provider).unwrap();
+
+        // the bounds hold for the latest block, but the transaction can't be included before the
+        // next block
+        conditional.block_number_max = Some(U64::from(10));
+        assert!(matches!(
+            check_conditions(&transaction_conditions(&conditional), &header, &provider),
+            Err(EthApiError::TransactionConditional(TransactionConditionalError::Conditions(
+                ConditionsError::BlockNumberOutOfRange(11)
+            )))
+        ));
+        conditional.block_number_max = None;
+        conditional.timestamp_max = Some(U64::from(header.timestamp));
+        assert!(matches!(
+            check_conditions(&transaction_conditions(&conditional), &header, &provider),
+            Err(EthApiError::TransactionConditional(TransactionConditionalError::Conditions(
+                ConditionsError::TimestampOutOfRange(_)
+            )))
+        ));
+
+        conditional.timestamp_max = None;
+        provider.add_account(address, ExtendedAccount::new(0, U256::ZERO));
+        assert!(matches!(
+            check_conditions(&transaction_conditions(&conditional), &header, &provider),
+            Err(EthApiError::TransactionConditional(TransactionConditionalError::Conditions(
+                ConditionsError::StorageSlotMismatch { .. }
+            )))
+        ));
+    }
+}
@@ -1,15 +1,26 @@
 //! Implementation specific Errors for the `eth_` namespace.
 
-use crate::result::{internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code};
+use crate::{
+    eth::{
+        conditional::TransactionConditionalError,
+        revert::{DecodedRevertReason, RevertDecoder, RevertErrorData},
+    },
+    result::{internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code},
+};
 use alloy_sol_types::decode_revert_reason;
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObject};
 use reth_interfaces::RethError;
-use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes, U256};
-use reth_revm::tracing::{js::JsInspectorError, MuxError};
+use reth_primitives::{revm_primitives::InvalidHeader, Address, Bytes, B256, U256};
+#[cfg(feature = "js-tracer")]
+use reth_revm::tracing::js::JsInspectorError;
+use reth_revm::tracing::MuxError;
 use reth_rpc_types::{error::EthRpcErrorCode, request::TransactionInputError, BlockError};
-use reth_transaction_pool::error::{
-    Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
-    PoolTransactionError,
+use reth_transaction_pool::{
+    error::{
+        Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
+        PoolTransactionError,
+    },
+    BlobStoreError,
 };
 use revm::primitives::{EVMError, ExecutionResult, HaltReason, OutOfGasError};
 use std::time::Duration;
@@ -100,9 +111,22 @@ pub enum EthApiError {
     /// Error thrown when a spawned blocking task failed to deliver an anticipated response
     #[error("internal eth error")]
     InternalEthError,
+    /// Error returned by a blocking task that stopped early because its result is no longer
+    /// awaited, e.g. because the call timed out.
+    #[error("request cancelled")]
+    Cancelled,
     /// Error thrown when a (tracing) call exceeds the configured timeout
     #[error("execution aborted (timeout = {0:?})")]
     ExecutionTimedOut(Duration),
+    /// Error thrown when a submitted transaction is not included in a block before the configured
+    /// timeout
+    #[error("transaction {hash} was not included in a block within {timeout:?}")]
+    TransactionConfirmationTimeout {
+        /// Hash of the submitted transaction.
+        hash: B256,
+        /// The configured timeout.
+        timeout: Duration,
+    },
     /// Internal Error thrown by the javascript tracer
     #[error("{0}")]
     InternalJsTracerError(String),
@@ -115,6 +139,12 @@ pub enum EthApiError {
     /// Error encountered when converting a transaction type
     #[error("Transaction conversion error")]
     TransactionConversionError,
+    /// Thrown when the conditions of a conditional transaction don't hold
+    #[error(transparent)]
+    TransactionConditional(#[from] TransactionConditionalError),
+    /// Thrown when the blob sidecars can't be read from the blob store
+    #[error(transparent)]
+    BlobStore(#[from] BlobStoreError),
     /// Error thrown when tracing with a muxTracer fails
     #[error(transparent)]
     MuxTracerError(#[from] MuxError),
@@ -163,15 +193,24 @@ impl From<EthApiError> for ErrorObject<'static> {
             err @ EthApiError::ExecutionTimedOut(_) => {
                 rpc_error_with_code(CALL_EXECUTION_FAILED_CODE, err.to_string())
             }
+            err @ EthApiError::TransactionConfirmationTimeout { .. } => {
+                internal_rpc_err(err.to_string())
+            }
             err @ EthApiError::InternalBlockingTaskError => internal_rpc_err(err.to_string()),
             err @ EthApiError::InternalEthError => internal_rpc_err(err.to_string()),
+            err @ EthApiError::Cancelled => internal_rpc_err(err.to_string()),
             err @ EthApiError::TransactionInputError(_) => invalid_params_rpc_err(err.to_string()),
+            err @ EthApiError::TransactionConditional(_) => {
+                rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), err.to_string())
+            }
+            err @ EthApiError::BlobStore(_) => internal_rpc_err(err.to_string()),
             EthApiError::Other(err) => err.to_rpc_error(),
             EthApiError::MuxTracerError(msg) => internal_rpc_err(msg.to_string()),
         }
     }
 }
 
+#[cfg(feature = "js-tracer")]
 impl From<JsInspectorError> for EthApiError {
     fn from(error: JsInspectorError) -> Self {
         match error {
@@ -400,14 +439,8 @@ impl RpcInvalidTransactionError {
 impl From<RpcInvalidTransactionError> for ErrorObject<'static> {
     fn from(err: RpcInvalidTransactionError) -> Self {
         match err {
-            RpcInvalidTransactionError::Revert(revert) => {
-                // include out data if some
-                rpc_err(
-                    revert.error_code(),
-                    revert.to_string(),
-                    revert.output.as_ref().map(|out| out.as_ref()),
-                )
-            }
+            // include out data if some
+            RpcInvalidTransactionError::Revert(revert) => revert.into_rpc_err(),
             err => rpc_err(err.error_code(), err.to_string(), None),
         }
     }
@@ -523,6 +556,10 @@ pub struct RevertError {
     ///
     /// Note: this is `None` if output was empty
     output: Option<Bytes>,
+    /// The revert reason decoded by a [RevertDecoder].
+    pub(crate) reason: Option<DecodedRevertReason>,
+    /// Whether the decoded reason is included in the error data, see [RevertDecoder].
+    pub(crate) structured_data: bool,
 }
 
 // === impl RevertError ==
@@ -532,22 +569,50 @@ impl RevertError {
     ///
     /// Note: this is intended to wrap an revm output
     pub fn new(output: Bytes) -> Self {
-        if output.is_empty() {
-            Self { output: None }
-        } else {
-            Self { output: Some(output) }
-        }
+        let output = if output.is_empty() { None } else { Some(output) };
+        Self { output, reason: None, structured_data: false }
+    }
+
+    /// Returns the output of the reverted transaction, if any.
+    pub fn output(&self) -> Option<&Bytes> {
+        self.output.as_ref()
+    }
+
+    /// Returns the decoded revert reason, if it was decoded by a [RevertDecoder].
+    pub fn reason(&self) -> Option<&DecodedRevertReason> {
+        self.reason.as_ref()
     }
 
     fn error_code(&self) -> i32 {
         EthRpcErrorCode::ExecutionError.code()
     }
+
+    /// Converts the error into a JSON-RPC error whose data is the output, or the output and the
+    /// decoded reason if structured data is enabled.
+    fn into_rpc_err(self) -> ErrorObject<'static> {
+        if !self.structured_data {
+            return rpc_err(self.error_code(), self.to_string(), self.output.as_deref())
+        }
+        let data = RevertErrorData { output: self.output.as_ref(), reason: self.reason.as_ref() };
+        ErrorObject::owned(
+            self.error_code(),
+            self.to_string(),
+            Some(
+                jsonrpsee::core::to_json_raw_value(&data)
+                    .expect("serializing revert data can't fail"),
+            ),
+        )
+    }
 }
 
 impl std::fmt::Display for RevertError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("execution reverted")?;
-        if let Some(reason) = self.output.as_ref().and_then(|bytes| decode_revert_reason(bytes)) {
+        if let Some(reason @ DecodedRevertReason::Custom { .. }) = &self.reason {
+            write!(f, ": {reason}")?;
+        } else if let Some(reason) =
+            self.output.as_ref().and_then(|bytes| decode_revert_reason(bytes))
+        {
             write!(f, ": {reason}")?;
         }
         Ok(())
@@ -669,6 +734,9 @@ pub enum SignError {
     /// Signer for requested account not found.
     #[error("unknown account")]
     NoAccount,
+    /// The account is known but its key could not be unlocked.
+    #[error("account is locked")]
+    AccountLocked,
     /// TypedData has invalid format.
     #[error("given typed data is not valid")]
     InvalidTypedData,
@@ -678,6 +746,27 @@ pub enum SignError {
     /// No chain ID was given.
     #[error("no chainid")]
     NoChainId,
+    /// No nonce was given but the nonce strategy requires an explicit nonce.
+    #[error("no nonce")]
+    NoNonce,
+    /// Blob referenced by its versioned hash is not in the local blob store.
+    #[error("unknown blob {0}")]
+    UnknownBlob(B256),
+    /// Blob is invalid or its KZG commitment or proof could not be computed.
+    #[error("invalid blob")]
+    InvalidBlob,
+    /// Versioned hashes of the request don't match the blobs.
+    #[error("blob versioned hashes don't match the blobs")]
+    BlobVersionedHashMismatch,
+    /// The remote signer rejected the request or returned an invalid response.
+    #[error("remote signer: {0}")]
+    Remote(String),
+    /// The hardware wallet rejected the request or is not connected.
+    #[error("hardware wallet: {0}")]
+    Hardware(String),
+    /// The signer of the account doesn't support the kind of signing.
+    #[error("{0} is not supported by the signer")]
+    Unsupported(&'static str),
 }
 
 /// Converts the evm [ExecutionResult] into a result where `Ok` variant is the output bytes if it is
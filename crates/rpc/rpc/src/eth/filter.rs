@@ -2,6 +2,9 @@ use super::cache::EthStateCache;
 use crate::{
     eth::{
         error::EthApiError,
+        filter_store::{
+            FilterStore, PersistedFilter, PersistedFilterKind, DEFAULT_MAX_PERSISTED_FILTERS,
+        },
         logs_utils::{self, append_matching_block_logs},
     },
     result::{rpc_error_with_code, ToRpcResult},
@@ -10,33 +13,51 @@ use crate::{
 use core::fmt;
 
 use async_trait::async_trait;
+use futures::StreamExt;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
-use reth_primitives::{ChainInfo, IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_primitives::{
+    BlockNumber, ChainInfo, Header, IntoRecoveredTransaction, PruneSegment, TxHash, B256, U64,
+};
+use reth_provider::{
+    BlockIdReader, BlockReader, EvmEnvProvider, LogIndexReader, ProviderError,
+    PruneCheckpointReader,
+};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{
-    BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
-    PendingTransactionFilterKind,
+    BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilterSet, FilteredParams,
+    Log, PendingTransactionFilterKind, ValueOrArray,
 };
 
-use reth_tasks::TaskSpawner;
+use reth_tasks::{pool::CancellationToken, TaskSpawner};
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use std::{
     collections::HashMap,
     iter::StepBy,
     ops::RangeInclusive,
+    path::PathBuf,
     sync::Arc,
     time::{Duration, Instant},
 };
 use tokio::{
-    sync::{mpsc::Receiver, Mutex},
+    sync::{mpsc::Receiver, oneshot, Mutex, Notify},
     time::MissedTickBehavior,
 };
-use tracing::trace;
+use tracing::{debug, trace, warn};
 
 /// The maximum number of headers we read at once when handling a range filter.
 const MAX_HEADERS_RANGE: u64 = 1_000; // with ~530bytes per header this is ~500kb
 
+/// The maximum number of shards of [MAX_HEADERS_RANGE] blocks of a range filter that are scanned
+/// concurrently.
+const MAX_CONCURRENT_LOG_SHARDS: usize = 8;
+
+/// The error code of a query that exceeds the limits of the node, see EIP-1474.
+const LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The number of blocks below the tip from which the log index is trusted if no block is finalized
+/// yet. Blocks above may have been reorged out before the index caught up.
+const LOG_INDEX_REORG_DEPTH: u64 = 64;
+
 /// `Eth` filter RPC implementation.
 pub struct EthFilter<Provider, Pool> {
     /// All nested fields bundled together
@@ -56,6 +77,9 @@ where
     /// See also [EthFilterConfig].
     ///
     /// This also spawns a task that periodically clears stale filters.
+    ///
+    /// If a filter store is configured, the filters of the store are installed and a task is
+    /// spawned that keeps the store up to date with the installed filters.
     pub fn new(
         provider: Provider,
         pool: Pool,
@@ -63,11 +87,20 @@ where
         config: EthFilterConfig,
         task_spawner: Box<dyn TaskSpawner>,
     ) -> Self {
-        let EthFilterConfig { max_blocks_per_filter, max_logs_per_response, stale_filter_ttl } =
-            config;
+        let EthFilterConfig {
+            max_blocks_per_filter,
+            max_logs_per_response,
+            stale_filter_ttl,
+            logs_query_timeout,
+            filter_store_path,
+            max_persisted_filters,
+        } = config;
+        let filter_store =
+            filter_store_path.map(|path| FilterStore::new(path, max_persisted_filters));
+        let active_filters = filter_store.as_ref().map(ActiveFilters::restore).unwrap_or_default();
         let inner = EthFilterInner {
             provider,
-            active_filters: Default::default(),
+            active_filters,
             pool,
             id_provider: Arc::new(EthSubscriptionIdProvider::default()),
             eth_cache,
@@ -77,6 +110,9 @@ where
             // if not set, use the max value, which is effectively no limit
             max_blocks_per_filter: max_blocks_per_filter.unwrap_or(u64::MAX),
             max_logs_per_response: max_logs_per_response.unwrap_or(usize::MAX),
+            logs_query_timeout,
+            filter_store,
+            filters_changed: Notify::new(),
         };
 
         let eth_filter = Self { inner: Arc::new(inner) };
@@ -89,6 +125,13 @@ where
             }),
         );
 
+        if let Some(store) = eth_filter.inner.filter_store.clone() {
+            let this = eth_filter.clone();
+            eth_filter.inner.task_spawner.spawn(Box::pin(async move {
+                this.persist_filters_on_change(store).await;
+            }));
+        }
+
         eth_filter
     }
 
@@ -112,24 +155,57 @@ where
     /// `stale_filter_ttl` at the given instant.
     pub async fn clear_stale_filters(&self, now: Instant) {
         trace!(target: "rpc::eth", "clear stale filters");
+        let mut evicted = false;
         self.active_filters().inner.lock().await.retain(|id, filter| {
             let is_valid = (now - filter.last_poll_timestamp) < self.inner.stale_filter_ttl;
 
             if !is_valid {
                 trace!(target: "rpc::eth", "evict filter with id: {:?}", id);
+                evicted = true;
             }
 
             is_valid
-        })
+        });
+        if evicted {
+            self.inner.on_filters_changed();
+        }
+    }
+
+    /// Endless future that writes the installed filters to the store whenever they changed.
+    ///
+    /// Changes that happen while the store is written are coalesced into the next write.
+    async fn persist_filters_on_change(&self, store: FilterStore) {
+        loop {
+            self.inner.filters_changed.notified().await;
+            let filters = self.active_filters().persisted(store.max_filters()).await;
+            let store = store.clone();
+            match tokio::task::spawn_blocking(move || store.save(&filters)).await {
+                Ok(Ok(())) => trace!(target: "rpc::eth::filter", "persisted filters"),
+                Ok(Err(err)) => {
+                    warn!(target: "rpc::eth::filter", %err, "Failed to persist filters")
+                }
+                Err(err) => warn!(target: "rpc::eth::filter", %err, "Failed to persist filters"),
+            }
+        }
     }
 }
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader
+        + BlockIdReader
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + LogIndexReader
+        + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
 {
+    /// Returns the logs matching the filter, like `eth_getLogs`.
+    pub async fn logs_for_filter(&self, filter: Filter) -> Result<Vec<Log>, FilterError> {
+        self.inner.logs_for_filter(filter).await
+    }
+
     /// Returns all the filter changes for the given id, if any
     pub async fn filter_changes(&self, id: FilterId) -> Result<FilterChanges, FilterError> {
         let info = self.inner.provider.chain_info()?;
@@ -155,6 +231,9 @@ where
 
             (block, filter.kind.clone())
         };
+        if kind.is_persistent() {
+            self.inner.on_filters_changed();
+        }
 
         match kind {
             FilterKind::PendingTransaction(filter) => Ok(filter.drain().await),
@@ -223,7 +302,12 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader
+        + BlockIdReader
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + LogIndexReader
+        + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -286,8 +370,11 @@ where
     async fn uninstall_filter(&self, id: FilterId) -> RpcResult<bool> {
         trace!(target: "rpc::eth", "Serving eth_uninstallFilter");
         let mut filters = self.inner.active_filters.inner.lock().await;
-        if filters.remove(&id).is_some() {
+        if let Some(filter) = filters.remove(&id) {
             trace!(target: "rpc::eth::filter", ?id, "uninstalled filter");
+            if filter.kind.is_persistent() {
+                self.inner.on_filters_changed();
+            }
             Ok(true)
         } else {
             Ok(false)
@@ -330,6 +417,8 @@ struct EthFilterInner<Provider, Pool> {
     max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a response
     max_logs_per_response: usize,
+    /// Maximum duration of a logs query over a range of blocks
+    logs_query_timeout: Option<Duration>,
     /// The async cache frontend for eth related data
     eth_cache: EthStateCache,
     /// maximum number of headers to read at once for range filter
@@ -338,15 +427,33 @@ struct EthFilterInner<Provider, Pool> {
     task_spawner: Box<dyn TaskSpawner>,
     /// Duration since the last filter poll, after which the filter is considered stale
     stale_filter_ttl: Duration,
+    /// The store the filters are persisted in, if configured.
+    filter_store: Option<FilterStore>,
+    /// Notifies the task persisting the filters that the installed filters changed.
+    filters_changed: Notify,
+}
+
+impl<Provider, Pool> EthFilterInner<Provider, Pool> {
+    /// Schedules writing the installed filters to the store, if one is configured.
+    fn on_filters_changed(&self) {
+        if self.filter_store.is_some() {
+            self.filters_changed.notify_one();
+        }
+    }
 }
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader
+        + BlockIdReader
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + LogIndexReader
+        + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
-    async fn logs_for_filter(&self, filter: Filter) -> Result<Vec<Log>, FilterError> {
+    async fn logs_for_filter(self: &Arc<Self>, filter: Filter) -> Result<Vec<Log>, FilterError> {
         match filter.block_option {
             FilterBlockOption::AtBlockHash(block_hash) => {
                 // for all matching logs in the block
@@ -355,6 +462,7 @@ where
                     .provider
                     .header_by_hash_or_number(block_hash.into())?
                     .ok_or(ProviderError::HeaderNotFound(block_hash.into()))?;
+                self.ensure_receipts_available(block.number)?;
 
                 // we also need to ensure that the receipts are available and return an error if
                 // not, in case the block hash been reorged
@@ -400,11 +508,28 @@ where
         }
     }
 
+    /// Returns an error if the receipts of the given block have been pruned, which means that its
+    /// logs are no longer available.
+    fn ensure_receipts_available(&self, block: BlockNumber) -> Result<(), FilterError> {
+        let pruned_block = self
+            .provider
+            .get_prune_checkpoint(PruneSegment::Receipts)?
+            .and_then(|checkpoint| checkpoint.block_number);
+
+        match pruned_block {
+            Some(pruned_block) if block <= pruned_block => {
+                Err(FilterError::ReceiptsPruned { earliest_block: pruned_block + 1 })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Installs a new filter and returns the new identifier.
     async fn install_filter(&self, kind: FilterKind) -> RpcResult<FilterId> {
         let last_poll_block_number = self.provider.best_block_number().to_rpc_result()?;
         let id = FilterId::from(self.id_provider.next_id());
         let mut filters = self.active_filters.inner.lock().await;
+        let is_persistent = kind.is_persistent();
         filters.insert(
             id.clone(),
             ActiveFilter {
@@ -413,6 +538,9 @@ where
                 kind,
             },
         );
+        if is_persistent {
+            self.on_filters_changed();
+        }
         Ok(id)
     }
 
@@ -420,9 +548,9 @@ where
     ///
     /// Returns an error if:
     ///  - underlying database error
-    ///  - amount of matches exceeds configured limit
+    ///  - the range, the amount of matches or the duration of the query exceeds configured limits
     async fn get_logs_in_block_range(
-        &self,
+        self: &Arc<Self>,
         filter: &Filter,
         from_block: u64,
         to_block: u64,
@@ -432,11 +560,16 @@ where
         let best_number = chain_info.best_number;
 
         if to_block - from_block > self.max_blocks_per_filter {
-            return Err(FilterError::QueryExceedsMaxBlocks(self.max_blocks_per_filter))
+            return Err(FilterError::QueryExceedsLimits {
+                limit: LogsQueryLimit::MaxBlocks(self.max_blocks_per_filter),
+                from: from_block,
+                to: from_block + self.max_blocks_per_filter,
+            })
         }
 
+        self.ensure_receipts_available(from_block)?;
+
         let mut all_logs = Vec::new();
-        let filter_params = FilteredParams::new(Some(filter.clone()));
 
         if (to_block == best_number) && (from_block == best_number) {
             // only one block to check and it's the current best block which we can fetch directly
@@ -449,7 +582,7 @@ where
                 logs_utils::append_matching_block_logs(
                     &mut all_logs,
                     &self.provider,
-                    &filter_params,
+                    &FilteredParams::new(Some(filter.clone())),
                     chain_info.into(),
                     &receipts,
                     false,
@@ -459,57 +592,237 @@ where
             return Ok(all_logs)
         }
 
-        // derive bloom filters from filter input, so we can check headers for matching logs
-        let address_filter = FilteredParams::address_filter(&filter.address);
-        let topics_filter = FilteredParams::topics_filter(&filter.topics);
+        // the range is split into shards of `max_headers_range` blocks that are scanned on the
+        // blocking pool concurrently, their logs are merged in order of the blocks
+        let filter = Arc::new(filter.clone());
+        let mut shards = futures::stream::iter(BlockRangeInclusiveIter::new(
+            from_block..=to_block,
+            self.max_headers_range,
+        ))
+        .map(|(from, to)| self.spawn_logs_shard(filter.clone(), from, to))
+        .buffered(MAX_CONCURRENT_LOG_SHARDS);
+
+        let deadline =
+            self.logs_query_timeout.map(|timeout| (timeout, tokio::time::Instant::now() + timeout));
+        // the last block whose logs have been merged
+        let mut last_merged_block = None;
+        let is_multi_block_range = from_block != to_block;
 
-        // loop over the range of new blocks and check logs if the filter matches the log's bloom
-        // filter
-        for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
-        {
-            let headers = self.provider.headers_range(from..=to)?;
-
-            for (idx, header) in headers.iter().enumerate() {
-                // only if filter matches
-                if FilteredParams::matches_address(header.logs_bloom, &address_filter) &&
-                    FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+        loop {
+            let shard = match deadline {
+                Some((timeout, deadline)) => tokio::time::timeout_at(deadline, shards.next())
+                    .await
+                    .map_err(|_| FilterError::QueryExceedsLimits {
+                        limit: LogsQueryLimit::Timeout(timeout),
+                        from: from_block,
+                        to: last_merged_block.unwrap_or(from_block),
+                    })?,
+                None => shards.next().await,
+            };
+            let Some(shard) = shard else { break };
+            let (shard_end, blocks) = shard?;
+
+            for (block, logs) in blocks {
+                // size check but only if range is multiple blocks, so we always return all logs of
+                // a single block
+                if is_multi_block_range && all_logs.len() + logs.len() > self.max_logs_per_response
                 {
-                    // these are consecutive headers, so we can use the parent hash of the next
-                    // block to get the current header's hash
-                    let block_hash = match headers.get(idx + 1) {
-                        Some(parent) => parent.parent_hash,
-                        None => self
+                    return Err(FilterError::QueryExceedsLimits {
+                        limit: LogsQueryLimit::MaxResults(self.max_logs_per_response),
+                        from: from_block,
+                        to: block.saturating_sub(1).max(from_block),
+                    })
+                }
+                all_logs.extend(logs);
+            }
+            last_merged_block = Some(shard_end);
+        }
+
+        Ok(all_logs)
+    }
+
+    /// Spawns a blocking task that scans the blocks of the _inclusive_ shard for logs matching the
+    /// filter.
+    ///
+    /// Returns the last block of the shard and the matching logs grouped by block.
+    async fn spawn_logs_shard(
+        self: &Arc<Self>,
+        filter: Arc<Filter>,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<(BlockNumber, Vec<(BlockNumber, Vec<Log>)>), FilterError> {
+        let this = Arc::clone(self);
+        let cancel = CancellationToken::new();
+        let task_cancel = cancel.clone();
+        // stops the scan once the query is dropped, e.g. because the call timed out
+        let _cancel_on_drop = cancel.drop_guard();
+        let (tx, rx) = oneshot::channel();
+        self.task_spawner.spawn_blocking(Box::pin(async move {
+            let _ = tx.send(this.logs_in_shard(&filter, from_block, to_block, &task_cancel));
+        }));
+        let blocks = rx.await.map_err(|_| FilterError::InternalError)??;
+        Ok((to_block, blocks))
+    }
+
+    /// Returns the logs matching the filter of the blocks in the _inclusive_ range, grouped by
+    /// block.
+    ///
+    /// The receipts are only read for blocks that may contain matching logs, according to the log
+    /// index for the blocks it covers, otherwise according to the logs bloom of the headers.
+    ///
+    /// Stops early if the scan is cancelled.
+    fn logs_in_shard(
+        &self,
+        filter: &Filter,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        cancel: &CancellationToken,
+    ) -> Result<Vec<(BlockNumber, Vec<Log>)>, FilterError> {
+        let filter_params = FilteredParams::new(Some(filter.clone()));
+
+        let (mut candidates, bloom_from_block) =
+            match self.indexed_blocks(filter, from_block, to_block)? {
+                Some((indexed_to_block, blocks)) => {
+                    let mut candidates = Vec::with_capacity(blocks.len());
+                    for number in blocks {
+                        let header = self
+                            .provider
+                            .header_by_number(number)?
+                            .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+                        let block_hash = self
                             .provider
-                            .block_hash(header.number)?
-                            .ok_or(ProviderError::HeaderNotFound(header.number.into()))?,
-                    };
-
-                    if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            &self.provider,
-                            &filter_params,
-                            BlockNumHash::new(header.number, block_hash),
-                            &receipts,
-                            false,
-                            header.timestamp,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(FilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
+                            .block_hash(number)?
+                            .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+                        candidates.push((header, block_hash));
                     }
+                    (candidates, indexed_to_block + 1)
+                }
+                None => (Vec::new(), from_block),
+            };
+        if bloom_from_block <= to_block {
+            candidates.extend(self.bloom_matching_blocks(filter, bloom_from_block, to_block)?);
+        }
+
+        let mut blocks = Vec::new();
+        for (header, block_hash) in candidates {
+            if cancel.is_cancelled() {
+                return Err(EthApiError::Cancelled.into())
+            }
+            if let Some(receipts) = self.provider.receipts_by_block(block_hash.into())? {
+                let mut logs = Vec::new();
+                append_matching_block_logs(
+                    &mut logs,
+                    &self.provider,
+                    &filter_params,
+                    BlockNumHash::new(header.number, block_hash),
+                    &receipts,
+                    false,
+                    header.timestamp,
+                )?;
+                if !logs.is_empty() {
+                    blocks.push((header.number, logs));
                 }
             }
         }
 
-        Ok(all_logs)
+        Ok(blocks)
+    }
+
+    /// Returns the last block of the _inclusive_ range covered by the log index and the blocks up
+    /// to it that may contain logs matching the filter, according to the log index.
+    ///
+    /// The index is only trusted up to the finalized block, or [LOG_INDEX_REORG_DEPTH] blocks
+    /// below the tip if no block is finalized yet, since it may still contain blocks that were
+    /// reorged out.
+    ///
+    /// Returns `None` if the filter matches all addresses and first topics, or if the log index is
+    /// not enabled or doesn't cover the start of the range.
+    fn indexed_blocks(
+        &self,
+        filter: &Filter,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Option<(BlockNumber, Vec<BlockNumber>)>, FilterError> {
+        let addresses = filter_set_values(&filter.address);
+        let topics = filter_set_values(&filter.topics[0]);
+        if addresses.is_empty() && topics.is_empty() {
+            return Ok(None)
+        }
+
+        let Some(checkpoint) = self.provider.log_index_checkpoint()? else { return Ok(None) };
+        let safe_block = match self.provider.finalized_block_number()? {
+            Some(finalized) => finalized,
+            None => self.provider.best_block_number()?.saturating_sub(LOG_INDEX_REORG_DEPTH),
+        };
+        let Some(indexed_to_block) =
+            indexed_range_end(from_block, to_block, checkpoint, safe_block)
+        else {
+            return Ok(None)
+        };
+
+        let blocks =
+            self.provider.blocks_with_logs(&addresses, &topics, from_block..=indexed_to_block)?;
+        Ok(Some((indexed_to_block, blocks)))
+    }
+
+    /// Returns the headers and hashes of the blocks of the _inclusive_ range whose logs bloom
+    /// matches the filter.
+    fn bloom_matching_blocks(
+        &self,
+        filter: &Filter,
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+    ) -> Result<Vec<(Header, B256)>, FilterError> {
+        // derive bloom filters from filter input, so we can check headers for matching logs
+        let address_filter = FilteredParams::address_filter(&filter.address);
+        let topics_filter = FilteredParams::topics_filter(&filter.topics);
+
+        let mut headers =
+            self.provider.headers_range(from_block..=to_block)?.into_iter().peekable();
+        let mut blocks = Vec::new();
+
+        while let Some(header) = headers.next() {
+            // only if filter matches
+            if !FilteredParams::matches_address(header.logs_bloom, &address_filter) ||
+                !FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+            {
+                continue
+            }
+
+            // these are consecutive headers, so we can use the parent hash of the next block to
+            // get the current header's hash
+            let block_hash = match headers.peek() {
+                Some(next) => next.parent_hash,
+                None => self
+                    .provider
+                    .block_hash(header.number)?
+                    .ok_or(ProviderError::HeaderNotFound(header.number.into()))?,
+            };
+            blocks.push((header, block_hash));
+        }
+
+        Ok(blocks)
+    }
+}
+
+/// Returns the last block of the _inclusive_ range that can be read from a log index with the
+/// given checkpoint, if any, without reading blocks above `safe_block`.
+fn indexed_range_end(
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    checkpoint: BlockNumber,
+    safe_block: BlockNumber,
+) -> Option<BlockNumber> {
+    let end = to_block.min(checkpoint).min(safe_block);
+    (end >= from_block).then_some(end)
+}
+
+/// Returns the values of the filter set, an empty set matches all values.
+fn filter_set_values<T: Clone + Eq + std::hash::Hash>(set: &FilterSet<T>) -> Vec<T> {
+    match set.to_value_or_array() {
+        Some(ValueOrArray::Value(value)) => vec![value],
+        Some(ValueOrArray::Array(values)) => values,
+        None => Vec::new(),
     }
 }
 
@@ -529,6 +842,17 @@ pub struct EthFilterConfig {
     /// A filter is considered stale if it has not been polled for longer than this duration and
     /// will be removed.
     pub stale_filter_ttl: Duration,
+    /// Maximum duration of a logs query over a range of blocks.
+    ///
+    /// If `None` then no limit is enforced.
+    pub logs_query_timeout: Option<Duration>,
+    /// The path of the file the log and block filters are persisted in, so they survive restarts
+    /// of the node.
+    ///
+    /// If `None` then filters are not persisted.
+    pub filter_store_path: Option<PathBuf>,
+    /// Maximum number of filters that are persisted, the most recently polled filters are kept.
+    pub max_persisted_filters: usize,
 }
 
 impl EthFilterConfig {
@@ -550,6 +874,24 @@ impl EthFilterConfig {
         self.stale_filter_ttl = duration;
         self
     }
+
+    /// Sets the maximum duration of a logs query over a range of blocks.
+    pub fn logs_query_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.logs_query_timeout = timeout;
+        self
+    }
+
+    /// Sets the path of the file the filters are persisted in.
+    pub fn filter_store_path(mut self, path: Option<PathBuf>) -> Self {
+        self.filter_store_path = path;
+        self
+    }
+
+    /// Sets the maximum number of filters that are persisted.
+    pub fn max_persisted_filters(mut self, num: usize) -> Self {
+        self.max_persisted_filters = num;
+        self
+    }
 }
 
 impl Default for EthFilterConfig {
@@ -559,6 +901,9 @@ impl Default for EthFilterConfig {
             max_logs_per_response: None,
             // 5min
             stale_filter_ttl: Duration::from_secs(5 * 60),
+            logs_query_timeout: None,
+            filter_store_path: None,
+            max_persisted_filters: DEFAULT_MAX_PERSISTED_FILTERS,
         }
     }
 }
@@ -569,6 +914,56 @@ pub struct ActiveFilters {
     inner: Arc<Mutex<HashMap<FilterId, ActiveFilter>>>,
 }
 
+impl ActiveFilters {
+    /// Installs the filters of the store.
+    ///
+    /// The restored filters are considered polled now, so clients have the full stale filter TTL
+    /// to reconnect.
+    fn restore(store: &FilterStore) -> Self {
+        let filters = match store.load() {
+            Ok(filters) => filters,
+            Err(err) => {
+                warn!(target: "rpc::eth::filter", %err, "Failed to restore filters");
+                Vec::new()
+            }
+        };
+        debug!(target: "rpc::eth::filter", count = filters.len(), "Restored filters");
+
+        let now = Instant::now();
+        let filters = filters
+            .into_iter()
+            .map(|PersistedFilter { id, block, kind }| {
+                let kind = match kind {
+                    PersistedFilterKind::Log(filter) => FilterKind::Log(filter),
+                    PersistedFilterKind::Block => FilterKind::Block,
+                };
+                (id, ActiveFilter { block, last_poll_timestamp: now, kind })
+            })
+            .collect();
+        Self { inner: Arc::new(Mutex::new(filters)) }
+    }
+
+    /// Returns up to `max_filters` of the filters that can be persisted, most recently polled
+    /// first.
+    async fn persisted(&self, max_filters: usize) -> Vec<PersistedFilter> {
+        let filters = self.inner.lock().await;
+        let mut persisted = filters
+            .iter()
+            .filter_map(|(id, filter)| {
+                let kind = match &filter.kind {
+                    FilterKind::Log(filter) => PersistedFilterKind::Log(filter.clone()),
+                    FilterKind::Block => PersistedFilterKind::Block,
+                    FilterKind::PendingTransaction(_) => return None,
+                };
+                let persisted = PersistedFilter { id: id.clone(), block: filter.block, kind };
+                Some((filter.last_poll_timestamp, persisted))
+            })
+            .collect::<Vec<_>>();
+        persisted.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+        persisted.into_iter().take(max_filters).map(|(_, filter)| filter).collect()
+    }
+}
+
 /// An installed filter
 #[derive(Debug)]
 struct ActiveFilter {
@@ -677,15 +1072,38 @@ enum FilterKind {
     PendingTransaction(PendingTransactionKind),
 }
 
+impl FilterKind {
+    /// Returns `true` if filters of this kind are persisted in the [FilterStore].
+    const fn is_persistent(&self) -> bool {
+        !matches!(self, Self::PendingTransaction(_))
+    }
+}
+
 /// Errors that can occur in the handler implementation
 #[derive(Debug, thiserror::Error)]
 pub enum FilterError {
     #[error("filter not found")]
     FilterNotFound(FilterId),
-    #[error("query exceeds max block range {0}")]
-    QueryExceedsMaxBlocks(u64),
-    #[error("query exceeds max results {0}")]
-    QueryExceedsMaxResults(usize),
+    /// Thrown if a logs query exceeds a configured limit.
+    ///
+    /// The error contains a smaller range, starting at the same block, that is expected to stay
+    /// within the limits.
+    #[error("query exceeds {limit}, try with this block range [{from:#x}, {to:#x}]")]
+    QueryExceedsLimits {
+        /// The exceeded limit.
+        limit: LogsQueryLimit,
+        /// The first block of the suggested range.
+        from: BlockNumber,
+        /// The last block of the suggested range.
+        to: BlockNumber,
+    },
+    /// Thrown if the logs of the requested blocks are not available because their receipts have
+    /// been pruned.
+    #[error("receipts have been pruned, earliest available block is {earliest_block}")]
+    ReceiptsPruned {
+        /// The lowest block whose logs are still available.
+        earliest_block: BlockNumber,
+    },
     #[error(transparent)]
     EthAPIError(#[from] EthApiError),
     /// Error thrown when a spawned task failed to deliver a response.
@@ -705,16 +1123,46 @@ impl From<FilterError> for jsonrpsee::types::error::ErrorObject<'static> {
                 rpc_error_with_code(jsonrpsee::types::error::INTERNAL_ERROR_CODE, err.to_string())
             }
             FilterError::EthAPIError(err) => err.into(),
-            err @ FilterError::QueryExceedsMaxBlocks(_) => {
-                rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
+            err @ FilterError::QueryExceedsLimits { limit, from, to } => {
+                let data = serde_json::json!({
+                    "limit": limit.to_string(),
+                    "from": U64::from(from),
+                    "to": U64::from(to),
+                });
+                jsonrpsee::types::error::ErrorObject::owned(
+                    LIMIT_EXCEEDED_CODE,
+                    err.to_string(),
+                    Some(data),
+                )
             }
-            err @ FilterError::QueryExceedsMaxResults(_) => {
+            err @ FilterError::ReceiptsPruned { .. } => {
                 rpc_error_with_code(jsonrpsee::types::error::INVALID_PARAMS_CODE, err.to_string())
             }
         }
     }
 }
 
+/// The limits of logs queries, see [EthFilterConfig].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogsQueryLimit {
+    /// The maximum number of blocks of the range.
+    MaxBlocks(u64),
+    /// The maximum number of logs of the response.
+    MaxResults(usize),
+    /// The maximum duration of the query.
+    Timeout(Duration),
+}
+
+impl fmt::Display for LogsQueryLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxBlocks(max) => write!(f, "max block range {max}"),
+            Self::MaxResults(max) => write!(f, "max results {max}"),
+            Self::Timeout(timeout) => write!(f, "timeout of {timeout:?}"),
+        }
+    }
+}
+
 impl From<ProviderError> for FilterError {
     fn from(err: ProviderError) -> Self {
         FilterError::EthAPIError(err.into())
@@ -775,4 +1223,40 @@ mod tests {
             assert_eq!(end, *range.end());
         }
     }
+
+    #[test]
+    fn indexed_range_end_stops_at_checkpoint_and_safe_block() {
+        // the index covers the range
+        assert_eq!(indexed_range_end(10, 20, 30, 40), Some(20));
+        // the rest of the range is above the checkpoint
+        assert_eq!(indexed_range_end(10, 20, 15, 40), Some(15));
+        // the rest of the range may have been reorged out
+        assert_eq!(indexed_range_end(10, 20, 30, 12), Some(12));
+        assert_eq!(indexed_range_end(10, 20, 15, 12), Some(12));
+        // the index doesn't cover the start of the range
+        assert_eq!(indexed_range_end(10, 20, 9, 40), None);
+        assert_eq!(indexed_range_end(10, 20, 30, 9), None);
+        assert_eq!(indexed_range_end(10, 10, 10, 10), Some(10));
+    }
+
+    #[test]
+    fn query_exceeds_limits_error() {
+        let err = FilterError::QueryExceedsLimits {
+            limit: LogsQueryLimit::MaxResults(10_000),
+            from: 16,
+            to: 31,
+        };
+        assert_eq!(
+            err.to_string(),
+            "query exceeds max results 10000, try with this block range [0x10, 0x1f]"
+        );
+
+        let err = jsonrpsee::types::error::ErrorObject::from(err);
+        assert_eq!(err.code(), LIMIT_EXCEEDED_CODE);
+        let data: serde_json::Value = serde_json::from_str(err.data().unwrap().get()).unwrap();
+        assert_eq!(
+            data,
+            serde_json::json!({ "limit": "max results 10000", "from": "0x10", "to": "0x1f" })
+        );
+    }
 }
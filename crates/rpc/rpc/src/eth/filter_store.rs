@@ -0,0 +1,147 @@
+//! On-disk store of installed filters, so they survive restarts of the node.
+
+use reth_primitives::BlockNumber;
+use reth_rpc_types::{Filter, FilterId};
+use serde::{Deserialize, Serialize};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+/// The default maximum number of filters kept in the [FilterStore].
+pub const DEFAULT_MAX_PERSISTED_FILTERS: usize = 1024;
+
+/// A filter as it is stored in the [FilterStore].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PersistedFilter {
+    /// The identifier of the filter.
+    pub id: FilterId,
+    /// The block from which the next poll fetches the changes of the filter.
+    pub block: BlockNumber,
+    /// What kind of filter it is.
+    pub kind: PersistedFilterKind,
+}
+
+/// The kinds of filters that can be persisted.
+///
+/// Pending transaction filters are not persisted, since the pending transactions of the pool are
+/// not retained across restarts either.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "filter", rename_all = "camelCase")]
+pub enum PersistedFilterKind {
+    /// A log filter installed with `eth_newFilter`.
+    Log(Box<Filter>),
+    /// A block filter installed with `eth_newBlockFilter`.
+    Block,
+}
+
+/// Stores the installed filters in a JSON file, so that clients polling filters with
+/// `eth_getFilterChanges` can continue where they left off after the node restarted.
+///
+/// The store is bounded, if there are more filters than `max_filters`, only the most recently
+/// polled ones are kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterStore {
+    /// The path of the JSON file.
+    path: PathBuf,
+    /// The maximum number of stored filters.
+    max_filters: usize,
+}
+
+impl FilterStore {
+    /// Creates a new store at the given path that keeps up to `max_filters` filters.
+    pub fn new(path: impl Into<PathBuf>, max_filters: usize) -> Self {
+        Self { path: path.into(), max_filters }
+    }
+
+    /// Returns the path of the JSON file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the maximum number of stored filters.
+    pub const fn max_filters(&self) -> usize {
+        self.max_filters
+    }
+
+    /// Loads the stored filters.
+    ///
+    /// Returns no filters if the file doesn't exist yet.
+    pub fn load(&self) -> Result<Vec<PersistedFilter>, FilterStoreError> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(FilterStoreError::Io(err, self.path.clone())),
+        };
+        let mut filters: Vec<PersistedFilter> = serde_json::from_str(&contents)
+            .map_err(|err| FilterStoreError::Parse(err, self.path.clone()))?;
+        filters.truncate(self.max_filters);
+        Ok(filters)
+    }
+
+    /// Replaces the stored filters with the given filters, ordered by priority.
+    ///
+    /// Filters beyond `max_filters` are dropped. The file is replaced atomically, so a crash while
+    /// saving leaves the previously stored filters intact.
+    pub fn save(&self, filters: &[PersistedFilter]) -> Result<(), FilterStoreError> {
+        let filters = &filters[..filters.len().min(self.max_filters)];
+        let contents = serde_json::to_vec(filters)
+            .map_err(|err| FilterStoreError::Parse(err, self.path.clone()))?;
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|err| FilterStoreError::Io(err, self.path.clone()))?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        std::fs::write(&tmp_path, contents)
+            .and_then(|_| std::fs::rename(&tmp_path, &self.path))
+            .map_err(|err| FilterStoreError::Io(err, self.path.clone()))
+    }
+}
+
+/// Errors that can occur when loading or saving the [FilterStore].
+#[derive(Debug, thiserror::Error)]
+pub enum FilterStoreError {
+    /// The file could not be read or written.
+    #[error("failed to access filter store {1:?}: {0}")]
+    Io(io::Error, PathBuf),
+    /// The file does not contain valid filters.
+    #[error("failed to parse filter store {1:?}: {0}")]
+    Parse(serde_json::Error, PathBuf),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load_filters() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FilterStore::new(dir.path().join("filters/filters.json"), 2);
+        assert!(store.load().unwrap().is_empty());
+
+        let filters = vec![
+            PersistedFilter {
+                id: FilterId::Str("0x1".to_string()),
+                block: 10,
+                kind: PersistedFilterKind::Log(Box::new(Filter::new().from_block(5))),
+            },
+            PersistedFilter {
+                id: FilterId::Str("0x2".to_string()),
+                block: 11,
+                kind: PersistedFilterKind::Block,
+            },
+            PersistedFilter {
+                id: FilterId::Str("0x3".to_string()),
+                block: 12,
+                kind: PersistedFilterKind::Block,
+            },
+        ];
+        store.save(&filters).unwrap();
+        assert_eq!(store.load().unwrap(), filters[..2]);
+
+        store.save(&[]).unwrap();
+        assert!(store.load().unwrap().is_empty());
+    }
+}
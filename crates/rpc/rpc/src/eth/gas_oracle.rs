@@ -6,11 +6,16 @@ use crate::eth::{
     error::{EthApiError, EthResult, RpcInvalidTransactionError},
 };
 use derive_more::{Deref, DerefMut};
+use parking_lot::RwLock;
 use reth_primitives::{constants::GWEI_TO_WEI, BlockNumberOrTag, B256, U256};
 use reth_provider::BlockReaderIdExt;
 use schnellru::{ByLength, LruMap};
 use serde::{Deserialize, Serialize};
-use std::fmt::{self, Debug, Formatter};
+use std::{
+    fmt::{self, Debug, Formatter},
+    str::FromStr,
+    sync::Arc,
+};
 use tokio::sync::Mutex;
 use tracing::warn;
 
@@ -57,6 +62,10 @@ pub struct GasPriceOracleConfig {
 
     /// The minimum gas price, under which the sample will be ignored
     pub ignore_price: Option<U256>,
+
+    /// The algorithm the priority fee is suggested with
+    #[serde(default)]
+    pub strategy: GasPriceOracleStrategy,
 }
 
 impl Default for GasPriceOracleConfig {
@@ -69,8 +78,123 @@ impl Default for GasPriceOracleConfig {
             default: None,
             max_price: Some(DEFAULT_MAX_GAS_PRICE),
             ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
+            strategy: GasPriceOracleStrategy::default(),
+        }
+    }
+}
+
+/// The built-in algorithms the priority fee is suggested with, see [PriorityFeeStrategy].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GasPriceOracleStrategy {
+    /// The configured percentile of the lowest effective tips of the recent blocks, like geth,
+    /// see [PercentileStrategy].
+    #[default]
+    Percentile,
+    /// The percentile of the recent blocks, raised to the tip that is required to be included in
+    /// the next block if the pending transactions of the pool don't fit into it, see
+    /// [PoolPressureStrategy].
+    PoolPressure,
+}
+
+impl GasPriceOracleStrategy {
+    /// Returns the implementation of the strategy.
+    pub fn into_strategy(self) -> Arc<dyn PriorityFeeStrategy> {
+        match self {
+            Self::Percentile => Arc::new(PercentileStrategy),
+            Self::PoolPressure => Arc::new(PoolPressureStrategy),
+        }
+    }
+}
+
+impl FromStr for GasPriceOracleStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "percentile" => Ok(Self::Percentile),
+            "pool-pressure" => Ok(Self::PoolPressure),
+            _ => Err(format!(
+                "invalid gas price oracle strategy: {s}, expected percentile or pool-pressure"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for GasPriceOracleStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Percentile => f.write_str("percentile"),
+            Self::PoolPressure => f.write_str("pool-pressure"),
+        }
+    }
+}
+
+/// An algorithm that suggests the priority fee of `eth_maxPriorityFeePerGas` and `eth_gasPrice`.
+pub trait PriorityFeeStrategy: Debug + Send + Sync {
+    /// Returns `true` if the strategy uses the [PriorityFeeInput::pending] transactions.
+    ///
+    /// The pending transactions are only collected from the pool if this returns `true`.
+    fn uses_pending_transactions(&self) -> bool {
+        false
+    }
+
+    /// Suggests the priority fee of a transaction for the next block.
+    fn suggest_tip_cap(&self, input: &PriorityFeeInput) -> U256;
+}
+
+/// The data a [PriorityFeeStrategy] suggests the priority fee from.
+#[derive(Debug, Clone, Default)]
+pub struct PriorityFeeInput {
+    /// The configured percentile of the lowest effective tips of the recent blocks.
+    pub percentile_tip: U256,
+    /// The gas limit of the next block.
+    pub gas_limit: u64,
+    /// The effective tips and gas limits of the best pending transactions of the pool at the base
+    /// fee of the next block, best first, until they exceed the gas limit of the next block.
+    pub pending: Vec<(u128, u64)>,
+}
+
+/// Suggests the [PriorityFeeInput::percentile_tip], see [GasPriceOracleStrategy::Percentile].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PercentileStrategy;
+
+impl PriorityFeeStrategy for PercentileStrategy {
+    fn suggest_tip_cap(&self, input: &PriorityFeeInput) -> U256 {
+        input.percentile_tip
+    }
+}
+
+/// Suggests a tip that outbids the first pending transaction that doesn't fit into the next block,
+/// if it is higher than the [PriorityFeeInput::percentile_tip], see
+/// [GasPriceOracleStrategy::PoolPressure].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoolPressureStrategy;
+
+impl PriorityFeeStrategy for PoolPressureStrategy {
+    fn uses_pending_transactions(&self) -> bool {
+        true
+    }
+
+    fn suggest_tip_cap(&self, input: &PriorityFeeInput) -> U256 {
+        let mut gas_used = 0u64;
+        for &(tip, gas_limit) in &input.pending {
+            gas_used = gas_used.saturating_add(gas_limit);
+            if gas_used > input.gas_limit {
+                return input.percentile_tip.max(U256::from(tip.saturating_add(1)))
+            }
         }
+        input.percentile_tip
+    }
+}
+
+/// Sanitizes the percentile of the config to be at most 100.
+fn sanitize_config(mut oracle_config: GasPriceOracleConfig) -> GasPriceOracleConfig {
+    if oracle_config.percentile > 100 {
+        warn!(prev_percentile = ?oracle_config.percentile, "Invalid configured gas price percentile, assuming 100.");
+        oracle_config.percentile = 100;
     }
+    oracle_config
 }
 
 /// Calculates a gas price depending on recent blocks.
@@ -81,12 +205,12 @@ pub struct GasPriceOracle<Provider> {
     /// The cache for blocks
     cache: EthStateCache,
     /// The config for the oracle
-    oracle_config: GasPriceOracleConfig,
-    /// The price under which the sample will be ignored.
-    ignore_price: Option<u128>,
+    oracle_config: RwLock<GasPriceOracleConfig>,
     /// Stores the latest calculated price and its block hash and Cache stores the lowest effective
     /// tip values of recent blocks
     inner: Mutex<GasPriceOracleInner>,
+    /// The strategy that is used instead of the configured [GasPriceOracleStrategy], if set
+    custom_strategy: Option<Arc<dyn PriorityFeeStrategy>>,
 }
 
 impl<Provider> GasPriceOracle<Provider>
@@ -96,15 +220,10 @@ where
     /// Creates and returns the [GasPriceOracle].
     pub fn new(
         provider: Provider,
-        mut oracle_config: GasPriceOracleConfig,
+        oracle_config: GasPriceOracleConfig,
         cache: EthStateCache,
     ) -> Self {
-        // sanitize the percentile to be less than 100
-        if oracle_config.percentile > 100 {
-            warn!(prev_percentile = ?oracle_config.percentile, "Invalid configured gas price percentile, assuming 100.");
-            oracle_config.percentile = 100;
-        }
-        let ignore_price = oracle_config.ignore_price.map(|price| price.saturating_to());
+        let oracle_config = sanitize_config(oracle_config);
 
         // this is the number of blocks that we will cache the values for
         let cached_values = (oracle_config.blocks * 5).max(oracle_config.max_block_history as u32);
@@ -115,21 +234,53 @@ where
             ))),
         });
 
-        Self { provider, oracle_config, cache, ignore_price, inner }
+        Self {
+            provider,
+            oracle_config: RwLock::new(oracle_config),
+            cache,
+            inner,
+            custom_strategy: None,
+        }
+    }
+
+    /// Suggests the priority fee with the given strategy instead of the configured
+    /// [GasPriceOracleStrategy].
+    pub fn with_strategy(mut self, strategy: impl PriorityFeeStrategy + 'static) -> Self {
+        self.custom_strategy = Some(Arc::new(strategy));
+        self
+    }
+
+    /// Returns the strategy the priority fee is suggested with.
+    pub fn strategy(&self) -> Arc<dyn PriorityFeeStrategy> {
+        match &self.custom_strategy {
+            Some(strategy) => strategy.clone(),
+            None => self.config().strategy.into_strategy(),
+        }
     }
 
     /// Returns the configuration of the gas price oracle.
-    pub fn config(&self) -> &GasPriceOracleConfig {
-        &self.oracle_config
+    pub fn config(&self) -> GasPriceOracleConfig {
+        self.oracle_config.read().clone()
+    }
+
+    /// Replaces the configuration of the gas price oracle.
+    ///
+    /// The new configuration is used for the estimate of the next block, the size of the cache of
+    /// the block values and the block values that are already cached are kept.
+    pub fn set_config(&self, oracle_config: GasPriceOracleConfig) {
+        *self.oracle_config.write() = sanitize_config(oracle_config);
     }
 
     /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
+    ///
+    /// This is the [PriorityFeeInput::percentile_tip] of the [PriorityFeeStrategy].
     pub async fn suggest_tip_cap(&self) -> EthResult<U256> {
         let header = self
             .provider
             .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
             .ok_or(EthApiError::UnknownBlockNumber)?;
 
+        let config = self.config();
         let mut inner = self.inner.lock().await;
 
         // if we have stored a last price, then we check whether or not it was for the same head
@@ -147,10 +298,10 @@ where
         let mut populated_blocks = 0;
 
         // we only check a maximum of 2 * max_block_history, or the number of blocks in the chain
-        let max_blocks = if self.oracle_config.max_block_history * 2 > header.number {
+        let max_blocks = if config.max_block_history * 2 > header.number {
             header.number
         } else {
-            self.oracle_config.max_block_history * 2
+            config.max_block_history * 2
         };
 
         for _ in 0..max_blocks {
@@ -161,7 +312,7 @@ where
                 } else {
                     // Otherwise we fetch it using get_block_values
                     let (parent_hash, block_values) = self
-                        .get_block_values(current_hash, SAMPLE_NUMBER)
+                        .get_block_values(current_hash, SAMPLE_NUMBER, config.ignore_price)
                         .await?
                         .ok_or(EthApiError::UnknownBlockNumber)?;
                     inner
@@ -178,7 +329,7 @@ where
             }
 
             // break when we have enough populated blocks
-            if populated_blocks >= self.oracle_config.blocks {
+            if populated_blocks >= config.blocks {
                 break
             }
 
@@ -188,7 +339,7 @@ where
         // sort results then take the configured percentile result
         let mut price = if !results.is_empty() {
             results.sort_unstable();
-            *results.get((results.len() - 1) * self.oracle_config.percentile as usize / 100).expect(
+            *results.get((results.len() - 1) * config.percentile as usize / 100).expect(
                 "gas price index is a percent of nonzero array length, so a value always exists",
             )
         } else {
@@ -196,7 +347,7 @@ where
         };
 
         // constrain to the max price
-        if let Some(max_price) = self.oracle_config.max_price {
+        if let Some(max_price) = config.max_price {
             if price > max_price {
                 price = max_price;
             }
@@ -207,9 +358,9 @@ where
         Ok(price)
     }
 
-    /// Get the `limit` lowest effective tip values for the given block. If the oracle has a
-    /// configured `ignore_price` threshold, then tip values under that threshold will be ignored
-    /// before returning a result.
+    /// Get the `limit` lowest effective tip values for the given block. If there is an
+    /// `ignore_price` threshold, then tip values under that threshold will be ignored before
+    /// returning a result.
     ///
     /// If the block cannot be found, then this will return `None`.
     ///
@@ -218,7 +369,10 @@ where
         &self,
         block_hash: B256,
         limit: usize,
+        ignore_price: Option<U256>,
     ) -> EthResult<Option<(B256, Vec<U256>)>> {
+        let ignore_price: Option<u128> = ignore_price.map(|price| price.saturating_to());
+
         // check the cache (this will hit the disk if the block is not cached)
         let mut block = match self.cache.get_block(block_hash).await? {
             Some(block) => block,
@@ -236,7 +390,7 @@ where
         for tx in block.body.iter() {
             let mut effective_gas_tip = None;
             // ignore transactions with a tip under the configured threshold
-            if let Some(ignore_under) = self.ignore_price {
+            if let Some(ignore_under) = ignore_price {
                 let tip = tx.effective_tip_per_gas(base_fee_per_gas);
                 effective_gas_tip = Some(tip);
                 if tip < Some(ignore_under) {
@@ -318,4 +472,29 @@ mod tests {
     fn ignore_price_sanity() {
         assert_eq!(DEFAULT_IGNORE_GAS_PRICE, U256::from(2u64));
     }
+
+    #[test]
+    fn pool_pressure_strategy() {
+        let mut input = PriorityFeeInput {
+            percentile_tip: U256::from(10),
+            gas_limit: 100_000,
+            pending: vec![(50, 50_000), (30, 50_000)],
+        };
+        // all pending transactions fit into the next block
+        assert_eq!(PoolPressureStrategy.suggest_tip_cap(&input), U256::from(10));
+
+        input.pending.push((20, 21_000));
+        assert_eq!(PoolPressureStrategy.suggest_tip_cap(&input), U256::from(21));
+        assert_eq!(PercentileStrategy.suggest_tip_cap(&input), U256::from(10));
+
+        input.percentile_tip = U256::from(25);
+        assert_eq!(PoolPressureStrategy.suggest_tip_cap(&input), U256::from(25));
+    }
+
+    #[test]
+    fn parse_strategy() {
+        for strategy in [GasPriceOracleStrategy::Percentile, GasPriceOracleStrategy::PoolPressure] {
+            assert_eq!(strategy.to_string().parse::<GasPriceOracleStrategy>(), Ok(strategy));
+        }
+    }
 }
@@ -0,0 +1,238 @@
+//! Signer for the accounts of a Ledger or Trezor hardware wallet, connected over USB.
+
+use crate::eth::{error::SignError, signer::EthSigner};
+use alloy_consensus::SignableTransaction;
+use alloy_dyn_abi::TypedData;
+use alloy_network::{eip2718::Encodable2718, EthereumSigner, TransactionBuilder};
+use alloy_signer::{Signer, TxSigner};
+use alloy_signer_ledger::{HDPath, LedgerSigner};
+use alloy_signer_trezor::{TrezorHDPath, TrezorSigner};
+use reth_primitives::{Address, Signature, TransactionSigned};
+use reth_rpc_types::TypedTransactionRequest;
+use reth_rpc_types_compat::transaction::{to_primitive_transaction, transaction_to_request};
+use std::{collections::HashMap, fmt, str::FromStr, sync::Arc};
+use tracing::debug;
+
+type Result<T> = std::result::Result<T, SignError>;
+
+/// The kind of hardware wallet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HardwareWallet {
+    /// A Ledger device with the Ethereum app opened.
+    Ledger,
+    /// A Trezor device.
+    Trezor,
+}
+
+impl FromStr for HardwareWallet {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "ledger" => Ok(Self::Ledger),
+            "trezor" => Ok(Self::Trezor),
+            _ => Err(format!("invalid hardware wallet: {s}, expected ledger or trezor")),
+        }
+    }
+}
+
+impl fmt::Display for HardwareWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ledger => f.write_str("ledger"),
+            Self::Trezor => f.write_str("trezor"),
+        }
+    }
+}
+
+/// Errors that can occur when connecting to a hardware wallet.
+#[derive(Debug, thiserror::Error)]
+pub enum HardwareSignerError {
+    /// The device could not be opened or refused to derive an account.
+    #[error("failed to connect to {wallet} account {index}: {source}")]
+    Connect {
+        /// The kind of hardware wallet.
+        wallet: HardwareWallet,
+        /// The index of the account.
+        index: usize,
+        /// The error of the device.
+        #[source]
+        source: alloy_signer::Error,
+    },
+}
+
+/// The signer of a single account of the device.
+#[derive(Clone)]
+enum AccountSigner {
+    Ledger(Arc<LedgerSigner>),
+    Trezor(Arc<TrezorSigner>),
+}
+
+impl AccountSigner {
+    /// Opens the device and derives the account with the wallet's default derivation path.
+    async fn connect(wallet: HardwareWallet, index: usize) -> alloy_signer::Result<Self> {
+        Ok(match wallet {
+            HardwareWallet::Ledger => {
+                let signer = LedgerSigner::new(HDPath::LedgerLive(index), None).await?;
+                Self::Ledger(Arc::new(signer))
+            }
+            HardwareWallet::Trezor => {
+                let signer = TrezorSigner::new(TrezorHDPath::TrezorLive(index), None).await?;
+                Self::Trezor(Arc::new(signer))
+            }
+        })
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            Self::Ledger(signer) => Signer::address(&**signer),
+            Self::Trezor(signer) => Signer::address(&**signer),
+        }
+    }
+
+    async fn sign_message(
+        &self,
+        message: &[u8],
+    ) -> alloy_signer::Result<alloy_primitives::Signature> {
+        match self {
+            Self::Ledger(signer) => signer.sign_message(message).await,
+            Self::Trezor(signer) => signer.sign_message(message).await,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TxSigner<alloy_primitives::Signature> for AccountSigner {
+    fn address(&self) -> Address {
+        AccountSigner::address(self)
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<alloy_primitives::Signature>,
+    ) -> alloy_signer::Result<alloy_primitives::Signature> {
+        match self {
+            Self::Ledger(signer) => signer.sign_transaction(tx).await,
+            Self::Trezor(signer) => signer.sign_transaction(tx).await,
+        }
+    }
+}
+
+/// Signer for the accounts of a hardware wallet.
+///
+/// The accounts are derived once when connecting, with the default derivation path of the wallet,
+/// i.e. `m/44'/60'/<index>'/0/0` for Ledger Live and `m/44'/60'/0'/0/<index>` for Trezor.
+/// Every signing request must be confirmed on the device.
+///
+/// Clones share the same device.
+#[derive(Clone)]
+pub(crate) struct HardwareSigner {
+    inner: Arc<HardwareSignerInner>,
+}
+
+struct HardwareSignerInner {
+    /// The kind of hardware wallet.
+    wallet: HardwareWallet,
+    /// The derived accounts, in derivation order.
+    addresses: Vec<Address>,
+    /// The signers of the derived accounts.
+    accounts: HashMap<Address, AccountSigner>,
+}
+
+impl HardwareSigner {
+    /// Connects to the device and derives the first `accounts` accounts.
+    pub(crate) async fn connect(
+        wallet: HardwareWallet,
+        accounts: usize,
+    ) -> std::result::Result<Self, HardwareSignerError> {
+        let mut addresses = Vec::with_capacity(accounts);
+        let mut signers = HashMap::with_capacity(accounts);
+        for index in 0..accounts {
+            let signer = AccountSigner::connect(wallet, index)
+                .await
+                .map_err(|source| HardwareSignerError::Connect { wallet, index, source })?;
+            let address = signer.address();
+            debug!(target: "rpc::eth", %wallet, index, %address, "Derived hardware wallet account");
+            addresses.push(address);
+            signers.insert(address, signer);
+        }
+
+        let inner = HardwareSignerInner { wallet, addresses, accounts: signers };
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Returns the signer of the account.
+    fn account(&self, address: &Address) -> Result<&AccountSigner> {
+        self.inner.accounts.get(address).ok_or(SignError::NoAccount)
+    }
+}
+
+#[async_trait::async_trait]
+impl EthSigner for HardwareSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.inner.addresses.clone()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.inner.accounts.contains_key(addr)
+    }
+
+    async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature> {
+        let signature = self
+            .account(&address)?
+            .sign_message(message)
+            .await
+            .map_err(|err| SignError::Hardware(err.to_string()))?;
+        Ok(from_alloy_signature(signature))
+    }
+
+    async fn sign_transaction(
+        &self,
+        request: TypedTransactionRequest,
+        address: &Address,
+    ) -> Result<TransactionSigned> {
+        let signer = EthereumSigner::new(self.account(address)?.clone());
+        let transaction =
+            to_primitive_transaction(request).ok_or(SignError::InvalidTransactionRequest)?;
+        let envelope = transaction_to_request(&transaction, *address)
+            .build(&signer)
+            .await
+            .map_err(|err| SignError::Hardware(err.to_string()))?;
+
+        let encoded = envelope.encoded_2718();
+        TransactionSigned::decode_enveloped(&mut encoded.as_slice())
+            .map_err(|_| SignError::Hardware("invalid signed transaction".to_string()))
+    }
+
+    async fn sign_typed_data(&self, address: Address, _payload: &TypedData) -> Result<Signature> {
+        self.account(&address)?;
+        Err(SignError::Hardware("signing typed data is not supported".to_string()))
+    }
+}
+
+impl fmt::Debug for HardwareSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HardwareSigner")
+            .field("wallet", &self.inner.wallet)
+            .field("accounts", &self.inner.addresses)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Converts the signature of the device into a [Signature].
+fn from_alloy_signature(signature: alloy_primitives::Signature) -> Signature {
+    Signature { r: signature.r(), s: signature.s(), odd_y_parity: signature.v().y_parity() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hardware_wallet() {
+        assert_eq!("ledger".parse::<HardwareWallet>(), Ok(HardwareWallet::Ledger));
+        assert_eq!("trezor".parse::<HardwareWallet>(), Ok(HardwareWallet::Trezor));
+        assert!("keepkey".parse::<HardwareWallet>().is_err());
+        assert_eq!(HardwareWallet::Trezor.to_string(), "trezor");
+    }
+}
@@ -0,0 +1,426 @@
+//! Signer for accounts of a geth compatible keystore directory.
+
+use crate::eth::{
+    error::SignError,
+    signer::{DevSigner, EthSigner},
+};
+use alloy_dyn_abi::TypedData;
+use parking_lot::RwLock;
+use reth_primitives::{
+    fs::FsPathError, hex, public_key_to_address, Address, Signature, TransactionSigned,
+};
+use reth_rpc_types::{Authorization, TypedTransactionRequest};
+use secp256k1::{SecretKey, SECP256K1};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+use tracing::{debug, warn};
+
+type Result<T> = std::result::Result<T, SignError>;
+
+/// Errors that can occur when managing the accounts of a keystore directory.
+#[derive(Debug, thiserror::Error)]
+pub enum KeystoreError {
+    /// Failed to read or write the keystore directory or one of its files.
+    #[error(transparent)]
+    Fs(#[from] FsPathError),
+    /// A file of the keystore directory is not a valid keystore file.
+    #[error("invalid keystore file {path:?}: {source}")]
+    InvalidFile {
+        /// Path of the keystore file.
+        path: PathBuf,
+        /// The decoding error.
+        #[source]
+        source: serde_json::Error,
+    },
+    /// Failed to encrypt or decrypt a keystore file.
+    #[error(transparent)]
+    Crypto(#[from] eth_keystore::KeystoreError),
+    /// The decrypted private key is invalid.
+    #[error("invalid private key in keystore file {0:?}")]
+    InvalidKey(PathBuf),
+    /// The account is not part of the keystore.
+    #[error("unknown account {0}")]
+    UnknownAccount(Address),
+    /// The imported account is already part of the keystore.
+    #[error("account already exists")]
+    AccountExists(Address),
+}
+
+impl KeystoreError {
+    /// Returns `true` if the keystore file could not be decrypted with the given passphrase.
+    pub fn is_invalid_passphrase(&self) -> bool {
+        matches!(self, KeystoreError::Crypto(eth_keystore::KeystoreError::MacMismatch))
+    }
+}
+
+/// The unencrypted part of a V3 keystore file.
+#[derive(Deserialize)]
+struct KeystoreFile {
+    /// Address of the account, geth always writes it.
+    address: Option<Address>,
+}
+
+/// A decrypted key, usable for signing until it expires.
+#[derive(Clone, Copy)]
+struct UnlockedKey {
+    key: SecretKey,
+    /// `None` if the key stays unlocked until shutdown.
+    expires_at: Option<Instant>,
+}
+
+impl UnlockedKey {
+    /// Returns when a key unlocked now for the given duration expires, `None` if it doesn't expire
+    /// or the duration exceeds the representable time.
+    fn expires_at(duration: Option<Duration>) -> Option<Instant> {
+        duration.and_then(|duration| Instant::now().checked_add(duration))
+    }
+
+    fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= Instant::now())
+    }
+}
+
+/// Signer for the V3 keystore files of a directory, as managed by geth's `account` commands.
+///
+/// All accounts of the keystore are listed, but only unlocked accounts can be used for signing.
+/// Accounts are unlocked on load if they can be decrypted with the passphrase, or later via
+/// [KeystoreSigner::unlock].
+///
+/// Clones share the same accounts.
+#[derive(Clone)]
+pub(crate) struct KeystoreSigner {
+    inner: Arc<KeystoreSignerInner>,
+}
+
+struct KeystoreSignerInner {
+    /// The keystore directory.
+    dir: PathBuf,
+    /// All accounts of the keystore and their files, in creation order.
+    accounts: RwLock<Vec<(Address, PathBuf)>>,
+    /// Decrypted keys of the unlocked accounts.
+    unlocked: RwLock<HashMap<Address, UnlockedKey>>,
+}
+
+impl KeystoreSigner {
+    /// Loads all keystore files of the directory and unlocks them with the passphrase, if any, for
+    /// the given duration or until shutdown if `None`.
+    ///
+    /// Keystore files that can't be decrypted with the passphrase stay locked.
+    pub(crate) fn load(
+        dir: &Path,
+        passphrase: Option<&str>,
+        unlock_duration: Option<Duration>,
+    ) -> std::result::Result<Self, KeystoreError> {
+        let expires_at = UnlockedKey::expires_at(unlock_duration);
+        let mut paths = reth_primitives::fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file() && !is_ignored(path))
+            .collect::<Vec<_>>();
+        // geth file names start with the creation time, so accounts are listed in creation order
+        paths.sort();
+
+        let mut accounts = Vec::with_capacity(paths.len());
+        let mut unlocked = HashMap::new();
+        for path in paths {
+            let file: KeystoreFile = serde_json::from_slice(&reth_primitives::fs::read(&path)?)
+                .map_err(|source| KeystoreError::InvalidFile { path: path.clone(), source })?;
+
+            let key = passphrase.and_then(|passphrase| match decrypt(&path, passphrase) {
+                Ok(key) => Some(key),
+                Err(err) => {
+                    warn!(target: "rpc::eth", ?path, %err, "Failed to unlock keystore account");
+                    None
+                }
+            });
+
+            let address = match (key, file.address) {
+                (Some(key), _) => {
+                    let address = public_key_to_address(key.public_key(SECP256K1));
+                    unlocked.insert(address, UnlockedKey { key, expires_at });
+                    address
+                }
+                (None, Some(address)) => address,
+                (None, None) => {
+                    warn!(target: "rpc::eth", ?path, "Skipping locked keystore file without address");
+                    continue
+                }
+            };
+            debug!(target: "rpc::eth", ?path, %address, "Loaded keystore account");
+            accounts.push((address, path));
+        }
+
+        let inner = KeystoreSignerInner {
+            dir: dir.to_path_buf(),
+            accounts: RwLock::new(accounts),
+            unlocked: RwLock::new(unlocked),
+        };
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Creates a new account, encrypted with the passphrase, in the keystore directory.
+    ///
+    /// The new account is locked.
+    pub(crate) fn new_account(
+        &self,
+        passphrase: &str,
+    ) -> std::result::Result<Address, KeystoreError> {
+        let (key, _) = secp256k1::generate_keypair(&mut rand::thread_rng());
+        self.import_key(key, passphrase)
+    }
+
+    /// Adds the account of the private key, encrypted with the passphrase, to the keystore
+    /// directory.
+    ///
+    /// The imported account is locked.
+    pub(crate) fn import_key(
+        &self,
+        key: SecretKey,
+        passphrase: &str,
+    ) -> std::result::Result<Address, KeystoreError> {
+        let address = public_key_to_address(key.public_key(SECP256K1));
+        if self.is_signer_for(&address) {
+            return Err(KeystoreError::AccountExists(address))
+        }
+
+        let name = key_file_name(SystemTime::now(), address);
+        eth_keystore::encrypt_key(
+            &self.inner.dir,
+            &mut rand::thread_rng(),
+            key.secret_bytes(),
+            passphrase,
+            Some(&name),
+        )?;
+
+        self.inner.accounts.write().push((address, self.inner.dir.join(name)));
+        Ok(address)
+    }
+
+    /// Unlocks the account with the passphrase, for the given duration or until shutdown if
+    /// `None`.
+    pub(crate) fn unlock(
+        &self,
+        address: Address,
+        passphrase: &str,
+        duration: Option<Duration>,
+    ) -> std::result::Result<(), KeystoreError> {
+        let key = self.decrypt_account(address, passphrase)?;
+        let expires_at = UnlockedKey::expires_at(duration);
+        self.inner.unlocked.write().insert(address, UnlockedKey { key, expires_at });
+        Ok(())
+    }
+
+    /// Locks the account again, if it is unlocked.
+    pub(crate) fn lock(&self, address: Address) -> std::result::Result<(), KeystoreError> {
+        if !self.is_signer_for(&address) {
+            return Err(KeystoreError::UnknownAccount(address))
+        }
+        self.inner.unlocked.write().remove(&address);
+        Ok(())
+    }
+
+    /// Returns a signer for the account, decrypted with the passphrase, without unlocking it.
+    pub(crate) fn signer_with_passphrase(
+        &self,
+        address: Address,
+        passphrase: &str,
+    ) -> std::result::Result<Box<dyn EthSigner>, KeystoreError> {
+        let key = self.decrypt_account(address, passphrase)?;
+        Ok(Box::new(DevSigner::from_keys([key])))
+    }
+
+    /// Decrypts the key of the account with the passphrase.
+    fn decrypt_account(
+        &self,
+        address: Address,
+        passphrase: &str,
+    ) -> std::result::Result<SecretKey, KeystoreError> {
+        let path = self
+            .inner
+            .accounts
+            .read()
+            .iter()
+            .find(|(account, _)| *account == address)
+            .map(|(_, path)| path.clone())
+            .ok_or(KeystoreError::UnknownAccount(address))?;
+        decrypt(&path, passphrase)
+    }
+
+    /// Returns a signer for the account if it is unlocked.
+    fn unlocked_signer(&self, address: &Address) -> Result<DevSigner> {
+        let mut unlocked = self.inner.unlocked.write();
+        if let Some(key) = unlocked.get(address).copied() {
+            if !key.is_expired() {
+                return Ok(DevSigner::from_keys([key.key]))
+            }
+            unlocked.remove(address);
+        }
+        drop(unlocked);
+
+        if self.is_signer_for(address) {
+            Err(SignError::AccountLocked)
+        } else {
+            Err(SignError::NoAccount)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthSigner for KeystoreSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.inner.accounts.read().iter().map(|(address, _)| *address).collect()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.inner.accounts.read().iter().any(|(address, _)| address == addr)
+    }
+
+    async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature> {
+        let signer = self.unlocked_signer(&address)?;
+        signer.sign(address, message).await
+    }
+
+    async fn sign_transaction(
+        &self,
+        request: TypedTransactionRequest,
+        address: &Address,
+    ) -> Result<TransactionSigned> {
+        self.unlocked_signer(address)?.sign_transaction(request, address).await
+    }
+
+    async fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
+        self.unlocked_signer(&address)?.sign_typed_data(address, payload).await
+    }
+
+    async fn sign_authorization(
+        &self,
+        address: Address,
+        authorization: &Authorization,
+    ) -> Result<Signature> {
+        self.unlocked_signer(&address)?.sign_authorization(address, authorization).await
+    }
+}
+
+impl std::fmt::Debug for KeystoreSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeystoreSigner")
+            .field("dir", &self.inner.dir)
+            .field("accounts", &self.inner.accounts.read().len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Decrypts the private key of the keystore file.
+fn decrypt(path: &Path, passphrase: &str) -> std::result::Result<SecretKey, KeystoreError> {
+    let key = eth_keystore::decrypt_key(path, passphrase)?;
+    SecretKey::from_slice(&key).map_err(|_| KeystoreError::InvalidKey(path.to_path_buf()))
+}
+
+/// Returns the name geth gives the keystore file of an account created at the given time, e.g.
+/// `UTC--2023-11-14T22-13-20.000000005Z--<address>`.
+fn key_file_name(created: SystemTime, address: Address) -> String {
+    let created = humantime::format_rfc3339_nanos(created).to_string().replace(':', "-");
+    format!("UTC--{created}--{}", hex::encode(address))
+}
+
+/// Returns `true` for hidden files and editor backups, which geth ignores as well.
+fn is_ignored(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .map_or(true, |name| name.starts_with('.') || name.ends_with('~'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[tokio::test]
+    async fn load_keystore_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let (secret, public) = secp256k1::generate_keypair(&mut rand::thread_rng());
+        let address = public_key_to_address(public);
+        eth_keystore::encrypt_key(
+            dir.path(),
+            &mut rand::thread_rng(),
+            secret.secret_bytes(),
+            "passphrase",
+            Some("account"),
+        )
+        .unwrap();
+
+        let signer = KeystoreSigner::load(dir.path(), Some("passphrase"), None).unwrap();
+        assert_eq!(signer.accounts(), vec![address]);
+        assert!(signer.sign(address, b"message").await.is_ok());
+
+        // accounts stay locked with a wrong passphrase
+        let signer = KeystoreSigner::load(dir.path(), Some("wrong"), None).unwrap();
+        assert_eq!(signer.accounts(), vec![address]);
+        assert!(matches!(signer.sign(address, b"message").await, Err(SignError::AccountLocked)));
+
+        // accounts unlocked on load are locked again once the duration elapsed
+        let signer =
+            KeystoreSigner::load(dir.path(), Some("passphrase"), Some(Duration::ZERO)).unwrap();
+        assert!(matches!(signer.sign(address, b"message").await, Err(SignError::AccountLocked)));
+    }
+
+    #[tokio::test]
+    async fn new_account_and_unlock() {
+        let dir = tempfile::tempdir().unwrap();
+        let signer = KeystoreSigner::load(dir.path(), None, None).unwrap();
+
+        let address = signer.new_account("passphrase").unwrap();
+        assert_eq!(signer.accounts(), vec![address]);
+        assert!(matches!(signer.sign(address, b"message").await, Err(SignError::AccountLocked)));
+
+        assert!(signer.unlock(address, "wrong", None).unwrap_err().is_invalid_passphrase());
+
+        signer.unlock(address, "passphrase", Some(Duration::from_secs(60))).unwrap();
+        assert!(signer.sign(address, b"message").await.is_ok());
+
+        // durations beyond the representable time don't expire
+        signer.unlock(address, "passphrase", Some(Duration::from_secs(u64::MAX))).unwrap();
+        assert!(signer.sign(address, b"message").await.is_ok());
+
+        // expired accounts are locked again
+        signer.unlock(address, "passphrase", Some(Duration::ZERO)).unwrap();
+        assert!(matches!(signer.sign(address, b"message").await, Err(SignError::AccountLocked)));
+
+        // the new account is persisted in the keystore directory
+        let reloaded = KeystoreSigner::load(dir.path(), Some("passphrase"), None).unwrap();
+        assert_eq!(reloaded.accounts(), vec![address]);
+    }
+
+    #[tokio::test]
+    async fn import_key_and_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let signer = KeystoreSigner::load(dir.path(), None, None).unwrap();
+
+        let (secret, public) = secp256k1::generate_keypair(&mut rand::thread_rng());
+        let address = signer.import_key(secret, "passphrase").unwrap();
+        assert_eq!(address, public_key_to_address(public));
+        assert!(matches!(
+            signer.import_key(secret, "passphrase"),
+            Err(KeystoreError::AccountExists(_))
+        ));
+
+        signer.unlock(address, "passphrase", None).unwrap();
+        assert!(signer.sign(address, b"message").await.is_ok());
+        signer.lock(address).unwrap();
+        assert!(matches!(signer.sign(address, b"message").await, Err(SignError::AccountLocked)));
+        assert!(signer.lock(Address::ZERO).is_err());
+    }
+
+    #[test]
+    fn geth_key_file_name() {
+        let created = UNIX_EPOCH + Duration::new(1_700_000_000, 5);
+        assert_eq!(
+            key_file_name(created, Address::with_last_byte(1)),
+            "UTC--2023-11-14T22-13-20.000000005Z--0000000000000000000000000000000000000001"
+        );
+    }
+}
@@ -1,28 +1,60 @@
 //! `eth` namespace handler implementation.
 
 mod api;
+pub mod blob_index;
+pub mod blobs;
 pub mod bundle;
 pub mod cache;
+pub mod conditional;
 pub mod error;
 mod filter;
+pub mod filter_store;
 pub mod gas_oracle;
+#[cfg(feature = "hardware-wallets")]
+pub mod hardware_signer;
 mod id_provider;
+pub mod keystore;
 mod logs_utils;
+pub mod preconfirmation;
 mod pubsub;
+pub mod remote_signer;
+pub mod revert;
 pub mod revm_utils;
-mod signer;
+pub(crate) mod signer;
+pub mod simulate;
 pub mod traits;
+pub mod user_operation;
 pub(crate) mod utils;
 
 #[cfg(feature = "optimism")]
 pub mod optimism;
 
+#[cfg(feature = "optimism")]
+pub(crate) use api::build_op_tx_meta;
+pub(crate) use api::build_transaction_receipt_with_block_receipts;
 pub use api::{
     fee_history::{fee_history_cache_new_blocks_task, FeeHistoryCache, FeeHistoryCacheConfig},
-    EthApi, EthApiSpec, EthTransactions, TransactionSource, RPC_DEFAULT_GAS_CAP,
+    EthApi, EthApiSpec, EthTransactions, GasEstimationConfig, NonceStrategy,
+    TransactionConfirmationConfig, TransactionSource, DEFAULT_ESTIMATE_GAS_ERROR_TOLERANCE_BPS,
+    DEFAULT_TX_CONFIRMATION_POLL_INTERVAL, DEFAULT_TX_CONFIRMATION_TIMEOUT, RPC_DEFAULT_GAS_CAP,
 };
 
-pub use bundle::EthBundle;
-pub use filter::{EthFilter, EthFilterConfig};
+pub use blob_index::BlobSidecarIndex;
+pub use blobs::LocalBlobStore;
+pub use bundle::{EthBundle, SendBundle};
+pub use filter::{EthFilter, EthFilterConfig, FilterError};
+pub use filter_store::{FilterStore, DEFAULT_MAX_PERSISTED_FILTERS};
+#[cfg(feature = "hardware-wallets")]
+pub use hardware_signer::{HardwareSignerError, HardwareWallet};
 pub use id_provider::EthSubscriptionIdProvider;
-pub use pubsub::EthPubSub;
+pub use keystore::KeystoreError;
+pub use preconfirmation::{preconfirmations_task, Preconfirmations};
+pub use pubsub::{
+    EthPubSub, EthPubSubConfig, SubscriptionOverflow, DEFAULT_MAX_BUFFERED_NOTIFICATIONS,
+};
+pub use remote_signer::{RemoteSignerApi, RemoteSignerError};
+pub use revert::{DecodedRevertReason, RevertDecoder, RevertErrorRegistry};
+pub use user_operation::{
+    EthUserOperation, PooledUserOperation, UserOperationConfig, UserOperationError,
+    UserOperationPool, ENTRY_POINT_V06,
+};
@@ -0,0 +1,236 @@
+//! Preconfirmations of the transactions selected into the payloads built by the node, served by
+//! `reth_subscribePreconfirmations`.
+
+use crate::eth::{
+    build_transaction_receipt_with_block_receipts,
+    error::{EthApiError, EthResult},
+    EthTransactions,
+};
+use futures::{Stream, StreamExt};
+use jsonrpsee::{SubscriptionMessage, SubscriptionSink};
+use reth_primitives::{
+    revm::env::tx_env_with_recovered, Receipt, SealedBlock, TransactionMeta, TxHash, B256,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_types::{AnyTransactionReceipt, Preconfirmation};
+use revm::{db::CacheDB, primitives::ResultAndState, DatabaseCommit};
+use revm_primitives::EnvWithHandlerCfg;
+use std::collections::HashSet;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tracing::debug;
+
+/// The number of preconfirmations buffered for slow subscribers.
+const PRECONFIRMATIONS_CHANNEL_SIZE: usize = 1024;
+
+/// Broadcasts the [Preconfirmation]s to the subscribers of `reth_subscribePreconfirmations`.
+///
+/// The preconfirmations are produced by [preconfirmations_task] from the payloads built by the
+/// node.
+///
+/// This type is cheap to clone.
+#[derive(Debug, Clone)]
+pub struct Preconfirmations {
+    sender: broadcast::Sender<Preconfirmation>,
+}
+
+impl Preconfirmations {
+    /// Returns a new receiver of the preconfirmations.
+    pub fn subscribe(&self) -> broadcast::Receiver<Preconfirmation> {
+        self.sender.subscribe()
+    }
+
+    /// Returns `true` if there is at least one subscriber.
+    pub fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+}
+
+impl Default for Preconfirmations {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(PRECONFIRMATIONS_CHANNEL_SIZE);
+        Self { sender }
+    }
+}
+
+/// The transactions that were already preconfirmed on top of the parent block.
+#[derive(Debug, Default)]
+struct Preconfirmed {
+    /// The parent block of the payloads.
+    parent_hash: B256,
+    /// The preconfirmed transactions.
+    transactions: HashSet<TxHash>,
+}
+
+impl Preconfirmed {
+    /// Records the transactions of a payload on top of the parent block.
+    ///
+    /// Returns for every transaction whether it wasn't preconfirmed yet. The preconfirmed
+    /// transactions are reset when the parent block changes.
+    fn insert_payload(
+        &mut self,
+        parent_hash: B256,
+        transactions: impl IntoIterator<Item = TxHash>,
+    ) -> Vec<bool> {
+        if self.parent_hash != parent_hash {
+            self.parent_hash = parent_hash;
+            self.transactions.clear();
+        }
+        transactions.into_iter().map(|hash| self.transactions.insert(hash)).collect()
+    }
+}
+
+/// Simulates the payloads built by the node and broadcasts the transactions that are
+/// preconfirmed for the first time on top of the parent block.
+///
+/// Payloads are skipped while there are no subscribers.
+pub async fn preconfirmations_task<Eth, St>(
+    preconfirmations: Preconfirmations,
+    eth_api: Eth,
+    mut payloads: St,
+) where
+    Eth: EthTransactions + 'static,
+    St: Stream<Item = SealedBlock> + Unpin,
+{
+    let mut preconfirmed = Preconfirmed::default();
+    while let Some(payload) = payloads.next().await {
+        if !preconfirmations.has_subscribers() {
+            continue
+        }
+
+        let parent_hash = payload.parent_hash;
+        let is_new =
+            preconfirmed.insert_payload(parent_hash, payload.body.iter().map(|tx| tx.hash()));
+        if !is_new.contains(&true) {
+            continue
+        }
+
+        let receipts = match simulate_payload(&eth_api, payload).await {
+            Ok(receipts) => receipts,
+            Err(err) => {
+                debug!(target: "rpc::reth", %err, %parent_hash, "Failed to simulate payload");
+                continue
+            }
+        };
+        for (receipt, is_new) in receipts.into_iter().zip(is_new) {
+            if is_new {
+                let _ = preconfirmations.sender.send(Preconfirmation { parent_hash, receipt });
+            }
+        }
+    }
+}
+
+/// Executes the transactions of the payload on top of its parent block and returns their
+/// receipts.
+async fn simulate_payload<Eth>(
+    eth_api: &Eth,
+    payload: SealedBlock,
+) -> EthResult<Vec<AnyTransactionReceipt>>
+where
+    Eth: EthTransactions + 'static,
+{
+    let (cfg, block_env) = eth_api.evm_env_for_raw_block(&payload.header).await?;
+    let SealedBlock { header, body, .. } = payload;
+
+    eth_api
+        .spawn_with_state_at_block(header.parent_hash.into(), move |state| {
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+            let mut cumulative_gas_used = 0;
+            let mut receipts = Vec::with_capacity(body.len());
+            for tx in &body {
+                let tx = tx
+                    .clone()
+                    .into_ecrecovered()
+                    .ok_or(EthApiError::InvalidTransactionSignature)?;
+                let env = EnvWithHandlerCfg::new_with_cfg_env(
+                    cfg.clone(),
+                    block_env.clone(),
+                    tx_env_with_recovered(&tx),
+                );
+                let mut evm =
+                    revm::Evm::builder().with_db(&mut db).with_env_with_handler_cfg(env).build();
+                let ResultAndState { result, state } = evm.transact()?;
+                drop(evm);
+                db.commit(state);
+
+                cumulative_gas_used += result.gas_used();
+                receipts.push(Receipt {
+                    tx_type: tx.tx_type(),
+                    success: result.is_success(),
+                    cumulative_gas_used,
+                    logs: result.into_logs().into_iter().map(Into::into).collect(),
+                    #[cfg(feature = "optimism")]
+                    deposit_nonce: None,
+                    #[cfg(feature = "optimism")]
+                    deposit_receipt_version: None,
+                });
+            }
+
+            body.into_iter()
+                .zip(receipts.iter().cloned())
+                .enumerate()
+                .map(|(index, (tx, receipt))| {
+                    let meta = TransactionMeta {
+                        tx_hash: tx.hash(),
+                        index: index as u64,
+                        block_hash: header.hash(),
+                        block_number: header.number,
+                        base_fee: header.base_fee_per_gas,
+                        excess_blob_gas: header.excess_blob_gas,
+                        timestamp: header.timestamp,
+                    };
+                    build_transaction_receipt_with_block_receipts(
+                        tx,
+                        meta,
+                        receipt,
+                        &receipts,
+                        #[cfg(feature = "optimism")]
+                        Default::default(),
+                    )
+                })
+                .collect()
+        })
+        .await
+}
+
+/// Sends the preconfirmations to the subscriber until the subscription is dropped.
+///
+/// Preconfirmations that were missed because the subscriber is too slow are skipped.
+pub(crate) async fn pipe_preconfirmations(
+    sink: SubscriptionSink,
+    mut preconfirmations: BroadcastStream<Preconfirmation>,
+) {
+    loop {
+        let preconfirmation = tokio::select! {
+            _ = sink.closed() => break,
+            item = preconfirmations.next() => match item {
+                Some(Ok(preconfirmation)) => preconfirmation,
+                Some(Err(_)) => continue,
+                None => break,
+            },
+        };
+        let Ok(msg) = SubscriptionMessage::from_json(&preconfirmation) else { continue };
+        if sink.send(msg).await.is_err() {
+            break
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preconfirm_new_transactions_once_per_parent() {
+        let mut preconfirmed = Preconfirmed::default();
+        let parent = B256::with_last_byte(1);
+        let (a, b) = (TxHash::with_last_byte(2), TxHash::with_last_byte(3));
+
+        assert_eq!(preconfirmed.insert_payload(parent, [a]), vec![true]);
+        // a better payload on the same parent only preconfirms the new transaction
+        assert_eq!(preconfirmed.insert_payload(parent, [a, b]), vec![false, true]);
+
+        // a new parent starts over
+        assert_eq!(preconfirmed.insert_payload(B256::with_last_byte(4), [a]), vec![true]);
+    }
+}
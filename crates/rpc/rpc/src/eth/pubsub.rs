@@ -17,16 +17,22 @@ use reth_rpc_types::{
         Params, PubSubSyncStatus, SubscriptionKind, SubscriptionResult as EthSubscriptionResult,
         SyncStatusMetadata,
     },
-    FilteredParams, Header, Log,
+    EthSubscriptionKind, EthSubscriptionParams, FilteredParams, Header, Log,
+    PendingTransactionsFilter, SubscriptionKindExtension,
 };
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::{NewTransactionEvent, TransactionPool};
-use serde::Serialize;
-use std::sync::Arc;
+use reth_transaction_pool::{NewTransactionEvent, PoolTransaction, TransactionPool};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, sync::Arc};
+use tokio::sync::mpsc::{self, error::TrySendError};
 use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
     Stream,
 };
+use tracing::debug;
+
+/// The default maximum number of notifications that are buffered per subscription.
+pub const DEFAULT_MAX_BUFFERED_NOTIFICATIONS: usize = 1024;
 
 /// `Eth` pubsub RPC implementation.
 ///
@@ -37,6 +43,8 @@ pub struct EthPubSub<Provider, Pool, Events, Network> {
     inner: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
+    /// How notifications are buffered for slow subscribers.
+    config: EthPubSubConfig,
 }
 
 // === impl EthPubSub ===
@@ -52,6 +60,7 @@ impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network>
             chain_events,
             network,
             Box::<TokioTaskExecutor>::default(),
+            EthPubSubConfig::default(),
         )
     }
 
@@ -62,9 +71,10 @@ impl<Provider, Pool, Events, Network> EthPubSub<Provider, Pool, Events, Network>
         chain_events: Events,
         network: Network,
         subscription_task_spawner: Box<dyn TaskSpawner>,
+        config: EthPubSubConfig,
     ) -> Self {
         let inner = EthPubSubInner { provider, pool, chain_events, network };
-        Self { inner: Arc::new(inner), subscription_task_spawner }
+        Self { inner: Arc::new(inner), subscription_task_spawner, config }
     }
 }
 
@@ -81,13 +91,14 @@ where
     async fn subscribe(
         &self,
         pending: PendingSubscriptionSink,
-        kind: SubscriptionKind,
-        params: Option<Params>,
+        kind: EthSubscriptionKind,
+        params: Option<EthSubscriptionParams>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
+        let config = self.config;
         self.subscription_task_spawner.spawn(Box::pin(async move {
-            let _ = handle_accepted(pubsub, sink, kind, params).await;
+            let _ = handle_accepted(pubsub, sink, kind, params, config).await;
         }));
 
         Ok(())
@@ -98,8 +109,9 @@ where
 async fn handle_accepted<Provider, Pool, Events, Network>(
     pubsub: Arc<EthPubSubInner<Provider, Pool, Events, Network>>,
     accepted_sink: SubscriptionSink,
-    kind: SubscriptionKind,
-    params: Option<Params>,
+    kind: EthSubscriptionKind,
+    params: Option<EthSubscriptionParams>,
+    config: EthPubSubConfig,
 ) -> Result<(), ErrorObject<'static>>
 where
     Provider: BlockReader + EvmEnvProvider + Clone + 'static,
@@ -107,12 +119,53 @@ where
     Events: CanonStateSubscriptions + Clone + 'static,
     Network: NetworkInfo + Clone + 'static,
 {
+    let kind = match kind {
+        EthSubscriptionKind::Standard(kind) => kind,
+        EthSubscriptionKind::Extension(SubscriptionKindExtension::AlchemyPendingTransactions) => {
+            // if no params are provided, all pending transactions are sent in full
+            let filter = match params {
+                Some(EthSubscriptionParams::PendingTransactions(filter)) => filter,
+                Some(EthSubscriptionParams::Standard(Params::None)) | None => {
+                    PendingTransactionsFilter::default()
+                }
+                Some(EthSubscriptionParams::Standard(_)) => {
+                    return Err(invalid_params_rpc_err(
+                        "Invalid params for alchemy_pendingTransactions",
+                    ))
+                }
+            };
+            let hashes_only = filter.hashes_only;
+            let stream = pubsub
+                .full_pending_transaction_stream()
+                .filter(move |tx| {
+                    futures::future::ready(
+                        filter.matches(tx.transaction.sender(), tx.transaction.to()),
+                    )
+                })
+                .map(move |tx| {
+                    if hashes_only {
+                        EthSubscriptionResult::TransactionHash(*tx.transaction.hash())
+                    } else {
+                        full_transaction_result(tx)
+                    }
+                });
+            return pipe_from_stream(accepted_sink, stream, config).await
+        }
+    };
+    let params = match params {
+        Some(EthSubscriptionParams::Standard(params)) => Some(params),
+        Some(EthSubscriptionParams::PendingTransactions(_)) => {
+            return Err(invalid_params_rpc_err(format!("Invalid params for {kind:?}")))
+        }
+        None => None,
+    };
+
     match kind {
         SubscriptionKind::NewHeads => {
             let stream = pubsub
                 .new_headers_stream()
                 .map(|block| EthSubscriptionResult::Header(Box::new(block.into())));
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, stream, config).await
         }
         SubscriptionKind::Logs => {
             // if no params are provided, used default filter params
@@ -125,21 +178,16 @@ where
             };
             let stream =
                 pubsub.log_stream(filter).map(|log| EthSubscriptionResult::Log(Box::new(log)));
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, stream, config).await
         }
         SubscriptionKind::NewPendingTransactions => {
             if let Some(params) = params {
                 match params {
                     Params::Bool(true) => {
                         // full transaction objects requested
-                        let stream = pubsub.full_pending_transaction_stream().map(|tx| {
-                            EthSubscriptionResult::FullTransaction(Box::new(
-                                reth_rpc_types_compat::transaction::from_recovered(
-                                    tx.transaction.to_recovered_transaction(),
-                                ),
-                            ))
-                        });
-                        return pipe_from_stream(accepted_sink, stream).await
+                        let stream =
+                            pubsub.full_pending_transaction_stream().map(full_transaction_result);
+                        return pipe_from_stream(accepted_sink, stream, config).await
                     }
                     Params::Bool(false) | Params::None => {
                         // only hashes requested
@@ -155,7 +203,7 @@ where
             let stream = pubsub
                 .pending_transaction_hashes_stream()
                 .map(EthSubscriptionResult::TransactionHash);
-            pipe_from_stream(accepted_sink, stream).await
+            pipe_from_stream(accepted_sink, stream, config).await
         }
         SubscriptionKind::Syncing => {
             // get new block subscription
@@ -194,6 +242,17 @@ where
     }
 }
 
+/// Converts a new pool transaction into a full transaction subscription result.
+fn full_transaction_result<T: PoolTransaction>(
+    tx: NewTransactionEvent<T>,
+) -> EthSubscriptionResult {
+    EthSubscriptionResult::FullTransaction(Box::new(
+        reth_rpc_types_compat::transaction::from_recovered(
+            tx.transaction.to_recovered_transaction(),
+        ),
+    ))
+}
+
 /// Helper to convert a serde error into an [`ErrorObject`]
 #[derive(Debug, thiserror::Error)]
 #[error("Failed to serialize subscription item: {0}")]
@@ -211,34 +270,176 @@ impl From<SubscriptionSerializeError> for ErrorObject<'static> {
     }
 }
 
+/// Config for the buffering of subscription notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EthPubSubConfig {
+    /// Maximum number of notifications that are buffered for a subscription whose client does not
+    /// keep up.
+    pub max_buffered_notifications: usize,
+    /// What happens to a subscription once its buffer is full.
+    pub overflow: SubscriptionOverflow,
+}
+
+impl EthPubSubConfig {
+    /// Sets the maximum number of notifications that are buffered per subscription.
+    pub fn max_buffered_notifications(mut self, num: usize) -> Self {
+        self.max_buffered_notifications = num;
+        self
+    }
+
+    /// Sets what happens to a subscription once its buffer is full.
+    pub fn overflow(mut self, overflow: SubscriptionOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+impl Default for EthPubSubConfig {
+    fn default() -> Self {
+        Self {
+            max_buffered_notifications: DEFAULT_MAX_BUFFERED_NOTIFICATIONS,
+            overflow: SubscriptionOverflow::default(),
+        }
+    }
+}
+
+/// What happens to a subscription whose buffer of notifications is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriptionOverflow {
+    /// The subscription is dropped.
+    #[default]
+    Drop,
+    /// Notifications are discarded until the buffer has room again, then a
+    /// `{"missedNotifications": N}` notification is sent before the next notification.
+    Missed,
+}
+
+impl FromStr for SubscriptionOverflow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop" => Ok(Self::Drop),
+            "missed" => Ok(Self::Missed),
+            _ => Err(format!("invalid subscription overflow policy: {s}, expected drop or missed")),
+        }
+    }
+}
+
+impl fmt::Display for SubscriptionOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Drop => f.write_str("drop"),
+            Self::Missed => f.write_str("missed"),
+        }
+    }
+}
+
+/// A notification that is buffered for a subscription.
+#[derive(Debug)]
+enum Notification<T> {
+    /// An item of the subscribed stream.
+    Item(T),
+    /// The number of items that were discarded because the buffer was full.
+    Missed(u64),
+}
+
+/// The notification that is sent in place of discarded notifications.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MissedNotifications {
+    missed_notifications: u64,
+}
+
 /// Pipes all stream items to the subscription sink.
+///
+/// The stream is always consumed, even if the client is slow, so that a slow client never delays
+/// the shared broadcast. Items are buffered for the client up to the configured limit, after which
+/// the [SubscriptionOverflow] policy applies.
 async fn pipe_from_stream<T, St>(
     sink: SubscriptionSink,
     mut stream: St,
+    config: EthPubSubConfig,
 ) -> Result<(), ErrorObject<'static>>
 where
     St: Stream<Item = T> + Unpin,
     T: Serialize,
 {
-    loop {
-        tokio::select! {
-            _ = sink.closed() => {
-                // connection dropped
-                break Ok(())
-            },
-            maybe_item = stream.next() => {
-                let item = match maybe_item {
+    let (tx, mut rx) = mpsc::channel(config.max_buffered_notifications.max(1));
+
+    // buffers the stream items, returns true if the subscription was dropped
+    let buffer = async {
+        // the sender is dropped once the stream ended, so that the remaining notifications are sent
+        let tx = tx;
+        let mut missed = 0u64;
+        loop {
+            let item = tokio::select! {
+                _ = sink.closed() => {
+                    // connection dropped
+                    return true
+                },
+                maybe_item = stream.next() => match maybe_item {
                     Some(item) => item,
-                    None => {
-                        // stream ended
-                        break  Ok(())
-                    },
-                };
-                let msg = SubscriptionMessage::from_json(&item).map_err(SubscriptionSerializeError::new)?;
-                if sink.send(msg).await.is_err() {
-                    break Ok(());
+                    // stream ended
+                    None => return false,
+                },
+            };
+
+            if missed > 0 {
+                match tx.try_send(Notification::Missed(missed)) {
+                    Ok(()) => missed = 0,
+                    Err(TrySendError::Full(_)) => {
+                        missed += 1;
+                        continue
+                    }
+                    Err(TrySendError::Closed(_)) => return true,
+                }
+            }
+
+            match tx.try_send(Notification::Item(item)) {
+                Ok(()) => {}
+                Err(TrySendError::Full(_)) => match config.overflow {
+                    SubscriptionOverflow::Drop => {
+                        debug!(
+                            target: "rpc::eth::pubsub",
+                            subscription = ?sink.subscription_id(),
+                            "Dropping subscription of slow client"
+                        );
+                        return true
+                    }
+                    SubscriptionOverflow::Missed => missed += 1,
+                },
+                Err(TrySendError::Closed(_)) => return true,
+            }
+        }
+    };
+
+    let send = async {
+        while let Some(notification) = rx.recv().await {
+            let msg = match notification {
+                Notification::Item(item) => SubscriptionMessage::from_json(&item),
+                Notification::Missed(missed_notifications) => {
+                    SubscriptionMessage::from_json(&MissedNotifications { missed_notifications })
                 }
             }
+            .map_err(SubscriptionSerializeError::new)?;
+            if sink.send(msg).await.is_err() {
+                break
+            }
+        }
+        Ok::<_, ErrorObject<'static>>(())
+    };
+
+    tokio::pin!(send);
+    tokio::select! {
+        res = &mut send => res,
+        dropped = buffer => {
+            if dropped {
+                return Ok(())
+            }
+            // send the remaining notifications after the stream ended
+            send.await
         }
     }
 }
@@ -324,9 +525,9 @@ where
     /// Returns a stream that yields all logs that match the given filter.
     fn log_stream(&self, filter: FilteredParams) -> impl Stream<Item = Log> {
         BroadcastStream::new(self.chain_events.subscribe_to_canonical_state())
-            .map(move |canon_state| {
-                canon_state.expect("new block subscription never ends").block_receipts()
-            })
+            // notifications that were missed because of a lagging receiver are skipped
+            .filter_map(|canon_state| futures::future::ready(canon_state.ok()))
+            .map(|canon_state| canon_state.block_receipts())
             .flat_map(futures::stream::iter)
             .flat_map(move |(block_receipts, removed)| {
                 let all_logs = logs_utils::matching_block_logs_with_tx_hashes(
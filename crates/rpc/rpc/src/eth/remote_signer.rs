@@ -0,0 +1,272 @@
+//! Signer that forwards signing requests to an external signing service, so that no keys are
+//! kept in the node process.
+
+use crate::eth::{error::SignError, signer::EthSigner};
+use alloy_dyn_abi::TypedData;
+use http::{header::AUTHORIZATION, HeaderMap, HeaderValue};
+use jsonrpsee::{
+    core::{client::ClientT, params::ArrayParams, ClientError},
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+use reth_primitives::{Address, Bytes, Signature, TransactionSigned, U256};
+use reth_rpc_types::TypedTransactionRequest;
+use reth_rpc_types_compat::transaction::{to_primitive_transaction, transaction_to_request};
+use serde::Deserialize;
+use std::{fmt, str::FromStr, sync::Arc};
+
+type Result<T> = std::result::Result<T, SignError>;
+
+/// The API of the external signing service.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RemoteSignerApi {
+    /// The `eth_` JSON-RPC API of Web3Signer in `eth1` mode.
+    #[default]
+    Web3Signer,
+    /// The `account_` JSON-RPC API of Clef.
+    Clef,
+}
+
+impl RemoteSignerApi {
+    /// Returns the method that lists the accounts.
+    const fn accounts_method(&self) -> &'static str {
+        match self {
+            Self::Web3Signer => "eth_accounts",
+            Self::Clef => "account_list",
+        }
+    }
+
+    /// Returns the method that signs typed data.
+    const fn sign_typed_data_method(&self) -> &'static str {
+        match self {
+            Self::Web3Signer => "eth_signTypedData",
+            Self::Clef => "account_signTypedData",
+        }
+    }
+}
+
+impl FromStr for RemoteSignerApi {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "web3signer" => Ok(Self::Web3Signer),
+            "clef" => Ok(Self::Clef),
+            _ => Err(format!("invalid remote signer api: {s}, expected web3signer or clef")),
+        }
+    }
+}
+
+impl fmt::Display for RemoteSignerApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Web3Signer => f.write_str("web3signer"),
+            Self::Clef => f.write_str("clef"),
+        }
+    }
+}
+
+/// Errors that can occur when connecting to a remote signer.
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteSignerError {
+    /// The request to the remote signer failed.
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    /// The auth token can't be sent in a header.
+    #[error("invalid remote signer auth token")]
+    InvalidAuthToken,
+}
+
+/// The result of Clef's `account_signTransaction`.
+#[derive(Deserialize)]
+struct ClefSignedTransaction {
+    /// The signed transaction, encoded in its network form.
+    raw: Bytes,
+}
+
+/// Signer for the accounts of an external signing service, e.g. Web3Signer or Clef.
+///
+/// The accounts are fetched once when connecting. All signing requests, including the EIP-712
+/// typed data, are passed through to the service unmodified.
+///
+/// Clones share the same connection.
+#[derive(Clone)]
+pub(crate) struct RemoteSigner {
+    inner: Arc<RemoteSignerInner>,
+}
+
+struct RemoteSignerInner {
+    /// The URL of the service.
+    url: String,
+    /// The API of the service.
+    api: RemoteSignerApi,
+    /// The client for the service.
+    client: HttpClient,
+    /// The accounts of the service.
+    accounts: Vec<Address>,
+}
+
+impl RemoteSigner {
+    /// Connects to the service at the URL and fetches its accounts.
+    ///
+    /// If an auth token is given, it's sent as bearer token in the `Authorization` header of every
+    /// request.
+    pub(crate) async fn connect(
+        url: &str,
+        api: RemoteSignerApi,
+        auth_token: Option<&str>,
+    ) -> std::result::Result<Self, RemoteSignerError> {
+        let mut headers = HeaderMap::new();
+        if let Some(token) = auth_token {
+            let value = HeaderValue::from_str(&format!("Bearer {token}"))
+                .map_err(|_| RemoteSignerError::InvalidAuthToken)?;
+            headers.insert(AUTHORIZATION, value);
+        }
+        let client = HttpClientBuilder::default().set_headers(headers).build(url)?;
+        let accounts = client.request(api.accounts_method(), rpc_params![]).await?;
+
+        let inner = RemoteSignerInner { url: url.to_string(), api, client, accounts };
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Sends the request to the service.
+    async fn request<R>(&self, method: &str, params: ArrayParams) -> Result<R>
+    where
+        R: serde::de::DeserializeOwned,
+    {
+        self.inner.client.request(method, params).await.map_err(|err| match err {
+            ClientError::Call(err) => SignError::Remote(err.message().to_string()),
+            err => SignError::Remote(err.to_string()),
+        })
+    }
+
+    /// Returns an error if the account is not one of the service's accounts.
+    fn ensure_account(&self, address: &Address) -> Result<()> {
+        if self.is_signer_for(address) {
+            Ok(())
+        } else {
+            Err(SignError::NoAccount)
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EthSigner for RemoteSigner {
+    fn accounts(&self) -> Vec<Address> {
+        self.inner.accounts.clone()
+    }
+
+    fn is_signer_for(&self, addr: &Address) -> bool {
+        self.inner.accounts.contains(addr)
+    }
+
+    async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature> {
+        self.ensure_account(&address)?;
+        let message = Bytes::copy_from_slice(message);
+        let signature: Bytes = match self.inner.api {
+            RemoteSignerApi::Web3Signer => {
+                self.request("eth_sign", rpc_params![address, message]).await?
+            }
+            // `text/plain` is the EIP-191 personal message, like `eth_sign`
+            RemoteSignerApi::Clef => {
+                self.request("account_signData", rpc_params!["text/plain", address, message])
+                    .await?
+            }
+        };
+        signature_from_bytes(&signature)
+    }
+
+    async fn sign_transaction(
+        &self,
+        request: TypedTransactionRequest,
+        address: &Address,
+    ) -> Result<TransactionSigned> {
+        self.ensure_account(address)?;
+        let transaction =
+            to_primitive_transaction(request).ok_or(SignError::InvalidTransactionRequest)?;
+        let request = transaction_to_request(&transaction, *address);
+
+        let raw = match self.inner.api {
+            RemoteSignerApi::Web3Signer => {
+                self.request::<Bytes>("eth_signTransaction", rpc_params![request]).await?
+            }
+            RemoteSignerApi::Clef => {
+                self.request::<ClefSignedTransaction>(
+                    "account_signTransaction",
+                    rpc_params![request],
+                )
+                .await?
+                .raw
+            }
+        };
+
+        let signed = TransactionSigned::decode_enveloped(&mut raw.as_ref())
+            .map_err(|_| SignError::Remote("invalid signed transaction".to_string()))?;
+        // the service must not sign anything else than the requested transaction
+        if signed.transaction != transaction || signed.recover_signer() != Some(*address) {
+            return Err(SignError::Remote("signed transaction doesn't match request".to_string()))
+        }
+        Ok(signed)
+    }
+
+    async fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
+        self.ensure_account(&address)?;
+        let signature: Bytes = self
+            .request(self.inner.api.sign_typed_data_method(), rpc_params![address, payload])
+            .await?;
+        signature_from_bytes(&signature)
+    }
+}
+
+impl fmt::Debug for RemoteSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSigner")
+            .field("url", &self.inner.url)
+            .field("api", &self.inner.api)
+            .field("accounts", &self.inner.accounts.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Decodes a 65 byte `r || s || v` signature, with `v` either `0`/`1` or `27`/`28`.
+fn signature_from_bytes(bytes: &[u8]) -> Result<Signature> {
+    if bytes.len() != 65 {
+        return Err(SignError::Remote("invalid signature".to_string()))
+    }
+    let odd_y_parity = match bytes[64] {
+        0 | 27 => false,
+        1 | 28 => true,
+        _ => return Err(SignError::Remote("invalid signature".to_string())),
+    };
+    Ok(Signature {
+        r: U256::from_be_slice(&bytes[..32]),
+        s: U256::from_be_slice(&bytes[32..64]),
+        odd_y_parity,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_remote_signer_api() {
+        assert_eq!("web3signer".parse::<RemoteSignerApi>(), Ok(RemoteSignerApi::Web3Signer));
+        assert_eq!("clef".parse::<RemoteSignerApi>(), Ok(RemoteSignerApi::Clef));
+        assert!("ledger".parse::<RemoteSignerApi>().is_err());
+        assert_eq!(RemoteSignerApi::Clef.to_string(), "clef");
+    }
+
+    #[test]
+    fn decode_signature() {
+        let signature = Signature { r: U256::from(1), s: U256::from(2), odd_y_parity: true };
+        assert_eq!(signature_from_bytes(&signature.to_bytes()).unwrap(), signature);
+
+        let mut bytes = signature.to_bytes();
+        bytes[64] = 0;
+        assert!(!signature_from_bytes(&bytes).unwrap().odd_y_parity);
+        bytes[64] = 2;
+        assert!(signature_from_bytes(&bytes).is_err());
+        assert!(signature_from_bytes(&bytes[..64]).is_err());
+    }
+}
@@ -0,0 +1,262 @@
+//! Decoding of the revert reasons of reverted calls.
+
+use crate::eth::error::{EthApiError, RpcInvalidTransactionError};
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_json_abi::{Error, JsonAbi};
+use alloy_sol_types::{Panic, Revert, SolError};
+use reth_primitives::{hex, Bytes, U256};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The decoded reason of a reverted call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DecodedRevertReason {
+    /// The `Error(string)` of `revert` and `require`.
+    Error {
+        /// The revert message.
+        reason: String,
+    },
+    /// The `Panic(uint256)` of failed assertions, checked arithmetic and invalid accesses.
+    Panic {
+        /// The panic code.
+        code: U256,
+        /// The description of a known panic code.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
+    /// A custom error of the [RevertErrorRegistry].
+    Custom {
+        /// The name of the error.
+        name: String,
+        /// The signature of the error, e.g. `InsufficientBalance(uint256,uint256)`.
+        signature: String,
+        /// The decoded arguments of the error.
+        args: Vec<String>,
+    },
+}
+
+impl DecodedRevertReason {
+    /// Decodes the standard `Error(string)` and `Panic(uint256)` revert reasons.
+    pub fn decode(output: &[u8]) -> Option<Self> {
+        if let Ok(revert) = Revert::abi_decode(output, true) {
+            return Some(Self::Error { reason: revert.reason })
+        }
+        if let Ok(panic) = Panic::abi_decode(output, true) {
+            let description = panic.kind().map(|kind| kind.to_string());
+            return Some(Self::Panic { code: panic.code, description })
+        }
+        None
+    }
+}
+
+impl fmt::Display for DecodedRevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error { reason } => f.write_str(reason),
+            Self::Panic { code, description: Some(description) } => {
+                write!(f, "panic: {description} ({code:#x})")
+            }
+            Self::Panic { code, description: None } => write!(f, "panic: unknown code {code:#x}"),
+            Self::Custom { name, args, .. } => write!(f, "{name}({})", args.join(", ")),
+        }
+    }
+}
+
+/// The custom errors that the revert reasons of reverted calls are decoded with, by selector.
+///
+/// The errors are loaded from a JSON ABI, other items of the ABI are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RevertErrorRegistry {
+    errors: HashMap<[u8; 4], Error>,
+}
+
+impl RevertErrorRegistry {
+    /// Creates a registry of the errors of the ABI.
+    pub fn new(abi: &JsonAbi) -> Self {
+        let mut registry = Self::default();
+        registry.extend(abi);
+        registry
+    }
+
+    /// Loads the errors of the JSON ABI file at the given path.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RevertErrorRegistryError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| RevertErrorRegistryError::Read(err, path.to_path_buf()))?;
+        let abi: JsonAbi = serde_json::from_str(&contents)
+            .map_err(|err| RevertErrorRegistryError::Parse(err, path.to_path_buf()))?;
+        Ok(Self::new(&abi))
+    }
+
+    /// Adds the errors of the ABI to the registry.
+    pub fn extend(&mut self, abi: &JsonAbi) {
+        self.errors.extend(abi.errors().map(|error| (error.selector().0, error.clone())));
+    }
+
+    /// Returns the number of registered errors.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Returns `true` if no errors are registered.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Decodes the output of a reverted call with the registered error of its selector.
+    pub fn decode(&self, output: &[u8]) -> Option<DecodedRevertReason> {
+        let selector: [u8; 4] = output.get(..4)?.try_into().ok()?;
+        let error = self.errors.get(&selector)?;
+        let args = error.abi_decode_input(&output[4..], true).ok()?;
+        Some(DecodedRevertReason::Custom {
+            name: error.name.clone(),
+            signature: error.signature(),
+            args: args.iter().map(format_value).collect(),
+        })
+    }
+}
+
+/// Errors that can occur when loading a [RevertErrorRegistry].
+#[derive(Debug, thiserror::Error)]
+pub enum RevertErrorRegistryError {
+    /// The file could not be read.
+    #[error("failed to read ABI file {1:?}: {0}")]
+    Read(std::io::Error, PathBuf),
+    /// The file is not a valid JSON ABI.
+    #[error("failed to parse ABI file {1:?}: {0}")]
+    Parse(serde_json::Error, PathBuf),
+}
+
+/// Decodes the revert reasons of the reverted calls of `eth_call` and `eth_estimateGas`.
+///
+/// This type is cheap to clone.
+#[derive(Debug, Clone, Default)]
+pub struct RevertDecoder {
+    /// The custom errors, if configured.
+    registry: Option<Arc<RevertErrorRegistry>>,
+    /// Whether the decoded reason is included in the data of the JSON-RPC error, instead of only
+    /// the output of the call.
+    structured_data: bool,
+}
+
+impl RevertDecoder {
+    /// Creates a new decoder with the optional custom errors.
+    pub fn new(registry: Option<RevertErrorRegistry>, structured_data: bool) -> Self {
+        Self { registry: registry.map(Arc::new), structured_data }
+    }
+
+    /// Decodes the output of a reverted call, with the custom errors first.
+    pub fn decode(&self, output: &[u8]) -> Option<DecodedRevertReason> {
+        self.registry
+            .as_ref()
+            .and_then(|registry| registry.decode(output))
+            .or_else(|| DecodedRevertReason::decode(output))
+    }
+
+    /// Decodes the revert reason of the error, if it is the error of a reverted call.
+    pub fn decode_error(&self, err: EthApiError) -> EthApiError {
+        match err {
+            EthApiError::InvalidTransaction(RpcInvalidTransactionError::Revert(mut revert)) => {
+                revert.reason = revert.output().and_then(|output| self.decode(output));
+                revert.structured_data = self.structured_data;
+                RpcInvalidTransactionError::Revert(revert).into()
+            }
+            err => err,
+        }
+    }
+}
+
+/// The data of the JSON-RPC error of a reverted call if structured data is enabled, see
+/// [RevertDecoder].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RevertErrorData<'a> {
+    /// The output of the reverted call.
+    pub(crate) output: Option<&'a Bytes>,
+    /// The decoded revert reason.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) reason: Option<&'a DecodedRevertReason>,
+}
+
+/// Formats a decoded argument of a custom error.
+fn format_value(value: &DynSolValue) -> String {
+    match value {
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]),
+        DynSolValue::Address(address) => address.to_checksum(None),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes),
+        DynSolValue::String(value) => format!("{value:?}"),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            format!("[{}]", values.iter().map(format_value).collect::<Vec<_>>().join(", "))
+        }
+        DynSolValue::Tuple(values) => {
+            format!("({})", values.iter().map(format_value).collect::<Vec<_>>().join(", "))
+        }
+        value => format!("{value:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::sol;
+
+    sol! {
+        error InsufficientBalance(uint256 available, address account);
+    }
+
+    #[test]
+    fn decode_standard_reasons() {
+        let output = Revert { reason: "not owner".to_string() }.abi_encode();
+        assert_eq!(
+            DecodedRevertReason::decode(&output),
+            Some(DecodedRevertReason::Error { reason: "not owner".to_string() })
+        );
+
+        let output = Panic { code: U256::from(0x11) }.abi_encode();
+        let reason = DecodedRevertReason::decode(&output).unwrap();
+        assert!(matches!(reason, DecodedRevertReason::Panic { description: Some(_), .. }));
+        assert!(reason.to_string().ends_with("(0x11)"));
+
+        assert_eq!(DecodedRevertReason::decode(&[1, 2, 3, 4]), None);
+    }
+
+    #[test]
+    fn decode_custom_errors() {
+        let abi: JsonAbi = serde_json::from_str(
+            r#"[{"type":"error","name":"InsufficientBalance","inputs":[{"name":"available","type":"uint256"},{"name":"account","type":"address"}]},{"type":"function","name":"foo","inputs":[],"outputs":[],"stateMutability":"view"}]"#,
+        )
+        .unwrap();
+        let registry = RevertErrorRegistry::new(&abi);
+        assert_eq!(registry.len(), 1);
+
+        let output = InsufficientBalance {
+            available: U256::from(7),
+            account: reth_primitives::Address::with_last_byte(1),
+        }
+        .abi_encode();
+        let reason = registry.decode(&output).unwrap();
+        assert_eq!(
+            reason.to_string(),
+            "InsufficientBalance(7, 0x0000000000000000000000000000000000000001)"
+        );
+        assert!(matches!(
+            reason,
+            DecodedRevertReason::Custom { ref signature, .. }
+                if signature == "InsufficientBalance(uint256,address)"
+        ));
+
+        // standard reasons are decoded if there is no matching custom error
+        let decoder = RevertDecoder::new(Some(registry), false);
+        let output = Revert { reason: "not owner".to_string() }.abi_encode();
+        assert!(matches!(decoder.decode(&output), Some(DecodedRevertReason::Error { .. })));
+    }
+}
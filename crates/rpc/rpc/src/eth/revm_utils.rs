@@ -16,7 +16,7 @@ use reth_rpc_types::{
 #[cfg(feature = "optimism")]
 use revm::primitives::{Bytes, OptimismFields};
 use revm::{
-    db::CacheDB,
+    db::{AccountState, BundleState, CacheDB, DbAccount},
     precompile::{PrecompileSpecId, Precompiles},
     primitives::{
         db::DatabaseRef, BlockEnv, Bytecode, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, SpecId,
@@ -441,7 +441,7 @@ impl CallFees {
 }
 
 /// Applies the given block overrides to the env
-fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
+pub(crate) fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
     let BlockOverrides {
         number,
         difficulty,
@@ -476,6 +476,28 @@ fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
     }
 }
 
+/// Applies the state changes of a block, e.g. of the locally built pending block, to the
+/// [CacheDB], so that calls are executed on top of the block.
+pub(crate) fn apply_bundle_state<DB>(bundle: &BundleState, db: &mut CacheDB<DB>) {
+    for (address, account) in bundle.state() {
+        let Some(info) = account.info.clone() else {
+            // the account was destroyed by the block
+            db.accounts.insert(*address, DbAccount::new_not_existing());
+            continue
+        };
+        db.insert_account_info(*address, info);
+        let db_account = db.accounts.get_mut(address).expect("account was inserted");
+        if account.was_destroyed() {
+            // the storage of the account before the block must not be read anymore
+            db_account.account_state = AccountState::StorageCleared;
+            db_account.storage.clear();
+        }
+        db_account
+            .storage
+            .extend(account.storage.iter().map(|(slot, value)| (*slot, value.present_value)));
+    }
+}
+
 /// Applies the given state overrides (a set of [AccountOverride]) to the [CacheDB].
 pub(crate) fn apply_state_overrides<DB>(
     overrides: StateOverride,
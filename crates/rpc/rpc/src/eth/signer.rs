@@ -5,11 +5,11 @@ use alloy_dyn_abi::TypedData;
 use reth_primitives::{
     eip191_hash_message, sign_message, Address, Signature, TransactionSigned, B256,
 };
-use reth_rpc_types::TypedTransactionRequest;
+use reth_rpc_types::{Authorization, TypedTransactionRequest};
 
 use dyn_clone::DynClone;
 use reth_rpc_types_compat::transaction::to_primitive_transaction;
-use secp256k1::SecretKey;
+use secp256k1::{SecretKey, SECP256K1};
 use std::collections::HashMap;
 
 type Result<T> = std::result::Result<T, SignError>;
@@ -29,14 +29,23 @@ pub(crate) trait EthSigner: Send + Sync + DynClone {
     async fn sign(&self, address: Address, message: &[u8]) -> Result<Signature>;
 
     /// signs a transaction request using the given account in request
-    fn sign_transaction(
+    async fn sign_transaction(
         &self,
         request: TypedTransactionRequest,
         address: &Address,
     ) -> Result<TransactionSigned>;
 
     /// Encodes and signs the typed data according EIP-712. Payload must implement Eip712 trait.
-    fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature>;
+    async fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature>;
+
+    /// Signs the EIP-7702 authorization with the given account.
+    async fn sign_authorization(
+        &self,
+        _address: Address,
+        _authorization: &Authorization,
+    ) -> Result<Signature> {
+        Err(SignError::Unsupported("signing authorizations"))
+    }
 }
 
 dyn_clone::clone_trait_object!(EthSigner);
@@ -71,6 +80,16 @@ impl DevSigner {
         signers
     }
 
+    /// Creates a signer for the given private keys.
+    pub(crate) fn from_keys(keys: impl IntoIterator<Item = SecretKey>) -> Self {
+        let accounts = keys
+            .into_iter()
+            .map(|sk| (reth_primitives::public_key_to_address(sk.public_key(SECP256K1)), sk))
+            .collect::<HashMap<_, _>>();
+        let addresses = accounts.keys().copied().collect();
+        DevSigner { addresses, accounts }
+    }
+
     fn get_key(&self, account: Address) -> Result<&SecretKey> {
         self.accounts.get(&account).ok_or(SignError::NoAccount)
     }
@@ -99,7 +118,7 @@ impl EthSigner for DevSigner {
         self.sign_hash(hash, address)
     }
 
-    fn sign_transaction(
+    async fn sign_transaction(
         &self,
         request: TypedTransactionRequest,
         address: &Address,
@@ -113,11 +132,19 @@ impl EthSigner for DevSigner {
         Ok(TransactionSigned::from_transaction_and_signature(transaction, signature))
     }
 
-    fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
+    async fn sign_typed_data(&self, address: Address, payload: &TypedData) -> Result<Signature> {
         let encoded = payload.eip712_signing_hash().map_err(|_| SignError::InvalidTypedData)?;
         // let b256 = encoded;
         self.sign_hash(encoded, address)
     }
+
+    async fn sign_authorization(
+        &self,
+        address: Address,
+        authorization: &Authorization,
+    ) -> Result<Signature> {
+        self.sign_hash(authorization.signature_hash(), address)
+    }
 }
 
 #[cfg(test)]
@@ -204,7 +231,7 @@ mod tests {
         );
         let data: TypedData = serde_json::from_value(eip_712_example).unwrap();
         let signer = build_signer();
-        let sig = signer.sign_typed_data(Address::default(), &data).unwrap();
+        let sig = signer.sign_typed_data(Address::default(), &data).await.unwrap();
         let expected = Signature {
             r: U256::from_str_radix(
                 "5318aee9942b84885761bb20e768372b76e7ee454fc4d39b59ce07338d15a06c",
@@ -241,4 +268,18 @@ mod tests {
         };
         assert_eq!(sig, expected)
     }
+
+    #[tokio::test]
+    async fn test_sign_authorization() {
+        let secret = SecretKey::new(&mut rand::thread_rng());
+        let signer = DevSigner::from_keys([secret]);
+        let address = signer.accounts()[0];
+        let authorization = Authorization {
+            chain_id: U256::from(1),
+            address: Address::with_last_byte(1),
+            nonce: reth_primitives::U64::from(3),
+        };
+        let sig = signer.sign_authorization(address, &authorization).await.unwrap();
+        assert_eq!(sig.recover_signer(authorization.signature_hash()), Some(address));
+    }
 }
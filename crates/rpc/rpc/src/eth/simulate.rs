@@ -0,0 +1,248 @@
+//! Helpers for simulating blocks of calls with `eth_simulateV1`.
+
+use crate::eth::error::{RevertError, RpcInvalidTransactionError};
+use reth_primitives::{
+    address, b256, logs_bloom, Address, BlockNumHash, Bytes, Header, Log, SealedHeader, B256, U256,
+    U64,
+};
+use reth_rpc_types::{
+    SimulateCallResult, SimulateError, SimulatedBlock, SIMULATE_REVERT_CODE, SIMULATE_VM_ERROR_CODE,
+};
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome},
+    primitives::ExecutionResult,
+    Database, EvmContext, Inspector,
+};
+
+/// The address of the logs of traced ETH transfers.
+pub const TRANSFER_LOG_ADDRESS: Address = address!("eeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee");
+
+/// The topic of the logs of traced ETH transfers, the signature of the ERC-20
+/// `Transfer(address,address,uint256)` event.
+pub const TRANSFER_EVENT_SIGNATURE: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+/// The default time between simulated blocks if the timestamp is not overridden.
+pub const SIMULATE_BLOCK_TIME: u64 = 12;
+
+/// An inspector that collects the logs of a call together with its ETH transfers as ERC-20
+/// `Transfer` logs of the [TRANSFER_LOG_ADDRESS].
+///
+/// The logs and transfers of reverted call frames are discarded.
+#[derive(Debug, Default)]
+pub struct TransferInspector {
+    /// The logs emitted so far, in execution order.
+    logs: Vec<Log>,
+    /// The number of logs before each open call frame.
+    frames: Vec<usize>,
+}
+
+impl TransferInspector {
+    /// Consumes the inspector and returns the collected logs.
+    pub fn into_logs(self) -> Vec<Log> {
+        self.logs
+    }
+
+    /// Opens a call frame.
+    fn enter(&mut self) {
+        self.frames.push(self.logs.len());
+    }
+
+    /// Closes the current call frame and discards its logs if it failed.
+    ///
+    /// Returns the number of logs before the frame.
+    fn exit(&mut self, success: bool) -> usize {
+        let start = self.frames.pop().unwrap_or_default();
+        if !success {
+            self.logs.truncate(start);
+        }
+        start
+    }
+}
+
+/// Returns the log of an ETH transfer.
+fn transfer_log(from: Address, to: Address, value: U256) -> Log {
+    Log::new_unchecked(
+        TRANSFER_LOG_ADDRESS,
+        vec![TRANSFER_EVENT_SIGNATURE, from.into_word(), to.into_word()],
+        Bytes::from(value.to_be_bytes::<32>()),
+    )
+}
+
+impl<DB> Inspector<DB> for TransferInspector
+where
+    DB: Database,
+{
+    fn log(&mut self, _context: &mut EvmContext<DB>, log: &Log) {
+        self.logs.push(log.clone());
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.enter();
+        let transfer = &inputs.transfer;
+        if transfer.value > U256::ZERO && transfer.source != transfer.target {
+            self.logs.push(transfer_log(transfer.source, transfer.target, transfer.value));
+        }
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit(outcome.result.result.is_ok());
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.enter();
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let start = self.exit(outcome.result.result.is_ok());
+        // the address of the created contract is only known once it was created, the transfer
+        // happens before the logs of the constructor
+        if let Some(address) = outcome.address {
+            if outcome.result.result.is_ok() && inputs.value > U256::ZERO {
+                self.logs.insert(start, transfer_log(inputs.caller, address, inputs.value));
+            }
+        }
+        outcome
+    }
+}
+
+/// Converts the result of a simulated call into a [SimulateCallResult] without logs.
+///
+/// The logs are added once the block is sealed, see [seal_simulated_block].
+fn simulate_call_result(result: ExecutionResult) -> SimulateCallResult {
+    let gas_used = U64::from(result.gas_used());
+    let (status, return_data, error) = match result {
+        ExecutionResult::Success { output, .. } => (1, output.into_data(), None),
+        ExecutionResult::Revert { output, .. } => {
+            let message = RevertError::new(output.clone()).to_string();
+            (0, output, Some(SimulateError { code: SIMULATE_REVERT_CODE, message }))
+        }
+        ExecutionResult::Halt { reason, gas_used } => {
+            let message = RpcInvalidTransactionError::halt(reason, gas_used).to_string();
+            (0, Bytes::new(), Some(SimulateError { code: SIMULATE_VM_ERROR_CODE, message }))
+        }
+    };
+    SimulateCallResult { return_data, logs: Vec::new(), gas_used, status: U64::from(status), error }
+}
+
+/// Completes the header of a simulated block with the gas used and the logs bloom of its calls
+/// and seals it.
+///
+/// The state, transactions and receipts roots are not computed, since the calls are not signed
+/// transactions and the state changes are not committed.
+///
+/// Returns the sealed header and the simulated block, with the logs of the calls assigned to
+/// the block.
+pub(crate) fn seal_simulated_block(
+    mut header: Header,
+    calls: Vec<(ExecutionResult, Vec<Log>)>,
+) -> (SealedHeader, SimulatedBlock) {
+    header.gas_used = calls.iter().map(|(result, _)| result.gas_used()).sum();
+    header.logs_bloom = logs_bloom(calls.iter().flat_map(|(_, logs)| logs));
+    let header = header.seal_slow();
+    let block = BlockNumHash::new(header.number, header.hash());
+
+    let mut log_index = 0;
+    let calls = calls
+        .into_iter()
+        .enumerate()
+        .map(|(transaction_index, (result, logs))| {
+            let mut call = simulate_call_result(result);
+            call.logs = logs
+                .into_iter()
+                .map(|log| {
+                    let log = reth_rpc_types::Log {
+                        inner: log,
+                        block_hash: Some(block.hash),
+                        block_number: Some(block.number),
+                        block_timestamp: Some(header.timestamp),
+                        transaction_hash: None,
+                        transaction_index: Some(transaction_index as u64),
+                        log_index: Some(log_index),
+                        removed: false,
+                    };
+                    log_index += 1;
+                    log
+                })
+                .collect();
+            call
+        })
+        .collect();
+
+    let block = SimulatedBlock {
+        header: reth_rpc_types_compat::block::from_primitive_with_hash(header.clone()),
+        calls,
+    };
+    (header, block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::keccak256;
+    use revm::primitives::{Output, SuccessReason};
+
+    #[test]
+    fn transfer_event_signature() {
+        assert_eq!(TRANSFER_EVENT_SIGNATURE, keccak256("Transfer(address,address,uint256)"));
+    }
+
+    #[test]
+    fn transfer_inspector_discards_failed_frames() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let mut inspector = TransferInspector::default();
+        inspector.enter();
+        inspector.logs.push(transfer_log(a, b, U256::from(1)));
+        inspector.enter();
+        inspector.logs.push(transfer_log(b, a, U256::from(1)));
+        assert_eq!(inspector.exit(false), 1);
+        assert_eq!(inspector.exit(true), 0);
+        assert_eq!(inspector.into_logs(), vec![transfer_log(a, b, U256::from(1))]);
+    }
+
+    #[test]
+    fn seal_block_with_logs() {
+        let log = transfer_log(Address::with_last_byte(1), Address::with_last_byte(2), U256::MAX);
+        let success = ExecutionResult::Success {
+            reason: SuccessReason::Stop,
+            gas_used: 21000,
+            gas_refunded: 0,
+            logs: vec![],
+            output: Output::Call(Bytes::new()),
+        };
+        let revert = ExecutionResult::Revert { gas_used: 30000, output: Bytes::new() };
+        let (header, block) = seal_simulated_block(
+            Header { number: 10, ..Default::default() },
+            vec![(success, vec![log.clone(), log]), (revert, vec![])],
+        );
+
+        assert_eq!(header.gas_used, 51000);
+        assert_eq!(block.header.hash, Some(header.hash()));
+        assert_eq!(block.calls[0].status, U64::from(1));
+        assert_eq!(block.calls[0].logs[1].log_index, Some(1));
+        assert_eq!(block.calls[0].logs[1].block_number, Some(10));
+        assert_eq!(block.calls[1].status, U64::ZERO);
+        assert_eq!(block.calls[1].error.as_ref().unwrap().code, SIMULATE_REVERT_CODE);
+    }
+}
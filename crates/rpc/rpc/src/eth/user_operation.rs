@@ -0,0 +1,802 @@
+//! `eth_` handlers of [ERC-4337](https://eips.ethereum.org/EIPS/eip-4337) user operations and the
+//! mempool of the validated user operations, so that bundlers can run directly on the node.
+
+use crate::{
+    eth::{
+        error::{EthApiError, RpcInvalidTransactionError},
+        filter::FilterError,
+        revm_utils::EvmOverrides,
+        EthFilter, EthTransactions,
+    },
+    result::rpc_error_with_code,
+    EthApi, EthApiSpec,
+};
+use alloy_sol_types::{sol, SolCall, SolError, SolEvent, SolValue};
+use jsonrpsee::{
+    core::RpcResult,
+    types::{error::INVALID_PARAMS_CODE, ErrorObject},
+};
+use parking_lot::RwLock;
+use reth_evm::ConfigureEvm;
+use reth_network_api::NetworkInfo;
+use reth_primitives::{address, Address, BlockId, BlockNumberOrTag, Bytes, B256, U256};
+use reth_provider::{
+    BlockIdReader, BlockNumReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider,
+    LogIndexReader, PruneCheckpointReader, StateProviderFactory,
+};
+use reth_rpc_api::EthUserOperationApiServer;
+use reth_rpc_types::{
+    Filter, Log, TransactionInput, TransactionRequest, UserOperation, UserOperationGasEstimation,
+    UserOperationReceipt,
+};
+use reth_transaction_pool::TransactionPool;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::trace;
+
+sol! {
+    /// The subset of the v0.6 EntryPoint that is used to validate user operations.
+    interface IEntryPoint {
+        struct UserOperation {
+            address sender;
+            uint256 nonce;
+            bytes initCode;
+            bytes callData;
+            uint256 callGasLimit;
+            uint256 verificationGasLimit;
+            uint256 preVerificationGas;
+            uint256 maxFeePerGas;
+            uint256 maxPriorityFeePerGas;
+            bytes paymasterAndData;
+            bytes signature;
+        }
+
+        struct ReturnInfo {
+            uint256 preOpGas;
+            uint256 prefund;
+            bool sigFailed;
+            uint48 validAfter;
+            uint48 validUntil;
+            bytes paymasterContext;
+        }
+
+        struct StakeInfo {
+            uint256 stake;
+            uint256 unstakeDelaySec;
+        }
+
+        error ValidationResult(
+            ReturnInfo returnInfo,
+            StakeInfo senderInfo,
+            StakeInfo factoryInfo,
+            StakeInfo paymasterInfo
+        );
+
+        error FailedOp(uint256 opIndex, string reason);
+
+        event BeforeExecution();
+
+        event UserOperationEvent(
+            bytes32 indexed userOpHash,
+            address indexed sender,
+            address indexed paymaster,
+            uint256 nonce,
+            bool success,
+            uint256 actualGasCost,
+            uint256 actualGasUsed
+        );
+
+        event UserOperationRevertReason(
+            bytes32 indexed userOpHash,
+            address indexed sender,
+            uint256 nonce,
+            bytes revertReason
+        );
+
+        function simulateValidation(UserOperation calldata userOp) external;
+    }
+}
+
+/// The address of the v0.6 EntryPoint, which is the same on all chains.
+pub const ENTRY_POINT_V06: Address = address!("5ff137d4b0fdcd49dca30c7cf57e578a026d2789");
+
+/// The default maximum number of user operations in the [UserOperationPool].
+pub const DEFAULT_MAX_USER_OPERATIONS: usize = 4096;
+
+/// The default number of recent blocks that are searched for the receipt of a user operation.
+pub const DEFAULT_USER_OPERATION_RECEIPT_BLOCKS: u64 = 1024;
+
+/// The verification gas limit that is used to estimate the verification gas, if the user
+/// operation doesn't set one.
+const ESTIMATION_VERIFICATION_GAS_LIMIT: u64 = 10_000_000;
+
+/// The minimum time a user operation must stay valid for, in seconds, so that it can still be
+/// included in a bundle.
+const MIN_VALID_DURATION: u64 = 30;
+
+/// The percentage the fees of a user operation must be increased by to replace a pooled user
+/// operation with the same sender and nonce.
+const REPLACEMENT_FEE_BUMP: u64 = 10;
+
+/// Configuration of the `eth_` user operation handlers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserOperationConfig {
+    /// The supported EntryPoints.
+    pub entry_points: Vec<Address>,
+    /// The maximum number of user operations in the [UserOperationPool].
+    pub max_user_operations: usize,
+    /// The number of recent blocks that are searched for the receipt of a user operation.
+    pub receipt_blocks: u64,
+}
+
+impl UserOperationConfig {
+    /// Sets the supported EntryPoints.
+    pub fn entry_points(mut self, entry_points: Vec<Address>) -> Self {
+        self.entry_points = entry_points;
+        self
+    }
+
+    /// Sets the maximum number of user operations in the [UserOperationPool].
+    pub fn max_user_operations(mut self, max: usize) -> Self {
+        self.max_user_operations = max;
+        self
+    }
+
+    /// Sets the number of recent blocks that are searched for the receipt of a user operation.
+    pub fn receipt_blocks(mut self, blocks: u64) -> Self {
+        self.receipt_blocks = blocks;
+        self
+    }
+}
+
+impl Default for UserOperationConfig {
+    fn default() -> Self {
+        Self {
+            entry_points: vec![ENTRY_POINT_V06],
+            max_user_operations: DEFAULT_MAX_USER_OPERATIONS,
+            receipt_blocks: DEFAULT_USER_OPERATION_RECEIPT_BLOCKS,
+        }
+    }
+}
+
+/// A validated user operation in the [UserOperationPool].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PooledUserOperation {
+    /// The user operation.
+    pub user_operation: UserOperation,
+    /// The EntryPoint the user operation was validated against.
+    pub entry_point: Address,
+    /// The hash of the user operation.
+    pub hash: B256,
+}
+
+/// The mempool of the user operations that passed the validation of `eth_sendUserOperation`.
+///
+/// There is at most one user operation per EntryPoint, sender and nonce, a user operation is only
+/// replaced if both of its fees are increased by at least 10%. Bundlers take the user operations
+/// with [UserOperationPool::best] and remove them once they were bundled.
+///
+/// This type is cheap to clone, clones share the same pool.
+#[derive(Debug, Clone)]
+pub struct UserOperationPool {
+    inner: Arc<RwLock<UserOperationPoolInner>>,
+    /// The maximum number of user operations.
+    max_user_operations: usize,
+}
+
+#[derive(Debug, Default)]
+struct UserOperationPoolInner {
+    /// The user operations by hash.
+    by_hash: HashMap<B256, PooledUserOperation>,
+    /// The hashes of the user operations by EntryPoint, sender and nonce.
+    by_id: HashMap<(Address, Address, U256), B256>,
+}
+
+impl UserOperationPool {
+    /// Creates an empty pool for up to `max_user_operations` user operations.
+    pub fn new(max_user_operations: usize) -> Self {
+        Self { inner: Default::default(), max_user_operations }
+    }
+
+    /// Adds the validated user operation.
+    ///
+    /// Returns the replaced user operation with the same sender and nonce, if any.
+    pub fn add(
+        &self,
+        user_operation: PooledUserOperation,
+    ) -> Result<Option<PooledUserOperation>, UserOperationError> {
+        let mut inner = self.inner.write();
+        let op = &user_operation.user_operation;
+        let id = (user_operation.entry_point, op.sender, op.nonce);
+
+        let replaced = match inner.by_id.get(&id).copied() {
+            Some(hash) => {
+                let existing = &inner.by_hash[&hash].user_operation;
+                if !is_fee_bumped(existing.max_fee_per_gas, op.max_fee_per_gas) ||
+                    !is_fee_bumped(
+                        existing.max_priority_fee_per_gas,
+                        op.max_priority_fee_per_gas,
+                    )
+                {
+                    return Err(UserOperationError::ReplacementUnderpriced)
+                }
+                inner.by_hash.remove(&hash)
+            }
+            None if inner.by_hash.len() >= self.max_user_operations => {
+                return Err(UserOperationError::PoolFull)
+            }
+            None => None,
+        };
+
+        inner.by_id.insert(id, user_operation.hash);
+        inner.by_hash.insert(user_operation.hash, user_operation);
+        Ok(replaced)
+    }
+
+    /// Returns the user operation with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<PooledUserOperation> {
+        self.inner.read().by_hash.get(hash).cloned()
+    }
+
+    /// Removes the user operation with the given hash.
+    pub fn remove(&self, hash: &B256) -> Option<PooledUserOperation> {
+        let mut inner = self.inner.write();
+        let removed = inner.by_hash.remove(hash)?;
+        let op = &removed.user_operation;
+        inner.by_id.remove(&(removed.entry_point, op.sender, op.nonce));
+        Some(removed)
+    }
+
+    /// Returns the user operations of the EntryPoint, ordered by their priority fee, highest
+    /// first, and by nonce for the same sender.
+    pub fn best(&self, entry_point: Address) -> Vec<PooledUserOperation> {
+        let mut ops = self
+            .inner
+            .read()
+            .by_hash
+            .values()
+            .filter(|op| op.entry_point == entry_point)
+            .cloned()
+            .collect::<Vec<_>>();
+        ops.sort_by(|a, b| {
+            let (a, b) = (&a.user_operation, &b.user_operation);
+            b.max_priority_fee_per_gas
+                .cmp(&a.max_priority_fee_per_gas)
+                .then_with(|| a.sender.cmp(&b.sender))
+                .then_with(|| a.nonce.cmp(&b.nonce))
+        });
+        ops
+    }
+
+    /// Returns the number of user operations.
+    pub fn len(&self) -> usize {
+        self.inner.read().by_hash.len()
+    }
+
+    /// Returns `true` if the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.read().by_hash.is_empty()
+    }
+}
+
+/// `eth_` handlers of ERC-4337 user operations.
+///
+/// User operations are validated with a simulated `eth_call` of the `simulateValidation` function
+/// of the EntryPoint, and added to the [UserOperationPool] if they are valid.
+pub struct EthUserOperation<Provider, Pool, Network, EvmConfig> {
+    inner: Arc<EthUserOperationInner<Provider, Pool, Network, EvmConfig>>,
+}
+
+struct EthUserOperationInner<Provider, Pool, Network, EvmConfig> {
+    /// Executes the simulated calls.
+    eth_api: EthApi<Provider, Pool, Network, EvmConfig>,
+    /// Finds the logs of the included user operations.
+    eth_filter: EthFilter<Provider, Pool>,
+    /// The configuration of the handlers.
+    config: UserOperationConfig,
+    /// The validated user operations.
+    pool: UserOperationPool,
+}
+
+impl<Provider, Pool, Network, EvmConfig> EthUserOperation<Provider, Pool, Network, EvmConfig> {
+    /// Creates the handlers with an empty [UserOperationPool].
+    pub fn new(
+        eth_api: EthApi<Provider, Pool, Network, EvmConfig>,
+        eth_filter: EthFilter<Provider, Pool>,
+        config: UserOperationConfig,
+    ) -> Self {
+        let pool = UserOperationPool::new(config.max_user_operations);
+        Self { inner: Arc::new(EthUserOperationInner { eth_api, eth_filter, config, pool }) }
+    }
+
+    /// Returns the pool of the validated user operations.
+    pub fn pool(&self) -> &UserOperationPool {
+        &self.inner.pool
+    }
+
+    /// Returns an error if the EntryPoint is not supported.
+    fn ensure_entry_point(&self, entry_point: Address) -> Result<(), UserOperationError> {
+        if self.inner.config.entry_points.contains(&entry_point) {
+            Ok(())
+        } else {
+            Err(UserOperationError::UnsupportedEntryPoint(entry_point))
+        }
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> EthUserOperation<Provider, Pool, Network, EvmConfig>
+where
+    Provider: BlockReaderIdExt
+        + BlockIdReader
+        + ChainSpecProvider
+        + StateProviderFactory
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + LogIndexReader
+        + 'static,
+    Pool: TransactionPool + Clone + 'static,
+    <Pool as TransactionPool>::Transaction: 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Validates the user operation and adds it to the [UserOperationPool].
+    ///
+    /// Returns the hash of the user operation.
+    pub async fn send_user_operation(
+        &self,
+        user_operation: UserOperation,
+        entry_point: Address,
+    ) -> Result<B256, UserOperationError> {
+        self.ensure_entry_point(entry_point)?;
+        validate_fields(&user_operation)?;
+
+        let info = self.simulate_validation(&user_operation, entry_point).await?;
+        if info.sigFailed {
+            return Err(UserOperationError::InvalidSignature)
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        if info.validAfter > now ||
+            (info.validUntil != 0 && info.validUntil < now + MIN_VALID_DURATION)
+        {
+            return Err(UserOperationError::InvalidTimeRange {
+                valid_after: info.validAfter,
+                valid_until: info.validUntil,
+            })
+        }
+
+        let hash = user_operation.hash(entry_point, self.inner.eth_api.chain_id().to());
+        self.inner.pool.add(PooledUserOperation { user_operation, entry_point, hash })?;
+        Ok(hash)
+    }
+
+    /// Estimates the gas values of the user operation.
+    ///
+    /// The verification gas is measured with `simulateValidation`, the signature is not checked so
+    /// that a dummy signature can be used. The call gas is estimated as a call of the account by
+    /// the EntryPoint.
+    pub async fn estimate_user_operation_gas(
+        &self,
+        mut user_operation: UserOperation,
+        entry_point: Address,
+    ) -> Result<UserOperationGasEstimation, UserOperationError> {
+        self.ensure_entry_point(entry_point)?;
+
+        let pre_verification_gas = U256::from(pre_verification_gas(&user_operation));
+        user_operation.pre_verification_gas = pre_verification_gas;
+        if user_operation.verification_gas_limit.is_zero() {
+            user_operation.verification_gas_limit = U256::from(ESTIMATION_VERIFICATION_GAS_LIMIT);
+        }
+        let info = self.simulate_validation(&user_operation, entry_point).await?;
+        let verification_gas_limit = info.preOpGas.saturating_sub(pre_verification_gas);
+
+        let request = TransactionRequest {
+            from: Some(entry_point),
+            to: Some(user_operation.sender),
+            input: TransactionInput::new(user_operation.call_data),
+            ..Default::default()
+        };
+        let call_gas = self
+            .inner
+            .eth_api
+            .estimate_gas_at(request, BlockId::Number(BlockNumberOrTag::Latest), None)
+            .await?;
+        // the intrinsic gas of the transaction is not part of the call gas
+        let call_gas_limit = call_gas.saturating_sub(U256::from(21_000));
+
+        Ok(UserOperationGasEstimation {
+            pre_verification_gas,
+            verification_gas_limit,
+            call_gas_limit,
+        })
+    }
+
+    /// Returns the receipt of the user operation, if it was included in one of the recent blocks,
+    /// see [UserOperationConfig::receipt_blocks].
+    ///
+    /// The user operation is removed from the [UserOperationPool] once it was included.
+    pub async fn user_operation_receipt(
+        &self,
+        hash: B256,
+    ) -> Result<Option<UserOperationReceipt>, UserOperationError> {
+        let best_block =
+            self.inner.eth_api.provider().best_block_number().map_err(EthApiError::from)?;
+        let filter = Filter::new()
+            .address(self.inner.config.entry_points.clone())
+            .event_signature(IEntryPoint::UserOperationEvent::SIGNATURE_HASH)
+            .topic1(hash)
+            .from_block(best_block.saturating_sub(self.inner.config.receipt_blocks))
+            .to_block(best_block);
+        let Some(event_log) = self.inner.eth_filter.logs_for_filter(filter).await?.pop() else {
+            return Ok(None)
+        };
+        let Some(transaction_hash) = event_log.transaction_hash else { return Ok(None) };
+        let Some(receipt) = self.inner.eth_api.transaction_receipt(transaction_hash).await? else {
+            return Ok(None)
+        };
+        let event = IEntryPoint::UserOperationEvent::decode_log_data(&event_log.inner.data, true)
+            .map_err(|err| UserOperationError::InvalidEvent(err.to_string()))?;
+
+        // the logs of the user operation are the logs after the previous user operation, or after
+        // the validation of the bundle for the first user operation
+        let logs = &receipt.inner.inner.inner.receipt.logs;
+        let end = logs.iter().position(|log| log.log_index == event_log.log_index).unwrap_or(0);
+        let start = logs[..end]
+            .iter()
+            .rposition(|log| {
+                log.inner.address == event_log.inner.address &&
+                    log.inner.topics().first().map_or(false, |topic| {
+                        *topic == IEntryPoint::UserOperationEvent::SIGNATURE_HASH ||
+                            *topic == IEntryPoint::BeforeExecution::SIGNATURE_HASH
+                    })
+            })
+            .map_or(0, |index| index + 1);
+        let op_logs = logs[start..end].to_vec();
+        let reason = op_logs.iter().find_map(|log| decode_revert_reason(log, hash));
+
+        self.inner.pool.remove(&hash);
+
+        Ok(Some(UserOperationReceipt {
+            user_op_hash: hash,
+            entry_point: event_log.inner.address,
+            sender: event.sender,
+            nonce: event.nonce,
+            paymaster: event.paymaster,
+            actual_gas_cost: event.actualGasCost,
+            actual_gas_used: event.actualGasUsed,
+            success: event.success,
+            reason,
+            logs: op_logs,
+            receipt,
+        }))
+    }
+
+    /// Executes `simulateValidation` of the EntryPoint for the user operation.
+    ///
+    /// `simulateValidation` always reverts, with `ValidationResult` if the user operation is
+    /// valid and with `FailedOp` otherwise.
+    async fn simulate_validation(
+        &self,
+        user_operation: &UserOperation,
+        entry_point: Address,
+    ) -> Result<IEntryPoint::ReturnInfo, UserOperationError> {
+        let input =
+            IEntryPoint::simulateValidationCall { userOp: to_sol(user_operation) }.abi_encode();
+        let request = TransactionRequest {
+            to: Some(entry_point),
+            input: TransactionInput::new(input.into()),
+            ..Default::default()
+        };
+        let at = Some(BlockId::Number(BlockNumberOrTag::Latest));
+        let output = match self.inner.eth_api.call(request, at, EvmOverrides::default()).await {
+            Ok(_) => {
+                return Err(UserOperationError::Rejected(
+                    "simulateValidation did not revert".to_string(),
+                ))
+            }
+            Err(EthApiError::InvalidTransaction(RpcInvalidTransactionError::Revert(revert))) => {
+                revert.output().cloned().unwrap_or_default()
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if let Ok(result) = IEntryPoint::ValidationResult::abi_decode(&output, true) {
+            return Ok(result.returnInfo)
+        }
+        if let Ok(failed) = IEntryPoint::FailedOp::abi_decode(&output, true) {
+            return Err(UserOperationError::Rejected(failed.reason))
+        }
+        Err(UserOperationError::Rejected(format!(
+            "unexpected revert of simulateValidation: {output}"
+        )))
+    }
+}
+
+#[async_trait::async_trait]
+impl<Provider, Pool, Network, EvmConfig> EthUserOperationApiServer
+    for EthUserOperation<Provider, Pool, Network, EvmConfig>
+where
+    Provider: BlockReaderIdExt
+        + BlockIdReader
+        + ChainSpecProvider
+        + StateProviderFactory
+        + EvmEnvProvider
+        + PruneCheckpointReader
+        + LogIndexReader
+        + 'static,
+    Pool: TransactionPool + Clone + 'static,
+    <Pool as TransactionPool>::Transaction: 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Handler for: `eth_sendUserOperation`
+    async fn send_user_operation(
+        &self,
+        user_operation: UserOperation,
+        entry_point: Address,
+    ) -> RpcResult<B256> {
+        trace!(target: "rpc::eth", %entry_point, "Serving eth_sendUserOperation");
+        Ok(EthUserOperation::send_user_operation(self, user_operation, entry_point).await?)
+    }
+
+    /// Handler for: `eth_estimateUserOperationGas`
+    async fn estimate_user_operation_gas(
+        &self,
+        user_operation: UserOperation,
+        entry_point: Address,
+    ) -> RpcResult<UserOperationGasEstimation> {
+        trace!(target: "rpc::eth", %entry_point, "Serving eth_estimateUserOperationGas");
+        Ok(EthUserOperation::estimate_user_operation_gas(self, user_operation, entry_point).await?)
+    }
+
+    /// Handler for: `eth_getUserOperationReceipt`
+    async fn user_operation_receipt(&self, hash: B256) -> RpcResult<Option<UserOperationReceipt>> {
+        trace!(target: "rpc::eth", ?hash, "Serving eth_getUserOperationReceipt");
+        Ok(EthUserOperation::user_operation_receipt(self, hash).await?)
+    }
+
+    /// Handler for: `eth_supportedEntryPoints`
+    fn supported_entry_points(&self) -> RpcResult<Vec<Address>> {
+        Ok(self.inner.config.entry_points.clone())
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> std::fmt::Debug
+    for EthUserOperation<Provider, Pool, Network, EvmConfig>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthUserOperation")
+            .field("config", &self.inner.config)
+            .field("pool", &self.inner.pool.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> Clone
+    for EthUserOperation<Provider, Pool, Network, EvmConfig>
+{
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+/// Errors of the `eth_` user operation handlers.
+#[derive(Debug, thiserror::Error)]
+pub enum UserOperationError {
+    /// The EntryPoint is not supported.
+    #[error("unsupported entry point {0}")]
+    UnsupportedEntryPoint(Address),
+    /// A field of the user operation is invalid.
+    #[error("invalid user operation: {0}")]
+    InvalidFields(String),
+    /// The EntryPoint rejected the user operation in `simulateValidation`.
+    #[error("user operation rejected: {0}")]
+    Rejected(String),
+    /// The signature of the user operation is invalid.
+    #[error("invalid user operation signature")]
+    InvalidSignature,
+    /// The user operation is not valid now or expires too soon.
+    #[error("user operation is only valid between {valid_after} and {valid_until}")]
+    InvalidTimeRange {
+        /// The time from which the user operation is valid.
+        valid_after: u64,
+        /// The time until which the user operation is valid, `0` for no limit.
+        valid_until: u64,
+    },
+    /// The fees of a replacement user operation are not increased enough.
+    #[error("replacement user operation underpriced")]
+    ReplacementUnderpriced,
+    /// The [UserOperationPool] is full.
+    #[error("user operation pool is full")]
+    PoolFull,
+    /// An event of the EntryPoint could not be decoded.
+    #[error("invalid entry point event: {0}")]
+    InvalidEvent(String),
+    /// The simulated call failed.
+    #[error(transparent)]
+    Eth(#[from] EthApiError),
+    /// The logs of the EntryPoint could not be queried.
+    #[error(transparent)]
+    Filter(#[from] FilterError),
+}
+
+impl UserOperationError {
+    /// The error code of a user operation rejected by the EntryPoint, see ERC-4337.
+    const REJECTED_CODE: i32 = -32500;
+    /// The error code of a user operation with an invalid time range, see ERC-4337.
+    const TIME_RANGE_CODE: i32 = -32503;
+    /// The error code of a user operation with an invalid signature, see ERC-4337.
+    const INVALID_SIGNATURE_CODE: i32 = -32507;
+}
+
+impl From<UserOperationError> for ErrorObject<'static> {
+    fn from(err: UserOperationError) -> Self {
+        match err {
+            UserOperationError::Rejected(_) => {
+                rpc_error_with_code(UserOperationError::REJECTED_CODE, err.to_string())
+            }
+            UserOperationError::InvalidTimeRange { .. } => {
+                rpc_error_with_code(UserOperationError::TIME_RANGE_CODE, err.to_string())
+            }
+            UserOperationError::InvalidSignature => {
+                rpc_error_with_code(UserOperationError::INVALID_SIGNATURE_CODE, err.to_string())
+            }
+            UserOperationError::Eth(err) => err.into(),
+            UserOperationError::Filter(err) => err.into(),
+            err => rpc_error_with_code(INVALID_PARAMS_CODE, err.to_string()),
+        }
+    }
+}
+
+/// Checks the fields of the user operation that don't require a simulation.
+fn validate_fields(user_operation: &UserOperation) -> Result<(), UserOperationError> {
+    if user_operation.max_priority_fee_per_gas > user_operation.max_fee_per_gas {
+        return Err(UserOperationError::InvalidFields(
+            "maxPriorityFeePerGas is higher than maxFeePerGas".to_string(),
+        ))
+    }
+    if !user_operation.init_code.is_empty() && user_operation.init_code.len() < 20 {
+        return Err(UserOperationError::InvalidFields(
+            "initCode must start with the factory address".to_string(),
+        ))
+    }
+    if !user_operation.paymaster_and_data.is_empty() && user_operation.paymaster_and_data.len() < 20
+    {
+        return Err(UserOperationError::InvalidFields(
+            "paymasterAndData must start with the paymaster address".to_string(),
+        ))
+    }
+    let required = pre_verification_gas(user_operation);
+    if user_operation.pre_verification_gas < U256::from(required) {
+        return Err(UserOperationError::InvalidFields(format!(
+            "preVerificationGas must be at least {required}"
+        )))
+    }
+    Ok(())
+}
+
+/// Returns the pre-verification gas of the user operation, which pays for its calldata and its
+/// share of the overhead of the bundle transaction.
+///
+/// This uses the default overheads of the reference bundler, for a bundle of a single user
+/// operation. An empty signature is replaced by a dummy 65 byte signature.
+fn pre_verification_gas(user_operation: &UserOperation) -> u64 {
+    const FIXED: u64 = 21_000;
+    const PER_USER_OP: u64 = 18_300;
+    const PER_USER_OP_WORD: u64 = 4;
+    const ZERO_BYTE: u64 = 4;
+    const NON_ZERO_BYTE: u64 = 16;
+
+    let mut op = to_sol(user_operation);
+    op.preVerificationGas = U256::from(FIXED);
+    if op.signature.is_empty() {
+        op.signature = Bytes::from_static(&[1; 65]);
+    }
+    let packed = op.abi_encode();
+    let calldata_cost: u64 =
+        packed.iter().map(|byte| if *byte == 0 { ZERO_BYTE } else { NON_ZERO_BYTE }).sum();
+    let words = (packed.len() as u64).div_ceil(32);
+    calldata_cost + FIXED + PER_USER_OP + PER_USER_OP_WORD * words
+}
+
+/// Decodes the revert data of the `UserOperationRevertReason` event of the user operation.
+fn decode_revert_reason(log: &Log, hash: B256) -> Option<Bytes> {
+    let topics = log.inner.topics();
+    if topics.first() != Some(&IEntryPoint::UserOperationRevertReason::SIGNATURE_HASH) ||
+        topics.get(1) != Some(&hash)
+    {
+        return None
+    }
+    IEntryPoint::UserOperationRevertReason::decode_log_data(&log.inner.data, true)
+        .ok()
+        .map(|event| event.revertReason)
+}
+
+/// Returns `true` if the new fee is at least [REPLACEMENT_FEE_BUMP] percent higher.
+fn is_fee_bumped(old: U256, new: U256) -> bool {
+    new.saturating_mul(U256::from(100)) >=
+        old.saturating_mul(U256::from(100 + REPLACEMENT_FEE_BUMP))
+}
+
+/// Converts the user operation into its ABI type.
+fn to_sol(user_operation: &UserOperation) -> IEntryPoint::UserOperation {
+    IEntryPoint::UserOperation {
+        sender: user_operation.sender,
+        nonce: user_operation.nonce,
+        initCode: user_operation.init_code.clone(),
+        callData: user_operation.call_data.clone(),
+        callGasLimit: user_operation.call_gas_limit,
+        verificationGasLimit: user_operation.verification_gas_limit,
+        preVerificationGas: user_operation.pre_verification_gas,
+        maxFeePerGas: user_operation.max_fee_per_gas,
+        maxPriorityFeePerGas: user_operation.max_priority_fee_per_gas,
+        paymasterAndData: user_operation.paymaster_and_data.clone(),
+        signature: user_operation.signature.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pooled(sender: u8, nonce: u64, fee: u64) -> PooledUserOperation {
+        let user_operation = UserOperation {
+            sender: Address::with_last_byte(sender),
+            nonce: U256::from(nonce),
+            max_fee_per_gas: U256::from(fee),
+            max_priority_fee_per_gas: U256::from(fee),
+            ..Default::default()
+        };
+        let hash = user_operation.hash(ENTRY_POINT_V06, 1);
+        PooledUserOperation { user_operation, entry_point: ENTRY_POINT_V06, hash }
+    }
+
+    #[test]
+    fn pool_replacement_and_limit() {
+        let pool = UserOperationPool::new(2);
+        pool.add(pooled(1, 0, 100)).unwrap();
+        pool.add(pooled(2, 0, 200)).unwrap();
+        assert!(matches!(pool.add(pooled(3, 0, 300)), Err(UserOperationError::PoolFull)));
+
+        // the same sender and nonce replaces the user operation if the fees are bumped
+        assert!(matches!(
+            pool.add(pooled(1, 0, 105)),
+            Err(UserOperationError::ReplacementUnderpriced)
+        ));
+        let replacement = pooled(1, 0, 110);
+        let replaced = pool.add(replacement.clone()).unwrap().unwrap();
+        assert_eq!(replaced.user_operation.max_fee_per_gas, U256::from(100));
+        assert_eq!(pool.len(), 2);
+
+        let best = pool.best(ENTRY_POINT_V06);
+        assert_eq!(best[0].user_operation.sender, Address::with_last_byte(2));
+        assert_eq!(best[1], replacement);
+        assert!(pool.best(Address::ZERO).is_empty());
+
+        assert_eq!(pool.remove(&replacement.hash), Some(replacement.clone()));
+        assert_eq!(pool.get(&replacement.hash), None);
+        pool.add(pooled(1, 0, 50)).unwrap();
+    }
+
+    #[test]
+    fn validate_user_operation_fields() {
+        let mut op = UserOperation {
+            max_fee_per_gas: U256::from(2),
+            max_priority_fee_per_gas: U256::from(1),
+            ..Default::default()
+        };
+        assert!(matches!(validate_fields(&op), Err(UserOperationError::InvalidFields(_))));
+
+        op.pre_verification_gas = U256::from(pre_verification_gas(&op));
+        validate_fields(&op).unwrap();
+
+        op.init_code = Bytes::from_static(&[1; 4]);
+        assert!(validate_fields(&op).is_err());
+        op.init_code = Bytes::new();
+        op.max_priority_fee_per_gas = U256::from(3);
+        assert!(validate_fields(&op).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+//! Batched loading of block data that is requested by a query.
+
+use super::GraphQlProvider;
+use async_graphql::dataloader::Loader;
+use reth_primitives::{BlockNumber, BlockWithSenders, Receipt, B256};
+use reth_provider::ProviderError;
+use std::{collections::HashMap, ops::RangeInclusive, sync::Arc};
+
+/// Loads the bodies of canonical blocks by number.
+///
+/// Consecutive block numbers are loaded with a single range query.
+#[derive(Debug)]
+pub(crate) struct BlockBodyLoader<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> BlockBodyLoader<Provider> {
+    pub(crate) const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+impl<Provider> Loader<BlockNumber> for BlockBodyLoader<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    type Value = Arc<BlockWithSenders>;
+    type Error = Arc<ProviderError>;
+
+    async fn load(
+        &self,
+        keys: &[BlockNumber],
+    ) -> Result<HashMap<BlockNumber, Self::Value>, Self::Error> {
+        let mut bodies = HashMap::with_capacity(keys.len());
+        for range in consecutive_ranges(keys) {
+            let blocks = self.provider.block_with_senders_range(range)?;
+            bodies.extend(blocks.into_iter().map(|block| (block.number, Arc::new(block))));
+        }
+        Ok(bodies)
+    }
+}
+
+/// Loads the receipts of blocks by hash.
+#[derive(Debug)]
+pub(crate) struct BlockReceiptsLoader<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> BlockReceiptsLoader<Provider> {
+    pub(crate) const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+impl<Provider> Loader<B256> for BlockReceiptsLoader<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    type Value = Arc<Vec<Receipt>>;
+    type Error = Arc<ProviderError>;
+
+    async fn load(&self, keys: &[B256]) -> Result<HashMap<B256, Self::Value>, Self::Error> {
+        let mut receipts = HashMap::with_capacity(keys.len());
+        for hash in keys {
+            if let Some(block_receipts) = self.provider.receipts_by_block((*hash).into())? {
+                receipts.insert(*hash, Arc::new(block_receipts));
+            }
+        }
+        Ok(receipts)
+    }
+}
+
+/// Splits the block numbers into ranges of consecutive numbers.
+fn consecutive_ranges(numbers: &[BlockNumber]) -> Vec<RangeInclusive<BlockNumber>> {
+    let mut numbers = numbers.to_vec();
+    numbers.sort_unstable();
+    numbers.dedup();
+
+    let mut ranges = Vec::new();
+    let mut iter = numbers.into_iter();
+    let Some(mut start) = iter.next() else { return ranges };
+    let mut end = start;
+    for number in iter {
+        if number != end + 1 {
+            ranges.push(start..=end);
+            start = number;
+        }
+        end = number;
+    }
+    ranges.push(start..=end);
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consecutive_ranges() {
+        assert!(consecutive_ranges(&[]).is_empty());
+        assert_eq!(consecutive_ranges(&[5]), vec![5..=5]);
+        assert_eq!(consecutive_ranges(&[3, 1, 2, 2, 7, 9, 8, 11]), vec![1..=3, 7..=9, 11..=11]);
+    }
+}
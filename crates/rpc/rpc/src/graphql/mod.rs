@@ -0,0 +1,167 @@
+//! GraphQL API as specified in [EIP-1767](https://eips.ethereum.org/EIPS/eip-1767).
+//!
+//! The API exposes blocks, transactions, receipts, logs and account state of the canonical chain.
+//! Block bodies and receipts requested by the same query are loaded from the provider in batches,
+//! so that a query over a range of blocks does not result in a provider lookup per resolved field.
+
+use async_graphql::{
+    dataloader::DataLoader, BatchRequest, BatchResponse, EmptyMutation, EmptySubscription,
+    Response, Schema, ServerError,
+};
+use futures::future::BoxFuture;
+use reth_provider::{BlockReaderIdExt, ChainSpecProvider, StateProviderFactory};
+use std::{fmt, sync::Arc};
+use tokio::runtime::Handle;
+
+mod loaders;
+mod scalars;
+mod schema;
+
+use loaders::{BlockBodyLoader, BlockReceiptsLoader};
+use schema::Query;
+
+/// The default maximum depth of a GraphQL query.
+pub const DEFAULT_GRAPHQL_MAX_DEPTH: usize = 16;
+
+/// The default maximum complexity of a GraphQL query.
+pub const DEFAULT_GRAPHQL_MAX_COMPLEXITY: usize = 10_000;
+
+/// The default maximum number of blocks returned by a `blocks` query.
+pub const DEFAULT_GRAPHQL_MAX_BLOCKS: u64 = 1_000;
+
+/// The default maximum size of a GraphQL request body in bytes.
+pub const DEFAULT_GRAPHQL_MAX_REQUEST_BODY_SIZE: u32 = 15 * 1024 * 1024;
+
+/// Helper trait with all the provider functionality the GraphQL API requires.
+pub trait GraphQlProvider:
+    BlockReaderIdExt + StateProviderFactory + ChainSpecProvider + Clone + Unpin + 'static
+{
+}
+
+impl<T> GraphQlProvider for T where
+    T: BlockReaderIdExt + StateProviderFactory + ChainSpecProvider + Clone + Unpin + 'static
+{
+}
+
+/// Settings for the GraphQL API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GraphQlConfig {
+    /// Maximum depth of a query.
+    pub max_depth: usize,
+    /// Maximum complexity of a query, every requested field adds one to the complexity.
+    pub max_complexity: usize,
+    /// Maximum number of blocks returned by a `blocks` query.
+    pub max_blocks: u64,
+    /// Maximum number of blocks that are scanned by a `logs` query.
+    pub max_blocks_per_filter: u64,
+    /// Maximum size of a request body in bytes.
+    pub max_request_body_size: u32,
+}
+
+impl GraphQlConfig {
+    /// Sets the maximum depth of a query.
+    pub const fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the maximum complexity of a query.
+    pub const fn max_complexity(mut self, max_complexity: usize) -> Self {
+        self.max_complexity = max_complexity;
+        self
+    }
+
+    /// Sets the maximum number of blocks returned by a `blocks` query.
+    pub const fn max_blocks(mut self, max_blocks: u64) -> Self {
+        self.max_blocks = max_blocks;
+        self
+    }
+
+    /// Sets the maximum number of blocks that are scanned by a `logs` query.
+    pub const fn max_blocks_per_filter(mut self, max_blocks: u64) -> Self {
+        self.max_blocks_per_filter = max_blocks;
+        self
+    }
+
+    /// Sets the maximum size of a request body in bytes.
+    pub const fn max_request_body_size(mut self, max_size: u32) -> Self {
+        self.max_request_body_size = max_size;
+        self
+    }
+}
+
+impl Default for GraphQlConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: DEFAULT_GRAPHQL_MAX_DEPTH,
+            max_complexity: DEFAULT_GRAPHQL_MAX_COMPLEXITY,
+            max_blocks: DEFAULT_GRAPHQL_MAX_BLOCKS,
+            max_blocks_per_filter: DEFAULT_GRAPHQL_MAX_BLOCKS * 100,
+            max_request_body_size: DEFAULT_GRAPHQL_MAX_REQUEST_BODY_SIZE,
+        }
+    }
+}
+
+/// Executes GraphQL requests against a provider.
+///
+/// The schema is generic over the provider, this type erases it so that it can be served by the
+/// http server.
+#[derive(Clone)]
+pub struct GraphQl {
+    execute: Arc<dyn Fn(BatchRequest) -> BoxFuture<'static, BatchResponse> + Send + Sync>,
+    config: GraphQlConfig,
+}
+
+impl GraphQl {
+    /// Creates the GraphQL API for the given provider.
+    pub fn new<Provider>(provider: Provider, config: GraphQlConfig) -> Self
+    where
+        Provider: GraphQlProvider,
+    {
+        let schema =
+            Schema::build(Query::new(provider.clone(), config), EmptyMutation, EmptySubscription)
+                .limit_depth(config.max_depth)
+                .limit_complexity(config.max_complexity)
+                .data(DataLoader::new(BlockBodyLoader::new(provider.clone()), tokio::spawn))
+                .data(DataLoader::new(BlockReceiptsLoader::new(provider), tokio::spawn))
+                .finish();
+
+        let execute = move |request: BatchRequest| {
+            let schema = schema.clone();
+            Box::pin(async move { schema.execute_batch(request).await }) as BoxFuture<'static, _>
+        };
+        Self { execute: Arc::new(execute), config }
+    }
+
+    /// Returns the config of the GraphQL API.
+    pub const fn config(&self) -> &GraphQlConfig {
+        &self.config
+    }
+
+    /// Executes the (batched) request.
+    ///
+    /// Resolving a query reads from the database, the request is therefore executed on a blocking
+    /// thread.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime.
+    pub async fn execute(&self, request: BatchRequest) -> BatchResponse {
+        let execute = self.execute.clone();
+        let handle = Handle::current();
+        tokio::task::spawn_blocking(move || handle.block_on(execute(request))).await.unwrap_or_else(
+            |_| {
+                BatchResponse::Single(Response::from_errors(vec![ServerError::new(
+                    "internal error while executing the request",
+                    None,
+                )]))
+            },
+        )
+    }
+}
+
+impl fmt::Debug for GraphQl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQl").field("config", &self.config).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,132 @@
+//! Scalar types of the EIP-1767 schema.
+
+use async_graphql::{InputType, InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use reth_primitives::{Address as PrimitiveAddress, Bytes as PrimitiveBytes, B256, U256};
+use std::str::FromStr;
+
+/// A 32 byte binary string, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Bytes32(pub(crate) B256);
+
+#[Scalar]
+impl ScalarType for Bytes32 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// A 20 byte Ethereum address, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Address(pub(crate) PrimitiveAddress);
+
+#[Scalar]
+impl ScalarType for Address {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// An arbitrary length binary string, represented as 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Bytes(pub(crate) PrimitiveBytes);
+
+#[Scalar]
+impl ScalarType for Bytes {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        parse_str(value).map(Self)
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+/// A large integer, input is accepted as either a JSON number or as a string, strings may be
+/// either decimal or 0x-prefixed hexadecimal. Output values are all 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BigInt(pub(crate) U256);
+
+#[Scalar]
+impl ScalarType for BigInt {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(number) => number
+                .as_u64()
+                .map(|number| Self(U256::from(number)))
+                .ok_or_else(|| InputValueError::custom("expected a non-negative integer")),
+            _ => parse_str(value).map(Self),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// A 64 bit unsigned integer, input is accepted as either a JSON number or as a string, strings
+/// may be either decimal or 0x-prefixed hexadecimal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Long(pub(crate) u64);
+
+#[Scalar]
+impl ScalarType for Long {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        let parsed = match &value {
+            Value::Number(number) => number.as_u64(),
+            Value::String(s) => match s.strip_prefix("0x") {
+                Some(hex) => u64::from_str_radix(hex, 16).ok(),
+                None => s.parse().ok(),
+            },
+            _ => return Err(InputValueError::expected_type(value)),
+        };
+        parsed
+            .map(Self)
+            .ok_or_else(|| InputValueError::custom("expected a non-negative 64 bit integer"))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Number(self.0.into())
+    }
+}
+
+/// Parses a string value.
+fn parse_str<T: FromStr, S: InputType>(value: Value) -> Result<T, InputValueError<S>> {
+    match &value {
+        Value::String(s) => {
+            s.parse().map_err(|_| InputValueError::custom(format!("invalid value: {s}")))
+        }
+        _ => Err(InputValueError::expected_type(value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scalars() {
+        assert_eq!(Long::parse(Value::from(10u64)).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::from("0x10")).unwrap(), Long(16));
+        assert_eq!(Long::parse(Value::from("10")).unwrap(), Long(10));
+        assert!(Long::parse(Value::from(-1)).is_err());
+
+        assert_eq!(BigInt::parse(Value::from("0x10")).unwrap(), BigInt(U256::from(16)));
+        assert_eq!(BigInt::parse(Value::from("10")).unwrap(), BigInt(U256::from(10)));
+        assert_eq!(BigInt(U256::from(16)).to_value(), Value::from("0x10"));
+
+        assert_eq!(
+            Bytes32::parse(Value::from(B256::with_last_byte(1).to_string())).unwrap(),
+            Bytes32(B256::with_last_byte(1))
+        );
+        assert!(Bytes32::parse(Value::from("0x01")).is_err());
+        assert!(Address::parse(Value::from(1)).is_err());
+    }
+}
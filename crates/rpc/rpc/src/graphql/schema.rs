@@ -0,0 +1,801 @@
+//! Object types of the EIP-1767 schema.
+
+use super::{
+    loaders::{BlockBodyLoader, BlockReceiptsLoader},
+    scalars::{Address, BigInt, Bytes, Bytes32, Long},
+    GraphQlConfig, GraphQlProvider,
+};
+use alloy_rlp::Encodable;
+use async_graphql::{dataloader::DataLoader, Context, Error, InputObject, Object, Result};
+use reth_primitives::{
+    Address as PrimitiveAddress, BlockId, BlockNumberOrTag, BlockWithSenders, BloomInput,
+    Log as PrimitiveLog, Receipt, SealedHeader, TransactionSigned, TxType, B256, U256,
+};
+use reth_provider::{StateProvider, StateProviderBox, TransactionVariant};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+/// The root of all queries.
+pub(crate) struct Query<Provider> {
+    provider: Provider,
+    config: GraphQlConfig,
+}
+
+impl<Provider> Query<Provider> {
+    pub(crate) const fn new(provider: Provider, config: GraphQlConfig) -> Self {
+        Self { provider, config }
+    }
+}
+
+#[Object(name = "Query")]
+impl<Provider> Query<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    /// Fetches an Ethereum block by number or by hash. If neither is supplied, the most recent
+    /// known block is returned.
+    async fn block(
+        &self,
+        number: Option<Long>,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Block<Provider>>> {
+        let header = match (number, hash) {
+            (Some(_), Some(_)) => return Err(Error::new("only one of number or hash is allowed")),
+            (Some(number), None) => self.provider.sealed_header(number.0)?,
+            (None, Some(hash)) => self.provider.header(&hash.0)?.map(|header| header.seal(hash.0)),
+            (None, None) => self.provider.latest_header()?,
+        };
+        Ok(header.map(|header| Block::new(self.provider.clone(), header)))
+    }
+
+    /// Returns all blocks in the inclusive range `from` to `to`, `to` defaults to the most recent
+    /// known block.
+    async fn blocks(&self, from: Long, to: Option<Long>) -> Result<Vec<Block<Provider>>> {
+        let to = match to {
+            Some(to) => to.0,
+            None => self.provider.best_block_number()?,
+        };
+        if from.0 > to {
+            return Ok(Vec::new())
+        }
+        if to - from.0 >= self.config.max_blocks {
+            return Err(Error::new(format!(
+                "block range exceeds the maximum of {} blocks",
+                self.config.max_blocks
+            )))
+        }
+        let headers = self.provider.sealed_headers_range(from.0..=to)?;
+        Ok(headers.into_iter().map(|header| Block::new(self.provider.clone(), header)).collect())
+    }
+
+    /// Returns a transaction specified by its hash.
+    async fn transaction(&self, hash: Bytes32) -> Result<Option<Transaction<Provider>>> {
+        let Some((_, meta)) = self.provider.transaction_by_hash_with_meta(hash.0)? else {
+            return Ok(None)
+        };
+        let Some(header) = self.provider.header(&meta.block_hash)? else { return Ok(None) };
+        let block = Block::new(self.provider.clone(), header.seal(meta.block_hash));
+        Ok(Some(Transaction { block, index: meta.index as usize }))
+    }
+
+    /// Returns log entries matching the provided filter.
+    async fn logs(&self, ctx: &Context<'_>, filter: FilterCriteria) -> Result<Vec<Log<Provider>>> {
+        let best = self.provider.best_block_number()?;
+        let from = filter.from_block.map_or(best, |from| from.0);
+        let to = filter.to_block.map_or(best, |to| to.0).min(best);
+        if from > to {
+            return Ok(Vec::new())
+        }
+        if to - from >= self.config.max_blocks_per_filter {
+            return Err(Error::new(format!(
+                "query exceeds max block range {}",
+                self.config.max_blocks_per_filter
+            )))
+        }
+
+        let filter = LogFilter::new(filter.addresses, filter.topics);
+        let mut logs = Vec::new();
+        for header in self.provider.sealed_headers_range(from..=to)? {
+            if filter.matches_bloom(&header) {
+                logs.extend(Block::new(self.provider.clone(), header).logs(ctx, &filter).await?);
+            }
+        }
+        Ok(logs)
+    }
+
+    /// Returns the current chain ID for transaction replay protection.
+    #[graphql(name = "chainID")]
+    async fn chain_id(&self) -> BigInt {
+        BigInt(U256::from(self.provider.chain_spec().chain.id()))
+    }
+}
+
+/// An Ethereum block.
+pub(crate) struct Block<Provider> {
+    provider: Provider,
+    inner: Arc<BlockInner>,
+}
+
+/// The data of a block, the body and receipts are loaded when they are first requested.
+struct BlockInner {
+    header: SealedHeader,
+    body: OnceCell<Arc<BlockWithSenders>>,
+    receipts: OnceCell<Arc<Vec<Receipt>>>,
+}
+
+impl<Provider: Clone> Clone for Block<Provider> {
+    fn clone(&self) -> Self {
+        Self { provider: self.provider.clone(), inner: self.inner.clone() }
+    }
+}
+
+impl<Provider> Block<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    fn new(provider: Provider, header: SealedHeader) -> Self {
+        let inner = BlockInner { header, body: OnceCell::new(), receipts: OnceCell::new() };
+        Self { provider, inner: Arc::new(inner) }
+    }
+
+    fn header(&self) -> &SealedHeader {
+        &self.inner.header
+    }
+
+    /// Returns the body of the block, all bodies requested by a query are loaded in batches.
+    async fn body(&self, ctx: &Context<'_>) -> Result<Arc<BlockWithSenders>> {
+        self.inner
+            .body
+            .get_or_try_init(|| async {
+                let loader = ctx.data_unchecked::<DataLoader<BlockBodyLoader<Provider>>>();
+                match loader.load_one(self.header().number).await? {
+                    Some(body) if body.header == *self.header().header() => Ok(body),
+                    // the block is not canonical (anymore), load it by hash
+                    _ => self
+                        .provider
+                        .block_with_senders(
+                            self.header().hash().into(),
+                            TransactionVariant::WithHash,
+                        )?
+                        .map(Arc::new)
+                        .ok_or_else(|| Error::new("block body not found")),
+                }
+            })
+            .await
+            .cloned()
+    }
+
+    /// Returns the receipts of the block, all receipts requested by a query are loaded in
+    /// batches.
+    async fn receipts(&self, ctx: &Context<'_>) -> Result<Arc<Vec<Receipt>>> {
+        self.inner
+            .receipts
+            .get_or_try_init(|| async {
+                let loader = ctx.data_unchecked::<DataLoader<BlockReceiptsLoader<Provider>>>();
+                loader
+                    .load_one(self.header().hash())
+                    .await?
+                    .ok_or_else(|| Error::new("block receipts not found"))
+            })
+            .await
+            .cloned()
+    }
+
+    /// Returns the logs of the block that match the filter.
+    async fn logs(&self, ctx: &Context<'_>, filter: &LogFilter) -> Result<Vec<Log<Provider>>> {
+        let mut logs = Vec::new();
+        // index of the log in the block
+        let mut log_index = 0;
+        for (index, receipt) in self.receipts(ctx).await?.iter().enumerate() {
+            for log in &receipt.logs {
+                if filter.matches(log) {
+                    logs.push(Log {
+                        transaction: Transaction { block: self.clone(), index },
+                        index: log_index,
+                        log: log.clone(),
+                    });
+                }
+                log_index += 1;
+            }
+        }
+        Ok(logs)
+    }
+
+    /// Returns the account at the state of this block or of the given block.
+    fn account(&self, address: PrimitiveAddress, block: Option<Long>) -> Account<Provider> {
+        let block = match block {
+            Some(number) => BlockNumberOrTag::Number(number.0).into(),
+            None => self.header().hash().into(),
+        };
+        Account { provider: self.provider.clone(), address, block }
+    }
+}
+
+#[Object(name = "Block")]
+impl<Provider> Block<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    /// The block number.
+    async fn number(&self) -> Long {
+        Long(self.header().number)
+    }
+
+    /// The block hash.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.header().hash())
+    }
+
+    /// The parent block, `null` for the genesis block.
+    async fn parent(&self) -> Result<Option<Block<Provider>>> {
+        if self.header().number == 0 {
+            return Ok(None)
+        }
+        let parent_hash = self.header().parent_hash;
+        let header = self.provider.header(&parent_hash)?.map(|header| header.seal(parent_hash));
+        Ok(header.map(|header| Block::new(self.provider.clone(), header)))
+    }
+
+    /// The block nonce, an 8 byte sequence determined by the miner.
+    async fn nonce(&self) -> Bytes {
+        Bytes(self.header().nonce.to_be_bytes().to_vec().into())
+    }
+
+    /// The root of the transactions trie of the block.
+    async fn transactions_root(&self) -> Bytes32 {
+        Bytes32(self.header().transactions_root)
+    }
+
+    /// The number of transactions in the block.
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<Long> {
+        Ok(Long(self.body(ctx).await?.body.len() as u64))
+    }
+
+    /// The root of the final state trie of the block.
+    async fn state_root(&self) -> Bytes32 {
+        Bytes32(self.header().state_root)
+    }
+
+    /// The root of the receipts trie of the block.
+    async fn receipts_root(&self) -> Bytes32 {
+        Bytes32(self.header().receipts_root)
+    }
+
+    /// The account that mined the block, queried at the given block or this block.
+    async fn miner(&self, block: Option<Long>) -> Account<Provider> {
+        self.account(self.header().beneficiary, block)
+    }
+
+    /// An arbitrary data field supplied by the miner.
+    async fn extra_data(&self) -> Bytes {
+        Bytes(self.header().extra_data.clone())
+    }
+
+    /// The maximum amount of gas that was available to transactions in the block.
+    async fn gas_limit(&self) -> Long {
+        Long(self.header().gas_limit)
+    }
+
+    /// The amount of gas that was used executing transactions in the block.
+    async fn gas_used(&self) -> Long {
+        Long(self.header().gas_used)
+    }
+
+    /// The price per unit of gas burned by transactions in the block, `null` before London.
+    async fn base_fee_per_gas(&self) -> Option<BigInt> {
+        self.header().base_fee_per_gas.map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The base fee per gas of the next block, `null` before London.
+    async fn next_base_fee_per_gas(&self) -> Option<BigInt> {
+        let params =
+            self.provider.chain_spec().base_fee_params_at_timestamp(self.header().timestamp);
+        self.header().next_block_base_fee(params).map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The unix timestamp at which the block was mined.
+    async fn timestamp(&self) -> Long {
+        Long(self.header().timestamp)
+    }
+
+    /// The bloom filter of the logs of the block.
+    async fn logs_bloom(&self) -> Bytes {
+        Bytes(self.header().logs_bloom.to_vec().into())
+    }
+
+    /// The hash that was used as an input to the PoW process.
+    async fn mix_hash(&self) -> Bytes32 {
+        Bytes32(self.header().mix_hash)
+    }
+
+    /// The difficulty of the block.
+    async fn difficulty(&self) -> BigInt {
+        BigInt(self.header().difficulty)
+    }
+
+    /// The sum of the difficulties of all blocks up to and including this block.
+    async fn total_difficulty(&self) -> Result<BigInt> {
+        let td = self.provider.header_td_by_number(self.header().number)?;
+        td.map(BigInt).ok_or_else(|| Error::new("total difficulty not found"))
+    }
+
+    /// The number of ommers of the block.
+    async fn ommer_count(&self, ctx: &Context<'_>) -> Result<Long> {
+        Ok(Long(self.body(ctx).await?.ommers.len() as u64))
+    }
+
+    /// The hash of the list of ommers of the block.
+    async fn ommer_hash(&self) -> Bytes32 {
+        Bytes32(self.header().ommers_hash)
+    }
+
+    /// The transactions of the block.
+    async fn transactions(&self, ctx: &Context<'_>) -> Result<Vec<Transaction<Provider>>> {
+        let count = self.body(ctx).await?.body.len();
+        Ok((0..count).map(|index| Transaction { block: self.clone(), index }).collect())
+    }
+
+    /// The transaction at the given index of the block.
+    async fn transaction_at(
+        &self,
+        ctx: &Context<'_>,
+        index: Long,
+    ) -> Result<Option<Transaction<Provider>>> {
+        let count = self.body(ctx).await?.body.len();
+        let index = index.0 as usize;
+        Ok((index < count).then(|| Transaction { block: self.clone(), index }))
+    }
+
+    /// The logs of the block that match the filter.
+    #[graphql(name = "logs")]
+    async fn filter_logs(
+        &self,
+        ctx: &Context<'_>,
+        filter: BlockFilterCriteria,
+    ) -> Result<Vec<Log<Provider>>> {
+        let filter = LogFilter::new(filter.addresses, filter.topics);
+        if !filter.matches_bloom(self.header()) {
+            return Ok(Vec::new())
+        }
+        self.logs(ctx, &filter).await
+    }
+
+    /// An account at the state of this block.
+    #[graphql(name = "account")]
+    async fn account_at(&self, address: Address) -> Account<Provider> {
+        self.account(address.0, None)
+    }
+
+    /// The RLP encoding of the header of the block.
+    async fn raw_header(&self) -> Bytes {
+        let mut out = Vec::new();
+        self.header().header().encode(&mut out);
+        Bytes(out.into())
+    }
+
+    /// The root of the withdrawals trie of the block, `null` before Shanghai.
+    async fn withdrawals_root(&self) -> Option<Bytes32> {
+        self.header().withdrawals_root.map(Bytes32)
+    }
+
+    /// The withdrawals of the block, `null` before Shanghai.
+    async fn withdrawals(&self, ctx: &Context<'_>) -> Result<Option<Vec<Withdrawal>>> {
+        let body = self.body(ctx).await?;
+        Ok(body.withdrawals.as_ref().map(|withdrawals| {
+            withdrawals
+                .iter()
+                .map(|withdrawal| Withdrawal {
+                    index: Long(withdrawal.index),
+                    validator: Long(withdrawal.validator_index),
+                    address: Address(withdrawal.address),
+                    amount: Long(withdrawal.amount),
+                })
+                .collect()
+        }))
+    }
+}
+
+/// An Ethereum transaction.
+pub(crate) struct Transaction<Provider> {
+    /// The block the transaction was included in
+    block: Block<Provider>,
+    /// The index of the transaction in the block
+    index: usize,
+}
+
+impl<Provider> Transaction<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    /// Returns the transaction and its sender.
+    async fn signed(&self, ctx: &Context<'_>) -> Result<(TransactionSigned, PrimitiveAddress)> {
+        let body = self.block.body(ctx).await?;
+        let tx = body.body.get(self.index).cloned();
+        let sender = body.senders.get(self.index).copied();
+        tx.zip(sender).ok_or_else(|| Error::new("transaction not found"))
+    }
+
+    /// Returns the receipt of the transaction and the cumulative gas used by all previous
+    /// transactions of the block.
+    async fn receipt(&self, ctx: &Context<'_>) -> Result<(Receipt, u64)> {
+        let receipts = self.block.receipts(ctx).await?;
+        let receipt =
+            receipts.get(self.index).cloned().ok_or_else(|| Error::new("receipt not found"))?;
+        let previous = self
+            .index
+            .checked_sub(1)
+            .and_then(|index| receipts.get(index))
+            .map_or(0, |receipt| receipt.cumulative_gas_used);
+        Ok((receipt, previous))
+    }
+}
+
+#[Object(name = "Transaction")]
+impl<Provider> Transaction<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    /// The hash of the transaction.
+    async fn hash(&self, ctx: &Context<'_>) -> Result<Bytes32> {
+        Ok(Bytes32(self.signed(ctx).await?.0.hash()))
+    }
+
+    /// The nonce of the account the transaction was sent from.
+    async fn nonce(&self, ctx: &Context<'_>) -> Result<Long> {
+        Ok(Long(self.signed(ctx).await?.0.nonce()))
+    }
+
+    /// The index of the transaction in the block.
+    async fn index(&self) -> Long {
+        Long(self.index as u64)
+    }
+
+    /// The account that sent the transaction, queried at the given block or the block of the
+    /// transaction.
+    async fn from(&self, ctx: &Context<'_>, block: Option<Long>) -> Result<Account<Provider>> {
+        let (_, sender) = self.signed(ctx).await?;
+        Ok(self.block.account(sender, block))
+    }
+
+    /// The account the transaction was sent to, `null` for contract creations.
+    async fn to(
+        &self,
+        ctx: &Context<'_>,
+        block: Option<Long>,
+    ) -> Result<Option<Account<Provider>>> {
+        let (tx, _) = self.signed(ctx).await?;
+        Ok(tx.to().map(|to| self.block.account(to, block)))
+    }
+
+    /// The value in wei that was transferred.
+    async fn value(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(self.signed(ctx).await?.0.value()))
+    }
+
+    /// The price per unit of gas that was paid by the sender.
+    async fn gas_price(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        self.effective_gas_price(ctx).await
+    }
+
+    /// The maximum fee per unit of gas the sender is willing to pay, `null` for transactions
+    /// without dynamic fees.
+    async fn max_fee_per_gas(&self, ctx: &Context<'_>) -> Result<Option<BigInt>> {
+        let (tx, _) = self.signed(ctx).await?;
+        Ok(tx.is_dynamic_fee().then(|| BigInt(U256::from(tx.max_fee_per_gas()))))
+    }
+
+    /// The maximum priority fee per unit of gas the sender is willing to pay, `null` for
+    /// transactions without dynamic fees.
+    async fn max_priority_fee_per_gas(&self, ctx: &Context<'_>) -> Result<Option<BigInt>> {
+        let (tx, _) = self.signed(ctx).await?;
+        Ok(tx.max_priority_fee_per_gas().map(|fee| BigInt(U256::from(fee))))
+    }
+
+    /// The priority fee per unit of gas that was paid to the miner.
+    async fn effective_tip(&self, ctx: &Context<'_>) -> Result<Option<BigInt>> {
+        let (tx, _) = self.signed(ctx).await?;
+        let base_fee = self.block.header().base_fee_per_gas;
+        Ok(tx.effective_tip_per_gas(base_fee).map(|tip| BigInt(U256::from(tip))))
+    }
+
+    /// The maximum amount of gas the transaction is allowed to use.
+    async fn gas(&self, ctx: &Context<'_>) -> Result<Long> {
+        Ok(Long(self.signed(ctx).await?.0.gas_limit()))
+    }
+
+    /// The data supplied to the target of the transaction.
+    async fn input_data(&self, ctx: &Context<'_>) -> Result<Bytes> {
+        Ok(Bytes(self.signed(ctx).await?.0.input().clone()))
+    }
+
+    /// The block the transaction was included in.
+    async fn block(&self) -> Block<Provider> {
+        self.block.clone()
+    }
+
+    /// The status of the transaction, 1 for success and 0 for failure.
+    async fn status(&self, ctx: &Context<'_>) -> Result<Long> {
+        Ok(Long(self.receipt(ctx).await?.0.success as u64))
+    }
+
+    /// The amount of gas that was used executing the transaction.
+    async fn gas_used(&self, ctx: &Context<'_>) -> Result<Long> {
+        let (receipt, previous) = self.receipt(ctx).await?;
+        Ok(Long(receipt.cumulative_gas_used - previous))
+    }
+
+    /// The amount of gas that was used executing this and all previous transactions of the
+    /// block.
+    async fn cumulative_gas_used(&self, ctx: &Context<'_>) -> Result<Long> {
+        Ok(Long(self.receipt(ctx).await?.0.cumulative_gas_used))
+    }
+
+    /// The price per unit of gas that was paid by the sender.
+    async fn effective_gas_price(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        let (tx, _) = self.signed(ctx).await?;
+        let base_fee = self.block.header().base_fee_per_gas;
+        Ok(BigInt(U256::from(tx.effective_gas_price(base_fee))))
+    }
+
+    /// The contract created by the transaction, `null` if it is not a contract creation.
+    async fn created_contract(
+        &self,
+        ctx: &Context<'_>,
+        block: Option<Long>,
+    ) -> Result<Option<Account<Provider>>> {
+        let (tx, sender) = self.signed(ctx).await?;
+        Ok(tx.to().is_none().then(|| self.block.account(sender.create(tx.nonce()), block)))
+    }
+
+    /// The logs emitted by the transaction.
+    async fn logs(&self, ctx: &Context<'_>) -> Result<Vec<Log<Provider>>> {
+        let receipts = self.block.receipts(ctx).await?;
+        let receipt = receipts.get(self.index).ok_or_else(|| Error::new("receipt not found"))?;
+        let first_log_index =
+            receipts.iter().take(self.index).map(|receipt| receipt.logs.len() as u64).sum::<u64>();
+        Ok(receipt
+            .logs
+            .iter()
+            .enumerate()
+            .map(|(index, log)| Log {
+                transaction: Transaction { block: self.block.clone(), index: self.index },
+                index: first_log_index + index as u64,
+                log: log.clone(),
+            })
+            .collect())
+    }
+
+    /// The R field of the signature.
+    async fn r(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(self.signed(ctx).await?.0.signature.r))
+    }
+
+    /// The S field of the signature.
+    async fn s(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(self.signed(ctx).await?.0.signature.s))
+    }
+
+    /// The V field of the signature, the y parity for typed transactions.
+    async fn v(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        let (tx, _) = self.signed(ctx).await?;
+        let v = match tx.tx_type() {
+            TxType::Legacy => tx.signature.v(tx.chain_id()),
+            _ => tx.signature.odd_y_parity as u64,
+        };
+        Ok(BigInt(U256::from(v)))
+    }
+
+    /// The EIP-2718 type of the transaction.
+    #[graphql(name = "type")]
+    async fn tx_type(&self, ctx: &Context<'_>) -> Result<Long> {
+        Ok(Long(u8::from(self.signed(ctx).await?.0.tx_type()) as u64))
+    }
+
+    /// The EIP-2718 encoding of the transaction.
+    async fn raw(&self, ctx: &Context<'_>) -> Result<Bytes> {
+        Ok(Bytes(self.signed(ctx).await?.0.envelope_encoded()))
+    }
+}
+
+/// An Ethereum event log.
+pub(crate) struct Log<Provider> {
+    /// The transaction that emitted the log
+    transaction: Transaction<Provider>,
+    /// The index of the log in the block
+    index: u64,
+    log: PrimitiveLog,
+}
+
+#[Object(name = "Log")]
+impl<Provider> Log<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    /// The index of the log in the block.
+    async fn index(&self) -> Long {
+        Long(self.index)
+    }
+
+    /// The account that emitted the log, queried at the given block or the block of the log.
+    async fn account(&self, block: Option<Long>) -> Account<Provider> {
+        self.transaction.block.account(self.log.address, block)
+    }
+
+    /// The topics of the log.
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.log.topics().iter().copied().map(Bytes32).collect()
+    }
+
+    /// The data of the log.
+    async fn data(&self) -> Bytes {
+        Bytes(self.log.data.data.clone())
+    }
+
+    /// The transaction that emitted the log.
+    async fn transaction(&self) -> Transaction<Provider> {
+        Transaction { block: self.transaction.block.clone(), index: self.transaction.index }
+    }
+}
+
+/// An Ethereum account at a particular block.
+pub(crate) struct Account<Provider> {
+    provider: Provider,
+    address: PrimitiveAddress,
+    /// The block at which the state of the account is queried
+    block: BlockId,
+}
+
+impl<Provider> Account<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    fn state(&self) -> Result<StateProviderBox> {
+        Ok(self.provider.state_by_block_id(self.block)?)
+    }
+}
+
+#[Object(name = "Account")]
+impl<Provider> Account<Provider>
+where
+    Provider: GraphQlProvider,
+{
+    /// The address of the account.
+    async fn address(&self) -> Address {
+        Address(self.address)
+    }
+
+    /// The balance of the account in wei.
+    async fn balance(&self) -> Result<BigInt> {
+        Ok(BigInt(self.state()?.account_balance(self.address)?.unwrap_or_default()))
+    }
+
+    /// The number of transactions sent from the account.
+    async fn transaction_count(&self) -> Result<Long> {
+        Ok(Long(self.state()?.account_nonce(self.address)?.unwrap_or_default()))
+    }
+
+    /// The code of the contract, empty for externally owned accounts.
+    async fn code(&self) -> Result<Bytes> {
+        let code = self.state()?.account_code(self.address)?.unwrap_or_default();
+        Ok(Bytes(code.original_bytes()))
+    }
+
+    /// The value of the storage slot of the account.
+    async fn storage(&self, slot: Bytes32) -> Result<Bytes32> {
+        let value = self.state()?.storage(self.address, slot.0)?.unwrap_or_default();
+        Ok(Bytes32(B256::new(value.to_be_bytes())))
+    }
+}
+
+/// A validator withdrawal.
+#[derive(async_graphql::SimpleObject)]
+pub(crate) struct Withdrawal {
+    /// The index of the withdrawal.
+    index: Long,
+    /// The index of the validator that withdrew.
+    validator: Long,
+    /// The recipient of the withdrawn ether.
+    address: Address,
+    /// The amount of the withdrawal in gwei.
+    amount: Long,
+}
+
+/// A filter of the logs of a range of blocks.
+#[derive(InputObject)]
+pub(crate) struct FilterCriteria {
+    /// The first block to return logs from, defaults to the most recent block.
+    from_block: Option<Long>,
+    /// The last block to return logs from, defaults to the most recent block.
+    to_block: Option<Long>,
+    /// The addresses of the accounts that emitted the logs, empty matches all accounts.
+    addresses: Option<Vec<Address>>,
+    /// The topics of the logs, see [`LogFilter`].
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// A filter of the logs of a single block.
+#[derive(InputObject)]
+pub(crate) struct BlockFilterCriteria {
+    /// The addresses of the accounts that emitted the logs, empty matches all accounts.
+    addresses: Option<Vec<Address>>,
+    /// The topics of the logs, see [`LogFilter`].
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// Matches logs by address and topics.
+///
+/// A log matches if it was emitted by any of the addresses and if for every position, its topic at
+/// that position is any of the topics of the filter. An empty list matches everything.
+#[derive(Debug, Default)]
+struct LogFilter {
+    addresses: Vec<PrimitiveAddress>,
+    topics: Vec<Vec<B256>>,
+}
+
+impl LogFilter {
+    fn new(addresses: Option<Vec<Address>>, topics: Option<Vec<Vec<Bytes32>>>) -> Self {
+        Self {
+            addresses: addresses.unwrap_or_default().into_iter().map(|address| address.0).collect(),
+            topics: topics
+                .unwrap_or_default()
+                .into_iter()
+                .map(|topics| topics.into_iter().map(|topic| topic.0).collect())
+                .collect(),
+        }
+    }
+
+    /// Returns false if the block does not contain a matching log.
+    fn matches_bloom(&self, header: &SealedHeader) -> bool {
+        let bloom = &header.logs_bloom;
+        let addresses = self.addresses.is_empty() ||
+            self.addresses
+                .iter()
+                .any(|address| bloom.contains_input(BloomInput::Raw(address.as_slice())));
+        addresses &&
+            self.topics.iter().all(|topics| {
+                topics.is_empty() ||
+                    topics
+                        .iter()
+                        .any(|topic| bloom.contains_input(BloomInput::Raw(topic.as_slice())))
+            })
+    }
+
+    fn matches(&self, log: &PrimitiveLog) -> bool {
+        if !self.addresses.is_empty() && !self.addresses.contains(&log.address) {
+            return false
+        }
+        self.topics.iter().enumerate().all(|(position, topics)| {
+            topics.is_empty() || log.topics().get(position).map_or(false, |t| topics.contains(t))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{logs_bloom, Header, LogData};
+
+    #[test]
+    fn log_filter() {
+        let address = PrimitiveAddress::with_last_byte(1);
+        let (topic0, topic1) = (B256::with_last_byte(2), B256::with_last_byte(3));
+        let log = PrimitiveLog {
+            address,
+            data: LogData::new_unchecked(vec![topic0, topic1], Default::default()),
+        };
+        let header = Header { logs_bloom: logs_bloom([&log]), ..Default::default() }.seal_slow();
+
+        let filter = LogFilter::default();
+        assert!(filter.matches_bloom(&header) && filter.matches(&log));
+
+        let filter = LogFilter { addresses: vec![address], topics: vec![vec![], vec![topic1]] };
+        assert!(filter.matches_bloom(&header) && filter.matches(&log));
+
+        let filter = LogFilter { addresses: vec![], topics: vec![vec![topic1]] };
+        assert!(filter.matches_bloom(&header));
+        assert!(!filter.matches(&log));
+
+        let filter = LogFilter { addresses: vec![PrimitiveAddress::ZERO], topics: vec![] };
+        assert!(!filter.matches_bloom(&header) && !filter.matches(&log));
+    }
+}
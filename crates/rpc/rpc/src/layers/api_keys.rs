@@ -0,0 +1,391 @@
+use jsonrpsee::core::RpcResult;
+use parking_lot::{Mutex, RwLock};
+use reth_primitives::{hex, keccak256};
+use reth_rpc_api::ApiKeysApiServer;
+use reth_rpc_types::ApiKeyUsage;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The default compute units of a method without a configured weight.
+pub(crate) const DEFAULT_METHOD_WEIGHT: u64 = 1;
+
+/// The header that carries the API key of a request.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// The API keys accepted by the rpc server and their quotas, usually loaded from a JSON file:
+///
+/// ```json
+/// {
+///     "keys": {
+///         "alice": { "dailyLimit": 100000, "monthlyLimit": 2000000 },
+///         "bob": {}
+///     },
+///     "methodWeights": { "eth_call": 20, "debug_traceTransaction": 300 },
+///     "defaultWeight": 1
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeysConfig {
+    /// The accepted API keys and their quotas.
+    pub keys: HashMap<String, ApiKeyQuota>,
+    /// The compute units charged per call of a method.
+    #[serde(default)]
+    pub method_weights: HashMap<String, u64>,
+    /// The compute units charged per call of a method without a configured weight.
+    #[serde(default = "default_method_weight")]
+    pub default_weight: u64,
+    /// The file the config was loaded from, see [ApiKeys::reload].
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+impl ApiKeysConfig {
+    /// Loads the config from the JSON file at the given path.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ApiKeysConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| ApiKeysConfigError::Read(err, path.to_path_buf()))?;
+        let config: Self = serde_json::from_str(&contents)
+            .map_err(|err| ApiKeysConfigError::Parse(err, path.to_path_buf()))?;
+        Ok(Self { path: Some(path.to_path_buf()), ..config })
+    }
+
+    /// Returns the compute units charged per call of the method.
+    pub fn method_weight(&self, method: &str) -> u64 {
+        self.method_weights.get(method).copied().unwrap_or(self.default_weight)
+    }
+}
+
+const fn default_method_weight() -> u64 {
+    DEFAULT_METHOD_WEIGHT
+}
+
+/// The compute unit quotas of an API key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyQuota {
+    /// The compute units the key may use per UTC day, unlimited if `None`.
+    pub daily_limit: Option<u64>,
+    /// The compute units the key may use per UTC month, unlimited if `None`.
+    pub monthly_limit: Option<u64>,
+}
+
+/// Errors that can occur when loading an [ApiKeysConfig].
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeysConfigError {
+    /// The file could not be read.
+    #[error("failed to read API keys file {1:?}: {0}")]
+    Read(std::io::Error, PathBuf),
+    /// The file is not a valid config.
+    #[error("failed to parse API keys file {1:?}: {0}")]
+    Parse(serde_json::Error, PathBuf),
+}
+
+/// A request was rejected because it would exceed a quota of the API key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum QuotaExceeded {
+    /// The daily quota would be exceeded.
+    #[error("daily compute unit quota of {limit} exceeded")]
+    Daily {
+        /// The daily quota.
+        limit: u64,
+    },
+    /// The monthly quota would be exceeded.
+    #[error("monthly compute unit quota of {limit} exceeded")]
+    Monthly {
+        /// The monthly quota.
+        limit: u64,
+    },
+}
+
+/// The API keys of the rpc server and the compute units they used.
+///
+/// This is shared between the http and ws servers, which charge every call to the API key of its
+/// client, and the `admin_apiKeyUsage` endpoint. Usage is kept in memory and reset when the node
+/// restarts.
+#[derive(Debug, Clone)]
+pub struct ApiKeys {
+    inner: Arc<ApiKeysInner>,
+}
+
+#[derive(Debug)]
+struct ApiKeysInner {
+    config: RwLock<ApiKeysConfig>,
+    usage: Mutex<HashMap<String, KeyUsage>>,
+}
+
+impl ApiKeys {
+    /// Creates the API keys from the config.
+    pub fn new(config: ApiKeysConfig) -> Self {
+        Self {
+            inner: Arc::new(ApiKeysInner {
+                config: RwLock::new(config),
+                usage: Default::default(),
+            }),
+        }
+    }
+
+    /// Returns the config of the API keys.
+    pub fn config(&self) -> ApiKeysConfig {
+        self.inner.config.read().clone()
+    }
+
+    /// Replaces the config of the API keys.
+    ///
+    /// The usage of the keys is kept, keys that are no longer configured are rejected.
+    pub fn set_config(&self, config: ApiKeysConfig) {
+        *self.inner.config.write() = config;
+    }
+
+    /// Reloads the config from the file it was loaded from, if any.
+    ///
+    /// Returns `true` if the config changed.
+    pub fn reload(&self) -> Result<bool, ApiKeysConfigError> {
+        let current = self.config();
+        let Some(path) = &current.path else { return Ok(false) };
+        let config = ApiKeysConfig::from_file(path)?;
+        if config == current {
+            return Ok(false)
+        }
+        self.set_config(config);
+        Ok(true)
+    }
+
+    /// Returns true if the API key is accepted.
+    pub fn contains(&self, key: &str) -> bool {
+        self.inner.config.read().keys.contains_key(key)
+    }
+
+    /// Returns the compute units charged per call of the method.
+    pub fn method_cost(&self, method: &str) -> u64 {
+        self.inner.config.read().method_weight(method)
+    }
+
+    /// Charges the compute units to the API key, unless this would exceed one of its quotas.
+    pub fn charge(&self, key: &str, cost: u64) -> Result<(), QuotaExceeded> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.charge_at(key, cost, now)
+    }
+
+    fn charge_at(&self, key: &str, cost: u64, timestamp: u64) -> Result<(), QuotaExceeded> {
+        let quota = self.inner.config.read().keys.get(key).copied().unwrap_or_default();
+        let mut usage = self.inner.usage.lock();
+        let usage = usage.entry(key.to_string()).or_default();
+        usage.roll_over(timestamp);
+
+        if let Some(limit) = quota.daily_limit {
+            if usage.daily.saturating_add(cost) > limit {
+                usage.rejected_requests += 1;
+                return Err(QuotaExceeded::Daily { limit })
+            }
+        }
+        if let Some(limit) = quota.monthly_limit {
+            if usage.monthly.saturating_add(cost) > limit {
+                usage.rejected_requests += 1;
+                return Err(QuotaExceeded::Monthly { limit })
+            }
+        }
+
+        usage.daily = usage.daily.saturating_add(cost);
+        usage.monthly = usage.monthly.saturating_add(cost);
+        usage.total = usage.total.saturating_add(cost);
+        usage.requests += 1;
+        Ok(())
+    }
+
+    /// Returns the usage of the given API key, or of all API keys sorted by key if `None`.
+    pub fn usage(&self, key: Option<&str>) -> Vec<ApiKeyUsage> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        self.usage_at(key, now)
+    }
+
+    fn usage_at(&self, key: Option<&str>, timestamp: u64) -> Vec<ApiKeyUsage> {
+        let config = self.inner.config.read();
+        let mut keys = match key {
+            Some(key) => config.keys.get_key_value(key).into_iter().collect::<Vec<_>>(),
+            None => config.keys.iter().collect(),
+        };
+        keys.sort_unstable_by_key(|(key, _)| *key);
+
+        let mut usage = self.inner.usage.lock();
+        keys.into_iter()
+            .map(|(key, quota)| {
+                let usage = usage.entry(key.clone()).or_default();
+                usage.roll_over(timestamp);
+                ApiKeyUsage {
+                    key: key.clone(),
+                    daily_used: usage.daily,
+                    daily_limit: quota.daily_limit,
+                    monthly_used: usage.monthly,
+                    monthly_limit: quota.monthly_limit,
+                    total_used: usage.total,
+                    requests: usage.requests,
+                    rejected_requests: usage.rejected_requests,
+                }
+            })
+            .collect()
+    }
+}
+
+impl ApiKeysApiServer for ApiKeys {
+    /// Handler for `admin_apiKeyUsage`
+    fn api_key_usage(&self, key: Option<String>) -> RpcResult<Vec<ApiKeyUsage>> {
+        Ok(self.usage(key.as_deref()))
+    }
+}
+
+/// Returns an identifier of the API key that can be logged, the first bytes of its hash.
+pub fn api_key_id(key: &str) -> String {
+    hex::encode(&keccak256(key)[..4])
+}
+
+/// The compute units used by an API key.
+#[derive(Debug, Default)]
+struct KeyUsage {
+    /// The day since the unix epoch the daily usage belongs to.
+    day: u64,
+    daily: u64,
+    /// The month since the unix epoch the monthly usage belongs to.
+    month: u64,
+    monthly: u64,
+    total: u64,
+    requests: u64,
+    rejected_requests: u64,
+}
+
+impl KeyUsage {
+    /// Resets the daily and monthly usage if the timestamp is in a later period.
+    fn roll_over(&mut self, timestamp: u64) {
+        let day = timestamp / SECONDS_PER_DAY;
+        if day != self.day {
+            self.day = day;
+            self.daily = 0;
+        }
+        let month = month_of_day(day);
+        if month != self.month {
+            self.month = month;
+            self.monthly = 0;
+        }
+    }
+}
+
+/// Returns the number of months since the unix epoch of the given day since the unix epoch.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn month_of_day(day: u64) -> u64 {
+    let z = day + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let (year, month) =
+        if mp < 10 { (yoe + era * 400, mp + 3) } else { (yoe + era * 400 + 1, mp - 9) };
+    (year - 1970) * 12 + month - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_keys() -> ApiKeys {
+        ApiKeys::new(
+            serde_json::from_str(
+                r#"{
+                    "keys": {
+                        "alice": { "dailyLimit": 100, "monthlyLimit": 150 },
+                        "bob": {}
+                    },
+                    "methodWeights": { "eth_call": 20 }
+                }"#,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn months_since_epoch() {
+        assert_eq!(month_of_day(0), 0);
+        assert_eq!(month_of_day(30), 0);
+        assert_eq!(month_of_day(31), 1);
+        // 2024-02-29 and 2024-03-01
+        assert_eq!(month_of_day(19_782), 54 * 12 + 1);
+        assert_eq!(month_of_day(19_783), 54 * 12 + 2);
+    }
+
+    #[test]
+    fn method_cost() {
+        let keys = api_keys();
+        assert_eq!(keys.method_cost("eth_call"), 20);
+        assert_eq!(keys.method_cost("eth_blockNumber"), DEFAULT_METHOD_WEIGHT);
+    }
+
+    #[test]
+    fn key_id_hides_key() {
+        let id = api_key_id("alice");
+        assert_eq!(id.len(), 8);
+        assert!(!id.contains("alice"));
+        assert_eq!(id, api_key_id("alice"));
+        assert_ne!(id, api_key_id("bob"));
+    }
+
+    #[test]
+    fn enforces_quotas() {
+        let keys = api_keys();
+        let day = SECONDS_PER_DAY;
+
+        assert_eq!(keys.charge_at("alice", 60, 0), Ok(()));
+        assert_eq!(keys.charge_at("alice", 60, 0), Err(QuotaExceeded::Daily { limit: 100 }));
+        // the daily usage is reset on the next day, but the monthly usage is not
+        assert_eq!(keys.charge_at("alice", 60, day), Ok(()));
+        assert_eq!(
+            keys.charge_at("alice", 60, 2 * day),
+            Err(QuotaExceeded::Monthly { limit: 150 })
+        );
+        // the monthly usage is reset on the next month
+        assert_eq!(keys.charge_at("alice", 60, 31 * day), Ok(()));
+        assert_eq!(keys.charge_at("bob", u64::MAX, 0), Ok(()));
+
+        let usage = keys.usage_at(None, 31 * day);
+        assert_eq!(usage.len(), 2);
+        assert_eq!(
+            usage[0],
+            ApiKeyUsage {
+                key: "alice".to_string(),
+                daily_used: 60,
+                daily_limit: Some(100),
+                monthly_used: 60,
+                monthly_limit: Some(150),
+                total_used: 180,
+                requests: 3,
+                rejected_requests: 2,
+            }
+        );
+        assert_eq!(keys.usage_at(Some("bob"), 31 * day)[0].requests, 1);
+        assert!(keys.usage_at(Some("carol"), 31 * day).is_empty());
+    }
+
+    #[test]
+    fn reload_from_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), r#"{"keys":{"alice":{"dailyLimit":100}}}"#).unwrap();
+        let keys = ApiKeys::new(ApiKeysConfig::from_file(file.path()).unwrap());
+        assert_eq!(keys.charge_at("alice", 60, 0), Ok(()));
+        assert!(!keys.reload().unwrap());
+
+        std::fs::write(file.path(), r#"{"keys":{"alice":{"dailyLimit":200},"bob":{}}}"#).unwrap();
+        assert!(keys.reload().unwrap());
+        assert!(keys.contains("bob"));
+        // the usage is kept
+        assert_eq!(keys.charge_at("alice", 60, 0), Ok(()));
+        assert_eq!(keys.usage_at(Some("alice"), 0)[0].daily_used, 120);
+    }
+}
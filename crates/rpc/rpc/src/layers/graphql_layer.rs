@@ -0,0 +1,194 @@
+use crate::graphql::GraphQl;
+use async_graphql::BatchRequest;
+use http::{header, Method, Request, Response, StatusCode};
+use hyper::Body;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// The path the GraphQL API is served at.
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+/// This is an Http middleware layer that serves the GraphQL API.
+///
+/// `POST` requests to [GRAPHQL_PATH] are executed by the [GraphQl] API, all other requests are
+/// proxied to the inner service.
+#[derive(Debug, Clone)]
+pub struct GraphQlLayer {
+    graphql: GraphQl,
+}
+
+impl GraphQlLayer {
+    /// Creates an instance of [`GraphQlLayer`] that serves the given GraphQL API.
+    pub fn new(graphql: GraphQl) -> Self {
+        Self { graphql }
+    }
+}
+
+impl<S> Layer<S> for GraphQlLayer {
+    type Service = GraphQlService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GraphQlService { graphql: self.graphql.clone(), inner }
+    }
+}
+
+/// This type is the actual implementation of the middleware. It executes GraphQL requests and
+/// proxies all other Http requests to its inner service.
+#[derive(Debug, Clone)]
+pub struct GraphQlService<S> {
+    /// The API GraphQL requests are executed by
+    graphql: GraphQl,
+    /// Recipient of all other Http requests
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for GraphQlService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if req.uri().path() != GRAPHQL_PATH {
+            return Box::pin(self.inner.call(req))
+        }
+
+        let graphql = self.graphql.clone();
+        Box::pin(async move {
+            if req.method() != Method::POST {
+                return Ok(response(
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    "text/plain",
+                    "only POST is allowed",
+                ))
+            }
+
+            let limit = graphql.config().max_request_body_size as usize;
+            let body = match hyper::body::to_bytes(http_body::Limited::new(req.into_body(), limit))
+                .await
+            {
+                Ok(body) => body,
+                Err(_) => {
+                    return Ok(response(
+                        StatusCode::PAYLOAD_TOO_LARGE,
+                        "text/plain",
+                        "request body too large",
+                    ))
+                }
+            };
+            let request = match serde_json::from_slice::<BatchRequest>(&body) {
+                Ok(request) => request,
+                Err(err) => {
+                    return Ok(response(StatusCode::BAD_REQUEST, "text/plain", err.to_string()))
+                }
+            };
+
+            let response_body = serde_json::to_vec(&graphql.execute(request).await)
+                .expect("GraphQL responses are serializable");
+            Ok(response(StatusCode::OK, "application/json", response_body))
+        })
+    }
+}
+
+fn response(status: StatusCode, content_type: &str, body: impl Into<Body>) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, content_type)
+        .body(body.into())
+        .expect("This should never happen")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphql::GraphQlConfig;
+    use jsonrpsee::{
+        server::{ServerBuilder, ServerHandle},
+        RpcModule,
+    };
+    use reth_primitives::{Block, Header};
+    use reth_provider::test_utils::MockEthProvider;
+    use std::net::SocketAddr;
+
+    async fn spawn_server(graphql: GraphQl) -> (ServerHandle, SocketAddr) {
+        let middleware = tower::ServiceBuilder::default().layer(GraphQlLayer::new(graphql));
+        let server = ServerBuilder::default()
+            .set_http_middleware(middleware)
+            .build("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let mut module = RpcModule::new(());
+        module.register_method("greet_hello", |_, _| "hello").unwrap();
+        (server.start(module), addr)
+    }
+
+    async fn send_request(addr: SocketAddr, path: &str, body: &str) -> (StatusCode, String) {
+        let req = Request::builder()
+            .method(Method::POST)
+            .uri(format!("http://{addr}{path}"))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let res = hyper::Client::new().request(req).await.unwrap();
+        let status = res.status();
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_graphql_layer() {
+        let provider = MockEthProvider::default();
+        let header = Header { number: 1, gas_limit: 30_000_000, ..Default::default() };
+        let hash = header.hash_slow();
+        provider.add_block(hash, Block { header, ..Default::default() });
+
+        let graphql = GraphQl::new(provider, GraphQlConfig::default());
+        let (handle, addr) = spawn_server(graphql).await;
+
+        let (status, body) = send_request(
+            addr,
+            GRAPHQL_PATH,
+            r#"{"query":"{ block(number: 1) { number gasLimit } }"}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, r#"{"data":{"block":{"number":1,"gasLimit":30000000}}}"#);
+
+        let (status, body) = send_request(
+            addr,
+            GRAPHQL_PATH,
+            r#"[{"query":"{ block(number: 2) { number } }"},{"query":"{ chainID }"}]"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, r#"[{"data":{"block":null}},{"data":{"chainID":"0x1"}}]"#);
+
+        let (status, _) = send_request(addr, GRAPHQL_PATH, "not json").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // other requests are proxied
+        let (status, body) = send_request(
+            addr,
+            "/",
+            r#"{"jsonrpc":"2.0","id":1,"method":"greet_hello","params":[]}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("hello"));
+
+        handle.stop().unwrap();
+    }
+}
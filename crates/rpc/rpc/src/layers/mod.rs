@@ -1,11 +1,27 @@
 use http::{HeaderMap, Response};
 
+mod api_keys;
 mod auth_layer;
+mod graphql_layer;
 mod jwt_secret;
 mod jwt_validator;
+mod rate_limit;
+#[cfg(feature = "rest")]
+mod rest_layer;
+pub use api_keys::{
+    api_key_id, ApiKeyQuota, ApiKeys, ApiKeysConfig, ApiKeysConfigError, QuotaExceeded,
+    API_KEY_HEADER,
+};
 pub use auth_layer::AuthLayer;
+pub use graphql_layer::{GraphQlLayer, GRAPHQL_PATH};
 pub use jwt_secret::{Claims, JwtError, JwtSecret};
 pub use jwt_validator::JwtAuthValidator;
+pub use rate_limit::{
+    RateLimit, RateLimitConfig, RateLimitConfigError, RateLimitExceeded, RateLimitedRequest,
+    RateLimiter, RATE_LIMIT_EXCEEDED_CODE,
+};
+#[cfg(feature = "rest")]
+pub use rest_layer::RestLayer;
 
 /// General purpose trait to validate Http Authorization headers. It's supposed to be integrated as
 /// a validator trait into an [`AuthLayer`].
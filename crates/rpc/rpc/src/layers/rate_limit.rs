@@ -0,0 +1,554 @@
+use super::api_keys::api_key_id;
+use metrics::Counter;
+use parking_lot::{Mutex, RwLock};
+use reth_metrics::Metrics;
+use schnellru::{ByLength, LruMap};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The error code of requests rejected by a rate limit, the `limit exceeded` code of EIP-1474.
+pub const RATE_LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The maximum number of client buckets, the least recently used bucket is evicted when a new
+/// client exceeds it.
+const MAX_CLIENT_BUCKETS: u32 = 10_000;
+
+/// The rate limits of the rpc server, usually loaded from a TOML file:
+///
+/// ```toml
+/// # the reverse proxies whose `X-Forwarded-For` and `X-Real-IP` headers are trusted
+/// trusted_proxies = ["127.0.0.1"]
+///
+/// # all requests of a client IP
+/// [per_ip]
+/// requests_per_second = 50
+/// burst = 100
+///
+/// # all requests of an API key, unless the key has its own limit
+/// [per_api_key]
+/// requests_per_second = 100
+///
+/// [api_keys.alice]
+/// requests_per_second = 1000
+///
+/// # the calls of a method, shared by all clients
+/// [methods.debug_traceTransaction]
+/// requests_per_second = 5
+/// burst = 10
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitConfig {
+    /// The reverse proxies that set the client IP of the requests they forward.
+    ///
+    /// The IP of a client is the peer address of its connection, unless the peer is one of these
+    /// proxies.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
+    /// The limit of the requests of a client IP.
+    pub per_ip: Option<RateLimit>,
+    /// The limit of the requests of an API key without its own limit.
+    pub per_api_key: Option<RateLimit>,
+    /// The limits of the requests of specific API keys.
+    #[serde(default)]
+    pub api_keys: HashMap<String, RateLimit>,
+    /// The limits of the calls of methods, shared by all clients.
+    #[serde(default)]
+    pub methods: HashMap<String, RateLimit>,
+    /// The file the config was loaded from, see [RateLimiter::reload].
+    #[serde(skip)]
+    pub path: Option<PathBuf>,
+}
+
+impl RateLimitConfig {
+    /// Loads the config from the TOML file at the given path.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, RateLimitConfigError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|err| RateLimitConfigError::Read(err, path.to_path_buf()))?;
+        let config: Self = toml::from_str(&contents)
+            .map_err(|err| RateLimitConfigError::Parse(err, path.to_path_buf()))?;
+        Ok(Self { path: Some(path.to_path_buf()), ..config })
+    }
+
+    /// Returns the limit of the requests of the API key.
+    fn api_key_limit(&self, key: &str) -> Option<&RateLimit> {
+        self.api_keys.get(key).or(self.per_api_key.as_ref())
+    }
+
+    /// Returns the limit of the bucket.
+    fn limit(&self, bucket: &Bucket) -> Option<&RateLimit> {
+        match bucket {
+            Bucket::Ip(_) => self.per_ip.as_ref(),
+            Bucket::ApiKey(key) => self.api_key_limit(key),
+            Bucket::Method(method) => self.methods.get(method),
+        }
+    }
+}
+
+/// A token bucket rate limit.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimit {
+    /// The sustained number of requests per second.
+    pub requests_per_second: f64,
+    /// The number of requests that can be made at once after being idle, defaults to the
+    /// requests per second.
+    pub burst: Option<u32>,
+}
+
+impl RateLimit {
+    /// Returns the capacity of the bucket, at least one request.
+    fn capacity(&self) -> f64 {
+        self.burst.map(f64::from).unwrap_or(self.requests_per_second).max(1.)
+    }
+}
+
+/// Errors that can occur when loading a [RateLimitConfig].
+#[derive(Debug, thiserror::Error)]
+pub enum RateLimitConfigError {
+    /// The file could not be read.
+    #[error("failed to read rate limit file {1:?}: {0}")]
+    Read(std::io::Error, PathBuf),
+    /// The file is not a valid config.
+    #[error("failed to parse rate limit file {1:?}: {0}")]
+    Parse(toml::de::Error, PathBuf),
+}
+
+/// A request was rejected because a rate limit has no tokens left.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("rate limit of {limiter} exceeded, retry in {}ms", retry_after.as_millis())]
+pub struct RateLimitExceeded {
+    /// The limit that was exceeded.
+    pub limiter: String,
+    /// The time until the request would be allowed.
+    pub retry_after: Duration,
+}
+
+/// The bucket of a rate limit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Bucket {
+    Ip(IpAddr),
+    ApiKey(String),
+    Method(String),
+}
+
+impl Bucket {
+    fn limiter(&self) -> String {
+        match self {
+            Self::Ip(ip) => format!("client {ip}"),
+            Self::ApiKey(key) => format!("API key {}", api_key_id(key)),
+            Self::Method(method) => format!("method {method}"),
+        }
+    }
+}
+
+/// The tokens of a bucket, refilled at the rate of its limit.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn full(limit: &RateLimit, now: Instant) -> Self {
+        Self { tokens: limit.capacity(), updated: now }
+    }
+
+    /// Refills the bucket for the time since it was last updated.
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * limit.requests_per_second).min(limit.capacity());
+        self.updated = now;
+    }
+
+    /// Returns the time until the bucket has the tokens, zero if it has them.
+    fn wait_time(&self, limit: &RateLimit, tokens: f64) -> Duration {
+        if self.tokens >= tokens {
+            return Duration::ZERO
+        }
+        if tokens > limit.capacity() || limit.requests_per_second <= 0. {
+            return Duration::MAX
+        }
+        Duration::from_secs_f64((tokens - self.tokens) / limit.requests_per_second)
+    }
+}
+
+/// The buckets of the rate limits.
+struct Buckets {
+    /// The buckets of client IPs and API keys, capped at [MAX_CLIENT_BUCKETS].
+    ///
+    /// An evicted bucket is recreated as a full bucket when the client returns.
+    clients: LruMap<Bucket, TokenBucket, ByLength>,
+    /// The buckets of the methods, one per limited method.
+    methods: HashMap<String, TokenBucket>,
+}
+
+impl Buckets {
+    fn new() -> Self {
+        Self { clients: LruMap::new(ByLength::new(MAX_CLIENT_BUCKETS)), methods: HashMap::new() }
+    }
+
+    /// Returns the tokens of the bucket, creating a full bucket if it's not tracked.
+    fn get_or_insert(
+        &mut self,
+        bucket: &Bucket,
+        limit: &RateLimit,
+        now: Instant,
+    ) -> &mut TokenBucket {
+        match bucket {
+            Bucket::Method(method) => {
+                self.methods.entry(method.clone()).or_insert_with(|| TokenBucket::full(limit, now))
+            }
+            _ => self
+                .clients
+                .get_or_insert(bucket.clone(), || TokenBucket::full(limit, now))
+                .expect("client buckets are capped by length"),
+        }
+    }
+
+    /// Stops tracking the buckets of the methods that are no longer limited.
+    fn retain_methods(&mut self, config: &RateLimitConfig) {
+        self.methods.retain(|method, _| config.methods.contains_key(method));
+    }
+
+    /// Returns the tokens of the bucket, if it's tracked.
+    fn get_mut(&mut self, bucket: &Bucket) -> Option<&mut TokenBucket> {
+        match bucket {
+            Bucket::Method(method) => self.methods.get_mut(method),
+            _ => self.clients.get(bucket),
+        }
+    }
+}
+
+impl std::fmt::Debug for Buckets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Buckets")
+            .field("clients", &self.clients.len())
+            .field("methods", &self.methods)
+            .finish()
+    }
+}
+
+/// A request of a client that is subject to the rate limits.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RateLimitedRequest<'a> {
+    /// The IP of the client, if known.
+    pub ip: Option<IpAddr>,
+    /// The API key of the request, if any.
+    pub api_key: Option<&'a str>,
+    /// The methods of the calls of the request.
+    pub methods: &'a [String],
+}
+
+/// The rate limits of the rpc server and the tokens left in their buckets.
+///
+/// This is shared by all connections of the http and ws servers, which check every call of a
+/// client against the limits.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    inner: Arc<RateLimiterInner>,
+}
+
+#[derive(Debug)]
+struct RateLimiterInner {
+    limits: RwLock<Limits>,
+    buckets: Mutex<Buckets>,
+    ip_metrics: RateLimiterMetrics,
+    api_key_metrics: RateLimiterMetrics,
+}
+
+/// The config of the rate limits and the metrics of the limited methods.
+#[derive(Debug)]
+struct Limits {
+    config: RateLimitConfig,
+    method_metrics: HashMap<String, RateLimiterMetrics>,
+}
+
+impl Limits {
+    fn new(config: RateLimitConfig) -> Self {
+        let method_metrics = config
+            .methods
+            .keys()
+            .map(|method| {
+                let labels = [("limiter", "method".to_string()), ("method", method.clone())];
+                (method.clone(), RateLimiterMetrics::new_with_labels(&labels))
+            })
+            .collect();
+        Self { config, method_metrics }
+    }
+}
+
+impl RateLimiter {
+    /// Creates the rate limiter from the config.
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            inner: Arc::new(RateLimiterInner {
+                limits: RwLock::new(Limits::new(config)),
+                buckets: Mutex::new(Buckets::new()),
+                ip_metrics: RateLimiterMetrics::new_with_labels(&[("limiter", "ip")]),
+                api_key_metrics: RateLimiterMetrics::new_with_labels(&[("limiter", "api_key")]),
+            }),
+        }
+    }
+
+    /// Returns the config of the rate limits.
+    pub fn config(&self) -> RateLimitConfig {
+        self.inner.limits.read().config.clone()
+    }
+
+    /// Returns the reverse proxies whose forwarded client IPs are trusted.
+    pub fn trusted_proxies(&self) -> Vec<IpAddr> {
+        self.inner.limits.read().config.trusted_proxies.clone()
+    }
+
+    /// Replaces the config of the rate limits.
+    ///
+    /// The tokens left in the buckets of the clients and of the methods that are still limited
+    /// are kept, and capped at the new limits.
+    pub fn set_config(&self, config: RateLimitConfig) {
+        let mut limits = self.inner.limits.write();
+        self.inner.buckets.lock().retain_methods(&config);
+        *limits = Limits::new(config);
+    }
+
+    /// Reloads the config from the file it was loaded from, if any.
+    ///
+    /// Returns `true` if the config changed.
+    pub fn reload(&self) -> Result<bool, RateLimitConfigError> {
+        let current = self.config();
+        let Some(path) = &current.path else { return Ok(false) };
+        let config = RateLimitConfig::from_file(path)?;
+        if config == current {
+            return Ok(false)
+        }
+        self.set_config(config);
+        Ok(true)
+    }
+
+    /// Takes the tokens of the request from every bucket that applies to it, unless one of them
+    /// doesn't have enough tokens left.
+    ///
+    /// A request takes one token per client bucket and one token per call of a limited method.
+    pub fn check(&self, request: RateLimitedRequest<'_>) -> Result<(), RateLimitExceeded> {
+        self.check_at(request, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        request: RateLimitedRequest<'_>,
+        now: Instant,
+    ) -> Result<(), RateLimitExceeded> {
+        let limits = self.inner.limits.read();
+        let config = &limits.config;
+        let mut costs = Vec::new();
+        if let Some(ip) = request.ip.filter(|_| config.per_ip.is_some()) {
+            costs.push((Bucket::Ip(ip), 1.));
+        }
+        if let Some(key) = request.api_key.filter(|key| config.api_key_limit(key).is_some()) {
+            costs.push((Bucket::ApiKey(key.to_string()), 1.));
+        }
+        for method in request.methods.iter().filter(|method| config.methods.contains_key(*method)) {
+            match costs.iter_mut().find(|(bucket, _)| *bucket == Bucket::Method(method.clone())) {
+                Some((_, cost)) => *cost += 1.,
+                None => costs.push((Bucket::Method(method.clone()), 1.)),
+            }
+        }
+        if costs.is_empty() {
+            return Ok(())
+        }
+
+        let mut buckets = self.inner.buckets.lock();
+
+        // check all buckets first, so a rejected request doesn't take any tokens
+        for (bucket, cost) in &costs {
+            let limit = config.limit(bucket).expect("bucket has a limit");
+            let tokens = buckets.get_or_insert(bucket, limit, now);
+            tokens.refill(limit, now);
+            let retry_after = tokens.wait_time(limit, *cost);
+            if !retry_after.is_zero() {
+                if let Some(metrics) = self.metrics(&limits, bucket) {
+                    metrics.rejected_requests.increment(1);
+                }
+                return Err(RateLimitExceeded { limiter: bucket.limiter(), retry_after })
+            }
+        }
+        for (bucket, cost) in &costs {
+            if let Some(tokens) = buckets.get_mut(bucket) {
+                tokens.tokens -= cost;
+            }
+            if let Some(metrics) = self.metrics(&limits, bucket) {
+                metrics.allowed_requests.increment(1);
+            }
+        }
+        Ok(())
+    }
+
+    fn metrics<'a>(
+        &'a self,
+        limits: &'a Limits,
+        bucket: &Bucket,
+    ) -> Option<&'a RateLimiterMetrics> {
+        match bucket {
+            Bucket::Ip(_) => Some(&self.inner.ip_metrics),
+            Bucket::ApiKey(_) => Some(&self.inner.api_key_metrics),
+            Bucket::Method(method) => limits.method_metrics.get(method),
+        }
+    }
+}
+
+/// Metrics of a rate limiter.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.rate_limit")]
+struct RateLimiterMetrics {
+    /// The number of requests that were allowed by the limiter.
+    allowed_requests: Counter,
+    /// The number of requests that were rejected by the limiter.
+    rejected_requests: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limiter() -> RateLimiter {
+        RateLimiter::new(
+            toml::from_str(
+                r#"
+                [per_ip]
+                requests_per_second = 1
+                burst = 2
+
+                [per_api_key]
+                requests_per_second = 10
+
+                [api_keys.alice]
+                requests_per_second = 1
+
+                [methods.eth_call]
+                requests_per_second = 2
+                "#,
+            )
+            .unwrap(),
+        )
+    }
+
+    #[test]
+    fn enforces_client_limits() {
+        let limiter = rate_limiter();
+        let now = Instant::now();
+        let ip = Some(IpAddr::from([127, 0, 0, 1]));
+        let request = RateLimitedRequest { ip, ..Default::default() };
+
+        assert_eq!(limiter.check_at(request, now), Ok(()));
+        assert_eq!(limiter.check_at(request, now), Ok(()));
+        let err = limiter.check_at(request, now).unwrap_err();
+        assert_eq!(err.limiter, "client 127.0.0.1");
+        assert_eq!(err.retry_after, Duration::from_secs(1));
+        // the bucket is refilled over time
+        assert_eq!(limiter.check_at(request, now + Duration::from_secs(1)), Ok(()));
+        // other clients have their own bucket
+        let other = RateLimitedRequest { ip: Some(IpAddr::from([127, 0, 0, 2])), ..request };
+        assert_eq!(limiter.check_at(other, now), Ok(()));
+
+        // an API key can have its own limit
+        let alice = RateLimitedRequest { api_key: Some("alice"), ..Default::default() };
+        assert_eq!(limiter.check_at(alice, now), Ok(()));
+        assert!(limiter.check_at(alice, now).is_err());
+        let bob = RateLimitedRequest { api_key: Some("bob"), ..Default::default() };
+        for _ in 0..10 {
+            assert_eq!(limiter.check_at(bob, now), Ok(()));
+        }
+        assert!(limiter.check_at(bob, now).is_err());
+    }
+
+    #[test]
+    fn enforces_method_limits() {
+        let limiter = rate_limiter();
+        let now = Instant::now();
+        let calls = ["eth_call".to_string(), "eth_call".to_string(), "eth_chainId".to_string()];
+        let batch = RateLimitedRequest { methods: &calls, ..Default::default() };
+
+        // every call of a limited method takes a token
+        assert_eq!(limiter.check_at(batch, now), Ok(()));
+        let err = limiter.check_at(batch, now).unwrap_err();
+        assert_eq!(err.limiter, "method eth_call");
+
+        // unlimited methods are always allowed
+        let unlimited = ["eth_chainId".to_string()];
+        let request = RateLimitedRequest { methods: &unlimited, ..Default::default() };
+        assert_eq!(limiter.check_at(request, now), Ok(()));
+    }
+
+    #[test]
+    fn rejected_requests_take_no_tokens() {
+        let limiter = rate_limiter();
+        let now = Instant::now();
+        let ip = Some(IpAddr::from([127, 0, 0, 1]));
+        let calls = ["eth_call".to_string(); 3];
+        let request = RateLimitedRequest { ip, methods: &calls, ..Default::default() };
+
+        // the batch exceeds the method limit, so the client bucket is untouched
+        assert!(limiter.check_at(request, now).is_err());
+        let request = RateLimitedRequest { ip, ..Default::default() };
+        assert_eq!(limiter.check_at(request, now), Ok(()));
+        assert_eq!(limiter.check_at(request, now), Ok(()));
+    }
+
+    #[test]
+    fn caps_client_buckets() {
+        let limiter = rate_limiter();
+        let now = Instant::now();
+        let calls = ["eth_call".to_string()];
+        let first = RateLimitedRequest {
+            ip: Some(IpAddr::from([10, 0, 0, 0])),
+            methods: &calls,
+            ..Default::default()
+        };
+        assert_eq!(limiter.check_at(first, now), Ok(()));
+        assert_eq!(limiter.check_at(first, now), Ok(()));
+
+        for i in 1..=MAX_CLIENT_BUCKETS {
+            let ip = IpAddr::from((0x0a00_0000 + i).to_be_bytes());
+            let request = RateLimitedRequest { ip: Some(ip), ..Default::default() };
+            assert_eq!(limiter.check_at(request, now), Ok(()));
+        }
+        let buckets = limiter.inner.buckets.lock();
+        assert_eq!(buckets.clients.len(), MAX_CLIENT_BUCKETS as usize);
+        // the least recently used client was evicted, but the method buckets are kept
+        assert!(buckets.clients.peek(&Bucket::Ip(IpAddr::from([10, 0, 0, 0]))).is_none());
+        assert_eq!(buckets.methods["eth_call"].tokens, 0.);
+    }
+
+    #[test]
+    fn reload_from_file() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), "[methods.eth_call]\nrequests_per_second = 1\n").unwrap();
+        let limiter = RateLimiter::new(RateLimitConfig::from_file(file.path()).unwrap());
+        let now = Instant::now();
+        let calls = ["eth_call".to_string()];
+        let request = RateLimitedRequest { methods: &calls, ..Default::default() };
+        assert_eq!(limiter.check_at(request, now), Ok(()));
+        assert!(limiter.check_at(request, now).is_err());
+        assert!(!limiter.reload().unwrap());
+
+        std::fs::write(file.path(), "[methods.eth_call]\nrequests_per_second = 1\nburst = 3\n")
+            .unwrap();
+        assert!(limiter.reload().unwrap());
+        assert_eq!(limiter.config().methods["eth_call"].burst, Some(3));
+        // the tokens left are kept and refilled up to the new burst
+        assert!(limiter.check_at(request, now).is_err());
+        assert_eq!(limiter.check_at(request, now + Duration::from_secs(1)), Ok(()));
+
+        std::fs::write(file.path(), "").unwrap();
+        assert!(limiter.reload().unwrap());
+        assert_eq!(limiter.check_at(request, now), Ok(()));
+        assert!(limiter.inner.buckets.lock().methods.is_empty());
+    }
+}
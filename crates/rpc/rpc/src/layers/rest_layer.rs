@@ -0,0 +1,167 @@
+use crate::rest::{RestApi, RestRoute};
+use http::{header, Method, Request, Response, StatusCode};
+use hyper::Body;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// This is an Http middleware layer that serves the REST API.
+///
+/// `GET` requests to a [RestRoute] are handled by the [RestApi], all other requests are proxied
+/// to the inner service.
+#[derive(Debug, Clone)]
+pub struct RestLayer {
+    api: RestApi,
+}
+
+impl RestLayer {
+    /// Creates an instance of [`RestLayer`] that serves the given REST API.
+    pub fn new(api: RestApi) -> Self {
+        Self { api }
+    }
+}
+
+impl<S> Layer<S> for RestLayer {
+    type Service = RestService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RestService { api: self.api.clone(), inner }
+    }
+}
+
+/// This type is the actual implementation of the middleware. It handles REST requests and proxies
+/// all other Http requests to its inner service.
+#[derive(Debug, Clone)]
+pub struct RestService<S> {
+    /// The API REST requests are handled by
+    api: RestApi,
+    /// Recipient of all other Http requests
+    inner: S,
+}
+
+impl<S> Service<Request<Body>> for RestService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let Some(route) = RestRoute::parse(req.uri().path(), req.uri().query()) else {
+            return Box::pin(self.inner.call(req))
+        };
+
+        let api = self.api.clone();
+        let method = req.method().clone();
+        Box::pin(async move {
+            if method != Method::GET {
+                return Ok(response(StatusCode::METHOD_NOT_ALLOWED, "only GET is allowed"))
+            }
+            match route {
+                Ok(route) => Ok(api.handle(route).await),
+                Err(err) => Ok(response(StatusCode::BAD_REQUEST, err.to_string())),
+            }
+        })
+    }
+}
+
+fn response(status: StatusCode, body: impl Into<Body>) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "text/plain")
+        .body(body.into())
+        .expect("This should never happen")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rest::RestConfig;
+    use jsonrpsee::{
+        server::{ServerBuilder, ServerHandle},
+        RpcModule,
+    };
+    use reth_primitives::{Block, Header};
+    use reth_provider::test_utils::MockEthProvider;
+    use std::net::SocketAddr;
+
+    async fn spawn_server(api: RestApi) -> (ServerHandle, SocketAddr) {
+        let middleware = tower::ServiceBuilder::default().layer(RestLayer::new(api));
+        let server = ServerBuilder::default()
+            .set_http_middleware(middleware)
+            .build("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let mut module = RpcModule::new(());
+        module.register_method("greet_hello", |_, _| "hello").unwrap();
+        (server.start(module), addr)
+    }
+
+    async fn send_request(
+        addr: SocketAddr,
+        method: Method,
+        path: &str,
+        body: &str,
+    ) -> (StatusCode, String) {
+        let req = Request::builder()
+            .method(method)
+            .uri(format!("http://{addr}{path}"))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let res = hyper::Client::new().request(req).await.unwrap();
+        let status = res.status();
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rest_layer() {
+        let provider = MockEthProvider::default();
+        let header = Header { number: 1, ..Default::default() };
+        let hash = header.hash_slow();
+        provider.add_block(hash, Block { header, ..Default::default() });
+
+        let (handle, addr) = spawn_server(RestApi::new(provider, RestConfig::default())).await;
+
+        let (status, body) = send_request(addr, Method::GET, "/blocks/1", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let block: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(block["hash"], serde_json::json!(hash));
+
+        let (status, _) = send_request(addr, Method::GET, "/blocks/head", "").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, _) = send_request(addr, Method::POST, "/blocks/1", "").await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+
+        let (status, body) = send_request(addr, Method::GET, "/openapi.json", "").await;
+        assert_eq!(status, StatusCode::OK);
+        let schema: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert!(schema["paths"]["/txs/{hash}"].is_object());
+
+        // other requests are proxied
+        let (status, body) = send_request(
+            addr,
+            Method::POST,
+            "/",
+            r#"{"jsonrpc":"2.0","id":1,"method":"greet_hello","params":[]}"#,
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.contains("hello"));
+
+        handle.stop().unwrap();
+    }
+}
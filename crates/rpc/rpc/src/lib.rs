@@ -16,6 +16,11 @@
 //! `blocking` attribute and async handlers are mutually exclusive. However, as mentioned above, a
 //! lot of handlers make use of async functions, caching for example, but are also using blocking
 //! disk-io, hence these calls are spawned as futures to a blocking task manually.
+//!
+//! ## Feature Flags
+//!
+//! - `js-tracer` (default): Supports geth-compatible JavaScript tracers in the `debug` trace
+//!   endpoints, executed by an embedded JavaScript engine.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -29,24 +34,40 @@ mod admin;
 mod debug;
 mod engine;
 pub mod eth;
+pub mod graphql;
 mod layers;
 mod net;
 mod otterscan;
+mod personal;
+#[cfg(feature = "rest")]
+pub mod rest;
 mod reth;
 mod rpc;
 mod trace;
+mod trace_cache;
 mod txpool;
 mod web3;
 pub use admin::AdminApi;
-pub use debug::DebugApi;
+pub use debug::{DebugApi, DebugRewindApi, DEFAULT_TRACE_CHAIN_CONCURRENCY};
 pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
-pub use layers::{AuthLayer, AuthValidator, Claims, JwtAuthValidator, JwtError, JwtSecret};
+#[cfg(feature = "rest")]
+pub use layers::RestLayer;
+pub use layers::{
+    api_key_id, ApiKeyQuota, ApiKeys, ApiKeysConfig, ApiKeysConfigError, AuthLayer, AuthValidator,
+    Claims, GraphQlLayer, JwtAuthValidator, JwtError, JwtSecret, QuotaExceeded, RateLimit,
+    RateLimitConfig, RateLimitConfigError, RateLimitExceeded, RateLimitedRequest, RateLimiter,
+    API_KEY_HEADER, GRAPHQL_PATH, RATE_LIMIT_EXCEEDED_CODE,
+};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
+pub use personal::{PersonalApi, PersonalPolicy, UnknownPersonalMethod, PERSONAL_METHODS};
 pub use reth::RethApi;
 pub use rpc::RPCApi;
-pub use trace::TraceApi;
+pub use trace::{
+    TraceApi, TraceFilterConfig, DEFAULT_TRACE_FILTER_MAX_BLOCKS, DEFAULT_TRACE_FILTER_MAX_TRACES,
+};
+pub use trace_cache::{ParityTraceCache, ParityTraceKind, DEFAULT_TRACE_CACHE_SIZE_MB};
 pub use txpool::TxPoolApi;
 pub use web3::Web3Api;
 pub mod result;
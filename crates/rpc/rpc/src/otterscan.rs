@@ -4,37 +4,461 @@ use jsonrpsee::core::RpcResult;
 use revm::inspectors::NoOpInspector;
 use revm_inspectors::transfer::{TransferInspector, TransferKind};
 use revm_primitives::ExecutionResult;
+use std::ops::Range;
 
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_consensus_common::calc::{base_block_reward, block_reward, ommer_reward};
+use reth_primitives::{
+    Address, BlockId, BlockNumber, BlockNumberOrTag, TxHash, TxNumber, B256, U256,
+};
+use reth_provider::{
+    AddressTransactionsReader, BlockReader, BlockReaderIdExt, ChainSpecProvider, SenderNoncesReader,
+};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_types::{
     trace::otterscan::{
-        BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
-        OtsReceipt, OtsTransactionReceipt, TraceEntry, TransactionsWithReceipts,
+        BlockDetails, ContractCreator, InternalIssuance, InternalOperation, OperationType,
+        OtsBlockTransactions, OtsReceipt, OtsTransactionReceipt, TraceEntry,
+        TransactionsWithReceipts,
     },
-    BlockTransactions, Transaction,
+    AnyTransactionReceipt, BlockTransactions, Transaction,
 };
 
-use crate::{eth::EthTransactions, result::internal_rpc_err};
+use crate::{
+    eth::EthTransactions,
+    result::{internal_rpc_err, invalid_params_rpc_err, ToRpcResult},
+};
 
 const API_LEVEL: u64 = 8;
 
+/// The maximum number of transactions of a page of `ots_searchTransactionsBefore` and
+/// `ots_searchTransactionsAfter`, excluding the transactions that complete its last block.
+const MAX_SEARCH_PAGE_SIZE: usize = 100;
+
+/// A page of the transactions of an address from the address transactions index.
+#[derive(Debug, PartialEq, Eq)]
+struct TransactionsPage {
+    /// The numbers of the transactions, the most recent first.
+    tx_numbers: Vec<TxNumber>,
+    /// Whether the page contains the most recent transaction of the address.
+    first_page: bool,
+    /// Whether the page contains the oldest transaction of the address.
+    last_page: bool,
+}
+
 /// Otterscan API.
+///
+/// `ots_searchTransactionsBefore` and `ots_searchTransactionsAfter` require the optional address
+/// transactions index, which only contains the transactions sent from or to an address and the
+/// transactions that created it, not the internal calls of transactions. Their page size is capped
+/// at [MAX_SEARCH_PAGE_SIZE].
 #[derive(Debug)]
-pub struct OtterscanApi<Eth> {
+pub struct OtterscanApi<Provider, Eth> {
+    /// The provider used to look up transactions in the optional sender nonces and address
+    /// transactions indices.
+    provider: Provider,
     eth: Eth,
 }
 
-impl<Eth> OtterscanApi<Eth> {
+impl<Provider, Eth> OtterscanApi<Provider, Eth> {
     /// Creates a new instance of `Otterscan`.
-    pub fn new(eth: Eth) -> Self {
-        Self { eth }
+    pub fn new(provider: Provider, eth: Eth) -> Self {
+        Self { provider, eth }
     }
 }
 
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: AddressTransactionsReader + BlockReader,
+{
+    /// Returns the numbers of the transactions of the address in the range from the address
+    /// transactions index.
+    fn indexed_address_transactions(
+        &self,
+        address: Address,
+        range: Range<TxNumber>,
+        reverse: bool,
+        limit: usize,
+    ) -> RpcResult<Vec<TxNumber>> {
+        self.provider
+            .address_transactions_by_range(address, range, reverse, limit)
+            .to_rpc_result()?
+            .ok_or_else(|| internal_rpc_err("the address transactions index is not enabled"))
+    }
+
+    /// Returns the range of the numbers of the transactions of the block.
+    fn block_tx_range(&self, number: BlockNumber) -> RpcResult<Range<TxNumber>> {
+        let indices = self
+            .provider
+            .block_body_indices(number)
+            .to_rpc_result()?
+            .ok_or_else(|| internal_rpc_err("block not found"))?;
+        Ok(indices.tx_num_range())
+    }
+
+    /// Returns the number of the block of the transaction and the range of the numbers of the
+    /// transactions of the block.
+    fn transaction_block(&self, tx_number: TxNumber) -> RpcResult<(BlockNumber, Range<TxNumber>)> {
+        let number = self
+            .provider
+            .transaction_block(tx_number)
+            .to_rpc_result()?
+            .ok_or_else(|| internal_rpc_err("transaction not found"))?;
+        Ok((number, self.block_tx_range(number)?))
+    }
+
+    /// Returns the page of the transactions of the address in the blocks before the block, or
+    /// before the tip of the chain if `None`.
+    fn transactions_before(
+        &self,
+        address: Address,
+        block_number: Option<BlockNumber>,
+        page_size: usize,
+    ) -> RpcResult<TransactionsPage> {
+        if page_size == 0 {
+            return Err(invalid_params_rpc_err("page size must be greater than zero"))
+        }
+        let page_size = page_size.min(MAX_SEARCH_PAGE_SIZE);
+        let end = match block_number {
+            Some(number) => self.block_tx_range(number)?.start,
+            None => u64::MAX,
+        };
+
+        let mut tx_numbers = self.indexed_address_transactions(address, 0..end, true, page_size)?;
+        let mut last_page = tx_numbers.len() < page_size;
+        if !last_page {
+            let last = *tx_numbers.last().expect("page is not empty");
+            let start = self.transaction_block(last)?.1.start;
+            tx_numbers.extend(self.indexed_address_transactions(
+                address,
+                start..last,
+                true,
+                usize::MAX,
+            )?);
+            last_page = self.indexed_address_transactions(address, 0..start, true, 1)?.is_empty();
+        }
+
+        Ok(TransactionsPage { tx_numbers, first_page: block_number.is_none(), last_page })
+    }
+
+    /// Returns the page of the transactions of the address in the blocks after the block, or
+    /// after genesis if `None`.
+    fn transactions_after(
+        &self,
+        address: Address,
+        block_number: Option<BlockNumber>,
+        page_size: usize,
+    ) -> RpcResult<TransactionsPage> {
+        if page_size == 0 {
+            return Err(invalid_params_rpc_err("page size must be greater than zero"))
+        }
+        let page_size = page_size.min(MAX_SEARCH_PAGE_SIZE);
+        let start = match block_number {
+            Some(number) => self.block_tx_range(number)?.end,
+            None => 0,
+        };
+
+        let mut tx_numbers =
+            self.indexed_address_transactions(address, start..u64::MAX, false, page_size)?;
+        let mut first_page = tx_numbers.len() < page_size;
+        if !first_page {
+            let last = *tx_numbers.last().expect("page is not empty");
+            let end = self.transaction_block(last)?.1.end;
+            tx_numbers.extend(self.indexed_address_transactions(
+                address,
+                last + 1..end,
+                false,
+                usize::MAX,
+            )?);
+            first_page =
+                self.indexed_address_transactions(address, end..u64::MAX, false, 1)?.is_empty();
+        }
+        tx_numbers.reverse();
+
+        Ok(TransactionsPage { tx_numbers, first_page, last_page: block_number.is_none() })
+    }
+}
+
+impl<Provider, Eth> OtterscanApi<Provider, Eth>
+where
+    Provider: SenderNoncesReader + AddressTransactionsReader + BlockReaderIdExt + ChainSpecProvider,
+    Eth: EthApiServer + EthTransactions,
+{
+    /// Returns the transaction of the sender with the nonce from the sender nonces index.
+    ///
+    /// Returns `None` if the index is not enabled or doesn't contain the transaction yet.
+    async fn indexed_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> RpcResult<Option<Transaction>> {
+        if self.provider.sender_nonces_checkpoint().to_rpc_result()?.is_none() {
+            return Ok(None)
+        }
+        let Some(hash) =
+            self.provider.transaction_hash_by_sender_and_nonce(sender, nonce).to_rpc_result()?
+        else {
+            return Ok(None)
+        };
+        // the entry is stale if the transaction was reorged out
+        let tx = self.eth.transaction_by_hash(hash).await?;
+        Ok(tx.filter(|tx| tx.from == sender && tx.nonce == nonce && tx.block_number.is_some()))
+    }
+
+    /// Returns the transaction of the sender with the nonce by binary searching the block in which
+    /// the nonce of the sender was increased past `nonce`.
+    async fn search_transaction_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> RpcResult<Option<Transaction>> {
+        // the nonce of contracts is increased by contract creations, not by transactions
+        if self.eth.get_code(sender, None).await.map(|code| !code.is_empty())? {
+            return Ok(None)
+        }
+        let highest = self.eth.transaction_count(sender, None).await?.saturating_to::<u64>();
+        // the transaction is pending or doesn't exist
+        if nonce >= highest {
+            return Ok(None)
+        }
+
+        let (mut low, mut high) = (1, self.eth.block_number()?.saturating_to::<u64>());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let mid_nonce = self
+                .eth
+                .transaction_count(sender, Some(BlockId::from(mid)))
+                .await?
+                .saturating_to::<u64>();
+            if mid_nonce > nonce {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        let Some(BlockTransactions::Full(transactions)) = self
+            .eth
+            .block_by_number(BlockNumberOrTag::Number(low), true)
+            .await?
+            .map(|block| block.inner.transactions)
+        else {
+            return Err(internal_rpc_err("block not found"))
+        };
+        Ok(transactions.into_iter().find(|tx| tx.from == sender && tx.nonce == nonce))
+    }
+
+    /// Resolves the number of the block.
+    fn block_number(&self, block_number: BlockNumberOrTag) -> RpcResult<BlockNumber> {
+        self.provider
+            .convert_block_number(block_number)
+            .to_rpc_result()?
+            .ok_or_else(|| internal_rpc_err("block not found"))
+    }
+
+    /// Returns the transactions and their receipts, in the order of the transaction numbers.
+    async fn transactions_with_receipts(
+        &self,
+        tx_numbers: Vec<TxNumber>,
+    ) -> RpcResult<(Vec<Transaction>, Vec<OtsTransactionReceipt>)> {
+        let mut txs = Vec::with_capacity(tx_numbers.len());
+        let mut receipts = Vec::with_capacity(tx_numbers.len());
+
+        // transactions of the same block are consecutive, each block is fetched once
+        let mut tx_numbers = tx_numbers.into_iter().peekable();
+        while let Some(&tx_number) = tx_numbers.peek() {
+            let (number, tx_range) = self.transaction_block(tx_number)?;
+            let block = self.eth.block_by_number(BlockNumberOrTag::Number(number), true);
+            let block_receipts = self.eth.block_receipts(BlockId::from(number));
+            let (block, block_receipts) = futures::try_join!(block, block_receipts)?;
+
+            let block = block.ok_or_else(|| internal_rpc_err("block not found"))?;
+            let block_receipts =
+                block_receipts.ok_or_else(|| internal_rpc_err("receipts not found"))?;
+            let timestamp = block.header.timestamp;
+            let BlockTransactions::Full(block_txs) = block.inner.transactions else {
+                return Err(internal_rpc_err("block is not full"))
+            };
+            let mut block_txs = block_txs.into_iter().map(Some).collect::<Vec<_>>();
+            let mut block_receipts = block_receipts.into_iter().map(Some).collect::<Vec<_>>();
+
+            while let Some(tx_number) = tx_numbers.next_if(|tx_number| tx_range.contains(tx_number))
+            {
+                let index = (tx_number - tx_range.start) as usize;
+                let tx = block_txs.get_mut(index).and_then(Option::take);
+                let receipt = block_receipts.get_mut(index).and_then(Option::take);
+                let (Some(tx), Some(receipt)) = (tx, receipt) else {
+                    return Err(internal_rpc_err("transaction not found"))
+                };
+                txs.push(tx);
+                receipts.push(ots_receipt(receipt, timestamp));
+            }
+        }
+
+        Ok((txs, receipts))
+    }
+
+    /// Returns the details of the block, with the issuance of the block and the fees paid by its
+    /// transactions.
+    async fn block_details(&self, number: Option<BlockNumber>) -> RpcResult<Option<BlockDetails>> {
+        let Some(number) = number else { return Ok(None) };
+        let Some(block) = self.eth.block_by_number(BlockNumberOrTag::Number(number), false).await?
+        else {
+            return Ok(None)
+        };
+
+        let mut details = BlockDetails::from(block);
+        details.issuance = self.block_issuance(number)?;
+        details.total_fees = self.block_fees(number)?;
+        Ok(Some(details))
+    }
+
+    /// Returns the block reward, the ommer rewards and their sum, which are all zero after the
+    /// merge.
+    fn block_issuance(&self, number: BlockNumber) -> RpcResult<InternalIssuance> {
+        let header = self
+            .provider
+            .header_by_number(number)
+            .to_rpc_result()?
+            .ok_or_else(|| internal_rpc_err("header not found"))?;
+        let td = self.provider.header_td_by_number(number).to_rpc_result()?.unwrap_or_default();
+        let chain_spec = self.provider.chain_spec();
+
+        let (mut block, mut ommers) = (0, 0);
+        if let Some(base_reward) = base_block_reward(&chain_spec, number, header.difficulty, td) {
+            let ommer_headers =
+                self.provider.ommers(number.into()).to_rpc_result()?.unwrap_or_default();
+            block = block_reward(base_reward, ommer_headers.len());
+            ommers = ommer_headers
+                .iter()
+                .map(|ommer| ommer_reward(base_reward, number, ommer.number))
+                .sum::<u128>();
+        }
+
+        Ok(InternalIssuance {
+            block_reward: U256::from(block),
+            uncle_reward: U256::from(ommers),
+            issuance: U256::from(block + ommers),
+        })
+    }
+
+    /// Returns the sum of the fees paid by the transactions of the block, including the burnt
+    /// base fees.
+    ///
+    /// Returns zero if the receipts of the block were pruned.
+    fn block_fees(&self, number: BlockNumber) -> RpcResult<U256> {
+        let header = self
+            .provider
+            .header_by_number(number)
+            .to_rpc_result()?
+            .ok_or_else(|| internal_rpc_err("header not found"))?;
+        let transactions = self.provider.transactions_by_block(number.into()).to_rpc_result()?;
+        let receipts = self.provider.receipts_by_block(number.into()).to_rpc_result()?;
+        let (Some(transactions), Some(receipts)) = (transactions, receipts) else {
+            return Ok(U256::ZERO)
+        };
+
+        let mut fees = U256::ZERO;
+        let mut cumulative_gas_used = 0;
+        for (tx, receipt) in transactions.iter().zip(&receipts) {
+            let gas_used = receipt.cumulative_gas_used - cumulative_gas_used;
+            cumulative_gas_used = receipt.cumulative_gas_used;
+            fees +=
+                U256::from(tx.effective_gas_price(header.base_fee_per_gas)) * U256::from(gas_used);
+        }
+        Ok(fees)
+    }
+
+    /// Returns the creation of the contract from the address transactions index.
+    ///
+    /// Returns `None` if the index is not enabled or the first transaction of the address did not
+    /// create it, e.g. because the contract was created by another contract.
+    fn indexed_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        let Some(tx_number) = self
+            .provider
+            .address_transactions_by_range(address, 0..u64::MAX, false, 1)
+            .to_rpc_result()?
+            .and_then(|tx_numbers| tx_numbers.first().copied())
+        else {
+            return Ok(None)
+        };
+        let Some(tx) = self.provider.transaction_by_id(tx_number).to_rpc_result()? else {
+            return Ok(None)
+        };
+        if tx.to().is_some() {
+            return Ok(None)
+        }
+        let sender = match self.provider.transaction_sender(tx_number).to_rpc_result()? {
+            Some(sender) => sender,
+            None => tx.recover_signer().ok_or_else(|| internal_rpc_err("invalid signature"))?,
+        };
+        Ok((sender.create(tx.nonce()) == address)
+            .then(|| ContractCreator { hash: tx.hash(), creator: sender }))
+    }
+
+    /// Returns the creation of the contract by binary searching the block in which its code was
+    /// deployed and tracing the transactions of the block.
+    async fn search_contract_creator(
+        &self,
+        address: Address,
+    ) -> RpcResult<Option<ContractCreator>> {
+        let (mut low, mut high) = (0, self.eth.block_number()?.saturating_to::<u64>());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let code = self.eth.get_code(address, Some(BlockId::from(mid))).await?;
+            if code.is_empty() {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let creations = self
+            .eth
+            .trace_block_with_inspector(
+                BlockId::from(low),
+                || TransferInspector::new(false),
+                move |tx_info, inspector, _, _, _| {
+                    let creator = inspector
+                        .into_transfers()
+                        .into_iter()
+                        .find(|transfer| {
+                            matches!(transfer.kind, TransferKind::Create | TransferKind::Create2) &&
+                                transfer.to == address
+                        })
+                        .map(|transfer| transfer.from);
+                    Ok(creator.zip(tx_info.hash))
+                },
+            )
+            .await?
+            .unwrap_or_default();
+        Ok(creations
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|(creator, hash)| ContractCreator { hash, creator }))
+    }
+}
+
+/// Converts the receipt into a receipt of the Otterscan API, which omits the logs and the bloom.
+fn ots_receipt(receipt: AnyTransactionReceipt, timestamp: u64) -> OtsTransactionReceipt {
+    let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
+        status: receipt.inner.receipt.status,
+        cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
+        logs: None,
+        logs_bloom: None,
+        r#type: receipt.r#type,
+    });
+    OtsTransactionReceipt { receipt, timestamp: Some(timestamp) }
+}
+
 #[async_trait]
-impl<Eth> OtterscanServer for OtterscanApi<Eth>
+impl<Provider, Eth> OtterscanServer for OtterscanApi<Provider, Eth>
 where
+    Provider: SenderNoncesReader
+        + AddressTransactionsReader
+        + BlockReaderIdExt
+        + ChainSpecProvider
+        + 'static,
     Eth: EthApiServer + EthTransactions,
 {
     /// Handler for `ots_hasCode`
@@ -104,14 +528,14 @@ where
         &self,
         block_number: BlockNumberOrTag,
     ) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_number(block_number, true).await?;
-        Ok(block.map(Into::into))
+        let number = self.provider.convert_block_number(block_number).to_rpc_result()?;
+        self.block_details(number).await
     }
 
     /// Handler for `getBlockDetailsByHash`
     async fn get_block_details_by_hash(&self, block_hash: B256) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_hash(block_hash, true).await?;
-        Ok(block.map(Into::into))
+        let number = self.provider.block_number(block_hash).to_rpc_result()?;
+        self.block_details(number).await
     }
 
     /// Handler for `getBlockTransactions`
@@ -158,55 +582,237 @@ where
         }
 
         // Crop receipts and transform them into OtsTransactionReceipt
-        let timestamp = Some(block.header.timestamp);
+        let timestamp = block.header.timestamp;
         let receipts = receipts
             .drain(page_start..page_end)
-            .map(|receipt| {
-                let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
-                    status: receipt.inner.receipt.status,
-                    cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: receipt.r#type,
-                });
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|receipt| ots_receipt(receipt, timestamp))
             .collect();
         Ok(OtsBlockTransactions { fullblock: block.inner.into(), receipts })
     }
 
     /// Handler for `searchTransactionsBefore`
+    ///
+    /// Returns the transactions of the address in the blocks before the block, the most recent
+    /// first. Block `0` searches from the tip of the chain. Pages end at block boundaries, so the
+    /// page contains all transactions of the address in its last block.
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = match block_number {
+            BlockNumberOrTag::Number(0) => None,
+            block_number => Some(self.block_number(block_number)?),
+        };
+        let page = self.transactions_before(address, block_number, page_size)?;
+
+        let (txs, receipts) = self.transactions_with_receipts(page.tx_numbers).await?;
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: page.first_page,
+            last_page: page.last_page,
+        })
     }
 
     /// Handler for `searchTransactionsAfter`
+    ///
+    /// Returns the transactions of the address in the blocks after the block, the most recent
+    /// first. Block `0` searches from genesis. Pages end at block boundaries, so the page contains
+    /// all transactions of the address in its most recent block.
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = match block_number {
+            BlockNumberOrTag::Number(0) => None,
+            block_number => Some(self.block_number(block_number)?),
+        };
+        let page = self.transactions_after(address, block_number, page_size)?;
+
+        let (txs, receipts) = self.transactions_with_receipts(page.tx_numbers).await?;
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: page.first_page,
+            last_page: page.last_page,
+        })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
     async fn get_transaction_by_sender_and_nonce(
         &self,
-        _sender: Address,
-        _nonce: u64,
+        sender: Address,
+        nonce: u64,
     ) -> RpcResult<Option<Transaction>> {
-        Err(internal_rpc_err("unimplemented"))
+        if let Some(tx) = self.indexed_transaction_by_sender_and_nonce(sender, nonce).await? {
+            return Ok(Some(tx))
+        }
+        self.search_transaction_by_sender_and_nonce(sender, nonce).await
     }
 
     /// Handler for `getContractCreator`
-    async fn get_contract_creator(&self, _address: Address) -> RpcResult<Option<ContractCreator>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        if !self.has_code(address, None).await? {
+            return Ok(None)
+        }
+        if let Some(creator) = self.indexed_contract_creator(address)? {
+            return Ok(Some(creator))
+        }
+        self.search_contract_creator(address).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{
+        models::{ShardedKey, StoredBlockBodyIndices},
+        tables,
+        transaction::DbTxMut,
+        TransactionNumberList,
+    };
+    use reth_provider::{test_utils::create_test_provider_factory, ADDRESS_TRANSACTIONS_EXEX_ID};
+
+    /// Blocks with three transactions each, block `n` contains the transactions
+    /// `3 * (n - 1)..3 * n`.
+    fn otterscan_with_address_transactions(
+        blocks: BlockNumber,
+        address: Address,
+        tx_numbers: &[TxNumber],
+    ) -> OtterscanApi<impl AddressTransactionsReader + BlockReader, ()> {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        for number in 1..=blocks {
+            let first_tx_num = 3 * (number - 1);
+            provider_rw
+                .tx_ref()
+                .put::<tables::BlockBodyIndices>(
+                    number,
+                    StoredBlockBodyIndices { first_tx_num, tx_count: 3 },
+                )
+                .unwrap();
+            provider_rw
+                .tx_ref()
+                .put::<tables::TransactionBlocks>(first_tx_num + 2, number)
+                .unwrap();
+        }
+        provider_rw
+            .tx_ref()
+            .put::<tables::AddressTransactions>(
+                ShardedKey::last(address),
+                TransactionNumberList::new_pre_sorted(tx_numbers),
+            )
+            .unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::ExExCheckpoints>(ADDRESS_TRANSACTIONS_EXEX_ID.to_string(), blocks)
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        OtterscanApi::new(factory, ())
+    }
+
+    fn page(tx_numbers: Vec<TxNumber>, first_page: bool, last_page: bool) -> TransactionsPage {
+        TransactionsPage { tx_numbers, first_page, last_page }
+    }
+
+    #[test]
+    fn search_transactions_before_ends_pages_at_block_boundaries() {
+        let address = Address::random();
+        let api = otterscan_with_address_transactions(5, address, &[0, 1, 4, 9, 10, 11, 13]);
+
+        // the page is completed with the transactions of its last block
+        assert_eq!(
+            api.transactions_before(address, Some(5), 2).unwrap(),
+            page(vec![11, 10, 9], false, false)
+        );
+        // the next page starts before the last block of the previous page
+        assert_eq!(
+            api.transactions_before(address, Some(4), 2).unwrap(),
+            page(vec![4, 1, 0], false, true)
+        );
+        // the transactions of the block itself are excluded
+        assert_eq!(
+            api.transactions_before(address, Some(2), 2).unwrap(),
+            page(vec![1, 0], false, true)
+        );
+        assert_eq!(
+            api.transactions_before(address, Some(1), 2).unwrap(),
+            page(vec![], false, true)
+        );
+        // from the tip
+        assert_eq!(
+            api.transactions_before(address, None, 2).unwrap(),
+            page(vec![13, 11, 10, 9], true, false)
+        );
+        assert_eq!(
+            api.transactions_before(address, None, 7).unwrap(),
+            page(vec![13, 11, 10, 9, 4, 1, 0], true, true)
+        );
+    }
+
+    #[test]
+    fn search_transactions_after_ends_pages_at_block_boundaries() {
+        let address = Address::random();
+        let api = otterscan_with_address_transactions(5, address, &[0, 1, 4, 9, 10, 11, 13]);
+
+        // the page is completed with the transactions of its most recent block
+        assert_eq!(
+            api.transactions_after(address, Some(1), 2).unwrap(),
+            page(vec![11, 10, 9, 4], false, false)
+        );
+        // the next page starts after the most recent block of the previous page
+        assert_eq!(
+            api.transactions_after(address, Some(4), 2).unwrap(),
+            page(vec![13], true, false)
+        );
+        assert_eq!(api.transactions_after(address, Some(5), 2).unwrap(), page(vec![], true, false));
+        // from genesis
+        assert_eq!(
+            api.transactions_after(address, None, 1).unwrap(),
+            page(vec![1, 0], false, true)
+        );
+        assert_eq!(
+            api.transactions_after(address, None, 7).unwrap(),
+            page(vec![13, 11, 10, 9, 4, 1, 0], true, true)
+        );
+    }
+
+    #[test]
+    fn search_transactions_page_size_limits() {
+        let address = Address::random();
+        let tx_numbers = (0..15).collect::<Vec<_>>();
+        let api = otterscan_with_address_transactions(5, address, &tx_numbers);
+
+        assert!(api.transactions_before(address, None, 0).is_err());
+        assert!(api.transactions_after(address, None, 0).is_err());
+
+        // pages exceed the page size by the transactions that complete their last block
+        assert_eq!(
+            api.transactions_before(address, None, 4).unwrap(),
+            page(vec![14, 13, 12, 11, 10, 9], true, false)
+        );
+        assert_eq!(
+            api.transactions_after(address, None, 4).unwrap(),
+            page(vec![5, 4, 3, 2, 1, 0], false, true)
+        );
+    }
+
+    #[test]
+    fn search_transactions_page_size_is_capped() {
+        let address = Address::random();
+        // the address is part of every transaction of 40 blocks
+        let tx_numbers = (0..120).collect::<Vec<_>>();
+        let api = otterscan_with_address_transactions(40, address, &tx_numbers);
+
+        // the last block of the page, block 7, contains the transactions 18 to 20
+        let page_before = api.transactions_before(address, None, usize::MAX).unwrap();
+        assert_eq!(page_before, page((18..120).rev().collect(), true, false));
+        let page_after = api.transactions_after(address, None, usize::MAX).unwrap();
+        assert_eq!(page_after, page((0..102).rev().collect(), false, true));
     }
 }
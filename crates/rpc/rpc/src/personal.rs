@@ -0,0 +1,266 @@
+use crate::{
+    eth::{
+        keystore::{KeystoreError, KeystoreSigner},
+        signer::EthSigner,
+        EthTransactions,
+    },
+    result::{internal_rpc_err, invalid_params_rpc_err, rpc_error_with_code},
+    EthApi,
+};
+use async_trait::async_trait;
+use jsonrpsee::{
+    core::RpcResult,
+    types::{error::METHOD_NOT_FOUND_CODE, ErrorObject},
+};
+use reth_evm::ConfigureEvm;
+use reth_network_api::NetworkInfo;
+use reth_primitives::{hex, Address, B256};
+use reth_provider::{BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
+use reth_rpc_api::PersonalApiServer;
+use reth_rpc_types::TransactionRequest;
+use reth_transaction_pool::TransactionPool;
+use secp256k1::SecretKey;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, time::Duration};
+use tracing::trace;
+
+/// Default duration accounts are unlocked for by `personal_unlockAccount`: 5 minutes
+const DEFAULT_UNLOCK_DURATION: Duration = Duration::from_secs(300);
+
+/// The methods of the `personal` namespace.
+pub const PERSONAL_METHODS: [&str; 6] = [
+    "personal_listAccounts",
+    "personal_newAccount",
+    "personal_importRawKey",
+    "personal_unlockAccount",
+    "personal_lockAccount",
+    "personal_sendTransaction",
+];
+
+/// Error returned when disabling a method that is not part of the `personal` namespace.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown method {0} of the personal namespace")]
+pub struct UnknownPersonalMethod(pub String);
+
+/// Which methods of the `personal` namespace are available.
+///
+/// All methods are available by default, disabled methods fail as if they didn't exist. This
+/// allows e.g. serving `personal_unlockAccount` without allowing new keys to be added with
+/// `personal_newAccount` and `personal_importRawKey`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PersonalPolicy {
+    /// The disabled methods, by their full name, e.g. `personal_importRawKey`.
+    disabled_methods: HashSet<String>,
+}
+
+impl PersonalPolicy {
+    /// Creates a policy that disables the given methods.
+    ///
+    /// Returns an error if a method is not one of the [PERSONAL_METHODS].
+    pub fn new(
+        disabled_methods: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Self, UnknownPersonalMethod> {
+        disabled_methods
+            .into_iter()
+            .try_fold(Self::default(), |policy, method| policy.disable(method))
+    }
+
+    /// Disables the method.
+    ///
+    /// Returns an error if the method is not one of the [PERSONAL_METHODS].
+    pub fn disable(mut self, method: impl Into<String>) -> Result<Self, UnknownPersonalMethod> {
+        let method = method.into();
+        if !PERSONAL_METHODS.contains(&method.as_str()) {
+            return Err(UnknownPersonalMethod(method))
+        }
+        self.disabled_methods.insert(method);
+        Ok(self)
+    }
+
+    /// Returns `true` if the method is available.
+    pub fn is_enabled(&self, method: &str) -> bool {
+        !self.disabled_methods.contains(method)
+    }
+
+    /// Returns an error if the method is disabled.
+    fn ensure_enabled(&self, method: &str) -> RpcResult<()> {
+        if self.is_enabled(method) {
+            return Ok(())
+        }
+        Err(rpc_error_with_code(
+            METHOD_NOT_FOUND_CODE,
+            format!("the method {method} does not exist/is not available"),
+        ))
+    }
+}
+
+/// `personal` API implementation.
+///
+/// This is a compatibility layer for legacy tooling that manages the accounts of the keystore
+/// configured with `--keystore`.
+pub struct PersonalApi<Provider, Pool, Network, EvmConfig> {
+    eth: EthApi<Provider, Pool, Network, EvmConfig>,
+    policy: PersonalPolicy,
+}
+
+impl<Provider, Pool, Network, EvmConfig> PersonalApi<Provider, Pool, Network, EvmConfig> {
+    /// Creates a new instance of `PersonalApi` with all methods available.
+    pub fn new(eth: EthApi<Provider, Pool, Network, EvmConfig>) -> Self {
+        Self { eth, policy: PersonalPolicy::default() }
+    }
+
+    /// Configures which methods are available.
+    pub fn with_policy(mut self, policy: PersonalPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the configured keystore.
+    fn keystore(&self) -> RpcResult<KeystoreSigner> {
+        self.eth
+            .keystore()
+            .ok_or_else(|| internal_rpc_err("no keystore configured, see --keystore"))
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> PersonalApi<Provider, Pool, Network, EvmConfig>
+where
+    Pool: TransactionPool + Clone + 'static,
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Runs the keystore operation on a blocking task, because key derivation is CPU bound.
+    async fn spawn_keystore<F, R>(&self, f: F) -> RpcResult<R>
+    where
+        F: FnOnce(KeystoreSigner) -> Result<R, KeystoreError> + Send + 'static,
+        R: Send + 'static,
+    {
+        let keystore = self.keystore()?;
+        self.eth.spawn_blocking(move || Ok(f(keystore))).await?.map_err(keystore_rpc_err)
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool, Network, EvmConfig> PersonalApiServer
+    for PersonalApi<Provider, Pool, Network, EvmConfig>
+where
+    Pool: TransactionPool + Clone + 'static,
+    Provider:
+        BlockReaderIdExt + ChainSpecProvider + StateProviderFactory + EvmEnvProvider + 'static,
+    Network: NetworkInfo + Send + Sync + 'static,
+    EvmConfig: ConfigureEvm + 'static,
+{
+    /// Handler for `personal_listAccounts`
+    async fn list_accounts(&self) -> RpcResult<Vec<Address>> {
+        trace!(target: "rpc::personal", "Serving personal_listAccounts");
+        self.policy.ensure_enabled("personal_listAccounts")?;
+        Ok(self.eth.keystore().map(|keystore| keystore.accounts()).unwrap_or_default())
+    }
+
+    /// Handler for `personal_newAccount`
+    async fn new_account(&self, passphrase: String) -> RpcResult<Address> {
+        trace!(target: "rpc::personal", "Serving personal_newAccount");
+        self.policy.ensure_enabled("personal_newAccount")?;
+        self.spawn_keystore(move |keystore| keystore.new_account(&passphrase)).await
+    }
+
+    /// Handler for `personal_importRawKey`
+    async fn import_raw_key(&self, private_key: String, passphrase: String) -> RpcResult<Address> {
+        trace!(target: "rpc::personal", "Serving personal_importRawKey");
+        self.policy.ensure_enabled("personal_importRawKey")?;
+        let key = hex::decode(private_key)
+            .ok()
+            .and_then(|key| SecretKey::from_slice(&key).ok())
+            .ok_or_else(|| invalid_params_rpc_err("invalid private key"))?;
+        self.spawn_keystore(move |keystore| keystore.import_key(key, &passphrase)).await
+    }
+
+    /// Handler for `personal_unlockAccount`
+    async fn unlock_account(
+        &self,
+        address: Address,
+        passphrase: String,
+        duration: Option<u64>,
+    ) -> RpcResult<bool> {
+        trace!(target: "rpc::personal", ?address, ?duration, "Serving personal_unlockAccount");
+        self.policy.ensure_enabled("personal_unlockAccount")?;
+        let duration = match duration {
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => Some(DEFAULT_UNLOCK_DURATION),
+        };
+        self.spawn_keystore(move |keystore| keystore.unlock(address, &passphrase, duration))
+            .await?;
+        Ok(true)
+    }
+
+    /// Handler for `personal_lockAccount`
+    async fn lock_account(&self, address: Address) -> RpcResult<bool> {
+        trace!(target: "rpc::personal", ?address, "Serving personal_lockAccount");
+        self.policy.ensure_enabled("personal_lockAccount")?;
+        self.keystore()?.lock(address).map_err(keystore_rpc_err)?;
+        Ok(true)
+    }
+
+    /// Handler for `personal_sendTransaction`
+    async fn send_transaction(
+        &self,
+        request: TransactionRequest,
+        passphrase: String,
+    ) -> RpcResult<B256> {
+        trace!(target: "rpc::personal", ?request, "Serving personal_sendTransaction");
+        self.policy.ensure_enabled("personal_sendTransaction")?;
+        let from = request.from.ok_or_else(|| invalid_params_rpc_err("missing from address"))?;
+        let signer = self
+            .spawn_keystore(move |keystore| keystore.signer_with_passphrase(from, &passphrase))
+            .await?;
+        Ok(self.eth.send_transaction_with_signer(request, Some(&*signer)).await?)
+    }
+}
+
+impl<Provider, Pool, Network, EvmConfig> std::fmt::Debug
+    for PersonalApi<Provider, Pool, Network, EvmConfig>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersonalApi").field("policy", &self.policy).finish_non_exhaustive()
+    }
+}
+
+/// Converts the keystore error into an RPC error, with geth's message for a wrong passphrase.
+fn keystore_rpc_err(err: KeystoreError) -> ErrorObject<'static> {
+    if err.is_invalid_passphrase() {
+        return invalid_params_rpc_err("could not decrypt key with given password")
+    }
+    match err {
+        KeystoreError::UnknownAccount(_) | KeystoreError::AccountExists(_) => {
+            invalid_params_rpc_err(err.to_string())
+        }
+        err => internal_rpc_err(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn personal_policy() {
+        let policy = PersonalPolicy::new(["personal_importRawKey"])
+            .and_then(|policy| policy.disable("personal_newAccount"))
+            .unwrap();
+        assert!(policy.is_enabled("personal_unlockAccount"));
+        assert!(!policy.is_enabled("personal_newAccount"));
+
+        let err = policy.ensure_enabled("personal_importRawKey").unwrap_err();
+        assert_eq!(err.code(), METHOD_NOT_FOUND_CODE);
+        assert!(PersonalPolicy::default().ensure_enabled("personal_importRawKey").is_ok());
+
+        // a misspelled method would silently stay enabled
+        assert_eq!(
+            PersonalPolicy::new(["personal_importRawkey"]),
+            Err(UnknownPersonalMethod("personal_importRawkey".to_string()))
+        );
+    }
+}
@@ -0,0 +1,345 @@
+//! REST API for lightweight block explorers.
+//!
+//! The API serves blocks, transactions and the transactions of an address directly from the
+//! providers, so that an explorer does not need a separate indexer:
+//!
+//! - `GET /blocks/{id}`: a block by number, hash or tag, `?full=true` includes the transactions
+//! - `GET /txs/{hash}`: a transaction and its receipt
+//! - `GET /address/{address}/txs?page=`: the transactions sent from or to an address
+//! - `GET /openapi.json`: the [OpenAPI](https://www.openapis.org/) schema of the API
+
+use crate::reth::address_transactions_page;
+use http::{header, Response, StatusCode};
+use hyper::Body;
+use reth_primitives::{
+    Address, BlockHashOrNumber, BlockId, TransactionMeta, TransactionSigned, B256, U256,
+};
+use reth_provider::{
+    AddressTransactionsReader, BlockReaderIdExt, ProviderError, TransactionVariant,
+};
+use reth_rpc_types::{Block, BlockTransactionsKind, Log, Transaction, TransactionsPage};
+use reth_rpc_types_compat::{block::from_block, transaction::from_recovered_with_block_context};
+use serde::Serialize;
+use std::{fmt, sync::Arc};
+
+mod openapi;
+mod route;
+
+pub use route::RestRoute;
+
+/// The default number of transactions per page of `/address/{address}/txs`.
+pub const DEFAULT_REST_PAGE_SIZE: usize = 25;
+
+/// The default maximum number of transactions per page of `/address/{address}/txs`.
+pub const DEFAULT_REST_MAX_PAGE_SIZE: usize = 100;
+
+/// Helper trait with all the provider functionality the REST API requires.
+pub trait RestProvider:
+    BlockReaderIdExt + AddressTransactionsReader + Clone + Unpin + 'static
+{
+}
+
+impl<T> RestProvider for T where
+    T: BlockReaderIdExt + AddressTransactionsReader + Clone + Unpin + 'static
+{
+}
+
+/// Settings for the REST API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestConfig {
+    /// Number of transactions per page if the request does not specify a page size.
+    pub default_page_size: usize,
+    /// Maximum number of transactions per page.
+    pub max_page_size: usize,
+}
+
+impl Default for RestConfig {
+    fn default() -> Self {
+        Self {
+            default_page_size: DEFAULT_REST_PAGE_SIZE,
+            max_page_size: DEFAULT_REST_MAX_PAGE_SIZE,
+        }
+    }
+}
+
+/// Errors returned by the REST API.
+#[derive(Debug, thiserror::Error)]
+pub enum RestError {
+    /// A path or query parameter is invalid.
+    #[error("invalid parameter: {0}")]
+    InvalidParam(String),
+    /// The requested resource does not exist.
+    #[error("not found")]
+    NotFound,
+    /// The route is not supported by this node.
+    #[error("{0}")]
+    Unsupported(&'static str),
+    /// The stored data could not be converted into a response.
+    #[error("{0}")]
+    Internal(String),
+    /// Error reading from the database.
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+impl RestError {
+    const fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidParam(_) => StatusCode::BAD_REQUEST,
+            Self::NotFound => StatusCode::NOT_FOUND,
+            Self::Unsupported(_) => StatusCode::NOT_IMPLEMENTED,
+            Self::Internal(_) | Self::Provider(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// A transaction with its receipt, see `/txs/{hash}`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionWithReceipt {
+    /// The transaction.
+    pub transaction: Transaction,
+    /// The receipt of the transaction.
+    pub receipt: TransactionReceiptSummary,
+}
+
+/// The execution outcome of a transaction.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionReceiptSummary {
+    /// Whether the transaction was executed successfully.
+    pub status: bool,
+    /// The gas used by the transaction.
+    pub gas_used: U256,
+    /// The gas used by this and all previous transactions of the block.
+    pub cumulative_gas_used: U256,
+    /// The logs emitted by the transaction.
+    pub logs: Vec<Log>,
+}
+
+/// Handles the requests of the REST API.
+///
+/// The handler is generic over the provider, this type erases it so that it can be served by the
+/// http server.
+#[derive(Clone)]
+pub struct RestApi {
+    handler: Arc<dyn RestHandler>,
+    config: RestConfig,
+}
+
+impl RestApi {
+    /// Creates the REST API for the given provider.
+    pub fn new<Provider>(provider: Provider, config: RestConfig) -> Self
+    where
+        Provider: RestProvider,
+    {
+        Self { handler: Arc::new(ProviderRestHandler { provider }), config }
+    }
+
+    /// Returns the config of the REST API.
+    pub const fn config(&self) -> &RestConfig {
+        &self.config
+    }
+
+    /// Handles the request for the route.
+    ///
+    /// Handling a request reads from the database, the request is therefore handled on a blocking
+    /// thread.
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime.
+    pub async fn handle(&self, route: RestRoute) -> Response<Body> {
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || this.handle_blocking(route)).await.unwrap_or_else(
+            |_| json_response(StatusCode::INTERNAL_SERVER_ERROR, &error_body("internal error")),
+        )
+    }
+
+    fn handle_blocking(&self, route: RestRoute) -> Response<Body> {
+        let result = match route {
+            RestRoute::Block { id, full } => self.handler.block(id, full).map(to_json),
+            RestRoute::Transaction { hash } => self.handler.transaction(hash).map(to_json),
+            RestRoute::AddressTransactions { address, page, page_size } => {
+                let page_size = page_size
+                    .unwrap_or(self.config.default_page_size)
+                    .min(self.config.max_page_size);
+                self.handler.address_transactions(address, page, page_size).map(to_json)
+            }
+            RestRoute::OpenApi => Ok(openapi::schema()),
+        };
+        match result {
+            Ok(body) => json_response(StatusCode::OK, &body),
+            Err(err) => json_response(err.status(), &error_body(&err.to_string())),
+        }
+    }
+}
+
+impl fmt::Debug for RestApi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RestApi").field("config", &self.config).finish_non_exhaustive()
+    }
+}
+
+/// Resolves the routes of the REST API.
+trait RestHandler: Send + Sync {
+    /// Returns the block, see `/blocks/{id}`.
+    fn block(&self, id: BlockId, full: bool) -> Result<Block, RestError>;
+
+    /// Returns the transaction and its receipt, see `/txs/{hash}`.
+    fn transaction(&self, hash: B256) -> Result<TransactionWithReceipt, RestError>;
+
+    /// Returns a page of the transactions of the address, see `/address/{address}/txs`.
+    fn address_transactions(
+        &self,
+        address: Address,
+        page: usize,
+        page_size: usize,
+    ) -> Result<TransactionsPage, RestError>;
+}
+
+/// [RestHandler] that reads from the providers.
+struct ProviderRestHandler<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> ProviderRestHandler<Provider>
+where
+    Provider: RestProvider,
+{
+    fn rpc_transaction(
+        &self,
+        tx: TransactionSigned,
+        meta: &TransactionMeta,
+    ) -> Result<Transaction, RestError> {
+        let tx = tx
+            .into_ecrecovered()
+            .ok_or_else(|| RestError::Internal("invalid transaction signature".to_string()))?;
+        Ok(from_recovered_with_block_context(
+            tx,
+            meta.block_hash,
+            meta.block_number,
+            meta.base_fee,
+            meta.index as usize,
+        ))
+    }
+}
+
+impl<Provider> RestHandler for ProviderRestHandler<Provider>
+where
+    Provider: RestProvider,
+{
+    fn block(&self, id: BlockId, full: bool) -> Result<Block, RestError> {
+        let block = self
+            .provider
+            .block_with_senders_by_id(id, TransactionVariant::WithHash)?
+            .ok_or(RestError::NotFound)?;
+        let td = self.provider.header_td_by_number(block.number)?.unwrap_or_default();
+        let kind = if full { BlockTransactionsKind::Full } else { BlockTransactionsKind::Hashes };
+        let hash = block.header.hash_slow();
+        from_block(block, td, kind, Some(hash)).map_err(|err| RestError::Internal(err.to_string()))
+    }
+
+    fn transaction(&self, hash: B256) -> Result<TransactionWithReceipt, RestError> {
+        let (tx, meta) =
+            self.provider.transaction_by_hash_with_meta(hash)?.ok_or(RestError::NotFound)?;
+        let receipts = self
+            .provider
+            .receipts_by_block(BlockHashOrNumber::Hash(meta.block_hash))?
+            .ok_or(RestError::NotFound)?;
+        let index = meta.index as usize;
+        let receipt = receipts.get(index).ok_or(RestError::NotFound)?;
+        let previous_gas_used = index
+            .checked_sub(1)
+            .and_then(|index| receipts.get(index))
+            .map_or(0, |receipt| receipt.cumulative_gas_used);
+        let first_log_index =
+            receipts.iter().take(index).map(|receipt| receipt.logs.len() as u64).sum::<u64>();
+
+        let logs = receipt
+            .logs
+            .iter()
+            .enumerate()
+            .map(|(log_index, log)| Log {
+                inner: log.clone(),
+                block_hash: Some(meta.block_hash),
+                block_number: Some(meta.block_number),
+                block_timestamp: Some(meta.timestamp),
+                transaction_hash: Some(meta.tx_hash),
+                transaction_index: Some(meta.index),
+                log_index: Some(first_log_index + log_index as u64),
+                removed: false,
+            })
+            .collect();
+        let receipt = TransactionReceiptSummary {
+            status: receipt.success,
+            gas_used: U256::from(receipt.cumulative_gas_used - previous_gas_used),
+            cumulative_gas_used: U256::from(receipt.cumulative_gas_used),
+            logs,
+        };
+
+        Ok(TransactionWithReceipt { transaction: self.rpc_transaction(tx, &meta)?, receipt })
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        page: usize,
+        page_size: usize,
+    ) -> Result<TransactionsPage, RestError> {
+        address_transactions_page(&self.provider, address, page as u64, page_size as u64)?
+            .ok_or(RestError::Unsupported("the address transactions index is not enabled"))
+    }
+}
+
+fn to_json<T: Serialize>(value: T) -> serde_json::Value {
+    serde_json::to_value(value).expect("REST responses are serializable")
+}
+
+fn error_body(message: &str) -> serde_json::Value {
+    serde_json::json!({ "error": message })
+}
+
+fn json_response(status: StatusCode, body: &serde_json::Value) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .expect("This should never happen")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{BlockNumberOrTag, Header};
+    use reth_provider::test_utils::MockEthProvider;
+
+    #[tokio::test]
+    async fn test_rest_api() {
+        let provider = MockEthProvider::default();
+        let api = RestApi::new(provider.clone(), RestConfig::default());
+
+        let res = api
+            .handle(RestRoute::Block { id: BlockNumberOrTag::Number(1).into(), full: false })
+            .await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let header = Header { number: 1, ..Default::default() };
+        let hash = header.hash_slow();
+        provider.add_block(hash, reth_primitives::Block { header, ..Default::default() });
+        let res = api.handle(RestRoute::Block { id: hash.into(), full: true }).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        let block: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(block["hash"], serde_json::json!(hash));
+
+        let res = api
+            .handle(RestRoute::AddressTransactions {
+                address: Address::ZERO,
+                page: 0,
+                page_size: None,
+            })
+            .await;
+        assert_eq!(res.status(), StatusCode::NOT_IMPLEMENTED);
+    }
+}
@@ -0,0 +1,151 @@
+//! [OpenAPI](https://spec.openapis.org/oas/v3.0.3) schema of the REST API.
+
+use serde_json::{json, Value};
+
+/// Returns the OpenAPI schema of the REST API.
+pub(crate) fn schema() -> Value {
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "reth REST API",
+            "description": "Blocks, transactions and the transactions of an address for block explorers",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": {
+            "/blocks/{id}": {
+                "get": {
+                    "summary": "Returns a block",
+                    "parameters": [
+                        {
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "description": "Block number, block hash or one of `latest`, `earliest`, `pending`, `safe`, `finalized`",
+                            "schema": { "type": "string" },
+                        },
+                        {
+                            "name": "full",
+                            "in": "query",
+                            "description": "Whether to include the full transactions instead of their hashes",
+                            "schema": { "type": "boolean", "default": false },
+                        },
+                    ],
+                    "responses": responses("The block", "#/components/schemas/Block"),
+                },
+            },
+            "/txs/{hash}": {
+                "get": {
+                    "summary": "Returns a transaction and its receipt",
+                    "parameters": [
+                        {
+                            "name": "hash",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "$ref": "#/components/schemas/Hash" },
+                        },
+                    ],
+                    "responses": responses(
+                        "The transaction and its receipt",
+                        "#/components/schemas/TransactionWithReceipt",
+                    ),
+                },
+            },
+            "/address/{address}/txs": {
+                "get": {
+                    "summary": "Returns the transactions sent from or to an address, the most recent first",
+                    "parameters": [
+                        {
+                            "name": "address",
+                            "in": "path",
+                            "required": true,
+                            "schema": { "$ref": "#/components/schemas/Address" },
+                        },
+                        {
+                            "name": "page",
+                            "in": "query",
+                            "schema": { "type": "integer", "minimum": 0, "default": 0 },
+                        },
+                        {
+                            "name": "limit",
+                            "in": "query",
+                            "description": "Number of transactions per page, capped by the node",
+                            "schema": { "type": "integer", "minimum": 0 },
+                        },
+                    ],
+                    "responses": responses(
+                        "A page of transactions",
+                        "#/components/schemas/TransactionsPage",
+                    ),
+                },
+            },
+        },
+        "components": {
+            "schemas": {
+                "Hash": { "type": "string", "pattern": "^0x[0-9a-fA-F]{64}$" },
+                "Address": { "type": "string", "pattern": "^0x[0-9a-fA-F]{40}$" },
+                "Quantity": { "type": "string", "pattern": "^0x[0-9a-fA-F]+$" },
+                "Block": {
+                    "type": "object",
+                    "description": "A block as returned by `eth_getBlockByHash`",
+                },
+                "Transaction": {
+                    "type": "object",
+                    "description": "A transaction as returned by `eth_getTransactionByHash`",
+                },
+                "Log": {
+                    "type": "object",
+                    "description": "A log as returned by `eth_getLogs`",
+                },
+                "TransactionReceipt": {
+                    "type": "object",
+                    "properties": {
+                        "status": { "type": "boolean" },
+                        "gasUsed": { "$ref": "#/components/schemas/Quantity" },
+                        "cumulativeGasUsed": { "$ref": "#/components/schemas/Quantity" },
+                        "logs": { "type": "array", "items": { "$ref": "#/components/schemas/Log" } },
+                    },
+                },
+                "TransactionWithReceipt": {
+                    "type": "object",
+                    "properties": {
+                        "transaction": { "$ref": "#/components/schemas/Transaction" },
+                        "receipt": { "$ref": "#/components/schemas/TransactionReceipt" },
+                    },
+                },
+                "TransactionsPage": {
+                    "type": "object",
+                    "properties": {
+                        "transactions": {
+                            "type": "array",
+                            "items": { "$ref": "#/components/schemas/Transaction" },
+                        },
+                        "page": { "type": "integer" },
+                        "nextPage": { "type": "integer", "nullable": true },
+                    },
+                },
+                "Error": {
+                    "type": "object",
+                    "properties": { "error": { "type": "string" } },
+                },
+            },
+        },
+    })
+}
+
+/// Returns the responses of a route that returns the given schema on success.
+fn responses(description: &str, schema: &str) -> Value {
+    let error = json!({
+        "content": {
+            "application/json": { "schema": { "$ref": "#/components/schemas/Error" } },
+        },
+    });
+    json!({
+        "200": {
+            "description": description,
+            "content": { "application/json": { "schema": { "$ref": schema } } },
+        },
+        "400": { "description": "Invalid parameter", "content": error["content"] },
+        "404": { "description": "Not found", "content": error["content"] },
+        "501": { "description": "Not supported by this node", "content": error["content"] },
+    })
+}
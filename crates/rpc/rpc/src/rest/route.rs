@@ -0,0 +1,150 @@
+use super::RestError;
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, B256};
+
+/// A route of the REST API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestRoute {
+    /// `GET /blocks/{id}?full=`
+    Block {
+        /// The requested block
+        id: BlockId,
+        /// Whether to include the full transactions instead of their hashes
+        full: bool,
+    },
+    /// `GET /txs/{hash}`
+    Transaction {
+        /// The hash of the requested transaction
+        hash: B256,
+    },
+    /// `GET /address/{address}/txs?page=&limit=`
+    AddressTransactions {
+        /// The address the transactions were sent from or to
+        address: Address,
+        /// The requested page, starting at 0
+        page: usize,
+        /// The requested number of transactions per page
+        page_size: Option<usize>,
+    },
+    /// `GET /openapi.json`
+    OpenApi,
+}
+
+impl RestRoute {
+    /// Parses the route of a request.
+    ///
+    /// Returns `None` if the path is not a route of the REST API and an error if the path or query
+    /// parameters of a route are invalid.
+    pub fn parse(path: &str, query: Option<&str>) -> Option<Result<Self, RestError>> {
+        let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+        let route = match segments.as_slice() {
+            ["blocks", id] => parse_block_id(id)
+                .ok_or_else(|| RestError::InvalidParam(format!("block id: {id}")))
+                .and_then(|id| {
+                    let full = match query_param(query, "full") {
+                        None => false,
+                        Some(full) => full
+                            .parse()
+                            .map_err(|_| RestError::InvalidParam(format!("full: {full}")))?,
+                    };
+                    Ok(Self::Block { id, full })
+                }),
+            ["txs", hash] => hash
+                .parse()
+                .map(|hash| Self::Transaction { hash })
+                .map_err(|_| RestError::InvalidParam(format!("transaction hash: {hash}"))),
+            ["address", address, "txs"] => address
+                .parse()
+                .map_err(|_| RestError::InvalidParam(format!("address: {address}")))
+                .and_then(|address| {
+                    let page = parse_number(query, "page")?.unwrap_or_default();
+                    let page_size = parse_number(query, "limit")?;
+                    Ok(Self::AddressTransactions { address, page, page_size })
+                }),
+            ["openapi.json"] => Ok(Self::OpenApi),
+            _ => return None,
+        };
+        Some(route)
+    }
+}
+
+/// Returns the value of the query parameter.
+fn query_param<'a>(query: Option<&'a str>, name: &str) -> Option<&'a str> {
+    query?.split('&').find_map(|param| {
+        let (key, value) = param.split_once('=').unwrap_or((param, ""));
+        (key == name).then_some(value)
+    })
+}
+
+/// Parses the value of a numeric query parameter.
+fn parse_number(query: Option<&str>, name: &str) -> Result<Option<usize>, RestError> {
+    query_param(query, name)
+        .map(|value| value.parse().map_err(|_| RestError::InvalidParam(format!("{name}: {value}"))))
+        .transpose()
+}
+
+/// Parses a block id of a path, a block number, block hash or one of the tags `latest`,
+/// `earliest`, `pending`, `safe` and `finalized`.
+fn parse_block_id(id: &str) -> Option<BlockId> {
+    let tag = match id {
+        "latest" => BlockNumberOrTag::Latest,
+        "earliest" => BlockNumberOrTag::Earliest,
+        "pending" => BlockNumberOrTag::Pending,
+        "safe" => BlockNumberOrTag::Safe,
+        "finalized" => BlockNumberOrTag::Finalized,
+        _ if id.len() == 66 => return id.parse::<B256>().ok().map(Into::into),
+        _ => BlockNumberOrTag::Number(match id.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+            None => id.parse().ok()?,
+        }),
+    };
+    Some(tag.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_block_id() {
+        assert_eq!(parse_block_id("latest"), Some(BlockNumberOrTag::Latest.into()));
+        assert_eq!(parse_block_id("10"), Some(BlockNumberOrTag::Number(10).into()));
+        assert_eq!(parse_block_id("0x10"), Some(BlockNumberOrTag::Number(16).into()));
+        assert_eq!(
+            parse_block_id(&B256::with_last_byte(1).to_string()),
+            Some(B256::with_last_byte(1).into())
+        );
+        assert_eq!(parse_block_id("head"), None);
+    }
+
+    #[test]
+    fn test_parse_route() {
+        assert!(RestRoute::parse("/", None).is_none());
+        assert!(RestRoute::parse("/blocks", None).is_none());
+        assert_eq!(
+            RestRoute::parse("/blocks/1", Some("full=true")).unwrap().unwrap(),
+            RestRoute::Block { id: BlockNumberOrTag::Number(1).into(), full: true }
+        );
+        assert!(RestRoute::parse("/blocks/head", None).unwrap().is_err());
+        assert!(RestRoute::parse("/blocks/1", Some("full=yes")).unwrap().is_err());
+
+        let hash = B256::with_last_byte(1);
+        assert_eq!(
+            RestRoute::parse(&format!("/txs/{hash}"), None).unwrap().unwrap(),
+            RestRoute::Transaction { hash }
+        );
+        assert!(RestRoute::parse("/txs/0x01", None).unwrap().is_err());
+
+        let address = Address::with_last_byte(1);
+        assert_eq!(
+            RestRoute::parse(&format!("/address/{address}/txs"), Some("page=2&limit=10"))
+                .unwrap()
+                .unwrap(),
+            RestRoute::AddressTransactions { address, page: 2, page_size: Some(10) }
+        );
+        assert!(RestRoute::parse(&format!("/address/{address}/txs"), Some("page=-1"))
+            .unwrap()
+            .is_err());
+
+        assert_eq!(RestRoute::parse("/openapi.json", None).unwrap().unwrap(), RestRoute::OpenApi);
+    }
+}
@@ -1,39 +1,127 @@
-use crate::eth::error::{EthApiError, EthResult};
+#[cfg(feature = "optimism")]
+use crate::eth::build_op_tx_meta;
+use crate::eth::{
+    build_transaction_receipt_with_block_receipts,
+    error::{EthApiError, EthResult},
+    preconfirmation::pipe_preconfirmations,
+    BlobSidecarIndex, LocalBlobStore, Preconfirmations,
+};
+use alloy_primitives::FixedBytes;
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
-use reth_interfaces::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use jsonrpsee::{
+    core::{RpcResult, SubscriptionResult},
+    PendingSubscriptionSink,
+};
+use reth_interfaces::{
+    blockchain_tree::{error::InsertBlockError, BlockStatus, BlockchainTreeEngine},
+    provider::{ProviderError, ProviderResult},
+    RethError, RethResult,
+};
+use reth_payload_validator::ExecutionPayloadValidator;
+use reth_primitives::{
+    basefee::calc_next_block_base_fee, Address, BaseFeeParams, BlockId, Bytes, ReorgRecord,
+    SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TxNumber, B256,
+    U256, U64,
+};
+use reth_provider::{
+    AddressTransactionsReader, BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
+    ReceiptProvider, ReorgHistoryReader, StateProviderFactory, TokenTransfersReader,
+    TransactionsProvider,
+};
 use reth_rpc_api::RethApiServer;
+use reth_rpc_types::{
+    engine::{CancunPayloadFields, PayloadStatus, PayloadStatusEnum},
+    AnyTransactionReceipt, BlobWithProof, DropReason, ExecutionPayload, FeeEstimate, FeeInterval,
+    TokenTransfersPage, TransactionStatus, TransactionsPage,
+};
+use reth_rpc_types_compat::{
+    token_transfer::from_primitive_token_transfer, transaction::from_recovered_with_block_context,
+};
 use reth_tasks::TaskSpawner;
-use std::{collections::HashMap, future::Future, sync::Arc};
+use reth_transaction_pool::{
+    BestTransactionsAttributes, DiscardReason, PoolTransaction, TransactionPool,
+    TransactionRemoval, ValidPoolTransaction,
+};
+use std::{collections::HashMap, future::Future, ops::Range, sync::Arc};
 use tokio::sync::oneshot;
+use tokio_stream::wrappers::BroadcastStream;
+
+/// The default number of reorgs returned by `reth_getReorgHistory`.
+const DEFAULT_REORG_HISTORY_LIMIT: u64 = 100;
+
+/// The maximum number of reorgs returned by `reth_getReorgHistory`.
+const MAX_REORG_HISTORY_LIMIT: u64 = 1000;
+
+/// The default number of transactions per page of `reth_getTransactionsByAddress`.
+const DEFAULT_ADDRESS_TRANSACTIONS_PAGE_SIZE: u64 = 25;
+
+/// The maximum number of transactions per page of `reth_getTransactionsByAddress`.
+const MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE: u64 = 100;
+
+/// The number of transfers per page of `reth_getTokenTransfers`.
+const TOKEN_TRANSFERS_PAGE_SIZE: usize = 25;
+
+/// The default confidence of `reth_estimateFees`, in percent.
+const DEFAULT_FEE_ESTIMATE_CONFIDENCE: f64 = 90.;
+
+/// The maximum number of hypothetical blocks built by `reth_estimateFees`.
+const MAX_FEE_ESTIMATE_TARGET_BLOCKS: u64 = 10;
+
+/// The maximum number of transaction hashes of `reth_getTransactionReceipts`.
+const MAX_TRANSACTION_RECEIPTS: usize = 1000;
 
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
-    inner: Arc<RethApiInner<Provider>>,
+pub struct RethApi<Provider, Pool> {
+    inner: Arc<RethApiInner<Provider, Pool>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider, Pool> RethApi<Provider, Pool> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
+    /// The transaction pool of the node.
+    pub fn pool(&self) -> &Pool {
+        &self.inner.pool
+    }
+
     /// Create a new instance of the [RethApi]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    pub fn new(
+        provider: Provider,
+        pool: Pool,
+        task_spawner: Box<dyn TaskSpawner>,
+        local_blob_store: LocalBlobStore,
+        blob_sidecar_index: BlobSidecarIndex,
+        preconfirmations: Preconfirmations,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner {
+            provider,
+            pool,
+            task_spawner,
+            local_blob_store,
+            blob_sidecar_index,
+            preconfirmations,
+        });
         Self { inner }
     }
 }
 
-impl<Provider> RethApi<Provider>
+impl<Provider, Pool> RethApi<Provider, Pool>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + AddressTransactionsReader
+        + ChainSpecProvider
+        + ChangeSetReader
+        + ReorgHistoryReader
+        + StateProviderFactory
+        + TokenTransfersReader
+        + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -81,12 +169,483 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the most recent reorgs recorded by the node, newest first.
+    pub async fn reorg_history(&self, limit: Option<u64>) -> EthResult<Vec<ReorgRecord>> {
+        let limit = limit.unwrap_or(DEFAULT_REORG_HISTORY_LIMIT).min(MAX_REORG_HISTORY_LIMIT);
+        self.on_blocking_task(
+            |this| async move { Ok(this.provider().reorg_history(limit as usize)?) },
+        )
+        .await
+    }
+
+    /// Returns a page of the transactions sent from or to the address, the most recent first.
+    pub async fn transactions_by_address(
+        &self,
+        address: Address,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> EthResult<TransactionsPage> {
+        let page = page.unwrap_or_default();
+        let page_size = page_size
+            .unwrap_or(DEFAULT_ADDRESS_TRANSACTIONS_PAGE_SIZE)
+            .min(MAX_ADDRESS_TRANSACTIONS_PAGE_SIZE);
+        self.on_blocking_task(|this| async move {
+            address_transactions_page(this.provider(), address, page, page_size)?
+                .ok_or(EthApiError::Unsupported("the address transactions index is not enabled"))
+        })
+        .await
+    }
+
+    /// Returns a page of the token transfers sent from or to the address, the most recent first.
+    pub async fn token_transfers(
+        &self,
+        address: Address,
+        page: Option<u64>,
+    ) -> EthResult<TokenTransfersPage> {
+        let page = page.unwrap_or_default();
+        let offset = (page as usize).saturating_mul(TOKEN_TRANSFERS_PAGE_SIZE);
+        self.on_blocking_task(|this| async move {
+            // fetch one more transfer to know whether there is a next page
+            let mut transfers = this
+                .provider()
+                .token_transfers(address, offset, TOKEN_TRANSFERS_PAGE_SIZE + 1)?
+                .ok_or(EthApiError::Unsupported("the token transfers index is not enabled"))?;
+            let next_page = (transfers.len() > TOKEN_TRANSFERS_PAGE_SIZE).then(|| {
+                transfers.truncate(TOKEN_TRANSFERS_PAGE_SIZE);
+                page + 1
+            });
+            let transfers = transfers.into_iter().map(from_primitive_token_transfer).collect();
+            Ok(TokenTransfersPage { transfers, page, next_page })
+        })
+        .await
+    }
+
+    /// Estimates the fees of a transaction to be included within `target_blocks` blocks with the
+    /// given confidence, see [estimate_fees].
+    pub async fn estimate_fees(
+        &self,
+        confidence: Option<f64>,
+        target_blocks: Option<u64>,
+    ) -> EthResult<FeeEstimate> {
+        let confidence = confidence.unwrap_or(DEFAULT_FEE_ESTIMATE_CONFIDENCE);
+        if confidence <= 0. || confidence > 100. {
+            return Err(EthApiError::InvalidParams(
+                "confidence must be greater than 0 and at most 100".to_string(),
+            ))
+        }
+        let target_blocks = target_blocks.unwrap_or(1);
+        if !(1..=MAX_FEE_ESTIMATE_TARGET_BLOCKS).contains(&target_blocks) {
+            return Err(EthApiError::InvalidParams(format!(
+                "target blocks must be between 1 and {MAX_FEE_ESTIMATE_TARGET_BLOCKS}"
+            )))
+        }
+
+        self.on_blocking_task(|this| async move {
+            let latest = this.provider().latest_header()?.ok_or(EthApiError::UnknownBlockNumber)?;
+            // assumed the next block is in the next slot: 12s
+            let base_fee_params =
+                this.provider().chain_spec().base_fee_params_at_timestamp(latest.timestamp + 12);
+            let base_fee = latest
+                .next_block_base_fee(base_fee_params)
+                .ok_or(EthApiError::Unsupported("fee estimation requires EIP-1559"))?;
+
+            let transactions = this
+                .pool()
+                .best_transactions_with_attributes(BestTransactionsAttributes::base_fee(base_fee));
+            Ok(estimate_fees(
+                transactions,
+                latest.gas_limit,
+                base_fee,
+                base_fee_params,
+                confidence,
+                target_blocks,
+            ))
+        })
+        .await
+    }
+
+    /// Returns the receipts of the transactions in the order of the hashes, `None` for transactions
+    /// that are not mined.
+    pub async fn transaction_receipts(
+        &self,
+        hashes: Vec<B256>,
+    ) -> EthResult<Vec<Option<AnyTransactionReceipt>>> {
+        if hashes.len() > MAX_TRANSACTION_RECEIPTS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many transaction hashes: {}, at most {MAX_TRANSACTION_RECEIPTS} are allowed",
+                hashes.len()
+            )))
+        }
+        self.on_blocking_task(|this| async move { this.try_transaction_receipts(&hashes) }).await
+    }
+
+    fn try_transaction_receipts(
+        &self,
+        hashes: &[B256],
+    ) -> EthResult<Vec<Option<AnyTransactionReceipt>>> {
+        // group the transactions by block, so that the receipts of each block are loaded once
+        let mut blocks: HashMap<B256, Vec<(usize, TransactionSigned, TransactionMeta)>> =
+            HashMap::new();
+        for (index, hash) in hashes.iter().enumerate() {
+            if let Some((tx, meta)) = self.provider().transaction_by_hash_with_meta(*hash)? {
+                blocks.entry(meta.block_hash).or_default().push((index, tx, meta));
+            }
+        }
+
+        let mut receipts = vec![None; hashes.len()];
+        for (block_hash, transactions) in blocks {
+            let Some(block_receipts) = self.provider().receipts_by_block(block_hash.into())? else {
+                continue
+            };
+
+            #[cfg(feature = "optimism")]
+            let l1_block_info = self
+                .provider()
+                .block_by_hash(block_hash)?
+                .and_then(|block| reth_revm::optimism::extract_l1_info(&block).ok());
+
+            for (index, tx, meta) in transactions {
+                let Some(receipt) = block_receipts.get(meta.index as usize).cloned() else {
+                    continue
+                };
+
+                #[cfg(feature = "optimism")]
+                let op_tx_meta = build_op_tx_meta(
+                    &self.provider().chain_spec(),
+                    &tx,
+                    l1_block_info.clone(),
+                    meta.timestamp,
+                )?;
+
+                receipts[index] = Some(build_transaction_receipt_with_block_receipts(
+                    tx,
+                    meta,
+                    receipt,
+                    &block_receipts,
+                    #[cfg(feature = "optimism")]
+                    op_tx_meta,
+                )?);
+            }
+        }
+        Ok(receipts)
+    }
+
+    /// Returns the status of the transaction in the pool or the chain, see
+    /// [TransactionPool::transaction_removal] for transactions that were removed from the pool.
+    pub async fn transaction_status(&self, hash: B256) -> EthResult<TransactionStatus> {
+        if let Some(status) = pool_transaction_status(self.pool(), &hash) {
+            return Ok(status)
+        }
+
+        let mined = self
+            .on_blocking_task(|this| async move {
+                Ok(this.provider().transaction_by_hash_with_meta(hash)?)
+            })
+            .await?;
+        if let Some((_, meta)) = mined {
+            return Ok(TransactionStatus::Mined {
+                block_hash: meta.block_hash,
+                block_number: U64::from(meta.block_number),
+            })
+        }
+
+        Ok(match self.pool().transaction_removal(&hash) {
+            Some(TransactionRemoval::Replaced(by)) => TransactionStatus::Replaced { by },
+            Some(TransactionRemoval::Discarded(reason)) => {
+                let reason = match reason {
+                    DiscardReason::Invalid => DropReason::Invalid,
+                    DiscardReason::PoolOverflow => DropReason::PoolOverflow,
+                    DiscardReason::Outdated => DropReason::Outdated,
+                    DiscardReason::Removed => DropReason::Removed,
+                };
+                TransactionStatus::Dropped { reason }
+            }
+            None => TransactionStatus::Unknown,
+        })
+    }
+
+    /// Returns the blob with the versioned hash of a canonical blob transaction, if its sidecar is
+    /// still in the blob store of the pool, see [BlobSidecarIndex].
+    pub async fn blob_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> EthResult<Option<BlobWithProof>> {
+        let Some((transaction_hash, index)) = self.inner.blob_sidecar_index.get(&versioned_hash)
+        else {
+            return Ok(None)
+        };
+        let sidecar = self
+            .on_blocking_task(|this| async move { Ok(this.pool().get_blob(transaction_hash)?) })
+            .await?;
+
+        Ok(sidecar.and_then(|sidecar| {
+            Some(BlobWithProof {
+                transaction_hash,
+                blob: Bytes::copy_from_slice(sidecar.blobs.get(index)?.as_slice()),
+                commitment: FixedBytes::from_slice(sidecar.commitments.get(index)?.as_slice()),
+                proof: FixedBytes::from_slice(sidecar.proofs.get(index)?.as_slice()),
+            })
+        }))
+    }
+
+    /// Validates the payload like `engine_newPayload`, including its execution and state root,
+    /// without inserting it into the tree.
+    pub async fn validate_payload(
+        &self,
+        payload: ExecutionPayload,
+        cancun_fields: Option<CancunPayloadFields>,
+    ) -> EthResult<PayloadStatus>
+    where
+        Provider: BlockchainTreeEngine,
+    {
+        self.on_blocking_task(
+            |this| async move { this.try_validate_payload(payload, cancun_fields) },
+        )
+        .await
+    }
+
+    fn try_validate_payload(
+        &self,
+        payload: ExecutionPayload,
+        cancun_fields: Option<CancunPayloadFields>,
+    ) -> EthResult<PayloadStatus>
+    where
+        Provider: BlockchainTreeEngine,
+    {
+        let validator = ExecutionPayloadValidator::new(self.provider().chain_spec());
+        let block = match validator
+            .ensure_well_formed_payload_with_senders(payload, cancun_fields.into())
+        {
+            Ok(block) => block,
+            Err(error) => return Ok(PayloadStatus::from_status(error.into())),
+        };
+        let block_hash = match &block {
+            Ok(block) => block.hash(),
+            Err(block) => block.hash(),
+        };
+
+        let status = block
+            .map_err(InsertBlockError::sender_recovery_error)
+            .and_then(|block| self.provider().validate_block_without_insert(block));
+        match status {
+            Ok(BlockStatus::Valid(_)) => {
+                Ok(PayloadStatus::new(PayloadStatusEnum::Valid, Some(block_hash)))
+            }
+            Ok(BlockStatus::Disconnected { .. }) => {
+                Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
+            }
+            Err(error) if error.kind().is_invalid_block() => {
+                Ok(PayloadStatus::from_status(PayloadStatusEnum::Invalid {
+                    validation_error: error.kind().to_string(),
+                }))
+            }
+            Err(error) => Err(EthApiError::Internal(RethError::Custom(error.to_string()))),
+        }
+    }
+}
+
+/// Returns the status of the transaction if it is in the pool.
+///
+/// The transaction is pending if it is one of the best transactions of the pool, which can be
+/// included in the next block.
+fn pool_transaction_status<Pool: TransactionPool>(
+    pool: &Pool,
+    hash: &B256,
+) -> Option<TransactionStatus> {
+    let transaction = pool.get(hash)?;
+    let Some(position) = pool.best_transactions().position(|tx| tx.hash() == hash) else {
+        return Some(TransactionStatus::Queued)
+    };
+    let effective_tip =
+        transaction.effective_tip_per_gas(pool.block_info().pending_basefee).unwrap_or_default();
+    Some(TransactionStatus::Pending {
+        pool_position: U64::from(position),
+        effective_tip: U256::from(effective_tip),
+    })
+}
+
+/// Estimates the fees of a transaction to be included within `target_blocks` blocks by filling
+/// hypothetical blocks with the given transactions, in the order they would be included. The gas
+/// used by a transaction is assumed to be its gas limit.
+///
+/// The interval of the priority fee spans the `100 - confidence` and `confidence` gas weighted
+/// percentiles of the priority fees of the target block, and is at least the lowest priority fee of
+/// the target block if it is full. The interval of the max fee spans the projected base fee of the
+/// target block and the base fee if all blocks until the target block are full.
+pub(crate) fn estimate_fees<T: PoolTransaction>(
+    transactions: impl IntoIterator<Item = Arc<ValidPoolTransaction<T>>>,
+    gas_limit: u64,
+    base_fee: u64,
+    base_fee_params: BaseFeeParams,
+    confidence: f64,
+    target_blocks: u64,
+) -> FeeEstimate {
+    let mut transactions = transactions.into_iter();
+    // the transaction that did not fit into the previous block
+    let mut next = None;
+    // the gas and priority fee of the transactions of the current block
+    let mut block = Vec::new();
+    let mut gas_used = 0;
+    let mut base_fee = base_fee as u128;
+    let mut max_base_fee = base_fee;
+
+    for number in 1..=target_blocks {
+        if number > 1 {
+            base_fee = calc_next_block_base_fee(
+                gas_used as u128,
+                gas_limit as u128,
+                base_fee,
+                base_fee_params,
+            );
+            max_base_fee = calc_next_block_base_fee(
+                gas_limit as u128,
+                gas_limit as u128,
+                max_base_fee,
+                base_fee_params,
+            );
+        }
+
+        block.clear();
+        gas_used = 0;
+        while let Some(tx) = next.take().or_else(|| transactions.next()) {
+            if tx.gas_limit() > gas_limit {
+                continue
+            }
+            if gas_used + tx.gas_limit() > gas_limit {
+                next = Some(tx);
+                break
+            }
+            // skip transactions that can't pay the base fee of the block
+            let Some(priority_fee) = tx.effective_tip_per_gas(base_fee as u64) else { continue };
+            gas_used += tx.gas_limit();
+            block.push((tx.gas_limit(), priority_fee));
+        }
+    }
+
+    // a full target block has to be outbid
+    let min_priority_fee = if next.is_some() {
+        block.iter().map(|(_, priority_fee)| *priority_fee).min().unwrap_or_default()
+    } else {
+        0
+    };
+    block.sort_by_key(|(_, priority_fee)| *priority_fee);
+    let priority_fee_at =
+        |percentile| priority_fee_percentile(&block, percentile).max(min_priority_fee);
+
+    let priority_fee = priority_fee_at(confidence);
+    let opposite_priority_fee = priority_fee_at(100. - confidence);
+    let lower_priority_fee = priority_fee.min(opposite_priority_fee);
+    let upper_priority_fee = priority_fee.max(opposite_priority_fee);
+    let max_fee = base_fee + ((max_base_fee - base_fee) as f64 * confidence / 100.) as u128;
+
+    FeeEstimate {
+        target_blocks: U64::from(target_blocks),
+        confidence,
+        base_fee_per_gas: U256::from(base_fee),
+        max_fee_per_gas: U256::from(max_fee + priority_fee),
+        max_priority_fee_per_gas: U256::from(priority_fee),
+        max_fee_per_gas_interval: FeeInterval {
+            lower: U256::from(base_fee + lower_priority_fee),
+            upper: U256::from(max_base_fee + upper_priority_fee),
+        },
+        max_priority_fee_per_gas_interval: FeeInterval {
+            lower: U256::from(lower_priority_fee),
+            upper: U256::from(upper_priority_fee),
+        },
+    }
+}
+
+/// Returns the gas weighted percentile of the priority fees, sorted in ascending order.
+fn priority_fee_percentile(priority_fees: &[(u64, u128)], percentile: f64) -> u128 {
+    let total_gas: u64 = priority_fees.iter().map(|(gas, _)| gas).sum();
+    let threshold = (total_gas as f64 * percentile / 100.) as u64;
+    let mut cumulative_gas = 0;
+    for (gas, priority_fee) in priority_fees {
+        cumulative_gas += gas;
+        if cumulative_gas >= threshold {
+            return *priority_fee
+        }
+    }
+    priority_fees.last().map(|(_, priority_fee)| *priority_fee).unwrap_or_default()
+}
+
+/// Returns a page of the transactions sent from or to the address, the most recent first.
+///
+/// Returns `None` if the address transactions index is not enabled.
+pub(crate) fn address_transactions_page<Provider>(
+    provider: &Provider,
+    address: Address,
+    page: u64,
+    page_size: u64,
+) -> ProviderResult<Option<TransactionsPage>>
+where
+    Provider: BlockReader + AddressTransactionsReader,
+{
+    let offset = page.saturating_mul(page_size) as usize;
+    // fetch one more transaction to know whether there is a next page
+    let Some(mut tx_numbers) =
+        provider.address_transactions(address, offset, page_size as usize + 1)?
+    else {
+        return Ok(None)
+    };
+    let next_page = (tx_numbers.len() > page_size as usize).then(|| {
+        tx_numbers.truncate(page_size as usize);
+        page + 1
+    });
+
+    // transactions of the same block are consecutive, the block of the previous transaction is
+    // cached
+    let mut block: Option<(SealedHeader, Range<TxNumber>)> = None;
+    let mut transactions = Vec::with_capacity(tx_numbers.len());
+    for tx_number in tx_numbers {
+        if !block.as_ref().is_some_and(|(_, tx_range)| tx_range.contains(&tx_number)) {
+            let block_number = provider
+                .transaction_block(tx_number)?
+                .ok_or(ProviderError::TransactionNotFound(tx_number.into()))?;
+            let header = provider
+                .sealed_header(block_number)?
+                .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+            let tx_range = provider
+                .block_body_indices(block_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?
+                .tx_num_range();
+            block = Some((header, tx_range));
+        }
+        let (header, tx_range) = block.as_ref().expect("block is set");
+
+        let transaction = provider
+            .transaction_by_id(tx_number)?
+            .ok_or(ProviderError::TransactionNotFound(tx_number.into()))?;
+        let sender = match provider.transaction_sender(tx_number)? {
+            Some(sender) => sender,
+            None => transaction.recover_signer().ok_or(ProviderError::SenderRecoveryError)?,
+        };
+        transactions.push(from_recovered_with_block_context(
+            TransactionSignedEcRecovered::from_signed_transaction(transaction, sender),
+            header.hash(),
+            header.number,
+            header.base_fee_per_gas,
+            (tx_number - tx_range.start) as usize,
+        ));
+    }
+
+    Ok(Some(TransactionsPage { transactions, page, next_page }))
 }
 
 #[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider, Pool> RethApiServer for RethApi<Provider, Pool>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + AddressTransactionsReader
+        + ChainSpecProvider
+        + ChangeSetReader
+        + ReorgHistoryReader
+        + StateProviderFactory
+        + TokenTransfersReader
+        + BlockchainTreeEngine
+        + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -95,23 +654,235 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(RethApi::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_storeBlob`
+    async fn reth_store_blob(&self, blob: Bytes) -> RpcResult<B256> {
+        Ok(self
+            .on_blocking_task(|this| async move {
+                // computing the KZG commitment and proof is CPU bound
+                Ok(this.inner.local_blob_store.store(blob)?)
+            })
+            .await?)
+    }
+
+    /// Handler for `reth_getReorgHistory`
+    async fn reth_get_reorg_history(&self, limit: Option<u64>) -> RpcResult<Vec<ReorgRecord>> {
+        Ok(RethApi::reorg_history(self, limit).await?)
+    }
+
+    /// Handler for `reth_getTransactionsByAddress`
+    async fn reth_get_transactions_by_address(
+        &self,
+        address: Address,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> RpcResult<TransactionsPage> {
+        Ok(RethApi::transactions_by_address(self, address, page, page_size).await?)
+    }
+
+    /// Handler for `reth_getTokenTransfers`
+    async fn reth_get_token_transfers(
+        &self,
+        address: Address,
+        page: Option<u64>,
+    ) -> RpcResult<TokenTransfersPage> {
+        Ok(RethApi::token_transfers(self, address, page).await?)
+    }
+
+    /// Handler for `reth_estimateFees`
+    async fn reth_estimate_fees(
+        &self,
+        confidence: Option<f64>,
+        target_blocks: Option<u64>,
+    ) -> RpcResult<FeeEstimate> {
+        Ok(RethApi::estimate_fees(self, confidence, target_blocks).await?)
+    }
+
+    /// Handler for `reth_getTransactionReceipts`
+    async fn reth_get_transaction_receipts(
+        &self,
+        hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<AnyTransactionReceipt>>> {
+        Ok(RethApi::transaction_receipts(self, hashes).await?)
+    }
+
+    /// Handler for `reth_getTransactionStatus`
+    async fn reth_get_transaction_status(&self, hash: B256) -> RpcResult<TransactionStatus> {
+        Ok(RethApi::transaction_status(self, hash).await?)
+    }
+
+    /// Handler for `reth_getBlobByVersionedHash`
+    async fn reth_get_blob_by_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> RpcResult<Option<BlobWithProof>> {
+        Ok(RethApi::blob_by_versioned_hash(self, versioned_hash).await?)
+    }
+
+    /// Handler for `reth_validatePayload`
+    async fn reth_validate_payload(
+        &self,
+        payload: ExecutionPayload,
+        versioned_hashes: Option<Vec<B256>>,
+        parent_beacon_block_root: Option<B256>,
+    ) -> RpcResult<PayloadStatus> {
+        let cancun_fields = match (versioned_hashes, parent_beacon_block_root) {
+            (Some(versioned_hashes), Some(parent_beacon_block_root)) => {
+                Some(CancunPayloadFields { versioned_hashes, parent_beacon_block_root })
+            }
+            (None, None) => None,
+            _ => {
+                return Err(EthApiError::InvalidParams(
+                    "versioned hashes and parent beacon block root must be provided together"
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+        Ok(RethApi::validate_payload(self, payload, cancun_fields).await?)
+    }
+
+    /// Handler for `reth_subscribePreconfirmations`
+    async fn reth_subscribe_preconfirmations(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> SubscriptionResult {
+        let sink = pending.accept().await?;
+        let preconfirmations = BroadcastStream::new(self.inner.preconfirmations.subscribe());
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            pipe_preconfirmations(sink, preconfirmations).await;
+        }));
+        Ok(())
+    }
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+impl<Provider, Pool> std::fmt::Debug for RethApi<Provider, Pool> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider, Pool> Clone for RethApi<Provider, Pool> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider> {
+struct RethApiInner<Provider, Pool> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// The transaction pool of the node.
+    pool: Pool,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// Blobs that can be referenced by blob transactions signed by the node.
+    local_blob_store: LocalBlobStore,
+    /// Blobs of canonical blob transactions whose sidecars are still in the blob store.
+    blob_sidecar_index: BlobSidecarIndex,
+    /// Preconfirmations of the transactions selected into the payloads built by the node.
+    preconfirmations: Preconfirmations,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction, MockTransactionFactory},
+        TransactionOrigin,
+    };
+
+    #[tokio::test]
+    async fn pool_transaction_statuses() {
+        let pool = testing_pool();
+        let pending = MockTransaction::eip1559();
+        // nonce gap
+        let queued = MockTransaction::eip1559().with_nonce(1);
+        pool.add_transaction(TransactionOrigin::External, pending.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, queued.clone()).await.unwrap();
+
+        assert!(matches!(
+            pool_transaction_status(&pool, pending.hash()),
+            Some(TransactionStatus::Pending { pool_position, .. }) if pool_position == U64::ZERO
+        ));
+        assert_eq!(pool_transaction_status(&pool, queued.hash()), Some(TransactionStatus::Queued));
+        assert_eq!(pool_transaction_status(&pool, &B256::ZERO), None);
+    }
+
+    #[tokio::test]
+    async fn reorg_history_is_limited() {
+        let provider = MockEthProvider::default();
+        let reorgs = (0..(MAX_REORG_HISTORY_LIMIT + 10))
+            .map(|fork_block| ReorgRecord { fork_block, ..Default::default() })
+            .collect::<Vec<_>>();
+        for reorg in &reorgs {
+            provider.add_reorg(reorg.clone());
+        }
+        let api = RethApi::new(
+            provider,
+            testing_pool(),
+            Box::<TokioTaskExecutor>::default(),
+            LocalBlobStore::default(),
+            BlobSidecarIndex::default(),
+            Preconfirmations::default(),
+        );
+
+        // newest first
+        let newest = reorgs.iter().rev().cloned();
+        assert_eq!(
+            api.reorg_history(Some(3)).await.unwrap(),
+            newest.clone().take(3).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            api.reorg_history(None).await.unwrap(),
+            newest.clone().take(DEFAULT_REORG_HISTORY_LIMIT as usize).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            api.reorg_history(Some(u64::MAX)).await.unwrap(),
+            newest.take(MAX_REORG_HISTORY_LIMIT as usize).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn estimate_fees_from_hypothetical_blocks() {
+        let mut factory = MockTransactionFactory::default();
+        // ten transactions with priority fees from 10 to 1 gwei, five fit into a block
+        let transactions = (1..=10u128)
+            .rev()
+            .map(|priority_fee| {
+                factory.validated_arc(
+                    MockTransaction::eip1559()
+                        .with_gas_limit(100_000)
+                        .with_max_fee(100_000_000_000)
+                        .with_priority_fee(priority_fee * 1_000_000_000),
+                )
+            })
+            .collect::<Vec<_>>();
+        let base_fee = 1_000_000_000;
+        let params = BaseFeeParams::ethereum();
+
+        // the first block is full and has to be outbid
+        let estimate = estimate_fees(transactions.clone(), 500_000, base_fee, params, 90., 1);
+        assert_eq!(estimate.base_fee_per_gas, U256::from(base_fee));
+        assert_eq!(estimate.max_priority_fee_per_gas, U256::from(10_000_000_000u64));
+        assert_eq!(
+            estimate.max_priority_fee_per_gas_interval,
+            FeeInterval {
+                lower: U256::from(6_000_000_000u64),
+                upper: U256::from(10_000_000_000u64)
+            }
+        );
+        assert_eq!(estimate.max_fee_per_gas, U256::from(base_fee + 10_000_000_000));
+
+        // the second block is not full, its base fee increases after the full first block
+        let estimate = estimate_fees(transactions, 500_000, base_fee, params, 50., 2);
+        let next_base_fee = calc_next_block_base_fee(500_000, 500_000, base_fee as u128, params);
+        assert_eq!(estimate.base_fee_per_gas, U256::from(next_base_fee));
+        assert_eq!(estimate.max_priority_fee_per_gas, U256::from(3_000_000_000u64));
+        assert_eq!(
+            estimate.max_fee_per_gas_interval.upper,
+            U256::from(next_base_fee + 3_000_000_000)
+        );
+    }
 }
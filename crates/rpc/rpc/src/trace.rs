@@ -1,16 +1,24 @@
-use crate::eth::{
-    error::{EthApiError, EthResult},
-    revm_utils::{prepare_call_env, EvmOverrides},
-    utils::recover_raw_transaction,
-    EthTransactions,
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult},
+        revm_utils::{prepare_call_env, EvmOverrides},
+        utils::recover_raw_transaction,
+        EthTransactions,
+    },
+    trace_cache::{ParityTraceCache, ParityTraceKind},
 };
 use async_trait::async_trait;
+use futures::{stream, StreamExt};
 use jsonrpsee::core::RpcResult as Result;
 use reth_consensus_common::calc::{base_block_reward, block_reward};
 use reth_primitives::{
-    revm::env::tx_env_with_recovered, BlockId, BlockNumberOrTag, Bytes, SealedHeader, B256, U256,
+    revm::env::tx_env_with_recovered, BlockId, BlockNumber, BlockNumberOrTag, Bytes, SealedHeader,
+    B256, U256,
+};
+use reth_provider::{
+    AddressTransactionsReader, BlockReader, ChainSpecProvider, EvmEnvProvider, ProviderError,
+    StateProviderFactory, TransactionVariant, TransactionsProvider,
 };
-use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_revm::{
     database::StateProviderDatabase,
     tracing::{parity::populate_state_diff, TracingInspector, TracingInspectorConfig},
@@ -19,12 +27,12 @@ use reth_rpc_api::TraceApiServer;
 use reth_rpc_types::{
     state::StateOverride,
     trace::{
-        filter::TraceFilter,
+        filter::{TraceFilter, TraceFilterMatcher, TraceFilterMode},
         opcode::{BlockOpcodeGas, TransactionOpcodeGas},
         parity::*,
         tracerequest::TraceCallRequest,
     },
-    BlockError, BlockOverrides, Index, TransactionRequest,
+    BlockOverrides, Index, TransactionRequest,
 };
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
@@ -32,9 +40,45 @@ use revm::{
     primitives::EnvWithHandlerCfg,
 };
 use revm_inspectors::opcode::OpcodeGasInspector;
-use std::{collections::HashSet, sync::Arc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeSet, HashSet},
+    ops::RangeInclusive,
+    sync::Arc,
+};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
+/// Default maximum number of blocks that are traced by a `trace_filter` request.
+pub const DEFAULT_TRACE_FILTER_MAX_BLOCKS: u64 = 100;
+
+/// Default maximum number of traces that are returned by a `trace_filter` request.
+pub const DEFAULT_TRACE_FILTER_MAX_TRACES: usize = 10_000;
+
+/// Number of blocks that are traced concurrently by a `trace_filter` request.
+const TRACE_FILTER_CONCURRENCY: usize = 4;
+
+/// Limits of `trace_filter` requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceFilterConfig {
+    /// Maximum number of blocks that are traced.
+    ///
+    /// Without the address transactions index this limits the block range of the filter, with
+    /// the index it limits the number of blocks that contain transactions of the addresses.
+    pub max_blocks: u64,
+    /// Maximum number of traces that are returned, larger results have to be paginated with
+    /// `after` and `count`.
+    pub max_traces: usize,
+}
+
+impl Default for TraceFilterConfig {
+    fn default() -> Self {
+        Self {
+            max_blocks: DEFAULT_TRACE_FILTER_MAX_BLOCKS,
+            max_traces: DEFAULT_TRACE_FILTER_MAX_TRACES,
+        }
+    }
+}
+
 /// `trace` API implementation.
 ///
 /// This type provides the functionality for handling `trace` related requests.
@@ -51,8 +95,20 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     }
 
     /// Create a new instance of the [TraceApi]
-    pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        trace_cache: ParityTraceCache,
+        trace_filter_config: TraceFilterConfig,
+    ) -> Self {
+        let inner = Arc::new(TraceApiInner {
+            provider,
+            eth_api,
+            blocking_task_guard,
+            trace_cache,
+            trace_filter_config,
+        });
         Self { inner }
     }
 
@@ -73,7 +129,12 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
 
 impl<Provider, Eth> TraceApi<Provider, Eth>
 where
-    Provider: BlockReader + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
+    Provider: BlockReader
+        + AddressTransactionsReader
+        + StateProviderFactory
+        + EvmEnvProvider
+        + ChainSpecProvider
+        + 'static,
     Eth: EthTransactions + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
@@ -240,59 +301,106 @@ where
     ///
     /// This is similar to [Self::trace_block] but only returns traces for transactions that match
     /// the filter.
+    ///
+    /// Only blocks with transactions that match the from and to addresses of the filter are
+    /// traced, which are looked up in the address transactions index if it is enabled. The traces
+    /// are paginated with `after` and `count` and capped at [TraceFilterConfig::max_traces].
     pub async fn trace_filter(
         &self,
         filter: TraceFilter,
     ) -> EthResult<Vec<LocalizedTransactionTrace>> {
-        let matcher = filter.matcher();
-        let TraceFilter { from_block, to_block, after: _after, count: _count, .. } = filter;
-        let start = from_block.unwrap_or(0);
-        let end = if let Some(to_block) = to_block {
-            to_block
-        } else {
-            self.provider().best_block_number()?
+        let config = self.inner.trace_filter_config;
+        let best_block = self.provider().best_block_number()?;
+        let start = filter.from_block.unwrap_or(0);
+        let end = filter.to_block.map_or(best_block, |to_block| to_block.min(best_block));
+
+        let blocks = match self.trace_filter_indexed_blocks(&filter, start..=end)? {
+            Some(blocks) => {
+                if blocks.len() as u64 > config.max_blocks {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "Filter matches transactions in more than {} blocks",
+                        config.max_blocks
+                    )))
+                }
+                blocks
+            }
+            None => {
+                // ensure that the range is not too large, since we need to fetch all blocks in
+                // the range
+                let distance = end.saturating_sub(start);
+                if distance > config.max_blocks {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "Block range too large; currently limited to {} blocks",
+                        config.max_blocks
+                    )))
+                }
+                (start..=end).collect()
+            }
         };
 
-        // ensure that the range is not too large, since we need to fetch all blocks in the range
-        let distance = end.saturating_sub(start);
-        if distance > 100 {
-            return Err(EthApiError::InvalidParams(
-                "Block range too large; currently limited to 100 blocks".to_string(),
-            ))
+        // blocks are traced until the requested page is complete, one trace more than the cap is
+        // collected to detect results that exceed it
+        let after = filter.after.unwrap_or(0) as usize;
+        let count = filter.count.map_or(usize::MAX, |count| count as usize);
+        let needed = after.saturating_add(count.min(config.max_traces.saturating_add(1)));
+
+        let matcher = filter.matcher();
+        let mut block_traces = stream::iter(blocks)
+            .map(|number| self.trace_filter_block(number, &matcher))
+            .buffered(TRACE_FILTER_CONCURRENCY);
+        let mut traces = Vec::new();
+        while traces.len() < needed {
+            let Some(block) = block_traces.next().await else { break };
+            traces.extend(block?);
         }
 
-        // fetch all blocks in that range
-        let blocks = self.provider().block_range(start..=end)?;
-
-        // find relevant blocks to trace
-        let mut target_blocks = Vec::new();
-        for block in blocks {
-            let mut transaction_indices = HashSet::new();
-            let mut highest_matching_index = 0;
-            for (tx_idx, tx) in block.body.iter().enumerate() {
-                let from = tx.recover_signer().ok_or(BlockError::InvalidSignature)?;
-                let to = tx.to();
-                if matcher.matches(from, to) {
-                    let idx = tx_idx as u64;
-                    transaction_indices.insert(idx);
-                    highest_matching_index = idx;
-                }
-            }
-            if !transaction_indices.is_empty() {
-                target_blocks.push((block.number, transaction_indices, highest_matching_index));
+        let mut traces = traces.into_iter().skip(after).collect::<Vec<_>>();
+        if traces.len() > config.max_traces {
+            return Err(EthApiError::InvalidParams(format!(
+                "Filter matches more than {} traces; use `after` and `count` to paginate",
+                config.max_traces
+            )))
+        }
+        traces.truncate(count);
+        Ok(traces)
+    }
+
+    /// Returns the traces of the transactions of the block that match the filter.
+    async fn trace_filter_block(
+        &self,
+        number: BlockNumber,
+        matcher: &TraceFilterMatcher,
+    ) -> EthResult<Vec<LocalizedTransactionTrace>> {
+        let Some(block) =
+            self.provider().block_with_senders(number.into(), TransactionVariant::NoHash)?
+        else {
+            return Ok(Vec::new())
+        };
+
+        // find relevant transactions to trace
+        let mut transaction_indices = HashSet::new();
+        let mut highest_matching_index = 0;
+        for (tx_idx, (tx, from)) in block.body.iter().zip(&block.senders).enumerate() {
+            if matcher.matches(*from, tx.to()) {
+                let idx = tx_idx as u64;
+                transaction_indices.insert(idx);
+                highest_matching_index = idx;
             }
         }
+        if transaction_indices.is_empty() {
+            return Ok(Vec::new())
+        }
 
-        // trace all relevant blocks
-        let mut block_traces = Vec::with_capacity(target_blocks.len());
-        for (num, indices, highest_idx) in target_blocks {
-            let traces = self.inner.eth_api.trace_block_until(
-                num.into(),
-                Some(highest_idx),
+        let traces = self
+            .inner
+            .eth_api
+            .trace_block_until(
+                number.into(),
+                Some(highest_matching_index),
                 TracingInspectorConfig::default_parity(),
                 move |tx_info, inspector, res, _, _| {
                     if let Some(idx) = tx_info.index {
-                        if !indices.contains(&idx) {
+                        if !transaction_indices.contains(&idx) {
                             // only record traces for relevant transactions
                             return Ok(None)
                         }
@@ -303,18 +411,68 @@ where
                         .into_localized_transaction_traces(tx_info);
                     Ok(Some(traces))
                 },
-            );
-            block_traces.push(traces);
+            )
+            .await?;
+        Ok(traces.into_iter().flatten().flatten().flatten().collect())
+    }
+
+    /// Returns the blocks of the range that contain transactions sent from or to the addresses of
+    /// the filter, from the address transactions index.
+    ///
+    /// Returns `None` if the filter matches transactions of any address or the index is not
+    /// enabled.
+    fn trace_filter_indexed_blocks(
+        &self,
+        filter: &TraceFilter,
+        range: RangeInclusive<BlockNumber>,
+    ) -> EthResult<Option<Vec<BlockNumber>>> {
+        // an empty address list matches any address
+        let (from, to) = (&filter.from_address, &filter.to_address);
+        let restricted = match filter.mode {
+            TraceFilterMode::Union => !from.is_empty() && !to.is_empty(),
+            TraceFilterMode::Intersection => !from.is_empty() || !to.is_empty(),
+        };
+        if !restricted || range.is_empty() {
+            return Ok(None)
         }
 
-        let block_traces = futures::future::try_join_all(block_traces).await?;
-        let all_traces = block_traces
-            .into_iter()
-            .flatten()
-            .flat_map(|traces| traces.into_iter().flatten().flat_map(|traces| traces.into_iter()))
-            .collect();
+        let provider = self.provider();
+        let first = provider.block_body_indices(*range.start())?;
+        let last = provider.block_body_indices(*range.end())?;
+        let (Some(first), Some(last)) = (first, last) else { return Ok(None) };
+        let tx_range = first.first_tx_num()..last.next_tx_num();
+
+        let mut tx_numbers = BTreeSet::new();
+        for address in from.iter().chain(to) {
+            let Some(numbers) = provider.address_transactions_by_range(
+                *address,
+                tx_range.clone(),
+                false,
+                usize::MAX,
+            )?
+            else {
+                return Ok(None)
+            };
+            tx_numbers.extend(numbers);
+        }
 
-        Ok(all_traces)
+        // transactions of the same block are consecutive, each block is looked up once
+        let mut blocks = Vec::new();
+        let mut block_tx_range = 0..0;
+        for tx_number in tx_numbers {
+            if block_tx_range.contains(&tx_number) {
+                continue
+            }
+            let number = provider
+                .transaction_block(tx_number)?
+                .ok_or(ProviderError::TransactionNotFound(tx_number.into()))?;
+            block_tx_range = provider
+                .block_body_indices(number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?
+                .tx_num_range();
+            blocks.push(number);
+        }
+        Ok(Some(blocks))
     }
 
     /// Returns all traces for the given transaction hash
@@ -322,7 +480,15 @@ where
         &self,
         hash: B256,
     ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
-        self.inner
+        let kind = ParityTraceKind::Transaction(hash);
+        if let Some((_, meta)) = self.provider().transaction_by_hash_with_meta(hash)? {
+            if let Some(traces) = self.inner.trace_cache.get(meta.block_hash, kind) {
+                return Ok(Some(traces))
+            }
+        }
+
+        let traces = self
+            .inner
             .eth_api
             .spawn_trace_transaction_in_block(
                 hash,
@@ -335,7 +501,15 @@ where
                     Ok(traces)
                 },
             )
-            .await
+            .await?;
+
+        // the traces are cached by the block the transaction was actually traced in
+        if let Some(traces) = &traces {
+            if let Some(block_hash) = traces.first().and_then(|trace| trace.block_hash) {
+                self.inner.trace_cache.insert(block_hash, kind, traces.clone());
+            }
+        }
+        Ok(traces)
     }
 
     /// Returns traces created at given block.
@@ -343,8 +517,19 @@ where
         &self,
         block_id: BlockId,
     ) -> EthResult<Option<Vec<LocalizedTransactionTrace>>> {
+        let Some(block) = self.inner.eth_api.block_by_id(block_id).await? else { return Ok(None) };
+
+        // the pending block can change and is not cached
+        let cached_hash =
+            (!matches!(block_id, BlockId::Number(BlockNumberOrTag::Pending))).then(|| block.hash());
+        if let Some(hash) = cached_hash {
+            if let Some(traces) = self.inner.trace_cache.get(hash, ParityTraceKind::Block) {
+                return Ok(Some(traces))
+            }
+        }
+
         let traces = self.inner.eth_api.trace_block_with(
-            block_id,
+            cached_hash.map_or(block_id, Into::into),
             TracingInspectorConfig::default_parity(),
             |tx_info, inspector, res, _, _| {
                 let traces = inspector
@@ -355,13 +540,10 @@ where
             },
         );
 
-        let block = self.inner.eth_api.block_by_id(block_id);
-        let (maybe_traces, maybe_block) = futures::try_join!(traces, block)?;
-
         let mut maybe_traces =
-            maybe_traces.map(|traces| traces.into_iter().flatten().collect::<Vec<_>>());
+            traces.await?.map(|traces| traces.into_iter().flatten().collect::<Vec<_>>());
 
-        if let (Some(block), Some(traces)) = (maybe_block, maybe_traces.as_mut()) {
+        if let Some(traces) = maybe_traces.as_mut() {
             if let Some(header_td) = self.provider().header_td(&block.header.hash())? {
                 if let Some(base_block_reward) = base_block_reward(
                     self.provider().chain_spec().as_ref(),
@@ -395,6 +577,9 @@ where
             }
         }
 
+        if let (Some(hash), Some(traces)) = (cached_hash, maybe_traces.as_ref()) {
+            self.inner.trace_cache.insert(hash, ParityTraceKind::Block, traces.clone());
+        }
         Ok(maybe_traces)
     }
 
@@ -490,7 +675,12 @@ where
 #[async_trait]
 impl<Provider, Eth> TraceApiServer for TraceApi<Provider, Eth>
 where
-    Provider: BlockReader + StateProviderFactory + EvmEnvProvider + ChainSpecProvider + 'static,
+    Provider: BlockReader
+        + AddressTransactionsReader
+        + StateProviderFactory
+        + EvmEnvProvider
+        + ChainSpecProvider
+        + 'static,
     Eth: EthTransactions + 'static,
 {
     /// Executes the given call and returns a number of possible traces for it.
@@ -624,6 +814,10 @@ struct TraceApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to `trace_*`
     blocking_task_guard: BlockingTaskGuard,
+    /// Cache of `trace_block` and `trace_transaction` results
+    trace_cache: ParityTraceCache,
+    /// Limits of `trace_filter` requests
+    trace_filter_config: TraceFilterConfig,
 }
 
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
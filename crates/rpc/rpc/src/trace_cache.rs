@@ -0,0 +1,217 @@
+//! Cache of parity traces of canonical blocks.
+
+use parking_lot::Mutex;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use reth_primitives::B256;
+use reth_rpc_types::trace::parity::{
+    Action, LocalizedTransactionTrace, TraceOutput, TransactionTrace,
+};
+use schnellru::{LruMap, Unlimited};
+use std::{fmt, mem::size_of, sync::Arc};
+
+/// Default maximum size of the parity trace cache in megabytes.
+pub const DEFAULT_TRACE_CACHE_SIZE_MB: usize = 64;
+
+/// The traces of a block that are cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ParityTraceKind {
+    /// The traces of all transactions of the block and the block rewards, see `trace_block`.
+    Block,
+    /// The traces of a single transaction of the block, see `trace_transaction`.
+    Transaction(B256),
+}
+
+/// A memory bounded LRU cache of parity traces, keyed by the hash of the traced block and the kind
+/// of traces.
+///
+/// Keying by block hash makes the cached traces immune to reorgs. The size of an entry is
+/// estimated from the traces it contains, the least recently used entries are evicted once the
+/// estimated size of all entries exceeds the configured maximum.
+#[derive(Clone)]
+pub struct ParityTraceCache {
+    inner: Option<Arc<Mutex<ParityTraceCacheInner>>>,
+    metrics: ParityTraceCacheMetrics,
+}
+
+impl ParityTraceCache {
+    /// Creates a new cache with the given maximum size in bytes.
+    ///
+    /// Nothing is cached if the size is 0.
+    pub fn new(max_size: usize) -> Self {
+        let inner = (max_size > 0).then(|| {
+            Arc::new(Mutex::new(ParityTraceCacheInner {
+                traces: LruMap::new(Unlimited),
+                size: 0,
+                max_size,
+            }))
+        });
+        Self { inner, metrics: Default::default() }
+    }
+
+    /// Returns the cached traces of the block, if any.
+    pub fn get(
+        &self,
+        block_hash: B256,
+        kind: ParityTraceKind,
+    ) -> Option<Vec<LocalizedTransactionTrace>> {
+        let inner = self.inner.as_ref()?;
+        let traces = inner.lock().traces.get(&(block_hash, kind)).map(|entry| entry.traces.clone());
+        if traces.is_some() {
+            self.metrics.hits.increment(1);
+        } else {
+            self.metrics.misses.increment(1);
+        }
+        traces.map(|traces| traces.as_ref().clone())
+    }
+
+    /// Caches the traces of the block.
+    ///
+    /// Traces that are larger than the maximum size of the cache are not cached.
+    pub fn insert(
+        &self,
+        block_hash: B256,
+        kind: ParityTraceKind,
+        traces: Vec<LocalizedTransactionTrace>,
+    ) {
+        let Some(inner) = self.inner.as_ref() else { return };
+        let size = traces.iter().map(trace_size).sum::<usize>();
+
+        let mut inner = inner.lock();
+        if size > inner.max_size {
+            return
+        }
+        let entry = CachedTraces { traces: Arc::new(traces), size };
+        if let Some(previous) = inner.traces.peek(&(block_hash, kind)).map(|entry| entry.size) {
+            inner.size -= previous;
+        }
+        inner.traces.insert((block_hash, kind), entry);
+        inner.size += size;
+        while inner.size > inner.max_size {
+            let Some((_, evicted)) = inner.traces.pop_oldest() else { break };
+            inner.size -= evicted.size;
+        }
+
+        self.metrics.cached_count.set(inner.traces.len() as f64);
+        self.metrics.cached_bytes.set(inner.size as f64);
+    }
+}
+
+impl fmt::Debug for ParityTraceCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = f.debug_struct("ParityTraceCache");
+        if let Some(inner) = self.inner.as_ref() {
+            let inner = inner.lock();
+            s.field("len", &inner.traces.len())
+                .field("size", &inner.size)
+                .field("max_size", &inner.max_size);
+        }
+        s.finish_non_exhaustive()
+    }
+}
+
+struct ParityTraceCacheInner {
+    traces: LruMap<(B256, ParityTraceKind), CachedTraces, Unlimited>,
+    /// The estimated size of all cached traces in bytes.
+    size: usize,
+    /// The maximum estimated size of all cached traces in bytes.
+    max_size: usize,
+}
+
+struct CachedTraces {
+    traces: Arc<Vec<LocalizedTransactionTrace>>,
+    /// The estimated size of the traces in bytes.
+    size: usize,
+}
+
+/// Returns the estimated heap and stack size of the trace in bytes.
+fn trace_size(trace: &LocalizedTransactionTrace) -> usize {
+    let TransactionTrace { action, result, trace_address, .. } = &trace.trace;
+    let action = match action {
+        Action::Call(call) => call.input.len(),
+        Action::Create(create) => create.init.len(),
+        Action::Selfdestruct(_) | Action::Reward(_) => 0,
+    };
+    let result = match result {
+        Some(TraceOutput::Call(call)) => call.output.len(),
+        Some(TraceOutput::Create(create)) => create.code.len(),
+        None => 0,
+    };
+    size_of::<LocalizedTransactionTrace>() +
+        action +
+        result +
+        trace_address.len() * size_of::<usize>()
+}
+
+/// Metrics for the parity trace cache.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "rpc.trace_cache")]
+struct ParityTraceCacheMetrics {
+    /// The number of cached blocks and transactions.
+    cached_count: Gauge,
+    /// The estimated size of the cached traces in bytes.
+    cached_bytes: Gauge,
+    /// The number of cache hits.
+    hits: Counter,
+    /// The number of cache misses.
+    misses: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::trace::parity::{RewardAction, RewardType};
+
+    fn traces(count: usize) -> Vec<LocalizedTransactionTrace> {
+        let trace = LocalizedTransactionTrace {
+            trace: TransactionTrace {
+                action: Action::Reward(RewardAction {
+                    author: Default::default(),
+                    reward_type: RewardType::Block,
+                    value: Default::default(),
+                }),
+                error: None,
+                result: None,
+                subtraces: 0,
+                trace_address: vec![],
+            },
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_position: None,
+        };
+        vec![trace; count]
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let entry_size = traces(10).iter().map(trace_size).sum::<usize>();
+        let cache = ParityTraceCache::new(entry_size * 2);
+        let (a, b, c) = (B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3));
+
+        cache.insert(a, ParityTraceKind::Block, traces(10));
+        cache.insert(b, ParityTraceKind::Block, traces(10));
+        assert_eq!(cache.get(a, ParityTraceKind::Block).map(|traces| traces.len()), Some(10));
+        assert!(cache.get(a, ParityTraceKind::Transaction(B256::ZERO)).is_none());
+
+        // `b` is the least recently used entry
+        cache.insert(c, ParityTraceKind::Block, traces(10));
+        assert!(cache.get(a, ParityTraceKind::Block).is_some());
+        assert!(cache.get(b, ParityTraceKind::Block).is_none());
+        assert!(cache.get(c, ParityTraceKind::Block).is_some());
+
+        // traces larger than the cache are not cached
+        cache.insert(b, ParityTraceKind::Block, traces(100));
+        assert!(cache.get(b, ParityTraceKind::Block).is_none());
+        assert!(cache.get(c, ParityTraceKind::Block).is_some());
+    }
+
+    #[test]
+    fn disabled_cache() {
+        let cache = ParityTraceCache::new(0);
+        cache.insert(B256::ZERO, ParityTraceKind::Block, traces(1));
+        assert!(cache.get(B256::ZERO, ParityTraceKind::Block).is_none());
+    }
+}
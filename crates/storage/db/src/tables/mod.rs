@@ -29,7 +29,7 @@ use crate::{
     tables::{
         codecs::CompactU256,
         models::{
-            accounts::{AccountBeforeTx, BlockNumberAddress},
+            accounts::{AccountBeforeTx, AddressNonce, BlockNumberAddress},
             blocks::{HeaderHash, StoredBlockOmmers},
             client_version::ClientVersion,
             storage_sharded_key::StorageShardedKey,
@@ -41,7 +41,8 @@ use reth_primitives::{
     stage::StageCheckpoint,
     trie::{StorageTrieEntry, StoredBranchNode, StoredNibbles, StoredNibblesSubKey},
     Account, Address, BlockHash, BlockNumber, Bytecode, Header, IntegerList, PruneCheckpoint,
-    PruneSegment, Receipt, StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
+    PruneSegment, Receipt, ReorgRecord, StorageEntry, TokenTransfer, TransactionSignedNoHash,
+    TxHash, TxNumber, B256,
 };
 use std::fmt;
 
@@ -333,6 +334,15 @@ tables! {
     /// Code example can be found in `reth_provider::HistoricalStateProviderRef`
     table StoragesHistory<Key = StorageShardedKey, Value = BlockNumberList>;
 
+    /// Stores pointers to the transactions sent from or to each address, including the contracts
+    /// created by a transaction.
+    ///
+    /// The index is sharded by transaction number the same way as [`AccountsHistory`] is sharded
+    /// by block number, the last shard of an address is keyed by `u64::MAX`.
+    ///
+    /// Only populated if the built-in address transactions ExEx is enabled.
+    table AddressTransactions<Key = ShardedKey<Address>, Value = TransactionNumberList>;
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
@@ -377,16 +387,62 @@ tables! {
 
     /// Stores the history of client versions that have accessed the database with write privileges by unix timestamp in seconds.
     table VersionHistory<Key = u64, Value = ClientVersion>;
+
+    /// Stores the reorgs of the canonical chain processed by the node, by sequence number.
+    table ReorgHistory<Key = u64, Value = ReorgRecord>;
+
+    /// Stores the ERC-20 and ERC-721 token transfers of the canonical chain, by transfer number.
+    ///
+    /// Only populated if the optional token transfers ExEx is enabled.
+    table TokenTransfers<Key = TransferNumber, Value = TokenTransfer>;
+
+    /// Stores pointers to the token transfers sent from or to each address.
+    ///
+    /// The index is sharded by transfer number the same way as [`AccountsHistory`] is sharded by
+    /// block number, the last shard of an address is keyed by `u64::MAX`.
+    table AddressTokenTransfers<Key = ShardedKey<Address>, Value = TransferNumberList>;
+
+    /// Stores the hash of the canonical transaction of each sender and nonce.
+    ///
+    /// Only populated if the optional sender nonces ExEx is enabled.
+    table SenderNonceTransactions<Key = AddressNonce, Value = TxHash>;
+
+    /// Stores the blocks with logs emitted by each address.
+    ///
+    /// Sharded by block number the same way as [`AccountsHistory`], the last shard of an address
+    /// is keyed by `u64::MAX`. Only populated if the optional log index ExEx is enabled.
+    table LogAddressBlocks<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores the blocks with logs whose first topic is the key, e.g. the event signature.
+    ///
+    /// Sharded the same way as [`LogAddressBlocks`]. Only populated if the optional log index
+    /// ExEx is enabled.
+    table LogTopicBlocks<Key = ShardedKey<B256>, Value = BlockNumberList>;
+
+    /// Stores the highest block processed by each built-in execution extension.
+    table ExExCheckpoints<Key = ExExId, Value = BlockNumber>;
 }
 
 // Alias types.
 
-/// List with transaction numbers.
+/// List with block numbers.
 pub type BlockNumberList = IntegerList;
 
+/// List with transaction numbers.
+pub type TransactionNumberList = IntegerList;
+
+/// List with token transfer numbers.
+pub type TransferNumberList = IntegerList;
+
+/// The number of a token transfer in the order of the canonical chain.
+pub type TransferNumber = u64;
+
 /// Encoded stage id.
 pub type StageId = String;
 
+/// Id of an execution extension.
+pub type ExExId = String;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,7 +154,47 @@ impl Decode for AddressStorageKey {
     }
 }
 
-impl_fixed_arbitrary!((BlockNumberAddress, 28), (AddressStorageKey, 52));
+/// [`Address`] concatenated with a nonce. Used as the key for
+/// [`SenderNonceTransactions`](crate::tables::SenderNonceTransactions)
+///
+/// Since it's used as a key, it isn't compressed when encoding it.
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Ord, PartialOrd, Hash,
+)]
+pub struct AddressNonce(pub (Address, u64));
+
+impl From<(Address, u64)> for AddressNonce {
+    fn from(tpl: (Address, u64)) -> Self {
+        AddressNonce(tpl)
+    }
+}
+
+impl Encode for AddressNonce {
+    type Encoded = [u8; 28];
+
+    fn encode(self) -> Self::Encoded {
+        let address = self.0 .0;
+        let nonce = self.0 .1;
+
+        let mut buf = [0u8; 28];
+
+        buf[..20].copy_from_slice(address.as_slice());
+        buf[20..].copy_from_slice(&nonce.to_be_bytes());
+        buf
+    }
+}
+
+impl Decode for AddressNonce {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        let value = value.as_ref();
+        let address = Address::from_slice(&value[..20]);
+        let nonce = u64::from_be_bytes(value[20..].try_into().map_err(|_| DatabaseError::Decode)?);
+
+        Ok(AddressNonce((address, nonce)))
+    }
+}
+
+impl_fixed_arbitrary!((BlockNumberAddress, 28), (AddressStorageKey, 52), (AddressNonce, 28));
 
 #[cfg(test)]
 mod tests {
@@ -211,4 +251,20 @@ mod tests {
         let key = AddressStorageKey::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
         assert_eq!(bytes, Encode::encode(key));
     }
+
+    #[test]
+    fn test_address_nonce() {
+        let address = Address::from_str("ba5e000000000000000000000000000000000000").unwrap();
+        let key = AddressNonce((address, 7));
+
+        let mut bytes = [0u8; 28];
+        bytes[..20].copy_from_slice(address.as_slice());
+        bytes[20..].copy_from_slice(&7u64.to_be_bytes());
+
+        let encoded = Encode::encode(key);
+        assert_eq!(encoded, bytes);
+
+        let decoded: AddressNonce = Decode::decode(encoded).unwrap();
+        assert_eq!(decoded, key);
+    }
 }
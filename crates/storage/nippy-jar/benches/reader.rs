@@ -0,0 +1,65 @@
+#![allow(missing_docs)]
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{rngs::SmallRng, Rng, RngCore, SeedableRng};
+use reth_nippy_jar::{
+    ColumnResult, DataReaderMode, MmapAdvice, NippyJar, NippyJarCursor, DIRECT_IO_BLOCK_SIZE,
+};
+use std::sync::Arc;
+
+const ROWS: usize = 100_000;
+const VALUE_SIZE: usize = 128;
+
+/// Compares random and sequential row reads across the available [`DataReaderMode`]s.
+pub fn reader_modes(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("jar");
+    create_jar(&path);
+
+    let jar = NippyJar::load_without_header(&path).unwrap();
+    let modes = [
+        ("mmap", DataReaderMode::Mmap(MmapAdvice::Normal)),
+        ("mmap-random", DataReaderMode::Mmap(MmapAdvice::Random)),
+        ("direct-small-cache", DataReaderMode::direct(DIRECT_IO_BLOCK_SIZE * 64)),
+        ("direct-full-cache", DataReaderMode::direct(ROWS * VALUE_SIZE * 2)),
+    ];
+
+    let mut group = c.benchmark_group("NippyJar reader");
+    for (name, mode) in modes {
+        let reader = Arc::new(jar.open_data_reader_with_mode(mode).unwrap());
+
+        group.bench_function(BenchmarkId::new("random", name), |b| {
+            let mut cursor = NippyJarCursor::with_reader(&jar, reader.clone()).unwrap();
+            let mut rng = SmallRng::seed_from_u64(1);
+            b.iter(|| {
+                let row = rng.gen_range(0..ROWS);
+                black_box(cursor.row_by_number(row).unwrap().unwrap().len());
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("sequential", name), |b| {
+            let mut cursor = NippyJarCursor::with_reader(&jar, reader.clone()).unwrap();
+            b.iter(|| {
+                if cursor.next_row().unwrap().is_none() {
+                    cursor.reset();
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn create_jar(path: &std::path::Path) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let column = (0..ROWS)
+        .map(|_| {
+            let mut value = vec![0; VALUE_SIZE];
+            rng.fill_bytes(&mut value);
+            Ok(value)
+        })
+        .collect::<Vec<ColumnResult<Vec<u8>>>>();
+
+    NippyJar::new_without_header(1, path).freeze(vec![column], ROWS as u64).unwrap();
+}
+
+criterion_group!(benches, reader_modes);
+criterion_main!(benches);
@@ -123,7 +123,9 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
         Ok(Some(
             row.into_iter()
                 .map(|v| match v {
-                    ValueRange::Mmap(range) => self.reader.data(range),
+                    ValueRange::Mmap(range) => {
+                        self.reader.mmap_data(range).expect("only created for mmap readers")
+                    }
                     ValueRange::Internal(range) => &self.internal_buffer[range],
                 })
                 .collect(),
@@ -198,7 +200,9 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
         Ok(Some(
             row.into_iter()
                 .map(|v| match v {
-                    ValueRange::Mmap(range) => self.reader.data(range),
+                    ValueRange::Mmap(range) => {
+                        self.reader.mmap_data(range).expect("only created for mmap readers")
+                    }
                     ValueRange::Internal(range) => &self.internal_buffer[range],
                 })
                 .collect(),
@@ -213,13 +217,13 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
     ) -> Result<(), NippyJarError> {
         // Find out the offset of the column value
         let offset_pos = self.row as usize * self.jar.columns + column;
-        let value_offset = self.reader.offset(offset_pos) as usize;
+        let value_offset = self.reader.offset(offset_pos)? as usize;
 
         let column_offset_range = if self.jar.rows * self.jar.columns == offset_pos + 1 {
             // It's the last column of the last row
             value_offset..self.reader.size()
         } else {
-            let next_value_offset = self.reader.offset(offset_pos + 1) as usize;
+            let next_value_offset = self.reader.offset(offset_pos + 1)? as usize;
             value_offset..next_value_offset
         };
 
@@ -236,7 +240,7 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
                         .expect("dictionary to be loaded");
                     let mut decompressor = Decompressor::with_prepared_dictionary(dictionaries)?;
                     Zstd::decompress_with_dictionary(
-                        self.reader.data(column_offset_range),
+                        &self.reader.data(column_offset_range)?,
                         &mut self.internal_buffer,
                         &mut decompressor,
                     )?;
@@ -244,7 +248,7 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
                 _ => {
                     // Uses the chosen default decompressor
                     compression.decompress_to(
-                        self.reader.data(column_offset_range),
+                        &self.reader.data(column_offset_range)?,
                         &mut self.internal_buffer,
                     )?;
                 }
@@ -252,17 +256,24 @@ impl<'a, H: NippyJarHeader> NippyJarCursor<'a, H> {
             let to = self.internal_buffer.len();
 
             row.push(ValueRange::Internal(from..to));
-        } else {
-            // Not compressed
+        } else if self.reader.is_mmap() {
+            // Not compressed, can be borrowed straight from the mmap
             row.push(ValueRange::Mmap(column_offset_range));
+        } else {
+            // Not compressed, but needs to be read from disk
+            let from = self.internal_buffer.len();
+            self.reader.read_into(column_offset_range, &mut self.internal_buffer)?;
+            let to = self.internal_buffer.len();
+
+            row.push(ValueRange::Internal(from..to));
         }
 
         Ok(())
     }
 }
 
-/// Helper type that stores the range of the decompressed column value either on a `mmap` slice or
-/// on the internal buffer.
+/// Helper type that stores the range of the column value either on a `mmap` slice or on the
+/// internal buffer, which holds decompressed values and values read through direct I/O.
 enum ValueRange {
     Mmap(Range<usize>),
     Internal(Range<usize>),
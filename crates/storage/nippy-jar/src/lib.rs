@@ -9,12 +9,10 @@
 #![allow(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-use memmap2::Mmap;
 use serde::{Deserialize, Serialize};
 use std::{
     error::Error as StdError,
     fs::File,
-    ops::Range,
     path::{Path, PathBuf},
 };
 use sucds::{int_vectors::PrefixSummedEliasFano, Serializable};
@@ -39,6 +37,9 @@ pub use cursor::NippyJarCursor;
 mod writer;
 pub use writer::NippyJarWriter;
 
+mod reader;
+pub use reader::{BlockCache, DataReader, DataReaderMode, MmapAdvice, DIRECT_IO_BLOCK_SIZE};
+
 const NIPPY_JAR_VERSION: usize = 1;
 
 const INDEX_FILE_EXTENSION: &str = "idx";
@@ -67,7 +68,8 @@ impl<T> NippyJarHeader for T where
 /// `NippyJar` is a specialized storage format designed for immutable data.
 ///
 /// Data is organized into a columnar format, enabling column-based compression. Data retrieval
-/// entails consulting an offset list and fetching the data from file via `mmap`.
+/// entails consulting an offset list and fetching the data from file via `mmap` or positioned
+/// reads, depending on the [`DataReaderMode`].
 ///
 /// PHF & Filters:
 /// For data membership verification, the `filter` field can be configured with algorithms like
@@ -302,6 +304,14 @@ impl<H: NippyJarHeader> NippyJar<H> {
         DataReader::new(self.data_path())
     }
 
+    /// Returns a [`DataReader`] of the data and offset file using the given [`DataReaderMode`].
+    pub fn open_data_reader_with_mode(
+        &self,
+        mode: DataReaderMode,
+    ) -> Result<DataReader, NippyJarError> {
+        DataReader::new_with_mode(self.data_path(), mode)
+    }
+
     /// If required, prepares any compression algorithm to an early pass of the data.
     pub fn prepare_compression(
         &mut self,
@@ -456,102 +466,11 @@ impl<H: NippyJarHeader> PerfectHashingFunction for NippyJar<H> {
     }
 }
 
-/// Manages the reading of static file data using memory-mapped files.
-///
-/// Holds file and mmap descriptors of the data and offsets files of a static_file.
-#[derive(Debug)]
-pub struct DataReader {
-    /// Data file descriptor. Needs to be kept alive as long as `data_mmap` handle.
-    #[allow(dead_code)]
-    data_file: File,
-    /// Mmap handle for data.
-    data_mmap: Mmap,
-    /// Offset file descriptor. Needs to be kept alive as long as `offset_mmap` handle.
-    #[allow(dead_code)]
-    offset_file: File,
-    /// Mmap handle for offsets.
-    offset_mmap: Mmap,
-    /// Number of bytes that represent one offset.
-    offset_size: u64,
-}
-
-impl DataReader {
-    /// Reads the respective data and offsets file and returns [`DataReader`].
-    pub fn new(path: impl AsRef<Path>) -> Result<Self, NippyJarError> {
-        let data_file = File::open(path.as_ref())?;
-        // SAFETY: File is read-only and its descriptor is kept alive as long as the mmap handle.
-        let data_mmap = unsafe { Mmap::map(&data_file)? };
-
-        let offset_file = File::open(path.as_ref().with_extension(OFFSETS_FILE_EXTENSION))?;
-        // SAFETY: File is read-only and its descriptor is kept alive as long as the mmap handle.
-        let offset_mmap = unsafe { Mmap::map(&offset_file)? };
-
-        Ok(Self {
-            data_file,
-            data_mmap,
-            offset_file,
-            // First byte is the size of one offset in bytes
-            offset_size: offset_mmap[0] as u64,
-            offset_mmap,
-        })
-    }
-
-    /// Returns the offset for the requested data index
-    pub fn offset(&self, index: usize) -> u64 {
-        // + 1 represents the offset_len u8 which is in the beginning of the file
-        let from = index * self.offset_size as usize + 1;
-
-        self.offset_at(from)
-    }
-
-    /// Returns the offset for the requested data index starting from the end
-    pub fn reverse_offset(&self, index: usize) -> Result<u64, NippyJarError> {
-        let offsets_file_size = self.offset_file.metadata()?.len() as usize;
-
-        if offsets_file_size > 1 {
-            let from = offsets_file_size - self.offset_size as usize * (index + 1);
-
-            Ok(self.offset_at(from))
-        } else {
-            Ok(0)
-        }
-    }
-
-    /// Returns total number of offsets in the file.
-    /// The size of one offset is determined by the file itself.
-    pub fn offsets_count(&self) -> Result<usize, NippyJarError> {
-        Ok((self.offset_file.metadata()?.len().saturating_sub(1) / self.offset_size) as usize)
-    }
-
-    /// Reads one offset-sized (determined by the offset file) u64 at the provided index.
-    fn offset_at(&self, index: usize) -> u64 {
-        let mut buffer: [u8; 8] = [0; 8];
-        buffer[..self.offset_size as usize]
-            .copy_from_slice(&self.offset_mmap[index..(index + self.offset_size as usize)]);
-        u64::from_le_bytes(buffer)
-    }
-
-    /// Returns number of bytes that represent one offset.
-    pub fn offset_size(&self) -> u64 {
-        self.offset_size
-    }
-
-    /// Returns the underlying data as a slice of bytes for the provided range.
-    pub fn data(&self, range: Range<usize>) -> &[u8] {
-        &self.data_mmap[range]
-    }
-
-    /// Returns total size of data
-    pub fn size(&self) -> usize {
-        self.data_mmap.len()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use rand::{rngs::SmallRng, seq::SliceRandom, RngCore, SeedableRng};
-    use std::{collections::HashSet, fs::OpenOptions};
+    use std::{collections::HashSet, fs::OpenOptions, sync::Arc};
 
     type ColumnResults<T> = Vec<ColumnResult<T>>;
     type ColumnValues = Vec<Vec<u8>>;
@@ -792,6 +711,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_direct_reader() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+
+        for compressed in [false, true] {
+            let file_path = tempfile::NamedTempFile::new().unwrap();
+            let mut nippy = NippyJar::new_without_header(num_columns, file_path.path());
+            if compressed {
+                nippy = nippy.with_lz4();
+            }
+            nippy
+                .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+                .unwrap();
+
+            let loaded_nippy = NippyJar::load_without_header(file_path.path()).unwrap();
+            let mode = DataReaderMode::direct(DIRECT_IO_BLOCK_SIZE * 4);
+            let reader = Arc::new(loaded_nippy.open_data_reader_with_mode(mode.clone()).unwrap());
+            assert!(!reader.is_mmap());
+
+            // Read twice, so the second pass goes through the block cache
+            for _ in 0..2 {
+                let mut cursor =
+                    NippyJarCursor::with_reader(&loaded_nippy, reader.clone()).unwrap();
+                let mut row_index = 0usize;
+                while let Some(row) = cursor.next_row().unwrap() {
+                    assert_eq!(
+                        (row[0], row[1]),
+                        (col1[row_index].as_slice(), col2[row_index].as_slice())
+                    );
+                    row_index += 1;
+                }
+                assert_eq!(row_index, col1.len());
+            }
+
+            let DataReaderMode::Direct(cache) = mode else { unreachable!() };
+            assert!(cache.hits() > 0);
+            assert!(cache.misses() > 0);
+        }
+    }
+
     #[test]
     fn test_zstd_no_dictionaries() {
         let (col1, col2) = test_data(None);
@@ -0,0 +1,435 @@
+use crate::{NippyJarError, OFFSETS_FILE_EXTENSION};
+use memmap2::Mmap;
+use parking_lot::Mutex;
+use schnellru::{ByLength, LruMap};
+use std::{
+    borrow::Cow,
+    fs::File,
+    io,
+    ops::Range,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use tracing::debug;
+
+/// Size of the blocks read from disk by [`DataReaderMode::Direct`].
+///
+/// Matches the usual page size, which also satisfies the buffer, offset and length alignment
+/// requirements of `O_DIRECT`.
+pub const DIRECT_IO_BLOCK_SIZE: usize = 4096;
+
+/// Strategy used by [`DataReader`] to access the data and offsets files of a
+/// [`NippyJar`](crate::NippyJar).
+#[derive(Debug, Clone)]
+pub enum DataReaderMode {
+    /// Memory-maps both files, passing the given access pattern hint to `madvise`.
+    Mmap(MmapAdvice),
+    /// Reads both files through positioned reads (`pread`), opened with `O_DIRECT` where the
+    /// platform and filesystem support it.
+    ///
+    /// Since `O_DIRECT` bypasses the page cache, recently read blocks are kept in the given
+    /// [`BlockCache`], which can be shared by every reader of a static file provider.
+    Direct(Arc<BlockCache>),
+}
+
+impl DataReaderMode {
+    /// Returns a [`DataReaderMode::Direct`] with a new block cache holding up to `capacity_bytes`.
+    pub fn direct(capacity_bytes: usize) -> Self {
+        Self::Direct(Arc::new(BlockCache::new(capacity_bytes)))
+    }
+}
+
+impl Default for DataReaderMode {
+    fn default() -> Self {
+        Self::Mmap(MmapAdvice::default())
+    }
+}
+
+/// Access pattern hint passed to `madvise` for memory-mapped static files.
+///
+/// Hints are only applied on unix platforms and ignored elsewhere.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MmapAdvice {
+    /// No special treatment, leaves the kernel readahead defaults in place.
+    #[default]
+    Normal,
+    /// Expect page references in random order, disabling readahead.
+    Random,
+    /// Expect page references in sequential order, enabling aggressive readahead.
+    Sequential,
+    /// Expect access in the near future, prefetching the whole file.
+    WillNeed,
+}
+
+impl MmapAdvice {
+    /// Applies the hint to the given memory map.
+    #[cfg(unix)]
+    fn apply(self, mmap: &Mmap) -> io::Result<()> {
+        let advice = match self {
+            Self::Normal => return Ok(()),
+            Self::Random => memmap2::Advice::Random,
+            Self::Sequential => memmap2::Advice::Sequential,
+            Self::WillNeed => memmap2::Advice::WillNeed,
+        };
+        mmap.advise(advice)
+    }
+
+    /// Applies the hint to the given memory map.
+    #[cfg(not(unix))]
+    fn apply(self, _mmap: &Mmap) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// LRU cache of [`DIRECT_IO_BLOCK_SIZE`] blocks read by [`DataReaderMode::Direct`] readers.
+///
+/// Blocks are keyed by a per-file identifier handed out by the cache itself, so a single cache can
+/// be shared across all files of a static file provider. Entries of files that are no longer
+/// open are never hit again and age out naturally.
+#[derive(Debug)]
+pub struct BlockCache {
+    /// Cached blocks keyed by file identifier and block index.
+    blocks: Mutex<LruMap<(u64, u64), Arc<[u8]>, ByLength>>,
+    /// Next file identifier to hand out.
+    next_file_id: AtomicU64,
+    /// Number of block lookups served from the cache.
+    hits: AtomicU64,
+    /// Number of block lookups that had to go to disk.
+    misses: AtomicU64,
+}
+
+impl BlockCache {
+    /// Creates a new [`BlockCache`] holding up to `capacity_bytes` worth of blocks.
+    pub fn new(capacity_bytes: usize) -> Self {
+        let max_blocks = (capacity_bytes / DIRECT_IO_BLOCK_SIZE).max(1) as u32;
+        Self {
+            blocks: Mutex::new(LruMap::new(ByLength::new(max_blocks))),
+            next_file_id: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the number of block lookups served from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of block lookups that had to read from disk.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn next_file_id(&self) -> u64 {
+        self.next_file_id.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn get(&self, key: (u64, u64)) -> Option<Arc<[u8]>> {
+        let block = self.blocks.lock().get(&key).cloned();
+        if block.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+        }
+        block
+    }
+
+    fn insert(&self, key: (u64, u64), block: Arc<[u8]>) {
+        self.blocks.lock().insert(key, block);
+    }
+}
+
+/// Manages the reading of static file data, either through memory-mapped files or through
+/// positioned reads backed by a [`BlockCache`]. See [`DataReaderMode`].
+///
+/// Holds file descriptors of the data and offsets files of a static_file.
+#[derive(Debug)]
+pub struct DataReader {
+    /// Data file source.
+    data: FileSource,
+    /// Offsets file source.
+    offsets: FileSource,
+    /// Number of bytes that represent one offset.
+    offset_size: u64,
+}
+
+impl DataReader {
+    /// Reads the respective data and offsets file through memory maps and returns [`DataReader`].
+    pub fn new(path: impl AsRef<Path>) -> Result<Self, NippyJarError> {
+        Self::new_with_mode(path, DataReaderMode::default())
+    }
+
+    /// Reads the respective data and offsets file with the given [`DataReaderMode`] and returns
+    /// [`DataReader`].
+    pub fn new_with_mode(
+        path: impl AsRef<Path>,
+        mode: DataReaderMode,
+    ) -> Result<Self, NippyJarError> {
+        let data = FileSource::open(path.as_ref(), &mode)?;
+        let offsets =
+            FileSource::open(&path.as_ref().with_extension(OFFSETS_FILE_EXTENSION), &mode)?;
+
+        // First byte is the size of one offset
+        let mut offset_size = [0u8; 1];
+        offsets.read_exact_at(0, &mut offset_size)?;
+
+        Ok(Self { data, offsets, offset_size: offset_size[0] as u64 })
+    }
+
+    /// Returns the offset for the requested data index
+    pub fn offset(&self, index: usize) -> Result<u64, NippyJarError> {
+        // + 1 represents the offset_len u8 which is in the beginning of the file
+        let from = index * self.offset_size as usize + 1;
+
+        self.offset_at(from)
+    }
+
+    /// Returns the offset for the requested data index starting from the end
+    pub fn reverse_offset(&self, index: usize) -> Result<u64, NippyJarError> {
+        let offsets_file_size = self.offsets.file().metadata()?.len() as usize;
+
+        if offsets_file_size > 1 {
+            let from = offsets_file_size - self.offset_size as usize * (index + 1);
+
+            self.offset_at(from)
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Returns total number of offsets in the file.
+    /// The size of one offset is determined by the file itself.
+    pub fn offsets_count(&self) -> Result<usize, NippyJarError> {
+        Ok((self.offsets.file().metadata()?.len().saturating_sub(1) / self.offset_size) as usize)
+    }
+
+    /// Reads one offset-sized (determined by the offset file) u64 at the provided index.
+    fn offset_at(&self, index: usize) -> Result<u64, NippyJarError> {
+        let mut buffer: [u8; 8] = [0; 8];
+        self.offsets.read_exact_at(index, &mut buffer[..self.offset_size as usize])?;
+        Ok(u64::from_le_bytes(buffer))
+    }
+
+    /// Returns number of bytes that represent one offset.
+    pub fn offset_size(&self) -> u64 {
+        self.offset_size
+    }
+
+    /// Returns `true` if the data file is memory-mapped, and [`DataReader::mmap_data`] can be used
+    /// to borrow data without copying.
+    pub fn is_mmap(&self) -> bool {
+        matches!(self.data, FileSource::Mmap { .. })
+    }
+
+    /// Returns the underlying data as a slice of bytes for the provided range, if the data file is
+    /// memory-mapped.
+    pub fn mmap_data(&self, range: Range<usize>) -> Option<&[u8]> {
+        match &self.data {
+            FileSource::Mmap { mmap, .. } => Some(&mmap[range]),
+            FileSource::Direct(_) => None,
+        }
+    }
+
+    /// Returns the underlying data for the provided range, borrowed from the memory map or read
+    /// from disk.
+    pub fn data(&self, range: Range<usize>) -> Result<Cow<'_, [u8]>, NippyJarError> {
+        if let Some(data) = self.mmap_data(range.clone()) {
+            return Ok(Cow::Borrowed(data))
+        }
+
+        let mut buffer = Vec::with_capacity(range.len());
+        self.read_into(range, &mut buffer)?;
+        Ok(Cow::Owned(buffer))
+    }
+
+    /// Appends the underlying data for the provided range to `buffer`.
+    pub fn read_into(
+        &self,
+        range: Range<usize>,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), NippyJarError> {
+        let from = buffer.len();
+        buffer.resize(from + range.len(), 0);
+        self.data.read_exact_at(range.start, &mut buffer[from..])
+    }
+
+    /// Returns total size of data
+    pub fn size(&self) -> usize {
+        self.data.len()
+    }
+}
+
+/// A file accessed either through a memory map or through positioned reads.
+#[derive(Debug)]
+enum FileSource {
+    Mmap {
+        /// File descriptor. Needs to be kept alive as long as `mmap` handle.
+        file: File,
+        /// Mmap handle.
+        mmap: Mmap,
+    },
+    Direct(DirectFile),
+}
+
+impl FileSource {
+    fn open(path: &Path, mode: &DataReaderMode) -> Result<Self, NippyJarError> {
+        match mode {
+            DataReaderMode::Mmap(advice) => {
+                let file = File::open(path)?;
+                // SAFETY: File is read-only and its descriptor is kept alive as long as the mmap
+                // handle.
+                let mmap = unsafe { Mmap::map(&file)? };
+                if let Err(err) = advice.apply(&mmap) {
+                    debug!(target: "nippy-jar", ?path, ?advice, %err, "Failed to apply mmap advice.");
+                }
+                Ok(Self::Mmap { file, mmap })
+            }
+            DataReaderMode::Direct(cache) => {
+                Ok(Self::Direct(DirectFile::open(path, cache.clone())?))
+            }
+        }
+    }
+
+    fn file(&self) -> &File {
+        match self {
+            Self::Mmap { file, .. } => file,
+            Self::Direct(direct) => &direct.file,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Mmap { mmap, .. } => mmap.len(),
+            Self::Direct(direct) => direct.len,
+        }
+    }
+
+    /// Fills `buf` with the file contents starting at `offset`.
+    fn read_exact_at(&self, offset: usize, buf: &mut [u8]) -> Result<(), NippyJarError> {
+        match self {
+            Self::Mmap { mmap, .. } => {
+                buf.copy_from_slice(&mmap[offset..offset + buf.len()]);
+                Ok(())
+            }
+            Self::Direct(direct) => direct.read_exact_at(offset, buf),
+        }
+    }
+}
+
+/// A file read in [`DIRECT_IO_BLOCK_SIZE`] blocks through a shared [`BlockCache`].
+#[derive(Debug)]
+struct DirectFile {
+    file: File,
+    /// File length at the time of opening. Like a memory map, the reader does not observe data
+    /// appended afterwards.
+    len: usize,
+    /// Identifier of this file in the block cache.
+    id: u64,
+    cache: Arc<BlockCache>,
+}
+
+impl DirectFile {
+    fn open(path: &Path, cache: Arc<BlockCache>) -> Result<Self, NippyJarError> {
+        let file = open_direct(path)?;
+        let len = file.metadata()?.len() as usize;
+        Ok(Self { file, len, id: cache.next_file_id(), cache })
+    }
+
+    fn read_exact_at(&self, mut offset: usize, buf: &mut [u8]) -> Result<(), NippyJarError> {
+        if offset + buf.len() > self.len {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+        }
+
+        let mut written = 0;
+        while written < buf.len() {
+            let block = self.block(offset / DIRECT_IO_BLOCK_SIZE)?;
+            let start = offset % DIRECT_IO_BLOCK_SIZE;
+            if start >= block.len() {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into())
+            }
+
+            let len = (block.len() - start).min(buf.len() - written);
+            buf[written..written + len].copy_from_slice(&block[start..start + len]);
+            written += len;
+            offset += len;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the block at `index`, reading it from disk if it's not cached.
+    fn block(&self, index: usize) -> Result<Arc<[u8]>, NippyJarError> {
+        let key = (self.id, index as u64);
+        if let Some(block) = self.cache.get(key) {
+            return Ok(block)
+        }
+
+        let mut aligned = AlignedBlock::new();
+        let read = read_at(&self.file, aligned.as_mut(), (index * DIRECT_IO_BLOCK_SIZE) as u64)?;
+        let block: Arc<[u8]> = Arc::from(&aligned.as_mut()[..read]);
+
+        // Only full blocks are cached, since the tail block may still be growing.
+        if read == DIRECT_IO_BLOCK_SIZE {
+            self.cache.insert(key, block.clone());
+        }
+
+        Ok(block)
+    }
+}
+
+/// A [`DIRECT_IO_BLOCK_SIZE`] buffer aligned to [`DIRECT_IO_BLOCK_SIZE`], as required by
+/// `O_DIRECT` reads.
+struct AlignedBlock {
+    buf: Vec<u8>,
+    start: usize,
+}
+
+impl AlignedBlock {
+    fn new() -> Self {
+        let buf = vec![0; DIRECT_IO_BLOCK_SIZE * 2 - 1];
+        let start = buf.as_ptr().align_offset(DIRECT_IO_BLOCK_SIZE);
+        Self { buf, start }
+    }
+
+    fn as_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[self.start..self.start + DIRECT_IO_BLOCK_SIZE]
+    }
+}
+
+/// Opens a file for reading, bypassing the page cache with `O_DIRECT` where supported.
+fn open_direct(path: &Path) -> io::Result<File> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        match std::fs::OpenOptions::new().read(true).custom_flags(libc::O_DIRECT).open(path) {
+            Ok(file) => return Ok(file),
+            // Some filesystems (e.g. tmpfs) reject `O_DIRECT`, fall back to buffered reads.
+            Err(err) if err.raw_os_error() == Some(libc::EINVAL) => {
+                debug!(target: "nippy-jar", ?path, "O_DIRECT not supported, using buffered reads.");
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    File::open(path)
+}
+
+/// Reads from `file` at `offset` into `buf`, returning the number of bytes read. Only returns
+/// fewer bytes than requested at the end of the file.
+fn read_at(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    loop {
+        #[cfg(unix)]
+        let result = std::os::unix::fs::FileExt::read_at(file, buf, offset);
+        #[cfg(windows)]
+        let result = std::os::windows::fs::FileExt::seek_read(file, buf, offset);
+
+        match result {
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            result => return result,
+        }
+    }
+}
@@ -2,24 +2,27 @@ use crate::{
     providers::{state::latest::LatestStateProvider, StaticFileProvider},
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode,
-    ProviderError, PruneCheckpointReader, StageCheckpointReader, StateProviderBox,
+    AddressTransactionsReader, BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider,
+    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
+    HeaderSyncMode, LogIndexReader, ProviderError, PruneCheckpointReader, ReorgHistoryReader,
+    SenderNoncesReader, StageCheckpointReader, StateProviderBox, TokenTransfersReader,
     TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::{database::Database, init_db, models::StoredBlockBodyIndices, DatabaseEnv};
 use reth_evm::ConfigureEvmEnv;
 use reth_interfaces::{provider::ProviderResult, RethError, RethResult};
+use reth_nippy_jar::DataReaderMode;
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, ChainInfo,
-    ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StaticFileSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash,
-    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, ReorgRecord, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, StaticFileSegment, TokenTransfer, TransactionMeta,
+    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
+    U256,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
-    ops::{RangeBounds, RangeInclusive},
+    ops::{Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -64,6 +67,12 @@ impl<DB> ProviderFactory<DB> {
         self
     }
 
+    /// Sets how the static file provider reads static files. See [`DataReaderMode`].
+    pub fn with_static_files_reader_mode(mut self, mode: DataReaderMode) -> Self {
+        self.static_file_provider = self.static_file_provider.with_reader_mode(mode);
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub fn db_ref(&self) -> &DB {
         &self.db
@@ -556,12 +565,88 @@ impl<DB: Database> PruneCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> ReorgHistoryReader for ProviderFactory<DB> {
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
+        self.provider()?.reorg_history(limit)
+    }
+}
+
+impl<DB: Database> AddressTransactionsReader for ProviderFactory<DB> {
+    fn address_transactions_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.address_transactions_checkpoint()
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        self.provider()?.address_transactions(address, offset, limit)
+    }
+
+    fn address_transactions_by_range(
+        &self,
+        address: Address,
+        range: Range<TxNumber>,
+        reverse: bool,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        self.provider()?.address_transactions_by_range(address, range, reverse, limit)
+    }
+}
+
+impl<DB: Database> TokenTransfersReader for ProviderFactory<DB> {
+    fn token_transfers_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.token_transfers_checkpoint()
+    }
+
+    fn token_transfers(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TokenTransfer>>> {
+        self.provider()?.token_transfers(address, offset, limit)
+    }
+}
+
+impl<DB: Database> LogIndexReader for ProviderFactory<DB> {
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.log_index_checkpoint()
+    }
+
+    fn blocks_with_logs(
+        &self,
+        addresses: &[Address],
+        topics: &[B256],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.blocks_with_logs(addresses, topics, range)
+    }
+}
+
+impl<DB: Database> SenderNoncesReader for ProviderFactory<DB> {
+    fn sender_nonces_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider()?.sender_nonces_checkpoint()
+    }
+
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> ProviderResult<Option<TxHash>> {
+        self.provider()?.transaction_hash_by_sender_and_nonce(sender, nonce)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::ProviderFactory;
     use crate::{
         providers::StaticFileWriter, test_utils::create_test_provider_factory, BlockHashReader,
-        BlockNumReader, BlockWriter, HeaderSyncGapProvider, HeaderSyncMode, TransactionsProvider,
+        BlockNumReader, BlockWriter, HeaderSyncGapProvider, HeaderSyncMode, ReorgHistoryReader,
+        ReorgHistoryWriter, StatsReader, TransactionsProvider,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -580,8 +665,8 @@ mod tests {
         RethError,
     };
     use reth_primitives::{
-        hex_literal::hex, ChainSpecBuilder, PruneMode, PruneModes, SealedBlock, StaticFileSegment,
-        TxNumber, B256, U256,
+        hex_literal::hex, ChainSpecBuilder, PruneMode, PruneModes, ReorgRecord, SealedBlock,
+        StaticFileSegment, TxNumber, B256, U256,
     };
     use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
@@ -743,4 +828,21 @@ mod tests {
         assert_eq!(gap.local_head, head);
         assert_eq!(gap.target.tip(), consensus_tip.into());
     }
+
+    #[test]
+    fn reorg_history_retention() {
+        let factory = create_test_provider_factory();
+        let reorg = |fork_block| ReorgRecord { fork_block, ..Default::default() };
+
+        // every reorg is committed separately, only the last three are kept
+        for fork_block in 0..5 {
+            let provider_rw = factory.provider_rw().unwrap();
+            provider_rw.insert_reorg(reorg(fork_block), 3).unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        assert_eq!(factory.reorg_history(10).unwrap(), vec![reorg(4), reorg(3), reorg(2)]);
+        assert_eq!(factory.reorg_history(2).unwrap(), vec![reorg(4), reorg(3)]);
+        assert_eq!(factory.provider().unwrap().count_entries::<tables::ReorgHistory>().unwrap(), 3);
+    }
 }
@@ -5,12 +5,16 @@ use crate::{
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    Chain, EvmEnvProvider, HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider,
-    HeaderSyncMode, HistoricalStateProvider, HistoryWriter, LatestStateProvider,
+    AccountReader, AddressTransactionsReader, AddressTransactionsWriter, BlockExecutionWriter,
+    BlockHashReader, BlockNumReader, BlockReader, BlockWriter, Chain, EvmEnvProvider,
+    HashingWriter, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, HeaderSyncMode,
+    HistoricalStateProvider, HistoryWriter, LatestStateProvider, LogIndexReader, LogIndexWriter,
     OriginalValuesKnown, ProviderError, PruneCheckpointReader, PruneCheckpointWriter,
-    StageCheckpointReader, StateProviderBox, StatsReader, StorageReader, TransactionVariant,
-    TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    ReorgHistoryReader, ReorgHistoryWriter, SenderNoncesReader, SenderNoncesWriter,
+    StageCheckpointReader, StateProviderBox, StatsReader, StorageReader, TokenTransfersReader,
+    TokenTransfersWriter, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    WithdrawalsProvider, ADDRESS_TRANSACTIONS_EXEX_ID, LOG_INDEX_EXEX_ID, SENDER_NONCES_EXEX_ID,
+    TOKEN_TRANSFERS_EXEX_ID,
 };
 use itertools::{izip, Itertools};
 use reth_db::{
@@ -39,9 +43,10 @@ use reth_primitives::{
     trie::Nibbles,
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
     ChainInfo, ChainSpec, GotExpected, Head, Header, PruneCheckpoint, PruneLimiter, PruneModes,
-    PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment,
-    StorageEntry, TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
-    TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
+    PruneSegment, Receipt, ReorgRecord, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    StaticFileSegment, StorageEntry, TokenTransfer, TransactionMeta, TransactionSigned,
+    TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, U256,
 };
 use reth_trie::{
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
@@ -354,6 +359,56 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             |_| true,
         )
     }
+
+    /// Returns the indices of the key within the range, from the shards of the history index `T`.
+    fn history_index_in_range<K, T>(
+        &self,
+        key: K,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<u64>>
+    where
+        K: PartialEq + Clone,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        // the first shard that may contain the start of the range is the first one whose highest
+        // index is not below it
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut indices = Vec::new();
+        let mut shard = cursor.seek(ShardedKey::new(key.clone(), *range.start()))?;
+        while let Some((sharded_key, list)) = shard {
+            if sharded_key.key != key {
+                break
+            }
+            indices.extend(
+                list.iter()
+                    .skip_while(|index| index < range.start())
+                    .take_while(|index| index <= range.end()),
+            );
+            if sharded_key.highest_block_number >= *range.end() {
+                break
+            }
+            shard = cursor.next()?;
+        }
+        Ok(indices)
+    }
+
+    /// Returns the union of the indices of the keys within the range, from the shards of the
+    /// history index `T`.
+    fn history_index_union_in_range<K, T>(
+        &self,
+        keys: &[K],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<u64>>
+    where
+        K: PartialEq + Clone,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut indices = BTreeSet::new();
+        for key in keys {
+            indices.extend(self.history_index_in_range::<K, T>(key.clone(), range.clone())?);
+        }
+        Ok(indices)
+    }
 }
 
 impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
@@ -974,6 +1029,95 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         Ok(Vec::new())
     }
 
+    /// Returns the indices of the address in a sharded index, the highest first.
+    ///
+    /// Skips the `offset` highest indices and returns at most `limit` indices.
+    fn address_index_rev<T>(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Vec<u64>>
+    where
+        T: Table<Key = ShardedKey<Address>, Value = BlockNumberList>,
+    {
+        // walk the shards of the address from the most recent one
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let mut indices = Vec::with_capacity(limit);
+        let mut skip = offset;
+        let mut shard = cursor.seek_exact(ShardedKey::last(address))?;
+        while let Some((sharded_key, list)) = shard {
+            if sharded_key.key != address || indices.len() >= limit {
+                break
+            }
+
+            let len = list.len() as usize;
+            if skip >= len {
+                skip -= len;
+            } else {
+                let list = list.iter().collect::<Vec<_>>();
+                indices.extend(list.into_iter().rev().skip(skip).take(limit - indices.len()));
+                skip = 0;
+            }
+            shard = cursor.prev()?;
+        }
+        Ok(indices)
+    }
+
+    /// Returns the indices of the address within the range from the index table `T`, in ascending
+    /// order or in descending order if `reverse` is set.
+    ///
+    /// Returns at most `limit` indices.
+    fn address_index_range<T>(
+        &self,
+        address: Address,
+        range: Range<u64>,
+        reverse: bool,
+        limit: usize,
+    ) -> ProviderResult<Vec<u64>>
+    where
+        T: Table<Key = ShardedKey<Address>, Value = BlockNumberList>,
+    {
+        let mut indices = Vec::new();
+        if range.is_empty() || limit == 0 {
+            return Ok(indices)
+        }
+
+        // shards are keyed by their highest index, so this is the first shard that can contain
+        // indices of the range in the direction of the walk
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        let start = if reverse { range.end - 1 } else { range.start };
+        let mut shard = cursor.seek(ShardedKey::new(address, start))?;
+        while let Some((sharded_key, list)) = shard {
+            if sharded_key.key != address {
+                break
+            }
+
+            let list = list.iter().collect::<Vec<_>>();
+            let in_range = list.iter().copied().filter(|index| range.contains(index));
+            let take = limit - indices.len();
+            if reverse {
+                indices.extend(in_range.rev().take(take));
+                // the previous shards only contain lower indices
+                if list.first().map_or(true, |lowest| *lowest <= range.start) {
+                    break
+                }
+                shard = cursor.prev()?;
+            } else {
+                indices.extend(in_range.take(take));
+                // the next shards only contain higher indices
+                if sharded_key.highest_block_number >= range.end - 1 {
+                    break
+                }
+                shard = cursor.next()?;
+            }
+            if indices.len() >= limit {
+                break
+            }
+        }
+        Ok(indices)
+    }
+
     /// Insert history index to the database.
     ///
     /// For each updated partial key, this function removes the last shard from
@@ -1016,6 +1160,38 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
         Ok(())
     }
+
+    /// Removes the indices from `first_removed` onwards of the keys from the shards of the history
+    /// index `T`.
+    fn unwind_history_index<K, T>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        first_removed: u64,
+    ) -> ProviderResult<()>
+    where
+        K: PartialEq + Clone,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for key in keys {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key.clone()),
+                first_removed,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<TX: DbTx> AccountReader for DatabaseProvider<TX> {
@@ -1776,6 +1952,11 @@ impl<TX: DbTx> ReceiptProvider for DatabaseProvider<TX> {
 
     fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
         if let Some(number) = self.convert_hash_or_number(block)? {
+            // The block index of the receipts static file avoids looking up the block body indices
+            if let Some(receipts) = self.static_file_provider.receipts_by_block_index(number)? {
+                return Ok(Some(receipts))
+            }
+
             if let Some(body) = self.block_body_indices(number)? {
                 let tx_range = body.tx_num_range();
                 return if tx_range.is_empty() {
@@ -2671,6 +2852,467 @@ impl<TX: DbTxMut> PruneCheckpointWriter for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> ReorgHistoryReader for DatabaseProvider<TX> {
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
+        let mut cursor = self.tx.cursor_read::<tables::ReorgHistory>()?;
+        let reorgs = cursor
+            .walk_back(None)?
+            .take(limit)
+            .map(|entry| entry.map(|(_, reorg)| reorg))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(reorgs)
+    }
+}
+
+impl<TX: DbTxMut> ReorgHistoryWriter for DatabaseProvider<TX> {
+    fn insert_reorg(&self, reorg: ReorgRecord, retention: u64) -> ProviderResult<()> {
+        let mut cursor = self.tx.cursor_write::<tables::ReorgHistory>()?;
+        let id = cursor.last()?.map_or(0, |(id, _)| id + 1);
+        cursor.append(id, reorg)?;
+
+        // remove the oldest reorgs beyond the retention
+        let retain_from = (id + 1).saturating_sub(retention);
+        while let Some((id, _)) = cursor.first()? {
+            if id >= retain_from {
+                break
+            }
+            cursor.delete_current()?;
+        }
+        Ok(())
+    }
+}
+
+impl<TX: DbTx> DatabaseProvider<TX> {
+    /// Returns the numbers of the transactions of the block range grouped by the addresses they
+    /// are indexed by in [tables::AddressTransactions].
+    ///
+    /// Senders that were pruned are recovered from the transaction signatures.
+    pub fn address_transactions_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Address, Vec<TxNumber>>> {
+        let tx_range = self.transaction_range_by_block_range(range)?;
+        let transactions = self.transactions_by_tx_range(tx_range.clone())?;
+        let mut senders = self.senders_by_tx_range(tx_range.clone())?;
+        if senders.len() != transactions.len() {
+            senders = transactions
+                .iter()
+                .map(|tx| tx.recover_signer().ok_or(ProviderError::SenderRecoveryError))
+                .collect::<Result<_, _>>()?;
+        }
+
+        let mut address_transactions = BTreeMap::<Address, Vec<TxNumber>>::new();
+        for ((tx_number, transaction), sender) in tx_range.zip(transactions).zip(senders) {
+            let other = transaction
+                .transaction
+                .to()
+                .unwrap_or_else(|| sender.create(transaction.transaction.nonce()));
+            address_transactions.entry(sender).or_default().push(tx_number);
+            if other != sender {
+                address_transactions.entry(other).or_default().push(tx_number);
+            }
+        }
+        Ok(address_transactions)
+    }
+
+    /// Decodes the ERC-20 and ERC-721 token transfers of the block range from the stored receipts.
+    ///
+    /// Blocks whose receipts were pruned are skipped, as are the transfers after a pruned receipt.
+    pub fn token_transfers_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<TokenTransfer>> {
+        let mut transfers = Vec::new();
+        for block_number in range {
+            let tx_range = self
+                .block_body_indices(block_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?
+                .tx_num_range();
+            let mut receipts = self
+                .receipts_by_tx_range(tx_range.clone())?
+                .into_iter()
+                .map(Some)
+                .collect::<Vec<_>>();
+            if receipts.is_empty() {
+                continue
+            }
+            // pruned receipts leave gaps, look the receipts up by transaction number to keep their
+            // positions in the block
+            if receipts.len() as u64 != tx_range.end - tx_range.start {
+                receipts = tx_range
+                    .clone()
+                    .map(|tx_number| self.receipt(tx_number))
+                    .collect::<ProviderResult<_>>()?;
+            }
+            let transactions = self.transactions_by_tx_range(tx_range)?;
+            transfers.extend(TokenTransfer::from_receipts(
+                block_number,
+                transactions.iter().map(|tx| tx.hash()),
+                receipts.iter().map(Option::as_ref),
+            ));
+        }
+        Ok(transfers)
+    }
+
+    /// Returns the sender, nonce and hash of the transactions of the block range.
+    ///
+    /// Senders that were pruned are recovered from the transaction signatures.
+    pub fn sender_nonces_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(Address, u64, TxHash)>> {
+        let mut sender_nonces = Vec::new();
+        for block_number in range {
+            let tx_range = self
+                .block_body_indices(block_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?
+                .tx_num_range();
+            let transactions = self.transactions_by_tx_range(tx_range.clone())?;
+            let mut senders = self.senders_by_tx_range(tx_range)?;
+            if senders.len() != transactions.len() {
+                senders =
+                    TransactionSignedNoHash::recover_signers(&transactions, transactions.len())
+                        .ok_or(ProviderError::SenderRecoveryError)?;
+            }
+            sender_nonces.extend(
+                transactions
+                    .iter()
+                    .zip(senders)
+                    .map(|(tx, sender)| (sender, tx.nonce(), tx.hash())),
+            );
+        }
+        Ok(sender_nonces)
+    }
+
+    /// Returns the blocks of the range with logs of each address and first topic.
+    pub fn log_index_by_block_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<(BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>)>
+    {
+        let mut addresses = BTreeMap::<Address, Vec<BlockNumber>>::new();
+        let mut topics = BTreeMap::<B256, Vec<BlockNumber>>::new();
+        for block_number in range {
+            let receipts = self
+                .receipts_by_block(block_number.into())?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?;
+            for log in receipts.iter().flat_map(|receipt| &receipt.logs) {
+                let blocks = addresses.entry(log.address).or_default();
+                if blocks.last() != Some(&block_number) {
+                    blocks.push(block_number);
+                }
+                if let Some(topic) = log.topics().first() {
+                    let blocks = topics.entry(*topic).or_default();
+                    if blocks.last() != Some(&block_number) {
+                        blocks.push(block_number);
+                    }
+                }
+            }
+        }
+        Ok((addresses, topics))
+    }
+}
+
+impl<TX: DbTx> AddressTransactionsReader for DatabaseProvider<TX> {
+    fn address_transactions_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ExExCheckpoints>(ADDRESS_TRANSACTIONS_EXEX_ID.to_string())?)
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        if self.address_transactions_checkpoint()?.is_none() {
+            return Ok(None)
+        }
+        self.address_index_rev::<tables::AddressTransactions>(address, offset, limit).map(Some)
+    }
+
+    fn address_transactions_by_range(
+        &self,
+        address: Address,
+        range: Range<TxNumber>,
+        reverse: bool,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        if self.address_transactions_checkpoint()?.is_none() {
+            return Ok(None)
+        }
+        self.address_index_range::<tables::AddressTransactions>(address, range, reverse, limit)
+            .map(Some)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> AddressTransactionsWriter for DatabaseProvider<TX> {
+    fn insert_address_transactions(
+        &self,
+        transactions: BTreeMap<Address, Vec<TxNumber>>,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::AddressTransactions>(transactions, ShardedKey::new)?;
+        self.tx
+            .put::<tables::ExExCheckpoints>(ADDRESS_TRANSACTIONS_EXEX_ID.to_string(), checkpoint)?;
+        Ok(())
+    }
+
+    fn unwind_address_transactions(
+        &self,
+        addresses: BTreeSet<Address>,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
+        let first_tx_number = self
+            .block_body_indices(block)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(block))?
+            .next_tx_num();
+
+        let mut cursor = self.tx.cursor_write::<tables::AddressTransactions>()?;
+        for address in addresses {
+            let partial_shard = unwind_history_shards::<_, tables::AddressTransactions, _>(
+                &mut cursor,
+                ShardedKey::last(address),
+                first_tx_number,
+                |sharded_key| sharded_key.key == address,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(address),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        self.tx.put::<tables::ExExCheckpoints>(ADDRESS_TRANSACTIONS_EXEX_ID.to_string(), block)?;
+        Ok(())
+    }
+
+    fn remove_address_transactions(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::AddressTransactions>()?;
+        self.tx
+            .delete::<tables::ExExCheckpoints>(ADDRESS_TRANSACTIONS_EXEX_ID.to_string(), None)?;
+        Ok(())
+    }
+}
+
+impl<TX: DbTx> TokenTransfersReader for DatabaseProvider<TX> {
+    fn token_transfers_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ExExCheckpoints>(TOKEN_TRANSFERS_EXEX_ID.to_string())?)
+    }
+
+    fn token_transfers(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TokenTransfer>>> {
+        if self.token_transfers_checkpoint()?.is_none() {
+            return Ok(None)
+        }
+        let mut transfers = Vec::new();
+        for number in
+            self.address_index_rev::<tables::AddressTokenTransfers>(address, offset, limit)?
+        {
+            if let Some(transfer) = self.tx.get::<tables::TokenTransfers>(number)? {
+                transfers.push(transfer);
+            }
+        }
+        Ok(Some(transfers))
+    }
+}
+
+impl<TX: DbTxMut + DbTx> TokenTransfersWriter for DatabaseProvider<TX> {
+    fn insert_token_transfers(
+        &self,
+        transfers: Vec<TokenTransfer>,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()> {
+        let mut cursor = self.tx.cursor_write::<tables::TokenTransfers>()?;
+        let mut next_number = cursor.last()?.map_or(0, |(number, _)| number + 1);
+        let mut address_transfers = BTreeMap::<Address, Vec<u64>>::new();
+        for transfer in transfers {
+            address_transfers.entry(transfer.from).or_default().push(next_number);
+            if transfer.to != transfer.from {
+                address_transfers.entry(transfer.to).or_default().push(next_number);
+            }
+            cursor.append(next_number, transfer)?;
+            next_number += 1;
+        }
+
+        self.append_history_index::<_, tables::AddressTokenTransfers>(
+            address_transfers,
+            ShardedKey::new,
+        )?;
+        self.tx.put::<tables::ExExCheckpoints>(TOKEN_TRANSFERS_EXEX_ID.to_string(), checkpoint)?;
+        Ok(())
+    }
+
+    fn unwind_token_transfers(&self, block: BlockNumber) -> ProviderResult<usize> {
+        // transfers are stored in the order of the chain, remove them from the last one
+        let mut cursor = self.tx.cursor_write::<tables::TokenTransfers>()?;
+        let mut first_removed = None;
+        let mut removed = 0;
+        let mut addresses = BTreeSet::new();
+        while let Some((number, transfer)) = cursor.last()? {
+            if transfer.block_number <= block {
+                break
+            }
+            addresses.insert(transfer.from);
+            addresses.insert(transfer.to);
+            first_removed = Some(number);
+            removed += 1;
+            cursor.delete_current()?;
+        }
+
+        if let Some(first_removed) = first_removed {
+            let mut cursor = self.tx.cursor_write::<tables::AddressTokenTransfers>()?;
+            for address in addresses {
+                let partial_shard = unwind_history_shards::<_, tables::AddressTokenTransfers, _>(
+                    &mut cursor,
+                    ShardedKey::last(address),
+                    first_removed,
+                    |sharded_key| sharded_key.key == address,
+                )?;
+
+                // Check the last returned partial shard.
+                // If it's not empty, the shard needs to be reinserted.
+                if !partial_shard.is_empty() {
+                    cursor.insert(
+                        ShardedKey::last(address),
+                        BlockNumberList::new_pre_sorted(partial_shard),
+                    )?;
+                }
+            }
+        }
+
+        self.tx.put::<tables::ExExCheckpoints>(TOKEN_TRANSFERS_EXEX_ID.to_string(), block)?;
+        Ok(removed)
+    }
+
+    fn remove_token_transfers(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::TokenTransfers>()?;
+        self.tx.clear::<tables::AddressTokenTransfers>()?;
+        self.tx.delete::<tables::ExExCheckpoints>(TOKEN_TRANSFERS_EXEX_ID.to_string(), None)?;
+        Ok(())
+    }
+}
+
+impl<TX: DbTx> SenderNoncesReader for DatabaseProvider<TX> {
+    fn sender_nonces_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ExExCheckpoints>(SENDER_NONCES_EXEX_ID.to_string())?)
+    }
+
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> ProviderResult<Option<TxHash>> {
+        Ok(self.tx.get::<tables::SenderNonceTransactions>((sender, nonce).into())?)
+    }
+}
+
+impl<TX: DbTxMut + DbTx> SenderNoncesWriter for DatabaseProvider<TX> {
+    fn insert_sender_nonces(
+        &self,
+        transactions: Vec<(Address, u64, TxHash)>,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()> {
+        for (sender, nonce, hash) in transactions {
+            self.tx.put::<tables::SenderNonceTransactions>((sender, nonce).into(), hash)?;
+        }
+        self.tx.put::<tables::ExExCheckpoints>(SENDER_NONCES_EXEX_ID.to_string(), checkpoint)?;
+        Ok(())
+    }
+
+    fn unwind_sender_nonces(
+        &self,
+        reverted: Vec<(Address, u64)>,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
+        for key in reverted {
+            self.tx.delete::<tables::SenderNonceTransactions>(key.into(), None)?;
+        }
+        self.tx.put::<tables::ExExCheckpoints>(SENDER_NONCES_EXEX_ID.to_string(), block)?;
+        Ok(())
+    }
+
+    fn remove_sender_nonces(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::SenderNonceTransactions>()?;
+        self.tx.delete::<tables::ExExCheckpoints>(SENDER_NONCES_EXEX_ID.to_string(), None)?;
+        Ok(())
+    }
+}
+
+impl<TX: DbTx> LogIndexReader for DatabaseProvider<TX> {
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ExExCheckpoints>(LOG_INDEX_EXEX_ID.to_string())?)
+    }
+
+    fn blocks_with_logs(
+        &self,
+        addresses: &[Address],
+        topics: &[B256],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        let blocks = match (addresses.is_empty(), topics.is_empty()) {
+            (true, true) => return Ok(range.collect()),
+            (false, true) => {
+                self.history_index_union_in_range::<_, tables::LogAddressBlocks>(addresses, range)?
+            }
+            (true, false) => {
+                self.history_index_union_in_range::<_, tables::LogTopicBlocks>(topics, range)?
+            }
+            (false, false) => {
+                let address_blocks = self
+                    .history_index_union_in_range::<_, tables::LogAddressBlocks>(
+                        addresses,
+                        range.clone(),
+                    )?;
+                let topic_blocks =
+                    self.history_index_union_in_range::<_, tables::LogTopicBlocks>(topics, range)?;
+                address_blocks.intersection(&topic_blocks).copied().collect()
+            }
+        };
+        Ok(blocks.into_iter().collect())
+    }
+}
+
+impl<TX: DbTxMut + DbTx> LogIndexWriter for DatabaseProvider<TX> {
+    fn insert_log_index(
+        &self,
+        addresses: BTreeMap<Address, Vec<BlockNumber>>,
+        topics: BTreeMap<B256, Vec<BlockNumber>>,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()> {
+        self.append_history_index::<_, tables::LogAddressBlocks>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicBlocks>(topics, ShardedKey::new)?;
+        self.tx.put::<tables::ExExCheckpoints>(LOG_INDEX_EXEX_ID.to_string(), checkpoint)?;
+        Ok(())
+    }
+
+    fn unwind_log_index(
+        &self,
+        addresses: BTreeSet<Address>,
+        topics: BTreeSet<B256>,
+        block: BlockNumber,
+    ) -> ProviderResult<()> {
+        self.unwind_history_index::<_, tables::LogAddressBlocks>(addresses, block + 1)?;
+        self.unwind_history_index::<_, tables::LogTopicBlocks>(topics, block + 1)?;
+        self.tx.put::<tables::ExExCheckpoints>(LOG_INDEX_EXEX_ID.to_string(), block)?;
+        Ok(())
+    }
+
+    fn remove_log_index(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::LogAddressBlocks>()?;
+        self.tx.clear::<tables::LogTopicBlocks>()?;
+        self.tx.delete::<tables::ExExCheckpoints>(LOG_INDEX_EXEX_ID.to_string(), None)?;
+        Ok(())
+    }
+}
+
 impl<TX: DbTx> StatsReader for DatabaseProvider<TX> {
     fn count_entries<T: Table>(&self) -> ProviderResult<usize> {
         let db_entries = self.tx.entries::<T>()?;
@@ -1,11 +1,12 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, BlockchainTreePendingStateProvider, BundleStateDataProvider, CanonChainTracker,
-    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, HeaderProvider, ProviderError, PruneCheckpointReader,
-    ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader, StateProviderBox,
-    StateProviderFactory, TransactionVariant, TransactionsProvider, TreeViewer,
-    WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BlockSource, BlockchainTreePendingStateProvider,
+    BundleStateDataProvider, CanonChainTracker, CanonStateNotifications, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider, HeaderProvider,
+    LogIndexReader, ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    ReorgHistoryReader, SenderNoncesReader, StageCheckpointReader, StateProviderBox,
+    StateProviderFactory, TokenTransfersReader, TransactionVariant, TransactionsProvider,
+    TreeViewer, WithdrawalsProvider,
 };
 use reth_db::{
     database::Database,
@@ -15,8 +16,8 @@ use reth_evm::ConfigureEvmEnv;
 use reth_interfaces::{
     blockchain_tree::{
         error::{CanonicalError, InsertBlockError},
-        BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer, CanonicalOutcome,
-        InsertPayloadOk,
+        BlockStatus, BlockValidationKind, BlockchainTreeEngine, BlockchainTreeViewer,
+        CanonicalOutcome, InsertPayloadOk,
     },
     provider::ProviderResult,
     RethResult,
@@ -25,14 +26,14 @@ use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
     BlockNumberOrTag, BlockWithSenders, ChainInfo, ChainSpec, Header, PruneCheckpoint,
-    PruneSegment, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    PruneSegment, Receipt, ReorgRecord, SealedBlock, SealedBlockWithSenders, SealedHeader,
+    TokenTransfer, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
+    Withdrawal, Withdrawals, B256, U256,
 };
 use revm::primitives::{BlockEnv, CfgEnvWithHandlerCfg};
 use std::{
     collections::{BTreeMap, HashSet},
-    ops::{RangeBounds, RangeInclusive},
+    ops::{Range, RangeBounds, RangeInclusive},
     sync::Arc,
     time::Instant,
 };
@@ -43,8 +44,8 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
-    StaticFileWriter,
+    StaticFileBlockIndex, StaticFileJarProvider, StaticFileProvider, StaticFileProviderRW,
+    StaticFileProviderRWRefMut, StaticFileWriter,
 };
 
 mod state;
@@ -551,6 +552,96 @@ where
     }
 }
 
+impl<DB> ReorgHistoryReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
+        self.database.reorg_history(limit)
+    }
+}
+
+impl<DB> AddressTransactionsReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn address_transactions_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.address_transactions_checkpoint()
+    }
+
+    fn address_transactions(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        self.database.address_transactions(address, offset, limit)
+    }
+
+    fn address_transactions_by_range(
+        &self,
+        address: Address,
+        range: Range<TxNumber>,
+        reverse: bool,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        self.database.address_transactions_by_range(address, range, reverse, limit)
+    }
+}
+
+impl<DB> TokenTransfersReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn token_transfers_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.token_transfers_checkpoint()
+    }
+
+    fn token_transfers(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TokenTransfer>>> {
+        self.database.token_transfers(address, offset, limit)
+    }
+}
+
+impl<DB> LogIndexReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.log_index_checkpoint()
+    }
+
+    fn blocks_with_logs(
+        &self,
+        addresses: &[Address],
+        topics: &[B256],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.blocks_with_logs(addresses, topics, range)
+    }
+}
+
+impl<DB> SenderNoncesReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn sender_nonces_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.sender_nonces_checkpoint()
+    }
+
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> ProviderResult<Option<TxHash>> {
+        self.database.transaction_hash_by_sender_and_nonce(sender, nonce)
+    }
+}
+
 impl<DB> ChainSpecProvider for BlockchainProvider<DB>
 where
     DB: Send + Sync,
@@ -652,6 +743,13 @@ where
         self.tree.insert_block(block, validation_kind)
     }
 
+    fn validate_block_without_insert(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BlockStatus, InsertBlockError> {
+        self.tree.validate_block_without_insert(block)
+    }
+
     fn finalize_block(&self, finalized_block: BlockNumber) {
         self.tree.finalize_block(finalized_block)
     }
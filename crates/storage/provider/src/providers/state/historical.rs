@@ -309,8 +309,13 @@ impl<'b, TX: DbTx> StateProvider for HistoricalStateProviderRef<'b, TX> {
     }
 
     /// Get account and storage proofs.
-    fn proof(&self, _address: Address, _keys: &[B256]) -> ProviderResult<AccountProof> {
-        Err(ProviderError::StateRootNotAvailableForHistoricalBlock)
+    ///
+    /// The proofs are generated by reverting the latest state to this block in memory, which is
+    /// expensive for old blocks.
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.revert_state()?
+            .account_proof(self.tx, address, keys)
+            .map_err(|err| ProviderError::Database(err.into()))
     }
 }
 
@@ -0,0 +1,176 @@
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{static_file::SegmentHeader, BlockNumber, TxNumber};
+use std::{
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// The file extension used for the block index of transaction based static files.
+const BLOCK_INDEX_FILE_EXTENSION: &str = "blocks";
+
+/// Compact index mapping every block of a transaction based static file to its range of rows.
+///
+/// It's stored next to the static file data as a list of little-endian `u32` transaction counts,
+/// one per block, starting at the first block of the static file. It allows serving all
+/// transactions or receipts of a block without looking up its body indices in the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaticFileBlockIndex {
+    /// Row offsets relative to the first transaction of the static file. Block `i` of the static
+    /// file spans `offsets[i]..offsets[i + 1]`.
+    offsets: Vec<u64>,
+}
+
+impl Default for StaticFileBlockIndex {
+    fn default() -> Self {
+        Self { offsets: vec![0] }
+    }
+}
+
+impl StaticFileBlockIndex {
+    /// Creates the index from the number of transactions of each block.
+    pub fn from_tx_counts(tx_counts: impl IntoIterator<Item = u64>) -> Self {
+        let mut index = Self::default();
+        for tx_count in tx_counts {
+            index.offsets.push(index.tx_len() + tx_count);
+        }
+        index
+    }
+
+    /// Returns the path of the block index belonging to the static file at `data_path`.
+    pub fn path(data_path: &Path) -> PathBuf {
+        data_path.with_extension(BLOCK_INDEX_FILE_EXTENSION)
+    }
+
+    /// Loads the block index belonging to the static file at `data_path`, if it exists.
+    pub fn load(data_path: &Path) -> ProviderResult<Option<Self>> {
+        let path = Self::path(data_path);
+        if !path.exists() {
+            return Ok(None)
+        }
+
+        let data = reth_primitives::fs::read(path)?;
+        Ok(Some(Self::from_tx_counts(
+            data.chunks_exact(4)
+                .map(|count| u32::from_le_bytes(count.try_into().expect("qed")) as u64),
+        )))
+    }
+
+    /// Writes the block index next to the static file at `data_path`.
+    pub fn save(&self, data_path: &Path) -> ProviderResult<()> {
+        let data = self
+            .offsets
+            .windows(2)
+            .flat_map(|window| ((window[1] - window[0]) as u32).to_le_bytes())
+            .collect::<Vec<_>>();
+
+        // Write to a temporary file first, so a crash never leaves a partially written index.
+        let path = Self::path(data_path);
+        let tmp_path = path.with_extension(format!("{BLOCK_INDEX_FILE_EXTENSION}.tmp"));
+        reth_primitives::fs::write(&tmp_path, data)?;
+        reth_primitives::fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    /// Deletes the block index belonging to the static file at `data_path`, if it exists.
+    pub fn delete(data_path: &Path) -> ProviderResult<()> {
+        let path = Self::path(data_path);
+        if path.exists() {
+            reth_primitives::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Number of blocks in the index.
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns `true` if the index has no blocks.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of transactions in the index.
+    pub fn tx_len(&self) -> u64 {
+        *self.offsets.last().expect("not empty")
+    }
+
+    /// Adds a new block without transactions.
+    pub fn push_block(&mut self) {
+        self.offsets.push(self.tx_len());
+    }
+
+    /// Adds a transaction to the last block.
+    pub fn push_tx(&mut self) {
+        *self.offsets.last_mut().expect("not empty") += 1;
+    }
+
+    /// Keeps only the first `blocks` blocks and `txs` transactions.
+    pub fn truncate(&mut self, blocks: usize, txs: u64) {
+        self.offsets.truncate(blocks + 1);
+        for offset in &mut self.offsets {
+            *offset = (*offset).min(txs);
+        }
+    }
+
+    /// Returns `true` if the index covers exactly the blocks and transactions of the static file.
+    pub fn is_consistent(&self, header: &SegmentHeader) -> bool {
+        self.len() as u64 == header.block_len().unwrap_or_default() &&
+            self.tx_len() == header.tx_len().unwrap_or_default()
+    }
+
+    /// Returns the range of transaction numbers of the given block.
+    pub fn tx_range(&self, header: &SegmentHeader, block: BlockNumber) -> Option<Range<TxNumber>> {
+        let index = block.checked_sub(header.block_start()?)? as usize;
+        let start = *self.offsets.get(index)?;
+        let end = *self.offsets.get(index + 1)?;
+        if start == end {
+            return Some(0..0)
+        }
+
+        let tx_start = header.tx_start()?;
+        Some(tx_start + start..tx_start + end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::StaticFileSegment;
+
+    #[test]
+    fn block_index_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_path = dir.path().join("static_file_receipts_0_499999");
+
+        let mut index = StaticFileBlockIndex::default();
+        for tx_count in [2, 0, 3] {
+            index.push_block();
+            for _ in 0..tx_count {
+                index.push_tx();
+            }
+        }
+        assert_eq!(index, StaticFileBlockIndex::from_tx_counts([2, 0, 3]));
+
+        index.save(&data_path).unwrap();
+        assert_eq!(StaticFileBlockIndex::load(&data_path).unwrap(), Some(index.clone()));
+
+        let header = SegmentHeader::new(
+            (10..=12).into(),
+            Some((10..=12).into()),
+            Some((100..=104).into()),
+            StaticFileSegment::Receipts,
+        );
+        assert!(index.is_consistent(&header));
+        assert_eq!(index.tx_range(&header, 10), Some(100..102));
+        assert_eq!(index.tx_range(&header, 11), Some(0..0));
+        assert_eq!(index.tx_range(&header, 12), Some(102..105));
+        assert_eq!(index.tx_range(&header, 13), None);
+
+        index.truncate(2, 2);
+        assert_eq!(index, StaticFileBlockIndex::from_tx_counts([2, 0]));
+
+        StaticFileBlockIndex::delete(&data_path).unwrap();
+        assert_eq!(StaticFileBlockIndex::load(&data_path).unwrap(), None);
+    }
+}
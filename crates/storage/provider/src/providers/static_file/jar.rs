@@ -16,7 +16,7 @@ use reth_primitives::{
     TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, B256, U256,
 };
 use std::{
-    ops::{Deref, RangeBounds},
+    ops::{Deref, Range, RangeBounds},
     sync::Arc,
 };
 
@@ -49,7 +49,7 @@ impl<'a> StaticFileJarProvider<'a> {
     where
         'b: 'a,
     {
-        let result = StaticFileCursor::new(self.value(), self.mmap_handle())?;
+        let result = StaticFileCursor::new(self.value(), self.data_reader())?;
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -73,6 +73,12 @@ impl<'a> StaticFileJarProvider<'a> {
         self.metrics = Some(metrics);
         self
     }
+
+    /// Returns the range of transaction numbers of the given block, if the static file has a block
+    /// index.
+    pub fn block_tx_range(&self, block: BlockNumber) -> Option<Range<TxNumber>> {
+        self.jar.value().block_index()?.tx_range(self.user_header(), block)
+    }
 }
 
 impl<'a> HeaderProvider for StaticFileJarProvider<'a> {
@@ -304,10 +310,16 @@ impl<'a> ReceiptProvider for StaticFileJarProvider<'a> {
         Ok(None)
     }
 
-    fn receipts_by_block(&self, _block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
-        // Related to indexing tables. StaticFile should get the tx_range and call static file
-        // provider with `receipt()` instead for each
-        Err(ProviderError::UnsupportedProvider)
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        // Related to indexing tables. Only static files with a block index can resolve the
+        // tx_range of a block by themselves.
+        let BlockHashOrNumber::Number(number) = block else {
+            return Err(ProviderError::UnsupportedProvider)
+        };
+        match self.block_tx_range(number) {
+            Some(range) => self.receipts_by_tx_range(range).map(Some),
+            None => Err(ProviderError::UnsupportedProvider),
+        }
     }
 
     fn receipts_by_tx_range(
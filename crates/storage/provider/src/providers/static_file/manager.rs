@@ -1,6 +1,6 @@
 use super::{
-    metrics::StaticFileProviderMetrics, LoadedJar, StaticFileJarProvider, StaticFileProviderRW,
-    StaticFileProviderRWRefMut, BLOCKS_PER_STATIC_FILE,
+    metrics::StaticFileProviderMetrics, LoadedJar, StaticFileBlockIndex, StaticFileJarProvider,
+    StaticFileProviderRW, StaticFileProviderRWRefMut, BLOCKS_PER_STATIC_FILE,
 };
 use crate::{
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
@@ -17,7 +17,7 @@ use reth_db::{
     tables,
 };
 use reth_interfaces::provider::{ProviderError, ProviderResult};
-use reth_nippy_jar::NippyJar;
+use reth_nippy_jar::{DataReaderMode, NippyJar};
 use reth_primitives::{
     keccak256,
     static_file::{find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive},
@@ -78,6 +78,8 @@ pub struct StaticFileProviderInner {
     /// Maintains a map of StaticFile writers for each [`StaticFileSegment`]
     writers: DashMap<StaticFileSegment, StaticFileProviderRW>,
     metrics: Option<Arc<StaticFileProviderMetrics>>,
+    /// How the data and offsets files of loaded jars are read.
+    reader_mode: DataReaderMode,
 }
 
 impl StaticFileProviderInner {
@@ -91,6 +93,7 @@ impl StaticFileProviderInner {
             path: path.as_ref().to_path_buf(),
             load_filters: false,
             metrics: None,
+            reader_mode: DataReaderMode::default(),
         };
 
         Ok(provider)
@@ -114,6 +117,18 @@ impl StaticFileProvider {
         Self(Arc::new(provider))
     }
 
+    /// Sets the [`DataReaderMode`] used to read static files, e.g. to avoid `mmap` on network
+    /// filesystems.
+    ///
+    /// Any jar loaded with the previous mode is dropped and reloaded on the next access.
+    pub fn with_reader_mode(self, mode: DataReaderMode) -> Self {
+        let mut provider =
+            Arc::try_unwrap(self.0).expect("should be called when initializing only");
+        provider.reader_mode = mode;
+        provider.map.clear();
+        Self(Arc::new(provider))
+    }
+
     /// Reports metrics for the static files.
     pub fn report_metrics(&self) -> ProviderResult<()> {
         let Some(metrics) = &self.metrics else { return Ok(()) };
@@ -252,7 +267,9 @@ impl StaticFileProvider {
             jar
         };
 
+        let data_path = jar.data_path().to_path_buf();
         jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        StaticFileBlockIndex::delete(&data_path)?;
 
         let mut segment_max_block = None;
         if fixed_block_range.start() > 0 {
@@ -284,7 +301,11 @@ impl StaticFileProvider {
                 jar.load_filters().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
             }
 
-            self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
+            self.map
+                .entry(key)
+                .insert(LoadedJar::new(jar, self.reader_mode.clone())?)
+                .downgrade()
+                .into()
         };
 
         if let Some(metrics) = &self.metrics {
@@ -399,7 +420,10 @@ impl StaticFileProvider {
                 }
 
                 // Update the cached provider.
-                self.map.insert((fixed_range.end(), segment), LoadedJar::new(jar)?);
+                self.map.insert(
+                    (fixed_range.end(), segment),
+                    LoadedJar::new(jar, self.reader_mode.clone())?,
+                );
 
                 // Delete any cached provider that no longer has an associated jar.
                 self.map.retain(|(end, seg), _| !(*seg == segment && *end > fixed_range.end()));
@@ -453,6 +477,30 @@ impl StaticFileProvider {
         self.static_files_max_block.read().get(&segment).copied()
     }
 
+    /// Gets the receipts of a block from static files by using the block index of the receipts
+    /// static file.
+    ///
+    /// Returns `None` if the block is not in static files, or if its static file has no block
+    /// index.
+    pub fn receipts_by_block_index(
+        &self,
+        block: BlockNumber,
+    ) -> ProviderResult<Option<Vec<Receipt>>> {
+        if self
+            .get_highest_static_file_block(StaticFileSegment::Receipts)
+            .map_or(true, |highest| block > highest)
+        {
+            return Ok(None)
+        }
+
+        let provider =
+            self.get_segment_provider_from_block(StaticFileSegment::Receipts, block, None)?;
+        match provider.block_tx_range(block) {
+            Some(range) => provider.receipts_by_tx_range(range).map(Some),
+            None => Ok(None),
+        }
+    }
+
     /// Gets the highest static file transaction.
     pub fn get_highest_static_file_tx(&self, segment: StaticFileSegment) -> Option<TxNumber> {
         self.static_files_tx_index
@@ -853,8 +901,12 @@ impl ReceiptProvider for StaticFileProvider {
         Ok(None)
     }
 
-    fn receipts_by_block(&self, _block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
-        unreachable!()
+    fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>> {
+        match block {
+            BlockHashOrNumber::Number(number) => self.receipts_by_block_index(number),
+            // Required data not present in static_files
+            BlockHashOrNumber::Hash(_) => Err(ProviderError::UnsupportedProvider),
+        }
     }
 
     fn receipts_by_tx_range(
@@ -9,8 +9,11 @@ pub use writer::{StaticFileProviderRW, StaticFileProviderRWRefMut};
 
 mod metrics;
 
+mod block_index;
+pub use block_index::StaticFileBlockIndex;
+
 use reth_interfaces::provider::{ProviderError, ProviderResult};
-use reth_nippy_jar::NippyJar;
+use reth_nippy_jar::{DataReader, DataReaderMode, NippyJar};
 use reth_primitives::{static_file::SegmentHeader, StaticFileSegment};
 use std::{ops::Deref, sync::Arc};
 
@@ -19,27 +22,41 @@ const BLOCKS_PER_STATIC_FILE: u64 = 500_000;
 /// Alias type for each specific `NippyJar`.
 type LoadedJarRef<'a> = dashmap::mapref::one::Ref<'a, (u64, StaticFileSegment), LoadedJar>;
 
-/// Helper type to reuse an associated static file mmap handle on created cursors.
+/// Helper type to reuse an associated static file data reader on created cursors.
 #[derive(Debug)]
 pub struct LoadedJar {
     jar: NippyJar<SegmentHeader>,
-    mmap_handle: Arc<reth_nippy_jar::DataReader>,
+    data_reader: Arc<DataReader>,
+    /// Block index of transaction based static files, if it exists and matches the static file.
+    block_index: Option<StaticFileBlockIndex>,
 }
 
 impl LoadedJar {
-    fn new(jar: NippyJar<SegmentHeader>) -> ProviderResult<Self> {
-        match jar.open_data_reader() {
+    fn new(jar: NippyJar<SegmentHeader>, mode: DataReaderMode) -> ProviderResult<Self> {
+        let block_index = if jar.user_header().segment().is_headers() {
+            None
+        } else {
+            StaticFileBlockIndex::load(jar.data_path())?
+                .filter(|index| index.is_consistent(jar.user_header()))
+        };
+
+        match jar.open_data_reader_with_mode(mode) {
             Ok(data_reader) => {
-                let mmap_handle = Arc::new(data_reader);
-                Ok(Self { jar, mmap_handle })
+                let data_reader = Arc::new(data_reader);
+                Ok(Self { jar, data_reader, block_index })
             }
             Err(e) => Err(ProviderError::NippyJar(e.to_string())),
         }
     }
 
-    /// Returns a clone of the mmap handle that can be used to instantiate a cursor.
-    fn mmap_handle(&self) -> Arc<reth_nippy_jar::DataReader> {
-        self.mmap_handle.clone()
+    /// Returns the block index of the static file, if any.
+    fn block_index(&self) -> Option<&StaticFileBlockIndex> {
+        self.block_index.as_ref()
+    }
+
+    /// Returns a clone of the data reader that can be used to instantiate a cursor.
+    fn data_reader(&self) -> Arc<DataReader> {
+        self.data_reader.clone()
     }
 
     fn segment(&self) -> StaticFileSegment {
@@ -1,7 +1,8 @@
 use crate::providers::static_file::metrics::StaticFileProviderOperation;
 
 use super::{
-    manager::StaticFileProviderInner, metrics::StaticFileProviderMetrics, StaticFileProvider,
+    manager::StaticFileProviderInner, metrics::StaticFileProviderMetrics, StaticFileBlockIndex,
+    StaticFileProvider,
 };
 use dashmap::mapref::one::RefMut;
 use reth_codecs::Compact;
@@ -34,6 +35,11 @@ pub struct StaticFileProviderRW {
     data_path: PathBuf,
     buf: Vec<u8>,
     metrics: Option<Arc<StaticFileProviderMetrics>>,
+    /// Block index of transaction based static files.
+    ///
+    /// `None` for headers, or if the static file was written without one. It can be rebuilt with
+    /// `reth db static-file-block-index`.
+    block_index: Option<StaticFileBlockIndex>,
 }
 
 impl StaticFileProviderRW {
@@ -45,7 +51,42 @@ impl StaticFileProviderRW {
         metrics: Option<Arc<StaticFileProviderMetrics>>,
     ) -> ProviderResult<Self> {
         let (writer, data_path) = Self::open(segment, block, reader.clone(), metrics.clone())?;
-        Ok(Self { writer, data_path, buf: Vec::with_capacity(100), reader, metrics })
+        let block_index = Self::load_block_index(&writer, &data_path)?;
+        Ok(Self { writer, data_path, buf: Vec::with_capacity(100), reader, metrics, block_index })
+    }
+
+    /// Loads the block index of transaction based static files.
+    ///
+    /// A new static file starts with an empty index. An existing static file whose index is
+    /// missing or doesn't match its data is written without one.
+    fn load_block_index(
+        writer: &NippyJarWriter<SegmentHeader>,
+        data_path: &Path,
+    ) -> ProviderResult<Option<StaticFileBlockIndex>> {
+        let header = writer.user_header();
+        if header.segment().is_headers() {
+            return Ok(None)
+        }
+
+        if let Some(index) = StaticFileBlockIndex::load(data_path)? {
+            if index.is_consistent(header) {
+                return Ok(Some(index))
+            }
+        }
+
+        if header.block_range().is_none() && header.tx_range().is_none() {
+            return Ok(Some(StaticFileBlockIndex::default()))
+        }
+
+        Ok(None)
+    }
+
+    /// Writes the block index next to the static file data.
+    fn commit_block_index(&self) -> ProviderResult<()> {
+        if let Some(index) = &self.block_index {
+            index.save(&self.data_path)?;
+        }
+        Ok(())
     }
 
     fn open(
@@ -102,6 +143,7 @@ impl StaticFileProviderRW {
 
         // Commits offsets and new user_header to disk
         self.writer.commit().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        self.commit_block_index()?;
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -135,6 +177,7 @@ impl StaticFileProviderRW {
         self.writer
             .commit_without_sync_all()
             .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+        self.commit_block_index()?;
 
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
@@ -207,10 +250,14 @@ impl StaticFileProviderRW {
 
                 *self.writer.user_header_mut() =
                     SegmentHeader::new(find_fixed_range(last_block + 1), None, None, segment);
+                self.block_index = Self::load_block_index(&self.writer, &self.data_path)?;
             }
         }
 
         let block = self.writer.user_header_mut().increment_block();
+        if let Some(index) = &mut self.block_index {
+            index.push_block();
+        }
         if let Some(metrics) = &self.metrics {
             metrics.record_segment_operation(
                 segment,
@@ -287,11 +334,13 @@ impl StaticFileProviderRW {
                     )?;
                     self.writer = writer;
                     self.data_path = data_path;
+                    self.block_index = Self::load_block_index(&self.writer, &self.data_path)?;
 
                     NippyJar::<SegmentHeader>::load(&previous_snap)
                         .map_err(|e| ProviderError::NippyJar(e.to_string()))?
                         .delete()
                         .map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+                    StaticFileBlockIndex::delete(&previous_snap)?;
                 } else {
                     // Update `SegmentHeader`
                     self.writer.user_header_mut().prune(len);
@@ -318,6 +367,14 @@ impl StaticFileProviderRW {
         if let Some(last_block) = last_block {
             let header = self.writer.user_header_mut();
             header.set_block_range(header.expected_block_start(), last_block);
+
+            let header = self.writer.user_header();
+            if let Some(index) = &mut self.block_index {
+                index.truncate(
+                    header.block_len().unwrap_or_default() as usize,
+                    header.tx_len().unwrap_or_default(),
+                );
+            }
         }
 
         // Commits new changes to disk.
@@ -353,6 +410,9 @@ impl StaticFileProviderRW {
         } else {
             self.writer.user_header_mut().increment_tx();
         }
+        if let Some(index) = &mut self.block_index {
+            index.push_tx();
+        }
 
         self.append_column(value)?;
 
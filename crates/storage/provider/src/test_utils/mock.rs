@@ -1,9 +1,10 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BundleStateDataProvider, ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider,
-    ReceiptProviderIdExt, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
-    TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, BundleStateDataProvider, ChainSpecProvider, ChangeSetReader,
+    EvmEnvProvider, HeaderProvider, LogIndexReader, ReceiptProviderIdExt, ReorgHistoryReader,
+    SenderNoncesReader, StateProvider, StateProviderBox, StateProviderFactory, StateRootProvider,
+    TokenTransfersReader, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use parking_lot::Mutex;
 use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
@@ -12,9 +13,9 @@ use reth_interfaces::provider::{ProviderError, ProviderResult};
 use reth_primitives::{
     keccak256, trie::AccountProof, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId,
     BlockNumber, BlockWithSenders, Bytecode, Bytes, ChainInfo, ChainSpec, Header, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    ReorgRecord, SealedBlock, SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue,
+    TokenTransfer, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
+    Withdrawal, Withdrawals, B256, U256,
 };
 use reth_trie::updates::TrieUpdates;
 use revm::{
@@ -23,7 +24,7 @@ use revm::{
 };
 use std::{
     collections::{BTreeMap, HashMap},
-    ops::{RangeBounds, RangeInclusive},
+    ops::{Range, RangeBounds, RangeInclusive},
     sync::Arc,
 };
 
@@ -38,6 +39,8 @@ pub struct MockEthProvider {
     pub accounts: Arc<Mutex<HashMap<Address, ExtendedAccount>>>,
     /// Local chain spec
     pub chain_spec: Arc<ChainSpec>,
+    /// Local reorg history, oldest first
+    pub reorgs: Arc<Mutex<Vec<ReorgRecord>>>,
 }
 
 impl Default for MockEthProvider {
@@ -47,6 +50,7 @@ impl Default for MockEthProvider {
             headers: Default::default(),
             accounts: Default::default(),
             chain_spec: Arc::new(reth_primitives::ChainSpecBuilder::mainnet().build()),
+            reorgs: Default::default(),
         }
     }
 }
@@ -95,6 +99,11 @@ impl MockEthProvider {
         self.blocks.lock().insert(hash, block);
     }
 
+    /// Add reorg to local reorg history
+    pub fn add_reorg(&self, reorg: ReorgRecord) {
+        self.reorgs.lock().push(reorg);
+    }
+
     /// Add multiple blocks to local block store
     pub fn extend_blocks(&self, iter: impl IntoIterator<Item = (B256, Block)>) {
         for (hash, block) in iter.into_iter() {
@@ -468,10 +477,10 @@ impl BlockReader for MockEthProvider {
 
     fn block_with_senders(
         &self,
-        _id: BlockHashOrNumber,
+        id: BlockHashOrNumber,
         _transaction_kind: TransactionVariant,
     ) -> ProviderResult<Option<BlockWithSenders>> {
-        Ok(None)
+        Ok(self.block(id)?.and_then(|block| block.with_recovered_senders()))
     }
 
     fn block_range(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<Vec<Block>> {
@@ -687,3 +696,78 @@ impl ChangeSetReader for MockEthProvider {
         Ok(Vec::default())
     }
 }
+
+impl ReorgHistoryReader for MockEthProvider {
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
+        Ok(self.reorgs.lock().iter().rev().take(limit).cloned().collect())
+    }
+}
+
+impl AddressTransactionsReader for MockEthProvider {
+    fn address_transactions_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn address_transactions(
+        &self,
+        _address: Address,
+        _offset: usize,
+        _limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        Ok(None)
+    }
+
+    fn address_transactions_by_range(
+        &self,
+        _address: Address,
+        _range: Range<TxNumber>,
+        _reverse: bool,
+        _limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        Ok(None)
+    }
+}
+
+impl TokenTransfersReader for MockEthProvider {
+    fn token_transfers_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn token_transfers(
+        &self,
+        _address: Address,
+        _offset: usize,
+        _limit: usize,
+    ) -> ProviderResult<Option<Vec<TokenTransfer>>> {
+        Ok(None)
+    }
+}
+
+impl LogIndexReader for MockEthProvider {
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn blocks_with_logs(
+        &self,
+        _addresses: &[Address],
+        _topics: &[B256],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(range.collect())
+    }
+}
+
+impl SenderNoncesReader for MockEthProvider {
+    fn sender_nonces_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        _sender: Address,
+        _nonce: u64,
+    ) -> ProviderResult<Option<TxHash>> {
+        Ok(None)
+    }
+}
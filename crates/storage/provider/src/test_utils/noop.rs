@@ -1,22 +1,30 @@
 use crate::{
     traits::{BlockSource, ReceiptProvider},
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    ChainSpecProvider, ChangeSetReader, EvmEnvProvider, HeaderProvider, PruneCheckpointReader,
-    ReceiptProviderIdExt, StageCheckpointReader, StateProvider, StateProviderBox,
-    StateProviderFactory, StateRootProvider, TransactionVariant, TransactionsProvider,
-    WithdrawalsProvider,
+    AccountReader, AddressTransactionsReader, BlockHashReader, BlockIdReader, BlockNumReader,
+    BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader, EvmEnvProvider,
+    HeaderProvider, LogIndexReader, PruneCheckpointReader, ReceiptProviderIdExt,
+    ReorgHistoryReader, SenderNoncesReader, StageCheckpointReader, StateProvider, StateProviderBox,
+    StateProviderFactory, StateRootProvider, TokenTransfersReader, TransactionVariant,
+    TransactionsProvider, WithdrawalsProvider,
 };
 use reth_db::models::{AccountBeforeTx, StoredBlockBodyIndices};
 use reth_evm::ConfigureEvmEnv;
-use reth_interfaces::provider::ProviderResult;
+use reth_interfaces::{
+    blockchain_tree::{
+        error::{BlockchainTreeError, CanonicalError, InsertBlockError},
+        BlockStatus, BlockValidationKind, BlockchainTreeEngine, CanonicalOutcome, InsertPayloadOk,
+    },
+    provider::ProviderResult,
+    RethResult,
+};
 use reth_primitives::{
     stage::{StageCheckpoint, StageId},
     trie::AccountProof,
     Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumber, BlockWithSenders,
-    Bytecode, ChainInfo, ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, SealedBlock,
-    SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    MAINNET, U256,
+    Bytecode, ChainInfo, ChainSpec, Header, PruneCheckpoint, PruneSegment, Receipt, ReorgRecord,
+    SealedBlock, SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, TokenTransfer,
+    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, MAINNET, U256,
 };
 use reth_trie::updates::TrieUpdates;
 use revm::{
@@ -24,7 +32,7 @@ use revm::{
     primitives::{BlockEnv, CfgEnvWithHandlerCfg},
 };
 use std::{
-    ops::{RangeBounds, RangeInclusive},
+    ops::{Range, RangeBounds, RangeInclusive},
     sync::Arc,
 };
 
@@ -448,6 +456,125 @@ impl WithdrawalsProvider for NoopProvider {
     }
 }
 
+impl ReorgHistoryReader for NoopProvider {
+    fn reorg_history(&self, _limit: usize) -> ProviderResult<Vec<ReorgRecord>> {
+        Ok(Vec::new())
+    }
+}
+
+impl AddressTransactionsReader for NoopProvider {
+    fn address_transactions_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn address_transactions(
+        &self,
+        _address: Address,
+        _offset: usize,
+        _limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        Ok(None)
+    }
+
+    fn address_transactions_by_range(
+        &self,
+        _address: Address,
+        _range: Range<TxNumber>,
+        _reverse: bool,
+        _limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>> {
+        Ok(None)
+    }
+}
+
+impl TokenTransfersReader for NoopProvider {
+    fn token_transfers_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn token_transfers(
+        &self,
+        _address: Address,
+        _offset: usize,
+        _limit: usize,
+    ) -> ProviderResult<Option<Vec<TokenTransfer>>> {
+        Ok(None)
+    }
+}
+
+impl LogIndexReader for NoopProvider {
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn blocks_with_logs(
+        &self,
+        _addresses: &[Address],
+        _topics: &[B256],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        Ok(range.collect())
+    }
+}
+
+impl SenderNoncesReader for NoopProvider {
+    fn sender_nonces_checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        _sender: Address,
+        _nonce: u64,
+    ) -> ProviderResult<Option<TxHash>> {
+        Ok(None)
+    }
+}
+
+impl BlockchainTreeEngine for NoopProvider {
+    fn buffer_block(&self, _block: SealedBlockWithSenders) -> Result<(), InsertBlockError> {
+        Ok(())
+    }
+
+    fn insert_block(
+        &self,
+        block: SealedBlockWithSenders,
+        _validation_kind: BlockValidationKind,
+    ) -> Result<InsertPayloadOk, InsertBlockError> {
+        Err(InsertBlockError::tree_error(
+            BlockchainTreeError::BlockHashNotFoundInChain { block_hash: block.hash() },
+            block.block,
+        ))
+    }
+
+    fn validate_block_without_insert(
+        &self,
+        block: SealedBlockWithSenders,
+    ) -> Result<BlockStatus, InsertBlockError> {
+        Err(InsertBlockError::tree_error(
+            BlockchainTreeError::BlockHashNotFoundInChain { block_hash: block.hash() },
+            block.block,
+        ))
+    }
+
+    fn finalize_block(&self, _finalized_block: BlockNumber) {}
+
+    fn connect_buffered_blocks_to_canonical_hashes_and_finalize(
+        &self,
+        _last_finalized_block: BlockNumber,
+    ) -> RethResult<()> {
+        Ok(())
+    }
+
+    fn connect_buffered_blocks_to_canonical_hashes(&self) -> RethResult<()> {
+        Ok(())
+    }
+
+    fn make_canonical(&self, block_hash: BlockHash) -> Result<CanonicalOutcome, CanonicalError> {
+        Err(BlockchainTreeError::BlockHashNotFoundInChain { block_hash }.into())
+    }
+}
+
 impl PruneCheckpointReader for NoopProvider {
     fn get_prune_checkpoint(
         &self,
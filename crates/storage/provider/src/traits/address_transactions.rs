@@ -0,0 +1,68 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, TxNumber};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+/// Id of the built-in ExEx that indexes the transactions of each address.
+pub const ADDRESS_TRANSACTIONS_EXEX_ID: &str = "AddressTransactions";
+
+/// The trait for fetching the transactions sent from or to an address.
+#[auto_impl(&, Arc)]
+pub trait AddressTransactionsReader: Send + Sync {
+    /// Returns the highest block whose transactions are indexed by address.
+    ///
+    /// Returns `None` if the address transactions index is not enabled.
+    fn address_transactions_checkpoint(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the numbers of the transactions sent from or to the address, the most recent first.
+    ///
+    /// Skips the `offset` most recent transactions and returns at most `limit` transactions.
+    ///
+    /// Returns `None` if the address transactions index is not enabled.
+    fn address_transactions(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>>;
+
+    /// Returns the numbers of the transactions sent from or to the address within the range of
+    /// transaction numbers, in ascending order or in descending order if `reverse` is set.
+    ///
+    /// Returns at most `limit` transactions.
+    ///
+    /// Returns `None` if the address transactions index is not enabled.
+    fn address_transactions_by_range(
+        &self,
+        address: Address,
+        range: Range<TxNumber>,
+        reverse: bool,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TxNumber>>>;
+}
+
+/// The trait for maintaining the index of the transactions sent from or to an address.
+#[auto_impl(&, Arc, Box)]
+pub trait AddressTransactionsWriter: Send + Sync {
+    /// Appends the numbers of the transactions of each address, which are expected in ascending
+    /// order above the indexed ones, and sets the highest indexed block to `checkpoint`.
+    fn insert_address_transactions(
+        &self,
+        transactions: BTreeMap<Address, Vec<TxNumber>>,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()>;
+
+    /// Removes the transactions of the blocks above `block` of the addresses of the reverted
+    /// transactions and sets the highest indexed block to `block`.
+    fn unwind_address_transactions(
+        &self,
+        addresses: BTreeSet<Address>,
+        block: BlockNumber,
+    ) -> ProviderResult<()>;
+
+    /// Removes all indexed transactions and disables the index.
+    fn remove_address_transactions(&self) -> ProviderResult<()>;
+}
@@ -1,8 +1,10 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, StageCheckpointReader, StateProviderFactory,
+    AccountReader, AddressTransactionsReader, BlockReaderIdExt, CanonStateSubscriptions,
+    ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory, EvmEnvProvider, LogIndexReader,
+    PruneCheckpointReader, ReorgHistoryReader, SenderNoncesReader, StageCheckpointReader,
+    StateProviderFactory, TokenTransfersReader,
 };
 use reth_db::database::Database;
 
@@ -15,8 +17,14 @@ pub trait FullProvider<DB: Database>:
     + EvmEnvProvider
     + ChainSpecProvider
     + ChangeSetReader
+    + ReorgHistoryReader
+    + AddressTransactionsReader
+    + TokenTransfersReader
+    + SenderNoncesReader
+    + LogIndexReader
     + CanonStateSubscriptions
     + StageCheckpointReader
+    + PruneCheckpointReader
     + Clone
     + Unpin
     + 'static
@@ -31,8 +39,14 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + ReorgHistoryReader
+        + AddressTransactionsReader
+        + TokenTransfersReader
+        + SenderNoncesReader
+        + LogIndexReader
         + CanonStateSubscriptions
         + StageCheckpointReader
+        + PruneCheckpointReader
         + Clone
         + Unpin
         + 'static
@@ -0,0 +1,58 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, B256};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+/// Id of the built-in ExEx that indexes the blocks with logs by address and first topic.
+pub const LOG_INDEX_EXEX_ID: &str = "LogIndex";
+
+/// The trait for looking up the blocks that contain logs of an address or with a first topic.
+#[auto_impl(&, Arc)]
+pub trait LogIndexReader: Send + Sync {
+    /// Returns the highest block whose logs are indexed.
+    ///
+    /// Returns `None` if the log index is not enabled.
+    fn log_index_checkpoint(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the blocks of the range, in ascending order, that contain a log emitted by one of
+    /// the `addresses` and a log whose first topic is one of the `topics`.
+    ///
+    /// An empty list of addresses or topics matches all blocks, at least one of them is expected
+    /// to be non-empty. The matching address and topic are not necessarily of the same log, so
+    /// the logs of the returned blocks still need to be filtered.
+    fn blocks_with_logs(
+        &self,
+        addresses: &[Address],
+        topics: &[B256],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
+
+/// The trait for maintaining the index of the blocks with logs by address and first topic.
+#[auto_impl(&, Arc, Box)]
+pub trait LogIndexWriter: Send + Sync {
+    /// Appends the blocks with logs of each address and first topic, which are expected in
+    /// ascending order above the current checkpoint, and sets the highest indexed block to
+    /// `checkpoint`.
+    fn insert_log_index(
+        &self,
+        addresses: BTreeMap<Address, Vec<BlockNumber>>,
+        topics: BTreeMap<B256, Vec<BlockNumber>>,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()>;
+
+    /// Removes the blocks above `block` of the addresses and first topics of the reverted logs and
+    /// sets the highest indexed block to `block`.
+    fn unwind_log_index(
+        &self,
+        addresses: BTreeSet<Address>,
+        topics: BTreeSet<B256>,
+        block: BlockNumber,
+    ) -> ProviderResult<()>;
+
+    /// Removes all indexed blocks and disables the index.
+    fn remove_log_index(&self) -> ProviderResult<()>;
+}
@@ -72,6 +72,23 @@ pub use history::HistoryWriter;
 mod prune_checkpoint;
 pub use prune_checkpoint::{PruneCheckpointReader, PruneCheckpointWriter};
 
+mod reorg_history;
+pub use reorg_history::{ReorgHistoryReader, ReorgHistoryWriter};
+
+mod address_transactions;
+pub use address_transactions::{
+    AddressTransactionsReader, AddressTransactionsWriter, ADDRESS_TRANSACTIONS_EXEX_ID,
+};
+
+mod token_transfers;
+pub use token_transfers::{TokenTransfersReader, TokenTransfersWriter, TOKEN_TRANSFERS_EXEX_ID};
+
+mod sender_nonces;
+pub use sender_nonces::{SenderNoncesReader, SenderNoncesWriter, SENDER_NONCES_EXEX_ID};
+
+mod log_index;
+pub use log_index::{LogIndexReader, LogIndexWriter, LOG_INDEX_EXEX_ID};
+
 mod database_provider;
 pub use database_provider::DatabaseProviderFactory;
 
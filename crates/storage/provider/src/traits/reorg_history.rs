@@ -0,0 +1,17 @@
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::ReorgRecord;
+
+/// The trait for fetching the reorgs processed by the node.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ReorgHistoryReader: Send + Sync {
+    /// Returns up to `limit` of the most recent reorgs, newest first.
+    fn reorg_history(&self, limit: usize) -> ProviderResult<Vec<ReorgRecord>>;
+}
+
+/// The trait for recording the reorgs processed by the node.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait ReorgHistoryWriter: Send + Sync {
+    /// Appends the reorg to the history and removes the oldest reorgs, so that at most
+    /// `retention` reorgs are kept.
+    fn insert_reorg(&self, reorg: ReorgRecord, retention: u64) -> ProviderResult<()>;
+}
@@ -0,0 +1,45 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, TxHash};
+
+/// Id of the built-in ExEx that indexes the transactions by sender and nonce.
+pub const SENDER_NONCES_EXEX_ID: &str = "SenderNonces";
+
+/// The trait for looking up canonical transactions by sender and nonce.
+#[auto_impl(&, Arc)]
+pub trait SenderNoncesReader: Send + Sync {
+    /// Returns the highest block whose transactions are indexed by sender and nonce.
+    ///
+    /// Returns `None` if the sender nonces index is not enabled.
+    fn sender_nonces_checkpoint(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the hash of the indexed transaction of the sender with the nonce.
+    fn transaction_hash_by_sender_and_nonce(
+        &self,
+        sender: Address,
+        nonce: u64,
+    ) -> ProviderResult<Option<TxHash>>;
+}
+
+/// The trait for maintaining the index of the transactions by sender and nonce.
+#[auto_impl(&, Arc, Box)]
+pub trait SenderNoncesWriter: Send + Sync {
+    /// Inserts the transactions by sender and nonce and sets the highest indexed block to
+    /// `checkpoint`.
+    fn insert_sender_nonces(
+        &self,
+        transactions: Vec<(Address, u64, TxHash)>,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()>;
+
+    /// Removes the transactions of the reverted blocks above `block` and sets the highest indexed
+    /// block to `block`.
+    fn unwind_sender_nonces(
+        &self,
+        reverted: Vec<(Address, u64)>,
+        block: BlockNumber,
+    ) -> ProviderResult<()>;
+
+    /// Removes all transactions and disables the index.
+    fn remove_sender_nonces(&self) -> ProviderResult<()>;
+}
@@ -0,0 +1,48 @@
+use auto_impl::auto_impl;
+use reth_interfaces::provider::ProviderResult;
+use reth_primitives::{Address, BlockNumber, TokenTransfer};
+
+/// Id of the built-in ExEx that indexes the token transfers.
+pub const TOKEN_TRANSFERS_EXEX_ID: &str = "TokenTransfers";
+
+/// The trait for fetching the ERC-20 and ERC-721 token transfers sent from or to an address.
+#[auto_impl(&, Arc)]
+pub trait TokenTransfersReader: Send + Sync {
+    /// Returns the highest block whose token transfers are indexed.
+    ///
+    /// Returns `None` if the token transfers index is not enabled.
+    fn token_transfers_checkpoint(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the token transfers sent from or to the address, the most recent first.
+    ///
+    /// Skips the `offset` most recent transfers and returns at most `limit` transfers.
+    ///
+    /// Returns `None` if the token transfers index is not enabled.
+    fn token_transfers(
+        &self,
+        address: Address,
+        offset: usize,
+        limit: usize,
+    ) -> ProviderResult<Option<Vec<TokenTransfer>>>;
+}
+
+/// The trait for maintaining the index of the token transfers.
+#[auto_impl(&, Arc, Box)]
+pub trait TokenTransfersWriter: Send + Sync {
+    /// Appends the token transfers, which are expected in the order of the chain, and sets the
+    /// highest indexed block to `checkpoint`.
+    fn insert_token_transfers(
+        &self,
+        transfers: Vec<TokenTransfer>,
+        checkpoint: BlockNumber,
+    ) -> ProviderResult<()>;
+
+    /// Removes the token transfers of the blocks above `block` and sets the highest indexed block
+    /// to `block`.
+    ///
+    /// Returns the number of removed transfers.
+    fn unwind_token_transfers(&self, block: BlockNumber) -> ProviderResult<usize>;
+
+    /// Removes all token transfers and disables the index.
+    fn remove_token_transfers(&self) -> ProviderResult<()>;
+}
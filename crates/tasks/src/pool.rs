@@ -1,10 +1,14 @@
 //! Additional helpers for executing tracing calls
 
 use std::{
+    cell::RefCell,
     future::Future,
     panic::{catch_unwind, AssertUnwindSafe},
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     task::{ready, Context, Poll},
     thread,
 };
@@ -39,6 +43,84 @@ impl BlockingTaskGuard {
     }
 }
 
+thread_local! {
+    /// The cancellation token of the blocking task that runs on the current thread.
+    static CURRENT_TOKEN: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// Signals a blocking task that its result is no longer awaited, for example because the RPC call
+/// that spawned it timed out or its client disconnected.
+///
+/// Cancellation is cooperative: long running tasks are expected to check [Self::is_cancelled], or
+/// [is_current_task_cancelled] if the token is not passed to them, and return early.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a new token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the task.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if the task was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Returns a guard that cancels the task once it's dropped.
+    pub fn drop_guard(self) -> CancelOnDrop {
+        CancelOnDrop(self)
+    }
+
+    /// Makes this the token of the task on the current thread until the returned guard is
+    /// dropped.
+    pub fn enter(&self) -> EnteredCancellationToken {
+        let previous = CURRENT_TOKEN.with(|current| current.replace(Some(self.clone())));
+        EnteredCancellationToken { previous }
+    }
+
+    /// Returns the token of the blocking task that runs on the current thread, if any.
+    pub fn current() -> Option<Self> {
+        CURRENT_TOKEN.with(|current| current.borrow().clone())
+    }
+}
+
+/// Returns true if the blocking task that runs on the current thread was cancelled.
+///
+/// Tasks of the [BlockingTaskPool] and tasks that [entered](CancellationToken::enter) a token can
+/// be cancelled, this is always false for any other thread.
+pub fn is_current_task_cancelled() -> bool {
+    CURRENT_TOKEN.with(|current| current.borrow().as_ref().is_some_and(|t| t.is_cancelled()))
+}
+
+/// Cancels the [CancellationToken] once dropped.
+#[derive(Debug)]
+pub struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
+/// Restores the previous token of the current thread once dropped.
+#[derive(Debug)]
+pub struct EnteredCancellationToken {
+    previous: Option<CancellationToken>,
+}
+
+impl Drop for EnteredCancellationToken {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CURRENT_TOKEN.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
 /// Used to execute blocking tasks on a rayon threadpool from within a tokio runtime.
 ///
 /// This is a dedicated threadpool for blocking tasks which are CPU bound.
@@ -81,18 +163,21 @@ impl BlockingTaskPool {
     /// function's return value.
     ///
     /// If the function panics, the future will resolve to an error.
+    ///
+    /// The task is cancelled once the returned handle is dropped: the function is not run if it
+    /// hasn't started yet, otherwise [is_current_task_cancelled] returns true within the function.
     pub fn spawn<F, R>(&self, func: F) -> BlockingTaskHandle<R>
     where
         F: FnOnce() -> R + Send + 'static,
         R: Send + 'static,
     {
         let (tx, rx) = oneshot::channel();
+        let token = CancellationToken::new();
+        let task = cancellable_task(token.clone(), tx, func);
 
-        self.pool.spawn(move || {
-            let _result = tx.send(catch_unwind(AssertUnwindSafe(func)));
-        });
+        self.pool.spawn(task);
 
-        BlockingTaskHandle { rx }
+        BlockingTaskHandle { rx, _cancel: token.drop_guard() }
     }
 
     /// Asynchronous wrapper around Rayon's
@@ -102,18 +187,40 @@ impl BlockingTaskPool {
     /// function's return value.
     ///
     /// If the function panics, the future will resolve to an error.
+    ///
+    /// The task is cancelled once the returned handle is dropped, see [Self::spawn].
     pub fn spawn_fifo<F, R>(&self, func: F) -> BlockingTaskHandle<R>
     where
         F: FnOnce() -> R + Send + 'static,
         R: Send + 'static,
     {
         let (tx, rx) = oneshot::channel();
+        let token = CancellationToken::new();
+        let task = cancellable_task(token.clone(), tx, func);
 
-        self.pool.spawn_fifo(move || {
-            let _result = tx.send(catch_unwind(AssertUnwindSafe(func)));
-        });
+        self.pool.spawn_fifo(task);
 
-        BlockingTaskHandle { rx }
+        BlockingTaskHandle { rx, _cancel: token.drop_guard() }
+    }
+}
+
+/// Wraps the function into a task that is skipped if it's cancelled before it started, and that
+/// runs the function with the token entered.
+fn cancellable_task<F, R>(
+    token: CancellationToken,
+    tx: oneshot::Sender<thread::Result<R>>,
+    func: F,
+) -> impl FnOnce() + Send + 'static
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    move || {
+        if token.is_cancelled() {
+            return
+        }
+        let _entered = token.enter();
+        let _result = tx.send(catch_unwind(AssertUnwindSafe(func)));
     }
 }
 
@@ -128,6 +235,8 @@ impl BlockingTaskPool {
 pub struct BlockingTaskHandle<T> {
     #[pin]
     pub(crate) rx: oneshot::Receiver<thread::Result<T>>,
+    /// Cancels the task once the handle is dropped
+    _cancel: CancelOnDrop,
 }
 
 impl<T> Future for BlockingTaskHandle<T> {
@@ -170,4 +279,35 @@ mod tests {
         let res = res.await;
         assert!(res.is_err());
     }
+
+    #[tokio::test]
+    async fn blocking_pool_cancel() {
+        let pool =
+            BlockingTaskPool::new(BlockingTaskPool::builder().num_threads(1).build().unwrap());
+        let (started_tx, started_rx) = oneshot::channel();
+        let (cancelled_tx, cancelled_rx) = std::sync::mpsc::channel();
+        let first = pool.spawn(move || {
+            let _ = started_tx.send(());
+            while !is_current_task_cancelled() {
+                thread::yield_now();
+            }
+            let _ = cancelled_tx.send(());
+        });
+        // queued behind the first task
+        let ran = Arc::new(AtomicBool::new(false));
+        let second = pool.spawn({
+            let ran = ran.clone();
+            move || ran.store(true, Ordering::Relaxed)
+        });
+
+        started_rx.await.unwrap();
+        drop(second);
+        drop(first);
+        cancelled_rx.recv().unwrap();
+
+        // runs after the skipped second task
+        assert!(!pool.spawn(is_current_task_cancelled).await.unwrap());
+        assert!(!ran.load(Ordering::Relaxed));
+        assert!(!is_current_task_cancelled());
+    }
 }
@@ -1,8 +1,11 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
 
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Layer, Registry};
 
 use crate::formatter::LogFormat;
 
@@ -22,6 +25,11 @@ const RETH_LOG_FILE_NAME: &str = "reth.log";
 const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 4] =
     ["hyper::proto::h1=off", "trust_dns_proto=off", "trust_dns_resolver=off", "discv5=off"];
 
+/// The handle to reload the filter of the stdout layer, with the default directive and filters the
+/// layer was created with.
+static STDOUT_FILTER: OnceLock<(reload::Handle<EnvFilter, Registry>, Directive, String)> =
+    OnceLock::new();
+
 /// Manages the collection of layers for a tracing subscriber.
 ///
 /// `Layers` acts as a container for different logging layers such as stdout, file, or journald.
@@ -57,6 +65,8 @@ impl Layers {
 
     /// Adds a stdout layer with specified formatting and filtering.
     ///
+    /// The filter of the layer can be replaced later with [reload_stdout_filter].
+    ///
     /// # Type Parameters
     /// * `S` - The type of subscriber that will use these layers.
     ///
@@ -75,7 +85,9 @@ impl Layers {
         filters: &str,
         color: Option<String>,
     ) -> eyre::Result<()> {
-        let filter = build_env_filter(Some(default_directive), filters)?;
+        let filter = build_env_filter(Some(default_directive.clone()), filters)?;
+        let (filter, handle) = reload::Layer::new(filter);
+        let _ = STDOUT_FILTER.set((handle, default_directive, filters.to_string()));
         let layer = format.apply(filter, color, None);
         self.inner.push(layer.boxed());
         Ok(())
@@ -151,6 +163,19 @@ impl FileInfo {
     }
 }
 
+/// Replaces the filter directives of the stdout layer.
+///
+/// The filters the layer was created with take precedence, so `filters` is only applied if the
+/// layer was created without filters. If `filters` is `None`, the filters the layer was created
+/// with are restored. Does nothing if the tracer has no stdout layer.
+pub fn reload_stdout_filter(filters: Option<&str>) -> eyre::Result<()> {
+    let Some((handle, default_directive, initial)) = STDOUT_FILTER.get() else { return Ok(()) };
+    let filters = filters.filter(|_| initial.is_empty()).unwrap_or(initial);
+    let filter = build_env_filter(Some(default_directive.clone()), filters)?;
+    handle.reload(filter)?;
+    Ok(())
+}
+
 /// Builds an environment filter for logging.
 ///
 /// The events are filtered by `default_directive`, unless overridden by `RUST_LOG`.
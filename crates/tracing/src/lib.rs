@@ -49,7 +49,7 @@ pub use tracing_subscriber;
 
 // Re-export LogFormat
 pub use formatter::LogFormat;
-pub use layers::{FileInfo, FileWorkerGuard};
+pub use layers::{reload_stdout_filter, FileInfo, FileWorkerGuard};
 
 pub use test_tracer::TestTracer;
 
@@ -0,0 +1,80 @@
+//! Index of the blobs in the blob store by their versioned hash.
+
+use reth_primitives::{
+    eip4844::kzg_to_versioned_hash, kzg::KzgCommitment, BlobTransactionSidecar, TxHash, B256,
+};
+use std::collections::HashMap;
+
+/// Maps the versioned hashes of the blobs in the blob store to the transaction that carries them
+/// and the index of the blob in the transaction's sidecar.
+#[derive(Debug, Default)]
+pub(crate) struct VersionedHashIndex {
+    /// Transaction hash and blob index by versioned hash.
+    blobs: HashMap<B256, (TxHash, usize)>,
+    /// Versioned hashes by transaction hash.
+    transactions: HashMap<TxHash, Vec<B256>>,
+}
+
+impl VersionedHashIndex {
+    /// Indexes the blobs of the sidecar of the given transaction.
+    pub(crate) fn insert(&mut self, tx: TxHash, sidecar: &BlobTransactionSidecar) {
+        let versioned_hashes = sidecar
+            .commitments
+            .iter()
+            .filter_map(|commitment| {
+                KzgCommitment::from_bytes(commitment.as_slice()).ok().map(kzg_to_versioned_hash)
+            })
+            .collect::<Vec<_>>();
+        for (index, versioned_hash) in versioned_hashes.iter().enumerate() {
+            self.blobs.insert(*versioned_hash, (tx, index));
+        }
+        self.transactions.insert(tx, versioned_hashes);
+    }
+
+    /// Removes the blobs of the given transaction from the index.
+    pub(crate) fn remove(&mut self, tx: &TxHash) {
+        for versioned_hash in self.transactions.remove(tx).unwrap_or_default() {
+            // another transaction may carry the same blob
+            if self.blobs.get(&versioned_hash).is_some_and(|(blob_tx, _)| blob_tx == tx) {
+                self.blobs.remove(&versioned_hash);
+            }
+        }
+    }
+
+    /// Returns the transaction that carries the blob with the given versioned hash and the index
+    /// of the blob in its sidecar.
+    pub(crate) fn get(&self, versioned_hash: &B256) -> Option<(TxHash, usize)> {
+        self.blobs.get(versioned_hash).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::kzg::Bytes48;
+
+    #[test]
+    fn index_blobs() {
+        // the KZG commitment of the zero blob, i.e. the point at infinity
+        let mut commitment = [0u8; 48];
+        commitment[0] = 0xc0;
+        let sidecar = BlobTransactionSidecar {
+            commitments: vec![Bytes48::from_bytes(&commitment).unwrap()],
+            ..Default::default()
+        };
+        let versioned_hash = kzg_to_versioned_hash(KzgCommitment::from_bytes(&commitment).unwrap());
+
+        let mut index = VersionedHashIndex::default();
+        let (tx1, tx2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        index.insert(tx1, &sidecar);
+        assert_eq!(index.get(&versioned_hash), Some((tx1, 0)));
+
+        // the blob is now served from the second transaction
+        index.insert(tx2, &sidecar);
+        index.remove(&tx1);
+        assert_eq!(index.get(&versioned_hash), Some((tx2, 0)));
+
+        index.remove(&tx2);
+        assert_eq!(index.get(&versioned_hash), None);
+    }
+}
@@ -1,9 +1,13 @@
 //! Storage for blob data of EIP4844 transactions.
 
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
+pub(crate) use index::VersionedHashIndex;
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
-use reth_primitives::{BlobTransactionSidecar, B256};
+use reth_primitives::{
+    kzg::{Blob, Bytes48},
+    BlobTransactionSidecar, B256,
+};
 use std::{
     fmt,
     sync::atomic::{AtomicUsize, Ordering},
@@ -11,6 +15,7 @@ use std::{
 pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 
 pub mod disk;
+mod index;
 mod mem;
 mod noop;
 mod tracker;
@@ -71,6 +76,15 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     fn blobs_len(&self) -> usize;
 }
 
+/// A blob from the blob store with its KZG proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlobAndProof {
+    /// The blob data.
+    pub blob: Blob,
+    /// The KZG proof of the blob.
+    pub proof: Bytes48,
+}
+
 /// Error variants that can occur when interacting with a blob store.
 #[derive(Debug, thiserror::Error)]
 pub enum BlobStoreError {
@@ -0,0 +1,233 @@
+//! Pool of transaction bundles, e.g. submitted with `eth_sendBundle`, that the payload builder
+//! includes at the top of a block.
+
+use crate::metrics::BundlePoolMetrics;
+use reth_primitives::{keccak256, BlockNumber, TransactionSignedEcRecovered, TxHash, B256};
+use std::{collections::HashMap, sync::Arc};
+
+/// The maximum number of bundles in the bundle pool.
+pub const MAX_BUNDLES: usize = 1024;
+
+/// Errors returned when adding a bundle to the pool.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BundlePoolError {
+    /// The bundle does not contain any transactions.
+    #[error("bundle contains no transactions")]
+    EmptyBundle,
+    /// The block range of the bundle is empty.
+    #[error("invalid bundle block range {0}..={1}")]
+    InvalidBlockRange(BlockNumber, BlockNumber),
+    /// The bundle pool reached [MAX_BUNDLES].
+    #[error("bundle pool is full")]
+    PoolFull,
+    /// The pool does not support bundles.
+    #[error("bundles are not supported")]
+    Unsupported,
+}
+
+/// A bundle of transactions that is either included atomically, in order, or not at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionBundle {
+    /// The hash of the bundle, the keccak256 hash of the concatenated transaction hashes.
+    pub hash: B256,
+    /// The transactions of the bundle in execution order.
+    pub transactions: Vec<TransactionSignedEcRecovered>,
+    /// Hashes of the transactions that are allowed to revert without invalidating the bundle.
+    pub reverting_tx_hashes: Vec<TxHash>,
+    /// The first block the bundle can be included in.
+    pub block_number: BlockNumber,
+    /// The last block the bundle can be included in.
+    pub max_block_number: BlockNumber,
+    /// The minimum timestamp of the block the bundle is included in.
+    pub min_timestamp: Option<u64>,
+    /// The maximum timestamp of the block the bundle is included in.
+    pub max_timestamp: Option<u64>,
+    /// Identifier that can be used to replace or cancel the bundle.
+    pub replacement_uuid: Option<String>,
+}
+
+impl TransactionBundle {
+    /// Creates a bundle of the given transactions that targets a single block.
+    pub fn new(transactions: Vec<TransactionSignedEcRecovered>, block_number: BlockNumber) -> Self {
+        let hash = keccak256(transactions.iter().flat_map(|tx| tx.hash().0).collect::<Vec<_>>());
+        Self {
+            hash,
+            transactions,
+            reverting_tx_hashes: Vec::new(),
+            block_number,
+            max_block_number: block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            replacement_uuid: None,
+        }
+    }
+
+    /// Sets the last block the bundle can be included in.
+    pub fn with_max_block_number(mut self, max_block_number: BlockNumber) -> Self {
+        self.max_block_number = max_block_number;
+        self
+    }
+
+    /// Sets the range of block timestamps the bundle can be included in.
+    pub fn with_timestamps(mut self, min: Option<u64>, max: Option<u64>) -> Self {
+        self.min_timestamp = min;
+        self.max_timestamp = max;
+        self
+    }
+
+    /// Sets the hashes of the transactions that are allowed to revert.
+    pub fn with_reverting_tx_hashes(mut self, reverting_tx_hashes: Vec<TxHash>) -> Self {
+        self.reverting_tx_hashes = reverting_tx_hashes;
+        self
+    }
+
+    /// Sets the identifier that can be used to replace or cancel the bundle.
+    pub fn with_replacement_uuid(mut self, replacement_uuid: Option<String>) -> Self {
+        self.replacement_uuid = replacement_uuid;
+        self
+    }
+
+    /// Returns `true` if the transaction is allowed to revert.
+    pub fn can_revert(&self, tx: &TxHash) -> bool {
+        self.reverting_tx_hashes.contains(tx)
+    }
+
+    /// Returns the sum of the gas limits of the transactions of the bundle.
+    pub fn gas_limit(&self) -> u64 {
+        self.transactions.iter().map(|tx| tx.gas_limit()).sum()
+    }
+
+    /// Returns `true` if the bundle can be included in the block with the given number and
+    /// timestamp.
+    pub fn is_valid_for(&self, block_number: BlockNumber, timestamp: u64) -> bool {
+        (self.block_number..=self.max_block_number).contains(&block_number) &&
+            self.min_timestamp.map_or(true, |min| timestamp >= min) &&
+            self.max_timestamp.map_or(true, |max| timestamp <= max)
+    }
+}
+
+/// Bundles that are waiting to be included in a block.
+///
+/// Bundles are removed once the canonical chain advanced past their block range.
+#[derive(Debug, Default)]
+pub(crate) struct BundlePool {
+    /// All bundles by their hash.
+    bundles: HashMap<B256, Arc<TransactionBundle>>,
+    /// Hash of the bundle by replacement uuid.
+    by_uuid: HashMap<String, B256>,
+    /// Metrics for the bundle pool.
+    metrics: BundlePoolMetrics,
+}
+
+impl BundlePool {
+    /// Adds a bundle to the pool, replacing the bundle with the same replacement uuid.
+    pub(crate) fn insert(&mut self, bundle: TransactionBundle) -> Result<B256, BundlePoolError> {
+        if bundle.transactions.is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+        if bundle.block_number > bundle.max_block_number {
+            return Err(BundlePoolError::InvalidBlockRange(
+                bundle.block_number,
+                bundle.max_block_number,
+            ))
+        }
+
+        if let Some(uuid) = &bundle.replacement_uuid {
+            self.cancel(uuid);
+        }
+        if self.bundles.len() >= MAX_BUNDLES && !self.bundles.contains_key(&bundle.hash) {
+            return Err(BundlePoolError::PoolFull)
+        }
+
+        let hash = bundle.hash;
+        if let Some(uuid) = bundle.replacement_uuid.clone() {
+            self.by_uuid.insert(uuid, hash);
+        }
+        self.bundles.insert(hash, Arc::new(bundle));
+        self.metrics.inserted_bundles.increment(1);
+        self.update_metrics();
+        Ok(hash)
+    }
+
+    /// Removes the bundle with the given replacement uuid.
+    ///
+    /// Returns `true` if a bundle was removed.
+    pub(crate) fn cancel(&mut self, replacement_uuid: &str) -> bool {
+        let Some(hash) = self.by_uuid.remove(replacement_uuid) else { return false };
+        let removed = self.bundles.remove(&hash).is_some();
+        if removed {
+            self.metrics.cancelled_bundles.increment(1);
+            self.update_metrics();
+        }
+        removed
+    }
+
+    /// Returns the bundles that can be included in the block with the given number and
+    /// timestamp.
+    pub(crate) fn bundles_for_block(
+        &self,
+        block_number: BlockNumber,
+        timestamp: u64,
+    ) -> Vec<Arc<TransactionBundle>> {
+        self.bundles
+            .values()
+            .filter(|bundle| bundle.is_valid_for(block_number, timestamp))
+            .cloned()
+            .collect()
+    }
+
+    /// Removes all bundles that can only be included in blocks up to the new canonical tip.
+    pub(crate) fn on_canonical_block(&mut self, tip: BlockNumber) {
+        self.bundles.retain(|_, bundle| bundle.max_block_number > tip);
+        let bundles = &self.bundles;
+        self.by_uuid.retain(|_, hash| bundles.contains_key(hash));
+        self.update_metrics();
+    }
+
+    fn update_metrics(&self) {
+        self.metrics.bundles.set(self.bundles.len() as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, Transaction, TransactionSigned, TxLegacy};
+
+    fn bundle(nonce: u64, block_number: BlockNumber) -> TransactionBundle {
+        let tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy { nonce, ..Default::default() }),
+            Default::default(),
+        );
+        TransactionBundle::new(
+            vec![TransactionSignedEcRecovered::from_signed_transaction(tx, Address::ZERO)],
+            block_number,
+        )
+    }
+
+    #[test]
+    fn insert_cancel_and_prune() {
+        let mut pool = BundlePool::default();
+        assert_eq!(
+            pool.insert(TransactionBundle::new(vec![], 1)),
+            Err(BundlePoolError::EmptyBundle)
+        );
+
+        let first = bundle(0, 10).with_replacement_uuid(Some("uuid".to_string()));
+        pool.insert(first.clone()).unwrap();
+        let replacement = bundle(1, 10).with_replacement_uuid(Some("uuid".to_string()));
+        pool.insert(replacement.clone()).unwrap();
+        let later = bundle(2, 11).with_max_block_number(12).with_timestamps(Some(100), None);
+        pool.insert(later.clone()).unwrap();
+
+        assert_eq!(pool.bundles_for_block(10, 0), vec![Arc::new(replacement)]);
+        assert!(pool.bundles_for_block(11, 99).is_empty());
+        assert_eq!(pool.bundles_for_block(12, 100), vec![Arc::new(later)]);
+
+        pool.on_canonical_block(10);
+        assert!(!pool.cancel("uuid"));
+        assert_eq!(pool.bundles.len(), 1);
+        pool.on_canonical_block(12);
+        assert!(pool.bundles.is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+//! Conditions of transactions, e.g. submitted with `eth_sendRawTransactionConditional`, that the
+//! payload builder checks before including the transactions in a block.
+
+use reth_primitives::{Address, BlockNumber, TxHash, B256};
+use reth_provider::{ProviderError, StateProvider};
+use std::{collections::HashMap, sync::Arc};
+
+/// The maximum number of transactions with conditions tracked by the pool.
+pub const MAX_CONDITIONAL_TRANSACTIONS: usize = 4096;
+
+/// Errors returned when adding the conditions of a transaction to the pool.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConditionalPoolError {
+    /// The pool tracks [MAX_CONDITIONAL_TRANSACTIONS] transactions with conditions.
+    #[error("too many conditional transactions")]
+    PoolFull,
+    /// The pool does not support conditional transactions.
+    #[error("conditional transactions are not supported")]
+    Unsupported,
+}
+
+/// Errors of transaction conditions that don't hold.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConditionsError {
+    /// Thrown when the block number is outside of the range of the conditions
+    #[error("block number {0} is out of the range of the conditional")]
+    BlockNumberOutOfRange(BlockNumber),
+    /// Thrown when the block timestamp is outside of the range of the conditions
+    #[error("timestamp {0} is out of the range of the conditional")]
+    TimestampOutOfRange(u64),
+    /// Thrown when the storage root of a known account doesn't match
+    #[error("storage root of account {0} does not match")]
+    StorageRootMismatch(Address),
+    /// Thrown when a storage slot of a known account doesn't match
+    #[error("storage slot {slot} of account {address} does not match")]
+    StorageSlotMismatch {
+        /// The address of the account.
+        address: Address,
+        /// The storage slot.
+        slot: B256,
+    },
+    /// Thrown when the state can't be read
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+}
+
+/// The conditions under which a transaction can be included in a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TransactionConditions {
+    /// The minimum block number, inclusive.
+    pub block_number_min: Option<BlockNumber>,
+    /// The maximum block number, inclusive.
+    pub block_number_max: Option<BlockNumber>,
+    /// The minimum block timestamp, inclusive.
+    pub timestamp_min: Option<u64>,
+    /// The maximum block timestamp, inclusive.
+    pub timestamp_max: Option<u64>,
+    /// The expected storage roots of accounts.
+    pub storage_roots: HashMap<Address, B256>,
+    /// The expected storage slots of accounts.
+    pub storage_slots: HashMap<Address, HashMap<B256, B256>>,
+}
+
+impl TransactionConditions {
+    /// Checks the block number and timestamp bounds against the block the transaction is included
+    /// in.
+    pub fn check_block(
+        &self,
+        block_number: BlockNumber,
+        timestamp: u64,
+    ) -> Result<(), ConditionsError> {
+        let in_range = |value: u64, min: Option<u64>, max: Option<u64>| {
+            min.map_or(true, |min| min <= value) && max.map_or(true, |max| value <= max)
+        };
+        if !in_range(block_number, self.block_number_min, self.block_number_max) {
+            return Err(ConditionsError::BlockNumberOutOfRange(block_number))
+        }
+        if !in_range(timestamp, self.timestamp_min, self.timestamp_max) {
+            return Err(ConditionsError::TimestampOutOfRange(timestamp))
+        }
+        Ok(())
+    }
+
+    /// Checks the known accounts against the state the transaction is executed on.
+    pub fn check_state(&self, state: &dyn StateProvider) -> Result<(), ConditionsError> {
+        for (address, root) in &self.storage_roots {
+            if state.proof(*address, &[])?.storage_root != *root {
+                return Err(ConditionsError::StorageRootMismatch(*address))
+            }
+        }
+        for (address, slots) in &self.storage_slots {
+            for (slot, value) in slots {
+                let current = state.storage(*address, *slot)?.unwrap_or_default();
+                if B256::from(current) != *value {
+                    return Err(ConditionsError::StorageSlotMismatch {
+                        address: *address,
+                        slot: *slot,
+                    })
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks all conditions against the block the transaction is included in and the state it's
+    /// executed on.
+    pub fn check(
+        &self,
+        block_number: BlockNumber,
+        timestamp: u64,
+        state: &dyn StateProvider,
+    ) -> Result<(), ConditionsError> {
+        self.check_block(block_number, timestamp)?;
+        self.check_state(state)
+    }
+}
+
+/// The conditions of the transactions of the pool, by transaction hash.
+#[derive(Debug, Default)]
+pub(crate) struct ConditionalPool {
+    conditions: HashMap<TxHash, Arc<TransactionConditions>>,
+}
+
+impl ConditionalPool {
+    /// Tracks the conditions of a transaction, replacing its previous conditions.
+    pub(crate) fn insert(
+        &mut self,
+        hash: TxHash,
+        conditions: TransactionConditions,
+    ) -> Result<(), ConditionalPoolError> {
+        if self.conditions.len() >= MAX_CONDITIONAL_TRANSACTIONS &&
+            !self.conditions.contains_key(&hash)
+        {
+            return Err(ConditionalPoolError::PoolFull)
+        }
+        self.conditions.insert(hash, Arc::new(conditions));
+        Ok(())
+    }
+
+    /// Stops tracking the conditions of a transaction.
+    pub(crate) fn remove(&mut self, hash: &TxHash) {
+        self.conditions.remove(hash);
+    }
+
+    /// Returns the conditions of a transaction.
+    pub(crate) fn get(&self, hash: &TxHash) -> Option<Arc<TransactionConditions>> {
+        self.conditions.get(hash).cloned()
+    }
+
+    /// Returns the conditions of all tracked transactions.
+    pub(crate) fn all(&self) -> Vec<(TxHash, Arc<TransactionConditions>)> {
+        self.conditions.iter().map(|(hash, conditions)| (*hash, conditions.clone())).collect()
+    }
+
+    /// Stops tracking the conditions of the transactions that are no longer in the pool.
+    pub(crate) fn retain(&mut self, mut in_pool: impl FnMut(&TxHash) -> bool) {
+        self.conditions.retain(|hash, _| in_pool(hash));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::U256;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+
+    #[test]
+    fn check_conditions() {
+        let address = Address::with_last_byte(1);
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(B256::with_last_byte(1), U256::from(2))]),
+        );
+
+        let conditions = TransactionConditions {
+            block_number_min: Some(10),
+            timestamp_max: Some(1000),
+            storage_slots: [(
+                address,
+                [
+                    (B256::with_last_byte(1), B256::with_last_byte(2)),
+                    (B256::with_last_byte(3), B256::ZERO),
+                ]
+                .into(),
+            )]
+            .into(),
+            ..Default::default()
+        };
+        conditions.check(10, 1000, &provider).unwrap();
+        assert_eq!(conditions.check_block(9, 1000), Err(ConditionsError::BlockNumberOutOfRange(9)));
+        assert_eq!(
+            conditions.check_block(11, 1012),
+            Err(ConditionsError::TimestampOutOfRange(1012))
+        );
+
+        provider.add_account(address, ExtendedAccount::new(0, U256::ZERO));
+        assert_eq!(
+            conditions.check_state(&provider),
+            Err(ConditionsError::StorageSlotMismatch { address, slot: B256::with_last_byte(1) })
+        );
+    }
+
+    #[test]
+    fn insert_up_to_the_limit() {
+        let mut pool = ConditionalPool::default();
+        for i in 0..MAX_CONDITIONAL_TRANSACTIONS {
+            pool.insert(TxHash::from(U256::from(i)), Default::default()).unwrap();
+        }
+        assert_eq!(
+            pool.insert(TxHash::repeat_byte(0xff), Default::default()),
+            Err(ConditionalPoolError::PoolFull)
+        );
+        // the conditions of a tracked transaction can be replaced
+        pool.insert(
+            TxHash::ZERO,
+            TransactionConditions { block_number_max: Some(1), ..Default::default() },
+        )
+        .unwrap();
+        assert_eq!(pool.get(&TxHash::ZERO).unwrap().block_number_max, Some(1));
+
+        pool.retain(|hash| *hash != TxHash::ZERO);
+        assert!(pool.get(&TxHash::ZERO).is_none());
+        pool.insert(TxHash::ZERO, Default::default()).unwrap();
+    }
+}
@@ -152,14 +152,20 @@
 use crate::{identifier::TransactionId, pool::PoolInner};
 use aquamarine as _;
 use reth_eth_wire::HandleMempoolData;
-use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, U256};
+use reth_primitives::{
+    Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, B256, U256,
+};
 use reth_provider::StateProviderFactory;
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
 pub use crate::{
-    blobstore::{BlobStore, BlobStoreError},
+    blobstore::{BlobAndProof, BlobStore, BlobStoreError},
+    bundle::{BundlePoolError, TransactionBundle, MAX_BUNDLES},
+    conditional::{
+        ConditionalPoolError, ConditionsError, TransactionConditions, MAX_CONDITIONAL_TRANSACTIONS,
+    },
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
@@ -167,9 +173,11 @@ pub use crate::{
     },
     error::PoolResult,
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    payload_ordering::{BestTransactionsFor, PayloadOrderingPolicy, PayloadTransactionOrdering},
+    payload_policy::{AddressBlocklist, PayloadInclusionPolicy},
     pool::{
-        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        TransactionEvent, TransactionEvents,
+        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, DiscardReason,
+        FullTransactionEvent, TransactionEvent, TransactionEvents, TransactionRemoval,
     },
     traits::*,
     validate::{
@@ -182,10 +190,14 @@ pub mod error;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
+pub mod payload_ordering;
+pub mod payload_policy;
 pub mod pool;
 pub mod validate;
 
 pub mod blobstore;
+pub mod bundle;
+pub mod conditional;
 mod config;
 mod identifier;
 mod ordering;
@@ -472,6 +484,10 @@ where
         self.inner().get_all(txs)
     }
 
+    fn transaction_removal(&self, tx_hash: &TxHash) -> Option<TransactionRemoval> {
+        self.inner().transaction_removal(tx_hash)
+    }
+
     fn on_propagated(&self, txs: PropagatedTransactions) {
         self.inner().on_propagated(txs)
     }
@@ -483,6 +499,13 @@ where
         self.pool.get_transactions_by_sender(sender)
     }
 
+    fn get_pending_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.get_pending_transactions_by_sender(sender)
+    }
+
     fn get_transactions_by_sender_and_nonce(
         &self,
         sender: Address,
@@ -521,6 +544,45 @@ where
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
         self.pool.blob_store().get_exact(tx_hashes)
     }
+
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        self.pool.get_blobs_for_versioned_hashes(versioned_hashes)
+    }
+
+    fn add_bundle(&self, bundle: TransactionBundle) -> Result<B256, BundlePoolError> {
+        self.pool.bundle_pool().write().insert(bundle)
+    }
+
+    fn cancel_bundle(&self, replacement_uuid: &str) -> bool {
+        self.pool.bundle_pool().write().cancel(replacement_uuid)
+    }
+
+    fn bundles_for_block(&self, block_number: u64, timestamp: u64) -> Vec<Arc<TransactionBundle>> {
+        self.pool.bundle_pool().read().bundles_for_block(block_number, timestamp)
+    }
+
+    fn add_transaction_conditions(
+        &self,
+        hash: TxHash,
+        conditions: TransactionConditions,
+    ) -> Result<(), ConditionalPoolError> {
+        self.pool.conditional_pool().write().insert(hash, conditions)
+    }
+
+    fn remove_transaction_conditions(&self, hash: &TxHash) {
+        self.pool.conditional_pool().write().remove(hash)
+    }
+
+    fn transaction_conditions(&self, hash: &TxHash) -> Option<Arc<TransactionConditions>> {
+        self.pool.conditional_pool().read().get(hash)
+    }
+
+    fn all_transaction_conditions(&self) -> Vec<(TxHash, Arc<TransactionConditions>)> {
+        self.pool.conditional_pool().read().all()
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
@@ -5,7 +5,7 @@ use crate::{
     error::PoolError,
     metrics::MaintainPoolMetrics,
     traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
-    BlockInfo,
+    BlockInfo, FullTransactionEvent, PoolTransaction, ValidPoolTransaction,
 };
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
@@ -14,7 +14,7 @@ use futures_util::{
 use reth_primitives::{
     fs::FsPathError, Address, BlockHash, BlockNumber, BlockNumberOrTag,
     FromRecoveredPooledTransaction, IntoRecoveredTransaction, PooledTransactionsElementEcRecovered,
-    TransactionSigned, TryFromRecoveredTransaction,
+    TransactionSigned, TryFromRecoveredTransaction, TxHash,
 };
 use reth_provider::{
     BlockReaderIdExt, BundleStateWithReceipts, CanonStateNotification, ChainSpecProvider,
@@ -26,6 +26,8 @@ use std::{
     collections::HashSet,
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
 };
 use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, warn};
@@ -42,14 +44,23 @@ pub struct MaintainPoolConfig {
     ///
     /// Default: 100
     pub max_reload_accounts: usize,
+    /// Number of blocks the sidecars of mined blob transactions are kept in the blob store after
+    /// their block is finalized, so that they can still be served for historical transactions.
+    ///
+    /// Default: 0
+    pub blob_retention: u64,
 }
 
 impl Default for MaintainPoolConfig {
     fn default() -> Self {
-        Self { max_update_depth: 64, max_reload_accounts: 100 }
+        Self { max_update_depth: 64, max_reload_accounts: 100, blob_retention: 0 }
     }
 }
 
+/// Delay after a change of the local transactions of the pool before the journal is rewritten, see
+/// [backup_local_transactions_task].
+pub const LOCAL_TXS_BACKUP_DEBOUNCE: Duration = Duration::from_secs(1);
+
 /// Settings for local transaction backup task
 #[derive(Debug, Clone, Default)]
 pub struct LocalTransactionBackupConfig {
@@ -100,7 +111,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+    let MaintainPoolConfig { max_update_depth, max_reload_accounts, blob_retention } = config;
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = latest.seal_slow();
@@ -186,7 +197,8 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         if let Some(finalized) =
             last_finalized_block.update(client.finalized_block_number().ok().flatten())
         {
-            match blob_store_tracker.on_finalized_block(finalized) {
+            // blobs of finalized blocks within the retention window are kept
+            match blob_store_tracker.on_finalized_block(finalized.saturating_sub(blob_retention)) {
                 BlobStoreUpdates::None => {}
                 BlobStoreUpdates::Finalized(blobs) => {
                     metrics.inc_deleted_tracked_blobs(blobs.len());
@@ -565,7 +577,10 @@ fn changed_accounts_iter(
 
 /// Loads transactions from a file, decodes them from the RLP format, and inserts them
 /// into the transaction pool on node boot up.
-/// The file is removed after the transactions have been successfully processed.
+///
+/// The transactions are validated again before they are inserted, transactions that were mined or
+/// became invalid while the node was down are dropped. The file is kept, it is rewritten with the
+/// local transactions of the pool once they change.
 async fn load_and_reinsert_transactions<P>(
     pool: P,
     file_path: &Path,
@@ -598,17 +613,48 @@ where
     let outcome = pool.add_transactions(crate::TransactionOrigin::Local, pool_transactions).await;
 
     info!(target: "txpool", txs_file =?file_path, num_txs=%outcome.len(), "Successfully reinserted local transactions from file");
-    reth_primitives::fs::remove_file(file_path)?;
     Ok(())
 }
 
-fn save_local_txs_backup<P>(pool: P, file_path: &Path)
+/// Writes the local transactions of the pool to the file on a blocking thread, or removes the
+/// file if there are none.
+///
+/// Returns the hashes of the journaled transactions.
+async fn save_local_txs_backup<P>(pool: P, file_path: &Path) -> HashSet<TxHash>
 where
     P: TransactionPool,
 {
     let local_transactions = pool.get_local_transactions();
+    let hashes = local_transactions.iter().map(|tx| *tx.hash()).collect();
+
+    let file_path = file_path.to_path_buf();
+    let write =
+        tokio::task::spawn_blocking(move || write_local_txs_backup(local_transactions, &file_path));
+    if let Err(err) = write.await {
+        warn!(target: "txpool", %err, "Failed to write local transactions to file");
+    }
+    hashes
+}
+
+fn write_local_txs_backup<T>(
+    local_transactions: Vec<Arc<ValidPoolTransaction<T>>>,
+    file_path: &Path,
+) where
+    T: PoolTransaction,
+{
     if local_transactions.is_empty() {
         trace!(target: "txpool", "no local transactions to save");
+        // don't reinsert transactions of an outdated backup on the next boot up
+        if file_path.exists() {
+            if let Err(err) = reth_primitives::fs::remove_file(file_path) {
+                warn!(
+                    target: "txpool",
+                    %err,
+                    txs_file=?file_path,
+                    "Failed to remove local transactions file"
+                );
+            }
+        }
         return
     }
 
@@ -623,7 +669,13 @@ where
     info!(target: "txpool", txs_file =?file_path, num_txs=%num_txs, "Saving current local transactions");
     let parent_dir = file_path.parent().map(std::fs::create_dir_all).transpose();
 
-    match parent_dir.map(|_| reth_primitives::fs::write(file_path, buf)) {
+    // write to a temporary file first, so that a crash while writing doesn't corrupt the backup
+    let tmp_path = file_path.with_extension("rlp.tmp");
+    let write = parent_dir.map_err(|err| FsPathError::create_dir(err, file_path)).and_then(|_| {
+        reth_primitives::fs::write(&tmp_path, buf)?;
+        reth_primitives::fs::rename(&tmp_path, file_path)
+    });
+    match write {
         Ok(_) => {
             info!(target: "txpool", txs_file=?file_path, "Wrote local transactions to file");
         }
@@ -647,8 +699,12 @@ pub enum TransactionsBackupError {
     Pool(#[from] PoolError),
 }
 
-/// Task which manages saving local transactions to the persistent file in case of shutdown.
-/// Reloads the transactions from the file on the boot up and inserts them into the pool.
+/// Task which journals the local transactions of the pool to a persistent file.
+///
+/// Reloads the transactions from the file on the boot up and inserts them into the pool. The file
+/// is rewritten whenever local transactions are added to or removed from the pool, and on
+/// shutdown, so that pending local transactions also survive a crash. Changes in quick succession
+/// are batched into a single write, see [LOCAL_TXS_BACKUP_DEBOUNCE].
 pub async fn backup_local_transactions_task<P>(
     shutdown: reth_tasks::shutdown::GracefulShutdown,
     pool: P,
@@ -661,14 +717,48 @@ pub async fn backup_local_transactions_task<P>(
         return
     };
 
+    let mut events = pool.all_transactions_event_listener();
     if let Err(err) = load_and_reinsert_transactions(pool.clone(), &transactions_path).await {
         error!(target: "txpool", "{}", err)
     }
+    // drop the transactions of the file that were not reinserted
+    let mut journaled = save_local_txs_backup(pool.clone(), &transactions_path).await;
 
-    let graceful_guard = shutdown.await;
+    let mut pending_write = None;
+    let mut shutdown = std::pin::pin!(shutdown);
+    let graceful_guard = loop {
+        tokio::select! {
+            guard = &mut shutdown => break guard,
+            Some(event) = events.next() => {
+                let changed = match event {
+                    FullTransactionEvent::Pending(hash) | FullTransactionEvent::Queued(hash) => {
+                        !journaled.contains(&hash) &&
+                            pool.get(&hash).is_some_and(|tx| tx.is_local())
+                    }
+                    FullTransactionEvent::Mined { tx_hash: hash, .. } |
+                    FullTransactionEvent::Discarded(hash) |
+                    FullTransactionEvent::Invalid(hash) => journaled.contains(&hash),
+                    FullTransactionEvent::Replaced { transaction, .. } => {
+                        journaled.contains(transaction.hash())
+                    }
+                    FullTransactionEvent::Propagated(_) => false,
+                };
+                if changed && pending_write.is_none() {
+                    pending_write =
+                        Some(Box::pin(tokio::time::sleep(LOCAL_TXS_BACKUP_DEBOUNCE)));
+                }
+            }
+            _ = async { pending_write.as_mut().expect("write is pending").await },
+                if pending_write.is_some() =>
+            {
+                pending_write = None;
+                journaled = save_local_txs_backup(pool.clone(), &transactions_path).await;
+            }
+        }
+    };
 
     // write transactions to disk
-    save_local_txs_backup(pool, &transactions_path);
+    save_local_txs_backup(pool, &transactions_path).await;
 
     drop(graceful_guard)
 }
@@ -678,7 +768,7 @@ mod tests {
     use super::*;
     use crate::{
         blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
+        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
     };
     use reth_primitives::{fs, hex, PooledTransactionsElement, MAINNET, U256};
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
@@ -695,29 +785,43 @@ mod tests {
     const EXTENSION: &str = "rlp";
     const FILENAME: &str = "test_transactions_backup";
 
-    #[tokio::test(flavor = "multi_thread")]
-    async fn test_save_local_txs_backup() {
-        let temp_dir = tempfile::tempdir().unwrap();
-        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+    /// Returns a pool that accepts the returned signed transaction, whose sender is funded.
+    fn pool_with_signed_transaction(
+    ) -> (impl TransactionPool<Transaction = EthPooledTransaction> + Clone, EthPooledTransaction)
+    {
         let tx_bytes = hex!("02f87201830655c2808505ef61f08482565f94388c818ca8b9251b393131c08a736a67ccb192978801049e39c4b5b1f580c001a01764ace353514e8abdfb92446de356b260e3c1225b73fc4c8876a6258d12a129a04f02294aa61ca7676061cd99f29275491218b4754b46a0248e5e42bc5091f507");
         let tx = PooledTransactionsElement::decode_enveloped(&mut &tx_bytes[..]).unwrap();
         let provider = MockEthProvider::default();
         let transaction = EthPooledTransaction::from_recovered_pooled_transaction(
             tx.try_into_ecrecovered().unwrap(),
         );
-        let tx_to_cmp = transaction.clone();
         let sender = hex!("1f9090aaE28b8a3dCeaDf281B0F12828e676c326").into();
         provider.add_account(sender, ExtendedAccount::new(42, U256::MAX));
         let blob_store = InMemoryBlobStore::default();
         let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
             .build(provider, blob_store.clone());
 
-        let txpool = Pool::new(
-            validator.clone(),
-            CoinbaseTipOrdering::default(),
-            blob_store.clone(),
-            Default::default(),
-        );
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        (txpool, transaction)
+    }
+
+    /// Waits until the condition holds.
+    async fn wait_until(condition: impl Fn() -> bool, message: &str) {
+        let mut attempts = 0;
+        while !condition() {
+            attempts += 1;
+            assert!(attempts < 500, "{message}");
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_save_local_txs_backup() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let (txpool, transaction) = pool_with_signed_transaction();
+        let tx_to_cmp = transaction.clone();
 
         txpool.add_transaction(TransactionOrigin::Local, transaction.clone()).await.unwrap();
 
@@ -744,4 +848,45 @@ mod tests {
 
         temp_dir.close().unwrap();
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_journal_local_txs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let transactions_path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let (txpool, transaction) = pool_with_signed_transaction();
+
+        let handle = tokio::runtime::Handle::current();
+        let manager = TaskManager::new(handle);
+        let config = LocalTransactionBackupConfig::with_local_txs_backup(transactions_path.clone());
+        manager.executor().spawn_critical_with_graceful_shutdown_signal("test task", |shutdown| {
+            let mut task =
+                Box::pin(backup_local_transactions_task(shutdown, txpool.clone(), config));
+            // subscribe to the pool events before any transactions are added
+            assert!((&mut task).now_or_never().is_none());
+            task
+        });
+
+        txpool.add_transaction(TransactionOrigin::Local, transaction.clone()).await.unwrap();
+
+        // the journal is written after the debounce delay, before shutdown
+        wait_until(|| transactions_path.exists(), "local transactions were not journaled").await;
+        let data = fs::read(&transactions_path).unwrap();
+        let txs: Vec<TransactionSigned> =
+            alloy_rlp::Decodable::decode(&mut data.as_slice()).unwrap();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].hash(), *transaction.hash());
+
+        // the journaled transactions are reinserted as local transactions and the journal is kept
+        let (reloaded_pool, _) = pool_with_signed_transaction();
+        load_and_reinsert_transactions(reloaded_pool.clone(), &transactions_path).await.unwrap();
+        assert!(reloaded_pool.get(transaction.hash()).is_some_and(|tx| tx.is_local()));
+        assert!(transactions_path.exists());
+
+        // the journal is rewritten once the local transaction is dropped from the pool
+        txpool.remove_transactions(vec![*transaction.hash()]);
+        wait_until(|| !transactions_path.exists(), "dropped transactions were not removed").await;
+
+        manager.graceful_shutdown();
+        temp_dir.close().unwrap();
+    }
 }
@@ -57,6 +57,36 @@ pub struct BlobStoreMetrics {
     pub(crate) blobstore_entries: Gauge,
 }
 
+/// Transaction pool bundle metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool")]
+pub struct BundlePoolMetrics {
+    /// Number of bundles inserted into the bundle pool
+    pub(crate) inserted_bundles: Counter,
+    /// Number of bundles that were cancelled or replaced
+    pub(crate) cancelled_bundles: Counter,
+    /// How many bundles are currently in the bundle pool
+    pub(crate) bundles: Gauge,
+}
+
+/// Metrics for transactions affected by the payload inclusion policy
+#[derive(Metrics)]
+#[metrics(scope = "payloads.policy")]
+pub struct PayloadPolicyMetrics {
+    /// Number of transactions of included senders that were tried first
+    pub(crate) included_transactions: Counter,
+    /// Number of transactions of excluded senders that were skipped
+    pub(crate) excluded_transactions: Counter,
+    /// Number of transactions from or to a blocklisted address that were skipped
+    pub(crate) blocklisted_transactions: Counter,
+    /// Number of bundles that were skipped because they contain an excluded transaction
+    pub(crate) excluded_bundles: Counter,
+    /// Number of times the blocklist file was reloaded
+    pub(crate) blocklist_reloads: Counter,
+    /// How many addresses are currently on the blocklist
+    pub(crate) blocklist_addresses: Gauge,
+}
+
 /// Transaction pool maintenance metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
@@ -4,7 +4,9 @@
 //! to be generic over it.
 
 use crate::{
-    blobstore::BlobStoreError,
+    blobstore::{BlobAndProof, BlobStoreError},
+    bundle::{BundlePoolError, TransactionBundle},
+    conditional::{ConditionalPoolError, TransactionConditions},
     error::PoolError,
     traits::{
         BestTransactionsAttributes, GetPooledTransactionLimit, NewBlobSidecar,
@@ -14,10 +16,10 @@ use crate::{
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPooledTransaction,
     NewTransactionEvent, PoolResult, PoolSize, PoolTransaction, PooledTransactionsElement,
     PropagatedTransactions, TransactionEvents, TransactionOrigin, TransactionPool,
-    TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
+    TransactionRemoval, TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
 };
 use reth_eth_wire::HandleMempoolData;
-use reth_primitives::{Address, BlobTransactionSidecar, TxHash, U256};
+use reth_primitives::{Address, BlobTransactionSidecar, TxHash, B256, U256};
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
 use tokio::sync::{mpsc, mpsc::Receiver};
 
@@ -194,6 +196,10 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn transaction_removal(&self, _tx_hash: &TxHash) -> Option<TransactionRemoval> {
+        None
+    }
+
     fn on_propagated(&self, _txs: PropagatedTransactions) {}
 
     fn get_transactions_by_sender(
@@ -203,6 +209,13 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn get_pending_transactions_by_sender(
+        &self,
+        _sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
     fn get_transactions_by_sender_and_nonce(
         &self,
         _sender: Address,
@@ -242,6 +255,47 @@ impl TransactionPool for NoopTransactionPool {
         }
         Err(BlobStoreError::MissingSidecar(tx_hashes[0]))
     }
+
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        Ok(vec![None; versioned_hashes.len()])
+    }
+
+    fn add_bundle(&self, _bundle: TransactionBundle) -> Result<B256, BundlePoolError> {
+        Err(BundlePoolError::Unsupported)
+    }
+
+    fn cancel_bundle(&self, _replacement_uuid: &str) -> bool {
+        false
+    }
+
+    fn bundles_for_block(
+        &self,
+        _block_number: u64,
+        _timestamp: u64,
+    ) -> Vec<Arc<TransactionBundle>> {
+        vec![]
+    }
+
+    fn add_transaction_conditions(
+        &self,
+        _hash: TxHash,
+        _conditions: TransactionConditions,
+    ) -> Result<(), ConditionalPoolError> {
+        Err(ConditionalPoolError::Unsupported)
+    }
+
+    fn remove_transaction_conditions(&self, _hash: &TxHash) {}
+
+    fn transaction_conditions(&self, _hash: &TxHash) -> Option<Arc<TransactionConditions>> {
+        None
+    }
+
+    fn all_transaction_conditions(&self) -> Vec<(TxHash, Arc<TransactionConditions>)> {
+        vec![]
+    }
 }
 
 /// A [`TransactionValidator`] that does nothing.
@@ -0,0 +1,277 @@
+//! Policies for the order in which payload builders try the transactions of the pool.
+//!
+//! The pool yields its pending transactions by their effective tip. Private networks may prefer to
+//! give every sender a fair share of the block, or to include transactions in the order they
+//! arrived, which the policies of this module implement on top of the pool's iterator.
+
+use crate::{
+    identifier::SenderId, BestTransactions, BestTransactionsAttributes, PoolTransaction,
+    TransactionPool, ValidPoolTransaction,
+};
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    fmt,
+    str::FromStr,
+    sync::Arc,
+    time::Instant,
+};
+
+/// The transactions of the pool that are tried for inclusion in a payload, in order.
+pub type BestTransactionsFor<Pool> = Box<
+    dyn BestTransactions<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>,
+>;
+
+/// Selects the order in which a payload builder tries the transactions of the pool.
+///
+/// Implementations must preserve the nonce order of the transactions of each sender.
+pub trait PayloadTransactionOrdering: fmt::Debug + Send + Sync {
+    /// Returns the transactions of the pool that can be included in a payload with the given
+    /// attributes, in the order they should be tried.
+    fn best_transactions<Pool: TransactionPool>(
+        &self,
+        pool: &Pool,
+        attributes: BestTransactionsAttributes,
+    ) -> BestTransactionsFor<Pool>;
+}
+
+/// The built-in [PayloadTransactionOrdering] policies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PayloadOrderingPolicy {
+    /// Transactions with the highest effective tip first, the order of the pool.
+    #[default]
+    EffectiveTip,
+    /// One transaction of every sender per round, senders in the order of their best transaction.
+    SenderRoundRobin,
+    /// Transactions in the order they were added to the pool.
+    Fifo,
+}
+
+impl PayloadOrderingPolicy {
+    /// Returns the name of the policy.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::EffectiveTip => "effective-tip",
+            Self::SenderRoundRobin => "sender-round-robin",
+            Self::Fifo => "fifo",
+        }
+    }
+}
+
+impl PayloadTransactionOrdering for PayloadOrderingPolicy {
+    fn best_transactions<Pool: TransactionPool>(
+        &self,
+        pool: &Pool,
+        attributes: BestTransactionsAttributes,
+    ) -> BestTransactionsFor<Pool> {
+        let best = pool.best_transactions_with_attributes(attributes);
+        match self {
+            Self::EffectiveTip => best,
+            Self::SenderRoundRobin => {
+                Box::new(ReorderedTransactions::new(best, ReadySenders::round_robin()))
+            }
+            Self::Fifo => Box::new(ReorderedTransactions::new(best, ReadySenders::arrival())),
+        }
+    }
+}
+
+impl fmt::Display for PayloadOrderingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PayloadOrderingPolicy {
+    type Err = UnknownPayloadOrderingPolicy;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "effective-tip" => Ok(Self::EffectiveTip),
+            "sender-round-robin" => Ok(Self::SenderRoundRobin),
+            "fifo" => Ok(Self::Fifo),
+            _ => Err(UnknownPayloadOrderingPolicy(s.to_string())),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown [PayloadOrderingPolicy].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unknown payload ordering policy {0}")]
+pub struct UnknownPayloadOrderingPolicy(String);
+
+/// Senders whose next transaction can be yielded, ordered by a policy.
+#[derive(Debug)]
+enum ReadySenders {
+    /// Senders take turns.
+    RoundRobin(VecDeque<usize>),
+    /// The sender whose next transaction arrived first is next.
+    Arrival(BinaryHeap<Reverse<(Instant, usize)>>),
+}
+
+impl ReadySenders {
+    const fn round_robin() -> Self {
+        Self::RoundRobin(VecDeque::new())
+    }
+
+    const fn arrival() -> Self {
+        Self::Arrival(BinaryHeap::new())
+    }
+
+    /// Marks the sender as ready, with the given transaction as its next transaction.
+    fn push<T: PoolTransaction>(&mut self, sender: usize, next: &ValidPoolTransaction<T>) {
+        match self {
+            Self::RoundRobin(senders) => senders.push_back(sender),
+            Self::Arrival(senders) => senders.push(Reverse((next.timestamp, sender))),
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        match self {
+            Self::RoundRobin(senders) => senders.pop_front(),
+            Self::Arrival(senders) => senders.pop().map(|Reverse((_, sender))| sender),
+        }
+    }
+}
+
+/// The pending transactions of the pool, yielded in the order of [ReadySenders] instead of the
+/// pool's priority, while preserving the nonce order of every sender.
+///
+/// This takes a snapshot of the pool, transactions that become pending afterwards are not
+/// yielded.
+struct ReorderedTransactions<T: PoolTransaction> {
+    /// Remaining transactions of every sender, in nonce order.
+    senders: Vec<VecDeque<Arc<ValidPoolTransaction<T>>>>,
+    /// Index of every sender in `senders`.
+    indices: HashMap<SenderId, usize>,
+    /// Senders whose next transaction can be yielded.
+    ready: ReadySenders,
+    /// Whether blob transactions are skipped.
+    skip_blobs: bool,
+}
+
+impl<T: PoolTransaction> ReorderedTransactions<T> {
+    fn new(
+        transactions: impl IntoIterator<Item = Arc<ValidPoolTransaction<T>>>,
+        mut ready: ReadySenders,
+    ) -> Self {
+        let mut senders: Vec<VecDeque<_>> = Vec::new();
+        let mut indices = HashMap::new();
+        for tx in transactions {
+            let index = *indices.entry(tx.sender_id()).or_insert_with(|| {
+                senders.push(VecDeque::new());
+                senders.len() - 1
+            });
+            senders[index].push_back(tx);
+        }
+        for (index, transactions) in senders.iter().enumerate() {
+            ready.push(index, &transactions[0]);
+        }
+        Self { senders, indices, ready, skip_blobs: false }
+    }
+}
+
+impl<T: PoolTransaction> Iterator for ReorderedTransactions<T> {
+    type Item = Arc<ValidPoolTransaction<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.ready.pop()?;
+            let transactions = &mut self.senders[index];
+            // the sender's transactions were marked invalid
+            let Some(tx) = transactions.pop_front() else { continue };
+            if self.skip_blobs && tx.is_eip4844() {
+                // skip the blob transaction and its descendants
+                transactions.clear();
+                continue
+            }
+            if let Some(next) = transactions.front() {
+                self.ready.push(index, next);
+            }
+            return Some(tx)
+        }
+    }
+}
+
+impl<T: PoolTransaction> BestTransactions for ReorderedTransactions<T> {
+    fn mark_invalid(&mut self, tx: &Self::Item) {
+        if let Some(index) = self.indices.get(&tx.sender_id()) {
+            self.senders[*index].retain(|other| other.nonce() < tx.nonce());
+        }
+    }
+
+    fn no_updates(&mut self) {}
+
+    fn skip_blobs(&mut self) {
+        self.set_skip_blobs(true)
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        self.skip_blobs = skip_blobs;
+    }
+}
+
+impl<T: PoolTransaction> fmt::Debug for ReorderedTransactions<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReorderedTransactions")
+            .field("senders", &self.senders.len())
+            .field("ready", &self.ready)
+            .field("skip_blobs", &self.skip_blobs)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{MockTransaction, MockTransactionFactory};
+    use std::time::Duration;
+
+    #[test]
+    fn parse_policy() {
+        for policy in [
+            PayloadOrderingPolicy::EffectiveTip,
+            PayloadOrderingPolicy::SenderRoundRobin,
+            PayloadOrderingPolicy::Fifo,
+        ] {
+            assert_eq!(policy.to_string().parse::<PayloadOrderingPolicy>(), Ok(policy));
+        }
+        assert!("lifo".parse::<PayloadOrderingPolicy>().is_err());
+    }
+
+    #[test]
+    fn reorder_transactions() {
+        let mut f = MockTransactionFactory::default();
+        let start = Instant::now();
+        let mut validated = |tx: MockTransaction, arrival: u64| {
+            let mut tx = f.validated(tx);
+            tx.timestamp = start + Duration::from_secs(arrival);
+            Arc::new(tx)
+        };
+        // the order of the pool: both transactions of a, then both of b
+        let a0 = MockTransaction::eip1559();
+        let a1 = a0.next();
+        let b0 = MockTransaction::eip1559();
+        let b1 = b0.next();
+        let pool_order =
+            vec![validated(a0, 2), validated(a1, 0), validated(b0, 1), validated(b1, 3)];
+        let hashes = |txs: Vec<Arc<ValidPoolTransaction<MockTransaction>>>| {
+            txs.iter().map(|tx| *tx.hash()).collect::<Vec<_>>()
+        };
+        let expected =
+            |order: &[usize]| order.iter().map(|i| *pool_order[*i].hash()).collect::<Vec<_>>();
+
+        let round_robin =
+            ReorderedTransactions::new(pool_order.clone(), ReadySenders::round_robin());
+        assert_eq!(hashes(round_robin.collect()), expected(&[0, 2, 1, 3]));
+
+        // a1 arrived first, but can only be included after a0
+        let fifo = ReorderedTransactions::new(pool_order.clone(), ReadySenders::arrival());
+        assert_eq!(hashes(fifo.collect()), expected(&[2, 0, 1, 3]));
+
+        // marking a transaction invalid drops its descendants
+        let mut fifo = ReorderedTransactions::new(pool_order.clone(), ReadySenders::arrival());
+        let b0 = fifo.next().unwrap();
+        fifo.mark_invalid(&b0);
+        assert_eq!(hashes(fifo.collect()), expected(&[0, 1]));
+    }
+}
@@ -0,0 +1,410 @@
+//! Inclusion and exclusion lists that payload builders apply to the transactions of the pool.
+//!
+//! Transactions of included senders, e.g. system transactions, are tried before all other
+//! transactions. Transactions of excluded senders, and transactions from or to an address of the
+//! blocklist file, are never included. The blocklist file is reloaded when it changes, see
+//! [refresh_blocklist_task].
+
+use crate::{
+    metrics::PayloadPolicyMetrics, payload_ordering::BestTransactionsFor, BestTransactions,
+    BestTransactionsAttributes, PoolTransaction, TransactionPool, ValidPoolTransaction,
+};
+use parking_lot::RwLock;
+use reth_primitives::{Address, TxHash};
+use std::{
+    collections::{HashSet, VecDeque},
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tracing::{debug, warn};
+
+/// The interval at which the blocklist file is checked for changes by [refresh_blocklist_task].
+pub const BLOCKLIST_REFRESH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Senders and addresses that payload builders force into or keep out of their payloads.
+#[derive(Debug, Clone, Default)]
+pub struct PayloadInclusionPolicy {
+    /// Senders whose transactions are tried before all others.
+    include_senders: Arc<HashSet<Address>>,
+    /// Senders whose transactions are never included.
+    exclude_senders: Arc<HashSet<Address>>,
+    /// Addresses no transaction from or to is included.
+    blocklist: Option<Arc<AddressBlocklist>>,
+    /// Metrics for transactions affected by the policy.
+    metrics: Arc<PayloadPolicyMetrics>,
+}
+
+impl PayloadInclusionPolicy {
+    /// Creates a new policy with the given included and excluded senders.
+    pub fn new(
+        include_senders: impl IntoIterator<Item = Address>,
+        exclude_senders: impl IntoIterator<Item = Address>,
+    ) -> Self {
+        Self {
+            include_senders: Arc::new(include_senders.into_iter().collect()),
+            exclude_senders: Arc::new(exclude_senders.into_iter().collect()),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the blocklist file, one address per line, that is reloaded when it changes.
+    pub fn with_blocklist(mut self, path: impl Into<PathBuf>) -> io::Result<Self> {
+        let blocklist = AddressBlocklist::load(path)?;
+        self.metrics.blocklist_addresses.set(blocklist.len() as f64);
+        self.blocklist = Some(Arc::new(blocklist));
+        Ok(self)
+    }
+
+    /// Returns `true` if the policy does not affect any transaction.
+    pub fn is_empty(&self) -> bool {
+        self.include_senders.is_empty() &&
+            self.exclude_senders.is_empty() &&
+            self.blocklist.is_none()
+    }
+
+    /// Returns `true` if the transactions of the sender are tried before all others.
+    pub fn is_included(&self, sender: &Address) -> bool {
+        self.include_senders.contains(sender)
+    }
+
+    /// Returns `true` if a transaction of the sender to the given recipient must not be included.
+    pub fn is_excluded(&self, sender: &Address, to: Option<Address>) -> bool {
+        if self.exclude_senders.contains(sender) {
+            self.metrics.excluded_transactions.increment(1);
+            return true
+        }
+        let Some(blocklist) = &self.blocklist else { return false };
+        if blocklist.contains(sender) || to.is_some_and(|to| blocklist.contains(&to)) {
+            self.metrics.blocklisted_transactions.increment(1);
+            return true
+        }
+        false
+    }
+
+    /// Records that a bundle was skipped because it contains an excluded transaction.
+    pub fn on_excluded_bundle(&self) {
+        self.metrics.excluded_bundles.increment(1);
+    }
+
+    /// Reloads the blocklist file if it changed since it was last read.
+    ///
+    /// The current blocklist is kept if the file can not be read.
+    pub fn refresh(&self) {
+        let Some(blocklist) = &self.blocklist else { return };
+        match blocklist.reload_if_changed() {
+            Ok(true) => {
+                self.metrics.blocklist_reloads.increment(1);
+                self.metrics.blocklist_addresses.set(blocklist.len() as f64);
+            }
+            Ok(false) => {}
+            Err(err) => {
+                warn!(
+                    target: "payload_builder",
+                    %err,
+                    path=?blocklist.path(),
+                    "Failed to reload blocklist"
+                )
+            }
+        }
+    }
+
+    /// Applies the policy to the best transactions of the pool: the pending transactions of
+    /// included senders that can be included with the given attributes are yielded first and
+    /// excluded transactions are skipped, together with their descendants.
+    ///
+    /// The transactions of included senders are looked up by sender, all other transactions are
+    /// yielded by `best`.
+    pub fn apply<Pool: TransactionPool>(
+        &self,
+        pool: &Pool,
+        attributes: BestTransactionsAttributes,
+        best: BestTransactionsFor<Pool>,
+    ) -> BestTransactionsFor<Pool> {
+        if self.is_empty() {
+            return best
+        }
+        let included = self
+            .include_senders
+            .iter()
+            .flat_map(|sender| {
+                // a transaction below the fees also blocks the following transactions of the sender
+                pool.get_pending_transactions_by_sender(*sender)
+                    .into_iter()
+                    .take_while(|tx| meets_fees(tx, attributes))
+            })
+            .collect::<VecDeque<_>>();
+        self.metrics.included_transactions.increment(included.len() as u64);
+        Box::new(PolicyTransactions {
+            included,
+            yielded: HashSet::new(),
+            best,
+            policy: self.clone(),
+            skip_blobs: false,
+        })
+    }
+}
+
+/// Returns `true` if the transaction pays at least the base fee and the blob fee of the attributes.
+fn meets_fees<T: PoolTransaction>(
+    tx: &ValidPoolTransaction<T>,
+    attributes: BestTransactionsAttributes,
+) -> bool {
+    let blob_fee = attributes.blob_fee.zip(tx.max_fee_per_blob_gas());
+    tx.max_fee_per_gas() >= attributes.basefee as u128 &&
+        blob_fee.map_or(true, |(blob_fee, max_fee)| max_fee >= blob_fee as u128)
+}
+
+/// Task which reloads the blocklist file of the policy when it changes.
+///
+/// The file is checked every [BLOCKLIST_REFRESH_INTERVAL] on a blocking thread, so that payload
+/// builders never wait for the file system. Returns immediately if the policy has no blocklist.
+pub async fn refresh_blocklist_task(policy: PayloadInclusionPolicy) {
+    if policy.blocklist.is_none() {
+        return
+    }
+    let mut interval = tokio::time::interval(BLOCKLIST_REFRESH_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // the blocklist was read when the policy was created
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        let policy = policy.clone();
+        if let Err(err) = tokio::task::spawn_blocking(move || policy.refresh()).await {
+            warn!(target: "payload_builder", %err, "Failed to reload blocklist");
+        }
+    }
+}
+
+/// Addresses read from a file, one per line, that is reloaded when its modification time changes.
+///
+/// Empty lines and lines starting with `#` are ignored.
+#[derive(Debug)]
+pub struct AddressBlocklist {
+    /// The file the addresses are read from.
+    path: PathBuf,
+    /// The addresses and the modification time of the file when they were read.
+    state: RwLock<(HashSet<Address>, Option<SystemTime>)>,
+}
+
+impl AddressBlocklist {
+    /// Reads the blocklist from the given file.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let modified = fs::metadata(&path)?.modified().ok();
+        let addresses = Self::read(&path)?;
+        Ok(Self { path, state: RwLock::new((addresses, modified)) })
+    }
+
+    /// Returns the file the addresses are read from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the number of addresses.
+    pub fn len(&self) -> usize {
+        self.state.read().0.len()
+    }
+
+    /// Returns `true` if the blocklist is empty.
+    pub fn is_empty(&self) -> bool {
+        self.state.read().0.is_empty()
+    }
+
+    /// Returns `true` if the address is on the blocklist.
+    pub fn contains(&self, address: &Address) -> bool {
+        self.state.read().0.contains(address)
+    }
+
+    /// Reads the file again if its modification time changed.
+    ///
+    /// Returns `true` if the blocklist was reloaded.
+    pub fn reload_if_changed(&self) -> io::Result<bool> {
+        let modified = fs::metadata(&self.path)?.modified().ok();
+        if modified.is_some() && modified == self.state.read().1 {
+            return Ok(false)
+        }
+        let addresses = Self::read(&self.path)?;
+        debug!(
+            target: "payload_builder",
+            path=?self.path,
+            addresses=addresses.len(),
+            "Reloaded blocklist"
+        );
+        *self.state.write() = (addresses, modified);
+        Ok(true)
+    }
+
+    fn read(path: &Path) -> io::Result<HashSet<Address>> {
+        fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(idx, line)| {
+                line.parse().map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("invalid address on line {}: {err}", idx + 1),
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// The transactions of the pool with a [PayloadInclusionPolicy] applied.
+struct PolicyTransactions<T: PoolTransaction> {
+    /// The pending transactions of the included senders, tried before all other transactions.
+    included: VecDeque<Arc<ValidPoolTransaction<T>>>,
+    /// The transactions of the included senders that were yielded, they are skipped when they are
+    /// yielded again by `best`.
+    yielded: HashSet<TxHash>,
+    /// The best transactions of the pool.
+    best: Box<dyn BestTransactions<Item = Arc<ValidPoolTransaction<T>>>>,
+    /// The applied policy.
+    policy: PayloadInclusionPolicy,
+    /// Whether blob transactions are skipped.
+    skip_blobs: bool,
+}
+
+impl<T: PoolTransaction> Iterator for PolicyTransactions<T> {
+    type Item = Arc<ValidPoolTransaction<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(tx) = self.included.pop_front() {
+            let skip_blob = self.skip_blobs && tx.is_eip4844();
+            if skip_blob || self.policy.is_excluded(&tx.sender(), tx.to()) {
+                self.mark_invalid(&tx);
+                continue
+            }
+            self.yielded.insert(*tx.hash());
+            return Some(tx)
+        }
+
+        loop {
+            let tx = self.best.next()?;
+            if self.yielded.contains(tx.hash()) {
+                continue
+            }
+            if self.policy.is_excluded(&tx.sender(), tx.to()) {
+                self.mark_invalid(&tx);
+                continue
+            }
+            return Some(tx)
+        }
+    }
+}
+
+impl<T: PoolTransaction> BestTransactions for PolicyTransactions<T> {
+    fn mark_invalid(&mut self, tx: &Self::Item) {
+        self.best.mark_invalid(tx);
+        let sender = tx.sender();
+        self.included.retain(|other| other.sender() != sender || other.nonce() < tx.nonce());
+    }
+
+    fn no_updates(&mut self) {
+        self.best.no_updates()
+    }
+
+    fn skip_blobs(&mut self) {
+        self.set_skip_blobs(true)
+    }
+
+    fn set_skip_blobs(&mut self, skip_blobs: bool) {
+        self.skip_blobs = skip_blobs;
+        self.best.set_skip_blobs(skip_blobs)
+    }
+}
+
+impl<T: PoolTransaction> fmt::Debug for PolicyTransactions<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolicyTransactions")
+            .field("policy", &self.policy)
+            .field("included", &self.included.len())
+            .field("yielded", &self.yielded.len())
+            .field("skip_blobs", &self.skip_blobs)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
+    use reth_primitives::constants::MIN_PROTOCOL_BASE_FEE;
+    use std::{fs::File, time::Duration};
+
+    #[tokio::test]
+    async fn include_and_exclude_senders() {
+        let pool = testing_pool();
+        let a0 = MockTransaction::eip1559();
+        let a1 = a0.next();
+        let b0 = MockTransaction::eip1559();
+        let c0 = MockTransaction::eip1559();
+        let policy = PayloadInclusionPolicy::new([c0.get_sender()], [b0.get_sender()]);
+        for tx in [a0.clone(), a1, b0, c0.clone()] {
+            pool.add_transaction(TransactionOrigin::External, tx).await.unwrap();
+        }
+
+        let attributes = BestTransactionsAttributes::new(MIN_PROTOCOL_BASE_FEE, None);
+        let mut txs =
+            policy.apply(&pool, attributes, pool.best_transactions_with_attributes(attributes));
+        let tx = txs.next().unwrap();
+        assert_eq!(tx.hash(), c0.get_hash());
+        // dropping a0 also drops a1, b0 is excluded and c0 is not yielded again
+        let tx = txs.next().unwrap();
+        assert_eq!(tx.hash(), a0.get_hash());
+        txs.mark_invalid(&tx);
+        assert!(txs.next().is_none());
+    }
+
+    #[tokio::test]
+    async fn included_senders_below_fees() {
+        let pool = testing_pool();
+        let a0 = MockTransaction::eip1559().with_max_fee(MIN_PROTOCOL_BASE_FEE as u128 + 10);
+        let c0 = MockTransaction::eip1559();
+        let c1 = c0.next().with_max_fee(MIN_PROTOCOL_BASE_FEE as u128 + 10);
+        let policy = PayloadInclusionPolicy::new([c0.get_sender()], []);
+        for tx in [a0.clone(), c0, c1] {
+            pool.add_transaction(TransactionOrigin::External, tx).await.unwrap();
+        }
+
+        // c0 doesn't pay the base fee, which also blocks c1
+        let attributes = BestTransactionsAttributes::new(MIN_PROTOCOL_BASE_FEE + 1, None);
+        let mut txs =
+            policy.apply(&pool, attributes, pool.best_transactions_with_attributes(attributes));
+        assert_eq!(txs.next().unwrap().hash(), a0.get_hash());
+        assert!(txs.next().is_none());
+    }
+
+    #[test]
+    fn reload_blocklist() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("blocklist.txt");
+        let (first, second) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        fs::write(&path, format!("# sanctioned\n{first}\n\n")).unwrap();
+
+        let blocklist = AddressBlocklist::load(&path).unwrap();
+        assert!(blocklist.contains(&first));
+        assert!(!blocklist.reload_if_changed().unwrap());
+
+        fs::write(&path, format!("{second}\n")).unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        assert!(blocklist.reload_if_changed().unwrap());
+        assert!(!blocklist.contains(&first));
+        assert!(blocklist.contains(&second));
+
+        fs::write(&path, "not an address").unwrap();
+        assert!(AddressBlocklist::load(&path).is_err());
+    }
+}
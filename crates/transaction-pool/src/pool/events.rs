@@ -76,6 +76,31 @@ pub enum TransactionEvent {
     Propagated(Arc<Vec<PropagateKind>>),
 }
 
+/// Why a transaction was discarded.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiscardReason {
+    /// The transaction failed validation and was not added to the pool.
+    Invalid,
+    /// The pool exceeded its size limits and evicted the transaction.
+    PoolOverflow,
+    /// The transaction can no longer be included after its sender's account changed, e.g. the
+    /// nonce of the account is higher than the nonce of the transaction.
+    Outdated,
+    /// The transaction was removed from the pool on request.
+    Removed,
+}
+
+/// Why a transaction is no longer in the pool, kept in a bounded history of the pool.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TransactionRemoval {
+    /// The transaction was replaced by the transaction belonging to the hash.
+    Replaced(TxHash),
+    /// The transaction was discarded.
+    Discarded(DiscardReason),
+}
+
 impl TransactionEvent {
     /// Returns `true` if the event is final and no more events are expected for this transaction
     /// hash.
@@ -1,14 +1,14 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    pool::events::{DiscardReason, FullTransactionEvent, TransactionEvent, TransactionRemoval},
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
 use futures_util::Stream;
 use reth_primitives::{TxHash, B256};
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, VecDeque},
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
@@ -20,6 +20,9 @@ use tokio::sync::mpsc::{
 /// The size of the event channel used to propagate transaction events.
 const TX_POOL_EVENT_CHANNEL_SIZE: usize = 1024;
 
+/// The number of removed transactions kept in the [RemovalHistory].
+pub const TX_POOL_REMOVAL_HISTORY_SIZE: usize = 10_000;
+
 /// A Stream that receives [TransactionEvent] only for the transaction with the given hash.
 #[derive(Debug)]
 #[must_use = "streams do nothing unless polled"]
@@ -78,6 +81,8 @@ pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
+    /// Why the most recently removed transactions were removed.
+    removals: RemovalHistory,
 }
 
 impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
@@ -85,6 +90,7 @@ impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
+            removals: RemovalHistory::default(),
         }
     }
 }
@@ -110,6 +116,11 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         self.all_events_broadcaster.broadcast(pool_event);
     }
 
+    /// Returns why the transaction was removed from the pool, if it is in the removal history.
+    pub(crate) fn removal(&self, tx_hash: &TxHash) -> Option<TransactionRemoval> {
+        self.removals.get(tx_hash)
+    }
+
     /// Create a new subscription for the given transaction hash.
     pub(crate) fn subscribe(&mut self, tx_hash: TxHash) -> TransactionEvents {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
@@ -144,6 +155,7 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 
     /// Notify listeners about a transaction that was replaced.
     pub(crate) fn replaced(&mut self, tx: Arc<ValidPoolTransaction<T>>, replaced_by: TxHash) {
+        self.removals.insert(*tx.hash(), TransactionRemoval::Replaced(replaced_by));
         let transaction = Arc::clone(&tx);
         self.broadcast_event(
             tx.hash(),
@@ -168,7 +180,8 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.removals.insert(*tx, TransactionRemoval::Discarded(reason));
         self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
     }
 
@@ -182,6 +195,33 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 }
 
+/// A bounded history of why transactions were removed from the pool, the oldest removals are
+/// forgotten first.
+#[derive(Debug, Default)]
+struct RemovalHistory {
+    removals: HashMap<TxHash, TransactionRemoval>,
+    /// The hashes of the removed transactions, in the order they were first removed.
+    order: VecDeque<TxHash>,
+}
+
+impl RemovalHistory {
+    fn get(&self, tx_hash: &TxHash) -> Option<TransactionRemoval> {
+        self.removals.get(tx_hash).copied()
+    }
+
+    fn insert(&mut self, tx_hash: TxHash, removal: TransactionRemoval) {
+        if self.removals.insert(tx_hash, removal).is_some() {
+            return
+        }
+        self.order.push_back(tx_hash);
+        if self.order.len() > TX_POOL_REMOVAL_HISTORY_SIZE {
+            if let Some(oldest) = self.order.pop_front() {
+                self.removals.remove(&oldest);
+            }
+        }
+    }
+}
+
 /// All Sender half(s) of the event channels for all transactions.
 ///
 /// This mimics [tokio::sync::broadcast] but uses separate channels.
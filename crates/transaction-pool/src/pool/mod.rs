@@ -97,7 +97,9 @@ use tokio::sync::mpsc;
 use tracing::{debug, trace, warn};
 mod events;
 use crate::{
-    blobstore::BlobStore,
+    blobstore::{BlobAndProof, BlobStore, BlobStoreError, VersionedHashIndex},
+    bundle::BundlePool,
+    conditional::ConditionalPool,
     metrics::BlobStoreMetrics,
     pool::txpool::UpdateOutcome,
     traits::{GetPooledTransactionLimit, NewBlobSidecar, TransactionListenerKind},
@@ -105,8 +107,8 @@ use crate::{
 };
 pub use best::BestTransactionFilter;
 pub use blob::{blob_tx_priority, fee_delta};
-pub use events::{FullTransactionEvent, TransactionEvent};
-pub use listener::{AllTransactionsEvents, TransactionEvents};
+pub use events::{DiscardReason, FullTransactionEvent, TransactionEvent, TransactionRemoval};
+pub use listener::{AllTransactionsEvents, TransactionEvents, TX_POOL_REMOVAL_HISTORY_SIZE};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
 
@@ -135,6 +137,12 @@ where
     validator: V,
     /// Storage for blob transactions
     blob_store: S,
+    /// Index of the blobs in the blob store by their versioned hash.
+    blob_index: RwLock<VersionedHashIndex>,
+    /// Bundles waiting to be included in a block.
+    bundle_pool: RwLock<BundlePool>,
+    /// Conditions of the transactions of the pool that the payload builder checks.
+    conditional_pool: RwLock<ConditionalPool>,
     /// The internal pool that manages all transactions.
     pool: RwLock<TxPool<T>>,
     /// Pool settings.
@@ -171,6 +179,9 @@ where
             blob_transaction_sidecar_listener: Default::default(),
             config,
             blob_store,
+            blob_index: Default::default(),
+            bundle_pool: Default::default(),
+            conditional_pool: Default::default(),
             blob_store_metrics: Default::default(),
         }
     }
@@ -180,6 +191,16 @@ where
         &self.blob_store
     }
 
+    /// Returns the pool of bundles waiting to be included in a block.
+    pub(crate) const fn bundle_pool(&self) -> &RwLock<BundlePool> {
+        &self.bundle_pool
+    }
+
+    /// Returns the conditions of the transactions of the pool.
+    pub(crate) const fn conditional_pool(&self) -> &RwLock<ConditionalPool> {
+        &self.conditional_pool
+    }
+
     /// Returns stats about the size of the pool.
     pub(crate) fn size(&self) -> PoolSize {
         self.get_pool_data().size()
@@ -373,6 +394,7 @@ where
         let block_info = update.block_info();
         let CanonicalStateUpdate { new_tip, changed_accounts, mined_transactions, .. } = update;
         self.validator.on_new_head_block(new_tip);
+        self.bundle_pool.write().on_canonical_block(new_tip.number);
 
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
@@ -388,6 +410,10 @@ where
 
         // notify listeners about updates
         self.notify_on_new_state(outcome);
+
+        // forget the conditions of the mined and discarded transactions
+        let pool = self.pool.read();
+        self.conditional_pool.write().retain(|hash| pool.contains(hash));
     }
 
     /// Performs account updates on the pool.
@@ -400,7 +426,7 @@ where
         let mut listener = self.event_listener.write();
 
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Outdated));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
         // called during txpool maintenance when the pool drifted.
@@ -481,12 +507,12 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Invalid);
                 Err(PoolError::new(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
+                listener.discarded(&tx_hash, DiscardReason::Invalid);
                 Err(PoolError::other(tx_hash, err))
             }
         }
@@ -525,7 +551,7 @@ where
 
         {
             let mut listener = self.event_listener.write();
-            discarded.iter().for_each(|tx| listener.discarded(tx));
+            discarded.iter().for_each(|tx| listener.discarded(tx, DiscardReason::PoolOverflow));
         }
 
         // It may happen that a newly added transaction is immediately discarded, so we need to
@@ -621,7 +647,7 @@ where
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
         promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Outdated));
     }
 
     /// Fire events for the newly added transaction if there are any.
@@ -634,7 +660,9 @@ where
 
                 listener.pending(transaction.hash(), replaced.clone());
                 promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-                discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+                discarded
+                    .iter()
+                    .for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Outdated));
             }
             AddedTransaction::Parked { transaction, replaced, .. } => {
                 listener.queued(transaction.hash());
@@ -691,7 +719,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -708,6 +736,11 @@ where
         announcement.retain_by_hash(|tx| !pool.contains(tx))
     }
 
+    /// Returns why the transaction was removed from the pool, if it is in the removal history.
+    pub(crate) fn transaction_removal(&self, tx_hash: &TxHash) -> Option<TransactionRemoval> {
+        self.event_listener.read().removal(tx_hash)
+    }
+
     /// Returns the transaction by hash.
     pub(crate) fn get(
         &self,
@@ -725,6 +758,15 @@ where
         self.get_pool_data().get_transactions_by_sender(sender_id)
     }
 
+    /// Returns the pending transactions of the sender, in nonce order.
+    pub(crate) fn get_pending_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let sender_id = self.get_sender_id(sender);
+        self.get_pool_data().get_pending_transactions_by_sender(sender_id)
+    }
+
     /// Returns all transactions that where submitted with the given [TransactionOrigin]
     pub(crate) fn get_transactions_by_origin(
         &self,
@@ -787,23 +829,66 @@ where
 
     /// Inserts a blob transaction into the blob store
     fn insert_blob(&self, hash: TxHash, blob: BlobTransactionSidecar) {
+        self.blob_index.write().insert(hash, &blob);
         if let Err(err) = self.blob_store.insert(hash, blob) {
             warn!(target: "txpool", %err, "[{:?}] failed to insert blob", hash);
             self.blob_store_metrics.blobstore_failed_inserts.increment(1);
+            self.blob_index.write().remove(&hash);
         }
         self.update_blob_store_metrics();
     }
 
     /// Delete a blob from the blob store
     pub(crate) fn delete_blob(&self, blob: TxHash) {
+        self.blob_index.write().remove(&blob);
         let _ = self.blob_store.delete(blob);
     }
 
     /// Delete all blobs from the blob store
     pub(crate) fn delete_blobs(&self, txs: Vec<TxHash>) {
+        {
+            let mut blob_index = self.blob_index.write();
+            for tx in &txs {
+                blob_index.remove(tx);
+            }
+        }
         let _ = self.blob_store.delete_all(txs);
     }
 
+    /// Returns the blobs and proofs for the given versioned hashes in the requested order, with
+    /// `None` for every blob that is not in the blob store.
+    pub(crate) fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError> {
+        let locations = {
+            let blob_index = self.blob_index.read();
+            versioned_hashes.iter().map(|hash| blob_index.get(hash)).collect::<Vec<_>>()
+        };
+
+        let txs = locations.iter().flatten().map(|(tx, _)| *tx).collect::<HashSet<_>>();
+        if txs.is_empty() {
+            return Ok(vec![None; versioned_hashes.len()])
+        }
+        let sidecars = self
+            .blob_store
+            .get_all(txs.into_iter().collect())?
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        Ok(locations
+            .into_iter()
+            .map(|location| {
+                let (tx, index) = location?;
+                let sidecar = sidecars.get(&tx)?;
+                Some(BlobAndProof {
+                    blob: sidecar.blobs.get(index)?.clone(),
+                    proof: *sidecar.proofs.get(index)?,
+                })
+            })
+            .collect())
+    }
+
     /// Cleans up the blob store
     pub(crate) fn cleanup_blobs(&self) {
         let stat = self.blob_store.cleanup();
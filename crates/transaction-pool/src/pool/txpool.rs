@@ -382,6 +382,18 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.txs_iter(sender).map(|(_, tx)| Arc::clone(&tx.transaction)).collect()
     }
 
+    /// Returns the pending transactions of the sender, in nonce order.
+    pub(crate) fn get_pending_transactions_by_sender(
+        &self,
+        sender: SenderId,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.all_transactions
+            .txs_iter(sender)
+            .filter(|(_, tx)| tx.subpool.is_pending())
+            .map(|(_, tx)| Arc::clone(&tx.transaction))
+            .collect()
+    }
+
     /// Updates the transactions for the changed senders.
     pub(crate) fn update_accounts(
         &mut self,
@@ -1,9 +1,11 @@
 #![allow(deprecated)]
 
 use crate::{
-    blobstore::BlobStoreError,
+    blobstore::{BlobAndProof, BlobStoreError},
+    bundle::{BundlePoolError, TransactionBundle},
+    conditional::{ConditionalPoolError, TransactionConditions},
     error::PoolResult,
-    pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
+    pool::{state::SubPool, BestTransactionFilter, TransactionEvents, TransactionRemoval},
     validate::ValidPoolTransaction,
     AllTransactionsEvents,
 };
@@ -40,7 +42,7 @@ use tokio::sync::mpsc::Receiver;
 #[auto_impl::auto_impl(Arc)]
 pub trait TransactionPool: Send + Sync + Clone {
     /// The transaction type of the pool
-    type Transaction: PoolTransaction;
+    type Transaction: PoolTransaction + 'static;
 
     /// Returns stats about the pool and all sub-pools.
     fn pool_size(&self) -> PoolSize;
@@ -319,6 +321,12 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Caution: This in case of blob transactions, this does not include the sidecar.
     fn get_all(&self, txs: Vec<TxHash>) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns why the transaction is no longer in the pool.
+    ///
+    /// Only the last [TX_POOL_REMOVAL_HISTORY_SIZE](crate::pool::TX_POOL_REMOVAL_HISTORY_SIZE)
+    /// removed transactions are remembered, mined transactions are not included.
+    fn transaction_removal(&self, tx_hash: &TxHash) -> Option<TransactionRemoval>;
+
     /// Notify the pool about transactions that are propagated to peers.
     ///
     /// Consumer: P2P
@@ -330,6 +338,12 @@ pub trait TransactionPool: Send + Sync + Clone {
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns the pending transactions sent by a given user, in nonce order
+    fn get_pending_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
     /// Returns a transaction sent by a given user with a given nonce
     fn get_transactions_by_sender_and_nonce(
         &self,
@@ -383,6 +397,48 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
+
+    /// Returns the blobs and their KZG proofs for the given versioned hashes in the order they
+    /// were requested.
+    ///
+    /// Contains `None` for every versioned hash whose blob is not in the blob store.
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProof>>, BlobStoreError>;
+
+    /// Adds a bundle that the payload builder considers for the blocks of the bundle's block
+    /// range, replacing the bundle with the same replacement uuid.
+    ///
+    /// Returns the hash of the bundle.
+    fn add_bundle(&self, bundle: TransactionBundle) -> Result<B256, BundlePoolError>;
+
+    /// Removes the bundle with the given replacement uuid.
+    ///
+    /// Returns `true` if a bundle was removed.
+    fn cancel_bundle(&self, replacement_uuid: &str) -> bool;
+
+    /// Returns all bundles that can be included in the block with the given number and timestamp.
+    fn bundles_for_block(&self, block_number: u64, timestamp: u64) -> Vec<Arc<TransactionBundle>>;
+
+    /// Adds the conditions under which the payload builder includes the transaction with the
+    /// given hash, replacing its previous conditions.
+    ///
+    /// The conditions are removed once the transaction is no longer in the pool.
+    fn add_transaction_conditions(
+        &self,
+        hash: TxHash,
+        conditions: TransactionConditions,
+    ) -> Result<(), ConditionalPoolError>;
+
+    /// Removes the conditions of the transaction with the given hash.
+    fn remove_transaction_conditions(&self, hash: &TxHash);
+
+    /// Returns the conditions of the transaction with the given hash, if any.
+    fn transaction_conditions(&self, hash: &TxHash) -> Option<Arc<TransactionConditions>>;
+
+    /// Returns the conditions of all transactions with conditions.
+    fn all_transaction_conditions(&self) -> Vec<(TxHash, Arc<TransactionConditions>)>;
 }
 
 /// Extension for [TransactionPool] trait that allows to set the current block info.
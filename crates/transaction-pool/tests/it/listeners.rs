@@ -1,9 +1,9 @@
 use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
-    test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, TransactionEvent, TransactionListenerKind, TransactionOrigin,
-    TransactionPool,
+    test_utils::{MockTransaction, MockTransactionFactory, TestPoolBuilder},
+    DiscardReason, FullTransactionEvent, PoolTransaction, TransactionEvent,
+    TransactionListenerKind, TransactionOrigin, TransactionPool, TransactionRemoval,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -89,3 +89,25 @@ async fn txpool_listener_new_propagate_only() {
     })
     .await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_removal_history() {
+    let txpool = TestPoolBuilder::default();
+    let transaction = MockTransaction::legacy().with_gas_price(100);
+    let replacement = transaction.inc_price_by(100).rng_hash();
+
+    txpool.add_transaction(TransactionOrigin::External, transaction.clone()).await.unwrap();
+    assert_eq!(txpool.transaction_removal(transaction.hash()), None);
+
+    txpool.add_transaction(TransactionOrigin::External, replacement.clone()).await.unwrap();
+    assert_eq!(
+        txpool.transaction_removal(transaction.hash()),
+        Some(TransactionRemoval::Replaced(*replacement.hash()))
+    );
+
+    txpool.remove_transactions(vec![*replacement.hash()]);
+    assert_eq!(
+        txpool.transaction_removal(replacement.hash()),
+        Some(TransactionRemoval::Discarded(DiscardReason::Removed))
+    );
+}
@@ -1,5 +1,5 @@
 use crate::stats::ParallelTrieStats;
-use metrics::Histogram;
+use metrics::{Gauge, Histogram};
 use reth_metrics::Metrics;
 use reth_trie::metrics::{TrieRootMetrics, TrieType};
 
@@ -42,3 +42,27 @@ pub struct ParallelTrieMetrics {
     /// The number of leaves for which we did not pre-compute the storage roots.
     pub missed_leaves: Histogram,
 }
+
+/// Detailed metrics of the parallel state root calculation, only recorded if enabled with
+/// `ParallelStateRoot::with_detailed_metrics`.
+///
+/// These break the calculation down into its phases, which helps to diagnose why the state root
+/// lags behind block execution.
+#[derive(Metrics)]
+#[metrics(scope = "trie_parallel.detailed")]
+pub struct ParallelStateRootDetailedMetrics {
+    /// The number of storage roots that are currently being pre-computed.
+    pub storage_roots_in_flight: Gauge,
+    /// The number of pre-computed storage roots that were not consumed by the account trie walk
+    /// yet.
+    pub pending_storage_roots: Gauge,
+    /// The time it took to pre-compute the storage roots of the changed accounts, in seconds.
+    pub storage_roots_duration: Histogram,
+    /// The time it took to walk the account trie, in seconds.
+    pub account_trie_duration: Histogram,
+    /// The time spent computing the storage roots of missed leaves during the account trie walk,
+    /// in seconds.
+    pub missed_leaves_duration: Histogram,
+    /// The number of pre-computed storage roots that were not consumed by the account trie walk.
+    pub unused_storage_roots: Histogram,
+}
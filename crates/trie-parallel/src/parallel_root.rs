@@ -11,17 +11,17 @@ use reth_provider::{providers::ConsistentDbView, DatabaseProviderFactory, Provid
 use reth_trie::{
     hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory},
     node_iter::{AccountNode, AccountNodeIter},
-    trie_cursor::TrieCursorFactory,
+    trie_cursor::{CachedTrieCursorFactory, TrieCursorFactory, TrieNodeCache},
     updates::TrieUpdates,
     walker::TrieWalker,
     HashedPostState, StorageRoot,
 };
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 use thiserror::Error;
 use tracing::*;
 
 #[cfg(feature = "metrics")]
-use crate::metrics::ParallelStateRootMetrics;
+use crate::metrics::{ParallelStateRootDetailedMetrics, ParallelStateRootMetrics};
 
 /// Parallel incremental state root calculator.
 ///
@@ -41,6 +41,11 @@ pub struct ParallelStateRoot<DB, Provider> {
     view: ConsistentDbView<DB, Provider>,
     /// Changed hashed state.
     hashed_state: HashedPostState,
+    /// Cache of the trie nodes in the database, shared between calculations.
+    trie_node_cache: Option<TrieNodeCache>,
+    /// Whether to record the detailed metrics of the calculation.
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    detailed_metrics: bool,
     /// Parallel state root metrics.
     #[cfg(feature = "metrics")]
     metrics: ParallelStateRootMetrics,
@@ -52,10 +57,29 @@ impl<DB, Provider> ParallelStateRoot<DB, Provider> {
         Self {
             view,
             hashed_state,
+            trie_node_cache: None,
+            detailed_metrics: false,
             #[cfg(feature = "metrics")]
             metrics: ParallelStateRootMetrics::default(),
         }
     }
+
+    /// Set the cache to read the trie nodes in the database through.
+    ///
+    /// The cache must be consistent with the database of the view.
+    pub fn with_trie_node_cache(mut self, cache: TrieNodeCache) -> Self {
+        self.trie_node_cache = Some(cache);
+        self
+    }
+
+    /// Set whether to record the detailed metrics of the calculation, i.e. the number of storage
+    /// roots in flight and the time spent in each phase.
+    ///
+    /// This has no effect without the `metrics` feature.
+    pub fn with_detailed_metrics(mut self, enabled: bool) -> Self {
+        self.detailed_metrics = enabled;
+        self
+    }
 }
 
 impl<DB, Provider> ParallelStateRoot<DB, Provider>
@@ -86,16 +110,27 @@ where
             prefix_sets.storage_prefix_sets,
         );
         let hashed_state_sorted = self.hashed_state.into_sorted();
+        #[cfg(feature = "metrics")]
+        let detailed_metrics =
+            self.detailed_metrics.then(ParallelStateRootDetailedMetrics::default);
 
         // Pre-calculate storage roots in parallel for accounts which were changed.
         tracker.set_precomputed_storage_roots(storage_root_targets.len() as u64);
         debug!(target: "trie::parallel_state_root", len = storage_root_targets.len(), "pre-calculating storage roots");
+        let storage_roots_start = Instant::now();
         let mut storage_roots = storage_root_targets
             .into_par_iter()
             .map(|(hashed_address, prefix_set)| {
+                #[cfg(feature = "metrics")]
+                if let Some(metrics) = &detailed_metrics {
+                    metrics.storage_roots_in_flight.increment(1.0);
+                }
                 let provider_ro = self.view.provider_ro()?;
                 let storage_root_result = StorageRoot::new_hashed(
-                    provider_ro.tx_ref(),
+                    CachedTrieCursorFactory::new(
+                        provider_ro.tx_ref(),
+                        self.trie_node_cache.as_ref(),
+                    ),
                     HashedPostStateCursorFactory::new(provider_ro.tx_ref(), &hashed_state_sorted),
                     hashed_address,
                     #[cfg(feature = "metrics")]
@@ -103,17 +138,29 @@ where
                 )
                 .with_prefix_set(prefix_set)
                 .calculate(retain_updates);
+                #[cfg(feature = "metrics")]
+                if let Some(metrics) = &detailed_metrics {
+                    metrics.storage_roots_in_flight.decrement(1.0);
+                }
                 Ok((hashed_address, storage_root_result?))
             })
             .collect::<Result<HashMap<_, _>, ParallelStateRootError>>()?;
+        let storage_roots_duration = storage_roots_start.elapsed();
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &detailed_metrics {
+            metrics.storage_roots_duration.record(storage_roots_duration);
+            metrics.pending_storage_roots.set(storage_roots.len() as f64);
+        }
 
         trace!(target: "trie::parallel_state_root", "calculating state root");
+        let account_trie_start = Instant::now();
         let mut trie_updates = TrieUpdates::default();
 
         let provider_ro = self.view.provider_ro()?;
         let hashed_cursor_factory =
             HashedPostStateCursorFactory::new(provider_ro.tx_ref(), &hashed_state_sorted);
-        let trie_cursor_factory = provider_ro.tx_ref();
+        let trie_cursor_factory =
+            CachedTrieCursorFactory::new(provider_ro.tx_ref(), self.trie_node_cache.as_ref());
 
         let hashed_account_cursor =
             hashed_cursor_factory.hashed_account_cursor().map_err(ProviderError::Database)?;
@@ -133,19 +180,32 @@ where
                 }
                 AccountNode::Leaf(hashed_address, account) => {
                     let (storage_root, _, updates) = match storage_roots.remove(&hashed_address) {
-                        Some(result) => result,
+                        Some(result) => {
+                            #[cfg(feature = "metrics")]
+                            if let Some(metrics) = &detailed_metrics {
+                                metrics.pending_storage_roots.decrement(1.0);
+                            }
+                            result
+                        }
                         // Since we do not store all intermediate nodes in the database, there might
                         // be a possibility of re-adding a non-modified leaf to the hash builder.
                         None => {
                             tracker.inc_missed_leaves();
-                            StorageRoot::new_hashed(
-                                trie_cursor_factory,
+                            #[cfg(feature = "metrics")]
+                            let start = Instant::now();
+                            let result = StorageRoot::new_hashed(
+                                trie_cursor_factory.clone(),
                                 hashed_cursor_factory.clone(),
                                 hashed_address,
                                 #[cfg(feature = "metrics")]
                                 self.metrics.storage_trie.clone(),
                             )
-                            .calculate(retain_updates)?
+                            .calculate(retain_updates)?;
+                            #[cfg(feature = "metrics")]
+                            if let Some(metrics) = &detailed_metrics {
+                                metrics.missed_leaves_duration.record(start.elapsed());
+                            }
+                            result
                         }
                     };
 
@@ -162,6 +222,13 @@ where
         }
 
         let root = hash_builder.root();
+        let account_trie_duration = account_trie_start.elapsed();
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &detailed_metrics {
+            metrics.account_trie_duration.record(account_trie_duration);
+            metrics.unused_storage_roots.record(storage_roots.len() as f64);
+            metrics.pending_storage_roots.set(0.0);
+        }
 
         trie_updates.finalize_state_updates(
             account_node_iter.walker,
@@ -178,6 +245,8 @@ where
             target: "trie::parallel_state_root",
             %root,
             duration = ?stats.duration(),
+            ?storage_roots_duration,
+            ?account_trie_duration,
             branches_added = stats.branches_added(),
             leaves_added = stats.leaves_added(),
             missed_leaves = stats.missed_leaves(),
@@ -1,7 +1,7 @@
 use crate::{
     hashed_cursor::{HashedCursorFactory, HashedStorageCursor},
     node_iter::{AccountNode, AccountNodeIter, StorageNode, StorageNodeIter},
-    prefix_set::PrefixSetMut,
+    prefix_set::{PrefixSetMut, TriePrefixSets},
     trie_cursor::{DatabaseAccountTrieCursor, DatabaseStorageTrieCursor},
     walker::TrieWalker,
 };
@@ -20,18 +20,37 @@ use reth_primitives::{
 /// Proof generator adds the target address and slots to the prefix set, enables the proof retainer
 /// on the hash builder and follows the same algorithm as the state root calculator.
 /// See `StateRoot::root` for more info.
+///
+/// Proofs for a state that differs from the database, e.g. a historical state, can be generated
+/// by setting a hashed cursor factory that overlays the changes together with the prefix sets of
+/// the changed keys.
 #[derive(Debug)]
 pub struct Proof<'a, TX, H> {
     /// A reference to the database transaction.
     tx: &'a TX,
     /// The factory for hashed cursors.
     hashed_cursor_factory: H,
+    /// A set of prefix sets that have changes.
+    prefix_sets: TriePrefixSets,
 }
 
 impl<'a, TX> Proof<'a, TX, &'a TX> {
     /// Create a new [Proof] instance.
     pub fn new(tx: &'a TX) -> Self {
-        Self { tx, hashed_cursor_factory: tx }
+        Self { tx, hashed_cursor_factory: tx, prefix_sets: TriePrefixSets::default() }
+    }
+}
+
+impl<'a, TX, H> Proof<'a, TX, H> {
+    /// Set the hashed cursor factory.
+    pub fn with_hashed_cursor_factory<HF>(self, hashed_cursor_factory: HF) -> Proof<'a, TX, HF> {
+        Proof { tx: self.tx, hashed_cursor_factory, prefix_sets: self.prefix_sets }
+    }
+
+    /// Set the prefix sets.
+    pub fn with_prefix_sets(mut self, prefix_sets: TriePrefixSets) -> Self {
+        self.prefix_sets = prefix_sets;
+        self
     }
 }
 
@@ -55,7 +74,8 @@ where
             DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
+        let mut prefix_set =
+            PrefixSetMut::from(self.prefix_sets.account_prefix_set.iter().cloned());
         prefix_set.insert(target_nibbles.clone());
         let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
 
@@ -119,7 +139,13 @@ where
         }
 
         let target_nibbles = proofs.iter().map(|p| p.nibbles.clone()).collect::<Vec<_>>();
-        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let mut prefix_set = PrefixSetMut::from(target_nibbles.clone());
+        if let Some(changed) = self.prefix_sets.storage_prefix_sets.get(&hashed_address) {
+            for nibbles in changed.iter() {
+                prefix_set.insert(nibbles.clone());
+            }
+        }
+        let prefix_set = prefix_set.freeze();
         let trie_cursor = DatabaseStorageTrieCursor::new(
             self.tx.cursor_dup_read::<tables::StoragesTrie>()?,
             hashed_address,
@@ -164,7 +190,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::StateRoot;
+    use crate::{HashedPostState, StateRoot};
     use once_cell::sync::Lazy;
     use reth_db::database::Database;
     use reth_interfaces::RethResult;
@@ -291,6 +317,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn testspec_post_state_proofs() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        insert_genesis(&factory, TEST_SPEC.clone()).unwrap();
+
+        let changed = Address::from_str("0x2031f89b3ea8014eb51a78c316e42af3e0d7695f").unwrap();
+        let untouched = Address::from_str("0x33f0fc440b8477fcfbe9d0bf8649e7dea9baedb2").unwrap();
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        let post_state =
+            HashedPostState::default().with_accounts([(keccak256(changed), Some(account))]);
+
+        // Generate the proofs with the changes applied in memory.
+        let provider = factory.provider_rw().unwrap();
+        let post_state_proofs = [changed, untouched]
+            .map(|target| post_state.account_proof(provider.tx_ref(), target, &[]).unwrap());
+        assert_eq!(post_state_proofs[0].info, Some(account));
+
+        // Apply the changes to the database and generate the proofs again.
+        let (_, updates) = post_state.state_root_with_updates(provider.tx_ref()).unwrap();
+        updates.flush(provider.tx_ref()).unwrap();
+        provider.insert_account_for_hashing([(changed, Some(account))]).unwrap();
+
+        for expected in post_state_proofs {
+            let account_proof =
+                Proof::new(provider.tx_ref()).account_proof(expected.address, &[]).unwrap();
+            similar_asserts::assert_eq!(account_proof, expected);
+        }
+    }
+
     #[test]
     fn testspec_empty_storage_proof() {
         // Create test database and insert genesis accounts.
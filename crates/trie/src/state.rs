@@ -1,6 +1,7 @@
 use crate::{
     hashed_cursor::HashedPostStateCursorFactory,
     prefix_set::{PrefixSetMut, TriePrefixSets},
+    proof::Proof,
     updates::TrieUpdates,
     StateRoot,
 };
@@ -13,8 +14,10 @@ use reth_db::{
 };
 use reth_interfaces::trie::StateRootError;
 use reth_primitives::{
-    keccak256, revm::compat::into_reth_acc, trie::Nibbles, Account, Address, BlockNumber, B256,
-    U256,
+    keccak256,
+    revm::compat::into_reth_acc,
+    trie::{AccountProof, Nibbles},
+    Account, Address, BlockNumber, B256, U256,
 };
 use revm::db::BundleAccount;
 use std::{
@@ -249,6 +252,23 @@ impl HashedPostState {
             .with_prefix_sets(prefix_sets)
             .root_with_updates()
     }
+
+    /// Generates the proof of the account and the given storage slots in the state that results
+    /// from applying this [HashedPostState] on top of the database state.
+    /// See [Self::state_root] for more info.
+    pub fn account_proof<TX: DbTx>(
+        &self,
+        tx: &TX,
+        address: Address,
+        slots: &[B256],
+    ) -> Result<AccountProof, StateRootError> {
+        let sorted = self.clone().into_sorted();
+        let prefix_sets = self.construct_prefix_sets();
+        Proof::new(tx)
+            .with_hashed_cursor_factory(HashedPostStateCursorFactory::new(tx, &sorted))
+            .with_prefix_sets(prefix_sets)
+            .account_proof(address, slots)
+    }
 }
 
 /// Representation of in-memory hashed storage.
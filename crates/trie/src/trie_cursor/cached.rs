@@ -0,0 +1,309 @@
+use super::{TrieCursor, TrieCursorFactory};
+use crate::updates::{TrieKey, TrieUpdates};
+use parking_lot::RwLock;
+use reth_db::DatabaseError;
+use reth_primitives::{
+    trie::{BranchNodeCompact, Nibbles, StoredNibbles, StoredNibblesSubKey},
+    B256,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// Default maximum number of seek results that are kept in a [TrieNodeCache].
+pub const DEFAULT_TRIE_NODE_CACHE_ENTRIES: usize = 100_000;
+
+/// The first node at or after a sought path.
+type SeekResult = Option<(Nibbles, BranchNodeCompact)>;
+
+/// A cache of the trie nodes read from the database, shared between consecutive state root
+/// computations.
+///
+/// The results of seeking paths in the account and storage tries are cached, so that the nodes
+/// on the paths of accounts that are touched by consecutive blocks are only read from the database
+/// once. The cache must be kept in sync with the database by invalidating it with the trie updates
+/// that are committed, or clearing it if the trie tables change otherwise.
+#[derive(Clone, Debug)]
+pub struct TrieNodeCache {
+    inner: Arc<RwLock<TrieNodeCacheInner>>,
+}
+
+#[derive(Debug, Default)]
+struct TrieNodeCacheInner {
+    /// Cached seek results of the account trie.
+    account_trie: BTreeMap<Nibbles, SeekResult>,
+    /// Cached seek results of the storage tries, keyed by hashed address.
+    storage_tries: HashMap<B256, BTreeMap<Nibbles, SeekResult>>,
+    /// Total number of cached seek results.
+    entries: usize,
+    /// Maximum number of cached seek results, the cache is cleared once it is exceeded.
+    max_entries: usize,
+}
+
+impl TrieNodeCacheInner {
+    fn trie(&self, hashed_address: Option<B256>) -> Option<&BTreeMap<Nibbles, SeekResult>> {
+        match hashed_address {
+            Some(hashed_address) => self.storage_tries.get(&hashed_address),
+            None => Some(&self.account_trie),
+        }
+    }
+
+    fn clear(&mut self) {
+        self.account_trie.clear();
+        self.storage_tries.clear();
+        self.entries = 0;
+    }
+}
+
+impl Default for TrieNodeCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TRIE_NODE_CACHE_ENTRIES)
+    }
+}
+
+impl TrieNodeCache {
+    /// Creates a new cache that holds up to `max_entries` seek results.
+    pub fn new(max_entries: usize) -> Self {
+        let inner = TrieNodeCacheInner { max_entries, ..Default::default() };
+        Self { inner: Arc::new(RwLock::new(inner)) }
+    }
+
+    /// Returns the number of cached seek results.
+    pub fn len(&self) -> usize {
+        self.inner.read().entries
+    }
+
+    /// Returns `true` if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached seek results.
+    pub fn clear(&self) {
+        self.inner.write().clear();
+    }
+
+    /// Removes the cached seek results that are affected by the given trie updates.
+    ///
+    /// This must be called with the trie updates that are written to the database.
+    pub fn invalidate(&self, updates: &TrieUpdates) {
+        let mut inner = self.inner.write();
+        let mut removed = 0;
+        for key in updates.keys() {
+            match key {
+                TrieKey::AccountNode(path) => {
+                    removed += invalidate_path(&mut inner.account_trie, &path.0);
+                }
+                TrieKey::StorageNode(hashed_address, path) => {
+                    if let Some(trie) = inner.storage_tries.get_mut(hashed_address) {
+                        removed += invalidate_path(trie, &path.0);
+                    }
+                }
+                TrieKey::StorageTrie(hashed_address) => {
+                    if let Some(trie) = inner.storage_tries.remove(hashed_address) {
+                        removed += trie.len();
+                    }
+                }
+            }
+        }
+        inner.entries -= removed;
+    }
+
+    fn get(&self, hashed_address: Option<B256>, path: &Nibbles) -> Option<SeekResult> {
+        self.inner.read().trie(hashed_address)?.get(path).cloned()
+    }
+
+    fn insert(&self, hashed_address: Option<B256>, path: Nibbles, result: SeekResult) {
+        let mut inner = self.inner.write();
+        if inner.entries >= inner.max_entries {
+            inner.clear();
+        }
+        let trie = match hashed_address {
+            Some(hashed_address) => inner.storage_tries.entry(hashed_address).or_default(),
+            None => &mut inner.account_trie,
+        };
+        if trie.insert(path, result).is_none() {
+            inner.entries += 1;
+        }
+    }
+}
+
+/// Removes the cached seek results of a trie that are affected by a change of the node at `path`
+/// and returns the number of removed results.
+///
+/// Only the results of seeking `path` or a path before it can resolve to `path`. Seek results are
+/// monotonic in the sought path, so the affected results are the ones right before `path` until
+/// the first one that resolves to a node before `path`.
+fn invalidate_path(trie: &mut BTreeMap<Nibbles, SeekResult>, path: &Nibbles) -> usize {
+    let affected = trie
+        .range(..=path.clone())
+        .rev()
+        .take_while(|(_, result)| result.as_ref().map_or(true, |(key, _)| key >= path))
+        .map(|(sought, _)| sought.clone())
+        .collect::<Vec<_>>();
+    for sought in &affected {
+        trie.remove(sought);
+    }
+    affected.len()
+}
+
+/// A [TrieCursorFactory] that serves the seeks of its cursors from a [TrieNodeCache], falling back
+/// to the cursors of the wrapped factory.
+///
+/// If no cache is set, the cursors of the wrapped factory are returned as is.
+#[derive(Clone, Debug)]
+pub struct CachedTrieCursorFactory<'a, F> {
+    factory: F,
+    cache: Option<&'a TrieNodeCache>,
+}
+
+impl<'a, F> CachedTrieCursorFactory<'a, F> {
+    /// Creates a new factory that wraps the cursors of the given factory.
+    pub fn new(factory: F, cache: Option<&'a TrieNodeCache>) -> Self {
+        Self { factory, cache }
+    }
+}
+
+impl<'a, F: TrieCursorFactory> TrieCursorFactory for CachedTrieCursorFactory<'a, F> {
+    fn account_trie_cursor(&self) -> Result<Box<dyn TrieCursor + '_>, DatabaseError> {
+        let cursor = self.factory.account_trie_cursor()?;
+        Ok(match self.cache {
+            Some(cache) => Box::new(CachedTrieCursor::new(cursor, cache, None)),
+            None => cursor,
+        })
+    }
+
+    fn storage_tries_cursor(
+        &self,
+        hashed_address: B256,
+    ) -> Result<Box<dyn TrieCursor + '_>, DatabaseError> {
+        let cursor = self.factory.storage_tries_cursor(hashed_address)?;
+        Ok(match self.cache {
+            Some(cache) => Box::new(CachedTrieCursor::new(cursor, cache, Some(hashed_address))),
+            None => cursor,
+        })
+    }
+}
+
+/// A trie cursor that serves seeks from a [TrieNodeCache] and caches the seeks of the wrapped
+/// cursor.
+pub struct CachedTrieCursor<'a> {
+    /// The wrapped cursor.
+    cursor: Box<dyn TrieCursor + 'a>,
+    /// The shared cache.
+    cache: &'a TrieNodeCache,
+    /// The hashed address of the storage trie, or `None` for the account trie.
+    hashed_address: Option<B256>,
+    /// The key of the last node that was sought.
+    last_key: Option<Nibbles>,
+}
+
+impl<'a> CachedTrieCursor<'a> {
+    /// Creates a new cached cursor for the account trie or the storage trie of the given account.
+    pub fn new(
+        cursor: Box<dyn TrieCursor + 'a>,
+        cache: &'a TrieNodeCache,
+        hashed_address: Option<B256>,
+    ) -> Self {
+        Self { cursor, cache, hashed_address, last_key: None }
+    }
+}
+
+impl<'a> std::fmt::Debug for CachedTrieCursor<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedTrieCursor")
+            .field("hashed_address", &self.hashed_address)
+            .field("last_key", &self.last_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> TrieCursor for CachedTrieCursor<'a> {
+    fn seek_exact(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        Ok(self.seek(key.clone())?.filter(|(found, _)| *found == key))
+    }
+
+    fn seek(
+        &mut self,
+        key: Nibbles,
+    ) -> Result<Option<(Nibbles, BranchNodeCompact)>, DatabaseError> {
+        let result = match self.cache.get(self.hashed_address, &key) {
+            Some(result) => result,
+            None => {
+                let result = self.cursor.seek(key.clone())?;
+                self.cache.insert(self.hashed_address, key, result.clone());
+                result
+            }
+        };
+        self.last_key = result.as_ref().map(|(found, _)| found.clone());
+        Ok(result)
+    }
+
+    fn current(&mut self) -> Result<Option<TrieKey>, DatabaseError> {
+        Ok(self.last_key.clone().map(|key| match self.hashed_address {
+            Some(hashed_address) => TrieKey::StorageNode(hashed_address, StoredNibblesSubKey(key)),
+            None => TrieKey::AccountNode(StoredNibbles(key)),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::updates::TrieOp;
+    use reth_db::{cursor::DbCursorRW, tables, transaction::DbTxMut};
+    use reth_primitives::trie::StoredBranchNode;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    fn node() -> BranchNodeCompact {
+        BranchNodeCompact::new(0b0000_0010_0000_0001, 0b0000_0010_0000_0001, 0, Vec::new(), None)
+    }
+
+    #[test]
+    fn serves_seeks_from_cache() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let mut cursor = provider.tx_ref().cursor_write::<tables::AccountsTrie>().unwrap();
+        for key in [vec![0x1], vec![0x3], vec![0x5]] {
+            cursor.upsert(key.into(), StoredBranchNode(node())).unwrap();
+        }
+
+        let cache = TrieNodeCache::default();
+        let factory = CachedTrieCursorFactory::new(provider.tx_ref(), Some(&cache));
+        let mut cursor = factory.account_trie_cursor().unwrap();
+        let found = cursor.seek(Nibbles::from_nibbles([0x2])).unwrap().map(|(key, _)| key);
+        assert_eq!(found, Some(Nibbles::from_nibbles([0x3])));
+        assert_eq!(
+            cursor.current().unwrap(),
+            Some(TrieKey::AccountNode(StoredNibbles(Nibbles::from_nibbles([0x3]))))
+        );
+        assert!(cursor.seek_exact(Nibbles::from_nibbles([0x4])).unwrap().is_none());
+        assert!(cursor.seek(Nibbles::from_nibbles([0x6])).unwrap().is_none());
+        assert_eq!(cache.len(), 3);
+        drop(cursor);
+
+        // a node is written between two cached seek paths, the cache still serves the old nodes
+        provider
+            .tx_ref()
+            .put::<tables::AccountsTrie>(vec![0x4].into(), StoredBranchNode(node()))
+            .unwrap();
+        let mut cursor = factory.account_trie_cursor().unwrap();
+        let found = cursor.seek(Nibbles::from_nibbles([0x4])).unwrap().map(|(key, _)| key);
+        assert_eq!(found, Some(Nibbles::from_nibbles([0x5])));
+
+        // only the seeks that can resolve to the written node are invalidated
+        cache.invalidate(&TrieUpdates::from([(
+            TrieKey::AccountNode(StoredNibbles(Nibbles::from_nibbles([0x4]))),
+            TrieOp::Update(node()),
+        )]));
+        assert_eq!(cache.len(), 2);
+        let found = cursor.seek(Nibbles::from_nibbles([0x4])).unwrap().map(|(key, _)| key);
+        assert_eq!(found, Some(Nibbles::from_nibbles([0x4])));
+        let found = cursor.seek(Nibbles::from_nibbles([0x2])).unwrap().map(|(key, _)| key);
+        assert_eq!(found, Some(Nibbles::from_nibbles([0x3])));
+    }
+}
@@ -5,6 +5,7 @@ use reth_primitives::{
     B256,
 };
 
+mod cached;
 mod database_cursors;
 mod subnode;
 
@@ -12,6 +13,9 @@ mod subnode;
 pub mod noop;
 
 pub use self::{
+    cached::{
+        CachedTrieCursor, CachedTrieCursorFactory, TrieNodeCache, DEFAULT_TRIE_NODE_CACHE_ENTRIES,
+    },
     database_cursors::{DatabaseAccountTrieCursor, DatabaseStorageTrieCursor},
     subnode::CursorSubNode,
 };
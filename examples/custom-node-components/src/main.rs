@@ -93,7 +93,7 @@ where
                     pool,
                     chain_events,
                     ctx.task_executor().clone(),
-                    Default::default(),
+                    ctx.maintain_pool_config(),
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
@@ -288,21 +288,22 @@ where
 
         // This reuses the default EthereumPayloadBuilder to build the payload
         // but any custom logic can be implemented here
-        reth_ethereum_payload_builder::EthereumPayloadBuilder::default().try_build(BuildArguments {
-            client,
-            pool,
-            cached_reads,
-            config: PayloadConfig {
-                initialized_block_env,
-                initialized_cfg,
-                parent_block,
-                extra_data,
-                attributes: attributes.0,
-                chain_spec,
-            },
-            cancel,
-            best_payload,
-        })
+        reth_ethereum_payload_builder::EthereumPayloadBuilder::new(EthEvmConfig::default())
+            .try_build(BuildArguments {
+                client,
+                pool,
+                cached_reads,
+                config: PayloadConfig {
+                    initialized_block_env,
+                    initialized_cfg,
+                    parent_block,
+                    extra_data,
+                    attributes: attributes.0,
+                    chain_spec,
+                },
+                cancel,
+                best_payload,
+            })
     }
 
     fn build_empty_payload(